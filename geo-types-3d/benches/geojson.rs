@@ -0,0 +1,102 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use geo_types_3d::bench_helpers::{point_cloud, polygons};
+use geo_types_3d::conversion::geojson::{geometry_to_string, read_geometries};
+use geo_types_3d::{Geometry, GeometryCollection, LineStringZSoA, PointZ};
+use std::str::FromStr;
+
+fn feature_collection(geometries: &GeometryCollection<f64>) -> String {
+    let features = geometries
+        .iter()
+        .enumerate()
+        .map(|(i, geometry)| {
+            format!(
+                r#"{{"type":"Feature","properties":{{"id":{i}}},"geometry":{}}}"#,
+                geometry_to_string(geometry)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(r#"{{"type":"FeatureCollection","features":[{features}]}}"#)
+}
+
+fn read(c: &mut Criterion) {
+    let json = feature_collection(&polygons(1_000, 64));
+
+    c.bench_function("read geojson via GeoJson", |b| {
+        b.iter(|| {
+            let geojson = geojson::GeoJson::from_str(black_box(&json)).unwrap();
+            GeometryCollection::<f64>::try_from(&geojson).unwrap()
+        })
+    });
+
+    c.bench_function("read_geometries", |b| {
+        b.iter_batched_ref(
+            || json.clone().into_bytes(),
+            |bytes| read_geometries::<f64>(bytes).unwrap(),
+            criterion::BatchSize::LargeInput,
+        )
+    });
+}
+
+fn write(c: &mut Criterion) {
+    let geometry = Geometry::GeometryCollection(polygons(1_000, 64));
+
+    c.bench_function("write geojson via geojson::Value", |b| {
+        b.iter(|| {
+            let value = geojson::Value::from(black_box(&geometry));
+            serde_json::to_string(&geojson::Geometry::new(value)).unwrap()
+        })
+    });
+
+    c.bench_function("geometry_to_string", |b| {
+        b.iter(|| geometry_to_string(black_box(&geometry)))
+    });
+}
+
+/// Points written from, and read into, the array of structures layout of the
+/// geometry types and a structure of arrays.
+fn layout(c: &mut Criterion) {
+    let cloud = point_cloud(100_000, 1);
+    let soa: LineStringZSoA = cloud.iter().map(|point| point.0).collect();
+    let geometry = Geometry::MultiPointZ(cloud);
+    let json = geometry_to_string(&geometry);
+
+    c.bench_function("write points from aos", |b| {
+        b.iter(|| geometry_to_string(black_box(&geometry)))
+    });
+
+    c.bench_function("write points from soa", |b| {
+        b.iter(|| {
+            let points = black_box(&soa).coords().map(PointZ);
+            geometry_to_string(&Geometry::MultiPointZ(points.collect()))
+        })
+    });
+
+    c.bench_function("read points into aos", |b| {
+        b.iter_batched_ref(
+            || json.clone().into_bytes(),
+            |bytes| read_geometries::<f64>(bytes).unwrap(),
+            criterion::BatchSize::LargeInput,
+        )
+    });
+
+    c.bench_function("read points into soa", |b| {
+        b.iter_batched_ref(
+            || json.clone().into_bytes(),
+            |bytes| {
+                let geometries = read_geometries::<f64>(bytes).unwrap();
+                match &geometries.0[0] {
+                    Geometry::MultiPointZ(points) => points
+                        .iter()
+                        .map(|point| point.0)
+                        .collect::<LineStringZSoA>(),
+                    _ => unreachable!(),
+                }
+            },
+            criterion::BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(benches, read, write, layout);
+criterion_main!(benches);
@@ -0,0 +1,298 @@
+//! Core operations on a large point cloud, with its coordinates laid out as an
+//! array of structures (a `MultiPointZ`) and as a structure of arrays (a
+//! `LineStringZSoA`), each computed one coordinate at a time, in fixed-width lanes
+//! the compiler can vectorize, and, with the `multithreading` feature, over the
+//! rayon thread pool.
+use std::ops::Range;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use geo_types_3d::algorithm::{AffineOpsZ, AffineTransform3D, BoundingCube};
+use geo_types_3d::bench_helpers::{coords, point_cloud};
+use geo_types_3d::{CoordZ, Cube, LineStringZSoA, MultiPointZ};
+
+const SIZES: [usize; 2] = [10_000, 1_000_000];
+const LANES: usize = 8;
+#[cfg(feature = "multithreading")]
+const CHUNK: usize = 4096;
+
+fn query() -> CoordZ<f64> {
+    CoordZ::from((500., 500., 50.))
+}
+
+fn distances(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sum of distances");
+    for size in SIZES {
+        let cloud = point_cloud(size, 1);
+        let soa: LineStringZSoA = coords(size, 1).into_iter().collect();
+        let q = query();
+
+        group.bench_with_input(BenchmarkId::new("aos scalar", size), &cloud, |b, cloud| {
+            b.iter(|| {
+                black_box(cloud)
+                    .iter()
+                    .map(|p| distance(p.0, q))
+                    .sum::<f64>()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("aos lanes", size), &cloud, |b, cloud| {
+            b.iter(|| {
+                let chunks = black_box(cloud).0.chunks_exact(LANES);
+                let tail: f64 = chunks.remainder().iter().map(|p| distance(p.0, q)).sum();
+                let mut sums = [0.; LANES];
+                for chunk in chunks {
+                    for (sum, p) in sums.iter_mut().zip(chunk) {
+                        *sum += distance(p.0, q);
+                    }
+                }
+                sums.iter().sum::<f64>() + tail
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("soa scalar", size), &soa, |b, soa| {
+            b.iter(|| black_box(soa).coords().map(|c| distance(c, q)).sum::<f64>())
+        });
+        group.bench_with_input(BenchmarkId::new("soa lanes", size), &soa, |b, soa| {
+            b.iter(|| sum_of_distances(black_box(soa), 0..soa.len(), q))
+        });
+        #[cfg(feature = "multithreading")]
+        {
+            use rayon::prelude::*;
+
+            group.bench_with_input(BenchmarkId::new("aos rayon", size), &cloud, |b, cloud| {
+                b.iter(|| {
+                    black_box(cloud)
+                        .0
+                        .par_iter()
+                        .map(|p| distance(p.0, q))
+                        .sum::<f64>()
+                })
+            });
+            group.bench_with_input(BenchmarkId::new("soa rayon", size), &soa, |b, soa| {
+                b.iter(|| {
+                    let soa = black_box(soa);
+                    chunks(soa.len())
+                        .into_par_iter()
+                        .map(|range| sum_of_distances(soa, range, q))
+                        .sum::<f64>()
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bounding_cubes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bounding cube");
+    for size in SIZES {
+        let cloud = point_cloud(size, 2);
+        let soa: LineStringZSoA = coords(size, 2).into_iter().collect();
+
+        group.bench_with_input(BenchmarkId::new("aos scalar", size), &cloud, |b, cloud| {
+            b.iter(|| black_box(cloud).bounding_cube())
+        });
+        group.bench_with_input(BenchmarkId::new("aos lanes", size), &cloud, |b, cloud| {
+            b.iter(|| {
+                let mut min = [[f64::INFINITY; LANES]; 3];
+                let mut max = [[f64::NEG_INFINITY; LANES]; 3];
+                let chunks = black_box(cloud).0.chunks_exact(LANES);
+                let tail = MultiPointZ::new(chunks.remainder().to_vec()).bounding_cube();
+                for chunk in chunks {
+                    for (lane, p) in chunk.iter().enumerate() {
+                        for (axis, v) in [p.x(), p.y(), p.z()].into_iter().enumerate() {
+                            min[axis][lane] = min[axis][lane].min(v);
+                            max[axis][lane] = max[axis][lane].max(v);
+                        }
+                    }
+                }
+                union(cube_from_lanes(min, max), tail)
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("soa scalar", size), &soa, |b, soa| {
+            b.iter(|| {
+                let soa = black_box(soa);
+                let range = |column: &[f64]| {
+                    column
+                        .iter()
+                        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| {
+                            (min.min(v), max.max(v))
+                        })
+                };
+                let (x, y, z) = (range(soa.xs()), range(soa.ys()), range(soa.zs()));
+                Cube::new((x.0, y.0, z.0), (x.1, y.1, z.1))
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("soa lanes", size), &soa, |b, soa| {
+            b.iter(|| bounding_cube(black_box(soa), 0..soa.len()))
+        });
+        #[cfg(feature = "multithreading")]
+        {
+            use rayon::prelude::*;
+
+            group.bench_with_input(BenchmarkId::new("aos rayon", size), &cloud, |b, cloud| {
+                b.iter(|| {
+                    black_box(cloud)
+                        .0
+                        .par_chunks(CHUNK)
+                        .map(|chunk| MultiPointZ::new(chunk.to_vec()).bounding_cube())
+                        .reduce(|| None, union)
+                })
+            });
+            group.bench_with_input(BenchmarkId::new("soa rayon", size), &soa, |b, soa| {
+                b.iter(|| {
+                    let soa = black_box(soa);
+                    chunks(soa.len())
+                        .into_par_iter()
+                        .map(|range| bounding_cube(soa, range))
+                        .reduce(|| None, union)
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+fn transforms(c: &mut Criterion) {
+    let mut group = c.benchmark_group("affine transform");
+    let transform = AffineTransform3D::rotate_z(30.)
+        .scaled(2., 2., 1.)
+        .translated(-500., -500., 10.);
+    let matrix = transform.matrix();
+    for size in SIZES {
+        let cloud = point_cloud(size, 3);
+        let soa: LineStringZSoA = coords(size, 3).into_iter().collect();
+
+        group.bench_with_input(BenchmarkId::new("aos scalar", size), &cloud, |b, cloud| {
+            b.iter(|| black_box(cloud).affine_transform(&transform))
+        });
+        group.bench_with_input(BenchmarkId::new("soa scalar", size), &soa, |b, soa| {
+            b.iter(|| {
+                let mut soa = black_box(soa).clone();
+                soa.affine_transform(matrix);
+                soa
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("soa lanes", size), &soa, |b, soa| {
+            b.iter(|| {
+                let mut soa = black_box(soa).clone();
+                let (xs, ys, zs) = soa.columns_mut();
+                let whole = xs.len() / LANES * LANES;
+                let (xs, x_tail) = xs.split_at_mut(whole);
+                let (ys, y_tail) = ys.split_at_mut(whole);
+                let (zs, z_tail) = zs.split_at_mut(whole);
+                let chunks = xs.chunks_exact_mut(LANES).zip(ys.chunks_exact_mut(LANES));
+                for ((x, y), z) in chunks.zip(zs.chunks_exact_mut(LANES)) {
+                    let (x0, y0, z0) = (lanes(x), lanes(y), lanes(z));
+                    for (column, row) in [x, y, z].into_iter().zip(matrix) {
+                        for (lane, value) in column.iter_mut().enumerate() {
+                            *value =
+                                row[0] * x0[lane] + row[1] * y0[lane] + row[2] * z0[lane] + row[3];
+                        }
+                    }
+                }
+                transform_columns(x_tail, y_tail, z_tail, matrix);
+                soa
+            })
+        });
+        #[cfg(feature = "multithreading")]
+        {
+            use geo_types_3d::algorithm::ParMapCoordsZ;
+            use rayon::prelude::*;
+
+            group.bench_with_input(BenchmarkId::new("aos rayon", size), &cloud, |b, cloud| {
+                let transform = &transform;
+                b.iter(|| black_box(cloud).par_map_coords(|c| transform.apply(c)))
+            });
+            group.bench_with_input(BenchmarkId::new("soa rayon", size), &soa, |b, soa| {
+                b.iter(|| {
+                    let mut soa = black_box(soa).clone();
+                    let (xs, ys, zs) = soa.columns_mut();
+                    xs.par_chunks_mut(CHUNK)
+                        .zip(ys.par_chunks_mut(CHUNK))
+                        .zip(zs.par_chunks_mut(CHUNK))
+                        .for_each(|((x, y), z)| transform_columns(x, y, z, matrix));
+                    soa
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+fn distance(a: CoordZ<f64>, b: CoordZ<f64>) -> f64 {
+    let d = a - b;
+    (d.x * d.x + d.y * d.y + d.z * d.z).sqrt()
+}
+
+/// The sum of the distances from `q` to the coordinates in `range`, computed in
+/// lanes.
+fn sum_of_distances(soa: &LineStringZSoA, range: Range<usize>, q: CoordZ<f64>) -> f64 {
+    let (xs, ys, zs) = (
+        &soa.xs()[range.clone()],
+        &soa.ys()[range.clone()],
+        &soa.zs()[range],
+    );
+    let distance = |i: usize| {
+        let (dx, dy, dz) = (xs[i] - q.x, ys[i] - q.y, zs[i] - q.z);
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    };
+    let whole = xs.len() / LANES * LANES;
+    let mut sums = [0.; LANES];
+    for start in (0..whole).step_by(LANES) {
+        for (lane, sum) in sums.iter_mut().enumerate() {
+            *sum += distance(start + lane);
+        }
+    }
+    sums.iter().sum::<f64>() + (whole..xs.len()).map(distance).sum::<f64>()
+}
+
+/// The bounding cube of the coordinates in `range`, computed in lanes.
+fn bounding_cube(soa: &LineStringZSoA, range: Range<usize>) -> Option<Cube<f64>> {
+    let mut min = [[f64::INFINITY; LANES]; 3];
+    let mut max = [[f64::NEG_INFINITY; LANES]; 3];
+    for (axis, column) in [soa.xs(), soa.ys(), soa.zs()].into_iter().enumerate() {
+        for (i, &v) in column[range.clone()].iter().enumerate() {
+            let lane = i % LANES;
+            min[axis][lane] = min[axis][lane].min(v);
+            max[axis][lane] = max[axis][lane].max(v);
+        }
+    }
+    cube_from_lanes(min, max)
+}
+
+fn cube_from_lanes(min: [[f64; LANES]; 3], max: [[f64; LANES]; 3]) -> Option<Cube<f64>> {
+    let low = min.map(|lanes| lanes.into_iter().fold(f64::INFINITY, f64::min));
+    let high = max.map(|lanes| lanes.into_iter().fold(f64::NEG_INFINITY, f64::max));
+    (low[0] <= high[0]).then(|| Cube::new(low, high))
+}
+
+fn union(a: Option<Cube<f64>>, b: Option<Cube<f64>>) -> Option<Cube<f64>> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.union(&b)),
+        (cube, None) | (None, cube) => cube,
+    }
+}
+
+fn lanes(column: &[f64]) -> [f64; LANES] {
+    column.try_into().expect("a whole chunk")
+}
+
+fn transform_columns(xs: &mut [f64], ys: &mut [f64], zs: &mut [f64], matrix: [[f64; 4]; 3]) {
+    let [[a, b, c, xoff], [d, e, f, yoff], [g, h, i, zoff]] = matrix;
+    for ((x, y), z) in xs.iter_mut().zip(ys).zip(zs) {
+        let (x0, y0, z0) = (*x, *y, *z);
+        *x = a * x0 + b * y0 + c * z0 + xoff;
+        *y = d * x0 + e * y0 + f * z0 + yoff;
+        *z = g * x0 + h * y0 + i * z0 + zoff;
+    }
+}
+
+/// Splits `0..len` into ranges of `CHUNK` to spread over the thread pool.
+#[cfg(feature = "multithreading")]
+fn chunks(len: usize) -> Vec<Range<usize>> {
+    (0..len)
+        .step_by(CHUNK)
+        .map(|start| start..(start + CHUNK).min(len))
+        .collect()
+}
+
+criterion_group!(benches, distances, bounding_cubes, transforms);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+#![no_main]
+
+use geo_types_3d::dataset::Dataset;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(dataset) = Dataset::from_bytes(data) {
+        for feature in dataset.iter() {
+            let _ = feature.bounding_cube();
+            let _ = feature.to_geometry();
+        }
+        if let Some(extent) = dataset.extent() {
+            let _ = dataset.query(&extent).count();
+        }
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use geo_types_3d::conversion::geojson::read_geometries_with_limits;
+use geo_types_3d::conversion::ParseLimits;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let limits = ParseLimits {
+        max_coordinates: 10_000,
+        max_depth: 8,
+        max_rings: 100,
+    };
+    let _ = read_geometries_with_limits::<f64>(&mut data.to_vec(), &limits);
+});
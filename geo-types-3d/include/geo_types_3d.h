@@ -0,0 +1,153 @@
+#ifndef GEO_TYPES_3D_H
+#define GEO_TYPES_3D_H
+
+/* Generated by cbindgen from src/capi.rs. Do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The type of a [`Geo3dGeometry`].
+ */
+typedef enum {
+  GEO3D_GEOMETRY_TYPE_POINT,
+  GEO3D_GEOMETRY_TYPE_POINT_Z,
+  GEO3D_GEOMETRY_TYPE_LINE,
+  GEO3D_GEOMETRY_TYPE_LINE_Z,
+  GEO3D_GEOMETRY_TYPE_LINE_STRING,
+  GEO3D_GEOMETRY_TYPE_LINE_STRING_Z,
+  GEO3D_GEOMETRY_TYPE_POLYGON,
+  GEO3D_GEOMETRY_TYPE_POLYGON_Z,
+  GEO3D_GEOMETRY_TYPE_MULTI_POINT,
+  GEO3D_GEOMETRY_TYPE_MULTI_POINT_Z,
+  GEO3D_GEOMETRY_TYPE_MULTI_LINE_STRING,
+  GEO3D_GEOMETRY_TYPE_MULTI_LINE_STRING_Z,
+  GEO3D_GEOMETRY_TYPE_MULTI_POLYGON,
+  GEO3D_GEOMETRY_TYPE_MULTI_POLYGON_Z,
+  GEO3D_GEOMETRY_TYPE_GEOMETRY_COLLECTION,
+  GEO3D_GEOMETRY_TYPE_RECT,
+  /**
+   * Returned for a null geometry.
+   */
+  GEO3D_GEOMETRY_TYPE_NULL,
+} Geo3dGeometryType;
+
+/**
+ * A geometry, owned by the library.
+ */
+typedef struct Geo3dGeometry Geo3dGeometry;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * The message describing the last error on this thread, or null if there was
+ * none. The message stays valid until the next failing call on this thread.
+ */
+const char *geo3d_last_error(void);
+
+/**
+ * A new point.
+ */
+Geo3dGeometry *geo3d_point_z_new(double x, double y, double z);
+
+/**
+ * A new line string through the `count` `x, y, z` triples at `coords`, or null
+ * if `coords` is null.
+ *
+ * # Safety
+ *
+ * `coords` must point to `3 * count` doubles, or may be null if `count` is zero.
+ */
+Geo3dGeometry *geo3d_line_string_z_new(const double *coords, size_t count);
+
+/**
+ * A new polygon with `ring_count` rings, the first one exterior. Ring `i` has
+ * `ring_sizes[i]` coordinates, and the rings' `x, y, z` triples follow each
+ * other at `coords`. Open rings are closed.
+ *
+ * # Safety
+ *
+ * `ring_sizes` must point to `ring_count` sizes, and `coords` to three doubles
+ * for each coordinate of every ring. Either may be null if its count is zero.
+ */
+Geo3dGeometry *geo3d_polygon_z_new(const double *coords,
+                                   const size_t *ring_sizes,
+                                   size_t ring_count);
+
+/**
+ * A new geometry read from the `len` bytes of well-known binary at `wkb`, or
+ * null if they aren't valid well-known binary.
+ *
+ * # Safety
+ *
+ * `wkb` must point to `len` bytes.
+ */
+Geo3dGeometry *geo3d_geometry_from_wkb(const uint8_t *wkb, size_t len);
+
+/**
+ * The geometry as ISO well-known binary, little-endian, with its length stored
+ * in `len`, or null if `geometry` or `len` is null. Release it with
+ * [`geo3d_bytes_free`].
+ *
+ * # Safety
+ *
+ * `geometry` must be null or come from this library and not have been freed,
+ * and `len` must be null or point to a `size_t`.
+ */
+uint8_t *geo3d_geometry_to_wkb(const Geo3dGeometry *geometry, size_t *len);
+
+/**
+ * Releases bytes returned by [`geo3d_geometry_to_wkb`].
+ *
+ * # Safety
+ *
+ * `bytes` and `len` must be as returned by [`geo3d_geometry_to_wkb`], and
+ * `bytes` not already freed. Null is ignored.
+ */
+void geo3d_bytes_free(uint8_t *bytes, size_t len);
+
+/**
+ * Releases a geometry.
+ *
+ * # Safety
+ *
+ * `geometry` must come from this library and not already be freed. Null is
+ * ignored.
+ */
+void geo3d_geometry_free(Geo3dGeometry *geometry);
+
+/**
+ * The type of the geometry, or [`Geo3dGeometryType::Null`] if `geometry` is
+ * null.
+ *
+ * # Safety
+ *
+ * `geometry` must be null or come from this library and not have been freed.
+ */
+Geo3dGeometryType geo3d_geometry_type(const Geo3dGeometry *geometry);
+
+/**
+ * Copies the geometry's coordinates, as `x, y, z` triples, to `coords`, which
+ * has room for `capacity` coordinates, and returns how many coordinates the
+ * geometry has, or zero if `geometry` is null. Nothing is copied if they don't
+ * all fit, so a first call with a `capacity` of zero gives the size to allocate.
+ * Planar members have a z of 0.
+ *
+ * # Safety
+ *
+ * `geometry` must be null or come from this library and not have been freed,
+ * and `coords` must have room for `3 * capacity` doubles, or may be null if
+ * `capacity` is zero.
+ */
+size_t geo3d_geometry_coords(const Geo3dGeometry *geometry, double *coords, size_t capacity);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* GEO_TYPES_3D_H */
@@ -0,0 +1,258 @@
+use crate::algorithm::{MapCoordsZ, MapCoordsZInPlace};
+use crate::{CoordFloat, CoordNum, CoordZ};
+
+/// Apply an [`AffineTransform3D`] to every coordinate of a geometry.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::{AffineOpsZ, AffineTransform3D};
+/// use geo_types_3d::{line_string_z, LineStringZ};
+///
+/// let line_string: LineStringZ = line_string_z![
+///     (x: 0., y: 0., z: 0.),
+///     (x: 1., y: 0., z: 0.),
+/// ];
+/// let transform = AffineTransform3D::rotate_z(90.).translated(0., 0., 10.);
+/// let moved = line_string.affine_transform(&transform);
+/// assert!(moved.0[1].x.abs() < 1e-12);
+/// assert_eq!((moved.0[1].y, moved.0[1].z), (1., 10.));
+/// ```
+pub trait AffineOpsZ<T: CoordNum> {
+    /// Applies `transform`, returning a new geometry.
+    #[must_use]
+    fn affine_transform(&self, transform: &AffineTransform3D<T>) -> Self;
+
+    /// Applies `transform` in place.
+    fn affine_transform_mut(&mut self, transform: &AffineTransform3D<T>);
+}
+
+impl<T: CoordNum, G: MapCoordsZ<T, T, Output = G> + MapCoordsZInPlace<T>> AffineOpsZ<T> for G {
+    fn affine_transform(&self, transform: &AffineTransform3D<T>) -> Self {
+        self.map_coords(|coord| transform.apply(coord))
+    }
+
+    fn affine_transform_mut(&mut self, transform: &AffineTransform3D<T>) {
+        self.map_coords_in_place(|coord| transform.apply(coord))
+    }
+}
+
+/// A 3D affine transform `p' = M p + t`, stored as the rows `[m0, m1, m2, t]` of a
+/// 3 × 4 matrix.
+///
+/// Transforms are built up by chaining: `a.compose(&b)` applies `a`, then `b`, and
+/// the `*ed` methods append a further translation, scaling or rotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AffineTransform3D<T: CoordNum = f64>([[T; 4]; 3]);
+
+impl<T: CoordNum> Default for AffineTransform3D<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<T: CoordNum> AffineTransform3D<T> {
+    /// Creates a transform from the rows `[m0, m1, m2, t]` of its matrix.
+    pub fn new(matrix: [[T; 4]; 3]) -> Self {
+        Self(matrix)
+    }
+
+    pub fn identity() -> Self {
+        Self::scale(T::one(), T::one(), T::one())
+    }
+
+    pub fn translate(x: T, y: T, z: T) -> Self {
+        let (o, l) = (T::zero(), T::one());
+        Self([[l, o, o, x], [o, l, o, y], [o, o, l, z]])
+    }
+
+    /// Scales about the origin.
+    pub fn scale(x: T, y: T, z: T) -> Self {
+        let o = T::zero();
+        Self([[x, o, o, o], [o, y, o, o], [o, o, z, o]])
+    }
+
+    /// The rows `[m0, m1, m2, t]` of the matrix.
+    pub fn matrix(&self) -> [[T; 4]; 3] {
+        self.0
+    }
+
+    /// The 4 × 4 matrix of the transform in homogeneous coordinates, with
+    /// `[0, 0, 0, 1]` as its last row.
+    pub fn homogeneous(&self) -> [[T; 4]; 4] {
+        let [x, y, z] = self.0;
+        let (o, l) = (T::zero(), T::one());
+        [x, y, z, [o, o, o, l]]
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::identity()
+    }
+
+    /// The transform applying this one, then `other`.
+    #[must_use]
+    pub fn compose(&self, other: &Self) -> Self {
+        let (a, b) = (&other.0, &self.0);
+        Self(core::array::from_fn(|row| {
+            core::array::from_fn(|col| {
+                let product = (0..3).fold(T::zero(), |sum, k| sum + a[row][k] * b[k][col]);
+                if col == 3 {
+                    product + a[row][3]
+                } else {
+                    product
+                }
+            })
+        }))
+    }
+
+    #[must_use]
+    pub fn translated(&self, x: T, y: T, z: T) -> Self {
+        self.compose(&Self::translate(x, y, z))
+    }
+
+    #[must_use]
+    pub fn scaled(&self, x: T, y: T, z: T) -> Self {
+        self.compose(&Self::scale(x, y, z))
+    }
+
+    pub fn apply(&self, coord: CoordZ<T>) -> CoordZ<T> {
+        let row = |[a, b, c, offset]: [T; 4]| a * coord.x + b * coord.y + c * coord.z + offset;
+        CoordZ {
+            x: row(self.0[0]),
+            y: row(self.0[1]),
+            z: row(self.0[2]),
+        }
+    }
+}
+
+impl<T: CoordFloat> AffineTransform3D<T> {
+    /// Rotates `degrees` anticlockwise about the x axis, seen looking back along
+    /// it towards the origin.
+    pub fn rotate_x(degrees: T) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let (o, l) = (T::zero(), T::one());
+        Self([[l, o, o, o], [o, cos, -sin, o], [o, sin, cos, o]])
+    }
+
+    /// Rotates `degrees` anticlockwise about the y axis.
+    pub fn rotate_y(degrees: T) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let (o, l) = (T::zero(), T::one());
+        Self([[cos, o, sin, o], [o, l, o, o], [-sin, o, cos, o]])
+    }
+
+    /// Rotates `degrees` anticlockwise about the z axis, as seen from above.
+    pub fn rotate_z(degrees: T) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let (o, l) = (T::zero(), T::one());
+        Self([[cos, -sin, o, o], [sin, cos, o, o], [o, o, l, o]])
+    }
+
+    #[must_use]
+    pub fn rotated_x(&self, degrees: T) -> Self {
+        self.compose(&Self::rotate_x(degrees))
+    }
+
+    #[must_use]
+    pub fn rotated_y(&self, degrees: T) -> Self {
+        self.compose(&Self::rotate_y(degrees))
+    }
+
+    #[must_use]
+    pub fn rotated_z(&self, degrees: T) -> Self {
+        self.compose(&Self::rotate_z(degrees))
+    }
+
+    /// The transform undoing this one, or `None` if it collapses space onto a
+    /// plane, line or point.
+    pub fn inverse(&self) -> Option<Self> {
+        let m = &self.0;
+        let cofactor = |r0: usize, r1: usize, c0: usize, c1: usize| {
+            m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+        };
+        // the adjugate of the linear part, which divided by the determinant inverts it
+        let adjugate = [
+            [
+                cofactor(1, 2, 1, 2),
+                -cofactor(0, 2, 1, 2),
+                cofactor(0, 1, 1, 2),
+            ],
+            [
+                -cofactor(1, 2, 0, 2),
+                cofactor(0, 2, 0, 2),
+                -cofactor(0, 1, 0, 2),
+            ],
+            [
+                cofactor(1, 2, 0, 1),
+                -cofactor(0, 2, 0, 1),
+                cofactor(0, 1, 0, 1),
+            ],
+        ];
+        let determinant = (0..3).fold(T::zero(), |sum, k| sum + m[0][k] * adjugate[k][0]);
+        if determinant == T::zero() || !determinant.is_finite() {
+            return None;
+        }
+        let linear: [[T; 3]; 3] = core::array::from_fn(|row| {
+            core::array::from_fn(|col| adjugate[row][col] / determinant)
+        });
+        Some(Self(core::array::from_fn(|row| {
+            let [a, b, c] = linear[row];
+            let offset = -(a * m[0][3] + b * m[1][3] + c * m[2][3]);
+            [a, b, c, offset]
+        })))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{LineStringZ, PointZ};
+    use alloc::vec;
+
+    #[test]
+    fn transforms() {
+        let transform = AffineTransform3D::rotate_x(30.)
+            .rotated_y(-45.)
+            .scaled(2., 1., 0.5)
+            .translated(1., 2., 3.);
+        let coord = CoordZ {
+            x: 0.5,
+            y: -2.,
+            z: 7.,
+        };
+        // composing matches applying each step in turn
+        let mut stepwise = AffineTransform3D::rotate_x(30.).apply(coord);
+        stepwise = AffineTransform3D::rotate_y(-45.).apply(stepwise);
+        stepwise = AffineTransform3D::scale(2., 1., 0.5).apply(stepwise);
+        stepwise = AffineTransform3D::translate(1., 2., 3.).apply(stepwise);
+        assert_relative_eq!(transform.apply(coord), stepwise, epsilon = 1e-12);
+
+        let inverse = transform.inverse().unwrap();
+        assert_relative_eq!(
+            inverse.apply(transform.apply(coord)),
+            coord,
+            epsilon = 1e-12
+        );
+        let round_trip = transform.compose(&inverse);
+        for (row, expected) in round_trip
+            .matrix()
+            .iter()
+            .zip(AffineTransform3D::identity().matrix())
+        {
+            for (value, expected) in row.iter().zip(expected) {
+                assert_relative_eq!(*value, expected, epsilon = 1e-12);
+            }
+        }
+        assert_eq!(AffineTransform3D::scale(1., 0., 1.).inverse(), None);
+
+        // a quarter turn about z takes x to y
+        let turned = PointZ::new(1., 0., 5.).affine_transform(&AffineTransform3D::rotate_z(90.));
+        assert_relative_eq!(turned, PointZ::new(0., 1., 5.), epsilon = 1e-12);
+
+        let mut line_string: LineStringZ<i32> = vec![(1, 2, 3), (4, 5, 6)].into();
+        line_string.affine_transform_mut(&AffineTransform3D::translate(1, 1, -3));
+        assert_eq!(line_string, vec![(2, 3, 0), (5, 6, 3)].into());
+        assert!(AffineTransform3D::<i32>::default().is_identity());
+    }
+}
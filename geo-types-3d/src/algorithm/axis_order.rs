@@ -0,0 +1,101 @@
+use crate::algorithm::MapCoordsZ;
+use crate::{CoordNum, CoordZ};
+
+/// The order of the horizontal axes of geographic coordinates.
+///
+/// This crate, [`geo`] and GeoJSON put longitude first, as x, and latitude
+/// second, as y. Some coordinate reference systems, notably EPSG:4326 as
+/// defined, and the formats and services following them, put latitude first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AxisOrder {
+    /// Longitude as x and latitude as y, as expected throughout this crate.
+    #[default]
+    LonLat,
+    /// Latitude as x and longitude as y.
+    LatLon,
+}
+
+/// Swap the x and y of every coordinate of a geometry, such as to bring
+/// latitude/longitude data into the longitude/latitude order this crate expects.
+///
+/// Planar members of a [`Geometry`](crate::Geometry) are swapped as well, and z
+/// is left alone.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::{AxisOrder, SwapXY};
+/// use geo_types_3d::PointZ;
+///
+/// // Amsterdam, as latitude, longitude and height from an EPSG:4326 source
+/// let point = PointZ::new(52.37, 4.9, 2.);
+/// let point = point.ensure_lon_lat(AxisOrder::LatLon);
+/// assert_eq!(point, PointZ::new(4.9, 52.37, 2.));
+/// // already in order
+/// assert_eq!(point.ensure_lon_lat(AxisOrder::LonLat), point);
+/// ```
+pub trait SwapXY<T: CoordNum> {
+    /// Swaps x and y, returning a new geometry.
+    #[must_use]
+    fn swap_xy(&self) -> Self;
+
+    /// Returns the geometry in longitude/latitude order, given the `order` its
+    /// coordinates are in, swapping x and y if that is [`AxisOrder::LatLon`].
+    #[must_use]
+    fn ensure_lon_lat(&self, order: AxisOrder) -> Self;
+}
+
+impl<T: CoordNum, G: MapCoordsZ<T, T, Output = G>> SwapXY<T> for G {
+    fn swap_xy(&self) -> Self {
+        self.map_coords(|coord| CoordZ {
+            x: coord.y,
+            y: coord.x,
+            z: coord.z,
+        })
+    }
+
+    fn ensure_lon_lat(&self, order: AxisOrder) -> Self {
+        match order {
+            AxisOrder::LonLat => self.map_coords(|coord| coord),
+            AxisOrder::LatLon => self.swap_xy(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Geometry, GeometryCollection, PolygonZ};
+    use alloc::vec;
+
+    #[test]
+    fn collections() {
+        let area: PolygonZ = polygon_z![
+            (x: 52., y: 4., z: 0.),
+            (x: 53., y: 4., z: 1.),
+            (x: 53., y: 5., z: 2.),
+        ];
+        let geometry = Geometry::GeometryCollection(GeometryCollection(vec![
+            area.into(),
+            Geometry::Point(geo_types::Point::new(52.5, 4.5)),
+        ]));
+        let swapped = geometry.ensure_lon_lat(AxisOrder::LatLon);
+        let Geometry::GeometryCollection(members) = &swapped else {
+            unreachable!()
+        };
+        assert_eq!(
+            members.0[0],
+            Geometry::PolygonZ(polygon_z![
+                (x: 4., y: 52., z: 0.),
+                (x: 4., y: 53., z: 1.),
+                (x: 5., y: 53., z: 2.),
+            ])
+        );
+        assert_eq!(
+            members.0[1],
+            Geometry::Point(geo_types::Point::new(4.5, 52.5))
+        );
+        assert_eq!(swapped.swap_xy(), geometry);
+    }
+}
@@ -0,0 +1,397 @@
+use alloc::vec::Vec;
+
+use geo::algorithm::bool_ops::BoolOpsNum;
+use geo::BooleanOps;
+use geo_types::{Coord, LineString, MultiPolygon, Polygon};
+use num_traits::Float;
+
+pub use geo::algorithm::bool_ops::OpType;
+
+use crate::algorithm::linalg::{dot, newell_normal, Axes};
+use crate::{CoordFloat, CoordZ, Error, LineStringZ, MultiPolygonZ, Plane, PolygonZ};
+
+/// Boolean operations on polygons that lie in a common plane.
+///
+/// The plane is fitted to the exterior rings of both operands, which may have any
+/// orientation in space. Every vertex has to lie within a tolerance of that plane,
+/// otherwise the operation fails with [`Error::NotCoplanar`]. The polygons are then
+/// projected onto the coordinate plane the fitted plane is most parallel to, clipped
+/// there with [`geo::BooleanOps`], and the result is lifted back onto the fitted
+/// plane. For horizontal and sloped planes this keeps x and y and interpolates z, so
+/// vertices created by the operation get the elevation of the plane at their
+/// location.
+///
+/// Degenerate polygons with zero area are ignored, as in [`geo::BooleanOps`]. The
+/// methods end in `_z` so that they don't clash with those of [`geo::BooleanOps`]
+/// when both traits are in scope.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::BooleanOpsZ;
+/// use geo_types_3d::{polygon_z, PolygonZ};
+///
+/// // two overlapping squares on a roof that rises by 1 per unit of x
+/// let a: PolygonZ = polygon_z![
+///     (x: 0.0, y: 0.0, z: 0.0),
+///     (x: 2.0, y: 0.0, z: 2.0),
+///     (x: 2.0, y: 2.0, z: 2.0),
+///     (x: 0.0, y: 2.0, z: 0.0),
+/// ];
+/// let b: PolygonZ = polygon_z![
+///     (x: 1.0, y: 1.0, z: 1.0),
+///     (x: 3.0, y: 1.0, z: 3.0),
+///     (x: 3.0, y: 3.0, z: 3.0),
+///     (x: 1.0, y: 3.0, z: 1.0),
+/// ];
+/// let overlap = a.intersection_z(&b).unwrap();
+///
+/// assert_eq!(overlap.0.len(), 1);
+/// for coord in overlap.0[0].exterior().coords() {
+///     assert!((coord.z - coord.x).abs() < 1e-9);
+/// }
+/// ```
+pub trait BooleanOpsZ {
+    type Scalar: BoolOpsNum + 'static;
+
+    /// The polygons making up the geometry.
+    fn polygons(&self) -> impl Iterator<Item = &PolygonZ<Self::Scalar>>;
+
+    /// Performs a boolean operation, accepting vertices up to a small distance
+    /// (relative to the size of the operands) from the common plane.
+    fn boolean_op_z(
+        &self,
+        other: &impl BooleanOpsZ<Scalar = Self::Scalar>,
+        op: OpType,
+    ) -> Result<MultiPolygonZ<Self::Scalar>, Error> {
+        let polygons: Vec<_> = self.polygons().chain(other.polygons()).collect();
+        let tolerance = default_tolerance(&polygons);
+        boolean_op(&polygons, self.polygons().count(), op, tolerance)
+    }
+
+    /// Performs a boolean operation, accepting vertices up to `tolerance` from the
+    /// common plane.
+    fn boolean_op_z_with_tolerance(
+        &self,
+        other: &impl BooleanOpsZ<Scalar = Self::Scalar>,
+        op: OpType,
+        tolerance: Self::Scalar,
+    ) -> Result<MultiPolygonZ<Self::Scalar>, Error> {
+        let polygons: Vec<_> = self.polygons().chain(other.polygons()).collect();
+        boolean_op(&polygons, self.polygons().count(), op, tolerance)
+    }
+
+    fn intersection_z(
+        &self,
+        other: &impl BooleanOpsZ<Scalar = Self::Scalar>,
+    ) -> Result<MultiPolygonZ<Self::Scalar>, Error> {
+        self.boolean_op_z(other, OpType::Intersection)
+    }
+
+    fn union_z(
+        &self,
+        other: &impl BooleanOpsZ<Scalar = Self::Scalar>,
+    ) -> Result<MultiPolygonZ<Self::Scalar>, Error> {
+        self.boolean_op_z(other, OpType::Union)
+    }
+
+    fn difference_z(
+        &self,
+        other: &impl BooleanOpsZ<Scalar = Self::Scalar>,
+    ) -> Result<MultiPolygonZ<Self::Scalar>, Error> {
+        self.boolean_op_z(other, OpType::Difference)
+    }
+
+    fn xor_z(
+        &self,
+        other: &impl BooleanOpsZ<Scalar = Self::Scalar>,
+    ) -> Result<MultiPolygonZ<Self::Scalar>, Error> {
+        self.boolean_op_z(other, OpType::Xor)
+    }
+}
+
+impl<T: BoolOpsNum + 'static> BooleanOpsZ for PolygonZ<T> {
+    type Scalar = T;
+
+    fn polygons(&self) -> impl Iterator<Item = &PolygonZ<T>> {
+        core::iter::once(self)
+    }
+}
+
+impl<T: BoolOpsNum + 'static> BooleanOpsZ for MultiPolygonZ<T> {
+    type Scalar = T;
+
+    fn polygons(&self) -> impl Iterator<Item = &PolygonZ<T>> {
+        self.0.iter()
+    }
+}
+
+/// The common plane of the operands, and the coordinate plane it is most parallel
+/// to, which they are clipped in.
+struct Projection<T: CoordFloat> {
+    plane: Plane<T>,
+    axes: Axes,
+}
+
+impl<T: BoolOpsNum> Projection<T> {
+    /// Fits a plane to the exterior rings, or returns `None` if they all have zero
+    /// area.
+    fn fit(polygons: &[&PolygonZ<T>]) -> Option<Self> {
+        let mut normal = CoordZ::zero();
+        let mut origin = CoordZ::zero();
+        let mut count = T::zero();
+        for polygon in polygons {
+            let ring = newell_normal(&polygon.exterior().0);
+            // orient each ring's normal like the accumulated one so that operands
+            // with opposite winding don't cancel out
+            if dot(ring, normal) < T::zero() {
+                normal -= ring;
+            } else {
+                normal += ring;
+            }
+            for coord in polygon.exterior().coords() {
+                origin += *coord;
+                count = count + T::one();
+            }
+        }
+        let plane = Plane::from_point_normal(origin / count, normal)?;
+        Some(Self {
+            axes: Axes::facing(plane.normal),
+            plane,
+        })
+    }
+
+    fn project(&self, coord: CoordZ<T>) -> Coord<T> {
+        let projected = self.axes.project(coord);
+        Coord {
+            x: projected.x,
+            y: projected.y,
+        }
+    }
+
+    /// The point of the plane that projects onto `coord`.
+    fn lift(&self, coord: Coord<T>) -> CoordZ<T> {
+        let normal = self.axes.project(self.plane.normal);
+        let dropped = (self.plane.offset - normal.x * coord.x - normal.y * coord.y) / normal.z;
+        self.axes.unproject(CoordZ {
+            x: coord.x,
+            y: coord.y,
+            z: dropped,
+        })
+    }
+
+    fn project_polygon(&self, polygon: &PolygonZ<T>) -> Polygon<T> {
+        let ring = |ring: &LineStringZ<T>| {
+            LineString(ring.coords().map(|coord| self.project(*coord)).collect())
+        };
+        Polygon::new(
+            ring(polygon.exterior()),
+            polygon.interiors().iter().map(ring).collect(),
+        )
+    }
+
+    fn lift_polygon(&self, polygon: &Polygon<T>) -> PolygonZ<T> {
+        let ring = |ring: &LineString<T>| {
+            LineStringZ(ring.0.iter().map(|coord| self.lift(*coord)).collect())
+        };
+        PolygonZ::new(
+            ring(polygon.exterior()),
+            polygon.interiors().iter().map(ring).collect(),
+        )
+    }
+}
+
+fn boolean_op<T: BoolOpsNum>(
+    polygons: &[&PolygonZ<T>],
+    split: usize,
+    op: OpType,
+    tolerance: T,
+) -> Result<MultiPolygonZ<T>, Error> {
+    let Some(projection) = Projection::fit(polygons) else {
+        return Ok(MultiPolygonZ(Vec::new()));
+    };
+    let coplanar = polygons.iter().all(|polygon| {
+        core::iter::once(polygon.exterior())
+            .chain(polygon.interiors())
+            .flat_map(|ring| ring.coords())
+            .all(|coord| Float::abs(projection.plane.signed_distance(*coord)) <= tolerance)
+    });
+    if !coplanar {
+        return Err(Error::NotCoplanar);
+    }
+
+    let project = |polygons: &[&PolygonZ<T>]| {
+        MultiPolygon(
+            polygons
+                .iter()
+                .map(|polygon| projection.project_polygon(polygon))
+                .collect(),
+        )
+    };
+    let (left, right) = polygons.split_at(split);
+    let result = project(left).boolean_op(&project(right), op);
+    Ok(MultiPolygonZ(
+        result
+            .0
+            .iter()
+            .map(|polygon| projection.lift_polygon(polygon))
+            .collect(),
+    ))
+}
+
+/// A distance tolerance proportional to the size of the operands.
+fn default_tolerance<T: BoolOpsNum>(polygons: &[&PolygonZ<T>]) -> T {
+    let mut min = [T::infinity(); 3];
+    let mut max = [T::neg_infinity(); 3];
+    for coord in polygons
+        .iter()
+        .flat_map(|polygon| polygon.exterior().coords())
+    {
+        let coord = to_array(*coord);
+        for i in 0..3 {
+            min[i] = Float::min(min[i], coord[i]);
+            max[i] = Float::max(max[i], coord[i]);
+        }
+    }
+    let size = (0..3)
+        .map(|i| max[i] - min[i])
+        .fold(T::zero(), |size, extent| Float::max(size, extent));
+    Float::max(size, T::one()) * Float::sqrt(<T as Float>::epsilon())
+}
+
+fn to_array<T: BoolOpsNum>(coord: CoordZ<T>) -> [T; 3] {
+    [coord.x, coord.y, coord.z]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::Area;
+
+    fn area<T: BoolOpsNum>(polygons: &MultiPolygonZ<T>, projection: &Projection<T>) -> T {
+        polygons
+            .0
+            .iter()
+            .map(|polygon| projection.project_polygon(polygon).unsigned_area())
+            .fold(T::zero(), |a, b| a + b)
+    }
+
+    #[test]
+    fn horizontal_squares() {
+        let a = polygon_z![
+            (x: 0., y: 0., z: 5.),
+            (x: 2., y: 0., z: 5.),
+            (x: 2., y: 2., z: 5.),
+            (x: 0., y: 2., z: 5.),
+        ];
+        let b = polygon_z![
+            (x: 1., y: 1., z: 5.),
+            (x: 3., y: 1., z: 5.),
+            (x: 3., y: 3., z: 5.),
+            (x: 1., y: 3., z: 5.),
+        ];
+        let projection = Projection::fit(&[&a]).unwrap();
+        assert_eq!(
+            projection.plane.normal,
+            CoordZ {
+                x: 0.,
+                y: 0.,
+                z: 1.
+            }
+        );
+
+        let intersection = a.intersection_z(&b).unwrap();
+        assert_relative_eq!(area(&intersection, &projection), 1.);
+        let union = a.union_z(&b).unwrap();
+        assert_relative_eq!(area(&union, &projection), 7.);
+        let difference = a.difference_z(&b).unwrap();
+        assert_relative_eq!(area(&difference, &projection), 3.);
+        let xor = a.xor_z(&b).unwrap();
+        assert_relative_eq!(area(&xor, &projection), 6.);
+
+        for result in [intersection, union, difference, xor] {
+            assert!(result
+                .0
+                .iter()
+                .flat_map(|polygon| polygon.exterior().coords())
+                .all(|coord| coord.z == 5.));
+        }
+    }
+
+    #[test]
+    fn vertical_wall() {
+        // two windows in the x = 0 plane, wound in opposite directions
+        let a = polygon_z![
+            (x: 0., y: 0., z: 0.),
+            (x: 0., y: 2., z: 0.),
+            (x: 0., y: 2., z: 2.),
+            (x: 0., y: 0., z: 2.),
+        ];
+        let b = polygon_z![
+            (x: 0., y: 1., z: 1.),
+            (x: 0., y: 1., z: 3.),
+            (x: 0., y: 3., z: 3.),
+            (x: 0., y: 3., z: 1.),
+        ];
+        let union = a.union_z(&b).unwrap();
+        assert_eq!(union.0.len(), 1);
+        let exterior = union.0[0].exterior();
+        assert!(exterior.coords().all(|coord| coord.x == 0.));
+        assert!(exterior
+            .coords()
+            .any(|coord| coord.y == 2. && coord.z == 1.));
+    }
+
+    #[test]
+    fn sloped_interpolation() {
+        let roof = MultiPolygonZ(vec![polygon_z![
+            (x: 0., y: 0., z: 10.),
+            (x: 4., y: 0., z: 10.),
+            (x: 4., y: 4., z: 12.),
+            (x: 0., y: 4., z: 12.),
+        ]]);
+        let cutout = polygon_z![
+            (x: 1., y: 1., z: 10.5),
+            (x: 3., y: 1., z: 10.5),
+            (x: 3., y: 3., z: 11.5),
+            (x: 1., y: 3., z: 11.5),
+        ];
+        let result = roof.difference_z(&cutout).unwrap();
+        assert_eq!(result.0.len(), 1);
+        assert_eq!(result.0[0].interiors().len(), 1);
+        for coord in result.0[0]
+            .exterior()
+            .coords()
+            .chain(result.0[0].interiors()[0].coords())
+        {
+            assert_relative_eq!(coord.z, 10. + coord.y / 2., epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn not_coplanar() {
+        let a = polygon_z![
+            (x: 0., y: 0., z: 0.),
+            (x: 2., y: 0., z: 0.),
+            (x: 2., y: 2., z: 0.),
+            (x: 0., y: 2., z: 0.),
+        ];
+        let b = polygon_z![
+            (x: 1., y: 1., z: 1.),
+            (x: 3., y: 1., z: 1.),
+            (x: 3., y: 3., z: 1.),
+            (x: 1., y: 3., z: 1.),
+        ];
+        assert!(matches!(a.union_z(&b), Err(Error::NotCoplanar)));
+        let union = a
+            .boolean_op_z_with_tolerance(&b, OpType::Union, 1.)
+            .unwrap();
+        assert_eq!(union.0.len(), 1);
+    }
+
+    #[test]
+    fn degenerate_input() {
+        let empty: PolygonZ<f64> = PolygonZ::new(LineStringZ::new(vec![]), vec![]);
+        assert!(empty.union_z(&empty).unwrap().0.is_empty());
+    }
+}
@@ -0,0 +1,273 @@
+use crate::{
+    CoordFloat, CoordNum, CoordZ, Cube, EllipsoidZ, Geometry, GeometryCollection, LineStringZ,
+    LineStringZArray, LineStringZSlice, LineZ, MultiLineStringZ, MultiPointZ, MultiPolygonZ, Obb,
+    PointZ, PolygonZ, PolygonZRef, Sphere, Tagged, TaggedCollection, TinZ, Triangle,
+};
+
+/// The smallest axis-aligned [`Cube`] containing a geometry.
+///
+/// Geometries that may be empty return an `Option`. Planar members of a
+/// [`Geometry`] or [`GeometryCollection`] are taken to lie at `z = 0`.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::BoundingCube;
+/// use geo_types_3d::{line_string_z, Cube, LineStringZ};
+///
+/// let line_string: LineStringZ = line_string_z![
+///     (x: 0.0, y: 4.0, z: 1.0),
+///     (x: 3.0, y: -2.0, z: 5.0),
+/// ];
+/// assert_eq!(
+///     line_string.bounding_cube(),
+///     Some(Cube::new((0.0, -2.0, 1.0), (3.0, 4.0, 5.0)))
+/// );
+/// ```
+pub trait BoundingCube<T: CoordNum> {
+    type Output: Into<Option<Cube<T>>>;
+
+    fn bounding_cube(&self) -> Self::Output;
+}
+
+impl<T: CoordNum> BoundingCube<T> for PointZ<T> {
+    type Output = Cube<T>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        Cube::new(self.0, self.0)
+    }
+}
+
+impl<T: CoordNum> BoundingCube<T> for LineZ<T> {
+    type Output = Cube<T>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        Cube::new(self.start, self.end)
+    }
+}
+
+impl<T: CoordNum> BoundingCube<T> for Triangle<T> {
+    type Output = Cube<T>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        Cube::new(self.0, self.1).union(&Cube::new(self.2, self.2))
+    }
+}
+
+impl<T: CoordNum> BoundingCube<T> for Cube<T> {
+    type Output = Cube<T>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        *self
+    }
+}
+
+impl<T: CoordFloat> BoundingCube<T> for Obb<T> {
+    type Output = Cube<T>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        bounds(self.corners()).expect("a box has corners")
+    }
+}
+
+impl<T: CoordFloat> BoundingCube<T> for Sphere<T> {
+    type Output = Cube<T>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        let r = CoordZ::from((self.radius, self.radius, self.radius));
+        Cube::new(self.center - r, self.center + r)
+    }
+}
+
+impl<T: CoordFloat> BoundingCube<T> for EllipsoidZ<T> {
+    type Output = Cube<T>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        Cube::new(self.center - self.semi_axes, self.center + self.semi_axes)
+    }
+}
+
+impl<T: CoordNum> BoundingCube<T> for MultiPointZ<T> {
+    type Output = Option<Cube<T>>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        bounds(self.0.iter().map(|point| point.0))
+    }
+}
+
+impl<T: CoordNum> BoundingCube<T> for TinZ<T> {
+    type Output = Option<Cube<T>>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        bounds(self.vertices().iter().copied())
+    }
+}
+
+impl<T: CoordNum> BoundingCube<T> for LineStringZ<T> {
+    type Output = Option<Cube<T>>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        bounds(self.0.iter().copied())
+    }
+}
+
+impl<T: CoordNum> BoundingCube<T> for LineStringZSlice<'_, T> {
+    type Output = Option<Cube<T>>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        bounds(self.0.iter().copied())
+    }
+}
+
+impl<T: CoordNum, const N: usize> BoundingCube<T> for LineStringZArray<T, N> {
+    type Output = Option<Cube<T>>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        bounds(self.coords().copied())
+    }
+}
+
+impl<T: CoordNum> BoundingCube<T> for MultiLineStringZ<T> {
+    type Output = Option<Cube<T>>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        union_all(self.0.iter().map(LineStringZ::bounding_cube))
+    }
+}
+
+impl<T: CoordNum> BoundingCube<T> for PolygonZ<T> {
+    type Output = Option<Cube<T>>;
+
+    /// Interior rings lie inside the exterior, so only the exterior is considered.
+    fn bounding_cube(&self) -> Self::Output {
+        self.exterior().bounding_cube()
+    }
+}
+
+impl<T: CoordNum> BoundingCube<T> for PolygonZRef<'_, T> {
+    type Output = Option<Cube<T>>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        self.exterior().bounding_cube()
+    }
+}
+
+impl<T: CoordNum> BoundingCube<T> for MultiPolygonZ<T> {
+    type Output = Option<Cube<T>>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        union_all(self.0.iter().map(PolygonZ::bounding_cube))
+    }
+}
+
+impl<T: CoordNum> BoundingCube<T> for GeometryCollection<T> {
+    type Output = Option<Cube<T>>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        union_all(self.iter().map(Geometry::bounding_cube))
+    }
+}
+
+impl<T: CoordNum> BoundingCube<T> for Geometry<T> {
+    type Output = Option<Cube<T>>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        let flat = |c: geo_types::Coord<T>| CoordZ {
+            x: c.x,
+            y: c.y,
+            z: T::zero(),
+        };
+        match self {
+            Geometry::Point(point) => Some(Cube::new(flat(point.0), flat(point.0))),
+            Geometry::Line(line) => Some(Cube::new(flat(line.start), flat(line.end))),
+            Geometry::LineString(line_string) => bounds(line_string.0.iter().copied().map(flat)),
+            Geometry::Polygon(polygon) => bounds(polygon.exterior().0.iter().copied().map(flat)),
+            Geometry::MultiPoint(multi) => bounds(multi.0.iter().map(|point| flat(point.0))),
+            Geometry::MultiLineString(multi) => bounds(
+                multi
+                    .0
+                    .iter()
+                    .flat_map(|line_string| line_string.0.iter().copied().map(flat)),
+            ),
+            Geometry::MultiPolygon(multi) => bounds(
+                multi
+                    .0
+                    .iter()
+                    .flat_map(|polygon| polygon.exterior().0.iter().copied().map(flat)),
+            ),
+            Geometry::Rect(rect) => Some(Cube::new(flat(rect.min()), flat(rect.max()))),
+            Geometry::PointZ(point) => Some(point.bounding_cube()),
+            Geometry::LineZ(line) => Some(line.bounding_cube()),
+            Geometry::LineStringZ(line_string) => line_string.bounding_cube(),
+            Geometry::PolygonZ(polygon) => polygon.bounding_cube(),
+            Geometry::MultiPointZ(multi) => multi.bounding_cube(),
+            Geometry::MultiLineStringZ(multi) => multi.bounding_cube(),
+            Geometry::MultiPolygonZ(multi) => multi.bounding_cube(),
+            Geometry::GeometryCollection(collection) => collection.bounding_cube(),
+        }
+    }
+}
+
+impl<T: CoordNum, G: BoundingCube<T>, D> BoundingCube<T> for Tagged<G, D> {
+    type Output = G::Output;
+
+    fn bounding_cube(&self) -> Self::Output {
+        self.geometry.bounding_cube()
+    }
+}
+
+impl<T: CoordNum, G: BoundingCube<T>, D> BoundingCube<T> for TaggedCollection<G, D> {
+    type Output = Option<Cube<T>>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        union_all(
+            self.geometries()
+                .map(|geometry| geometry.bounding_cube().into()),
+        )
+    }
+}
+
+fn bounds<T: CoordNum>(coords: impl IntoIterator<Item = CoordZ<T>>) -> Option<Cube<T>> {
+    union_all(
+        coords
+            .into_iter()
+            .map(|coord| Some(Cube::new(coord, coord))),
+    )
+}
+
+fn union_all<T: CoordNum>(cubes: impl IntoIterator<Item = Option<Cube<T>>>) -> Option<Cube<T>> {
+    cubes
+        .into_iter()
+        .flatten()
+        .reduce(|acc, cube| acc.union(&cube))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn collections() {
+        let polygon = polygon_z![
+            (x: 0., y: 0., z: 1.),
+            (x: 2., y: 0., z: 1.),
+            (x: 0., y: 2., z: 3.),
+        ];
+        let collection = GeometryCollection(vec![
+            Geometry::PolygonZ(polygon.clone()),
+            Geometry::Point(geo_types::Point::new(-1., 5.)),
+            Geometry::MultiPointZ(MultiPointZ(vec![])),
+        ]);
+        assert_eq!(
+            collection.bounding_cube(),
+            Some(Cube::new((-1., 0., 0.), (2., 5., 3.)))
+        );
+        assert_eq!(
+            MultiPolygonZ(vec![polygon]).bounding_cube(),
+            Some(Cube::new((0., 0., 1.), (2., 2., 3.)))
+        );
+        assert_eq!(MultiLineStringZ::<f64>(vec![]).bounding_cube(), None);
+        assert_eq!(GeometryCollection::<f64>(vec![]).bounding_cube(), None);
+    }
+}
@@ -0,0 +1,190 @@
+use geo::algorithm::bool_ops::BoolOpsNum;
+use geo::Buffer;
+
+pub use geo::algorithm::buffer::{BufferStyle, LineCap, LineJoin};
+
+use super::xy::{line_string_xy, multi_line_string_xy, multi_polygon_xy, polygon_xy, NearestZ};
+use crate::{LineStringZ, MultiLineStringZ, MultiPolygonZ, PolygonZ};
+
+/// Buffer the XY footprint of a geometry while preserving its elevation.
+///
+/// The buffer is computed in the XY plane exactly like [`geo::Buffer`]. Every vertex
+/// of the result then gets the z of the closest point on the source geometry's
+/// edges, interpolated along that edge. Around caps and joins this is the z of the
+/// nearest source vertex.
+///
+/// The source segments are indexed in a [`Bvh`](crate::index::Bvh), so each output
+/// vertex finds its closest segment without visiting every other one.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::BufferZ;
+/// use geo_types_3d::{line_string_z, LineStringZ};
+///
+/// let ramp: LineStringZ = line_string_z![
+///     (x: 0.0, y: 0.0, z: 0.0),
+///     (x: 10.0, y: 0.0, z: 10.0),
+/// ];
+/// let footprint = ramp.buffer_z(1.0);
+///
+/// for coord in footprint.0[0].exterior().coords() {
+///     let expected = coord.x.max(0.0).min(10.0);
+///     assert!((coord.z - expected).abs() < 1e-9);
+/// }
+/// ```
+pub trait BufferZ {
+    type Scalar: BoolOpsNum + 'static;
+
+    /// Buffer by `distance` with round caps and joins.
+    fn buffer_z(&self, distance: Self::Scalar) -> MultiPolygonZ<Self::Scalar> {
+        self.buffer_z_with_style(BufferStyle::new(distance))
+    }
+
+    /// Buffer with an explicit [`BufferStyle`].
+    fn buffer_z_with_style(&self, style: BufferStyle<Self::Scalar>) -> MultiPolygonZ<Self::Scalar>;
+}
+
+impl<T: BoolOpsNum + 'static> BufferZ for LineStringZ<T> {
+    type Scalar = T;
+
+    fn buffer_z_with_style(&self, style: BufferStyle<T>) -> MultiPolygonZ<T> {
+        let mut z = NearestZ::new();
+        z.add_line_string(self);
+        z.lift_multi_polygon(&line_string_xy(self).buffer_with_style(style))
+    }
+}
+
+impl<T: BoolOpsNum + 'static> BufferZ for MultiLineStringZ<T> {
+    type Scalar = T;
+
+    fn buffer_z_with_style(&self, style: BufferStyle<T>) -> MultiPolygonZ<T> {
+        let mut z = NearestZ::new();
+        for line_string in &self.0 {
+            z.add_line_string(line_string);
+        }
+        z.lift_multi_polygon(&multi_line_string_xy(self).buffer_with_style(style))
+    }
+}
+
+impl<T: BoolOpsNum + 'static> BufferZ for PolygonZ<T> {
+    type Scalar = T;
+
+    fn buffer_z_with_style(&self, style: BufferStyle<T>) -> MultiPolygonZ<T> {
+        let mut z = NearestZ::new();
+        z.add_polygon(self);
+        z.lift_multi_polygon(&polygon_xy(self).buffer_with_style(style))
+    }
+}
+
+impl<T: BoolOpsNum + 'static> BufferZ for MultiPolygonZ<T> {
+    type Scalar = T;
+
+    fn buffer_z_with_style(&self, style: BufferStyle<T>) -> MultiPolygonZ<T> {
+        let mut z = NearestZ::new();
+        for polygon in &self.0 {
+            z.add_polygon(polygon);
+        }
+        z.lift_multi_polygon(&multi_polygon_xy(self).buffer_with_style(style))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::xy::coord_xy;
+    use super::*;
+
+    #[test]
+    fn line_string_z_is_interpolated() {
+        let ramp = line_string_z![
+            (x: 0., y: 0., z: 0.),
+            (x: 10., y: 0., z: 5.),
+            (x: 10., y: 10., z: 5.),
+        ];
+        let buffered = ramp.buffer_z(1.);
+        assert_eq!(buffered.0.len(), 1);
+        for coord in buffered.0[0].exterior().coords() {
+            assert!(coord.z >= 0. && coord.z <= 5.);
+            if coord.y < -0.5 && coord.x > 0. && coord.x < 10. {
+                // below the first segment
+                assert_relative_eq!(coord.z, coord.x / 2., epsilon = 1e-9);
+            }
+            if coord.x > 10.5 && coord.y > 0. {
+                // beside the flat second segment
+                assert_relative_eq!(coord.z, 5.);
+            }
+        }
+    }
+
+    #[test]
+    fn polygon_keeps_ring_elevation() {
+        let roof = polygon_z![
+            (x: 0., y: 0., z: 3.),
+            (x: 4., y: 0., z: 3.),
+            (x: 4., y: 4., z: 3.),
+            (x: 0., y: 4., z: 3.),
+        ];
+        let buffered = roof.buffer_z(0.5);
+        assert_eq!(buffered.0.len(), 1);
+        assert!(buffered.0[0].exterior().coords().all(|coord| coord.z == 3.));
+        assert!(buffered.0[0]
+            .exterior()
+            .coords()
+            .any(|coord| coord.x < -0.4));
+    }
+
+    #[test]
+    fn negative_distance_shrinks_polygon() {
+        let square = polygon_z![
+            (x: 0., y: 0., z: 1.),
+            (x: 4., y: 0., z: 1.),
+            (x: 4., y: 4., z: 2.),
+            (x: 0., y: 4., z: 2.),
+        ];
+        let shrunk =
+            square.buffer_z_with_style(BufferStyle::new(-1.).line_join(LineJoin::Miter(1.)));
+        assert_eq!(shrunk.0.len(), 1);
+        for coord in shrunk.0[0].exterior().coords() {
+            assert!(coord.x >= 1. - 1e-9 && coord.x <= 3. + 1e-9);
+            assert!(coord.z >= 1. && coord.z <= 2.);
+        }
+    }
+
+    #[test]
+    fn long_line_string_matches_a_scan() {
+        let zigzag: LineStringZ = (0..200)
+            .map(|i| {
+                let x = i as f64;
+                (x, (i % 2) as f64 * 3., x / 4.)
+            })
+            .collect::<Vec<_>>()
+            .into();
+        let buffered = zigzag.buffer_z(0.75);
+        for coord in buffered
+            .0
+            .iter()
+            .flat_map(|polygon| polygon.exterior().coords())
+        {
+            let candidates: Vec<(f64, f64)> = zigzag
+                .lines()
+                .map(|line| {
+                    let (d, s) = (line.delta(), coord_xy(*coord) - coord_xy(line.start));
+                    let t = ((s.x * d.x + s.y * d.y) / (d.x * d.x + d.y * d.y)).clamp(0., 1.);
+                    let (dx, dy) = (s.x - d.x * t, s.y - d.y * t);
+                    (dx * dx + dy * dy, line.start.z + d.z * t)
+                })
+                .collect();
+            let closest = candidates.iter().map(|c| c.0).fold(f64::INFINITY, f64::min);
+            // vertices where joins meet are as close to both segments, so either z is right
+            assert!(candidates
+                .iter()
+                .any(|&(dist2, z)| dist2 < closest + 1e-9 && (coord.z - z).abs() < 1e-9));
+        }
+    }
+
+    #[test]
+    fn empty_input() {
+        let empty: LineStringZ<f64> = LineStringZ::new(vec![]);
+        assert!(empty.buffer_z(1.).0.is_empty());
+    }
+}
@@ -0,0 +1,458 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use geo_types::{Coord, LineString, MultiLineString, MultiPoint, MultiPolygon, Polygon, Rect};
+
+use crate::conversion::wkb::write_wkb_into;
+use crate::{
+    CoordNum, CoordZ, Geometry, GeometryCollection, LineStringZ, LineZ, MultiLineStringZ,
+    MultiPointZ, MultiPolygonZ, Point, PointZ, PolygonZ,
+};
+
+/// Put geometries into a canonical form, so that geometries describing the same
+/// shape in different ways compare equal and hash alike.
+///
+/// In the canonical form:
+///
+/// - polygon rings start at their smallest vertex, ordering vertices by x, then y,
+///   then z;
+/// - exterior rings run anticlockwise and interior rings clockwise, seen from above;
+///   rings with no area seen from above run towards the smaller of the two
+///   neighbours of their first vertex;
+/// - the interior rings of a polygon, and the members of multi-geometries and
+///   collections, are in a fixed order;
+/// - negative zero ordinates are replaced by zero.
+///
+/// Line strings keep their direction, which often carries meaning, such as the
+/// direction of travel along a road.
+///
+/// [`Canonicalize::digest`] hashes the canonical form, to find duplicates across
+/// datasets.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::Canonicalize;
+/// use geo_types_3d::{polygon_z, PolygonZ};
+///
+/// let square: PolygonZ = polygon_z![
+///     (x: 0., y: 0., z: 1.),
+///     (x: 1., y: 0., z: 1.),
+///     (x: 1., y: 1., z: 1.),
+///     (x: 0., y: 1., z: 1.),
+/// ];
+/// // the same square, starting from another corner and running clockwise
+/// let redrawn: PolygonZ = polygon_z![
+///     (x: 1., y: 1., z: 1.),
+///     (x: 1., y: 0., z: 1.),
+///     (x: 0., y: 0., z: 1.),
+///     (x: 0., y: 1., z: 1.),
+/// ];
+/// assert_ne!(square, redrawn);
+/// assert_eq!(square.canonicalize(), redrawn.canonicalize());
+/// assert_eq!(square.digest(), redrawn.digest());
+/// ```
+pub trait Canonicalize<T: CoordNum>: Clone + Into<Geometry<T>> {
+    /// The canonical form of the geometry.
+    #[must_use]
+    fn canonicalize(&self) -> Self;
+
+    /// A 64-bit digest of the canonical form, the same on every platform and in
+    /// every version of this crate.
+    ///
+    /// This is the xxHash64 (seed 0) of the canonical form written as ISO
+    /// well-known binary, little-endian, with ordinates as `f64`.
+    fn digest(&self) -> u64 {
+        let mut bytes = Vec::new();
+        write_wkb_into(&self.canonicalize().into(), &mut bytes);
+        xxh64(&bytes, 0)
+    }
+}
+
+impl<T: CoordNum> Canonicalize<T> for PointZ<T> {
+    fn canonicalize(&self) -> Self {
+        PointZ(self.0.normalized())
+    }
+}
+
+impl<T: CoordNum> Canonicalize<T> for LineZ<T> {
+    fn canonicalize(&self) -> Self {
+        LineZ::new(self.start.normalized(), self.end.normalized())
+    }
+}
+
+impl<T: CoordNum> Canonicalize<T> for LineStringZ<T> {
+    fn canonicalize(&self) -> Self {
+        LineStringZ(self.0.iter().map(|c| c.normalized()).collect())
+    }
+}
+
+impl<T: CoordNum> Canonicalize<T> for PolygonZ<T> {
+    fn canonicalize(&self) -> Self {
+        let (exterior, interiors) = canonical_rings(
+            &self.exterior().0,
+            self.interiors().iter().map(|ring| &ring.0[..]),
+        );
+        PolygonZ::new(
+            LineStringZ(exterior),
+            interiors.into_iter().map(LineStringZ).collect(),
+        )
+    }
+}
+
+impl<T: CoordNum> Canonicalize<T> for MultiPointZ<T> {
+    fn canonicalize(&self) -> Self {
+        MultiPointZ(sorted(
+            self.0.iter().map(Canonicalize::canonicalize),
+            Into::into,
+        ))
+    }
+}
+
+impl<T: CoordNum> Canonicalize<T> for MultiLineStringZ<T> {
+    fn canonicalize(&self) -> Self {
+        MultiLineStringZ(sorted(
+            self.0.iter().map(Canonicalize::canonicalize),
+            Into::into,
+        ))
+    }
+}
+
+impl<T: CoordNum> Canonicalize<T> for MultiPolygonZ<T> {
+    fn canonicalize(&self) -> Self {
+        MultiPolygonZ(sorted(
+            self.0.iter().map(Canonicalize::canonicalize),
+            Into::into,
+        ))
+    }
+}
+
+impl<T: CoordNum> Canonicalize<T> for GeometryCollection<T> {
+    fn canonicalize(&self) -> Self {
+        GeometryCollection(sorted(
+            self.0.iter().map(Canonicalize::canonicalize),
+            Into::into,
+        ))
+    }
+}
+
+impl<T: CoordNum> Canonicalize<T> for Geometry<T> {
+    fn canonicalize(&self) -> Self {
+        match self {
+            Geometry::Point(point) => Geometry::Point(Point(point.0.normalized())),
+            Geometry::PointZ(point) => Geometry::PointZ(point.canonicalize()),
+            Geometry::Line(line) => Geometry::Line(geo_types::Line::new(
+                line.start.normalized(),
+                line.end.normalized(),
+            )),
+            Geometry::LineZ(line) => Geometry::LineZ(line.canonicalize()),
+            Geometry::LineString(line_string) => Geometry::LineString(LineString(
+                line_string.0.iter().map(|c| c.normalized()).collect(),
+            )),
+            Geometry::LineStringZ(line_string) => Geometry::LineStringZ(line_string.canonicalize()),
+            Geometry::Polygon(polygon) => Geometry::Polygon(canonical_polygon(polygon)),
+            Geometry::PolygonZ(polygon) => Geometry::PolygonZ(polygon.canonicalize()),
+            Geometry::MultiPoint(multi_point) => Geometry::MultiPoint(MultiPoint(sorted(
+                multi_point.iter().map(|point| Point(point.0.normalized())),
+                Geometry::Point,
+            ))),
+            Geometry::MultiPointZ(multi_point) => Geometry::MultiPointZ(multi_point.canonicalize()),
+            Geometry::MultiLineString(multi_line_string) => {
+                Geometry::MultiLineString(MultiLineString(sorted(
+                    multi_line_string.iter().map(|line_string| {
+                        LineString(line_string.0.iter().map(|c| c.normalized()).collect())
+                    }),
+                    Geometry::LineString,
+                )))
+            }
+            Geometry::MultiLineStringZ(multi_line_string) => {
+                Geometry::MultiLineStringZ(multi_line_string.canonicalize())
+            }
+            Geometry::MultiPolygon(multi_polygon) => Geometry::MultiPolygon(MultiPolygon(sorted(
+                multi_polygon.iter().map(canonical_polygon),
+                Geometry::Polygon,
+            ))),
+            Geometry::MultiPolygonZ(multi_polygon) => {
+                Geometry::MultiPolygonZ(multi_polygon.canonicalize())
+            }
+            Geometry::GeometryCollection(collection) => {
+                Geometry::GeometryCollection(collection.canonicalize())
+            }
+            Geometry::Rect(rect) => {
+                Geometry::Rect(Rect::new(rect.min().normalized(), rect.max().normalized()))
+            }
+        }
+    }
+}
+
+/// A coordinate of a ring, in two or three dimensions.
+trait Vertex<T: CoordNum>: Copy {
+    /// The ordinates, with zero z for two dimensions.
+    fn key(&self) -> [T; 3];
+
+    /// The coordinate with negative zeros replaced by zero.
+    fn normalized(self) -> Self;
+}
+
+impl<T: CoordNum> Vertex<T> for Coord<T> {
+    fn key(&self) -> [T; 3] {
+        [self.x, self.y, T::zero()]
+    }
+
+    fn normalized(self) -> Self {
+        Coord {
+            x: normalized(self.x),
+            y: normalized(self.y),
+        }
+    }
+}
+
+impl<T: CoordNum> Vertex<T> for CoordZ<T> {
+    fn key(&self) -> [T; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    fn normalized(self) -> Self {
+        CoordZ {
+            x: normalized(self.x),
+            y: normalized(self.y),
+            z: normalized(self.z),
+        }
+    }
+}
+
+fn normalized<T: CoordNum>(value: T) -> T {
+    if value == T::zero() {
+        T::zero()
+    } else {
+        value
+    }
+}
+
+fn compare<T: CoordNum, C: Vertex<T>>(a: &C, b: &C) -> Ordering {
+    a.key().partial_cmp(&b.key()).unwrap_or(Ordering::Equal)
+}
+
+fn canonical_polygon<T: CoordNum>(polygon: &Polygon<T>) -> Polygon<T> {
+    let (exterior, interiors) = canonical_rings(
+        &polygon.exterior().0,
+        polygon.interiors().iter().map(|ring| &ring.0[..]),
+    );
+    Polygon::new(
+        LineString(exterior),
+        interiors.into_iter().map(LineString).collect(),
+    )
+}
+
+/// The canonical exterior and interior rings of a polygon.
+fn canonical_rings<'a, T: CoordNum, C: Vertex<T> + 'a>(
+    exterior: &[C],
+    interiors: impl Iterator<Item = &'a [C]>,
+) -> (Vec<C>, Vec<Vec<C>>) {
+    let mut interiors: Vec<Vec<C>> = interiors.map(|ring| canonical_ring(ring, false)).collect();
+    interiors.sort_by(|a, b| {
+        a.iter()
+            .map(Vertex::key)
+            .partial_cmp(b.iter().map(Vertex::key))
+            .unwrap_or(Ordering::Equal)
+    });
+    (canonical_ring(exterior, true), interiors)
+}
+
+/// Starts a closed ring at its smallest vertex, running anticlockwise or
+/// clockwise seen from above.
+fn canonical_ring<T: CoordNum, C: Vertex<T>>(ring: &[C], anticlockwise: bool) -> Vec<C> {
+    let mut coords: Vec<C> = ring.iter().map(|c| c.normalized()).collect();
+    let closed = coords.len() >= 4 && compare(&coords[0], &coords[coords.len() - 1]).is_eq();
+    if !closed {
+        return coords;
+    }
+    coords.pop();
+    let n = coords.len();
+    // twice the signed area seen from above, positive for anticlockwise rings
+    let area = (0..n).fold(T::zero(), |sum, i| {
+        let (a, b) = (coords[i].key(), coords[(i + 1) % n].key());
+        sum + a[0] * b[1] - b[0] * a[1]
+    });
+    let start = (0..n)
+        .min_by(|&a, &b| compare(&coords[a], &coords[b]))
+        .unwrap_or(0);
+    coords.rotate_left(start);
+    let reverse = if area == T::zero() {
+        compare(&coords[n - 1], &coords[1]).is_lt()
+    } else {
+        (area > T::zero()) != anticlockwise
+    };
+    if reverse {
+        coords[1..].reverse();
+    }
+    coords.push(coords[0]);
+    coords
+}
+
+/// The geometries in order of their well-known binary.
+fn sorted<T: CoordNum, G: Clone>(
+    members: impl Iterator<Item = G>,
+    geometry: impl Fn(G) -> Geometry<T>,
+) -> Vec<G> {
+    let mut keyed: Vec<(Vec<u8>, G)> = members
+        .map(|member| {
+            let mut bytes = Vec::new();
+            write_wkb_into(&geometry(member.clone()), &mut bytes);
+            (bytes, member)
+        })
+        .collect();
+    keyed.sort_by(|a, b| a.0.cmp(&b.0));
+    keyed.into_iter().map(|(_, member)| member).collect()
+}
+
+/// The 64-bit xxHash of `bytes`.
+fn xxh64(bytes: &[u8], seed: u64) -> u64 {
+    const P1: u64 = 0x9E37_79B1_85EB_CA87;
+    const P2: u64 = 0xC2B2_AE3D_27D4_EB4F;
+    const P3: u64 = 0x1656_67B1_9E37_79F9;
+    const P4: u64 = 0x85EB_CA77_C2B2_AE63;
+    const P5: u64 = 0x27D4_EB2F_1656_67C5;
+    let round = |acc: u64, input: u64| {
+        acc.wrapping_add(input.wrapping_mul(P2))
+            .rotate_left(31)
+            .wrapping_mul(P1)
+    };
+    let merge = |acc: u64, value: u64| (acc ^ round(0, value)).wrapping_mul(P1).wrapping_add(P4);
+    let u64_at = |chunk: &[u8]| u64::from_le_bytes(chunk[..8].try_into().unwrap());
+
+    let stripes = bytes.chunks_exact(32);
+    let mut rest = stripes.remainder();
+    let mut hash = if bytes.len() >= 32 {
+        let mut v = [
+            seed.wrapping_add(P1).wrapping_add(P2),
+            seed.wrapping_add(P2),
+            seed,
+            seed.wrapping_sub(P1),
+        ];
+        for stripe in stripes {
+            for (lane, word) in v.iter_mut().zip(stripe.chunks_exact(8)) {
+                *lane = round(*lane, u64_at(word));
+            }
+        }
+        let hash = v[0]
+            .rotate_left(1)
+            .wrapping_add(v[1].rotate_left(7))
+            .wrapping_add(v[2].rotate_left(12))
+            .wrapping_add(v[3].rotate_left(18));
+        v.iter().fold(hash, |hash, &lane| merge(hash, lane))
+    } else {
+        seed.wrapping_add(P5)
+    };
+    hash = hash.wrapping_add(bytes.len() as u64);
+
+    while rest.len() >= 8 {
+        hash ^= round(0, u64_at(rest));
+        hash = hash.rotate_left(27).wrapping_mul(P1).wrapping_add(P4);
+        rest = &rest[8..];
+    }
+    if rest.len() >= 4 {
+        let word = u32::from_le_bytes(rest[..4].try_into().unwrap()) as u64;
+        hash ^= word.wrapping_mul(P1);
+        hash = hash.rotate_left(23).wrapping_mul(P2).wrapping_add(P3);
+        rest = &rest[4..];
+    }
+    for &byte in rest {
+        hash ^= (byte as u64).wrapping_mul(P5);
+        hash = hash.rotate_left(11).wrapping_mul(P1);
+    }
+
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(P2);
+    hash ^= hash >> 29;
+    hash = hash.wrapping_mul(P3);
+    hash ^ (hash >> 32)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn xxhash() {
+        assert_eq!(xxh64(b"", 0), 0xEF46_DB37_51D8_E999);
+        assert_eq!(xxh64(b"abc", 0), 0x44BC_2CF5_AD77_0999);
+        assert_eq!(
+            xxh64(b"Nobody inspects the spammish repetition", 0),
+            0xFBCE_A83C_8A37_8BF1
+        );
+    }
+
+    #[test]
+    fn canonical() {
+        // a square with a hole, drawn two ways
+        let a = PolygonZ::new(
+            vec![
+                (0., 0., 0.),
+                (4., 0., 0.),
+                (4., 4., 0.),
+                (0., 4., 0.),
+                (0., 0., 0.),
+            ]
+            .into(),
+            vec![vec![(1., 1., 0.), (2., 1., 0.), (2., 2., 0.), (1., 1., 0.)].into()],
+        );
+        let b = PolygonZ::new(
+            vec![
+                (4., 4., 0.),
+                (4., 0., -0.),
+                (0., 0., 0.),
+                (0., 4., 0.),
+                (4., 4., 0.),
+            ]
+            .into(),
+            vec![vec![(2., 2., 0.), (1., 1., 0.), (2., 1., 0.), (2., 2., 0.)].into()],
+        );
+        let canonical = a.canonicalize();
+        assert_eq!(canonical, b.canonicalize());
+        assert_eq!(canonical.exterior().0[1], CoordZ::from((4., 0., 0.)));
+        assert_eq!(canonical.interiors()[0].0[1], CoordZ::from((2., 2., 0.)));
+        assert_eq!(canonical.canonicalize(), canonical);
+        assert_eq!(a.digest(), b.digest());
+
+        // a vertical wall has no area from above
+        let wall = |coords: Vec<(f64, f64, f64)>| PolygonZ::new(coords.into(), vec![]);
+        let east = wall(vec![
+            (0., 0., 0.),
+            (1., 0., 0.),
+            (1., 0., 3.),
+            (0., 0., 3.),
+            (0., 0., 0.),
+        ]);
+        let west = wall(vec![
+            (1., 0., 3.),
+            (1., 0., 0.),
+            (0., 0., 0.),
+            (0., 0., 3.),
+            (1., 0., 3.),
+        ]);
+        assert_eq!(east.canonicalize(), west.canonicalize());
+
+        // member order doesn't matter, but line direction does
+        let line_strings = |order: [usize; 2]| {
+            let members: [LineStringZ; 2] = [
+                vec![(0., 0., 0.), (1., 1., 1.)].into(),
+                vec![(5., 5., 5.), (6., 6., 6.)].into(),
+            ];
+            MultiLineStringZ::new(order.map(|i| members[i].clone()).to_vec())
+        };
+        assert_eq!(line_strings([0, 1]).digest(), line_strings([1, 0]).digest());
+        let reversed: LineStringZ = vec![(1., 1., 1.), (0., 0., 0.)].into();
+        assert_ne!(line_strings([0, 1]).0[0].digest(), reversed.digest());
+
+        let collection = |members: Vec<Geometry>| GeometryCollection(members);
+        let point: Geometry = PointZ::new(1., 2., 3.).into();
+        let polygon: Geometry = a.into();
+        assert_eq!(
+            collection(vec![point.clone(), polygon.clone()]).digest(),
+            collection(vec![polygon.clone(), point.clone()]).digest()
+        );
+        assert_ne!(point.digest(), polygon.digest());
+    }
+}
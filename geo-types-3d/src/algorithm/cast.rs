@@ -0,0 +1,160 @@
+use core::cell::Cell;
+
+use crate::algorithm::MapCoordsZ;
+use crate::{CoordNum, CoordZ, Error};
+
+/// Convert the coordinates of a geometry to another scalar type, such as from
+/// `f64` to `f32` to halve the memory a large point cloud takes.
+///
+/// Each ordinate is converted as by [`num_traits::NumCast`], so floats are rounded
+/// to the nearest value of a narrower float type, and truncated towards zero when
+/// converted to an integer type. Planar members of a [`Geometry`](crate::Geometry)
+/// keep only their x and y, as with [`MapCoordsZ`].
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::Cast;
+/// use geo_types_3d::{Error, PointZ};
+///
+/// let point = PointZ::new(1.5, 2.25, 1e3);
+/// assert_eq!(point.cast::<f32>().unwrap(), PointZ::new(1.5f32, 2.25, 1e3));
+/// assert_eq!(point.cast::<i16>().unwrap(), PointZ::new(1i16, 2, 1000));
+///
+/// let far = PointZ::new(0., 0., 1e300);
+/// assert!(matches!(far.cast::<f32>(), Err(Error::CastOverflow)));
+/// ```
+pub trait Cast<T: CoordNum> {
+    /// The geometry with its coordinates converted to `U`, or
+    /// [`Error::CastOverflow`] if any is out of the range of `U`. A finite value
+    /// out of range of a float type is an overflow too, rather than becoming
+    /// infinite, as is NaN converted to an integer type.
+    fn cast<U: CoordNum>(&self) -> Result<<Self as MapCoordsZ<T, U>>::Output, Error>
+    where
+        Self: MapCoordsZ<T, U>;
+}
+
+impl<T: CoordNum, G: MapCoordsZ<T, T>> Cast<T> for G {
+    fn cast<U: CoordNum>(&self) -> Result<<Self as MapCoordsZ<T, U>>::Output, Error>
+    where
+        Self: MapCoordsZ<T, U>,
+    {
+        let overflow = Cell::new(false);
+        let cast = MapCoordsZ::<T, U>::map_coords(self, |c| {
+            match (convert(c.x), convert(c.y), convert(c.z)) {
+                (Some(x), Some(y), Some(z)) => CoordZ { x, y, z },
+                _ => {
+                    overflow.set(true);
+                    CoordZ::zero()
+                }
+            }
+        });
+        if overflow.get() {
+            Err(Error::CastOverflow)
+        } else {
+            Ok(cast)
+        }
+    }
+}
+
+fn convert<T: CoordNum, U: CoordNum>(value: T) -> Option<U> {
+    let cast = U::from(value)?;
+    // float conversions saturate to infinity instead of failing
+    let finite = |value: Option<f64>| value.is_some_and(f64::is_finite);
+    if finite(value.to_f64()) && !finite(cast.to_f64()) {
+        return None;
+    }
+    Some(cast)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Geometry, GeometryCollection, LineStringZ, MultiPointZ, PointZ};
+    use alloc::vec;
+
+    #[test]
+    fn cast() {
+        let line = LineStringZ::from(vec![(0.1, -2.7, 3.), (f64::NAN, 1e10, -1e30)]);
+        let narrow: LineStringZ<f32> = line.cast().unwrap();
+        assert_eq!(narrow.0[0], (0.1f32, -2.7, 3.).into());
+        assert!(narrow.0[1].x.is_nan());
+        assert_eq!(narrow.0[1].z, -1e30);
+        assert!(matches!(line.cast::<i64>(), Err(Error::CastOverflow)));
+
+        let collection = GeometryCollection::new_from(vec![
+            Geometry::PointZ(PointZ::new(1.9, -1.9, 70_000.)),
+            Geometry::Point(geo_types::Point::new(0.5, 2.)),
+        ]);
+        assert!(matches!(collection.cast::<i16>(), Err(Error::CastOverflow)));
+        let whole = collection.cast::<i32>().unwrap();
+        assert_eq!(whole.0[0], Geometry::PointZ(PointZ::new(1, -1, 70_000)));
+        assert_eq!(whole.0[1], Geometry::Point(geo_types::Point::new(0, 2)));
+
+        let points: MultiPointZ<f32> = MultiPointZ::new(vec![PointZ::new(1., 2., f32::MAX)]);
+        let wide = points.cast::<f64>().unwrap();
+        assert_eq!(wide.0[0].z(), f64::from(f32::MAX));
+        assert!(matches!(
+            wide.map_coords(|c| c * 2.).cast::<f32>(),
+            Err(Error::CastOverflow)
+        ));
+    }
+
+    #[test]
+    fn single_precision() {
+        use crate::algorithm::{AffineOpsZ, AffineTransform3D, BoundingCube};
+        use crate::MultiLineStringZ;
+
+        // two blobs of points, cast down from f64 and run through the point cloud
+        // algorithms in f32
+        let cloud: MultiPointZ = (0..40)
+            .map(|i| {
+                let f = |k: usize| ((i * k) % 7) as f64 * 0.1;
+                let offset = if i % 2 == 0 { 0. } else { 10. };
+                PointZ::new(offset + f(3), f(5), f(2) * 0.5)
+            })
+            .collect();
+        let cloud: MultiPointZ<f32> = cloud.cast().unwrap();
+        assert_eq!(cloud.kmeans(2).len(), 2);
+        assert_eq!(cloud.dbscan(1., 3).len(), 2);
+        assert!(cloud.principal_axes().unwrap().axes[0].x.abs() > 0.99);
+        assert!(cloud.geometric_median(1e-4).is_some());
+        assert_eq!(cloud.remove_radius_outliers(1., 2).0.len(), 40);
+        let cube = cloud.bounding_cube().unwrap();
+        assert!(cube.max().x > 10. && cube.min().x == 0.);
+        let survey: MultiPointZ = (0..400)
+            .map(|i| {
+                let (x, y) = ((i % 20) as f64, (i / 20) as f64);
+                PointZ::new(x, y, (x / 3.).sin() * (y / 4.).cos() * 3.)
+            })
+            .collect();
+        let survey: MultiPointZ<f32> = survey.cast().unwrap();
+        let offset = AffineTransform3D::rotate_z(4.).translated(0.6, -0.4, 0.3);
+        let (_, rms) = survey.affine_transform(&offset).align_to(&survey).unwrap();
+        assert!(rms < 1e-3);
+
+        // linework noded and polygonized in f32
+        let lines: MultiLineStringZ<f32> = MultiLineStringZ::new(vec![
+            LineStringZ::from(vec![(0., 0., 1.), (2., 0., 1.), (2., 2., 1.), (0., 2., 1.)]),
+            LineStringZ::from(vec![(0., 2., 1.), (0., 0., 1.), (1., 1., 1.)]),
+        ]);
+        let noded = lines.node(0.01);
+        assert_eq!(noded.0.len(), 3);
+        assert_eq!(noded.polygonize().unwrap().polygons.0.len(), 1);
+
+        #[cfg(feature = "std")]
+        {
+            use crate::algorithm::{Interpolation, RelateZ};
+            use crate::conversion::geojson::geometry_to_string;
+
+            let z = cloud.interpolate_z_at(5., 0.3, Interpolation::Nearest);
+            assert!(z.is_some());
+            let polygon = noded.polygonize().unwrap().polygons.0.remove(0);
+            assert!(PointZ::new(0.5f32, 1., 1.).within(&polygon).unwrap());
+            assert_eq!(
+                geometry_to_string(&Geometry::from(PointZ::new(0.1f32, 0.2, 0.3))),
+                r#"{"type":"Point","coordinates":[0.1,0.2,0.3]}"#
+            );
+        }
+    }
+}
@@ -0,0 +1,607 @@
+use alloc::vec::Vec;
+
+use crate::{
+    CoordFloat, CoordNum, CoordZ, Cube, Geometry, GeometryCollection, LineStringZ,
+    LineStringZArray, LineStringZSlice, LineZ, MultiLineStringZ, MultiPointZ, MultiPolygonZ,
+    PointZ, PolygonZ, PolygonZRef, Tagged,
+};
+
+/// Clip a geometry to an axis-aligned [`Cube`], keeping the parts inside it.
+///
+/// Points on the faces of the cube count as inside. Lines and line strings are
+/// clipped with the Liang–Barsky algorithm, so a line string that leaves and
+/// re-enters the cube is split into several pieces. Polygon rings are clipped against
+/// each of the six faces in turn (Sutherland–Hodgman). For polygons that are concave
+/// along a face this can leave zero-area slivers running along that face.
+///
+/// Points can be clipped whatever their coordinate type; everything else needs
+/// floats, to place the new vertices where it crosses the faces.
+///
+/// A [`Geometry`] is clipped member by member, returning `None` if nothing is
+/// left. Its planar members are lifted to z = 0 first, so only 3D members remain.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::Clip;
+/// use geo_types_3d::{line_string_z, Cube, LineStringZ};
+///
+/// let tile = Cube::new((0.0, 0.0, 0.0), (10.0, 10.0, 10.0));
+/// let track: LineStringZ = line_string_z![
+///     (x: -5.0, y: 5.0, z: 0.0),
+///     (x: 5.0, y: 5.0, z: 10.0),
+///     (x: 15.0, y: 5.0, z: 10.0),
+/// ];
+/// let clipped = track.clip(&tile);
+///
+/// assert_eq!(
+///     clipped.0,
+///     vec![line_string_z![
+///         (x: 0.0, y: 5.0, z: 5.0),
+///         (x: 5.0, y: 5.0, z: 10.0),
+///         (x: 10.0, y: 5.0, z: 10.0),
+///     ]]
+/// );
+/// ```
+pub trait Clip<T: CoordNum> {
+    type Output;
+
+    fn clip(&self, cube: &Cube<T>) -> Self::Output;
+}
+
+impl<T: CoordNum> Clip<T> for PointZ<T> {
+    type Output = Option<PointZ<T>>;
+
+    fn clip(&self, cube: &Cube<T>) -> Self::Output {
+        cube.contains_coord(self.0).then_some(*self)
+    }
+}
+
+impl<T: CoordNum> Clip<T> for MultiPointZ<T> {
+    type Output = MultiPointZ<T>;
+
+    fn clip(&self, cube: &Cube<T>) -> Self::Output {
+        MultiPointZ(self.0.iter().filter_map(|point| point.clip(cube)).collect())
+    }
+}
+
+impl<T: CoordFloat> Clip<T> for LineZ<T> {
+    type Output = Option<LineZ<T>>;
+
+    fn clip(&self, cube: &Cube<T>) -> Self::Output {
+        let (t0, t1) = liang_barsky(self.start, self.end, cube)?;
+        Some(LineZ::new(
+            self.start.lerp(self.end, t0),
+            self.start.lerp(self.end, t1),
+        ))
+    }
+}
+
+impl<T: CoordFloat> Clip<T> for LineStringZ<T> {
+    type Output = MultiLineStringZ<T>;
+
+    fn clip(&self, cube: &Cube<T>) -> Self::Output {
+        let mut pieces = Vec::new();
+        clip_line_string(&self.0, cube, &mut pieces);
+        MultiLineStringZ(pieces)
+    }
+}
+
+impl<T: CoordFloat> Clip<T> for LineStringZSlice<'_, T> {
+    type Output = MultiLineStringZ<T>;
+
+    fn clip(&self, cube: &Cube<T>) -> Self::Output {
+        let mut pieces = Vec::new();
+        clip_line_string(self.0, cube, &mut pieces);
+        MultiLineStringZ(pieces)
+    }
+}
+
+impl<T: CoordFloat, const N: usize> Clip<T> for LineStringZArray<T, N> {
+    type Output = MultiLineStringZ<T>;
+
+    fn clip(&self, cube: &Cube<T>) -> Self::Output {
+        self.as_view().clip(cube)
+    }
+}
+
+impl<T: CoordFloat> Clip<T> for MultiLineStringZ<T> {
+    type Output = MultiLineStringZ<T>;
+
+    fn clip(&self, cube: &Cube<T>) -> Self::Output {
+        let mut pieces = Vec::new();
+        for line_string in &self.0 {
+            clip_line_string(&line_string.0, cube, &mut pieces);
+        }
+        MultiLineStringZ(pieces)
+    }
+}
+
+impl<T: CoordFloat> Clip<T> for PolygonZ<T> {
+    type Output = Option<PolygonZ<T>>;
+
+    /// Returns `None` if nothing of the exterior ring is left. Interior rings that
+    /// lie completely outside the cube are dropped.
+    fn clip(&self, cube: &Cube<T>) -> Self::Output {
+        let exterior = clip_ring(&self.exterior().0, cube)?;
+        let interiors = self
+            .interiors()
+            .iter()
+            .filter_map(|ring| clip_ring(&ring.0, cube))
+            .collect();
+        Some(PolygonZ::new(exterior, interiors))
+    }
+}
+
+impl<T: CoordFloat> Clip<T> for PolygonZRef<'_, T> {
+    type Output = Option<PolygonZ<T>>;
+
+    fn clip(&self, cube: &Cube<T>) -> Self::Output {
+        let exterior = clip_ring(self.exterior().0, cube)?;
+        let interiors = self
+            .interiors()
+            .filter_map(|ring| clip_ring(ring.0, cube))
+            .collect();
+        Some(PolygonZ::new(exterior, interiors))
+    }
+}
+
+impl<T: CoordFloat> Clip<T> for MultiPolygonZ<T> {
+    type Output = MultiPolygonZ<T>;
+
+    fn clip(&self, cube: &Cube<T>) -> Self::Output {
+        MultiPolygonZ(
+            self.0
+                .iter()
+                .filter_map(|polygon| polygon.clip(cube))
+                .collect(),
+        )
+    }
+}
+
+impl<T: CoordNum, G: Clip<T>, D: Clone> Clip<T> for Tagged<G, D> {
+    type Output = Tagged<G::Output, D>;
+
+    fn clip(&self, cube: &Cube<T>) -> Self::Output {
+        Tagged::new(self.geometry.clip(cube), self.data.clone())
+    }
+}
+
+/// Clip a geometry to a horizontal band between two heights, keeping the parts
+/// from `min_z` to `max_z`, such as to pick out one floor of a building.
+///
+/// Geometries are [clipped](Clip) as to a cube without sides, with the same
+/// output: parts leaving the band are split off, with new vertices where they
+/// cross its top and bottom. A [`Geometry`] is clipped member by member,
+/// returning `None` if nothing is left. Its planar members, which lie at z = 0,
+/// are kept whole if the band includes z = 0, and dropped otherwise.
+///
+/// The bounds may be given either way round.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::ClipZRange;
+/// use geo_types_3d::{polygon_z, PolygonZ};
+///
+/// // a wall of a building 9 high, and the part of it on the second floor
+/// let wall: PolygonZ = polygon_z![
+///     (x: 0., y: 0., z: 0.),
+///     (x: 10., y: 0., z: 0.),
+///     (x: 10., y: 0., z: 9.),
+///     (x: 0., y: 0., z: 9.),
+/// ];
+/// let floor = wall.clip_z_range(3., 6.).unwrap();
+/// assert_eq!(
+///     floor,
+///     polygon_z![
+///         (x: 0., y: 0., z: 6.),
+///         (x: 0., y: 0., z: 3.),
+///         (x: 10., y: 0., z: 3.),
+///         (x: 10., y: 0., z: 6.),
+///     ]
+/// );
+/// assert!(wall.clip_z_range(10., 12.).is_none());
+/// ```
+pub trait ClipZRange<T: CoordFloat> {
+    type Output;
+
+    fn clip_z_range(&self, min_z: T, max_z: T) -> Self::Output;
+}
+
+macro_rules! impl_clip_z_range {
+    ($($type:ty),+) => {
+        $(
+            impl<T: CoordFloat> ClipZRange<T> for $type {
+                type Output = <Self as Clip<T>>::Output;
+
+                fn clip_z_range(&self, min_z: T, max_z: T) -> Self::Output {
+                    self.clip(&band(min_z, max_z))
+                }
+            }
+        )+
+    };
+}
+
+impl_clip_z_range!(
+    PointZ<T>,
+    MultiPointZ<T>,
+    LineZ<T>,
+    LineStringZ<T>,
+    LineStringZSlice<'_, T>,
+    MultiLineStringZ<T>,
+    PolygonZ<T>,
+    PolygonZRef<'_, T>,
+    MultiPolygonZ<T>
+);
+
+impl<T: CoordFloat, const N: usize> ClipZRange<T> for LineStringZArray<T, N> {
+    type Output = MultiLineStringZ<T>;
+
+    fn clip_z_range(&self, min_z: T, max_z: T) -> Self::Output {
+        self.clip(&band(min_z, max_z))
+    }
+}
+
+impl<T: CoordFloat, G: ClipZRange<T>, D: Clone> ClipZRange<T> for Tagged<G, D> {
+    type Output = Tagged<G::Output, D>;
+
+    fn clip_z_range(&self, min_z: T, max_z: T) -> Self::Output {
+        Tagged::new(self.geometry.clip_z_range(min_z, max_z), self.data.clone())
+    }
+}
+
+impl<T: CoordFloat> ClipZRange<T> for Geometry<T> {
+    type Output = Option<Geometry<T>>;
+
+    fn clip_z_range(&self, min_z: T, max_z: T) -> Self::Output {
+        let band = band(min_z, max_z);
+        let in_band = band.min().z <= T::zero() && T::zero() <= band.max().z;
+        clip_geometry(self, &band, |planar| in_band.then(|| planar.clone()))
+    }
+}
+
+/// Planar members are lifted as by [`Geometry::to_z_variant`].
+impl<T: CoordFloat> Clip<T> for Geometry<T> {
+    type Output = Option<Geometry<T>>;
+
+    fn clip(&self, cube: &Cube<T>) -> Self::Output {
+        clip_geometry(self, cube, |planar| {
+            clip_geometry(&planar.to_z_variant(T::zero()), cube, |_| None)
+        })
+    }
+}
+
+/// Clips the 3D members of `geometry` to `cube`, leaving the planar ones to
+/// `planar`, and dropping those left empty.
+fn clip_geometry<T: CoordFloat>(
+    geometry: &Geometry<T>,
+    cube: &Cube<T>,
+    planar: impl Fn(&Geometry<T>) -> Option<Geometry<T>> + Copy,
+) -> Option<Geometry<T>> {
+    let non_empty = |geometry: Geometry<T>| match &geometry {
+        Geometry::MultiPointZ(multi) if multi.0.is_empty() => None,
+        Geometry::MultiLineStringZ(multi) if multi.0.is_empty() => None,
+        Geometry::MultiPolygonZ(multi) if multi.0.is_empty() => None,
+        _ => Some(geometry),
+    };
+    match geometry {
+        Geometry::PointZ(point) => point.clip(cube).map(Geometry::PointZ),
+        Geometry::LineZ(line) => line.clip(cube).map(Geometry::LineZ),
+        Geometry::LineStringZ(line_string) => {
+            non_empty(Geometry::MultiLineStringZ(line_string.clip(cube)))
+        }
+        Geometry::PolygonZ(polygon) => polygon.clip(cube).map(Geometry::PolygonZ),
+        Geometry::MultiPointZ(multi) => non_empty(Geometry::MultiPointZ(multi.clip(cube))),
+        Geometry::MultiLineStringZ(multi) => {
+            non_empty(Geometry::MultiLineStringZ(multi.clip(cube)))
+        }
+        Geometry::MultiPolygonZ(multi) => non_empty(Geometry::MultiPolygonZ(multi.clip(cube))),
+        Geometry::GeometryCollection(collection) => {
+            let members: Vec<Geometry<T>> = collection
+                .0
+                .iter()
+                .filter_map(|member| clip_geometry(member, cube, planar))
+                .collect();
+            (!members.is_empty())
+                .then_some(Geometry::GeometryCollection(GeometryCollection(members)))
+        }
+        Geometry::Point(_)
+        | Geometry::Line(_)
+        | Geometry::LineString(_)
+        | Geometry::Polygon(_)
+        | Geometry::MultiPoint(_)
+        | Geometry::MultiLineString(_)
+        | Geometry::MultiPolygon(_)
+        | Geometry::Rect(_) => planar(geometry),
+    }
+}
+
+/// The cube spanning everything from `min_z` to `max_z`.
+fn band<T: CoordFloat>(min_z: T, max_z: T) -> Cube<T> {
+    let (lowest, highest) = (T::neg_infinity(), T::infinity());
+    Cube::new((lowest, lowest, min_z), (highest, highest, max_z))
+}
+
+/// Clips the segment from `a` to `b`, returning the parameters of the part inside
+/// the cube.
+fn liang_barsky<T: CoordFloat>(a: CoordZ<T>, b: CoordZ<T>, cube: &Cube<T>) -> Option<(T, T)> {
+    let (min, max) = (to_array(cube.min()), to_array(cube.max()));
+    let (a, b) = (to_array(a), to_array(b));
+    let (mut t0, mut t1) = (T::zero(), T::one());
+    for axis in 0..3 {
+        let delta = b[axis] - a[axis];
+        // the entering and leaving boundary, as seen from `a`
+        for (p, q) in [(-delta, a[axis] - min[axis]), (delta, max[axis] - a[axis])] {
+            if p == T::zero() {
+                if q < T::zero() {
+                    return None;
+                }
+                continue;
+            }
+            let t = q / p;
+            if p < T::zero() {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+            if t0 > t1 {
+                return None;
+            }
+        }
+    }
+    Some((t0, t1))
+}
+
+fn clip_line_string<T: CoordFloat>(
+    coords: &[CoordZ<T>],
+    cube: &Cube<T>,
+    pieces: &mut Vec<LineStringZ<T>>,
+) {
+    if let [coord] = coords {
+        if cube.contains_coord(*coord) {
+            pieces.push(LineStringZ(coords.to_vec()));
+        }
+        return;
+    }
+    let mut current: Vec<CoordZ<T>> = Vec::new();
+    // whether the previous segment reached its end inside the cube
+    let mut connected = false;
+    for line in coords.windows(2).map(|w| LineZ::new(w[0], w[1])) {
+        let Some((t0, t1)) = liang_barsky(line.start, line.end, cube) else {
+            connected = false;
+            continue;
+        };
+        if !(connected && t0 == T::zero()) {
+            if current.len() > 1 {
+                pieces.push(LineStringZ(core::mem::take(&mut current)));
+            }
+            current.clear();
+            current.push(line.start.lerp(line.end, t0));
+        }
+        if t1 > t0 {
+            current.push(line.start.lerp(line.end, t1));
+        }
+        connected = t1 == T::one();
+    }
+    if current.len() > 1 {
+        pieces.push(LineStringZ(current));
+    }
+}
+
+/// Clips a ring against each face of the cube in turn.
+fn clip_ring<T: CoordFloat>(ring: &[CoordZ<T>], cube: &Cube<T>) -> Option<LineStringZ<T>> {
+    let (min, max) = (to_array(cube.min()), to_array(cube.max()));
+    let mut coords: Vec<CoordZ<T>> = ring.to_vec();
+    if coords.len() > 1 && coords.first() == coords.last() {
+        coords.pop();
+    }
+    for axis in 0..3 {
+        for (bound, keep_above) in [(min[axis], true), (max[axis], false)] {
+            let inside = |coord: CoordZ<T>| {
+                let value = to_array(coord)[axis];
+                if keep_above {
+                    value >= bound
+                } else {
+                    value <= bound
+                }
+            };
+            let mut clipped = Vec::with_capacity(coords.len() + 1);
+            for (i, &current) in coords.iter().enumerate() {
+                let previous = coords[(i + coords.len() - 1) % coords.len()];
+                match (inside(previous), inside(current)) {
+                    (true, true) => clipped.push(current),
+                    (true, false) => clipped.push(crossing(previous, current, axis, bound)),
+                    (false, true) => {
+                        clipped.push(crossing(previous, current, axis, bound));
+                        clipped.push(current);
+                    }
+                    (false, false) => {}
+                }
+            }
+            coords = clipped;
+            if coords.is_empty() {
+                return None;
+            }
+        }
+    }
+    (coords.len() >= 3).then(|| {
+        let mut ring = LineStringZ(coords);
+        ring.close();
+        ring
+    })
+}
+
+/// The point where the segment from `a` to `b` crosses `axis == bound`.
+fn crossing<T: CoordFloat>(a: CoordZ<T>, b: CoordZ<T>, axis: usize, bound: T) -> CoordZ<T> {
+    let (from, to) = (to_array(a)[axis], to_array(b)[axis]);
+    let mut coord = a.lerp(b, (bound - from) / (to - from));
+    // avoid rounding the crossing to just outside the face
+    match axis {
+        0 => coord.x = bound,
+        1 => coord.y = bound,
+        _ => coord.z = bound,
+    }
+    coord
+}
+
+fn to_array<T: CoordFloat>(coord: CoordZ<T>) -> [T; 3] {
+    [coord.x, coord.y, coord.z]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    fn unit_cube() -> Cube<f64> {
+        Cube::new((0., 0., 0.), (1., 1., 1.))
+    }
+
+    #[test]
+    fn lines() {
+        let cube = unit_cube();
+        let diagonal = LineZ::new((-1., -1., -1.), (2., 2., 2.));
+        assert_eq!(
+            diagonal.clip(&cube),
+            Some(LineZ::new((0., 0., 0.), (1., 1., 1.)))
+        );
+
+        let above = LineZ::new((-1., 0.5, 2.), (2., 0.5, 2.));
+        assert_eq!(above.clip(&cube), None);
+
+        let inside = LineZ::new((0.25, 0.5, 0.5), (0.75, 0.5, 0.5));
+        assert_eq!(inside.clip(&cube), Some(inside));
+
+        // entering through the top face
+        let falling = LineZ::new((0.5, 0.5, 3.), (0.5, 0.5, 0.5));
+        assert_eq!(
+            falling.clip(&cube),
+            Some(LineZ::new((0.5, 0.5, 1.), (0.5, 0.5, 0.5)))
+        );
+    }
+
+    #[test]
+    fn line_string_is_split() {
+        let cube = unit_cube();
+        // dips into the cube twice
+        let line_string = line_string_z![
+            (x: -1., y: 0.5, z: 0.5),
+            (x: 0.5, y: 0.5, z: 0.5),
+            (x: 0.5, y: 0.5, z: 2.),
+            (x: 0.75, y: 0.5, z: 2.),
+            (x: 0.75, y: 0.5, z: 0.25),
+        ];
+        let clipped = line_string.clip(&cube);
+        assert_eq!(
+            clipped.0,
+            vec![
+                line_string_z![
+                    (x: 0., y: 0.5, z: 0.5),
+                    (x: 0.5, y: 0.5, z: 0.5),
+                    (x: 0.5, y: 0.5, z: 1.),
+                ],
+                line_string_z![
+                    (x: 0.75, y: 0.5, z: 1.),
+                    (x: 0.75, y: 0.5, z: 0.25),
+                ],
+            ]
+        );
+
+        let outside = line_string_z![(x: 2., y: 2., z: 2.), (x: 3., y: 3., z: 3.)];
+        assert!(outside.clip(&cube).0.is_empty());
+    }
+
+    #[test]
+    fn polygon_on_a_slope() {
+        let cube = unit_cube();
+        // a square tilted so that it leaves the cube through the top face
+        let polygon = polygon_z![
+            (x: -1., y: -1., z: 0.),
+            (x: 2., y: -1., z: 0.),
+            (x: 2., y: 2., z: 1.5),
+            (x: -1., y: 2., z: 1.5),
+        ];
+        let clipped = polygon.clip(&cube).unwrap();
+        let exterior = clipped.exterior();
+        assert!(exterior.is_closed());
+        for coord in exterior.coords() {
+            assert!(cube.contains_coord(*coord));
+            assert_relative_eq!(coord.z, (coord.y + 1.) / 2.);
+        }
+        // the plane reaches the top face at y = 1
+        assert!(exterior
+            .coords()
+            .any(|coord| coord.y == 1. && coord.z == 1.));
+        assert!(exterior.coords().all(|coord| coord.y <= 1.));
+    }
+
+    #[test]
+    fn polygon_with_hole() {
+        let cube = Cube::new((0., 0., -1.), (4., 4., 1.));
+        let polygon = polygon_z![
+            exterior: [
+                (x: -2., y: -2., z: 0.),
+                (x: 6., y: -2., z: 0.),
+                (x: 6., y: 6., z: 0.),
+                (x: -2., y: 6., z: 0.),
+            ],
+            interiors: [
+                [
+                    (x: 1., y: 1., z: 0.),
+                    (x: 2., y: 1., z: 0.),
+                    (x: 2., y: 2., z: 0.),
+                    (x: 1., y: 2., z: 0.),
+                ],
+                [
+                    (x: 5., y: 5., z: 0.),
+                    (x: 5.5, y: 5., z: 0.),
+                    (x: 5.5, y: 5.5, z: 0.),
+                ],
+            ],
+        ];
+        let clipped = polygon.clip(&cube).unwrap();
+        assert_eq!(clipped.interiors().len(), 1);
+        assert_eq!(clipped.interiors()[0], polygon.interiors()[0]);
+        assert_eq!(clipped.exterior().0.len(), 5);
+
+        let elsewhere = Cube::new((10., 10., 10.), (11., 11., 11.));
+        assert!(polygon.clip(&elsewhere).is_none());
+        assert!(MultiPolygonZ(vec![polygon]).clip(&elsewhere).0.is_empty());
+    }
+
+    #[test]
+    fn floors() {
+        // a stairwell climbing 3 per floor, cut into the second floor
+        let stairs = line_string_z![
+            (x: 0., y: 0., z: 0.),
+            (x: 6., y: 0., z: 6.),
+            (x: 6., y: 2., z: 6.),
+            (x: 0., y: 2., z: 12.),
+        ];
+        assert_eq!(
+            stairs.clip_z_range(6., 3.).0,
+            vec![line_string_z![
+                (x: 3., y: 0., z: 3.),
+                (x: 6., y: 0., z: 6.),
+                (x: 6., y: 2., z: 6.),
+            ]]
+        );
+
+        let building = Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::LineStringZ(stairs),
+            Geometry::PointZ(PointZ::new(1., 1., 10.)),
+            Geometry::Point(geo_types::Point::new(5., 5.)),
+        ]));
+        let Some(Geometry::GeometryCollection(second)) = building.clip_z_range(3., 6.) else {
+            panic!("the stairs cross the second floor");
+        };
+        assert_eq!(second.0.len(), 1);
+        let Some(Geometry::GeometryCollection(ground)) = building.clip_z_range(-1., 1.) else {
+            panic!("the stairs start on the ground floor");
+        };
+        assert_eq!(ground.0.len(), 2);
+        assert!(matches!(ground.0[1], Geometry::Point(_)));
+        assert_eq!(building.clip_z_range(20., 30.), None);
+    }
+}
@@ -0,0 +1,275 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algorithm::linalg::distance_2;
+use crate::index::KdTree;
+use crate::{CoordFloat, CoordZ, MultiPointZ, PointZ};
+
+/// The most rounds of assigning and updating [`MultiPointZ::kmeans`] runs.
+pub const MAX_ITERATIONS: usize = 100;
+
+/// The result of clustering a [`MultiPointZ`]: the cluster of each point, and the
+/// centroid of each cluster.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clusters<T: CoordFloat = f64> {
+    /// The index into `centroids` of the cluster of each point, in the order of the
+    /// points, or `None` for noise points belonging to no cluster.
+    pub labels: Vec<Option<usize>>,
+    /// The mean of the points in each cluster.
+    pub centroids: Vec<CoordZ<T>>,
+}
+
+impl<T: CoordFloat> Clusters<T> {
+    /// The number of clusters.
+    pub fn len(&self) -> usize {
+        self.centroids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.centroids.is_empty()
+    }
+
+    /// The indices of the points in cluster `label`.
+    pub fn members(&self, label: usize) -> impl Iterator<Item = usize> + '_ {
+        self.labels
+            .iter()
+            .enumerate()
+            .filter(move |(_, found)| **found == Some(label))
+            .map(|(id, _)| id)
+    }
+}
+
+impl<T: CoordFloat> MultiPointZ<T> {
+    /// Splits the points into `k` clusters by k-means, each point joining the cluster
+    /// with the nearest centroid.
+    ///
+    /// The first centroid is the first point, and each further one the point
+    /// farthest from the centroids already chosen, so the same points always give
+    /// the same clusters. Rounds of assigning points and moving centroids then run
+    /// until no point changes cluster, or for at most [`MAX_ITERATIONS`] rounds. A
+    /// cluster left without points keeps its centroid. There are fewer than `k`
+    /// clusters if there are fewer than `k` points.
+    ///
+    /// # Panics
+    ///
+    /// If `k` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{MultiPointZ, PointZ};
+    ///
+    /// // two tight groups of points, far apart
+    /// let points: MultiPointZ = (0..20)
+    ///     .map(|i| {
+    ///         let offset = if i < 10 { 0. } else { 100. };
+    ///         PointZ::new(offset + (i % 3) as f64, (i % 2) as f64, offset)
+    ///     })
+    ///     .collect();
+    /// let clusters = points.kmeans(2);
+    /// assert_eq!(clusters.len(), 2);
+    /// assert!(clusters.labels[..10].iter().all(|&label| label == clusters.labels[0]));
+    /// assert!(clusters.labels[10..].iter().all(|&label| label == clusters.labels[10]));
+    /// assert_ne!(clusters.labels[0], clusters.labels[10]);
+    /// ```
+    pub fn kmeans(&self, k: usize) -> Clusters<T> {
+        assert!(k > 0, "k must be positive");
+        let coords: Vec<CoordZ<T>> = self.0.iter().map(|point| point.0).collect();
+        let mut centroids = farthest_first(&coords, k);
+        let mut labels: Vec<Option<usize>> = vec![None; coords.len()];
+        for _ in 0..MAX_ITERATIONS {
+            let tree = KdTree::new(centroids.iter().map(|&c| PointZ(c)));
+            let mut changed = false;
+            for (label, &coord) in labels.iter_mut().zip(&coords) {
+                let nearest = tree.nearest(&PointZ(coord)).map(|(id, _)| id);
+                changed |= *label != nearest;
+                *label = nearest;
+            }
+            if !changed {
+                break;
+            }
+            let mut sums = vec![(CoordZ::zero(), 0usize); centroids.len()];
+            for (label, &coord) in labels.iter().zip(&coords) {
+                let sum = &mut sums[label.expect("every point has a cluster")];
+                *sum = (sum.0 + coord, sum.1 + 1);
+            }
+            for (centroid, (sum, count)) in centroids.iter_mut().zip(sums) {
+                if count > 0 {
+                    *centroid = sum / T::from(count).unwrap();
+                }
+            }
+        }
+        Clusters { labels, centroids }
+    }
+
+    /// Groups the points by density with DBSCAN: points with at least `min_points`
+    /// points within `radius` of them, counting themselves, are core points, and
+    /// each cluster is a set of core points within `radius` of one another together
+    /// with the other points within `radius` of them.
+    ///
+    /// Unlike [`MultiPointZ::kmeans`], the number of clusters needn't be known in
+    /// advance and clusters may have any shape. Points in no cluster are noise, and
+    /// have no label. Clusters are numbered in the order of their first points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{MultiPointZ, PointZ};
+    ///
+    /// // two rows of points, and a stray point between them
+    /// let mut points: Vec<PointZ> = (0..10).map(|i| PointZ::new(i as f64, 0., 0.)).collect();
+    /// points.extend((0..10).map(|i| PointZ::new(i as f64, 10., 0.)));
+    /// points.push(PointZ::new(5., 5., 0.));
+    /// let points = MultiPointZ::new(points);
+    ///
+    /// let clusters = points.dbscan(1.5, 3);
+    /// assert_eq!(clusters.len(), 2);
+    /// assert_eq!(clusters.labels[3], Some(0));
+    /// assert_eq!(clusters.labels[13], Some(1));
+    /// assert_eq!(clusters.labels[20], None);
+    /// assert_eq!(clusters.centroids[1], PointZ::new(4.5, 10., 0.).0);
+    /// ```
+    pub fn dbscan(&self, radius: T, min_points: usize) -> Clusters<T> {
+        let tree = self.kdtree();
+        let mut labels: Vec<Option<usize>> = vec![None; self.0.len()];
+        let mut visited = vec![false; self.0.len()];
+        let mut centroids = Vec::new();
+        let mut stack = Vec::new();
+        for start in 0..self.0.len() {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let neighbors = tree.within_radius(&self.0[start], radius);
+            if neighbors.len() < min_points {
+                continue;
+            }
+            let label = centroids.len();
+            let mut sum = CoordZ::zero();
+            let mut count = 0usize;
+            labels[start] = Some(label);
+            stack.extend(neighbors.into_iter().map(|(id, _)| id));
+            while let Some(id) = stack.pop() {
+                if labels[id].is_none() {
+                    labels[id] = Some(label);
+                }
+                if visited[id] {
+                    continue;
+                }
+                visited[id] = true;
+                let neighbors = tree.within_radius(&self.0[id], radius);
+                if neighbors.len() >= min_points {
+                    stack.extend(neighbors.into_iter().map(|(id, _)| id));
+                }
+            }
+            for (id, found) in labels.iter().enumerate() {
+                if *found == Some(label) {
+                    sum += self.0[id].0;
+                    count += 1;
+                }
+            }
+            centroids.push(sum / T::from(count).unwrap());
+        }
+        Clusters { labels, centroids }
+    }
+}
+
+/// Up to `k` distinct coordinates, each the farthest from those chosen before it.
+fn farthest_first<T: CoordFloat>(coords: &[CoordZ<T>], k: usize) -> Vec<CoordZ<T>> {
+    let mut chosen = Vec::with_capacity(k);
+    let Some(&first) = coords.first() else {
+        return chosen;
+    };
+    chosen.push(first);
+    let mut distances: Vec<T> = coords.iter().map(|&c| distance_2(c, first)).collect();
+    while chosen.len() < k {
+        let Some((index, &farthest)) = distances
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(core::cmp::Ordering::Equal))
+        else {
+            break;
+        };
+        if farthest <= T::zero() {
+            break;
+        }
+        let next = coords[index];
+        chosen.push(next);
+        for (distance, &coord) in distances.iter_mut().zip(coords) {
+            *distance = distance.min(distance_2(coord, next));
+        }
+    }
+    chosen
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn blobs() -> MultiPointZ {
+        // three groups of 50 points, spread around centres far apart
+        let centres = [(0., 0., 0.), (20., 0., 5.), (0., 30., -5.)];
+        (0..150)
+            .map(|i| {
+                let (x, y, z) = centres[i / 50];
+                let f = |k: usize| ((i * k) % 17) as f64 / 17. - 0.5;
+                PointZ::new(x + f(3), y + f(5), z + f(7))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn kmeans() {
+        let points = blobs();
+        let clusters = points.kmeans(3);
+        assert_eq!(clusters.len(), 3);
+        for group in clusters.labels.chunks(50) {
+            assert!(group.iter().all(|&label| label == group[0]));
+        }
+        let mut sorted = clusters.centroids.clone();
+        sorted.sort_by(|a, b| (a.x + a.y).partial_cmp(&(b.x + b.y)).unwrap());
+        assert_relative_eq!(
+            sorted[0],
+            CoordZ {
+                x: 0.,
+                y: 0.,
+                z: 0.
+            },
+            epsilon = 0.1
+        );
+        assert_relative_eq!(
+            sorted[2],
+            CoordZ {
+                x: 0.,
+                y: 30.,
+                z: -5.
+            },
+            epsilon = 0.1
+        );
+
+        // fewer distinct points than clusters asked for
+        let pair = MultiPointZ::new(vec![PointZ::new(1., 1., 1.); 4]);
+        let clusters = pair.kmeans(3);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters.labels, vec![Some(0); 4]);
+        assert!(MultiPointZ::<f64>::empty().kmeans(2).is_empty());
+    }
+
+    #[test]
+    fn dbscan() {
+        let mut points = blobs();
+        points.0.push(PointZ::new(10., 10., 10.));
+        let clusters = points.dbscan(1., 4);
+        assert_eq!(clusters.len(), 3);
+        assert_eq!(clusters.labels[150], None);
+        for (label, group) in clusters.labels[..150].chunks(50).enumerate() {
+            assert!(group.iter().all(|&found| found == Some(label)));
+            assert_eq!(clusters.members(label).count(), 50);
+        }
+        // too sparse for any cluster
+        let row: MultiPointZ = (0..10).map(|i| PointZ::new(i as f64, 0., 0.)).collect();
+        let sparse = row.dbscan(0.5, 2);
+        assert!(sparse.is_empty());
+        assert_eq!(sparse.labels, vec![None; 10]);
+    }
+}
@@ -0,0 +1,439 @@
+use alloc::collections::{BTreeMap, BinaryHeap};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::algorithm::linalg::{self, cross, dot, norm};
+use crate::{CoordFloat, CoordZ, TinZ};
+
+/// How much more it costs to move a vertex off the boundary of the surface than
+/// off the surface itself, so that the outline of a tile is kept.
+const BOUNDARY_WEIGHT: f64 = 1000.;
+
+impl<T: CoordFloat> TinZ<T> {
+    /// A simplified copy of the surface with at most `target_triangle_count`
+    /// triangles where it can get there, such as for the coarser levels of a
+    /// level-of-detail pyramid.
+    ///
+    /// Edges are collapsed one at a time, cheapest first, into the point that
+    /// strays least from the planes of the triangles they replace, measured by
+    /// quadric error metrics (Garland and Heckbert). Flat areas go first and
+    /// sharp features last. The outline of the surface is kept as far as it can
+    /// be, so that neighbouring tiles still meet. Collapses that would fold a
+    /// triangle over or tear the surface are skipped, so a surface may keep more
+    /// triangles than asked for.
+    ///
+    /// Vertices left without a triangle are dropped, and the others keep their
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{CoordZ, TinZ};
+    ///
+    /// // a flat 10 × 10 grid, which two triangles cover as well as 200
+    /// let mut grid: TinZ = TinZ::empty();
+    /// for j in 0..=10 {
+    ///     for i in 0..=10 {
+    ///         grid.push_vertex(CoordZ::from((i as f64, j as f64, 5.)));
+    ///     }
+    /// }
+    /// for j in 0..10 {
+    ///     for i in 0..10 {
+    ///         let corner = j * 11 + i;
+    ///         grid.push([corner, corner + 1, corner + 12]);
+    ///         grid.push([corner, corner + 12, corner + 11]);
+    ///     }
+    /// }
+    ///
+    /// let coarse = grid.decimate(20);
+    /// assert!(coarse.len() <= 20);
+    /// assert!(coarse.vertices().iter().all(|v| v.z == 5.));
+    /// ```
+    pub fn decimate(&self, target_triangle_count: usize) -> Self {
+        let mut mesh = Decimation::new(self);
+        mesh.run(target_triangle_count);
+        mesh.into_tin()
+    }
+}
+
+/// The error of a point from a set of planes, as the symmetric matrix `Q` with
+/// `[x, y, z, 1] Q [x, y, z, 1]ᵀ` the weighted sum of its squared distances to
+/// them.
+type Quadric<T> = [[T; 4]; 4];
+
+struct Decimation<T: CoordFloat> {
+    vertices: Vec<CoordZ<T>>,
+    quadrics: Vec<Quadric<T>>,
+    /// Bumped whenever a vertex moves or goes, so queued collapses can be checked
+    /// for being out of date.
+    stamps: Vec<usize>,
+    removed: Vec<bool>,
+    triangles: Vec<Option<[usize; 3]>>,
+    /// The live triangles at each vertex.
+    incident: Vec<Vec<usize>>,
+    count: usize,
+    queue: BinaryHeap<Collapse<T>>,
+}
+
+impl<T: CoordFloat> Decimation<T> {
+    fn new(tin: &TinZ<T>) -> Self {
+        let vertices = tin.vertices().to_vec();
+        let mut quadrics = vec![[[T::zero(); 4]; 4]; vertices.len()];
+        let mut incident = vec![Vec::new(); vertices.len()];
+        // the triangles at each edge, to find the boundary
+        let mut edges: BTreeMap<[usize; 2], Vec<usize>> = BTreeMap::new();
+        for (index, &triangle) in tin.triangle_indices().iter().enumerate() {
+            let [a, b, c] = triangle.map(|i| vertices[i]);
+            let normal = cross(b - a, c - a);
+            let length = norm(normal);
+            if length > T::zero() {
+                let plane = plane(normal / length, a);
+                // weighted by area
+                let weight = length / (T::one() + T::one());
+                for corner in triangle {
+                    add(&mut quadrics[corner], &plane, weight);
+                }
+            }
+            for corner in triangle {
+                incident[corner].push(index);
+            }
+            for [p, q] in sides(triangle) {
+                edges.entry(sorted(p, q)).or_default().push(index);
+            }
+        }
+
+        let boundary_weight = T::from(BOUNDARY_WEIGHT).unwrap();
+        for (&[p, q], triangles) in &edges {
+            let &[triangle] = triangles.as_slice() else {
+                continue;
+            };
+            let [a, b, c] = tin.triangle_indices()[triangle].map(|i| vertices[i]);
+            let side = vertices[q] - vertices[p];
+            // the plane through the edge, at right angles to its triangle
+            let across = cross(side, cross(b - a, c - a));
+            let length = norm(across);
+            if length > T::zero() {
+                let plane = plane(across / length, vertices[p]);
+                let weight = boundary_weight * dot(side, side);
+                add(&mut quadrics[p], &plane, weight);
+                add(&mut quadrics[q], &plane, weight);
+            }
+        }
+
+        let mut mesh = Self {
+            stamps: vec![0; vertices.len()],
+            removed: vec![false; vertices.len()],
+            triangles: tin.triangle_indices().iter().copied().map(Some).collect(),
+            count: tin.len(),
+            vertices,
+            quadrics,
+            incident,
+            queue: BinaryHeap::new(),
+        };
+        for &[p, q] in edges.keys() {
+            mesh.enqueue(p, q);
+        }
+        mesh
+    }
+
+    fn run(&mut self, target: usize) {
+        while self.count > target {
+            let Some(collapse) = self.queue.pop() else {
+                break;
+            };
+            let [p, q] = collapse.edge;
+            let current = !self.removed[p]
+                && !self.removed[q]
+                && collapse.stamps == [self.stamps[p], self.stamps[q]];
+            if current && self.can_collapse(p, q, collapse.target) {
+                self.collapse(p, q, collapse.target);
+            }
+        }
+    }
+
+    /// Queues the collapse of the edge between `p` and `q` into the point of least
+    /// error.
+    fn enqueue(&mut self, p: usize, q: usize) {
+        let quadric = sum(&self.quadrics[p], &self.quadrics[q]);
+        let target = optimum(&quadric).unwrap_or_else(|| {
+            let (a, b) = (self.vertices[p], self.vertices[q]);
+            let middle = (a + b) / (T::one() + T::one());
+            [a, b, middle]
+                .into_iter()
+                .min_by(|&u, &v| {
+                    error(&quadric, u)
+                        .partial_cmp(&error(&quadric, v))
+                        .unwrap_or(Ordering::Equal)
+                })
+                .unwrap()
+        });
+        self.queue.push(Collapse {
+            cost: error(&quadric, target),
+            edge: [p, q],
+            stamps: [self.stamps[p], self.stamps[q]],
+            target,
+        });
+    }
+
+    /// Whether merging `q` into `p` at `target` leaves the surface whole and
+    /// turns no triangle over.
+    fn can_collapse(&self, p: usize, q: usize, target: CoordZ<T>) -> bool {
+        // the vertices next to both ends must be the far corners of the triangles
+        // on the edge, or the collapse would pinch the surface
+        let shared = self.incident[p]
+            .iter()
+            .filter(|&&t| self.corners(t).contains(&q))
+            .count();
+        let (near_p, near_q) = (self.neighbours(p), self.neighbours(q));
+        let common = near_p
+            .iter()
+            .filter(|v| near_q.binary_search(v).is_ok())
+            .count();
+        if common != shared {
+            return false;
+        }
+
+        let moved = |v: usize| {
+            if v == p || v == q {
+                target
+            } else {
+                self.vertices[v]
+            }
+        };
+        self.incident[p]
+            .iter()
+            .chain(&self.incident[q])
+            .map(|&t| self.corners(t))
+            .filter(|corners| !(corners.contains(&p) && corners.contains(&q)))
+            .all(|corners| {
+                let [a, b, c] = corners.map(|v| self.vertices[v]);
+                let before = cross(b - a, c - a);
+                let [a, b, c] = corners.map(moved);
+                let after = cross(b - a, c - a);
+                dot(before, after) > T::zero() || dot(before, before) == T::zero()
+            })
+    }
+
+    /// Merges `q` into `p`, which moves to `target`.
+    fn collapse(&mut self, p: usize, q: usize, target: CoordZ<T>) {
+        for t in core::mem::take(&mut self.incident[q]) {
+            let corners = self.corners(t);
+            if corners.contains(&p) {
+                self.triangles[t] = None;
+                self.count -= 1;
+                for v in corners {
+                    self.incident[v].retain(|&other| other != t);
+                }
+            } else {
+                self.triangles[t] = Some(corners.map(|v| if v == q { p } else { v }));
+                self.incident[p].push(t);
+            }
+        }
+        self.vertices[p] = target;
+        self.quadrics[p] = sum(&self.quadrics[p], &self.quadrics[q]);
+        self.removed[q] = true;
+        self.stamps[p] += 1;
+        self.stamps[q] += 1;
+        for v in self.neighbours(p) {
+            self.enqueue(p, v);
+        }
+    }
+
+    fn corners(&self, triangle: usize) -> [usize; 3] {
+        self.triangles[triangle].expect("only live triangles are incident")
+    }
+
+    /// The vertices sharing a triangle with `v`, sorted.
+    fn neighbours(&self, v: usize) -> Vec<usize> {
+        let mut neighbours: Vec<usize> = self.incident[v]
+            .iter()
+            .flat_map(|&t| self.corners(t))
+            .filter(|&other| other != v)
+            .collect();
+        neighbours.sort_unstable();
+        neighbours.dedup();
+        neighbours
+    }
+
+    fn into_tin(self) -> TinZ<T> {
+        let mut index = vec![usize::MAX; self.vertices.len()];
+        let mut vertices = Vec::new();
+        for (v, triangles) in self.incident.iter().enumerate() {
+            if !triangles.is_empty() {
+                index[v] = vertices.len();
+                vertices.push(self.vertices[v]);
+            }
+        }
+        let triangles = self
+            .triangles
+            .into_iter()
+            .flatten()
+            .map(|corners| corners.map(|v| index[v]))
+            .collect();
+        TinZ::new(vertices, triangles)
+    }
+}
+
+/// A queued edge collapse, ordered so that the cheapest is popped first.
+struct Collapse<T: CoordFloat> {
+    cost: T,
+    edge: [usize; 2],
+    stamps: [usize; 2],
+    target: CoordZ<T>,
+}
+
+impl<T: CoordFloat> Ord for Collapse<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed, as `BinaryHeap` is a max-heap; ties go to the lowest edge, so
+        // that the result doesn't depend on the order of the queue
+        other
+            .cost
+            .partial_cmp(&self.cost)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.edge.cmp(&self.edge))
+    }
+}
+
+impl<T: CoordFloat> PartialOrd for Collapse<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: CoordFloat> PartialEq for Collapse<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: CoordFloat> Eq for Collapse<T> {}
+
+/// The quadric of the plane with unit `normal` through `point`.
+fn plane<T: CoordFloat>(normal: CoordZ<T>, point: CoordZ<T>) -> Quadric<T> {
+    let p = [normal.x, normal.y, normal.z, -dot(normal, point)];
+    core::array::from_fn(|i| core::array::from_fn(|j| p[i] * p[j]))
+}
+
+fn add<T: CoordFloat>(quadric: &mut Quadric<T>, other: &Quadric<T>, weight: T) {
+    for (row, other) in quadric.iter_mut().zip(other) {
+        for (value, &other) in row.iter_mut().zip(other) {
+            *value = *value + weight * other;
+        }
+    }
+}
+
+fn sum<T: CoordFloat>(a: &Quadric<T>, b: &Quadric<T>) -> Quadric<T> {
+    let mut sum = *a;
+    add(&mut sum, b, T::one());
+    sum
+}
+
+fn error<T: CoordFloat>(quadric: &Quadric<T>, point: CoordZ<T>) -> T {
+    let p = [point.x, point.y, point.z, T::one()];
+    (0..4).fold(T::zero(), |error, i| {
+        (0..4).fold(error, |error, j| error + p[i] * quadric[i][j] * p[j])
+    })
+}
+
+/// The point of least error, or `None` if there is no single one, as along a
+/// straight crease or across a flat area.
+fn optimum<T: CoordFloat>(quadric: &Quadric<T>) -> Option<CoordZ<T>> {
+    let a = core::array::from_fn(|i| core::array::from_fn(|j| quadric[i][j]));
+    let b = core::array::from_fn(|i| -quadric[i][3]);
+    let [x, y, z] = linalg::solve(a, b)?;
+    Some(CoordZ { x, y, z })
+}
+
+/// The sides of a triangle, each as the indices of its ends.
+fn sides([a, b, c]: [usize; 3]) -> [[usize; 2]; 3] {
+    [[a, b], [b, c], [c, a]]
+}
+
+fn sorted(p: usize, q: usize) -> [usize; 2] {
+    [p.min(q), p.max(q)]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A `size × size` grid of square cells over `height`, two triangles each.
+    fn grid(size: usize, height: impl Fn(f64, f64) -> f64) -> TinZ<f64> {
+        let mut tin = TinZ::empty();
+        for j in 0..=size {
+            for i in 0..=size {
+                let (x, y) = (i as f64, j as f64);
+                tin.push_vertex(CoordZ {
+                    x,
+                    y,
+                    z: height(x, y),
+                });
+            }
+        }
+        let row = size + 1;
+        for j in 0..size {
+            for i in 0..size {
+                let corner = j * row + i;
+                tin.push([corner, corner + 1, corner + row + 1]);
+                tin.push([corner, corner + row + 1, corner + row]);
+            }
+        }
+        tin
+    }
+
+    fn area(tin: &TinZ<f64>) -> f64 {
+        tin.triangles()
+            .map(|t| cross(t.1 - t.0, t.2 - t.0).z / 2.)
+            .sum()
+    }
+
+    #[test]
+    fn flat() {
+        let tin = grid(8, |_, _| 2.);
+        let coarse = tin.decimate(2);
+        assert_eq!(coarse.len(), 2);
+        assert_eq!(coarse.vertices().len(), 4);
+        // the outline is kept, and nothing is turned over
+        assert!((area(&coarse) - 64.).abs() < 1e-9);
+        assert!(coarse
+            .triangles()
+            .all(|t| cross(t.1 - t.0, t.2 - t.0).z > 0.));
+        for corner in [(0., 0.), (8., 0.), (0., 8.), (8., 8.)] {
+            assert!(coarse
+                .vertices()
+                .iter()
+                .any(|v| (v.x, v.y, v.z) == (corner.0, corner.1, 2.)));
+        }
+
+        assert_eq!(tin.decimate(1000), tin);
+        assert!(TinZ::<f64>::empty().decimate(0).is_empty());
+    }
+
+    #[test]
+    fn ridge() {
+        // a roof, rising to a ridge along x = 5
+        let tin = grid(10, |x, _| 5. - (x - 5f64).abs());
+        let coarse = tin.decimate(8);
+        assert!(coarse.len() <= 8);
+        assert!((area(&coarse) - 100.).abs() < 1e-9);
+        // both slopes are planes, so the ridge survives
+        for vertex in coarse.vertices() {
+            assert!((vertex.z - (5. - (vertex.x - 5.).abs())).abs() < 1e-9);
+        }
+        assert!(coarse.vertices().iter().any(|v| (v.z - 5.).abs() < 1e-9));
+
+        // a bumpy surface keeps close to the original
+        let height = |x: f64, y: f64| (x / 2.).sin() * (y / 3.).cos();
+        let bumpy = grid(16, height);
+        let coarse = bumpy.decimate(200);
+        assert!(coarse.len() <= 200);
+        assert!((area(&coarse) - 256.).abs() < 1e-2);
+        let worst = coarse
+            .vertices()
+            .iter()
+            .map(|v| (v.z - height(v.x, v.y)).abs())
+            .fold(0., f64::max);
+        assert!(worst < 0.2, "{worst}");
+    }
+}
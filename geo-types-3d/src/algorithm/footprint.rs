@@ -0,0 +1,291 @@
+use alloc::vec::Vec;
+
+use geo::algorithm::bool_ops::{unary_union, BoolOpsNum};
+use geo::orient::{Direction, Orient};
+use geo::{ConvexHull, GeoFloat, Winding};
+use geo_types::{Coord, MultiPoint, MultiPolygon, Polygon, Rect};
+
+use super::xy::polygon_xy;
+use super::BoundingCube;
+use crate::{
+    CoordVisitor, CoordZ, Geometry, GeometryCollection, GeometryVisitor, LineStringZ, LineZ,
+    MultiLineStringZ, MultiPointZ, MultiPolygonZ, Obb, PointZ, PolygonZ,
+};
+
+/// The smallest rectangle around a geometry in XY, in any orientation, extruded
+/// through the geometry's range of heights, such as to find the orientation and
+/// size of a building from its walls and roof.
+///
+/// The rectangle is found in the XY projection with rotating calipers: one of its
+/// sides lies along an edge of the convex hull of the coordinates. It is returned
+/// as an [`Obb`] standing upright, whose first axis runs along the rectangle's
+/// longer side and whose third axis is z, reaching from the lowest to the highest
+/// coordinate of the geometry, as by [`BoundingCube`]. Members of collections
+/// without z are taken to lie at z = 0. Geometries with all their coordinates on
+/// a line, or at a single point, give boxes with no width, and empty geometries
+/// give `None`.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::MinimumRotatedRectZ;
+/// use geo_types_3d::{wkt, MultiPointZ};
+///
+/// // the corners of a shed 6 by 2, turned 30°, with a ridge 4 high
+/// let shed: MultiPointZ = wkt! {
+///     MULTIPOINT Z(
+///         (0. 0. 0.),(5.196152422706632 3. 0.),(4.196152422706632 4.732050807568877 0.),
+///         (-1. 1.7320508075688772 0.),(2.098076211353316 2.366025403784439 4.)
+///     )
+/// };
+/// let obb = shed.minimum_rotated_rect().unwrap();
+///
+/// let [along, _, up] = obb.axes();
+/// assert!((along.y.atan2(along.x).to_degrees().rem_euclid(180.) - 30.).abs() < 1e-9);
+/// assert_eq!(up.z, 1.);
+/// assert!((obb.half_extents.x - 3.).abs() < 1e-9);
+/// assert!((obb.half_extents.y - 1.).abs() < 1e-9);
+/// assert_eq!((obb.center.z, obb.half_extents.z), (2., 2.));
+/// ```
+pub trait MinimumRotatedRectZ<T: GeoFloat>: Clone + Into<Geometry<T>> {
+    /// The minimum rotated rectangle in XY, through the range of z, or `None` if
+    /// the geometry is empty.
+    fn minimum_rotated_rect(&self) -> Option<Obb<T>> {
+        let geometry: Geometry<T> = self.clone().into();
+        let range = geometry.bounding_cube()?;
+        let mut points = XyPoints(Vec::new());
+        geometry.accept_coords(&mut points);
+        let hull = MultiPoint::from(points.0).convex_hull();
+        let ring = &hull.exterior().0;
+
+        let mut directions: Vec<Coord<T>> = ring
+            .windows(2)
+            .filter_map(|pair| {
+                let d = pair[1] - pair[0];
+                let length = d.x.hypot(d.y);
+                (length > T::zero()).then(|| d / length)
+            })
+            .collect();
+        if directions.is_empty() {
+            directions.push(Coord {
+                x: T::one(),
+                y: T::zero(),
+            });
+        }
+        // the extents of the hull along a direction and across it
+        let extents = |u: Coord<T>| {
+            let mut low = [T::infinity(); 2];
+            let mut high = [T::neg_infinity(); 2];
+            for c in ring {
+                let local = [c.x * u.x + c.y * u.y, c.y * u.x - c.x * u.y];
+                for i in 0..2 {
+                    low[i] = low[i].min(local[i]);
+                    high[i] = high[i].max(local[i]);
+                }
+            }
+            (low, high)
+        };
+        let mut best: Option<(T, Coord<T>)> = None;
+        for u in directions {
+            let (low, high) = extents(u);
+            let area = (high[0] - low[0]) * (high[1] - low[1]);
+            if best.is_none_or(|best| area < best.0) {
+                best = Some((area, u));
+            }
+        }
+        let (_, mut u) = best?;
+        let (mut low, mut high) = extents(u);
+        // along the longer side, turning a quarter to keep the axes right-handed
+        if high[1] - low[1] > high[0] - low[0] {
+            u = Coord { x: -u.y, y: u.x };
+            (low, high) = ([low[1], -high[0]], [high[1], -low[0]]);
+        }
+
+        let two = T::one() + T::one();
+        let v = Coord { x: -u.y, y: u.x };
+        let (min, max) = (range.min(), range.max());
+        let (cu, cv) = ((low[0] + high[0]) / two, (low[1] + high[1]) / two);
+        Some(Obb::new(
+            CoordZ {
+                x: u.x * cu + v.x * cv,
+                y: u.y * cu + v.y * cv,
+                z: (min.z + max.z) / two,
+            },
+            CoordZ {
+                x: (high[0] - low[0]) / two,
+                y: (high[1] - low[1]) / two,
+                z: (max.z - min.z) / two,
+            },
+            [
+                [u.x, v.x, T::zero()],
+                [u.y, v.y, T::zero()],
+                [T::zero(), T::zero(), T::one()],
+            ],
+        ))
+    }
+}
+
+impl<T: GeoFloat> MinimumRotatedRectZ<T> for PointZ<T> {}
+impl<T: GeoFloat> MinimumRotatedRectZ<T> for LineZ<T> {}
+impl<T: GeoFloat> MinimumRotatedRectZ<T> for LineStringZ<T> {}
+impl<T: GeoFloat> MinimumRotatedRectZ<T> for PolygonZ<T> {}
+impl<T: GeoFloat> MinimumRotatedRectZ<T> for MultiPointZ<T> {}
+impl<T: GeoFloat> MinimumRotatedRectZ<T> for MultiLineStringZ<T> {}
+impl<T: GeoFloat> MinimumRotatedRectZ<T> for MultiPolygonZ<T> {}
+impl<T: GeoFloat> MinimumRotatedRectZ<T> for GeometryCollection<T> {}
+impl<T: GeoFloat> MinimumRotatedRectZ<T> for Geometry<T> {}
+
+/// The outline of a geometry seen from above, as planar polygons for 2D maps
+/// and tools, such as the footprint of a building modelled as a solid.
+///
+/// Every polygon of the geometry is projected onto XY, dropping z, and the
+/// projections are merged into the area covered by any of them, whichever way
+/// each one faced: roofs, floors and sloping faces all count, while vertical
+/// walls cover no area and drop out. Points and lines cover no area either, so a
+/// geometry without polygons has an empty footprint. Members of collections
+/// without z are merged in as they are.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::Footprint;
+/// use geo_types_3d::{wkt, MultiPolygonZ};
+/// use geo::Area;
+///
+/// // a house 4 by 4 with a gable roof, and a lean-to 2 wide along one side
+/// let house: MultiPolygonZ = wkt! {
+///     MULTIPOLYGON Z(
+///         ((0. 0. 3.,4. 0. 3.,4. 2. 5.,0. 2. 5.,0. 0. 3.)),
+///         ((0. 4. 3.,0. 2. 5.,4. 2. 5.,4. 4. 3.,0. 4. 3.)),
+///         ((4. 0. 0.,4. 4. 0.,4. 4. 3.,4. 0. 3.,4. 0. 0.)),
+///         ((4. 0. 2.,6. 0. 1.,6. 4. 1.,4. 4. 2.,4. 0. 2.))
+///     )
+/// };
+/// let footprint = house.footprint();
+///
+/// assert_eq!(footprint.0.len(), 1);
+/// assert_eq!(footprint.unsigned_area(), 24.);
+/// ```
+pub trait Footprint<T: BoolOpsNum + 'static>: Clone + Into<Geometry<T>> {
+    /// The XY area covered by the polygons of the geometry.
+    fn footprint(&self) -> MultiPolygon<T> {
+        let mut polygons = XyPolygons(Vec::new());
+        self.clone().into().accept(&mut polygons);
+        unary_union(&polygons.0)
+    }
+}
+
+impl<T: BoolOpsNum + 'static> Footprint<T> for PointZ<T> {}
+impl<T: BoolOpsNum + 'static> Footprint<T> for LineZ<T> {}
+impl<T: BoolOpsNum + 'static> Footprint<T> for LineStringZ<T> {}
+impl<T: BoolOpsNum + 'static> Footprint<T> for PolygonZ<T> {}
+impl<T: BoolOpsNum + 'static> Footprint<T> for MultiPointZ<T> {}
+impl<T: BoolOpsNum + 'static> Footprint<T> for MultiLineStringZ<T> {}
+impl<T: BoolOpsNum + 'static> Footprint<T> for MultiPolygonZ<T> {}
+impl<T: BoolOpsNum + 'static> Footprint<T> for GeometryCollection<T> {}
+impl<T: BoolOpsNum + 'static> Footprint<T> for Geometry<T> {}
+
+/// Gathers the XY coordinates of a geometry.
+struct XyPoints<T: GeoFloat>(Vec<Coord<T>>);
+
+impl<T: GeoFloat> CoordVisitor<T> for XyPoints<T> {
+    fn visit_coord_z(&mut self, coord: &CoordZ<T>) {
+        self.0.push(Coord {
+            x: coord.x,
+            y: coord.y,
+        });
+    }
+}
+
+/// Gathers the XY projections of the polygons of a geometry that cover any area,
+/// turned counter-clockwise so that they add up whichever way they faced.
+struct XyPolygons<T: GeoFloat>(Vec<Polygon<T>>);
+
+impl<T: GeoFloat> GeometryVisitor<T> for XyPolygons<T> {
+    fn visit_polygon(&mut self, polygon: &Polygon<T>) {
+        if polygon.exterior().winding_order().is_some() {
+            self.0.push(polygon.orient(Direction::Default));
+        }
+    }
+
+    fn visit_polygon_z(&mut self, polygon: &PolygonZ<T>) {
+        self.visit_polygon(&polygon_xy(polygon))
+    }
+
+    fn visit_rect(&mut self, rect: &Rect<T>) {
+        self.visit_polygon(&rect.to_polygon())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::Area;
+
+    #[test]
+    fn minimum_rotated_rect() {
+        // a diamond, with the rectangle along its edges rather than its diagonals
+        let diamond: PolygonZ =
+            wkt! { POLYGON Z((0. -1. 2.,2. 0. 2.,0. 1. 5.,-2. 0. 5.,0. -1. 2.)) };
+        let obb = diamond.minimum_rotated_rect().unwrap();
+        let [along, across, up] = obb.axes();
+        assert_relative_eq!((along.y / along.x).abs(), 0.5, epsilon = 1e-12);
+        assert_relative_eq!(along.x * across.x + along.y * across.y, 0., epsilon = 1e-12);
+        assert_eq!(up, CoordZ::from((0., 0., 1.)));
+        assert_relative_eq!(obb.volume(), 6.4 * 3., epsilon = 1e-9);
+        assert_relative_eq!(obb.center.x, 0., epsilon = 1e-12);
+        assert_eq!(obb.center.z, 3.5);
+        for corner in diamond.exterior().coords() {
+            let local = obb.to_local(*corner);
+            assert!(local.x.abs() <= obb.half_extents.x + 1e-9);
+            assert!(local.y.abs() <= obb.half_extents.y + 1e-9);
+        }
+
+        // a line, and a single point with planar members at z = 0
+        let line: LineStringZ = wkt! { LINESTRING Z(0. 0. 1.,3. 4. 1.,6. 8. 3.) };
+        let obb = line.minimum_rotated_rect().unwrap();
+        assert_relative_eq!(obb.half_extents.x, 5., epsilon = 1e-12);
+        assert_relative_eq!(obb.half_extents.y, 0., epsilon = 1e-12);
+        assert_relative_eq!(obb.center.x, 3., epsilon = 1e-12);
+        let point = Geometry::GeometryCollection(GeometryCollection(vec![
+            PointZ::new(1., 1., 4.).into(),
+            Geometry::Point(geo_types::Point::new(1., 1.)),
+        ]));
+        let obb = point.minimum_rotated_rect().unwrap();
+        assert_eq!(obb.half_extents, CoordZ::from((0., 0., 2.)));
+        assert_eq!(obb.center, CoordZ::from((1., 1., 2.)));
+        assert!(MultiPointZ::<f64>::empty().minimum_rotated_rect().is_none());
+    }
+
+    #[test]
+    fn footprint() {
+        // a box 2 by 2 by 2, with faces turned every way
+        let solid: MultiPolygonZ = wkt! {
+            MULTIPOLYGON Z(
+                ((0. 0. 0.,0. 2. 0.,2. 2. 0.,2. 0. 0.,0. 0. 0.)),
+                ((0. 0. 2.,2. 0. 2.,2. 2. 2.,0. 2. 2.,0. 0. 2.)),
+                ((0. 0. 0.,2. 0. 0.,2. 0. 2.,0. 0. 2.,0. 0. 0.)),
+                ((2. 0. 0.,2. 2. 0.,2. 2. 2.,2. 0. 2.,2. 0. 0.))
+            )
+        };
+        let footprint = solid.footprint();
+        assert_eq!(footprint.0.len(), 1);
+        assert_eq!(footprint.unsigned_area(), 4.);
+
+        let yard: PolygonZ = wkt! {
+            POLYGON Z((0. 0. 1.,6. 0. 1.,6. 6. 1.,0. 6. 1.,0. 0. 1.),(2. 2. 1.,2. 4. 1.,4. 4. 1.,4. 2. 1.,2. 2. 1.))
+        };
+        let collection = Geometry::GeometryCollection(GeometryCollection(vec![
+            yard.into(),
+            Geometry::Rect(geo_types::Rect::new((10., 0.), (11., 1.))),
+            PointZ::new(20., 20., 0.).into(),
+        ]));
+        let footprint = collection.footprint();
+        assert_eq!(footprint.0.len(), 2);
+        assert_eq!(footprint.unsigned_area(), 32. + 1.);
+        assert_eq!(footprint.0[0].interiors().len(), 1);
+
+        let line: LineStringZ = wkt! { LINESTRING Z(0. 0. 0.,1. 1. 1.) };
+        assert!(line.footprint().0.is_empty());
+    }
+}
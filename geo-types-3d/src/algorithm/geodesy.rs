@@ -0,0 +1,327 @@
+use geo::{
+    Bearing, Contains, Destination, Distance, Geodesic, GeodesicArea, InterpolatePoint, Point,
+};
+
+use super::xy::{multi_polygon_xy, polygon_xy};
+use crate::{CoordZ, LineStringZ, MultiPolygonZ, PointZ, PolygonZ};
+
+/// The longest edge, in meters, [`GeodesicPolygonZ::geodesic_contains`] leaves
+/// undivided.
+pub const DEFAULT_MAX_ARC: f64 = 10_000.0;
+
+/// Densify the edges of a lon/lat geometry along geodesics.
+///
+/// Planar algorithms treat an edge between two lon/lat positions as a straight line
+/// in lon/lat space, which can be hundreds of kilometres away from the geodesic
+/// between them. Densifying first bounds that error by the length of the new edges.
+///
+/// Coordinates are `x = longitude`, `y = latitude` in degrees on the WGS84
+/// ellipsoid. The z of new vertices is interpolated linearly along each edge.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::DensifyGeodesic;
+/// use geo_types_3d::{line_string_z, LineStringZ};
+///
+/// let flight: LineStringZ = line_string_z![
+///     (x: -0.45, y: 51.47, z: 0.0),
+///     (x: -73.78, y: 40.64, z: 100.0),
+/// ];
+/// // about 5,550 km
+/// let densified = flight.densify_for_geodesy(1_000_000.0);
+/// assert_eq!(densified.0.len(), 7);
+/// // the geodesic passes north of both airports
+/// assert!(densified.0[3].y > 51.47);
+/// assert!((densified.0[3].z - 50.0).abs() < 1e-9);
+/// ```
+pub trait DensifyGeodesic {
+    /// Adds vertices so that no edge is longer than `max_arc` meters.
+    ///
+    /// # Panics
+    ///
+    /// If `max_arc` isn't positive.
+    fn densify_for_geodesy(&self, max_arc: f64) -> Self;
+}
+
+impl DensifyGeodesic for LineStringZ<f64> {
+    fn densify_for_geodesy(&self, max_arc: f64) -> Self {
+        assert!(max_arc > 0.0, "max_arc must be positive");
+        let mut coords = Vec::with_capacity(self.0.len());
+        for pair in self.0.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            coords.push(a);
+            let (start, end) = (Point::new(a.x, a.y), Point::new(b.x, b.y));
+            let segments = (Geodesic.distance(start, end) / max_arc).ceil();
+            for i in 1..segments as usize {
+                let ratio = i as f64 / segments;
+                let position = Geodesic.point_at_ratio_between(start, end, ratio);
+                coords.push(CoordZ {
+                    x: position.x(),
+                    y: position.y(),
+                    z: a.z + (b.z - a.z) * ratio,
+                });
+            }
+        }
+        coords.extend(self.0.last());
+        LineStringZ(coords)
+    }
+}
+
+impl DensifyGeodesic for PolygonZ<f64> {
+    fn densify_for_geodesy(&self, max_arc: f64) -> Self {
+        PolygonZ::new(
+            self.exterior().densify_for_geodesy(max_arc),
+            self.interiors()
+                .iter()
+                .map(|ring| ring.densify_for_geodesy(max_arc))
+                .collect(),
+        )
+    }
+}
+
+impl DensifyGeodesic for MultiPolygonZ<f64> {
+    fn densify_for_geodesy(&self, max_arc: f64) -> Self {
+        MultiPolygonZ(
+            self.0
+                .iter()
+                .map(|polygon| polygon.densify_for_geodesy(max_arc))
+                .collect(),
+        )
+    }
+}
+
+/// Area and containment for lon/lat polygons whose edges are geodesics.
+///
+/// Only the XY footprint is considered. Polygons crossing the antimeridian are not
+/// supported.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::GeodesicPolygonZ;
+/// use geo_types_3d::{polygon_z, PointZ};
+///
+/// // corners at 60°N and 61°N, but the geodesic edges between them bulge north to
+/// // about 66°N and 67°N halfway along
+/// let band = polygon_z![
+///     (x: -40.0, y: 60.0, z: 0.0),
+///     (x: 40.0, y: 60.0, z: 0.0),
+///     (x: 40.0, y: 61.0, z: 0.0),
+///     (x: -40.0, y: 61.0, z: 0.0),
+/// ];
+/// let north = PointZ::new(0.0, 66.5, 0.0);
+/// assert!(band.geodesic_contains(&north));
+/// assert!(!band.geodesic_contains_with(&north, None));
+/// ```
+pub trait GeodesicPolygonZ: DensifyGeodesic {
+    /// The area of the footprint on the WGS84 ellipsoid, in square meters.
+    ///
+    /// The area is computed by [`geo::GeodesicArea`], which already follows the
+    /// geodesic between each pair of vertices, so no densification is needed.
+    fn geodesic_area(&self) -> f64;
+
+    /// Checks whether the footprint contains `point`, after densifying the edges to
+    /// at most [`DEFAULT_MAX_ARC`] meters.
+    fn geodesic_contains(&self, point: &PointZ<f64>) -> bool {
+        self.geodesic_contains_with(point, Some(DEFAULT_MAX_ARC))
+    }
+
+    /// Checks whether the footprint contains `point`. If `max_arc` is given, the
+    /// edges are first densified to at most that many meters, otherwise they are
+    /// treated as straight lines in lon/lat space.
+    fn geodesic_contains_with(&self, point: &PointZ<f64>, max_arc: Option<f64>) -> bool;
+}
+
+impl GeodesicPolygonZ for PolygonZ<f64> {
+    fn geodesic_area(&self) -> f64 {
+        polygon_xy(self).geodesic_area_unsigned()
+    }
+
+    fn geodesic_contains_with(&self, point: &PointZ<f64>, max_arc: Option<f64>) -> bool {
+        let point = Point::new(point.x(), point.y());
+        match max_arc {
+            Some(max_arc) => polygon_xy(&self.densify_for_geodesy(max_arc)).contains(&point),
+            None => polygon_xy(self).contains(&point),
+        }
+    }
+}
+
+impl GeodesicPolygonZ for MultiPolygonZ<f64> {
+    fn geodesic_area(&self) -> f64 {
+        multi_polygon_xy(self).geodesic_area_unsigned()
+    }
+
+    fn geodesic_contains_with(&self, point: &PointZ<f64>, max_arc: Option<f64>) -> bool {
+        self.0
+            .iter()
+            .any(|polygon| polygon.geodesic_contains_with(point, max_arc))
+    }
+}
+
+/// Bearings, pitches and destinations between lon/lat points with altitudes.
+///
+/// Coordinates are `x = longitude`, `y = latitude` in degrees on the WGS84
+/// ellipsoid, and `z` the altitude in meters. For points in a projected, metric
+/// space, see [`PointZ::bearing_and_pitch_to`].
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::GeodesicPointZ;
+/// use geo_types_3d::PointZ;
+///
+/// // a drone 1 km north of a ground station and 1 km up
+/// let station = PointZ::new(4.9, 52.37, 0.);
+/// let drone = PointZ::new(4.9, 52.37 + 1_000. / 111_254., 1_000.);
+/// let (bearing, pitch) = station.geodesic_bearing_and_pitch_to(&drone);
+/// assert!(bearing.abs() < 1e-6);
+/// assert!((pitch - 45.).abs() < 0.01);
+///
+/// // and back down to the station
+/// let back = drone.geodesic_destination(180., -45., 2f64.sqrt() * 1_000.);
+/// assert!((back.y() - 52.37).abs() < 1e-4 && back.z().abs() < 1e-9);
+/// ```
+pub trait GeodesicPointZ {
+    /// The bearing and pitch of `other` seen from this point, in degrees.
+    ///
+    /// The bearing is that of the geodesic to `other` where it sets off, clockwise
+    /// from north, from 0 up to 360. The pitch is the angle of the climb to `other`
+    /// against the length of the geodesic, from -90 to 90, so it leaves out the
+    /// curve of the earth under a line of sight. Both are 0 if the points are the
+    /// same.
+    fn geodesic_bearing_and_pitch_to(&self, other: &PointZ<f64>) -> (f64, f64);
+
+    /// The point `distance` meters away along `bearing` and `pitch` in degrees, as
+    /// [`geodesic_bearing_and_pitch_to`](Self::geodesic_bearing_and_pitch_to)
+    /// measures them: `distance × cos(pitch)` along the geodesic setting off on
+    /// `bearing`, and `distance × sin(pitch)` up.
+    fn geodesic_destination(&self, bearing: f64, pitch: f64, distance: f64) -> PointZ<f64>;
+}
+
+impl GeodesicPointZ for PointZ<f64> {
+    fn geodesic_bearing_and_pitch_to(&self, other: &PointZ<f64>) -> (f64, f64) {
+        let (from, to) = (
+            Point::new(self.x(), self.y()),
+            Point::new(other.x(), other.y()),
+        );
+        let distance = Geodesic.distance(from, to);
+        let bearing = if distance > 0. {
+            Geodesic.bearing(from, to)
+        } else {
+            0.
+        };
+        let pitch = (other.z() - self.z()).atan2(distance).to_degrees();
+        (bearing, pitch)
+    }
+
+    fn geodesic_destination(&self, bearing: f64, pitch: f64, distance: f64) -> PointZ<f64> {
+        let (sin, cos) = pitch.to_radians().sin_cos();
+        let from = Point::new(self.x(), self.y());
+        let to = Geodesic.destination(from, bearing, distance * cos);
+        PointZ::new(to.x(), to.y(), self.z() + distance * sin)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn densified_edges_are_short_and_keep_z() {
+        let ring = polygon_z![
+            (x: 0., y: 0., z: 10.),
+            (x: 10., y: 0., z: 20.),
+            (x: 10., y: 10., z: 20.),
+            (x: 0., y: 10., z: 10.),
+        ];
+        let densified = ring.densify_for_geodesy(50_000.);
+        let exterior = densified.exterior();
+        assert!(exterior.is_closed());
+        assert!(exterior.0.len() > 4 * 20);
+        for line in exterior.lines() {
+            let (a, b) = (
+                Point::new(line.start.x, line.start.y),
+                Point::new(line.end.x, line.end.y),
+            );
+            assert!(Geodesic.distance(a, b) <= 50_000. + 1e-6);
+            assert!((10. ..=20.).contains(&line.start.z));
+        }
+        // the original vertices are kept
+        for coord in ring.exterior().coords() {
+            assert!(exterior.0.contains(coord));
+        }
+        assert_relative_eq!(
+            densified.geodesic_area(),
+            ring.geodesic_area(),
+            max_relative = 1e-9
+        );
+        assert_relative_eq!(ring.geodesic_area(), 1.2279e12, max_relative = 1e-3);
+    }
+
+    #[test]
+    fn containment_follows_geodesics() {
+        // the geodesic from (-40, 60) to (40, 60) runs far north of the 60th parallel
+        let polygon = polygon_z![
+            (x: -40., y: 50., z: 0.),
+            (x: 40., y: 50., z: 0.),
+            (x: 40., y: 60., z: 0.),
+            (x: -40., y: 60., z: 0.),
+        ];
+        let point = PointZ::new(0., 63., 0.);
+        assert!(polygon.geodesic_contains(&point));
+        assert!(!polygon.geodesic_contains_with(&point, None));
+
+        let multi = MultiPolygonZ(vec![polygon]);
+        assert!(multi.geodesic_contains(&point));
+        assert!(!multi.geodesic_contains(&PointZ::new(0., 70., 0.)));
+    }
+
+    #[test]
+    fn bearing_and_pitch() {
+        // a degree of longitude along the equator is about 111.3 km
+        let (a, b) = (PointZ::new(0., 0., 100.), PointZ::new(1., 0., 1_213.));
+        let (bearing, pitch) = a.geodesic_bearing_and_pitch_to(&b);
+        assert_relative_eq!(bearing, 90., epsilon = 1e-9);
+        assert_relative_eq!(
+            pitch,
+            (1_113f64 / 111_319.5).atan().to_degrees(),
+            epsilon = 1e-4
+        );
+        let (bearing, pitch) = b.geodesic_bearing_and_pitch_to(&a);
+        assert_relative_eq!(bearing, 270., epsilon = 1e-9);
+        assert_relative_eq!(
+            pitch,
+            (-1_113f64 / 111_319.5).atan().to_degrees(),
+            epsilon = 1e-4
+        );
+
+        // the geodesic from London to New York sets off north of west
+        let london = PointZ::new(-0.45, 51.47, 25.);
+        let (bearing, _) = london.geodesic_bearing_and_pitch_to(&PointZ::new(-73.78, 40.64, 4.));
+        assert_relative_eq!(bearing, 288., epsilon = 0.1);
+
+        let above = PointZ::new(-0.45, 51.47, 525.);
+        assert_eq!(london.geodesic_bearing_and_pitch_to(&above), (0., 90.));
+        assert_eq!(london.geodesic_bearing_and_pitch_to(&london), (0., 0.));
+
+        // destinations are the inverse
+        for (bearing, pitch) in [(0., 0.), (45., 3.), (181., -20.), (288., 0.5)] {
+            let there = london.geodesic_destination(bearing, pitch, 50_000.);
+            let (b, p) = london.geodesic_bearing_and_pitch_to(&there);
+            assert_relative_eq!(b, bearing, epsilon = 1e-9);
+            assert_relative_eq!(p, pitch, epsilon = 1e-9);
+        }
+        let there = london.geodesic_destination(90., 30., 2_000.);
+        assert_relative_eq!(there.z(), 1_025., epsilon = 1e-9);
+        let (from, to) = (
+            Point::new(london.x(), london.y()),
+            Point::new(there.x(), there.y()),
+        );
+        assert_relative_eq!(
+            Geodesic.distance(from, to),
+            3f64.sqrt() * 1_000.,
+            epsilon = 1e-6
+        );
+    }
+}
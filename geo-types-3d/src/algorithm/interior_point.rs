@@ -0,0 +1,242 @@
+use geo::{Area, GeoFloat, InteriorPoint};
+use geo_types::{Coord, LineString, Polygon};
+
+use crate::algorithm::linalg::distance;
+use crate::{
+    CoordZ, LineStringZ, LineZ, MultiLineStringZ, MultiPointZ, MultiPolygonZ, PointZ, PolygonZ,
+};
+
+/// A point guaranteed to lie on a geometry, and inside it where it has an
+/// interior, such as to anchor a label in a 3D scene.
+///
+/// Unlike a centroid, which falls outside an L-shaped room or a ring-shaped
+/// courtyard, the point is always on the geometry, following the rules of
+/// [`geo`]'s `InteriorPoint`:
+///
+/// - for a polygon, the middle of the widest stretch across it, in its own plane
+///   and in any orientation, on a line through the middle of its extent. Of
+///   several polygons, the point is taken in the largest. A polygon enclosing no
+///   area is treated as its exterior ring.
+/// - for line strings, the vertex nearest their centroid other than their ends,
+///   or else the nearest end.
+/// - for points, the point nearest their mean.
+///
+/// Returns `None` for empty geometries.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::InteriorPointZ;
+/// use geo_types_3d::{polygon_z, PointZ};
+///
+/// // an L-shaped floor on a ramp, rising half a unit with each unit of x
+/// let floor = polygon_z![
+///     (x: 0., y: 0., z: 0.),
+///     (x: 4., y: 0., z: 2.),
+///     (x: 4., y: 1., z: 2.),
+///     (x: 1., y: 1., z: 0.5),
+///     (x: 1., y: 4., z: 0.5),
+///     (x: 0., y: 4., z: 0.),
+/// ];
+/// assert_eq!(floor.interior_point(), Some(PointZ::new(0.5, 2., 0.25)));
+/// ```
+pub trait InteriorPointZ<T: GeoFloat> {
+    fn interior_point(&self) -> Option<PointZ<T>>;
+}
+
+impl<T: GeoFloat> InteriorPointZ<T> for PointZ<T> {
+    fn interior_point(&self) -> Option<PointZ<T>> {
+        Some(*self)
+    }
+}
+
+impl<T: GeoFloat> InteriorPointZ<T> for MultiPointZ<T> {
+    fn interior_point(&self) -> Option<PointZ<T>> {
+        let mean = self.mean()?;
+        nearest(self.0.iter().map(|p| p.0), mean.0).map(PointZ)
+    }
+}
+
+impl<T: GeoFloat> InteriorPointZ<T> for LineZ<T> {
+    fn interior_point(&self) -> Option<PointZ<T>> {
+        LineStringZ::new(vec![self.start, self.end]).interior_point()
+    }
+}
+
+impl<T: GeoFloat> InteriorPointZ<T> for LineStringZ<T> {
+    fn interior_point(&self) -> Option<PointZ<T>> {
+        linear_interior_point(core::slice::from_ref(self))
+    }
+}
+
+impl<T: GeoFloat> InteriorPointZ<T> for MultiLineStringZ<T> {
+    fn interior_point(&self) -> Option<PointZ<T>> {
+        linear_interior_point(&self.0)
+    }
+}
+
+impl<T: GeoFloat> InteriorPointZ<T> for PolygonZ<T> {
+    fn interior_point(&self) -> Option<PointZ<T>> {
+        polygon_interior_point(self).map(|(point, _)| point)
+    }
+}
+
+impl<T: GeoFloat> InteriorPointZ<T> for MultiPolygonZ<T> {
+    fn interior_point(&self) -> Option<PointZ<T>> {
+        self.0
+            .iter()
+            .filter_map(polygon_interior_point)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(point, _)| point)
+    }
+}
+
+/// The interior point of a polygon and its area, zero if it encloses none.
+fn polygon_interior_point<T: GeoFloat>(polygon: &PolygonZ<T>) -> Option<(PointZ<T>, T)> {
+    let Some(normal) = polygon.normal() else {
+        return polygon
+            .exterior()
+            .interior_point()
+            .map(|point| (point, T::zero()));
+    };
+    // project onto the coordinate plane most parallel to the polygon's, and lift
+    // the point back onto the polygon's plane along the dropped axis
+    let normal = [normal.x, normal.y, normal.z];
+    let dropped = (0..3)
+        .rev()
+        .max_by(|&a, &b| normal[a].abs().total_cmp(&normal[b].abs()))
+        .unwrap_or(2);
+    let (a, b) = ((dropped + 1) % 3, (dropped + 2) % 3);
+    let project = |ring: &LineStringZ<T>| {
+        LineString(
+            ring.0
+                .iter()
+                .map(|c| {
+                    let c = [c.x, c.y, c.z];
+                    Coord { x: c[a], y: c[b] }
+                })
+                .collect(),
+        )
+    };
+    let flat = Polygon::new(
+        project(polygon.exterior()),
+        polygon.interiors().iter().map(project).collect(),
+    );
+    let point = flat.interior_point()?;
+    let origin = polygon.exterior().0[0];
+    let origin = [origin.x, origin.y, origin.z];
+    let mut lifted = [T::zero(); 3];
+    lifted[a] = point.x();
+    lifted[b] = point.y();
+    lifted[dropped] = origin[dropped]
+        - ((lifted[a] - origin[a]) * normal[a] + (lifted[b] - origin[b]) * normal[b])
+            / normal[dropped];
+    let area = flat.unsigned_area() / normal[dropped].abs();
+    Some((PointZ::new(lifted[0], lifted[1], lifted[2]), area))
+}
+
+fn linear_interior_point<T: GeoFloat>(lines: &[LineStringZ<T>]) -> Option<PointZ<T>> {
+    let two = T::one() + T::one();
+    let (mut sum, mut length) = (CoordZ::zero(), T::zero());
+    for line in lines {
+        for pair in line.0.windows(2) {
+            let segment = distance(pair[0], pair[1]);
+            sum += (pair[0] + pair[1]) * (segment / two);
+            length = length + segment;
+        }
+    }
+    // without any length, fall back on the mean of the coordinates
+    let centroid = if length > T::zero() {
+        sum / length
+    } else {
+        let coords = lines.iter().flat_map(|line| line.0.iter().copied());
+        let count = T::from(coords.clone().count())?;
+        coords.fold(CoordZ::zero(), |sum, c| sum + c) / count
+    };
+    let interior = lines
+        .iter()
+        .flat_map(|line| line.0.get(1..line.0.len().saturating_sub(1)).unwrap_or(&[]))
+        .copied();
+    let ends = lines
+        .iter()
+        .flat_map(|line| line.0.first().into_iter().chain(line.0.last()))
+        .copied();
+    nearest(interior, centroid)
+        .or_else(|| nearest(ends, centroid))
+        .map(PointZ)
+}
+
+/// The first of `coords` nearest `target`.
+fn nearest<T: GeoFloat>(
+    coords: impl Iterator<Item = CoordZ<T>>,
+    target: CoordZ<T>,
+) -> Option<CoordZ<T>> {
+    coords
+        .map(|c| (c, distance(c, target)))
+        .reduce(|best, next| if next.1 < best.1 { next } else { best })
+        .map(|(c, _)| c)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn vertical_courtyard() {
+        // a wall in the plane y = 2 with a large window, which takes in its
+        // centre
+        let wall = polygon_z!(
+            exterior: [
+                (x: 0., y: 2., z: 0.),
+                (x: 10., y: 2., z: 0.),
+                (x: 10., y: 2., z: 10.),
+                (x: 0., y: 2., z: 10.),
+            ],
+            interiors: [[
+                (x: 1., y: 2., z: 1.),
+                (x: 1., y: 2., z: 9.),
+                (x: 9., y: 2., z: 9.),
+                (x: 9., y: 2., z: 1.),
+            ]],
+        );
+        let point = wall.interior_point().unwrap();
+        assert_eq!(point.y(), 2.);
+        assert!(point.x() < 1. || point.x() > 9. || point.z() < 1. || point.z() > 9.);
+
+        // the larger of two polygons wins
+        let window: PolygonZ = polygon_z![
+            (x: 3., y: 0., z: 3.),
+            (x: 4., y: 0., z: 3.),
+            (x: 4., y: 0., z: 4.),
+            (x: 3., y: 0., z: 4.),
+        ];
+        let both = MultiPolygonZ::new(vec![window.clone(), wall]);
+        assert_eq!(both.interior_point().unwrap().y(), 2.);
+        assert_eq!(window.interior_point(), Some(PointZ::new(3.5, 0., 3.5)));
+        assert_eq!(MultiPolygonZ::<f64>::empty().interior_point(), None);
+    }
+
+    #[test]
+    fn linear_and_puntal() {
+        let line = line_string_z![
+            (x: 0., y: 0., z: 0.),
+            (x: 1., y: 0., z: 5.),
+            (x: 2., y: 0., z: 1.),
+            (x: 10., y: 0., z: 0.),
+        ];
+        assert_eq!(line.interior_point(), Some(PointZ::new(2., 0., 1.)));
+
+        let segment = LineZ::new((0., 0., 0.), (0., 0., 4.));
+        assert_eq!(segment.interior_point(), Some(PointZ::new(0., 0., 0.)));
+        let lines = MultiLineStringZ::new(vec![
+            line_string_z![(x: 0., y: 0., z: 0.), (x: 4., y: 0., z: 0.)],
+            line_string_z![(x: 4., y: 1., z: 0.), (x: 5., y: 1., z: 0.)],
+        ]);
+        assert_eq!(lines.interior_point(), Some(PointZ::new(4., 0., 0.)));
+        assert_eq!(LineStringZ::<f64>::empty().interior_point(), None);
+
+        let points: MultiPointZ = vec![(0., 0., 0.), (1., 1., 1.), (5., 5., 5.)].into();
+        assert_eq!(points.interior_point(), Some(PointZ::new(1., 1., 1.)));
+        assert_eq!(MultiPointZ::<f64>::empty().interior_point(), None);
+    }
+}
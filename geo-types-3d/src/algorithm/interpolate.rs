@@ -0,0 +1,330 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::index::KdTree;
+use geo_types::Coord;
+
+use crate::{CoordFloat, CoordZ, MultiPointZ, Point, PointZ, TinZ};
+
+/// How [`MultiPointZ::interpolate_z_at`] estimates z between scattered points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Interpolation<T: CoordFloat = f64> {
+    /// The mean z of all the points, each weighted by one over its horizontal
+    /// distance raised to `power`. Higher powers favour nearby points more; 2 is the
+    /// usual choice.
+    InverseDistance { power: T },
+    /// The z of the horizontally nearest point.
+    Nearest,
+    /// Linear interpolation across the [Delaunay triangulation](TinZ::delaunay) of
+    /// the points. There is no estimate outside their convex hull.
+    Linear,
+}
+
+impl<T: CoordFloat> MultiPointZ<T> {
+    /// Estimates z at `(x, y)` from the points, taken as samples of a surface such
+    /// as terrain, or `None` if there are no points to estimate from.
+    ///
+    /// To estimate z at many places, [`MultiPointZ::interpolate_grid`] prepares the
+    /// points once rather than for every estimate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::algorithm::Interpolation;
+    /// use geo_types_3d::{MultiPointZ, PointZ};
+    ///
+    /// let spot_heights = MultiPointZ::new(vec![
+    ///     PointZ::new(0., 0., 10.),
+    ///     PointZ::new(10., 0., 20.),
+    ///     PointZ::new(0., 10., 30.),
+    /// ]);
+    /// assert_eq!(spot_heights.interpolate_z_at(2., 2., Interpolation::Nearest), Some(10.));
+    /// assert_eq!(spot_heights.interpolate_z_at(5., 5., Interpolation::Linear), Some(25.));
+    /// assert_eq!(spot_heights.interpolate_z_at(9., 9., Interpolation::Linear), None);
+    ///
+    /// let idw = Interpolation::InverseDistance { power: 2. };
+    /// let z = spot_heights.interpolate_z_at(1., 1., idw).unwrap();
+    /// assert!(z > 10. && z < 12.);
+    /// ```
+    pub fn interpolate_z_at(&self, x: T, y: T, method: Interpolation<T>) -> Option<T> {
+        Surface::new(self, method).z_at(x, y)
+    }
+
+    /// Estimates z at the centre of each cell of a grid of `columns × rows` square
+    /// cells of side `cell_size`, starting from `origin`, such as to build a digital
+    /// elevation model from survey points.
+    ///
+    /// The estimates are returned row by row: cell `(column, row)` is centred on
+    /// `origin + ((column, row) + 0.5) × cell_size`, and its estimate is at index
+    /// `row × columns + column`. Cells without an estimate are NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::algorithm::Interpolation;
+    /// use geo_types_3d::{MultiPointZ, Point, PointZ};
+    ///
+    /// // samples of the plane z = x + 2y
+    /// let samples: MultiPointZ = (0..25)
+    ///     .map(|i| {
+    ///         let (x, y) = ((i % 5) as f64 * 2.5, (i / 5) as f64 * 2.5);
+    ///         PointZ::new(x, y, x + 2. * y)
+    ///     })
+    ///     .collect();
+    /// let dem = samples.interpolate_grid(Point::new(0., 0.), 1., [10, 10], Interpolation::Linear);
+    /// assert_eq!(dem.len(), 100);
+    /// assert!((dem[3 * 10 + 7] - (7.5 + 2. * 3.5)).abs() < 1e-9);
+    /// ```
+    pub fn interpolate_grid(
+        &self,
+        origin: Point<T>,
+        cell_size: T,
+        [columns, rows]: [usize; 2],
+        method: Interpolation<T>,
+    ) -> Vec<T> {
+        let surface = Surface::new(self, method);
+        let half = T::from(0.5).unwrap();
+        let mut grid = Vec::with_capacity(columns * rows);
+        for row in 0..rows {
+            let y = origin.y() + (T::from(row).unwrap() + half) * cell_size;
+            for column in 0..columns {
+                let x = origin.x() + (T::from(column).unwrap() + half) * cell_size;
+                grid.push(surface.z_at(x, y).unwrap_or_else(T::nan));
+            }
+        }
+        grid
+    }
+}
+
+impl<T: CoordFloat> TinZ<T> {
+    /// The Delaunay triangulation of `vertices` seen from above: the triangulation
+    /// in which no vertex lies inside the circle through the corners of any
+    /// triangle, so that its triangles are as close to equilateral as they can be.
+    ///
+    /// The triangles cover the convex hull of the vertices in XY, anticlockwise.
+    /// Vertices with the same x and y as an earlier one are left out of the
+    /// triangles, and there are none if all the vertices lie on one line. This
+    /// adds the vertices one at a time by the Bowyer–Watson algorithm, which is
+    /// quick enough for a few thousand vertices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{coordZ, TinZ};
+    ///
+    /// let tin = TinZ::delaunay(vec![
+    ///     coordZ! { x: 0., y: 0., z: 1. },
+    ///     coordZ! { x: 4., y: 0., z: 2. },
+    ///     coordZ! { x: 4., y: 3., z: 3. },
+    ///     coordZ! { x: 0., y: 3., z: 4. },
+    ///     coordZ! { x: 2., y: 1., z: 5. },
+    /// ]);
+    /// assert_eq!(tin.len(), 4);
+    /// ```
+    pub fn delaunay(vertices: Vec<CoordZ<T>>) -> Self {
+        let triangles = delaunay(&vertices);
+        TinZ::new(vertices, triangles)
+    }
+}
+
+/// Points prepared for estimating z by one [`Interpolation`] method.
+enum Surface<'a, T: CoordFloat> {
+    InverseDistance(&'a MultiPointZ<T>, T),
+    Nearest(&'a MultiPointZ<T>, KdTree<T>),
+    Linear(TinZ<T>),
+}
+
+impl<'a, T: CoordFloat> Surface<'a, T> {
+    fn new(points: &'a MultiPointZ<T>, method: Interpolation<T>) -> Self {
+        match method {
+            Interpolation::InverseDistance { power } => Self::InverseDistance(points, power),
+            Interpolation::Nearest => {
+                // points flattened onto z = 0, so the nearest is nearest horizontally
+                let flat = points
+                    .0
+                    .iter()
+                    .map(|p| PointZ::new(p.x(), p.y(), T::zero()));
+                Self::Nearest(points, KdTree::new(flat))
+            }
+            Interpolation::Linear => {
+                Self::Linear(TinZ::delaunay(points.0.iter().map(|p| p.0).collect()))
+            }
+        }
+    }
+
+    fn z_at(&self, x: T, y: T) -> Option<T> {
+        match self {
+            Self::InverseDistance(points, power) => {
+                let mut weighted = T::zero();
+                let mut weights = T::zero();
+                for point in &points.0 {
+                    let (dx, dy) = (point.x() - x, point.y() - y);
+                    let distance_2 = dx * dx + dy * dy;
+                    if distance_2 == T::zero() {
+                        return Some(point.z());
+                    }
+                    let two = T::one() + T::one();
+                    let weight = T::one() / distance_2.powf(*power / two);
+                    weighted = weighted + point.z() * weight;
+                    weights = weights + weight;
+                }
+                (weights > T::zero()).then(|| weighted / weights)
+            }
+            Self::Nearest(points, tree) => {
+                let (id, _) = tree.nearest(&PointZ::new(x, y, T::zero()))?;
+                Some(points.0[id].z())
+            }
+            Self::Linear(tin) => tin.elevation_at(Point::new(x, y)),
+        }
+    }
+}
+
+/// The Delaunay triangles of `coords` in XY, anticlockwise, by Bowyer–Watson.
+fn delaunay<T: CoordFloat>(coords: &[CoordZ<T>]) -> Vec<[usize; 3]> {
+    let mut order: Vec<usize> = (0..coords.len()).collect();
+    let compare = |a: &usize, b: &usize| {
+        let (a, b) = (coords[*a], coords[*b]);
+        a.x.partial_cmp(&b.x)
+            .unwrap_or(Ordering::Equal)
+            .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+    };
+    order.sort_by(compare);
+    order.dedup_by(|a, b| coords[*a].x == coords[*b].x && coords[*a].y == coords[*b].y);
+    if order.len() < 3 {
+        return Vec::new();
+    }
+
+    // a triangle around all the points, far enough away not to disturb the result
+    let (mut min, mut max) = (
+        Coord::from((T::infinity(), T::infinity())),
+        Coord::from((T::neg_infinity(), T::neg_infinity())),
+    );
+    for &i in &order {
+        min = Coord::from((min.x.min(coords[i].x), min.y.min(coords[i].y)));
+        max = Coord::from((max.x.max(coords[i].x), max.y.max(coords[i].y)));
+    }
+    let span = (max.x - min.x).max(max.y - min.y);
+    let far = span * T::from(100).unwrap();
+    let centre = (min + max) / (T::one() + T::one());
+    let mut xy: Vec<Coord<T>> = coords.iter().map(|c| Coord::from((c.x, c.y))).collect();
+    let outer = xy.len();
+    xy.push(Coord::from((centre.x - far, centre.y - far)));
+    xy.push(Coord::from((centre.x + far, centre.y - far)));
+    xy.push(Coord::from((centre.x, centre.y + far)));
+
+    let mut triangles: Vec<[usize; 3]> = vec![[outer, outer + 1, outer + 2]];
+    let mut edges: Vec<[usize; 2]> = Vec::new();
+    for &point in &order {
+        let p = xy[point];
+        edges.clear();
+        triangles.retain(|&[a, b, c]| {
+            if !in_circle(xy[a], xy[b], xy[c], p) {
+                return true;
+            }
+            for edge in [[a, b], [b, c], [c, a]] {
+                // an edge shared by two removed triangles is inside the hole
+                if let Some(shared) = edges
+                    .iter()
+                    .position(|&[s, e]| s == edge[1] && e == edge[0])
+                {
+                    edges.swap_remove(shared);
+                } else {
+                    edges.push(edge);
+                }
+            }
+            false
+        });
+        triangles.extend(edges.iter().map(|&[a, b]| [a, b, point]));
+    }
+    triangles.retain(|triangle| triangle.iter().all(|&vertex| vertex < outer));
+    triangles
+}
+
+/// Whether `p` lies strictly inside the circle through the anticlockwise triangle
+/// `a`, `b`, `c`.
+fn in_circle<T: CoordFloat>(a: Coord<T>, b: Coord<T>, c: Coord<T>, p: Coord<T>) -> bool {
+    let (a, b, c) = (a - p, b - p, c - p);
+    let lift = |v: Coord<T>| v.x * v.x + v.y * v.y;
+    let det = lift(a) * (b.x * c.y - c.x * b.y) - lift(b) * (a.x * c.y - c.x * a.y)
+        + lift(c) * (a.x * b.y - b.x * a.y);
+    det > T::zero()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn samples() -> MultiPointZ {
+        // irregularly placed samples of the plane z = 3 + 0.5x - 2y
+        (0..60)
+            .map(|i| {
+                let x = ((i * 37) % 61) as f64 / 6.;
+                let y = ((i * 17) % 53) as f64 / 5.3;
+                PointZ::new(x, y, 3. + 0.5 * x - 2. * y)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn delaunay() {
+        let points = samples();
+        let tin = TinZ::delaunay(points.0.iter().map(|p| p.0).collect());
+        assert!(!tin.is_empty());
+        for &[a, b, c] in tin.triangle_indices() {
+            let [a, b, c] =
+                [a, b, c].map(|i| Coord::from((tin.vertices()[i].x, tin.vertices()[i].y)));
+            // anticlockwise, and no vertex inside its circumcircle
+            assert!((b - a).x * (c - a).y - (b - a).y * (c - a).x > 0.);
+            for vertex in tin.vertices() {
+                assert!(!in_circle(a, b, c, Coord::from((vertex.x, vertex.y))));
+            }
+        }
+
+        // the triangles of a square grid cover it
+        let grid: Vec<CoordZ> = (0..25)
+            .map(|i| CoordZ::from(((i % 5) as f64, (i / 5) as f64, 0.)))
+            .collect();
+        assert_eq!(TinZ::delaunay(grid).len(), 32);
+        let line = (0..5)
+            .map(|i| CoordZ::from((i as f64, i as f64, 0.)))
+            .collect();
+        assert!(TinZ::delaunay(line).is_empty());
+    }
+
+    #[test]
+    fn interpolate() {
+        let points = samples();
+        let plane = |x: f64, y: f64| 3. + 0.5 * x - 2. * y;
+        let linear = points
+            .interpolate_z_at(4.2, 5.1, Interpolation::Linear)
+            .unwrap();
+        assert_relative_eq!(linear, plane(4.2, 5.1), epsilon = 1e-9);
+        assert_eq!(
+            points.interpolate_z_at(-5., 5., Interpolation::Linear),
+            None
+        );
+
+        let sample = points.0[7];
+        let idw = Interpolation::InverseDistance { power: 2. };
+        assert_eq!(
+            points.interpolate_z_at(sample.x(), sample.y(), idw),
+            Some(sample.z())
+        );
+        let nearest =
+            points.interpolate_z_at(sample.x() + 0.01, sample.y(), Interpolation::Nearest);
+        assert_eq!(nearest, Some(sample.z()));
+        assert_eq!(MultiPointZ::empty().interpolate_z_at(0., 0., idw), None);
+
+        let grid =
+            points.interpolate_grid(Point::new(-1., 2.), 0.5, [30, 10], Interpolation::Linear);
+        assert_eq!(grid.len(), 300);
+        // the first columns lie outside the samples
+        assert!(grid[0].is_nan());
+        let (x, y) = (-1. + 20.5 * 0.5, 2. + 6.5 * 0.5);
+        assert_relative_eq!(grid[6 * 30 + 20], plane(x, y), epsilon = 1e-9);
+        let idw_grid = points.interpolate_grid(Point::new(-1., 2.), 0.5, [30, 10], idw);
+        assert!(idw_grid.iter().all(|z| z.is_finite()));
+    }
+}
@@ -0,0 +1,103 @@
+use num_traits::Float;
+
+use crate::{CoordNum, CoordZ, LineStringZ, LineZ, MultiLineStringZ};
+
+/// Coordinate types with a square root: the exact one for floats, and the integer
+/// square root, rounded down, for integers.
+pub trait SquareRoot: CoordNum {
+    fn square_root(self) -> Self;
+}
+
+macro_rules! impl_square_root {
+    ($method:path: $($ty:ty),*) => {
+        $(
+            impl SquareRoot for $ty {
+                fn square_root(self) -> Self {
+                    $method(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_square_root!(Float::sqrt: f32, f64);
+impl_square_root!(Self::isqrt: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// The 3D Euclidean length of a linear geometry.
+///
+/// With integer coordinates, such as tile or grid coordinates, the length of each
+/// segment is rounded down to a whole number before they are summed, and the
+/// squared length of each segment must fit in the coordinate type.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::LengthZ;
+/// use geo_types_3d::LineStringZ;
+///
+/// let path: LineStringZ = vec![(0., 0., 0.), (3., 4., 0.), (3., 4., 12.)].into();
+/// assert_eq!(path.length(), 17.);
+///
+/// // on a grid of tiles, a step of one tile diagonally counts as one
+/// let tiles: LineStringZ<i32> = vec![(0, 0, 0), (3, 4, 0), (4, 5, 0)].into();
+/// assert_eq!(tiles.length(), 6);
+/// ```
+pub trait LengthZ<T: SquareRoot> {
+    fn length(&self) -> T;
+}
+
+impl<T: SquareRoot> LengthZ<T> for LineZ<T> {
+    fn length(&self) -> T {
+        segment_length(self.start, self.end)
+    }
+}
+
+impl<T: SquareRoot> LengthZ<T> for LineStringZ<T> {
+    fn length(&self) -> T {
+        self.0.windows(2).fold(T::zero(), |sum, pair| {
+            sum + segment_length(pair[0], pair[1])
+        })
+    }
+}
+
+impl<T: SquareRoot> LengthZ<T> for MultiLineStringZ<T> {
+    fn length(&self) -> T {
+        self.0
+            .iter()
+            .fold(T::zero(), |sum, line| sum + line.length())
+    }
+}
+
+fn segment_length<T: SquareRoot>(a: CoordZ<T>, b: CoordZ<T>) -> T {
+    // differences of unsigned integers can't go below zero
+    let delta = |a: T, b: T| if a > b { a - b } else { b - a };
+    let (x, y, z) = (delta(a.x, b.x), delta(a.y, b.y), delta(a.z, b.z));
+    (x * x + y * y + z * z).square_root()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::{AffineOpsZ, AffineTransform3D, BoundingCube, Cast, Clip};
+    use crate::{Cube, MultiPointZ};
+    use alloc::vec;
+
+    #[test]
+    fn integer_tiles() {
+        // a track across a grid of u32 tiles, moved and measured without floats
+        let track: LineStringZ<u32> = vec![(2, 1, 0), (2, 9, 0), (8, 1, 0), (8, 1, 3)].into();
+        assert_eq!(track.length(), 8 + 10 + 3);
+        assert_eq!(LineZ::new((5u8, 5, 5), (0, 0, 0)).length(), 8);
+        let moved = track.affine_transform(&AffineTransform3D::translate(10, 20, 0));
+        assert_eq!(
+            moved.bounding_cube(),
+            Some(Cube::new((12, 21, 0), (18, 29, 3)))
+        );
+        let tracks = MultiLineStringZ::new(vec![track.clone(), moved]);
+        assert_eq!(tracks.length(), 42);
+
+        let points = MultiPointZ::new(track.cast::<i32>().unwrap().into_points());
+        let tile = Cube::new((0, 0, 0), (4, 10, 1));
+        assert_eq!(points.clip(&tile).0.len(), 2);
+    }
+}
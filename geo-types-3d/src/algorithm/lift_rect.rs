@@ -0,0 +1,78 @@
+use alloc::vec::Vec;
+
+use geo_types::Rect;
+
+use crate::{CoordNum, CoordZ, Cube, LineStringZ, PolygonZ};
+
+/// Lift a planar [`Rect`] into 3D, such as to use the
+/// [`Geometry::Rect`](crate::Geometry::Rect) members of a collection alongside
+/// its 3D ones.
+///
+/// The other way, a [`Cube`] converts into the `Rect` of its XY footprint with
+/// `Rect::from`.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types::Rect;
+/// use geo_types_3d::algorithm::LiftRect;
+/// use geo_types_3d::{wkt, Cube};
+///
+/// let footprint = Rect::new((0., 0.), (4., 3.));
+/// assert_eq!(
+///     footprint.to_polygon_z(10.),
+///     wkt! { POLYGON Z((4. 0. 10.,4. 3. 10.,0. 3. 10.,0. 0. 10.,4. 0. 10.)) }
+/// );
+///
+/// let building = footprint.extrude(0., 25.);
+/// assert_eq!(building, Cube::new((0., 0., 0.), (4., 3., 25.)));
+/// assert_eq!(Rect::from(building), footprint);
+/// ```
+pub trait LiftRect<T: CoordNum> {
+    /// The rectangle as a polygon at height `z`, with its exterior ring in the
+    /// order of [`Rect::to_polygon`].
+    fn to_polygon_z(&self, z: T) -> PolygonZ<T>;
+
+    /// The cube spanning the rectangle from height `z0` to `z1`, in either order.
+    fn extrude(&self, z0: T, z1: T) -> Cube<T>;
+}
+
+impl<T: CoordNum> LiftRect<T> for Rect<T> {
+    fn to_polygon_z(&self, z: T) -> PolygonZ<T> {
+        let exterior = self
+            .to_polygon()
+            .exterior()
+            .coords()
+            .map(|coord| CoordZ {
+                x: coord.x,
+                y: coord.y,
+                z,
+            })
+            .collect();
+        PolygonZ::new(LineStringZ(exterior), Vec::new())
+    }
+
+    fn extrude(&self, z0: T, z1: T) -> Cube<T> {
+        let (min, max) = (self.min(), self.max());
+        Cube::new((min.x, min.y, z0), (max.x, max.y, z1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::BoundingCube;
+
+    #[test]
+    fn round_trips() {
+        let rect = Rect::new((-2, 5), (3, -1));
+        let cube = rect.extrude(7, -4);
+        assert_eq!(cube, Cube::new((-2, -1, -4), (3, 5, 7)));
+        assert_eq!(Rect::from(cube), rect);
+
+        let polygon = rect.to_polygon_z(2);
+        assert_eq!(polygon.exterior().0.len(), 5);
+        assert!(polygon.exterior().0.iter().all(|coord| coord.z == 2));
+        assert_eq!(polygon.bounding_cube(), Some(rect.extrude(2, 2)));
+    }
+}
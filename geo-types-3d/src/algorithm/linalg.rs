@@ -0,0 +1,224 @@
+//! Small dense linear algebra for the fitting algorithms: eigen-decomposition of
+//! symmetric matrices, solving linear systems, products of coordinates as vectors,
+//! and the coordinate planes used to work on planar geometries in 2D.
+
+use core::cmp::Ordering;
+
+use crate::{CoordFloat, CoordZ};
+
+/// The eigenvalues of a symmetric matrix, in decreasing order, and the unit
+/// eigenvector of each, by cyclic Jacobi rotations.
+pub(crate) fn symmetric_eigen<T: CoordFloat, const N: usize>(
+    mut a: [[T; N]; N],
+) -> [(T, [T; N]); N] {
+    // the eigenvectors are the columns of v
+    let mut v: [[T; N]; N] = core::array::from_fn(|i| {
+        core::array::from_fn(|j| if i == j { T::one() } else { T::zero() })
+    });
+    let two = T::one() + T::one();
+    for _ in 0..50 {
+        let mut off_diagonal = T::zero();
+        let mut diagonal = T::zero();
+        for (i, row) in a.iter().enumerate() {
+            for (j, &value) in row.iter().enumerate() {
+                if i == j {
+                    diagonal = diagonal + value * value;
+                } else {
+                    off_diagonal = off_diagonal + value * value;
+                }
+            }
+        }
+        if off_diagonal <= diagonal * T::epsilon() * T::epsilon() {
+            break;
+        }
+        for p in 0..N {
+            for q in p + 1..N {
+                if a[p][q] == T::zero() {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (two * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + T::one()).sqrt());
+                let c = T::one() / (t * t + T::one()).sqrt();
+                let s = t * c;
+                // rotate the columns p and q, then the rows, and the eigenvectors
+                for row in a.iter_mut().chain(v.iter_mut()) {
+                    let (kp, kq) = (row[p], row[q]);
+                    row[p] = c * kp - s * kq;
+                    row[q] = s * kp + c * kq;
+                }
+                let (mut row_p, mut row_q) = (a[p], a[q]);
+                for (pk, qk) in row_p.iter_mut().zip(&mut row_q) {
+                    let (old_p, old_q) = (*pk, *qk);
+                    *pk = c * old_p - s * old_q;
+                    *qk = s * old_p + c * old_q;
+                }
+                (a[p], a[q]) = (row_p, row_q);
+            }
+        }
+    }
+    let mut pairs: [(T, [T; N]); N] =
+        core::array::from_fn(|i| (a[i][i], core::array::from_fn(|k| v[k][i])));
+    pairs.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+    pairs
+}
+
+/// Solves `a x = b` by Gaussian elimination with partial pivoting, or `None` if
+/// `a` is singular.
+pub(crate) fn solve<T: CoordFloat, const N: usize>(
+    mut a: [[T; N]; N],
+    mut b: [T; N],
+) -> Option<[T; N]> {
+    let scale = a
+        .iter()
+        .flatten()
+        .fold(T::zero(), |max, value| max.max(value.abs()));
+    for column in 0..N {
+        let pivot = (column..N).max_by(|&i, &j| {
+            a[i][column]
+                .abs()
+                .partial_cmp(&a[j][column].abs())
+                .unwrap_or(Ordering::Equal)
+        })?;
+        let size = a[pivot][column].abs();
+        if size.is_nan() || size <= scale * T::epsilon() {
+            return None;
+        }
+        a.swap(column, pivot);
+        b.swap(column, pivot);
+        for row in column + 1..N {
+            let factor = a[row][column] / a[column][column];
+            let pivot_row = a[column];
+            for (value, &above) in a[row].iter_mut().zip(&pivot_row).skip(column) {
+                *value = *value - factor * above;
+            }
+            b[row] = b[row] - factor * b[column];
+        }
+    }
+    let mut x = [T::zero(); N];
+    for row in (0..N).rev() {
+        let known = (row + 1..N).fold(T::zero(), |sum, k| sum + a[row][k] * x[k]);
+        x[row] = (b[row] - known) / a[row][row];
+    }
+    Some(x)
+}
+
+pub(crate) fn dot<T: CoordFloat>(a: CoordZ<T>, b: CoordZ<T>) -> T {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
+pub(crate) fn cross<T: CoordFloat>(a: CoordZ<T>, b: CoordZ<T>) -> CoordZ<T> {
+    CoordZ {
+        x: a.y * b.z - a.z * b.y,
+        y: a.z * b.x - a.x * b.z,
+        z: a.x * b.y - a.y * b.x,
+    }
+}
+
+/// The Euclidean length of `a` as a vector.
+pub(crate) fn norm<T: CoordFloat>(a: CoordZ<T>) -> T {
+    dot(a, a).sqrt()
+}
+
+/// `a` scaled to unit length. `a` must not be zero.
+pub(crate) fn normalize<T: CoordFloat>(a: CoordZ<T>) -> CoordZ<T> {
+    a / norm(a)
+}
+
+pub(crate) fn distance<T: CoordFloat>(a: CoordZ<T>, b: CoordZ<T>) -> T {
+    distance_2(a, b).sqrt()
+}
+
+/// The square of the distance between `a` and `b`, for comparing distances
+/// without taking roots.
+pub(crate) fn distance_2<T: CoordFloat>(a: CoordZ<T>, b: CoordZ<T>) -> T {
+    let delta = a - b;
+    dot(delta, delta)
+}
+
+/// The normal of a ring by Newell's method, with a length of twice the area it
+/// encloses. The ring may or may not repeat its first coordinate at the end.
+pub(crate) fn newell_normal<T: CoordFloat>(ring: &[CoordZ<T>]) -> CoordZ<T> {
+    let mut normal = CoordZ::zero();
+    for (i, a) in ring.iter().enumerate() {
+        let b = ring[(i + 1) % ring.len()];
+        normal.x = normal.x + (a.y - b.y) * (a.z + b.z);
+        normal.y = normal.y + (a.z - b.z) * (a.x + b.x);
+        normal.z = normal.z + (a.x - b.x) * (a.y + b.y);
+    }
+    normal
+}
+
+/// Reorders coordinates so that the axes kept by a projection come first, and the
+/// dropped one takes the place of z.
+#[derive(Clone, Copy)]
+pub(crate) struct Axes {
+    kept: (usize, usize),
+    dropped: usize,
+}
+
+impl Axes {
+    pub(crate) fn new(dropped: usize) -> Self {
+        Self {
+            kept: ((dropped + 1) % 3, (dropped + 2) % 3),
+            dropped,
+        }
+    }
+
+    /// The projection onto the coordinate plane most parallel to a plane with
+    /// `normal`, dropping the axis it is most nearly along.
+    pub(crate) fn facing<T: CoordFloat>(normal: CoordZ<T>) -> Self {
+        let [x, y, z] = [normal.x, normal.y, normal.z].map(|value| value.abs());
+        Self::new(if z >= x && z >= y {
+            2
+        } else if x >= y {
+            0
+        } else {
+            1
+        })
+    }
+
+    pub(crate) fn project<T: CoordFloat>(&self, coord: CoordZ<T>) -> CoordZ<T> {
+        let coord = [coord.x, coord.y, coord.z];
+        CoordZ {
+            x: coord[self.kept.0],
+            y: coord[self.kept.1],
+            z: coord[self.dropped],
+        }
+    }
+
+    pub(crate) fn unproject<T: CoordFloat>(&self, coord: CoordZ<T>) -> CoordZ<T> {
+        let mut unprojected = [coord.z; 3];
+        unprojected[self.kept.0] = coord.x;
+        unprojected[self.kept.1] = coord.y;
+        CoordZ {
+            x: unprojected[0],
+            y: unprojected[1],
+            z: unprojected[2],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn eigen_and_solve() {
+        let a = [[4., 1., 0.5], [1., 3., 0.], [0.5, 0., 1.]];
+        let pairs = symmetric_eigen(a);
+        assert!(pairs[0].0 >= pairs[1].0 && pairs[1].0 >= pairs[2].0);
+        for (value, vector) in pairs {
+            for row in 0..3 {
+                let product: f64 = (0..3).map(|k| a[row][k] * vector[k]).sum();
+                assert_relative_eq!(product, value * vector[row], epsilon = 1e-12);
+            }
+        }
+
+        let x = solve(a, [1., 2., 3.]).unwrap();
+        for row in 0..3 {
+            let product: f64 = (0..3).map(|k| a[row][k] * x[k]).sum();
+            assert_relative_eq!(product, [1., 2., 3.][row], epsilon = 1e-12);
+        }
+        assert_eq!(solve([[1., 2.], [2., 4.]], [1., 1.]), None);
+    }
+}
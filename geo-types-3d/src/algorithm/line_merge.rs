@@ -0,0 +1,219 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::index::KdTree;
+use crate::{CoordFloat, CoordZ, LineStringZ, MultiLineStringZ, PointZ};
+
+impl<T: CoordFloat> MultiLineStringZ<T> {
+    /// Joins line strings that meet end to end into the longest paths they form.
+    ///
+    /// Two ends meet if they are at most `tolerance` apart horizontally and
+    /// `z_tolerance` apart vertically, so that a bridge passing over a road doesn't
+    /// join it. Paths run through each place where exactly two ends meet, and stop
+    /// at places where one end or three or more meet, as at junctions in a road
+    /// network. Line strings forming closed loops with no junctions become closed
+    /// line strings.
+    ///
+    /// Where ends meet within the tolerances, the joined path keeps the end of the
+    /// earlier line string along it. Each path runs in the direction of most of its
+    /// line strings. Line strings with fewer than two coordinates are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{line_string_z, MultiLineStringZ};
+    ///
+    /// // a road exported in pieces, one of them drawn the other way, and a bridge
+    /// // crossing over its middle
+    /// let pieces: MultiLineStringZ = MultiLineStringZ::new(vec![
+    ///     line_string_z![(x: 0., y: 0., z: 0.), (x: 5., y: 0., z: 0.)],
+    ///     line_string_z![(x: 10., y: 0., z: 0.), (x: 5.01, y: 0., z: 0.)],
+    ///     line_string_z![(x: 10., y: 0., z: 0.), (x: 15., y: 0., z: 1.)],
+    ///     line_string_z![(x: 5., y: -5., z: 8.), (x: 5., y: 0., z: 8.)],
+    /// ]);
+    /// let merged = pieces.line_merge(0.05, 0.5);
+    /// assert_eq!(merged.0.len(), 2);
+    /// assert_eq!(merged.0[0].0.len(), 4);
+    /// assert_eq!(merged.0[0].0[3].x, 15.);
+    /// ```
+    pub fn line_merge(&self, tolerance: T, z_tolerance: T) -> MultiLineStringZ<T> {
+        let lines: Vec<&LineStringZ<T>> = self.0.iter().filter(|line| line.0.len() >= 2).collect();
+        let nodes = Nodes::new(&lines, tolerance, z_tolerance);
+        let mut used = vec![false; lines.len()];
+        let mut merged = Vec::new();
+
+        // paths from dead ends and junctions
+        for line in 0..lines.len() {
+            if used[line] {
+                continue;
+            }
+            let (start, end) = (nodes.of(line, 0), nodes.of(line, 1));
+            let forward = if nodes.degree(start) != 2 {
+                true
+            } else if nodes.degree(end) != 2 {
+                false
+            } else {
+                continue;
+            };
+            merged.push(walk(&lines, &nodes, &mut used, line, forward));
+        }
+
+        // what is left forms loops
+        for line in 0..lines.len() {
+            if !used[line] {
+                let mut path = walk(&lines, &nodes, &mut used, line, true);
+                let first = path.0[0];
+                if let Some(last) = path.0.last_mut() {
+                    *last = first;
+                }
+                merged.push(path);
+            }
+        }
+        MultiLineStringZ::new(merged)
+    }
+}
+
+/// The places where line string ends meet: each end's node, and the ends at each
+/// node. End `2 * line + 0` is the start of a line string, and `2 * line + 1` its
+/// end.
+struct Nodes {
+    node_of: Vec<usize>,
+    ends: Vec<Vec<usize>>,
+}
+
+impl Nodes {
+    fn new<T: CoordFloat>(lines: &[&LineStringZ<T>], tolerance: T, z_tolerance: T) -> Self {
+        let points: Vec<PointZ<T>> = lines
+            .iter()
+            .flat_map(|line| [line.0[0], line.0[line.0.len() - 1]])
+            .map(PointZ)
+            .collect();
+        let tree = KdTree::new(points.iter().copied());
+        let radius = (tolerance * tolerance + z_tolerance * z_tolerance).sqrt();
+        let mut node_of = vec![usize::MAX; points.len()];
+        let mut ends = Vec::new();
+        // each end joins the node of the first end meeting it, or starts a new one
+        for (end, point) in points.iter().enumerate() {
+            let meets = |other: &PointZ<T>| {
+                let delta: CoordZ<T> = other.0 - point.0;
+                delta.x.hypot(delta.y) <= tolerance && delta.z.abs() <= z_tolerance
+            };
+            let node = tree
+                .within_radius(point, radius)
+                .into_iter()
+                .filter(|(other, found)| *other < end && meets(found))
+                .map(|(other, _)| node_of[other])
+                .min()
+                .unwrap_or_else(|| {
+                    ends.push(Vec::new());
+                    ends.len() - 1
+                });
+            node_of[end] = node;
+            ends[node].push(end);
+        }
+        Self { node_of, ends }
+    }
+
+    fn of(&self, line: usize, end: usize) -> usize {
+        self.node_of[2 * line + end]
+    }
+
+    fn degree(&self, node: usize) -> usize {
+        self.ends[node].len()
+    }
+}
+
+/// Follows the path starting with `line`, forwards or backwards, through nodes
+/// where exactly two ends meet.
+fn walk<T: CoordFloat>(
+    lines: &[&LineStringZ<T>],
+    nodes: &Nodes,
+    used: &mut [bool],
+    mut line: usize,
+    mut forward: bool,
+) -> LineStringZ<T> {
+    let mut coords: Vec<CoordZ<T>> = Vec::new();
+    let mut reversed = 0usize;
+    let mut count = 0usize;
+    loop {
+        used[line] = true;
+        count += 1;
+        let mut next = lines[line].0.clone();
+        if !forward {
+            next.reverse();
+            reversed += 1;
+        }
+        let skip = if coords.is_empty() { 0 } else { 1 };
+        coords.extend(next.into_iter().skip(skip));
+
+        let exit = 2 * line + usize::from(forward);
+        let node = nodes.node_of[exit];
+        if nodes.degree(node) != 2 {
+            break;
+        }
+        let Some(&entry) = nodes.ends[node].iter().find(|&&end| end != exit) else {
+            break;
+        };
+        if used[entry / 2] {
+            break;
+        }
+        line = entry / 2;
+        // entering at its start means following it forwards
+        forward = entry % 2 == 0;
+    }
+    if reversed * 2 > count {
+        coords.reverse();
+    }
+    LineStringZ::new(coords)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn merges() {
+        let line = |coords: &[(f64, f64, f64)]| LineStringZ::from(coords.to_vec());
+        // a T junction: two arms meeting a stem, one arm in two pieces
+        let lines = MultiLineStringZ::new(vec![
+            line(&[(0., 0., 0.), (1., 0., 0.)]),
+            line(&[(2., 0., 0.), (1., 0., 0.)]),
+            line(&[(2., 0., 0.), (3., 0., 0.)]),
+            line(&[(4., 0., 0.), (3., 0., 0.)]),
+            line(&[(2., 0., 0.), (2., 5., 0.)]),
+        ]);
+        let merged = lines.line_merge(1e-9, 1e-9);
+        assert_eq!(
+            merged,
+            MultiLineStringZ::new(vec![
+                line(&[(0., 0., 0.), (1., 0., 0.), (2., 0., 0.)]),
+                line(&[(2., 0., 0.), (3., 0., 0.), (4., 0., 0.)]),
+                line(&[(2., 0., 0.), (2., 5., 0.)]),
+            ])
+        );
+
+        // a triangle in three pieces closes into a loop, while ends too far apart
+        // in z stay apart
+        let lines = MultiLineStringZ::new(vec![
+            line(&[(0., 0., 0.), (1., 0., 0.)]),
+            line(&[(1., 0.001, 0.), (0., 1., 0.)]),
+            line(&[(0., 1., 0.), (0., 0.001, 0.)]),
+            line(&[(5., 5., 0.), (6., 6., 0.)]),
+            line(&[(6., 6., 3.), (7., 7., 3.)]),
+            line(&[(8., 8., 8.)]),
+        ]);
+        let merged = lines.line_merge(0.01, 0.5);
+        assert_eq!(merged.0.len(), 3);
+        assert_eq!(
+            merged.0[2],
+            line(&[(0., 0., 0.), (1., 0., 0.), (0., 1., 0.), (0., 0., 0.)])
+        );
+        assert!(merged.0[2].is_closed());
+        assert_eq!(merged.0[0].0.len(), 2);
+        assert_eq!(merged.0[1].0.len(), 2);
+        assert!(MultiLineStringZ::<f64>::empty()
+            .line_merge(1., 1.)
+            .0
+            .is_empty());
+    }
+}
@@ -0,0 +1,281 @@
+use alloc::vec::Vec;
+
+use geo::algorithm::bool_ops::{unary_union, BoolOpsNum, OpType};
+use geo::BooleanOps;
+use geo_types::{Coord, LineString, MultiPolygon, Polygon};
+
+use super::linalg::Axes;
+use super::ransac::fit_plane;
+use super::xy::NearestZ;
+use super::MapCoordsZ;
+use crate::{CoordZ, LineStringZ, MultiPolygonZ, PolygonZ};
+
+/// Repair polygons that aren't topologically valid, such as those exported from
+/// CAD software, into valid ones covering the same area.
+///
+/// Rings are closed, and repeated vertices and the spikes where a ring goes out
+/// to a vertex and straight back are removed. Each ring is then projected onto
+/// the coordinate plane most parallel to the polygons, as for
+/// [`BooleanOpsZ`](super::BooleanOpsZ), and rebuilt there: the ring is noded
+/// where it crosses itself and the faces it encloses an odd number of times are
+/// kept, so that a self-intersecting bow tie becomes two triangles. Holes are cut
+/// out of their exterior ring, losing any part outside it, and the polygons of a
+/// [`MultiPolygonZ`] are merged where they overlap. The result is lifted back to
+/// 3D, with vertices created where rings crossed taking the height of the input
+/// at that location.
+///
+/// Rings left with no area, and polygons whose exterior has none, are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::MakeValid;
+/// use geo_types_3d::{wkt, PolygonZ};
+///
+/// // a bow tie, crossing itself at (1, 1)
+/// let bow_tie: PolygonZ = wkt! { POLYGON Z((0. 0. 5.,2. 2. 5.,2. 0. 5.,0. 2. 5.,0. 0. 5.)) };
+/// let valid = bow_tie.make_valid();
+///
+/// assert_eq!(valid.0.len(), 2);
+/// assert!(valid.0.iter().all(|triangle| triangle.exterior().0.len() == 4));
+/// ```
+pub trait MakeValid {
+    type Scalar: BoolOpsNum + 'static;
+
+    /// The valid polygons covering the geometry.
+    fn make_valid(&self) -> MultiPolygonZ<Self::Scalar>;
+}
+
+impl<T: BoolOpsNum + 'static> MakeValid for PolygonZ<T> {
+    type Scalar = T;
+
+    fn make_valid(&self) -> MultiPolygonZ<T> {
+        make_valid(&[self])
+    }
+}
+
+impl<T: BoolOpsNum + 'static> MakeValid for MultiPolygonZ<T> {
+    type Scalar = T;
+
+    fn make_valid(&self) -> MultiPolygonZ<T> {
+        make_valid(&self.0.iter().collect::<Vec<_>>())
+    }
+}
+
+fn make_valid<T: BoolOpsNum + 'static>(polygons: &[&PolygonZ<T>]) -> MultiPolygonZ<T> {
+    let cleaned: Vec<PolygonZ<T>> = polygons
+        .iter()
+        .filter_map(|polygon| {
+            let exterior = clean_ring(polygon.exterior())?;
+            let interiors = polygon.interiors().iter().filter_map(clean_ring).collect();
+            Some(PolygonZ::new(exterior, interiors))
+        })
+        .collect();
+    // the plane is fitted to the vertices rather than to the rings' areas, which
+    // cancel out in a bow tie
+    let coords: Vec<CoordZ<T>> = cleaned
+        .iter()
+        .flat_map(|polygon| polygon.exterior().0.iter().copied())
+        .collect();
+    let axes = fit_plane(&coords).map_or(Axes::new(2), |plane| Axes::facing(plane.normal));
+
+    // the dropped coordinate is restored from the rings, in the projection
+    let mut source = NearestZ::new();
+    for polygon in &cleaned {
+        source.add_polygon(&polygon.map_coords(|coord| axes.project(coord)));
+    }
+    let project = |ring: &LineStringZ<T>| -> LineString<T> {
+        ring.0
+            .iter()
+            .map(|&coord| {
+                let projected = axes.project(coord);
+                Coord {
+                    x: projected.x,
+                    y: projected.y,
+                }
+            })
+            .collect()
+    };
+    let parts: Vec<MultiPolygon<T>> = cleaned
+        .iter()
+        .map(|polygon| {
+            let holes: Vec<_> = polygon
+                .interiors()
+                .iter()
+                .map(|ring| fill(project(ring)))
+                .collect();
+            fill(project(polygon.exterior())).difference(&unary_union(&holes))
+        })
+        .collect();
+    let merged = unary_union(&parts);
+    MultiPolygonZ(
+        source
+            .lift_multi_polygon(&merged)
+            .0
+            .iter()
+            .map(|polygon| polygon.map_coords(|coord| axes.unproject(coord)))
+            .collect(),
+    )
+}
+
+/// The area a ring encloses an odd number of times, as valid polygons.
+fn fill<T: BoolOpsNum>(ring: LineString<T>) -> MultiPolygon<T> {
+    Polygon::new(ring, Vec::new()).boolean_op(&MultiPolygon::new(Vec::new()), OpType::Union)
+}
+
+/// The ring closed, without repeated vertices or spikes, or `None` if fewer than
+/// three vertices are left.
+///
+/// Only spikes returning exactly to the vertex they left are removed here. Those
+/// that come back elsewhere along the same line enclose no area, and are dropped
+/// when the ring is rebuilt.
+fn clean_ring<T: BoolOpsNum>(ring: &LineStringZ<T>) -> Option<LineStringZ<T>> {
+    let mut coords: Vec<CoordZ<T>> = Vec::with_capacity(ring.0.len());
+    for &coord in &ring.0 {
+        match coords.as_slice() {
+            [.., last] if *last == coord => {}
+            // back at the vertex before the last, which was the tip of a spike
+            [.., before, _] if *before == coord => {
+                coords.pop();
+            }
+            _ => coords.push(coord),
+        }
+    }
+    // the same again where the ring wraps around
+    loop {
+        match coords.as_slice() {
+            [first, .., last] if first == last => {
+                coords.pop();
+            }
+            [first, .., before, _] if first == before => {
+                coords.pop();
+            }
+            [_, second, .., last] if second == last => {
+                coords.remove(0);
+            }
+            _ => break,
+        }
+    }
+    if coords.len() < 3 {
+        return None;
+    }
+    coords.push(coords[0]);
+    Some(LineStringZ(coords))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::xy::polygon_xy;
+    use geo::{Area, Validation};
+
+    fn area(polygons: &MultiPolygonZ) -> f64 {
+        polygons
+            .0
+            .iter()
+            .map(|polygon| polygon_xy(polygon).unsigned_area())
+            .sum()
+    }
+
+    #[test]
+    fn self_intersections() {
+        // a bow tie on a slope where z = x
+        let bow_tie: PolygonZ = wkt! { POLYGON Z((0. 0. 0.,2. 2. 2.,2. 0. 2.,0. 2. 0.,0. 0. 0.)) };
+        let valid = bow_tie.make_valid();
+        assert_eq!(valid.0.len(), 2);
+        assert_eq!(area(&valid), 2.);
+        for polygon in &valid.0 {
+            assert!(polygon_xy(polygon).is_valid());
+            assert!(polygon.exterior().coords().all(|coord| coord.z == coord.x));
+        }
+        // the crossing, new in both triangles
+        let crossing = CoordZ::from((1., 1., 1.));
+        assert!(valid
+            .0
+            .iter()
+            .all(|polygon| polygon.exterior().0.contains(&crossing)));
+
+        // the same in a vertical wall, which the XY projection would flatten
+        let wall: PolygonZ = wkt! { POLYGON Z((0. 5. 0.,2. 5. 2.,2. 5. 0.,0. 5. 2.,0. 5. 0.)) };
+        let valid = wall.make_valid();
+        assert_eq!(valid.0.len(), 2);
+        assert!(valid
+            .0
+            .iter()
+            .flat_map(|polygon| polygon.exterior().coords())
+            .all(|coord| coord.y == 5.));
+
+        // already valid, unchanged but for where the ring starts
+        let square: PolygonZ = wkt! { POLYGON Z((0. 0. 1.,4. 0. 1.,4. 4. 1.,0. 4. 1.,0. 0. 1.)) };
+        let valid = square.make_valid();
+        assert_eq!(valid.0.len(), 1);
+        assert_eq!(area(&valid), 16.);
+        assert_eq!(valid.0[0].exterior().0.len(), 5);
+    }
+
+    #[test]
+    fn spikes_and_repeats() {
+        let ring = LineStringZ::from(vec![
+            (0., 0., 1.),
+            (4., 0., 1.),
+            (4., 0., 1.),
+            (4., 4., 1.),
+            // a spike out to (6, 6) and back
+            (6., 6., 1.),
+            (4., 4., 1.),
+            (0., 4., 1.),
+        ]);
+        assert_eq!(clean_ring(&ring).unwrap().0.len(), 5);
+        let valid = PolygonZ::new(ring, Vec::new()).make_valid();
+        assert_eq!(valid.0.len(), 1);
+        assert_eq!(valid.0[0].exterior().0.len(), 5);
+        assert_eq!(area(&valid), 16.);
+
+        // spikes across the start of the ring
+        let ring = LineStringZ::from(vec![
+            (9., 9., 0.),
+            (0., 0., 0.),
+            (4., 0., 0.),
+            (0., 4., 0.),
+            (0., 0., 0.),
+        ]);
+        assert_eq!(clean_ring(&ring).unwrap().0.len(), 4);
+        assert!(clean_ring(&LineStringZ::from(vec![
+            (0., 0., 0.),
+            (1., 1., 1.),
+            (0., 0., 0.)
+        ]))
+        .is_none());
+        let flat: PolygonZ = wkt! { POLYGON Z((0. 0. 0.,1. 1. 1.,2. 2. 2.,0. 0. 0.)) };
+        assert!(flat.make_valid().0.is_empty());
+    }
+
+    #[test]
+    fn nesting() {
+        // one hole crossing the exterior, another outside it
+        let polygon: PolygonZ = wkt! {
+            POLYGON Z(
+                (0. 0. 1.,4. 0. 1.,4. 4. 1.,0. 4. 1.,0. 0. 1.),
+                (3. 1. 1.,5. 1. 1.,5. 2. 1.,3. 2. 1.,3. 1. 1.),
+                (10. 10. 1.,11. 10. 1.,11. 11. 1.,10. 10. 1.)
+            )
+        };
+        let valid = polygon.make_valid();
+        assert_eq!(valid.0.len(), 1);
+        assert!(valid.0[0].interiors().is_empty());
+        assert_eq!(area(&valid), 15.);
+
+        // overlapping members are merged
+        let multi: MultiPolygonZ = wkt! {
+            MULTIPOLYGON Z(
+                ((0. 0. 1.,4. 0. 1.,4. 4. 1.,0. 4. 1.,0. 0. 1.)),
+                ((2. 2. 1.,6. 2. 1.,6. 6. 1.,2. 6. 1.,2. 2. 1.)),
+                ((8. 8. 1.,9. 8. 1.,9. 9. 1.,8. 8. 1.))
+            )
+        };
+        let valid = multi.make_valid();
+        assert_eq!(valid.0.len(), 2);
+        assert_eq!(area(&valid), 28.5);
+        assert!(geo::MultiPolygon(valid.0.iter().map(polygon_xy).collect()).is_valid());
+    }
+}
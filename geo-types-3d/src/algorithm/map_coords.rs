@@ -0,0 +1,496 @@
+use alloc::vec::Vec;
+
+use geo_types::{
+    Coord, Line, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon, Rect,
+};
+
+use crate::{
+    CoordNum, CoordZ, Cube, Geometry, GeometryCollection, LineStringZ, LineStringZArray,
+    LineStringZSlice, LineZ, MultiLineStringZ, MultiPointZ, MultiPolygonZ, PointZ, PolygonZ,
+    PolygonZRef, Tagged, Triangle,
+};
+
+/// Map a function over all the coordinates of a geometry, returning a new geometry.
+///
+/// Planar members of a [`Geometry`] or [`GeometryCollection`] are passed to the
+/// function with `z = 0`, and keep only the x and y of the result.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::MapCoordsZ;
+/// use geo_types_3d::{coordZ, PointZ};
+///
+/// let point = PointZ::new(1.0, 2.0, 3.0);
+/// let lifted = point.map_coords(|c| coordZ! { x: c.x, y: c.y, z: c.z + 100.0 });
+/// assert_eq!(lifted, PointZ::new(1.0, 2.0, 103.0));
+///
+/// let rounded: PointZ<i32> = point.map_coords(|c| coordZ! { x: c.x as i32, y: c.y as i32, z: c.z as i32 });
+/// assert_eq!(rounded, PointZ::new(1, 2, 3));
+/// ```
+pub trait MapCoordsZ<T: CoordNum, NT: CoordNum> {
+    type Output;
+
+    fn map_coords(&self, func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy) -> Self::Output;
+}
+
+/// Map a function over all the coordinates of a geometry, in place.
+pub trait MapCoordsZInPlace<T: CoordNum> {
+    fn map_coords_in_place(&mut self, func: impl Fn(CoordZ<T>) -> CoordZ<T> + Copy);
+}
+
+impl<T: CoordNum, NT: CoordNum> MapCoordsZ<T, NT> for CoordZ<T> {
+    type Output = CoordZ<NT>;
+
+    fn map_coords(&self, func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy) -> Self::Output {
+        func(*self)
+    }
+}
+
+impl<T: CoordNum> MapCoordsZInPlace<T> for CoordZ<T> {
+    fn map_coords_in_place(&mut self, func: impl Fn(CoordZ<T>) -> CoordZ<T> + Copy) {
+        *self = func(*self)
+    }
+}
+
+impl<T: CoordNum, NT: CoordNum> MapCoordsZ<T, NT> for PointZ<T> {
+    type Output = PointZ<NT>;
+
+    fn map_coords(&self, func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy) -> Self::Output {
+        PointZ(func(self.0))
+    }
+}
+
+impl<T: CoordNum> MapCoordsZInPlace<T> for PointZ<T> {
+    fn map_coords_in_place(&mut self, func: impl Fn(CoordZ<T>) -> CoordZ<T> + Copy) {
+        self.0 = func(self.0)
+    }
+}
+
+impl<T: CoordNum, NT: CoordNum> MapCoordsZ<T, NT> for LineZ<T> {
+    type Output = LineZ<NT>;
+
+    fn map_coords(&self, func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy) -> Self::Output {
+        LineZ::new(func(self.start), func(self.end))
+    }
+}
+
+impl<T: CoordNum> MapCoordsZInPlace<T> for LineZ<T> {
+    fn map_coords_in_place(&mut self, func: impl Fn(CoordZ<T>) -> CoordZ<T> + Copy) {
+        self.start = func(self.start);
+        self.end = func(self.end);
+    }
+}
+
+impl<T: CoordNum, NT: CoordNum> MapCoordsZ<T, NT> for LineStringZ<T> {
+    type Output = LineStringZ<NT>;
+
+    fn map_coords(&self, func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy) -> Self::Output {
+        LineStringZ(self.0.iter().map(|&coord| func(coord)).collect())
+    }
+}
+
+impl<T: CoordNum> MapCoordsZInPlace<T> for LineStringZ<T> {
+    fn map_coords_in_place(&mut self, func: impl Fn(CoordZ<T>) -> CoordZ<T> + Copy) {
+        for coord in &mut self.0 {
+            *coord = func(*coord);
+        }
+    }
+}
+
+impl<T: CoordNum, NT: CoordNum> MapCoordsZ<T, NT> for PolygonZ<T> {
+    type Output = PolygonZ<NT>;
+
+    fn map_coords(&self, func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy) -> Self::Output {
+        PolygonZ::new(
+            self.exterior().map_coords(func),
+            self.interiors()
+                .iter()
+                .map(|ring| ring.map_coords(func))
+                .collect(),
+        )
+    }
+}
+
+impl<T: CoordNum> MapCoordsZInPlace<T> for PolygonZ<T> {
+    fn map_coords_in_place(&mut self, func: impl Fn(CoordZ<T>) -> CoordZ<T> + Copy) {
+        self.exterior_mut(|ring| ring.map_coords_in_place(func));
+        self.interiors_mut(|rings| {
+            for ring in rings {
+                ring.map_coords_in_place(func);
+            }
+        });
+    }
+}
+
+impl<T: CoordNum, NT: CoordNum, const N: usize> MapCoordsZ<T, NT> for LineStringZArray<T, N> {
+    type Output = LineStringZArray<NT, N>;
+
+    fn map_coords(&self, func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy) -> Self::Output {
+        let mut mapped = LineStringZArray::new();
+        for &coord in self.coords() {
+            mapped
+                .try_push(func(coord))
+                .expect("same capacity as the input");
+        }
+        mapped
+    }
+}
+
+impl<T: CoordNum, const N: usize> MapCoordsZInPlace<T> for LineStringZArray<T, N> {
+    fn map_coords_in_place(&mut self, func: impl Fn(CoordZ<T>) -> CoordZ<T> + Copy) {
+        for coord in self.as_mut_slice() {
+            *coord = func(*coord);
+        }
+    }
+}
+
+impl<T: CoordNum, NT: CoordNum> MapCoordsZ<T, NT> for LineStringZSlice<'_, T> {
+    type Output = LineStringZ<NT>;
+
+    fn map_coords(&self, func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy) -> Self::Output {
+        LineStringZ(self.0.iter().map(|&coord| func(coord)).collect())
+    }
+}
+
+impl<T: CoordNum, NT: CoordNum> MapCoordsZ<T, NT> for PolygonZRef<'_, T> {
+    type Output = PolygonZ<NT>;
+
+    fn map_coords(&self, func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy) -> Self::Output {
+        PolygonZ::new(
+            self.exterior().map_coords(func),
+            self.interiors().map(|ring| ring.map_coords(func)).collect(),
+        )
+    }
+}
+
+macro_rules! impl_multi {
+    ($type:ident) => {
+        impl<T: CoordNum, NT: CoordNum> MapCoordsZ<T, NT> for $type<T> {
+            type Output = $type<NT>;
+
+            fn map_coords(&self, func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy) -> Self::Output {
+                $type(
+                    self.0
+                        .iter()
+                        .map(|member| member.map_coords(func))
+                        .collect(),
+                )
+            }
+        }
+
+        impl<T: CoordNum> MapCoordsZInPlace<T> for $type<T> {
+            fn map_coords_in_place(&mut self, func: impl Fn(CoordZ<T>) -> CoordZ<T> + Copy) {
+                for member in &mut self.0 {
+                    member.map_coords_in_place(func);
+                }
+            }
+        }
+    };
+}
+
+impl_multi!(MultiPointZ);
+impl_multi!(MultiLineStringZ);
+impl_multi!(MultiPolygonZ);
+impl_multi!(GeometryCollection);
+
+impl<T: CoordNum, NT: CoordNum> MapCoordsZ<T, NT> for Triangle<T> {
+    type Output = Triangle<NT>;
+
+    fn map_coords(&self, func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy) -> Self::Output {
+        Triangle(func(self.0), func(self.1), func(self.2))
+    }
+}
+
+impl<T: CoordNum> MapCoordsZInPlace<T> for Triangle<T> {
+    fn map_coords_in_place(&mut self, func: impl Fn(CoordZ<T>) -> CoordZ<T> + Copy) {
+        *self = self.map_coords(func)
+    }
+}
+
+impl<T: CoordNum, NT: CoordNum> MapCoordsZ<T, NT> for Cube<T> {
+    type Output = Cube<NT>;
+
+    /// The result is the cube spanned by the two mapped corners.
+    fn map_coords(&self, func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy) -> Self::Output {
+        Cube::new(func(self.min()), func(self.max()))
+    }
+}
+
+impl<T: CoordNum> MapCoordsZInPlace<T> for Cube<T> {
+    fn map_coords_in_place(&mut self, func: impl Fn(CoordZ<T>) -> CoordZ<T> + Copy) {
+        *self = self.map_coords(func)
+    }
+}
+
+/// Passes a planar coordinate through `func` at `z = 0`.
+fn flat<T: CoordNum, NT: CoordNum>(
+    func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy,
+) -> impl Fn(Coord<T>) -> Coord<NT> + Copy {
+    move |coord: Coord<T>| {
+        let mapped = func(CoordZ {
+            x: coord.x,
+            y: coord.y,
+            z: T::zero(),
+        });
+        Coord {
+            x: mapped.x,
+            y: mapped.y,
+        }
+    }
+}
+
+fn line_string<T: CoordNum, NT: CoordNum>(
+    line_string: &LineString<T>,
+    func: impl Fn(Coord<T>) -> Coord<NT> + Copy,
+) -> LineString<NT> {
+    LineString(line_string.0.iter().map(|&coord| func(coord)).collect())
+}
+
+fn polygon<T: CoordNum, NT: CoordNum>(
+    polygon: &Polygon<T>,
+    func: impl Fn(Coord<T>) -> Coord<NT> + Copy,
+) -> Polygon<NT> {
+    Polygon::new(
+        line_string(polygon.exterior(), func),
+        polygon
+            .interiors()
+            .iter()
+            .map(|ring| line_string(ring, func))
+            .collect(),
+    )
+}
+
+impl<T: CoordNum, NT: CoordNum> MapCoordsZ<T, NT> for Geometry<T> {
+    type Output = Geometry<NT>;
+
+    fn map_coords(&self, func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy) -> Self::Output {
+        let flat = flat(func);
+        match self {
+            Geometry::Point(g) => Geometry::Point(Point(flat(g.0))),
+            Geometry::Line(g) => Geometry::Line(Line::new(flat(g.start), flat(g.end))),
+            Geometry::LineString(g) => Geometry::LineString(line_string(g, flat)),
+            Geometry::Polygon(g) => Geometry::Polygon(polygon(g, flat)),
+            Geometry::MultiPoint(g) => {
+                Geometry::MultiPoint(MultiPoint(g.0.iter().map(|p| Point(flat(p.0))).collect()))
+            }
+            Geometry::MultiLineString(g) => Geometry::MultiLineString(MultiLineString(
+                g.0.iter().map(|ls| line_string(ls, flat)).collect(),
+            )),
+            Geometry::MultiPolygon(g) => Geometry::MultiPolygon(MultiPolygon(
+                g.0.iter().map(|p| polygon(p, flat)).collect::<Vec<_>>(),
+            )),
+            Geometry::Rect(g) => Geometry::Rect(Rect::new(flat(g.min()), flat(g.max()))),
+            Geometry::PointZ(g) => Geometry::PointZ(g.map_coords(func)),
+            Geometry::LineZ(g) => Geometry::LineZ(g.map_coords(func)),
+            Geometry::LineStringZ(g) => Geometry::LineStringZ(g.map_coords(func)),
+            Geometry::PolygonZ(g) => Geometry::PolygonZ(g.map_coords(func)),
+            Geometry::MultiPointZ(g) => Geometry::MultiPointZ(g.map_coords(func)),
+            Geometry::MultiLineStringZ(g) => Geometry::MultiLineStringZ(g.map_coords(func)),
+            Geometry::MultiPolygonZ(g) => Geometry::MultiPolygonZ(g.map_coords(func)),
+            Geometry::GeometryCollection(g) => Geometry::GeometryCollection(g.map_coords(func)),
+        }
+    }
+}
+
+impl<T: CoordNum> MapCoordsZInPlace<T> for Geometry<T> {
+    fn map_coords_in_place(&mut self, func: impl Fn(CoordZ<T>) -> CoordZ<T> + Copy) {
+        match self {
+            Geometry::PointZ(g) => g.map_coords_in_place(func),
+            Geometry::LineZ(g) => g.map_coords_in_place(func),
+            Geometry::LineStringZ(g) => g.map_coords_in_place(func),
+            Geometry::PolygonZ(g) => g.map_coords_in_place(func),
+            Geometry::MultiPointZ(g) => g.map_coords_in_place(func),
+            Geometry::MultiLineStringZ(g) => g.map_coords_in_place(func),
+            Geometry::MultiPolygonZ(g) => g.map_coords_in_place(func),
+            Geometry::GeometryCollection(g) => g.map_coords_in_place(func),
+            // planar geometries are rebuilt
+            _ => *self = self.map_coords(func),
+        }
+    }
+}
+
+impl<T: CoordNum, NT: CoordNum, G: MapCoordsZ<T, NT>, D: Clone> MapCoordsZ<T, NT> for Tagged<G, D> {
+    type Output = Tagged<G::Output, D>;
+
+    fn map_coords(&self, func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy) -> Self::Output {
+        Tagged::new(self.geometry.map_coords(func), self.data.clone())
+    }
+}
+
+impl<T: CoordNum, G: MapCoordsZInPlace<T>, D> MapCoordsZInPlace<T> for Tagged<G, D> {
+    fn map_coords_in_place(&mut self, func: impl Fn(CoordZ<T>) -> CoordZ<T> + Copy) {
+        self.geometry.map_coords_in_place(func)
+    }
+}
+
+#[cfg(feature = "multithreading")]
+pub use parallel::{ParMapCoordsZ, ParMapCoordsZInPlace};
+
+#[cfg(feature = "multithreading")]
+mod parallel {
+    use rayon::prelude::*;
+
+    use super::{MapCoordsZ, MapCoordsZInPlace};
+    use crate::{
+        CoordNum, CoordZ, GeometryCollection, LineStringZ, MultiLineStringZ, MultiPointZ,
+        MultiPolygonZ,
+    };
+
+    /// Like [`MapCoordsZ`], but spreads the work over the rayon thread pool.
+    ///
+    /// Line strings are split by coordinate, and multi-geometries and collections by
+    /// member.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::algorithm::ParMapCoordsZ;
+    /// use geo_types_3d::{coordZ, LineStringZ};
+    ///
+    /// let line_string: LineStringZ = (0..10_000).map(|i| (i as f64, 0.0, 0.0)).collect();
+    /// let raised = line_string.par_map_coords(|c| coordZ! { x: c.x, y: c.y, z: c.x / 2.0 });
+    /// assert_eq!(raised.0[9_000].z, 4_500.0);
+    /// ```
+    pub trait ParMapCoordsZ<T: CoordNum, NT: CoordNum> {
+        type Output;
+
+        fn par_map_coords(
+            &self,
+            func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy + Send + Sync,
+        ) -> Self::Output;
+    }
+
+    /// Like [`MapCoordsZInPlace`], but spreads the work over the rayon thread pool.
+    pub trait ParMapCoordsZInPlace<T: CoordNum> {
+        fn par_map_coords_in_place(
+            &mut self,
+            func: impl Fn(CoordZ<T>) -> CoordZ<T> + Copy + Send + Sync,
+        );
+    }
+
+    impl<T: CoordNum + Send + Sync, NT: CoordNum + Send> ParMapCoordsZ<T, NT> for LineStringZ<T> {
+        type Output = LineStringZ<NT>;
+
+        fn par_map_coords(
+            &self,
+            func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy + Send + Sync,
+        ) -> Self::Output {
+            LineStringZ(self.0.par_iter().map(|&coord| func(coord)).collect())
+        }
+    }
+
+    impl<T: CoordNum + Send + Sync> ParMapCoordsZInPlace<T> for LineStringZ<T> {
+        fn par_map_coords_in_place(
+            &mut self,
+            func: impl Fn(CoordZ<T>) -> CoordZ<T> + Copy + Send + Sync,
+        ) {
+            self.0
+                .par_iter_mut()
+                .for_each(|coord| *coord = func(*coord));
+        }
+    }
+
+    macro_rules! impl_par_multi {
+        ($type:ident) => {
+            impl<T: CoordNum + Send + Sync, NT: CoordNum + Send> ParMapCoordsZ<T, NT> for $type<T> {
+                type Output = $type<NT>;
+
+                fn par_map_coords(
+                    &self,
+                    func: impl Fn(CoordZ<T>) -> CoordZ<NT> + Copy + Send + Sync,
+                ) -> Self::Output {
+                    $type(
+                        self.0
+                            .par_iter()
+                            .map(|member| member.map_coords(func))
+                            .collect(),
+                    )
+                }
+            }
+
+            impl<T: CoordNum + Send + Sync> ParMapCoordsZInPlace<T> for $type<T> {
+                fn par_map_coords_in_place(
+                    &mut self,
+                    func: impl Fn(CoordZ<T>) -> CoordZ<T> + Copy + Send + Sync,
+                ) {
+                    self.0
+                        .par_iter_mut()
+                        .for_each(|member| member.map_coords_in_place(func));
+                }
+            }
+        };
+    }
+
+    impl_par_multi!(MultiPointZ);
+    impl_par_multi!(MultiLineStringZ);
+    impl_par_multi!(MultiPolygonZ);
+    impl_par_multi!(GeometryCollection);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn mixed_collection() {
+        let mut collection = GeometryCollection(vec![
+            Geometry::Point(geo_types::Point::new(1., 2.)),
+            Geometry::PolygonZ(polygon_z![
+                (x: 0., y: 0., z: 1.),
+                (x: 1., y: 0., z: 1.),
+                (x: 0., y: 1., z: 1.),
+            ]),
+        ]);
+        let shift = |c: CoordZ<f64>| CoordZ {
+            x: c.x + 10.,
+            y: c.y,
+            z: c.z + 5.,
+        };
+        let shifted = collection.map_coords(shift);
+        assert_eq!(shifted[0], Geometry::Point(geo_types::Point::new(11., 2.)));
+        assert_eq!(
+            shifted[1],
+            Geometry::PolygonZ(polygon_z![
+                (x: 10., y: 0., z: 6.),
+                (x: 11., y: 0., z: 6.),
+                (x: 10., y: 1., z: 6.),
+            ])
+        );
+        collection.map_coords_in_place(shift);
+        assert_eq!(collection, shifted);
+
+        let cube = Cube::new((0., 0., 0.), (1., 1., 1.));
+        let flipped = cube.map_coords(|c| CoordZ {
+            x: -c.x,
+            y: c.y,
+            z: c.z,
+        });
+        assert_eq!(flipped, Cube::new((-1., 0., 0.), (0., 1., 1.)));
+    }
+
+    #[cfg(feature = "multithreading")]
+    #[test]
+    fn parallel_matches_sequential() {
+        let polygons: MultiPolygonZ<f64> = (0..100)
+            .map(|i| {
+                let i = i as f64;
+                polygon_z![
+                    (x: i, y: 0., z: 1.),
+                    (x: i + 1., y: 0., z: 2.),
+                    (x: i, y: 1., z: 3.),
+                ]
+            })
+            .collect();
+        let func = |c: CoordZ<f64>| CoordZ {
+            x: c.y,
+            y: c.x,
+            z: c.z * 2.,
+        };
+        assert_eq!(polygons.par_map_coords(func), polygons.map_coords(func));
+        let mut in_place = polygons.clone();
+        in_place.par_map_coords_in_place(func);
+        assert_eq!(in_place, polygons.map_coords(func));
+    }
+}
@@ -0,0 +1,439 @@
+use alloc::collections::{BTreeMap, BinaryHeap};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::algorithm::linalg::{distance, dot};
+use crate::index::Bvh;
+use crate::{CoordFloat, CoordZ, Cube, LineStringZ, LineZ, MultiLineStringZ, PointZ};
+
+/// Where [`MultiLineStringZ::map_match`] placed a fix of a track on the network.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatchedPoint<T: CoordFloat = f64> {
+    /// The index of the line string of the network the fix was placed on.
+    pub edge: usize,
+    /// The distance along the line string, from its start to the matched point.
+    pub offset: T,
+    /// The matched point.
+    pub point: PointZ<T>,
+}
+
+impl<T: CoordFloat> MultiLineStringZ<T> {
+    /// Snaps a noisy track, such as one logged by GPS, onto the network of roads,
+    /// rails or flight corridors formed by the line strings, with a hidden Markov
+    /// model after Newson and Krumm (2009).
+    ///
+    /// Each fix of the track may be placed at the nearest point of any line string
+    /// within `search_radius` of it. The places are chosen together rather than one
+    /// by one, so that they are close to their fixes, with `sigma` the standard
+    /// deviation of the noise in the fixes, and also joined by routes along the
+    /// network about as long as the way between the fixes. A fix is then kept on
+    /// the road it is travelling along even where it strays nearer another road,
+    /// or a bridge above it, that the track can't reach. Routes more than about
+    /// twice as long as the way between two fixes aren't followed.
+    ///
+    /// The line strings are joined where their ends have exactly equal
+    /// coordinates, as after [`node`](MultiLineStringZ::node), and can be travelled
+    /// either way. Distances are in 3D.
+    ///
+    /// Returns the place of each fix, or `None` for fixes with no line string
+    /// within `search_radius`, which are skipped. Where no route joins one fix to
+    /// the next, the matching starts afresh from the next.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{line_string_z, MultiLineStringZ};
+    ///
+    /// // a road, with a bridge passing over it
+    /// let network: MultiLineStringZ = MultiLineStringZ::new(vec![
+    ///     line_string_z![(x: 0., y: 0., z: 0.), (x: 100., y: 0., z: 0.)],
+    ///     line_string_z![(x: 50., y: -50., z: 6.), (x: 50., y: 50., z: 6.)],
+    /// ]);
+    /// // driving along the road, with the height of one fix nearer the bridge
+    /// let track = line_string_z![
+    ///     (x: 20., y: 1., z: 1.),
+    ///     (x: 40., y: -1., z: 2.),
+    ///     (x: 50., y: 0., z: 4.),
+    ///     (x: 60., y: 1., z: 1.),
+    /// ];
+    /// let matched = network.map_match(&track, 5., 2.);
+    /// assert!(matched.iter().all(|place| place.unwrap().edge == 0));
+    /// assert_eq!(matched[2].unwrap().offset, 50.);
+    /// ```
+    pub fn map_match(
+        &self,
+        track: &LineStringZ<T>,
+        search_radius: T,
+        sigma: T,
+    ) -> Vec<Option<MatchedPoint<T>>> {
+        let network = Network::new(&self.0);
+        let candidates: Vec<Vec<MatchedPoint<T>>> = track
+            .0
+            .iter()
+            .map(|&fix| network.candidates(fix, search_radius))
+            .collect();
+        let two = T::one() + T::one();
+        let emission = |fix: usize, candidate: &MatchedPoint<T>| {
+            let d = distance(track.0[fix], candidate.point.0) / sigma;
+            d * d / two
+        };
+
+        let mut matched = vec![None; track.0.len()];
+        // for each fix, the fix before it in the same run and, for each of its
+        // candidates, the best candidate of that fix to come from
+        let mut previous_fix: Vec<Option<usize>> = vec![None; track.0.len()];
+        let mut from: Vec<Vec<usize>> = vec![Vec::new(); track.0.len()];
+        // the last fix of the current run, and the least cost of a path through
+        // the run to each of its candidates
+        let mut last: Option<(usize, Vec<T>)> = None;
+        for fix in 0..track.0.len() {
+            if candidates[fix].is_empty() {
+                continue;
+            }
+            let emissions: Vec<T> = candidates[fix]
+                .iter()
+                .map(|candidate| emission(fix, candidate))
+                .collect();
+            let Some((before, costs)) = last.take() else {
+                last = Some((fix, emissions));
+                continue;
+            };
+            let straight = distance(track.0[before], track.0[fix]);
+            let limit = two * (straight + two * search_radius);
+            let routes = network.routes(&candidates[before], &candidates[fix], limit);
+            let mut next = Vec::with_capacity(emissions.len());
+            let mut best_from = Vec::with_capacity(emissions.len());
+            for (to, emission) in emissions.iter().enumerate() {
+                let best = routes
+                    .iter()
+                    .zip(&costs)
+                    .enumerate()
+                    .filter_map(|(k, (routes, &cost))| {
+                        routes[to].map(|route| (k, cost + (route - straight).abs() / sigma))
+                    })
+                    .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+                let (k, cost) = best.unwrap_or((0, T::infinity()));
+                next.push(cost + *emission);
+                best_from.push(k);
+            }
+            if next.iter().all(|cost| cost.is_infinite()) {
+                // no route joins the fixes, so the run ends
+                backtrack(
+                    &candidates,
+                    &previous_fix,
+                    &from,
+                    before,
+                    &costs,
+                    &mut matched,
+                );
+                last = Some((fix, emissions));
+            } else {
+                previous_fix[fix] = Some(before);
+                from[fix] = best_from;
+                last = Some((fix, next));
+            }
+        }
+        if let Some((fix, costs)) = last {
+            backtrack(&candidates, &previous_fix, &from, fix, &costs, &mut matched);
+        }
+        matched
+    }
+}
+
+/// Places the fixes of the run ending at `fix` on the candidates of the best path
+/// through it.
+fn backtrack<T: CoordFloat>(
+    candidates: &[Vec<MatchedPoint<T>>],
+    previous_fix: &[Option<usize>],
+    from: &[Vec<usize>],
+    fix: usize,
+    costs: &[T],
+    matched: &mut [Option<MatchedPoint<T>>],
+) {
+    let mut fix = fix;
+    let mut candidate = (0..costs.len())
+        .min_by(|&a, &b| costs[a].partial_cmp(&costs[b]).unwrap_or(Ordering::Equal))
+        .unwrap_or(0);
+    loop {
+        matched[fix] = Some(candidates[fix][candidate]);
+        let Some(before) = previous_fix[fix] else {
+            break;
+        };
+        candidate = from[fix][candidate];
+        fix = before;
+    }
+}
+
+/// The line strings as a graph, with a node at each end of the line strings and
+/// the line strings as edges.
+struct Network<T: CoordFloat> {
+    /// The distance along each line string to each of its vertices.
+    measures: Vec<Vec<T>>,
+    /// The start and end nodes of each line string, if it has coordinates.
+    ends: Vec<Option<[usize; 2]>>,
+    /// The line strings from each node, as the node at their other end and their
+    /// length.
+    adjacent: Vec<Vec<(usize, T)>>,
+    segments: Bvh<T, LineZ<T>>,
+    /// The line string of each segment, and the index of its first vertex.
+    segment_of: Vec<(usize, usize)>,
+}
+
+impl<T: CoordFloat> Network<T> {
+    fn new(lines: &[LineStringZ<T>]) -> Self {
+        let measures: Vec<Vec<T>> = lines
+            .iter()
+            .map(|line| {
+                let mut measure = T::zero();
+                let mut previous = line.0.first().copied();
+                line.0
+                    .iter()
+                    .map(|&coord| {
+                        measure = measure + distance(previous.unwrap_or(coord), coord);
+                        previous = Some(coord);
+                        measure
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut nodes: Vec<CoordZ<T>> = Vec::new();
+        let mut node = |coord: CoordZ<T>| match nodes.iter().position(|n| *n == coord) {
+            Some(index) => index,
+            None => {
+                nodes.push(coord);
+                nodes.len() - 1
+            }
+        };
+        let ends: Vec<Option<[usize; 2]>> = lines
+            .iter()
+            .map(|line| {
+                let (&first, &last) = line.0.first().zip(line.0.last())?;
+                Some([node(first), node(last)])
+            })
+            .collect();
+        let mut adjacent = vec![Vec::new(); nodes.len()];
+        for (ends, measure) in ends.iter().zip(&measures) {
+            if let (Some([start, end]), Some(&length)) = (ends, measure.last()) {
+                adjacent[*start].push((*end, length));
+                adjacent[*end].push((*start, length));
+            }
+        }
+        let (segments, segment_of) = lines
+            .iter()
+            .enumerate()
+            .flat_map(|(index, line)| {
+                line.0
+                    .windows(2)
+                    .enumerate()
+                    .map(move |(vertex, pair)| (LineZ::new(pair[0], pair[1]), (index, vertex)))
+            })
+            .unzip();
+        Self {
+            measures,
+            ends,
+            adjacent,
+            segments: Bvh::new(segments),
+            segment_of,
+        }
+    }
+
+    /// The nearest point of each line string within `radius` of `fix`.
+    fn candidates(&self, fix: CoordZ<T>, radius: T) -> Vec<MatchedPoint<T>> {
+        let reach = CoordZ::from((radius, radius, radius));
+        let mut nearest: BTreeMap<usize, (T, MatchedPoint<T>)> = BTreeMap::new();
+        for (id, segment) in self.segments.query(&Cube::new(fix - reach, fix + reach)) {
+            let (line, vertex) = self.segment_of[id];
+            let along = segment.end - segment.start;
+            let length_squared = dot(along, along);
+            let t = if length_squared > T::zero() {
+                (dot(fix - segment.start, along) / length_squared)
+                    .max(T::zero())
+                    .min(T::one())
+            } else {
+                T::zero()
+            };
+            let point = segment.start + along * t;
+            let d = distance(fix, point);
+            if d > radius || nearest.get(&line).is_some_and(|(best, _)| *best <= d) {
+                continue;
+            }
+            let measures = &self.measures[line];
+            let offset = measures[vertex] + (measures[vertex + 1] - measures[vertex]) * t;
+            let candidate = MatchedPoint {
+                edge: line,
+                offset,
+                point: PointZ(point),
+            };
+            nearest.insert(line, (d, candidate));
+        }
+        nearest
+            .into_values()
+            .map(|(_, candidate)| candidate)
+            .collect()
+    }
+
+    /// The length of the shortest route from each of `starts` to each of `ends`,
+    /// if there is one no longer than `limit`.
+    fn routes(
+        &self,
+        starts: &[MatchedPoint<T>],
+        ends: &[MatchedPoint<T>],
+        limit: T,
+    ) -> Vec<Vec<Option<T>>> {
+        starts
+            .iter()
+            .map(|start| {
+                let length = self.length(start.edge);
+                let [first, last] = self.ends[start.edge].unwrap();
+                let from_first = self.distances(first, limit);
+                let from_last = self.distances(last, limit);
+                ends.iter()
+                    .map(|end| {
+                        let direct =
+                            (start.edge == end.edge).then(|| (end.offset - start.offset).abs());
+                        let [end_first, end_last] = self.ends[end.edge].unwrap();
+                        let end_length = self.length(end.edge);
+                        let via = [
+                            (start.offset, &from_first),
+                            (length - start.offset, &from_last),
+                        ]
+                        .into_iter()
+                        .flat_map(|(leave, distances)| {
+                            [(end_first, end.offset), (end_last, end_length - end.offset)]
+                                .into_iter()
+                                .filter_map(move |(node, arrive)| {
+                                    distances.get(&node).map(|&d| leave + d + arrive)
+                                })
+                        });
+                        direct
+                            .into_iter()
+                            .chain(via)
+                            .filter(|&route| route <= limit)
+                            .min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The distance along the network from `source` to each node within `limit`
+    /// of it, by Dijkstra's algorithm.
+    fn distances(&self, source: usize, limit: T) -> BTreeMap<usize, T> {
+        let mut settled = BTreeMap::new();
+        let mut queue = BinaryHeap::from([Visit {
+            distance: T::zero(),
+            node: source,
+        }]);
+        while let Some(Visit { distance, node }) = queue.pop() {
+            if settled.contains_key(&node) {
+                continue;
+            }
+            settled.insert(node, distance);
+            for &(next, length) in &self.adjacent[node] {
+                let distance = distance + length;
+                if distance <= limit && !settled.contains_key(&next) {
+                    queue.push(Visit {
+                        distance,
+                        node: next,
+                    });
+                }
+            }
+        }
+        settled
+    }
+
+    fn length(&self, line: usize) -> T {
+        self.measures[line].last().copied().unwrap_or_else(T::zero)
+    }
+}
+
+/// A node to settle in [`Network::distances`].
+struct Visit<T: CoordFloat> {
+    distance: T,
+    node: usize,
+}
+
+impl<T: CoordFloat> Ord for Visit<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed, as `BinaryHeap` is a max-heap
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+impl<T: CoordFloat> PartialOrd for Visit<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: CoordFloat> PartialEq for Visit<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: CoordFloat> Eq for Visit<T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn junction() {
+        // a road with a turning at x = 10, and a service road beside it that
+        // joins neither
+        let network: MultiLineStringZ = MultiLineStringZ::new(vec![
+            line_string_z![(x: 0., y: 0., z: 0.), (x: 10., y: 0., z: 0.)],
+            line_string_z![(x: 10., y: 0., z: 0.), (x: 20., y: 0., z: 0.)],
+            line_string_z![(x: 10., y: 0., z: 0.), (x: 10., y: 10., z: 0.)],
+            line_string_z![(x: 0., y: 2., z: 0.), (x: 20., y: 2., z: 0.)],
+        ]);
+        let track = line_string_z![
+            (x: 1., y: 0.2, z: 0.),
+            // nearer the service road
+            (x: 3., y: 1.1, z: 0.),
+            (x: 5., y: 0.3, z: 0.),
+            (x: 7., y: -0.2, z: 0.),
+            (x: 9.5, y: 0.8, z: 0.),
+            (x: 10.4, y: 3., z: 0.),
+            (x: 9.7, y: 6., z: 0.),
+            // lost
+            (x: 30., y: 30., z: 0.),
+            (x: 10.2, y: 9., z: 0.),
+        ];
+        let matched = network.map_match(&track, 2.5, 1.);
+        let edges: Vec<Option<usize>> = matched.iter().map(|m| m.map(|m| m.edge)).collect();
+        assert_eq!(edges[..4], [Some(0); 4]);
+        assert_eq!(edges[5..], [Some(2), Some(2), None, Some(2)]);
+        assert_eq!(matched[0].unwrap().offset, 1.);
+        assert_eq!(matched[1].unwrap().point, PointZ::new(3., 0., 0.));
+        assert_eq!(matched[8].unwrap().offset, 9.);
+
+        // jumping between roads that don't join, the track is matched in two runs
+        let apart: MultiLineStringZ = MultiLineStringZ::new(vec![
+            line_string_z![(x: 0., y: 0., z: 0.), (x: 20., y: 0., z: 0.)],
+            line_string_z![(x: 0., y: 6., z: 0.), (x: 20., y: 6., z: 0.)],
+        ]);
+        let track = line_string_z![
+            (x: 1., y: 0.2, z: 0.),
+            (x: 3., y: 0.4, z: 0.),
+            (x: 5., y: 5.8, z: 0.),
+            (x: 7., y: 6.2, z: 0.),
+        ];
+        let edges: Vec<usize> = apart
+            .map_match(&track, 2.5, 1.)
+            .iter()
+            .map(|m| m.unwrap().edge)
+            .collect();
+        assert_eq!(edges, [0, 0, 1, 1]);
+        assert!(MultiLineStringZ::<f64>::empty()
+            .map_match(&track, 2.5, 1.)
+            .iter()
+            .all(Option::is_none));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::algorithm::linalg::norm;
+use crate::{CoordFloat, CoordZ, MultiPointZ, PointZ};
+
+/// The most steps [`MultiPointZ::geometric_median`] takes.
+pub const MAX_ITERATIONS: usize = 1000;
+
+impl<T: CoordFloat> MultiPointZ<T> {
+    /// The mean of the points, each counted in proportion to its weight, or `None`
+    /// if there are no points or the weights sum to zero.
+    ///
+    /// # Panics
+    ///
+    /// If there isn't one weight for each point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{MultiPointZ, PointZ};
+    ///
+    /// let points = MultiPointZ::new(vec![PointZ::new(0., 0., 0.), PointZ::new(4., 8., 2.)]);
+    /// assert_eq!(points.weighted_centroid(&[3., 1.]), Some(PointZ::new(1., 2., 0.5)));
+    /// assert_eq!(points.weighted_centroid(&[0., 0.]), None);
+    /// ```
+    pub fn weighted_centroid(&self, weights: &[T]) -> Option<PointZ<T>> {
+        assert_eq!(
+            self.0.len(),
+            weights.len(),
+            "there must be one weight for each point"
+        );
+        let (sum, total) = self
+            .0
+            .iter()
+            .zip(weights)
+            .fold((CoordZ::zero(), T::zero()), |(sum, total), (point, &w)| {
+                (sum + point.0 * w, total + w)
+            });
+        (total != T::zero()).then(|| PointZ(sum / total))
+    }
+
+    /// The point minimising the sum of the distances to all the points, or `None`
+    /// if there are none.
+    ///
+    /// Unlike the mean, the geometric median isn't dragged far away by a few
+    /// points far from the rest. It is found by Weiszfeld's algorithm, with Vardi
+    /// and Zhang's correction for steps landing on one of the points, starting from
+    /// the mean and stopping once a step moves less than `tolerance`, or after
+    /// [`MAX_ITERATIONS`] steps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{MultiPointZ, PointZ};
+    ///
+    /// // the corners of a square, and a point far above it
+    /// let points: MultiPointZ = MultiPointZ::new(vec![
+    ///     PointZ::new(0., 0., 0.),
+    ///     PointZ::new(2., 0., 0.),
+    ///     PointZ::new(2., 2., 0.),
+    ///     PointZ::new(0., 2., 0.),
+    ///     PointZ::new(1., 1., 100.),
+    /// ]);
+    /// let median = points.geometric_median(1e-9).unwrap();
+    /// assert!((median.x() - 1.).abs() < 1e-6 && (median.y() - 1.).abs() < 1e-6);
+    /// assert!(median.z() < 1.);
+    /// ```
+    pub fn geometric_median(&self, tolerance: T) -> Option<PointZ<T>> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let count = T::from(self.0.len()).unwrap();
+        let mut median = self.0.iter().fold(CoordZ::zero(), |sum, p| sum + p.0) / count;
+        for _ in 0..MAX_ITERATIONS {
+            // the weighted mean of the points apart from those at `median`, each
+            // weighted by one over its distance, and the pull of those points
+            let mut weighted = CoordZ::zero();
+            let mut weights = T::zero();
+            let mut pull = CoordZ::zero();
+            let mut coincident = T::zero();
+            for point in &self.0 {
+                let delta = point.0 - median;
+                let distance = norm(delta);
+                if distance == T::zero() {
+                    coincident = coincident + T::one();
+                    continue;
+                }
+                weighted += point.0 / distance;
+                weights = weights + T::one() / distance;
+                pull += delta / distance;
+            }
+            if weights == T::zero() {
+                break;
+            }
+            let mut next = weighted / weights;
+            if coincident > T::zero() {
+                let strength = norm(pull);
+                // the point at `median` outweighs the pull of all the others
+                if strength <= coincident {
+                    break;
+                }
+                let share = coincident / strength;
+                next = next * (T::one() - share) + median * share;
+            }
+            let step = norm(next - median);
+            median = next;
+            if step <= tolerance {
+                break;
+            }
+        }
+        Some(PointZ(median))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn geometric_median() {
+        // a point repeated more than all the others together is the median
+        let mut points = vec![PointZ::new(1., 2., 3.); 4];
+        points.extend([
+            PointZ::new(5., 2., 3.),
+            PointZ::new(1., -3., 3.),
+            PointZ::new(1., 2., 10.),
+        ]);
+        let points = MultiPointZ::new(points);
+        let median = points.geometric_median(1e-12).unwrap();
+        assert_relative_eq!(median, PointZ::new(1., 2., 3.), epsilon = 1e-9);
+
+        // the median of a regular tetrahedron's corners is its centre
+        let tetrahedron = MultiPointZ::new(vec![
+            PointZ::new(1., 1., 1.),
+            PointZ::new(1., -1., -1.),
+            PointZ::new(-1., 1., -1.),
+            PointZ::new(-1., -1., 1.),
+        ]);
+        let median = tetrahedron.geometric_median(1e-12).unwrap();
+        assert_relative_eq!(median, PointZ::new(0., 0., 0.), epsilon = 1e-9);
+
+        // the sum of distances is smallest at the median
+        let scattered: MultiPointZ = (0..30)
+            .map(|i| {
+                let f = |k: usize| ((i * k) % 23) as f64;
+                PointZ::new(f(3), f(5) * 0.5, f(7) * f(2) * 0.1)
+            })
+            .collect();
+        let median = scattered.geometric_median(1e-12).unwrap();
+        let cost = |c: CoordZ| scattered.iter().map(|p| norm(p.0 - c)).sum::<f64>();
+        for delta in [(0.01, 0., 0.), (0., -0.01, 0.), (0., 0., 0.01)] {
+            let nudged = median.0 + CoordZ::from(delta);
+            assert!(cost(median.0) < cost(nudged));
+        }
+        assert_eq!(MultiPointZ::<f64>::empty().geometric_median(1e-9), None);
+    }
+}
@@ -0,0 +1,570 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use geo_types::{Coord, Line, LineString, Point, Polygon, Rect};
+#[cfg(feature = "multithreading")]
+use rayon::prelude::*;
+
+use crate::algorithm::linalg::{distance, dot};
+use crate::algorithm::BoundingCube;
+use crate::index::Bvh;
+use crate::{
+    CoordFloat, CoordZ, Cube, Geometry, GeometryVisitor, LineStringZ, LineZ, MultiPointZ, Plane,
+    PointZ, PolygonZ,
+};
+
+/// The closest points of two sets of geometries, as found by
+/// [`min_distance_indexed`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClosestPoints<T: CoordFloat = f64> {
+    /// The index of the closest geometry of the first set.
+    pub index_a: usize,
+    /// The closest point of that geometry.
+    pub point_a: PointZ<T>,
+    /// The index of the closest geometry of the second set.
+    pub index_b: usize,
+    /// The closest point of that geometry.
+    pub point_b: PointZ<T>,
+    pub distance: T,
+}
+
+/// The closest points between any geometry of `a` and any geometry of `b`, or
+/// `None` if either set is empty.
+///
+/// The geometries are broken into points, segments and polygon faces, which are
+/// indexed in a [`Bvh`] on each side, and pairs are searched by branch and bound
+/// with [`Bvh::closest_pair_with`]. Most pairs are never compared, so this scales
+/// to sets of millions of features, where comparing every pair wouldn't.
+///
+/// Polygons count with their interiors, taken to lie in the plane of their
+/// [normal](PolygonZ::normal), so that a point above a roof is as far from it
+/// as it is high. Planar geometries are taken to lie at `z = 0`. Where the sets
+/// touch or overlap, the distance is zero and the points are one of the places
+/// they meet.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::min_distance_indexed;
+/// use geo_types_3d::{line_string_z, polygon_z, Geometry, PointZ};
+///
+/// // a power line passing over two roofs
+/// let roofs: Vec<Geometry> = vec![
+///     polygon_z![
+///         (x: 0., y: 0., z: 5.),
+///         (x: 10., y: 0., z: 5.),
+///         (x: 10., y: 10., z: 5.),
+///         (x: 0., y: 10., z: 5.),
+///     ]
+///     .into(),
+///     polygon_z![
+///         (x: 20., y: 0., z: 8.),
+///         (x: 30., y: 0., z: 8.),
+///         (x: 30., y: 10., z: 8.),
+///         (x: 20., y: 10., z: 8.),
+///     ]
+///     .into(),
+/// ];
+/// let lines: Vec<Geometry> =
+///     vec![line_string_z![(x: -10., y: 5., z: 12.), (x: 40., y: 5., z: 12.)].into()];
+///
+/// let closest = min_distance_indexed(&roofs, &lines).unwrap();
+/// assert_eq!(closest.distance, 4.);
+/// assert_eq!(closest.index_a, 1);
+/// assert_eq!(closest.point_b.z(), 12.);
+/// ```
+pub fn min_distance_indexed<T: CoordFloat>(
+    a: &[Geometry<T>],
+    b: &[Geometry<T>],
+) -> Option<ClosestPoints<T>> {
+    let (a, b) = (Bvh::new(pieces(a)), Bvh::new(pieces(b)));
+    let (i, j, distance) =
+        a.closest_pair_with(&b, |p, q| closest(p, q).map(|(p, q)| distance(p, q)))?;
+    let (p, q) = (&a.geometries()[i], &b.geometries()[j]);
+    let (point_a, point_b) = closest(p, q)?;
+    Some(ClosestPoints {
+        index_a: p.feature,
+        point_a: PointZ(point_a),
+        index_b: q.feature,
+        point_b: PointZ(point_b),
+        distance,
+    })
+}
+
+/// The pairs of a geometry of `a` and a geometry of `b` within `epsilon` of one
+/// another, as their indices, in order of the index in `a` and then in `b`.
+///
+/// Distances are measured as for [`min_distance_indexed`]. The geometries of `b`
+/// are indexed in a [`Bvh`] up front, while those of `a` are joined one at a time
+/// as the iterator advances, so that a huge join can be streamed or stopped early
+/// without holding every pair at once.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::within_distance_join;
+/// use geo_types_3d::{line_string_z, Geometry, PointZ};
+///
+/// // tree tops that come within 3 m of a power line
+/// let trees: Vec<Geometry> = vec![
+///     PointZ::new(10., 2., 12.).into(),
+///     PointZ::new(20., 1., 5.).into(),
+///     PointZ::new(30., -1., 14.).into(),
+/// ];
+/// let line: Vec<Geometry> =
+///     vec![line_string_z![(x: 0., y: 0., z: 15.), (x: 40., y: 0., z: 15.)].into()];
+/// let close: Vec<(usize, usize)> = within_distance_join(&trees, &line, 3.).collect();
+/// assert_eq!(close, [(2, 0)]);
+/// ```
+pub fn within_distance_join<'a, T: CoordFloat>(
+    a: &'a [Geometry<T>],
+    b: &[Geometry<T>],
+    epsilon: T,
+) -> impl Iterator<Item = (usize, usize)> + 'a {
+    let index = Bvh::new(pieces(b));
+    let reach = CoordZ::from((epsilon, epsilon, epsilon));
+    a.iter().enumerate().flat_map(move |(i, geometry)| {
+        let mut found = BTreeSet::new();
+        for piece in pieces(core::slice::from_ref(geometry)) {
+            let bounds = Cube::new(piece.bounds.min() - reach, piece.bounds.max() + reach);
+            for (_, other) in index.query(&bounds) {
+                if !found.contains(&other.feature)
+                    && closest(&piece, other).is_some_and(|(p, q)| distance(p, q) <= epsilon)
+                {
+                    found.insert(other.feature);
+                }
+            }
+        }
+        found.into_iter().map(move |j| (i, j))
+    })
+}
+
+impl<T: CoordFloat> MultiPointZ<T> {
+    /// For each point, the index of the nearest of `geometries` and the distance
+    /// to it, or `None` if there are no geometries.
+    ///
+    /// The most common 3D spatial join, such as of sensor readings to the nearest
+    /// pipe or building. Distances are measured as for [`min_distance_indexed`],
+    /// through a [`Bvh`] over the geometries, built once for all the points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{line_string_z, Geometry, MultiPointZ, PointZ};
+    ///
+    /// // two pipes at different depths, one above the other
+    /// let pipes: Vec<Geometry> = vec![
+    ///     line_string_z![(x: 0., y: 0., z: -2.), (x: 100., y: 0., z: -2.)].into(),
+    ///     line_string_z![(x: 0., y: 0., z: -6.), (x: 100., y: 0., z: -6.)].into(),
+    /// ];
+    /// let readings: MultiPointZ = vec![(10., 1., -3.), (50., -1., -5.)].into();
+    /// let joined = readings.nearest_join(&pipes);
+    /// assert_eq!(joined, [Some((0, 2f64.sqrt())), Some((1, 2f64.sqrt()))]);
+    /// ```
+    pub fn nearest_join(&self, geometries: &[Geometry<T>]) -> Vec<Option<(usize, T)>> {
+        let index = Bvh::new(pieces(geometries));
+        self.0
+            .iter()
+            .map(|point| nearest(&index, point.0))
+            .collect()
+    }
+}
+
+#[cfg(feature = "multithreading")]
+impl<T: CoordFloat + Send + Sync> MultiPointZ<T> {
+    /// Like [`MultiPointZ::nearest_join`], but joins the points in parallel.
+    pub fn par_nearest_join(&self, geometries: &[Geometry<T>]) -> Vec<Option<(usize, T)>> {
+        let index = Bvh::new(pieces(geometries));
+        self.0
+            .par_iter()
+            .map(|point| nearest(&index, point.0))
+            .collect()
+    }
+}
+
+/// The feature of the piece nearest `coord`, and the distance to it.
+fn nearest<T: CoordFloat>(index: &Bvh<T, Piece<T>>, coord: CoordZ<T>) -> Option<(usize, T)> {
+    let point = Piece {
+        feature: 0,
+        shape: Shape::Segment(coord, coord),
+        bounds: Cube::new(coord, coord),
+    };
+    let (id, distance) = index.nearest_with(coord, |piece| {
+        closest(&point, piece).map(|(p, q)| self::distance(p, q))
+    })?;
+    Some((index.geometries()[id].feature, distance))
+}
+
+/// A piece of a geometry: a segment, which may be a single point, or the interior
+/// of a polygon, whose boundary is made of segments of its own.
+struct Piece<T: CoordFloat> {
+    feature: usize,
+    shape: Shape<T>,
+    bounds: Cube<T>,
+}
+
+enum Shape<T: CoordFloat> {
+    Segment(CoordZ<T>, CoordZ<T>),
+    Face(Face<T>),
+}
+
+impl<T: CoordFloat> BoundingCube<T> for Piece<T> {
+    type Output = Cube<T>;
+
+    fn bounding_cube(&self) -> Self::Output {
+        self.bounds
+    }
+}
+
+/// The interior of a polygon, as its plane and its rings projected onto the
+/// coordinate plane most parallel to it.
+struct Face<T: CoordFloat> {
+    plane: Plane<T>,
+    kept: [usize; 2],
+    rings: Vec<Vec<[T; 2]>>,
+}
+
+impl<T: CoordFloat> Face<T> {
+    fn new(polygon: &PolygonZ<T>) -> Option<Self> {
+        let normal = polygon.normal()?;
+        let plane = Plane::from_point_normal(polygon.exterior().0[0], normal)?;
+        let n = [normal.x.abs(), normal.y.abs(), normal.z.abs()];
+        let dropped = (0..3)
+            .rev()
+            .max_by(|&a, &b| n[a].partial_cmp(&n[b]).unwrap_or(Ordering::Equal))
+            .unwrap_or(2);
+        let kept = [(dropped + 1) % 3, (dropped + 2) % 3];
+        let rings = core::iter::once(polygon.exterior())
+            .chain(polygon.interiors())
+            .map(|ring| ring.0.iter().map(|&c| project(c, kept)).collect())
+            .collect();
+        Some(Self { plane, kept, rings })
+    }
+
+    /// Whether a point of the plane is inside the polygon, by the even-odd rule.
+    fn contains(&self, coord: CoordZ<T>) -> bool {
+        let [x, y] = project(coord, self.kept);
+        let mut inside = false;
+        for ring in &self.rings {
+            for pair in ring.windows(2) {
+                let ([x0, y0], [x1, y1]) = (pair[0], pair[1]);
+                if (y0 > y) != (y1 > y) && x < x0 + (y - y0) / (y1 - y0) * (x1 - x0) {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    /// The closest points of a segment and the interior, if they are closer than
+    /// the segment is to the boundary, or the boundary may be as close.
+    fn closest_to_segment(&self, a: CoordZ<T>, b: CoordZ<T>) -> Option<(CoordZ<T>, CoordZ<T>)> {
+        let (da, db) = (self.plane.signed_distance(a), self.plane.signed_distance(b));
+        let crosses = (da <= T::zero() && db >= T::zero()) || (da >= T::zero() && db <= T::zero());
+        if crosses && da != db {
+            let crossing = a + (b - a) * (da / (da - db));
+            if self.contains(crossing) {
+                return Some((crossing, crossing));
+            }
+        }
+        // otherwise the closest point of the segment to the interior is an end
+        [a, b]
+            .into_iter()
+            .map(|end| (end, self.plane.project(end)))
+            .filter(|&(_, foot)| self.contains(foot))
+            .min_by(|x, y| {
+                distance(x.0, x.1)
+                    .partial_cmp(&distance(y.0, y.1))
+                    .unwrap_or(Ordering::Equal)
+            })
+    }
+}
+
+/// The closest points of two pieces, or `None` if the boundary of a face is
+/// always at least as close, as between two faces.
+fn closest<T: CoordFloat>(p: &Piece<T>, q: &Piece<T>) -> Option<(CoordZ<T>, CoordZ<T>)> {
+    match (&p.shape, &q.shape) {
+        (&Shape::Segment(a, b), &Shape::Segment(c, d)) => Some(closest_segments(a, b, c, d)),
+        (&Shape::Segment(a, b), Shape::Face(face)) => face.closest_to_segment(a, b),
+        (Shape::Face(face), &Shape::Segment(a, b)) => face
+            .closest_to_segment(a, b)
+            .map(|(on_segment, on_face)| (on_face, on_segment)),
+        (Shape::Face(_), Shape::Face(_)) => None,
+    }
+}
+
+/// The closest points of the segments `a`–`b` and `c`–`d`, after Ericson's
+/// Real-Time Collision Detection.
+fn closest_segments<T: CoordFloat>(
+    a: CoordZ<T>,
+    b: CoordZ<T>,
+    c: CoordZ<T>,
+    d: CoordZ<T>,
+) -> (CoordZ<T>, CoordZ<T>) {
+    let clamp = |t: T| t.max(T::zero()).min(T::one());
+    let (d1, d2, r) = (b - a, d - c, a - c);
+    let (length_1, length_2, f) = (dot(d1, d1), dot(d2, d2), dot(d2, r));
+    let (s, t) = if length_1 == T::zero() && length_2 == T::zero() {
+        (T::zero(), T::zero())
+    } else if length_1 == T::zero() {
+        (T::zero(), clamp(f / length_2))
+    } else {
+        let e = dot(d1, r);
+        if length_2 == T::zero() {
+            (clamp(-e / length_1), T::zero())
+        } else {
+            let along = dot(d1, d2);
+            let denominator = length_1 * length_2 - along * along;
+            let s = if denominator > T::zero() {
+                clamp((along * f - e * length_2) / denominator)
+            } else {
+                T::zero()
+            };
+            let t = (along * s + f) / length_2;
+            if t < T::zero() {
+                (clamp(-e / length_1), T::zero())
+            } else if t > T::one() {
+                (clamp((along - e) / length_1), T::one())
+            } else {
+                (s, t)
+            }
+        }
+    };
+    (a + d1 * s, c + d2 * t)
+}
+
+/// The pieces of each geometry of `geometries`.
+fn pieces<T: CoordFloat>(geometries: &[Geometry<T>]) -> Vec<Piece<T>> {
+    let mut pieces = Pieces {
+        feature: 0,
+        pieces: Vec::new(),
+    };
+    for (feature, geometry) in geometries.iter().enumerate() {
+        pieces.feature = feature;
+        geometry.accept(&mut pieces);
+    }
+    pieces.pieces
+}
+
+struct Pieces<T: CoordFloat> {
+    feature: usize,
+    pieces: Vec<Piece<T>>,
+}
+
+impl<T: CoordFloat> Pieces<T> {
+    fn segment(&mut self, a: CoordZ<T>, b: CoordZ<T>) {
+        self.pieces.push(Piece {
+            feature: self.feature,
+            shape: Shape::Segment(a, b),
+            bounds: Cube::new(a, b),
+        });
+    }
+
+    fn line_string(&mut self, coords: &[CoordZ<T>]) {
+        match coords {
+            [] => {}
+            [coord] => self.segment(*coord, *coord),
+            coords => coords
+                .windows(2)
+                .for_each(|pair| self.segment(pair[0], pair[1])),
+        }
+    }
+
+    fn polygon(&mut self, polygon: &PolygonZ<T>) {
+        self.line_string(&polygon.exterior().0);
+        for ring in polygon.interiors() {
+            self.line_string(&ring.0);
+        }
+        if let (Some(face), Some(bounds)) = (Face::new(polygon), polygon.bounding_cube()) {
+            self.pieces.push(Piece {
+                feature: self.feature,
+                shape: Shape::Face(face),
+                bounds,
+            });
+        }
+    }
+}
+
+impl<T: CoordFloat> GeometryVisitor<T> for Pieces<T> {
+    fn visit_point(&mut self, point: &Point<T>) {
+        self.segment(flat(point.0), flat(point.0))
+    }
+
+    fn visit_point_z(&mut self, point: &PointZ<T>) {
+        self.segment(point.0, point.0)
+    }
+
+    fn visit_line(&mut self, line: &Line<T>) {
+        self.segment(flat(line.start), flat(line.end))
+    }
+
+    fn visit_line_z(&mut self, line: &LineZ<T>) {
+        self.segment(line.start, line.end)
+    }
+
+    fn visit_line_string(&mut self, line_string: &LineString<T>) {
+        self.line_string(&flat_ring(line_string).0)
+    }
+
+    fn visit_line_string_z(&mut self, line_string: &LineStringZ<T>) {
+        self.line_string(&line_string.0)
+    }
+
+    fn visit_polygon(&mut self, polygon: &Polygon<T>) {
+        self.polygon(&PolygonZ::new(
+            flat_ring(polygon.exterior()),
+            polygon.interiors().iter().map(flat_ring).collect(),
+        ))
+    }
+
+    fn visit_polygon_z(&mut self, polygon: &PolygonZ<T>) {
+        self.polygon(polygon)
+    }
+
+    fn visit_rect(&mut self, rect: &Rect<T>) {
+        self.visit_polygon(&rect.to_polygon())
+    }
+}
+
+fn flat<T: CoordFloat>(coord: Coord<T>) -> CoordZ<T> {
+    CoordZ {
+        x: coord.x,
+        y: coord.y,
+        z: T::zero(),
+    }
+}
+
+fn flat_ring<T: CoordFloat>(line_string: &LineString<T>) -> LineStringZ<T> {
+    LineStringZ(line_string.0.iter().map(|&c| flat(c)).collect())
+}
+
+fn project<T: CoordFloat>(coord: CoordZ<T>, [a, b]: [usize; 2]) -> [T; 2] {
+    let coord = [coord.x, coord.y, coord.z];
+    [coord[a], coord[b]]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+    use core::slice;
+
+    #[test]
+    fn matches_brute_force() {
+        // short segments scattered through a box, and points scattered around it
+        let f = |i: usize, k: usize| ((i * k) % 101) as f64 / 101.;
+        let lines: Vec<Geometry> = (0..300)
+            .map(|i| {
+                let start = (20. * f(i, 7), 20. * f(i, 13), 20. * f(i, 29));
+                let end = (start.0 + f(i, 31), start.1 - f(i, 37), start.2 + 0.5);
+                LineZ::new(start, end).into()
+            })
+            .collect();
+        let points: Vec<Geometry> = (0..200)
+            .map(|i| PointZ::new(40. * f(i, 17) - 10., 40. * f(i, 19) - 10., 25. * f(i, 23)).into())
+            .collect();
+        let closest = min_distance_indexed(&lines, &points).unwrap();
+
+        let mut expected = f64::INFINITY;
+        for line in &lines {
+            let Geometry::LineZ(line) = line else {
+                unreachable!()
+            };
+            for point in &points {
+                let Geometry::PointZ(point) = point else {
+                    unreachable!()
+                };
+                let (p, q) = closest_segments(line.start, line.end, point.0, point.0);
+                expected = expected.min(distance(p, q));
+            }
+        }
+        assert_eq!(closest.distance, expected);
+        assert_eq!(closest.point_b, {
+            let Geometry::PointZ(point) = points[closest.index_b] else {
+                unreachable!()
+            };
+            point
+        });
+        assert_eq!(distance(closest.point_a.0, closest.point_b.0), expected);
+        assert!(min_distance_indexed(&lines, &[]).is_none());
+
+        // joining the points to the segments finds the same distances as brute force
+        let points = MultiPointZ::new(
+            points
+                .iter()
+                .map(|point| match point {
+                    Geometry::PointZ(point) => *point,
+                    _ => unreachable!(),
+                })
+                .collect(),
+        );
+        let joined = points.nearest_join(&lines);
+        for (point, joined) in points.0.iter().zip(&joined) {
+            let (index, d) = joined.unwrap();
+            let Geometry::LineZ(line) = lines[index] else {
+                unreachable!()
+            };
+            let (p, q) = closest_segments(line.start, line.end, point.0, point.0);
+            assert_eq!(d, distance(p, q));
+            let single = min_distance_indexed(&lines, &[(*point).into()]).unwrap();
+            assert_eq!(d, single.distance);
+        }
+        #[cfg(feature = "multithreading")]
+        assert_eq!(points.par_nearest_join(&lines), joined);
+
+        // the pairs within a distance are those whose closest points are
+        let pairs: Vec<(usize, usize)> = within_distance_join(&lines, &lines, 0.8).collect();
+        let mut expected = Vec::new();
+        for i in 0..lines.len() {
+            for j in 0..lines.len() {
+                let closest =
+                    min_distance_indexed(slice::from_ref(&lines[i]), slice::from_ref(&lines[j]));
+                if closest.unwrap().distance <= 0.8 {
+                    expected.push((i, j));
+                }
+            }
+        }
+        assert!(expected.len() > 2 * lines.len());
+        assert_eq!(pairs, expected);
+        assert_eq!(points.nearest_join(&[]), vec![None; points.0.len()]);
+    }
+
+    #[test]
+    fn faces() {
+        let roof: Geometry = crate::polygon_z![
+            (x: 0., y: 0., z: 3.),
+            (x: 4., y: 0., z: 3.),
+            (x: 4., y: 4., z: 3.),
+            (x: 0., y: 4., z: 3.),
+        ]
+        .into();
+        // a point above the middle is nearest the interior, not the boundary
+        let above = [PointZ::new(2., 2., 5.).into()];
+        let closest = min_distance_indexed(slice::from_ref(&roof), &above).unwrap();
+        assert_eq!(closest.distance, 2.);
+        assert_eq!(closest.point_a, PointZ::new(2., 2., 3.));
+
+        // a pole through the roof touches it
+        let pole = [LineZ::new((1., 1., 0.), (1., 1., 10.)).into()];
+        let closest = min_distance_indexed(&pole, slice::from_ref(&roof)).unwrap();
+        assert_eq!(closest.distance, 0.);
+        assert_eq!(closest.point_a, PointZ::new(1., 1., 3.));
+
+        // a planar rectangle on the ground, and a wall standing beside it
+        let ground = [Geometry::Rect(Rect::new((0., 0.), (4., 4.)))];
+        let wall: Geometry = crate::polygon_z![
+            (x: 6., y: 1., z: 0.),
+            (x: 6., y: 3., z: 0.),
+            (x: 6., y: 3., z: 2.),
+            (x: 6., y: 1., z: 2.),
+        ]
+        .into();
+        let closest = min_distance_indexed(&ground, slice::from_ref(&wall)).unwrap();
+        assert_eq!(closest.distance, 2.);
+        assert_eq!(
+            min_distance_indexed(&[roof], &[wall]).unwrap().distance,
+            5f64.sqrt()
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! Algorithms on 3D geometries.
+//!
+//! Where a problem is inherently planar (buffering, boolean operations, ...) the
+//! algorithms here project onto the XY plane, reuse the implementations from the
+//! [`geo`] crate, and then restore z.
+//!
+//! Most algorithms need [`CoordFloat`](crate::CoordFloat) coordinates. Those that
+//! also work on integer coordinates, such as tile or grid coordinates, are bounded
+//! by [`CoordNum`](crate::CoordNum) instead: [`BoundingCube`], [`MapCoordsZ`],
+//! [`Cast`], [`Canonicalize`], [`AffineOpsZ`] with translations and scalings,
+//! [`Clip`] for points, and [`LengthZ`], which rounds lengths down to whole
+//! numbers.
+
+pub(crate) mod linalg;
+#[cfg(feature = "std")]
+pub(crate) mod xy;
+
+/// Swap the horizontal axes of geometries, between latitude/longitude and
+/// longitude/latitude order.
+pub mod axis_order;
+pub use axis_order::{AxisOrder, SwapXY};
+
+/// Rotate, scale and translate geometries with 3D affine transforms.
+pub mod affine_ops;
+pub use affine_ops::{AffineOpsZ, AffineTransform3D};
+
+/// The axis-aligned bounding box of a geometry.
+pub mod bounding_cube;
+pub use bounding_cube::BoundingCube;
+
+/// Buffer the XY footprint of a geometry, interpolating z for the new vertices.
+#[cfg(feature = "std")]
+pub mod buffer_z;
+#[cfg(feature = "std")]
+pub use buffer_z::BufferZ;
+
+/// Boolean operations on coplanar polygons, in any orientation.
+#[cfg(feature = "std")]
+pub mod bool_ops_z;
+#[cfg(feature = "std")]
+pub use bool_ops_z::BooleanOpsZ;
+
+/// Convert the coordinates of geometries to another scalar type.
+pub mod cast;
+pub use cast::Cast;
+
+/// Canonical forms and stable digests of geometries, to find duplicates.
+pub mod canonicalize;
+pub use canonicalize::Canonicalize;
+
+/// Group point clouds into clusters by k-means or DBSCAN.
+pub mod cluster;
+pub use cluster::Clusters;
+
+/// Simplify triangulated surfaces by collapsing edges, for levels of detail.
+pub mod decimate;
+
+/// Minimum rotated rectangles and XY footprints of 3D geometries, for 2D maps.
+#[cfg(feature = "std")]
+pub mod footprint;
+#[cfg(feature = "std")]
+pub use footprint::{Footprint, MinimumRotatedRectZ};
+
+/// Geodesic densification, area and containment for lon/lat polygons.
+#[cfg(feature = "std")]
+pub mod geodesy;
+#[cfg(feature = "std")]
+pub use geodesy::{DensifyGeodesic, GeodesicPointZ, GeodesicPolygonZ};
+
+/// Estimate z between scattered points, such as to build elevation models.
+#[cfg(feature = "std")]
+pub mod interpolate;
+#[cfg(feature = "std")]
+pub use interpolate::Interpolation;
+
+/// A point on a geometry, inside it where it has an interior, to anchor labels.
+#[cfg(feature = "std")]
+pub mod interior_point;
+#[cfg(feature = "std")]
+pub use interior_point::InteriorPointZ;
+
+/// The lengths of linear geometries, for float and integer coordinates.
+pub mod length;
+pub use length::{LengthZ, SquareRoot};
+
+/// Lift planar rectangles into 3D polygons and cubes.
+pub mod lift_rect;
+pub use lift_rect::LiftRect;
+
+/// Join line strings that meet end to end.
+pub mod line_merge;
+
+/// Repair invalid polygons, such as those exported from CAD software.
+#[cfg(feature = "std")]
+pub mod make_valid;
+#[cfg(feature = "std")]
+pub use make_valid::MakeValid;
+
+/// Snap noisy tracks onto a network of line strings.
+pub mod map_match;
+pub use map_match::MatchedPoint;
+
+/// Apply a function to every coordinate of a geometry.
+pub mod map_coords;
+pub use map_coords::{MapCoordsZ, MapCoordsZInPlace};
+#[cfg(feature = "multithreading")]
+pub use map_coords::{ParMapCoordsZ, ParMapCoordsZInPlace};
+
+/// Clip geometries to an axis-aligned [`Cube`](crate::Cube), or to a band of
+/// heights.
+pub mod clip;
+pub use clip::{Clip, ClipZRange};
+
+/// The closest points of two large sets of geometries, and nearest-neighbour and
+/// within-distance joins, found through spatial indexes.
+pub mod min_distance;
+pub use min_distance::{min_distance_indexed, within_distance_join, ClosestPoints};
+
+/// Weighted centroids and geometric medians of point sets.
+pub mod median;
+
+/// Split line strings where they cross, leaving bridges and tunnels whole.
+pub mod node;
+
+/// Offset curves running parallel to line strings, in their plane or in XY.
+#[cfg(feature = "std")]
+pub mod offset_curve;
+#[cfg(feature = "std")]
+pub use offset_curve::{OffsetCurveZ, Side};
+
+/// Remove stray points from noisy point clouds.
+pub mod outliers;
+
+/// Jitter geometries by small, seeded pseudo-random offsets.
+pub mod perturb;
+pub use perturb::Perturb;
+
+/// Build polygons from the faces enclosed by linework.
+pub mod polygonize;
+pub use polygonize::Polygonized;
+
+/// Principal axes and oriented bounding boxes of point clouds.
+pub mod principal_axes;
+pub use principal_axes::PrincipalAxes;
+
+/// Project geometries onto the screen through a camera, with their depths.
+pub mod project;
+pub use project::ProjectToScreen;
+
+/// Quantize geometries onto integer grids for compact storage, and restore them.
+pub mod quantize;
+pub use quantize::{fit_grid, Dequantize, Quantize};
+
+/// Fit planes and spheres to noisy point clouds by random sample consensus.
+pub mod ransac;
+
+/// Clean up surfaces imported from STL, OBJ and the like: weld vertices, drop
+/// degenerate triangles, fix windings and fill holes.
+pub mod repair;
+
+/// DE-9IM relationships between coplanar geometries, in any orientation.
+#[cfg(feature = "std")]
+pub mod relate;
+#[cfg(feature = "std")]
+pub use relate::{IntersectionMatrix, RelateZ};
+
+/// Rigid alignment of point clouds by iterative closest point.
+pub mod registration;
+
+/// Straight skeletons of footprints, and the hipped roofs built on them.
+pub mod straight_skeleton;
+pub use straight_skeleton::StraightSkeleton;
+
+/// Compare paths and trajectories by dynamic time warping and Fréchet distance.
+pub mod similarity;
+pub use similarity::TrajectorySimilarity;
+
+/// Node linework by snap rounding onto a fixed grid.
+pub mod snap_round;
+
+/// Sort collections along Hilbert and Morton curves, to keep nearby members
+/// together.
+pub mod spatial_sort;
+pub use spatial_sort::{hilbert_key, morton_key, SpatialSort};
+
+/// Summaries of geometries, such as to check imported data.
+pub mod stats;
+pub use stats::GeometryStats;
+
+/// Exaggerate, shift and rescale the heights of geometries, and convert them
+/// between vertical datums.
+pub mod vertical;
+pub use vertical::{GeoidGrid, VerticalDatum, VerticalOps};
+
+/// Convert geometries into grids of occupied cells, and back into meshes.
+pub mod voxelize;
+pub use voxelize::{OccupancyGrid, Voxelize};
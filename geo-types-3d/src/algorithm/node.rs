@@ -0,0 +1,326 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::{CoordFloat, CoordZ, LineStringZ, MultiLineStringZ};
+
+impl<T: CoordFloat> MultiLineStringZ<T> {
+    /// Splits the line strings wherever they cross or touch one another, or
+    /// themselves, in XY, so that they only meet at their ends.
+    ///
+    /// Where two line strings cross, each has a z there, interpolated along its
+    /// segment. They are only split if the two differ by at most `z_tolerance`,
+    /// so that a bridge passing over a road, or a tunnel under it, is left whole.
+    /// Both pieces then meet at the same coordinate: an existing vertex if one of
+    /// the line strings has one there, or else the crossing with the mean of the
+    /// two z values. Where they overlap along a stretch, they are split at both
+    /// ends of it.
+    ///
+    /// Crossings within a tiny fraction of a segment's length of one of its
+    /// vertices are placed at the vertex. Line strings with fewer than two
+    /// coordinates are left out. The pieces of each line string follow one
+    /// another, in the order of the line strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{line_string_z, MultiLineStringZ};
+    ///
+    /// // two roads crossing on a gentle slope, and a bridge passing over both
+    /// let roads: MultiLineStringZ = MultiLineStringZ::new(vec![
+    ///     line_string_z![(x: 0., y: 5., z: 1.), (x: 10., y: 5., z: 1.)],
+    ///     line_string_z![(x: 5., y: 0., z: 0.), (x: 5., y: 10., z: 2.)],
+    ///     line_string_z![(x: 0., y: 0., z: 9.), (x: 10., y: 10., z: 9.)],
+    /// ]);
+    /// let noded = roads.node(0.5);
+    /// assert_eq!(noded.0.len(), 5);
+    /// assert_eq!(noded.0[0].0[1].x, 5.);
+    /// assert_eq!(noded.0[0].0[1].z, 1.);
+    /// assert_eq!(noded.0[4], roads.0[2]);
+    /// ```
+    pub fn node(&self, z_tolerance: T) -> MultiLineStringZ<T> {
+        let lines: Vec<&LineStringZ<T>> = self.0.iter().filter(|line| line.0.len() >= 2).collect();
+        let segments = segments(&lines);
+        let mut splits: Vec<Vec<Split<T>>> = vec![Vec::new(); lines.len()];
+        sweep(&segments, |a, b| {
+            for (t, u) in a.crossings(b) {
+                let (za, zb) = (a.at(t).z, b.at(u).z);
+                if (za - zb).abs() > z_tolerance {
+                    continue;
+                }
+                let coord = if t == T::zero() || t == T::one() {
+                    a.at(t)
+                } else if u == T::zero() || u == T::one() {
+                    b.at(u)
+                } else {
+                    let mut coord = a.at(t);
+                    coord.z = (za + zb) / (T::one() + T::one());
+                    coord
+                };
+                splits[a.line].push(Split::new(a.index, t, coord));
+                splits[b.line].push(Split::new(b.index, u, coord));
+            }
+        });
+
+        let mut pieces = Vec::new();
+        for (line, mut splits) in lines.into_iter().zip(splits) {
+            splits.sort_by(|a, b| {
+                (a.index, a.t)
+                    .partial_cmp(&(b.index, b.t))
+                    .unwrap_or(Ordering::Equal)
+            });
+            splits.dedup_by(|a, b| (a.index, a.t) == (b.index, b.t));
+            pieces.extend(cut(line, &splits));
+        }
+        MultiLineStringZ::new(pieces)
+    }
+}
+
+/// The segments of the line strings, each of which has at least two coordinates.
+pub(super) fn segments<T: CoordFloat>(lines: &[&LineStringZ<T>]) -> Vec<Segment<T>> {
+    lines
+        .iter()
+        .enumerate()
+        .flat_map(|(line, coords)| {
+            coords
+                .0
+                .windows(2)
+                .enumerate()
+                .map(move |(index, pair)| Segment {
+                    line,
+                    index,
+                    start: pair[0],
+                    end: pair[1],
+                })
+        })
+        .collect()
+}
+
+/// Calls `visit` with each pair of segments whose bounding boxes overlap in XY,
+/// other than neighbours along a line string, sweeping along x.
+pub(super) fn sweep<T: CoordFloat>(
+    segments: &[Segment<T>],
+    mut visit: impl FnMut(&Segment<T>, &Segment<T>),
+) {
+    let mut order: Vec<usize> = (0..segments.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (a, b) = (segments[a].min_x(), segments[b].min_x());
+        a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+    });
+    for (i, &a) in order.iter().enumerate() {
+        let a = &segments[a];
+        for &b in &order[i + 1..] {
+            let b = &segments[b];
+            if b.min_x() > a.max_x() {
+                break;
+            }
+            let adjacent = a.line == b.line && a.index.abs_diff(b.index) == 1;
+            if !adjacent && b.min_y() <= a.max_y() && a.min_y() <= b.max_y() {
+                visit(a, b);
+            }
+        }
+    }
+}
+
+/// A segment of one of the line strings.
+pub(super) struct Segment<T: CoordFloat> {
+    pub(super) line: usize,
+    pub(super) index: usize,
+    pub(super) start: CoordZ<T>,
+    pub(super) end: CoordZ<T>,
+}
+
+impl<T: CoordFloat> Segment<T> {
+    fn min_x(&self) -> T {
+        self.start.x.min(self.end.x)
+    }
+
+    fn max_x(&self) -> T {
+        self.start.x.max(self.end.x)
+    }
+
+    fn min_y(&self) -> T {
+        self.start.y.min(self.end.y)
+    }
+
+    fn max_y(&self) -> T {
+        self.start.y.max(self.end.y)
+    }
+
+    /// The point a fraction `t` of the way along.
+    pub(super) fn at(&self, t: T) -> CoordZ<T> {
+        if t == T::zero() {
+            self.start
+        } else if t == T::one() {
+            self.end
+        } else {
+            self.start + (self.end - self.start) * t
+        }
+    }
+
+    /// Where the two segments meet in XY, as fractions of the way along each: one
+    /// point where they cross or touch, or the two ends of a stretch where they
+    /// overlap.
+    pub(super) fn crossings(&self, other: &Self) -> Vec<(T, T)> {
+        let cross = |a: CoordZ<T>, b: CoordZ<T>| a.x * b.y - a.y * b.x;
+        let dot = |a: CoordZ<T>, b: CoordZ<T>| a.x * b.x + a.y * b.y;
+        let (r, s) = (self.end - self.start, other.end - other.start);
+        let offset = other.start - self.start;
+        let (rr, ss) = (dot(r, r), dot(s, s));
+        if rr == T::zero() || ss == T::zero() {
+            return Vec::new();
+        }
+        let denominator = cross(r, s);
+        let eps = T::epsilon().sqrt();
+
+        if denominator.abs() > eps * (rr * ss).sqrt() {
+            let t = snap(cross(offset, s) / denominator, eps);
+            let u = snap(cross(offset, r) / denominator, eps);
+            let unit = |v: T| v >= T::zero() && v <= T::one();
+            return if unit(t) && unit(u) {
+                vec![(t, u)]
+            } else {
+                Vec::new()
+            };
+        }
+
+        // parallel: they only meet if they lie on one line, along the overlap
+        if cross(offset, r).abs() > eps * (rr * dot(offset, offset)).sqrt() {
+            return Vec::new();
+        }
+        let along = |c: CoordZ<T>| dot(c - self.start, r) / rr;
+        let (t0, t1) = (along(other.start), along(other.end));
+        let (low, high) = (t0.min(t1).max(T::zero()), t0.max(t1).min(T::one()));
+        if low > high {
+            return Vec::new();
+        }
+        let mut crossings = Vec::new();
+        for t in [low, high] {
+            let t = snap(t, eps);
+            let u = snap(dot(self.at(t) - other.start, s) / ss, eps);
+            if !crossings.contains(&(t, u)) {
+                crossings.push((t, u));
+            }
+        }
+        crossings
+    }
+}
+
+/// Moves a fraction of the way along a segment within `eps` of either end to the
+/// end.
+fn snap<T: CoordFloat>(t: T, eps: T) -> T {
+    if t.abs() <= eps {
+        T::zero()
+    } else if (t - T::one()).abs() <= eps {
+        T::one()
+    } else {
+        t
+    }
+}
+
+/// A place to split a line string: a fraction `t` of the way along segment
+/// `index`, or at vertex `index` if `t` is zero.
+#[derive(Clone)]
+struct Split<T: CoordFloat> {
+    index: usize,
+    t: T,
+    coord: CoordZ<T>,
+}
+
+impl<T: CoordFloat> Split<T> {
+    fn new(index: usize, t: T, coord: CoordZ<T>) -> Self {
+        if t == T::one() {
+            Self {
+                index: index + 1,
+                t: T::zero(),
+                coord,
+            }
+        } else {
+            Self { index, t, coord }
+        }
+    }
+}
+
+/// Cuts a line string at sorted splits.
+fn cut<T: CoordFloat>(line: &LineStringZ<T>, splits: &[Split<T>]) -> Vec<LineStringZ<T>> {
+    let last = line.0.len() - 1;
+    let mut pieces = Vec::new();
+    let mut piece = vec![line.0[0]];
+    let mut splits = splits.iter().peekable();
+    for (index, &coord) in line.0.iter().enumerate() {
+        if index > 0 {
+            piece.push(coord);
+        }
+        while let Some(split) = splits.next_if(|split| split.index == index) {
+            let at = if split.t == T::zero() {
+                // nothing to split at either end
+                if index == 0 || index == last {
+                    continue;
+                }
+                coord
+            } else {
+                piece.push(split.coord);
+                split.coord
+            };
+            pieces.push(LineStringZ::new(core::mem::replace(&mut piece, vec![at])));
+        }
+    }
+    pieces.push(LineStringZ::new(piece));
+    pieces
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn line(coords: &[(f64, f64, f64)]) -> LineStringZ {
+        LineStringZ::from(coords.to_vec())
+    }
+
+    #[test]
+    fn node() {
+        let lines = MultiLineStringZ::new(vec![
+            // a crossing, with the crossing's z the mean of the two
+            line(&[(0., 0., 0.), (4., 4., 4.)]),
+            line(&[(0., 4., 3.), (4., 0., 3.)]),
+            // a T junction at a vertex, and an overpass left whole
+            line(&[(10., 0., 0.), (10., 2., 0.), (10., 4., 0.)]),
+            line(&[(8., 0., 0.), (10., 2., 0.)]),
+            line(&[(8., 4., 6.), (12., 0., 6.)]),
+            // an overlap along a stretch
+            line(&[(20., 0., 0.), (24., 0., 0.)]),
+            line(&[(22., 0., 0.), (26., 0., 0.)]),
+        ]);
+        let noded = lines.node(1.5);
+        assert_eq!(
+            noded,
+            MultiLineStringZ::new(vec![
+                line(&[(0., 0., 0.), (2., 2., 2.5)]),
+                line(&[(2., 2., 2.5), (4., 4., 4.)]),
+                line(&[(0., 4., 3.), (2., 2., 2.5)]),
+                line(&[(2., 2., 2.5), (4., 0., 3.)]),
+                line(&[(10., 0., 0.), (10., 2., 0.)]),
+                line(&[(10., 2., 0.), (10., 4., 0.)]),
+                line(&[(8., 0., 0.), (10., 2., 0.)]),
+                line(&[(8., 4., 6.), (12., 0., 6.)]),
+                line(&[(20., 0., 0.), (22., 0., 0.)]),
+                line(&[(22., 0., 0.), (24., 0., 0.)]),
+                line(&[(22., 0., 0.), (24., 0., 0.)]),
+                line(&[(24., 0., 0.), (26., 0., 0.)]),
+            ])
+        );
+
+        // a loop crossing itself
+        let figure_eight = MultiLineStringZ::new(vec![line(&[
+            (0., 0., 0.),
+            (2., 2., 0.),
+            (2., 0., 0.),
+            (0., 2., 0.),
+            (0., 0., 0.),
+        ])]);
+        let noded = figure_eight.node(0.);
+        assert_eq!(noded.0.len(), 3);
+        assert_eq!(noded.0[0].0.last(), Some(&(1., 1., 0.).into()));
+        assert!(MultiLineStringZ::<f64>::empty().node(1.).0.is_empty());
+    }
+}
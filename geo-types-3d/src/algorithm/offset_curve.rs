@@ -0,0 +1,460 @@
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+use geo::algorithm::bool_ops::BoolOpsNum;
+
+pub use geo::algorithm::buffer::LineJoin;
+
+use super::ransac::fit_plane;
+use crate::algorithm::linalg::{cross, dot, normalize};
+use crate::{CoordFloat, CoordZ, LineStringZ, MultiLineStringZ};
+
+/// Which side of a line string to offset it to, looking along it from its
+/// start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// Offset curves of line strings: the line running parallel to them at a
+/// distance, such as the edges of a lane or corridor along a centre line.
+///
+/// A line string lying in a plane, such as a road on a steady slope, is offset
+/// within the plane fitted to it, so that the offset stays on the same surface
+/// and is `distance` away along it. Left and right are as seen from the side of
+/// the plane its normal faces, which is above for any plane that isn't vertical.
+/// Other line strings, and those on a single line, are offset in XY, each new
+/// vertex taking the z of the vertex it was offset from, so that z is
+/// interpolated along the segments as in the source.
+///
+/// Where the line bends away from the offset side, the offset segments are
+/// joined as [`LineJoin`] says, as in [`BufferZ`](super::BufferZ): `Bevel`
+/// joins their ends directly, `Miter(angle)` extends them to meet, cutting off
+/// corners sharper than `angle` radians, and `Round(angle)` follows an arc
+/// around the vertex in steps of about `angle` radians. On the inside of a bend
+/// the segments are cut where they cross. The curve isn't cleaned up where it is
+/// offset further than the radius of a bend, which leaves loops in it.
+///
+/// Closed line strings are offset as rings, with a join at their start.
+/// Repeated coordinates are dropped, and a line string with fewer than two
+/// distinct coordinates is returned as it is.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::offset_curve::LineJoin;
+/// use geo_types_3d::algorithm::{OffsetCurveZ, Side};
+/// use geo_types_3d::{line_string_z, LineStringZ};
+///
+/// // a centre line climbing steadily on a plane where z = y / 2, turning right
+/// let centre: LineStringZ = line_string_z![
+///     (x: 0., y: 0., z: 0.),
+///     (x: 0., y: 10., z: 5.),
+///     (x: 10., y: 10., z: 5.),
+/// ];
+/// let kerb = centre.offset_with_join(2., Side::Left, LineJoin::Miter(0.5));
+/// for coord in kerb.coords() {
+///     assert!((coord.z - coord.y / 2.).abs() < 1e-9);
+/// }
+/// assert_eq!(kerb.0.len(), 3);
+/// ```
+pub trait OffsetCurveZ {
+    type Scalar: BoolOpsNum + 'static;
+
+    /// The offset `distance` away to `side`, with round joins.
+    fn offset(&self, distance: Self::Scalar, side: Side) -> Self;
+
+    /// The offset `distance` away to `side`, joining segments as `join` says.
+    fn offset_with_join(
+        &self,
+        distance: Self::Scalar,
+        side: Side,
+        join: LineJoin<Self::Scalar>,
+    ) -> Self;
+}
+
+impl<T: BoolOpsNum + 'static> OffsetCurveZ for LineStringZ<T> {
+    type Scalar = T;
+
+    fn offset(&self, distance: T, side: Side) -> Self {
+        let angle = T::from(0.2).unwrap_or_else(T::one);
+        self.offset_with_join(distance, side, LineJoin::Round(angle))
+    }
+
+    fn offset_with_join(&self, distance: T, side: Side, join: LineJoin<T>) -> Self {
+        let join = match join {
+            LineJoin::Bevel => Join::Bevel,
+            LineJoin::Miter(angle) => Join::Miter(clamp(angle, 0.01, 0.99)),
+            LineJoin::Round(angle) => Join::Round(clamp(angle, 0.01, 0.25)),
+        };
+        let distance = match side {
+            Side::Left => distance,
+            Side::Right => -distance,
+        };
+        offset(self, distance, join)
+    }
+}
+
+impl<T: BoolOpsNum + 'static> OffsetCurveZ for MultiLineStringZ<T> {
+    type Scalar = T;
+
+    fn offset(&self, distance: T, side: Side) -> Self {
+        MultiLineStringZ::new(
+            self.0
+                .iter()
+                .map(|line| line.offset(distance, side))
+                .collect(),
+        )
+    }
+
+    fn offset_with_join(&self, distance: T, side: Side, join: LineJoin<T>) -> Self {
+        MultiLineStringZ::new(
+            self.0
+                .iter()
+                .map(|line| line.offset_with_join(distance, side, join.clone()))
+                .collect(),
+        )
+    }
+}
+
+/// An angle limited to a range of multiples of π, as `i_overlay` does for
+/// buffers.
+fn clamp<T: CoordFloat>(angle: T, min: f64, max: f64) -> T {
+    let angle = angle.to_f64().unwrap_or(min * PI).clamp(min * PI, max * PI);
+    T::from(angle).unwrap_or_else(T::one)
+}
+
+#[derive(Clone, Copy)]
+enum Join<T> {
+    Bevel,
+    Miter(T),
+    Round(T),
+}
+
+/// Axes in which a line string is offset: in its plane, or in XY.
+struct Frame<T: CoordFloat> {
+    origin: CoordZ<T>,
+    u: CoordZ<T>,
+    v: CoordZ<T>,
+    normal: CoordZ<T>,
+}
+
+impl<T: CoordFloat> Frame<T> {
+    fn new(coords: &[CoordZ<T>]) -> Self {
+        let xy = Self {
+            origin: CoordZ::from((T::zero(), T::zero(), T::zero())),
+            u: CoordZ::from((T::one(), T::zero(), T::zero())),
+            v: CoordZ::from((T::zero(), T::one(), T::zero())),
+            normal: CoordZ::from((T::zero(), T::zero(), T::one())),
+        };
+        let Some(plane) = fit_plane(coords) else {
+            return xy;
+        };
+        let mut min = coords[0];
+        let mut max = coords[0];
+        for c in coords {
+            min = CoordZ::from((min.x.min(c.x), min.y.min(c.y), min.z.min(c.z)));
+            max = CoordZ::from((max.x.max(c.x), max.y.max(c.y), max.z.max(c.z)));
+        }
+        let size = (max.x - min.x).max(max.y - min.y).max(max.z - min.z);
+        let tolerance = size * T::epsilon().sqrt();
+        if coords
+            .iter()
+            .any(|&c| plane.signed_distance(c).abs() > tolerance)
+        {
+            return xy;
+        }
+
+        // the normal facing up, or failing that towards +y or +x
+        let n = plane.normal;
+        let up = if n.z != T::zero() {
+            n.z
+        } else if n.y != T::zero() {
+            n.y
+        } else {
+            n.x
+        };
+        let normal = if up < T::zero() { -n } else { n };
+        if normal.z == T::one() {
+            return xy;
+        }
+        let origin = plane.project(coords[0]);
+        let along = coords[1] - coords[0];
+        let u = normalize(along - normal * dot(along, normal));
+        Self {
+            origin,
+            u,
+            v: cross(normal, u),
+            normal,
+        }
+    }
+
+    fn to_local(&self, coord: CoordZ<T>) -> CoordZ<T> {
+        let d = coord - self.origin;
+        CoordZ::from((dot(d, self.u), dot(d, self.v), dot(d, self.normal)))
+    }
+
+    fn to_world(&self, coord: CoordZ<T>) -> CoordZ<T> {
+        self.origin + self.u * coord.x + self.v * coord.y + self.normal * coord.z
+    }
+}
+
+/// Offsets a line string by `distance`, to the left if positive.
+fn offset<T: CoordFloat>(line: &LineStringZ<T>, distance: T, join: Join<T>) -> LineStringZ<T> {
+    let mut coords: Vec<CoordZ<T>> = Vec::with_capacity(line.0.len());
+    for &coord in &line.0 {
+        if coords.last() != Some(&coord) {
+            coords.push(coord);
+        }
+    }
+    if coords.len() < 2 || distance == T::zero() {
+        return LineStringZ(coords);
+    }
+    let frame = Frame::new(&coords);
+    let local: Vec<CoordZ<T>> = coords.iter().map(|&c| frame.to_local(c)).collect();
+    let segments: Vec<Segment<T>> = local
+        .windows(2)
+        .filter(|pair| pair[0].x != pair[1].x || pair[0].y != pair[1].y)
+        .map(|pair| Segment::new(pair[0], pair[1], distance))
+        .collect();
+    let Some(first) = segments.first() else {
+        return LineStringZ(coords);
+    };
+    let closed = line.is_closed() && segments.len() >= 3;
+
+    let mut offset = Vec::with_capacity(segments.len() * 2);
+    if closed {
+        join_at(
+            &segments[segments.len() - 1],
+            first,
+            distance,
+            join,
+            &mut offset,
+        );
+    } else {
+        offset.push(first.start);
+    }
+    for pair in segments.windows(2) {
+        join_at(&pair[0], &pair[1], distance, join, &mut offset);
+    }
+    if closed {
+        offset.push(offset[0]);
+    } else if let Some(last) = segments.last() {
+        offset.push(last.end);
+    }
+    LineStringZ(offset.into_iter().map(|c| frame.to_world(c)).collect())
+}
+
+/// A segment in the frame, with its offset.
+struct Segment<T: CoordFloat> {
+    /// The vertex at the end, where it joins the next segment.
+    vertex: CoordZ<T>,
+    direction: CoordZ<T>,
+    normal: CoordZ<T>,
+    start: CoordZ<T>,
+    end: CoordZ<T>,
+}
+
+impl<T: CoordFloat> Segment<T> {
+    fn new(a: CoordZ<T>, b: CoordZ<T>, distance: T) -> Self {
+        let direction = normalize(CoordZ::from((b.x - a.x, b.y - a.y, T::zero())));
+        let normal = CoordZ::from((-direction.y, direction.x, T::zero()));
+        Self {
+            vertex: b,
+            direction,
+            normal,
+            start: a + normal * distance,
+            end: b + normal * distance,
+        }
+    }
+}
+
+/// Pushes the points joining the offsets of two consecutive segments.
+fn join_at<T: CoordFloat>(
+    from: &Segment<T>,
+    to: &Segment<T>,
+    distance: T,
+    join: Join<T>,
+    offset: &mut Vec<CoordZ<T>>,
+) {
+    let turn = cross(from.direction, to.direction).z;
+    let dot = dot(from.direction, to.direction);
+    let vertex = from.vertex;
+    let miter = || {
+        let scale = distance / (T::one() + dot);
+        vertex + (from.normal + to.normal) * scale
+    };
+    if turn.abs() <= T::epsilon().sqrt() && dot > T::zero() {
+        offset.push(from.end);
+        return;
+    }
+    // bending towards the offset side, where the offsets cross
+    if turn.abs() > T::epsilon().sqrt() && (turn > T::zero()) == (distance > T::zero()) {
+        offset.push(miter());
+        return;
+    }
+    match join {
+        Join::Bevel => offset.extend([from.end, to.start]),
+        Join::Miter(angle) => {
+            if dot >= -angle.cos() {
+                offset.push(miter());
+            } else {
+                let length = distance.abs() / (angle / (T::one() + T::one())).tan();
+                offset.extend([
+                    from.end,
+                    from.end + from.direction * length,
+                    to.start - to.direction * length,
+                    to.start,
+                ]);
+            }
+        }
+        Join::Round(angle) => {
+            let sweep = dot.max(-T::one()).min(T::one()).acos();
+            let steps = (sweep / angle).ceil().max(T::one());
+            let step = -distance.signum() * sweep / steps;
+            let count = steps.to_usize().unwrap_or(1);
+            offset.push(from.end);
+            for k in 1..count {
+                let (sin, cos) = (step * T::from(k).unwrap_or_else(T::one)).sin_cos();
+                let n = from.normal;
+                let rotated =
+                    CoordZ::from((n.x * cos - n.y * sin, n.x * sin + n.y * cos, T::zero()));
+                offset.push(vertex + rotated * distance);
+            }
+            offset.push(to.start);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn line(coords: &[(f64, f64, f64)]) -> LineStringZ {
+        LineStringZ::from(coords.to_vec())
+    }
+
+    fn assert_close(actual: &LineStringZ, expected: &[(f64, f64, f64)]) {
+        assert_eq!(actual.0.len(), expected.len(), "{actual:?}");
+        for (a, &e) in actual.0.iter().zip(expected) {
+            let e = CoordZ::from(e);
+            assert_relative_eq!(a.x, e.x, epsilon = 1e-9);
+            assert_relative_eq!(a.y, e.y, epsilon = 1e-9);
+            assert_relative_eq!(a.z, e.z, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn joins() {
+        // a line that isn't planar, offset in XY, turning left then right
+        let path = line(&[(0., 0., 0.), (10., 0., 5.), (10., 10., 0.), (20., 10., 0.)]);
+        assert_close(
+            &path.offset_with_join(1., Side::Left, LineJoin::Miter(0.5)),
+            &[(0., 1., 0.), (9., 1., 5.), (9., 11., 0.), (20., 11., 0.)],
+        );
+        assert_close(
+            &path.offset_with_join(1., Side::Right, LineJoin::Miter(0.5)),
+            &[(0., -1., 0.), (11., -1., 5.), (11., 9., 0.), (20., 9., 0.)],
+        );
+        assert_close(
+            &path.offset_with_join(1., Side::Right, LineJoin::Bevel),
+            &[
+                (0., -1., 0.),
+                (10., -1., 5.),
+                (11., 0., 5.),
+                (11., 9., 0.),
+                (20., 9., 0.),
+            ],
+        );
+        // a right angle is sharper than the miter limit, and cut off
+        assert_close(
+            &path.offset_with_join(1., Side::Right, LineJoin::Miter(2.)),
+            &[
+                (0., -1., 0.),
+                (10., -1., 5.),
+                (10. + 1. / 1f64.tan(), -1., 5.),
+                (11., -1. / 1f64.tan(), 5.),
+                (11., 0., 5.),
+                (11., 9., 0.),
+                (20., 9., 0.),
+            ],
+        );
+        let round = path.offset(1., Side::Right);
+        assert_eq!(round.0.len(), 4 + 8);
+        for c in &round.0[1..10] {
+            assert_relative_eq!((c.x - 10.).hypot(c.y), 1., epsilon = 1e-9);
+            assert_eq!(c.z, 5.);
+        }
+
+        // turning back on itself
+        let back = line(&[(0., 0., 0.), (4., 0., 0.), (0., 0., 0.)]);
+        assert_close(
+            &back.offset_with_join(1., Side::Left, LineJoin::Bevel),
+            &[(0., 1., 0.), (4., 1., 0.), (4., -1., 0.), (0., -1., 0.)],
+        );
+        assert!(back.offset(1., Side::Left).0.iter().any(|c| c.x == 5.));
+    }
+
+    #[test]
+    fn planes() {
+        // on a slope where z = x, offsets stay on the slope
+        let ramp = line(&[(0., 0., 0.), (4., 0., 4.), (4., 4., 4.), (8., 4., 8.)]);
+        let offset = ramp.offset_with_join(1., Side::Left, LineJoin::Miter(0.5));
+        assert_eq!(offset.0.len(), 4);
+        assert_relative_eq!(offset.0[0].y, 1., epsilon = 1e-9);
+        for c in &offset.0 {
+            assert_relative_eq!(c.z, c.x, epsilon = 1e-9);
+        }
+        // a metre away along the slope, not in XY
+        let first = offset.0[1] - offset.0[0];
+        assert_relative_eq!(offset.0[0].x, 0., epsilon = 1e-9);
+        assert_relative_eq!(first.y, 0., epsilon = 1e-9);
+        assert_relative_eq!(offset.0[1].x, 4. - 0.5f64.sqrt(), epsilon = 1e-9);
+
+        // up a vertical wall at x = 3
+        let wall = line(&[(3., 0., 0.), (3., 5., 0.), (3., 5., 5.)]);
+        let offset = wall.offset(1., Side::Right);
+        assert!(offset.0.len() > 3);
+        for c in &offset.0 {
+            assert_relative_eq!(c.x, 3., epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn rings() {
+        let square = line(&[
+            (0., 0., 2.),
+            (4., 0., 2.),
+            (4., 4., 2.),
+            (0., 4., 2.),
+            (0., 0., 2.),
+        ]);
+        assert_close(
+            &square.offset(1., Side::Left),
+            &[
+                (1., 1., 2.),
+                (3., 1., 2.),
+                (3., 3., 2.),
+                (1., 3., 2.),
+                (1., 1., 2.),
+            ],
+        );
+        let outside = square.offset_with_join(1., Side::Right, LineJoin::Miter(0.5));
+        assert_close(
+            &outside,
+            &[
+                (-1., -1., 2.),
+                (5., -1., 2.),
+                (5., 5., 2.),
+                (-1., 5., 2.),
+                (-1., -1., 2.),
+            ],
+        );
+
+        let lanes = MultiLineStringZ::new(vec![square, line(&[(0., 0., 0.), (0., 0., 0.)])]);
+        let offset = lanes.offset(-1., Side::Right);
+        assert_eq!(offset.0[0].0.len(), 5);
+        assert_eq!(offset.0[1], line(&[(0., 0., 0.)]));
+    }
+}
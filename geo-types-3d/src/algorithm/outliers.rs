@@ -0,0 +1,147 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{CoordFloat, MultiPointZ};
+
+impl<T: CoordFloat> MultiPointZ<T> {
+    /// Flags the points to keep by statistical outlier removal, `true` for each
+    /// point that isn't an outlier.
+    ///
+    /// For each point, this finds the mean distance to its `k` nearest neighbours.
+    /// Points whose mean distance is more than `std_ratio` standard deviations
+    /// above the mean over all points are outliers. This picks out isolated
+    /// returns, such as those from dust or birds in a LiDAR scan, while keeping the
+    /// points of sparser but evenly sampled surfaces.
+    ///
+    /// All points are kept if there are no more than `k` of them, or `k` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{MultiPointZ, PointZ};
+    ///
+    /// let mut points: Vec<PointZ> = (0..100)
+    ///     .map(|i| PointZ::new((i % 10) as f64, (i / 10) as f64, 0.))
+    ///     .collect();
+    /// points.push(PointZ::new(4.5, 4.5, 20.));
+    /// let points = MultiPointZ::new(points);
+    ///
+    /// let keep = points.statistical_outlier_mask(8, 2.);
+    /// assert_eq!(keep.iter().filter(|&&keep| keep).count(), 100);
+    /// assert!(!keep[100]);
+    /// ```
+    pub fn statistical_outlier_mask(&self, k: usize, std_ratio: T) -> Vec<bool> {
+        if k == 0 || self.0.len() <= k {
+            return vec![true; self.0.len()];
+        }
+        let tree = self.kdtree();
+        let divisor = T::from(k).unwrap();
+        let means: Vec<T> = self
+            .0
+            .iter()
+            .map(|point| {
+                // the nearest point is the point itself
+                let total = tree.nearest_k(point, k + 1).into_iter().skip(1).fold(
+                    T::zero(),
+                    |sum, (_, neighbor)| {
+                        let delta = neighbor.0 - point.0;
+                        sum + (delta.x * delta.x + delta.y * delta.y + delta.z * delta.z).sqrt()
+                    },
+                );
+                total / divisor
+            })
+            .collect();
+        let count = T::from(means.len()).unwrap();
+        let mean = means.iter().fold(T::zero(), |sum, &d| sum + d) / count;
+        let variance = means
+            .iter()
+            .fold(T::zero(), |sum, &d| sum + (d - mean) * (d - mean))
+            / count;
+        let limit = mean + std_ratio * variance.sqrt();
+        means.into_iter().map(|d| d <= limit).collect()
+    }
+
+    /// The points kept by [`MultiPointZ::statistical_outlier_mask`].
+    #[must_use]
+    pub fn remove_statistical_outliers(&self, k: usize, std_ratio: T) -> Self {
+        self.filtered(&self.statistical_outlier_mask(k, std_ratio))
+    }
+
+    /// Flags the points to keep by radius outlier removal, `true` for each point with
+    /// at least `min_neighbors` other points within `radius` of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{MultiPointZ, PointZ};
+    ///
+    /// let points = MultiPointZ::new(vec![
+    ///     PointZ::new(0., 0., 0.),
+    ///     PointZ::new(0.5, 0., 0.),
+    ///     PointZ::new(0., 0.5, 0.),
+    ///     PointZ::new(5., 5., 5.),
+    /// ]);
+    /// assert_eq!(points.radius_outlier_mask(1., 2), vec![true, true, true, false]);
+    /// assert_eq!(points.remove_radius_outliers(1., 2).0.len(), 3);
+    /// ```
+    pub fn radius_outlier_mask(&self, radius: T, min_neighbors: usize) -> Vec<bool> {
+        let tree = self.kdtree();
+        self.0
+            .iter()
+            // the point itself is among those found
+            .map(|point| tree.within_radius(point, radius).len() > min_neighbors)
+            .collect()
+    }
+
+    /// The points kept by [`MultiPointZ::radius_outlier_mask`].
+    #[must_use]
+    pub fn remove_radius_outliers(&self, radius: T, min_neighbors: usize) -> Self {
+        self.filtered(&self.radius_outlier_mask(radius, min_neighbors))
+    }
+
+    fn filtered(&self, keep: &[bool]) -> Self {
+        self.0
+            .iter()
+            .zip(keep)
+            .filter(|(_, &keep)| keep)
+            .map(|(point, _)| *point)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::PointZ;
+
+    #[test]
+    fn removes_outliers() {
+        // a noisy sampled surface, with a few stray points around it
+        let mut points: Vec<PointZ> = (0..400)
+            .map(|i| {
+                let (x, y) = ((i % 20) as f64 * 0.5, (i / 20) as f64 * 0.5);
+                let noise = ((i * 31 % 7) as f64 - 3.) * 0.01;
+                PointZ::new(x, y, (x * 0.3).sin() + noise)
+            })
+            .collect();
+        let strays = [(2., 3., 6.), (-4., 5., 0.), (5., 14., 1.), (9., 9., -3.)];
+        points.extend(strays.iter().map(|&(x, y, z)| PointZ::new(x, y, z)));
+        let points = MultiPointZ::new(points);
+
+        let keep = points.statistical_outlier_mask(6, 1.);
+        assert!(keep[..400].iter().all(|&keep| keep));
+        assert!(keep[400..].iter().all(|&keep| !keep));
+        assert_eq!(points.remove_statistical_outliers(6, 1.).0, points.0[..400]);
+
+        let keep = points.radius_outlier_mask(0.8, 2);
+        assert_eq!(keep, [vec![true; 400], vec![false; 4]].concat());
+        assert_eq!(points.remove_radius_outliers(0.8, 2).0, points.0[..400]);
+
+        // too few points to judge
+        let few = MultiPointZ::new(points.0[..3].to_vec());
+        assert_eq!(few.statistical_outlier_mask(6, 1.), vec![true; 3]);
+        assert!(MultiPointZ::<f64>::empty()
+            .radius_outlier_mask(1., 1)
+            .is_empty());
+    }
+}
@@ -0,0 +1,91 @@
+use core::cell::Cell;
+
+use crate::algorithm::MapCoordsZ;
+use crate::splitmix::SplitMix64;
+use crate::{CoordFloat, CoordZ};
+
+/// Move each coordinate of a geometry by a small pseudo-random offset, such as to
+/// pull apart overlapping points on a map, or to test how robust an algorithm is
+/// to noise.
+///
+/// Each ordinate moves by up to `max_offset` either way, uniformly. The offsets
+/// come from a SplitMix64 generator seeded with `seed`, so the same geometry and
+/// seed always give the same result, on any platform and without depending on
+/// `rand`. Coincident coordinates get different offsets. Planar members of a
+/// [`Geometry`](crate::Geometry) keep only their x and y, as with [`MapCoordsZ`].
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::Perturb;
+/// use geo_types_3d::{MultiPointZ, PointZ};
+///
+/// // three sensors reported at the same place
+/// let sensors: MultiPointZ = MultiPointZ::from(vec![(10., 20., 5.); 3]);
+/// let jittered = sensors.perturb(0.5, 42);
+/// assert_eq!(jittered, sensors.perturb(0.5, 42));
+/// assert_ne!(jittered.0[0], jittered.0[1]);
+/// for point in jittered.iter() {
+///     let offset = (*point - PointZ::new(10., 20., 5.)).0;
+///     assert!(offset.x.abs() <= 0.5 && offset.y.abs() <= 0.5 && offset.z.abs() <= 0.5);
+/// }
+/// ```
+pub trait Perturb<T: CoordFloat> {
+    #[must_use]
+    fn perturb(&self, max_offset: T, seed: u64) -> Self;
+}
+
+impl<T: CoordFloat, G: MapCoordsZ<T, T, Output = G>> Perturb<T> for G {
+    fn perturb(&self, max_offset: T, seed: u64) -> Self {
+        let random = Cell::new(SplitMix64(seed));
+        let offset = || {
+            let mut generator = random.get();
+            let unit = generator.next_f64();
+            random.set(generator);
+            (T::from(unit).unwrap() * (T::one() + T::one()) - T::one()) * max_offset
+        };
+        self.map_coords(|coord| {
+            coord
+                + CoordZ {
+                    x: offset(),
+                    y: offset(),
+                    z: offset(),
+                }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::BoundingCube;
+    use crate::{Cube, Geometry, GeometryCollection, LineStringZ};
+    use alloc::vec;
+
+    #[test]
+    fn seeded() {
+        let line: LineStringZ = (0..1000).map(|i| (f64::from(i), 0., 0.)).collect();
+        let a = line.perturb(0.1, 1);
+        assert_eq!(a, line.perturb(0.1, 1));
+        assert_ne!(a, line.perturb(0.1, 2));
+        let bounds = a.bounding_cube().unwrap();
+        assert!(Cube::new((-0.1, -0.1, -0.1), (999.1, 0.1, 0.1)).contains_coord(bounds.min()));
+        assert!(Cube::new((-0.1, -0.1, -0.1), (999.1, 0.1, 0.1)).contains_coord(bounds.max()));
+        // the offsets fill the range
+        assert!(bounds.min().z < -0.09 && bounds.max().z > 0.09);
+        assert_eq!(line.perturb(0., 7), line);
+
+        let mixed = Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::Point(geo_types::Point::new(1., 1.)),
+            Geometry::from(line),
+        ]));
+        let Geometry::GeometryCollection(perturbed) = mixed.perturb(1e-3, 3) else {
+            unreachable!()
+        };
+        assert!(matches!(perturbed.0[0], Geometry::Point(_)));
+        assert_ne!(
+            perturbed.0[0],
+            Geometry::Point(geo_types::Point::new(1., 1.))
+        );
+    }
+}
@@ -0,0 +1,403 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use super::ransac::fit_plane;
+use crate::{CoordFloat, CoordZ, Error, LineStringZ, MultiLineStringZ, MultiPolygonZ, PolygonZ};
+
+/// The result of [`MultiLineStringZ::polygonize`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygonized<T: CoordFloat = f64> {
+    /// The faces enclosed by the linework.
+    pub polygons: MultiPolygonZ<T>,
+    /// Line strings with an end touching no other line string, directly or through
+    /// other dangles, which bound no face.
+    pub dangles: MultiLineStringZ<T>,
+    /// Line strings joining faces, or joining a face to the rest of the linework,
+    /// with the same face on both sides.
+    pub cut_edges: MultiLineStringZ<T>,
+}
+
+impl<T: CoordFloat> MultiLineStringZ<T> {
+    /// Builds the polygons enclosed by linework lying in a common plane, as GEOS's
+    /// polygonizer does for planar linework.
+    ///
+    /// The line strings must be noded: they may only meet at their ends, with
+    /// exactly equal coordinates. Each closed area they enclose becomes a polygon,
+    /// with holes for any linework nested inside it, keeping the original
+    /// coordinates, including z. Line strings bounding no face are reported as
+    /// dangles or cut edges instead.
+    ///
+    /// The plane is fitted to all the coordinates and may have any orientation, as
+    /// for [`BooleanOpsZ`](crate::algorithm::BooleanOpsZ); fails with
+    /// [`Error::NotCoplanar`] if any coordinate is further from it than a small
+    /// distance relative to the size of the linework. See
+    /// [`MultiLineStringZ::polygonize_with_tolerance`] to choose the distance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{line_string_z, MultiLineStringZ};
+    ///
+    /// // two rooms on a sloping floor, sharing a wall, with a corridor stub
+    /// let walls: MultiLineStringZ = MultiLineStringZ::new(vec![
+    ///     line_string_z![(x: 0., y: 0., z: 0.), (x: 4., y: 0., z: 0.)],
+    ///     line_string_z![(x: 4., y: 0., z: 0.), (x: 4., y: 4., z: 1.)],
+    ///     line_string_z![(x: 4., y: 4., z: 1.), (x: 0., y: 4., z: 1.), (x: 0., y: 0., z: 0.)],
+    ///     line_string_z![(x: 4., y: 0., z: 0.), (x: 8., y: 0., z: 0.), (x: 8., y: 4., z: 1.)],
+    ///     line_string_z![(x: 8., y: 4., z: 1.), (x: 4., y: 4., z: 1.)],
+    ///     line_string_z![(x: 8., y: 4., z: 1.), (x: 10., y: 4., z: 1.)],
+    /// ]);
+    /// let result = walls.polygonize().unwrap();
+    /// assert_eq!(result.polygons.0.len(), 2);
+    /// assert_eq!(result.dangles.0.len(), 1);
+    /// assert!(result.cut_edges.0.is_empty());
+    /// ```
+    pub fn polygonize(&self) -> Result<Polygonized<T>, Error> {
+        let coords: Vec<CoordZ<T>> = self
+            .0
+            .iter()
+            .flat_map(|line| line.0.iter().copied())
+            .collect();
+        let mut min = CoordZ::from((T::infinity(), T::infinity(), T::infinity()));
+        let mut max = CoordZ::from((T::neg_infinity(), T::neg_infinity(), T::neg_infinity()));
+        for c in &coords {
+            min = CoordZ::from((min.x.min(c.x), min.y.min(c.y), min.z.min(c.z)));
+            max = CoordZ::from((max.x.max(c.x), max.y.max(c.y), max.z.max(c.z)));
+        }
+        let size = (max.x - min.x).max(max.y - min.y).max(max.z - min.z);
+        self.polygonize_with_tolerance(size.max(T::one()) * T::epsilon().sqrt())
+    }
+
+    /// Like [`MultiLineStringZ::polygonize`], accepting coordinates up to
+    /// `tolerance` from the common plane.
+    pub fn polygonize_with_tolerance(&self, tolerance: T) -> Result<Polygonized<T>, Error> {
+        let coords: Vec<CoordZ<T>> = self
+            .0
+            .iter()
+            .flat_map(|line| line.0.iter().copied())
+            .collect();
+        let Some(plane) = (!coords.is_empty()).then(|| fit_plane(&coords)).flatten() else {
+            return Ok(Polygonized {
+                polygons: MultiPolygonZ::empty(),
+                dangles: MultiLineStringZ::empty(),
+                cut_edges: MultiLineStringZ::empty(),
+            });
+        };
+        if coords
+            .iter()
+            .any(|&c| plane.signed_distance(c).abs() > tolerance)
+        {
+            return Err(Error::NotCoplanar);
+        }
+        // project onto the coordinate plane most parallel to the fitted one
+        let n = plane.normal;
+        let project: fn(CoordZ<T>) -> [T; 2] = if n.z.abs() >= n.x.abs() && n.z.abs() >= n.y.abs() {
+            |c| [c.x, c.y]
+        } else if n.x.abs() >= n.y.abs() {
+            |c| [c.y, c.z]
+        } else {
+            |c| [c.z, c.x]
+        };
+        Ok(Graph::new(&self.0, project).polygonize())
+    }
+}
+
+/// The linework as a graph, with a node at each end of the line strings and the
+/// line strings as edges. Half-edge `2 * edge` runs along its line string, and
+/// `2 * edge + 1` back.
+struct Graph<'a, T: CoordFloat> {
+    edges: Vec<&'a LineStringZ<T>>,
+    nodes: Vec<CoordZ<T>>,
+    /// The start and end nodes of each edge.
+    ends: Vec<[usize; 2]>,
+    project: fn(CoordZ<T>) -> [T; 2],
+}
+
+impl<'a, T: CoordFloat> Graph<'a, T> {
+    fn new(lines: &'a [LineStringZ<T>], project: fn(CoordZ<T>) -> [T; 2]) -> Self {
+        // line strings of one point, or with all their points equal, are no edges
+        let edges: Vec<&LineStringZ<T>> = lines
+            .iter()
+            .filter(|line| line.0.iter().any(|c| *c != line.0[0]))
+            .collect();
+        let mut nodes: Vec<CoordZ<T>> = Vec::new();
+        let mut node = |coord: CoordZ<T>| match nodes.iter().position(|n| *n == coord) {
+            Some(index) => index,
+            None => {
+                nodes.push(coord);
+                nodes.len() - 1
+            }
+        };
+        let ends = edges
+            .iter()
+            .map(|line| [node(line.0[0]), node(line.0[line.0.len() - 1])])
+            .collect();
+        Self {
+            edges,
+            nodes,
+            ends,
+            project,
+        }
+    }
+
+    fn polygonize(&self) -> Polygonized<T> {
+        let mut active = vec![true; self.edges.len()];
+        let dangles = self.remove_dangles(&mut active);
+        let (rings, ring_of) = self.rings(&active);
+        let mut cut_edges = Vec::new();
+        for edge in 0..self.edges.len() {
+            if active[edge] && ring_of[2 * edge] == ring_of[2 * edge + 1] {
+                active[edge] = false;
+                cut_edges.push(self.edges[edge].clone());
+            }
+        }
+        let rings = if cut_edges.is_empty() {
+            rings
+        } else {
+            self.rings(&active).0
+        };
+        let component = self.components(&active);
+
+        // faces run anticlockwise; the outer boundary of each connected part of the
+        // linework runs clockwise, and is a hole in the face it lies in, if any
+        let (shells, holes): (Vec<_>, Vec<_>) = rings
+            .into_iter()
+            .map(|(ring, first)| {
+                let area = self.area(&ring);
+                (ring, area, component[self.ends[first / 2][0]])
+            })
+            .partition(|(_, area, _)| *area > T::zero());
+        let mut interiors: Vec<Vec<LineStringZ<T>>> = vec![Vec::new(); shells.len()];
+        for (hole, _, part) in holes {
+            let inside = self.point(hole.0[0]);
+            let smallest = shells
+                .iter()
+                .enumerate()
+                .filter(|(_, (shell, _, shell_part))| {
+                    *shell_part != part && self.contains(shell, inside)
+                })
+                .min_by(|a, b| a.1 .1.partial_cmp(&b.1 .1).unwrap_or(Ordering::Equal));
+            if let Some((index, _)) = smallest {
+                interiors[index].push(hole);
+            }
+        }
+        Polygonized {
+            polygons: MultiPolygonZ::new(
+                shells
+                    .into_iter()
+                    .zip(interiors)
+                    .map(|((shell, _, _), interiors)| PolygonZ::new(shell, interiors))
+                    .collect(),
+            ),
+            dangles: MultiLineStringZ::new(dangles),
+            cut_edges: MultiLineStringZ::new(cut_edges),
+        }
+    }
+
+    /// Removes edges with an end at a node no other edge reaches, repeatedly.
+    fn remove_dangles(&self, active: &mut [bool]) -> Vec<LineStringZ<T>> {
+        let mut degree = vec![0usize; self.nodes.len()];
+        for &[start, end] in &self.ends {
+            degree[start] += 1;
+            degree[end] += 1;
+        }
+        let mut removed = Vec::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (edge, &[start, end]) in self.ends.iter().enumerate() {
+                if active[edge] && (degree[start] == 1 || degree[end] == 1) {
+                    active[edge] = false;
+                    degree[start] -= 1;
+                    degree[end] -= 1;
+                    removed.push(edge);
+                    changed = true;
+                }
+            }
+        }
+        removed.sort_unstable();
+        removed
+            .into_iter()
+            .map(|edge| self.edges[edge].clone())
+            .collect()
+    }
+
+    /// The rings of half-edges around each face, keeping the face on their left,
+    /// each with its first half-edge, and the ring of each half-edge.
+    #[allow(clippy::type_complexity)]
+    fn rings(&self, active: &[bool]) -> (Vec<(LineStringZ<T>, usize)>, Vec<usize>) {
+        // the half-edges leaving each node, anticlockwise
+        let mut leaving: Vec<Vec<(T, usize)>> = vec![Vec::new(); self.nodes.len()];
+        for (edge, _) in active.iter().enumerate().filter(|(_, active)| **active) {
+            for half in [2 * edge, 2 * edge + 1] {
+                let coords = self.coords(half);
+                let from = self.point(coords[0]);
+                let towards = coords
+                    .iter()
+                    .map(|&c| self.point(c))
+                    .find(|c| *c != from)
+                    .unwrap_or(from);
+                let angle = (towards[1] - from[1]).atan2(towards[0] - from[0]);
+                leaving[self.origin(half)].push((angle, half));
+            }
+        }
+        for half_edges in &mut leaving {
+            half_edges.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        }
+
+        let mut ring_of = vec![usize::MAX; 2 * self.edges.len()];
+        let mut rings = Vec::new();
+        for first in 0..2 * self.edges.len() {
+            if !active[first / 2] || ring_of[first] != usize::MAX {
+                continue;
+            }
+            let mut coords: Vec<CoordZ<T>> = Vec::new();
+            let mut half = first;
+            while ring_of[half] == usize::MAX {
+                ring_of[half] = rings.len();
+                let skip = usize::from(!coords.is_empty());
+                coords.extend(self.coords(half).into_iter().skip(skip));
+                // turn as far right as possible: the half-edge just clockwise of
+                // the way back
+                let at = &leaving[self.origin(half ^ 1)];
+                let back = at.iter().position(|&(_, h)| h == half ^ 1).unwrap_or(0);
+                half = at[(back + at.len() - 1) % at.len()].1;
+            }
+            rings.push((LineStringZ::new(coords), first));
+        }
+        (rings, ring_of)
+    }
+
+    /// A label for each node, the same for nodes joined by active edges.
+    fn components(&self, active: &[bool]) -> Vec<usize> {
+        let mut label: Vec<usize> = (0..self.nodes.len()).collect();
+        fn find(label: &mut [usize], mut node: usize) -> usize {
+            while label[node] != node {
+                label[node] = label[label[node]];
+                node = label[node];
+            }
+            node
+        }
+        for (edge, &[start, end]) in self.ends.iter().enumerate() {
+            if active[edge] {
+                let (a, b) = (find(&mut label, start), find(&mut label, end));
+                label[a] = b;
+            }
+        }
+        (0..self.nodes.len())
+            .map(|node| find(&mut label, node))
+            .collect()
+    }
+
+    fn origin(&self, half: usize) -> usize {
+        self.ends[half / 2][half % 2]
+    }
+
+    fn coords(&self, half: usize) -> Vec<CoordZ<T>> {
+        let mut coords = self.edges[half / 2].0.clone();
+        if half % 2 == 1 {
+            coords.reverse();
+        }
+        coords
+    }
+
+    fn point(&self, coord: CoordZ<T>) -> [T; 2] {
+        (self.project)(coord)
+    }
+
+    /// Twice the signed area of a closed ring, projected.
+    fn area(&self, ring: &LineStringZ<T>) -> T {
+        ring.lines().fold(T::zero(), |sum, line| {
+            let (a, b) = (self.point(line.start), self.point(line.end));
+            sum + a[0] * b[1] - b[0] * a[1]
+        })
+    }
+
+    /// Whether `point` lies inside the projected ring, by counting crossings.
+    fn contains(&self, ring: &LineStringZ<T>, point: [T; 2]) -> bool {
+        let mut inside = false;
+        for line in ring.lines() {
+            let (a, b) = (self.point(line.start), self.point(line.end));
+            if (a[1] > point[1]) != (b[1] > point[1]) {
+                let x = a[0] + (point[1] - a[1]) / (b[1] - a[1]) * (b[0] - a[0]);
+                if point[0] < x {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn line(coords: &[(f64, f64, f64)]) -> LineStringZ {
+        coords.to_vec().into()
+    }
+
+    #[test]
+    fn polygonize() {
+        // on a wall in the plane y = 2: a square split in two, a square island in
+        // the right half with a dangle reaching out from it, and a second square
+        // joined to the first by a cut edge
+        let wall = |coords: &[(f64, f64)]| {
+            line(&coords.iter().map(|&(x, z)| (x, 2., z)).collect::<Vec<_>>())
+        };
+        let lines = MultiLineStringZ::new(vec![
+            wall(&[(0., 0.), (5., 0.)]),
+            wall(&[(5., 0.), (10., 0.), (10., 10.)]),
+            wall(&[(10., 10.), (5., 10.)]),
+            wall(&[(5., 10.), (0., 10.), (0., 0.)]),
+            wall(&[(5., 10.), (5., 0.)]),
+            wall(&[(6., 2.), (8., 2.), (8., 4.), (6., 4.), (6., 2.)]),
+            wall(&[(6., 2.), (5.5, 1.)]),
+            wall(&[(10., 10.), (12., 12.)]),
+            wall(&[(12., 12.), (14., 12.), (14., 14.), (12., 14.), (12., 12.)]),
+        ]);
+        let result = lines.polygonize().unwrap();
+        assert_eq!(
+            result.dangles,
+            MultiLineStringZ::new(vec![lines.0[6].clone()])
+        );
+        assert_eq!(
+            result.cut_edges,
+            MultiLineStringZ::new(vec![lines.0[7].clone()])
+        );
+
+        // the areas of the faces, less their holes, and their numbers of holes
+        let ring_area = |ring: &LineStringZ| {
+            ring.lines()
+                .map(|l| l.start.z * l.end.x - l.end.z * l.start.x)
+                .sum::<f64>()
+                .abs()
+                / 2.
+        };
+        let mut faces: Vec<(f64, usize)> = result
+            .polygons
+            .iter()
+            .map(|polygon| {
+                let holes: f64 = polygon.interiors().iter().map(ring_area).sum();
+                (
+                    ring_area(polygon.exterior()) - holes,
+                    polygon.interiors().len(),
+                )
+            })
+            .collect();
+        faces.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(faces, vec![(4., 0), (4., 0), (46., 1), (50., 0)]);
+
+        let bent = MultiLineStringZ::new(vec![line(&[
+            (0., 0., 0.),
+            (1., 0., 0.),
+            (1., 1., 5.),
+            (0., 1., 0.),
+            (0., 0., 0.),
+        ])]);
+        assert!(matches!(bent.polygonize(), Err(Error::NotCoplanar)));
+        let empty = MultiLineStringZ::<f64>::empty().polygonize().unwrap();
+        assert!(empty.polygons.0.is_empty());
+    }
+}
@@ -0,0 +1,201 @@
+use crate::algorithm::linalg::symmetric_eigen;
+use crate::{CoordFloat, CoordZ, MultiPointZ, Obb};
+
+/// The directions in which a set of points spreads out most and least, from
+/// principal component analysis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrincipalAxes<T: CoordFloat = f64> {
+    /// The mean of the points.
+    pub centroid: CoordZ<T>,
+    /// Unit axes at right angles to one another, in decreasing order of variance,
+    /// with the third the cross product of the first two.
+    pub axes: [CoordZ<T>; 3],
+    /// The variance of the points along each axis.
+    pub variances: [T; 3],
+}
+
+impl<T: CoordFloat> MultiPointZ<T> {
+    /// The principal axes of the points, or `None` if there are none.
+    ///
+    /// The first axis follows the length of an elongated cloud, such as a scan of
+    /// a pipe, and the last is normal to a flat one, such as a scan of a wall.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{MultiPointZ, PointZ};
+    ///
+    /// // points along a diagonal line, spread a little across it
+    /// let points: MultiPointZ = (0..50)
+    ///     .map(|i| {
+    ///         let t = i as f64;
+    ///         let wobble = if i % 2 == 0 { 0.1 } else { -0.1 };
+    ///         PointZ::new(t, t + wobble, 5.)
+    ///     })
+    ///     .collect();
+    /// let principal = points.principal_axes().unwrap();
+    /// let along = principal.axes[0];
+    /// assert!((along.x.abs() - 0.5f64.sqrt()).abs() < 1e-3);
+    /// assert!((along.y.abs() - 0.5f64.sqrt()).abs() < 1e-3);
+    /// assert!(principal.variances[2] < 1e-12);
+    /// ```
+    pub fn principal_axes(&self) -> Option<PrincipalAxes<T>> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let count = T::from(self.0.len()).unwrap();
+        let centroid = self.0.iter().fold(CoordZ::zero(), |sum, p| sum + p.0) / count;
+        let mut covariance = [[T::zero(); 3]; 3];
+        for point in &self.0 {
+            let d = point.0 - centroid;
+            let d = [d.x, d.y, d.z];
+            for (row, &a) in covariance.iter_mut().zip(&d) {
+                for (value, &b) in row.iter_mut().zip(&d) {
+                    *value = *value + a * b / count;
+                }
+            }
+        }
+        let [first, second, third] = symmetric_eigen(covariance);
+        let axis = |[x, y, z]: [T; 3]| CoordZ { x, y, z };
+        let (a, b) = (axis(first.1), axis(second.1));
+        // make the axes right-handed
+        let c = CoordZ {
+            x: a.y * b.z - a.z * b.y,
+            y: a.z * b.x - a.x * b.z,
+            z: a.x * b.y - a.y * b.x,
+        };
+        Some(PrincipalAxes {
+            centroid,
+            axes: [a, b, c],
+            variances: [first.0, second.0, third.0.max(T::zero())],
+        })
+    }
+
+    /// A box around the points aligned with their [principal axes], or `None` if
+    /// there are no points.
+    ///
+    /// This is usually much tighter than the [bounding cube] of points that don't
+    /// line up with the x, y and z axes, though not always the smallest box.
+    ///
+    /// [principal axes]: MultiPointZ::principal_axes
+    /// [bounding cube]: crate::algorithm::BoundingCube
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::algorithm::BoundingCube;
+    /// use geo_types_3d::{MultiPointZ, PointZ};
+    ///
+    /// // the corners of a long, thin box, turned 30° about z
+    /// let (sin, cos) = 30f64.to_radians().sin_cos();
+    /// let points: MultiPointZ = [(-5., -1.), (5., -1.), (5., 1.), (-5., 1.)]
+    ///     .into_iter()
+    ///     .flat_map(|(x, y)| [(x, y, 0.), (x, y, 2.)])
+    ///     .map(|(x, y, z)| PointZ::new(x * cos - y * sin, x * sin + y * cos, z))
+    ///     .collect();
+    ///
+    /// let obb = points.oriented_bounding_box().unwrap();
+    /// assert!((obb.volume() - 40.).abs() < 1e-9);
+    /// assert!(points.bounding_cube().unwrap().volume() > 100.);
+    /// ```
+    pub fn oriented_bounding_box(&self) -> Option<Obb<T>> {
+        let principal = self.principal_axes()?;
+        let axes = principal.axes;
+        let mut low = [T::infinity(); 3];
+        let mut high = [T::neg_infinity(); 3];
+        for point in &self.0 {
+            let d = point.0 - principal.centroid;
+            for (i, axis) in axes.iter().enumerate() {
+                let along = axis.x * d.x + axis.y * d.y + axis.z * d.z;
+                low[i] = low[i].min(along);
+                high[i] = high[i].max(along);
+            }
+        }
+        let two = T::one() + T::one();
+        let [cx, cy, cz] = core::array::from_fn(|i| (low[i] + high[i]) / two);
+        let [hx, hy, hz] = core::array::from_fn(|i| (high[i] - low[i]) / two);
+        let rotation = [
+            [axes[0].x, axes[1].x, axes[2].x],
+            [axes[0].y, axes[1].y, axes[2].y],
+            [axes[0].z, axes[1].z, axes[2].z],
+        ];
+        let mut obb = Obb::new(
+            CoordZ::zero(),
+            CoordZ {
+                x: hx,
+                y: hy,
+                z: hz,
+            },
+            rotation,
+        );
+        obb.center = principal.centroid
+            + obb.to_world(CoordZ {
+                x: cx,
+                y: cy,
+                z: cz,
+            });
+        Some(obb)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::PointZ;
+    use alloc::vec;
+
+    #[test]
+    fn oriented_box() {
+        // a slab, turned about two axes and moved away from the origin
+        let transform = crate::algorithm::AffineTransform3D::rotate_x(25.)
+            .rotated_z(-60.)
+            .translated(10., -4., 7.);
+        let points: MultiPointZ = (0..1000)
+            .map(|i| {
+                let f = |k: usize| ((i * k) % 101) as f64 / 100.;
+                let local = CoordZ {
+                    x: f(7) * 8. - 4.,
+                    y: f(13) * 3. - 1.5,
+                    z: f(31) * 0.5 - 0.25,
+                };
+                PointZ(transform.apply(local))
+            })
+            .collect();
+
+        let principal = points.principal_axes().unwrap();
+        assert!(principal.variances[0] > principal.variances[1]);
+        assert!(principal.variances[1] > principal.variances[2]);
+
+        let obb = points.oriented_bounding_box().unwrap();
+        assert!(points.iter().all(|p| {
+            let local = obb.to_local(p.0);
+            let e = obb.half_extents;
+            local.x.abs() <= e.x + 1e-9
+                && local.y.abs() <= e.y + 1e-9
+                && local.z.abs() <= e.z + 1e-9
+        }));
+        assert_relative_eq!(
+            obb.half_extents,
+            CoordZ {
+                x: 4.,
+                y: 1.5,
+                z: 0.25
+            },
+            epsilon = 0.05
+        );
+        assert_relative_eq!(obb.center, transform.apply(CoordZ::zero()), epsilon = 0.05);
+
+        let single = MultiPointZ::new(vec![PointZ::new(1., 2., 3.)]);
+        let obb = single.oriented_bounding_box().unwrap();
+        assert_eq!(
+            obb.center,
+            CoordZ {
+                x: 1.,
+                y: 2.,
+                z: 3.
+            }
+        );
+        assert_eq!(obb.volume(), 0.);
+        assert!(MultiPointZ::<f64>::empty().principal_axes().is_none());
+    }
+}
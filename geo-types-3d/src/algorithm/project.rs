@@ -0,0 +1,376 @@
+use alloc::vec::Vec;
+
+use geo_types::{Coord, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon};
+
+use crate::{
+    CoordFloat, CoordZ, Geometry, GeometryCollection, LineStringZ, LineZ, MultiLineStringZ,
+    MultiPointZ, MultiPolygonZ, PointZ, PolygonZ,
+};
+
+/// The part of the screen, in pixels, that a camera draws into.
+///
+/// Screen coordinates run right and down from the top left corner of the screen,
+/// as for images and pointer events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Viewport<T: CoordFloat = f64> {
+    /// The left edge of the viewport.
+    pub x: T,
+    /// The top edge of the viewport.
+    pub y: T,
+    pub width: T,
+    pub height: T,
+}
+
+impl<T: CoordFloat> Viewport<T> {
+    /// A viewport of `width` by `height` pixels at the top left of the screen.
+    pub fn new(width: T, height: T) -> Self {
+        Self {
+            x: T::zero(),
+            y: T::zero(),
+            width,
+            height,
+        }
+    }
+}
+
+/// A geometry projected onto the screen, with the depth of each of its coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projected<G, T: CoordFloat = f64> {
+    /// The geometry in screen coordinates.
+    pub geometry: G,
+    /// The depth of each coordinate of the geometry, in the order they are stored,
+    /// from 0 on the near plane of the camera to 1 on its far plane.
+    pub depths: Vec<T>,
+}
+
+impl<G, T: CoordFloat> Projected<G, T> {
+    /// The depth of the nearest coordinate, or `None` if there are none, to draw or
+    /// hit-test geometries from front to back.
+    pub fn nearest(&self) -> Option<T> {
+        self.depths.iter().copied().reduce(T::min)
+    }
+}
+
+/// Project geometries onto the screen through a camera, as planar [`geo_types`]
+/// geometries with the depth of each coordinate.
+///
+/// The camera is a view-projection matrix, given by its rows, taking
+/// `[x, y, z, 1]` in world coordinates to clip coordinates `[x, y, z, w]`, as for
+/// [`Frustum::from_matrix`](crate::Frustum::from_matrix). An orthographic camera
+/// may be an [`AffineTransform3D`](super::AffineTransform3D), by its
+/// [`homogeneous`](super::AffineTransform3D::homogeneous) matrix.
+///
+/// Returns `None` if any coordinate lies behind the camera, where it has no place
+/// on the screen. [`Frustum::cull`](crate::Frustum::cull) leaves out geometries
+/// that can't be seen at all, but those crossing the plane of the camera need
+/// clipping first.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::project::{ProjectToScreen, Viewport};
+/// use geo_types_3d::algorithm::AffineTransform3D;
+/// use geo_types_3d::PointZ;
+///
+/// // looking down -z at the square from -10 to 10 in x and y, seeing z from 0 to -20
+/// let camera = AffineTransform3D::translate(0., 0., 10.).scaled(0.1, 0.1, -0.1);
+/// let viewport = Viewport::new(800., 600.);
+///
+/// let label = PointZ::new(5., 5., -15.)
+///     .project_to_screen(&camera.homogeneous(), &viewport)
+///     .unwrap();
+/// assert_eq!(label.geometry, geo_types::Point::new(600., 150.));
+/// assert_eq!(label.depths, [0.75]);
+/// ```
+pub trait ProjectToScreen<T: CoordFloat> {
+    type Output;
+
+    fn project_to_screen(
+        &self,
+        view_proj: &[[T; 4]; 4],
+        viewport: &Viewport<T>,
+    ) -> Option<Projected<Self::Output, T>>;
+}
+
+/// Projects coordinates one at a time, collecting their depths.
+struct Projector<'a, T: CoordFloat> {
+    view_proj: &'a [[T; 4]; 4],
+    viewport: &'a Viewport<T>,
+    depths: Vec<T>,
+}
+
+impl<'a, T: CoordFloat> Projector<'a, T> {
+    fn new(view_proj: &'a [[T; 4]; 4], viewport: &'a Viewport<T>) -> Self {
+        Self {
+            view_proj,
+            viewport,
+            depths: Vec::new(),
+        }
+    }
+
+    fn coord(&mut self, coord: CoordZ<T>) -> Option<Coord<T>> {
+        let [x, y, z, w] = self
+            .view_proj
+            .map(|[a, b, c, d]| a * coord.x + b * coord.y + c * coord.z + d);
+        if w <= T::zero() {
+            return None;
+        }
+        let half = T::from(0.5).unwrap();
+        let v = self.viewport;
+        self.depths.push((z / w + T::one()) * half);
+        Some(Coord {
+            x: v.x + (x / w + T::one()) * half * v.width,
+            y: v.y + (T::one() - y / w) * half * v.height,
+        })
+    }
+
+    fn flat(&mut self, coord: Coord<T>) -> Option<Coord<T>> {
+        self.coord(CoordZ {
+            x: coord.x,
+            y: coord.y,
+            z: T::zero(),
+        })
+    }
+
+    fn line_string(&mut self, line_string: &LineStringZ<T>) -> Option<LineString<T>> {
+        line_string.0.iter().map(|c| self.coord(*c)).collect()
+    }
+
+    fn polygon(&mut self, polygon: &PolygonZ<T>) -> Option<Polygon<T>> {
+        Some(Polygon::new(
+            self.line_string(polygon.exterior())?,
+            polygon
+                .interiors()
+                .iter()
+                .map(|ring| self.line_string(ring))
+                .collect::<Option<_>>()?,
+        ))
+    }
+
+    fn flat_line_string(&mut self, line_string: &LineString<T>) -> Option<LineString<T>> {
+        line_string.0.iter().map(|c| self.flat(*c)).collect()
+    }
+
+    fn flat_polygon(&mut self, polygon: &Polygon<T>) -> Option<Polygon<T>> {
+        Some(Polygon::new(
+            self.flat_line_string(polygon.exterior())?,
+            polygon
+                .interiors()
+                .iter()
+                .map(|ring| self.flat_line_string(ring))
+                .collect::<Option<_>>()?,
+        ))
+    }
+
+    fn geometry(&mut self, geometry: &Geometry<T>) -> Option<geo_types::Geometry<T>> {
+        use geo_types::Geometry as G;
+        Some(match geometry {
+            Geometry::Point(point) => G::Point(Point(self.flat(point.0)?)),
+            Geometry::PointZ(point) => G::Point(Point(self.coord(point.0)?)),
+            Geometry::Line(line) => G::Line(geo_types::Line::new(
+                self.flat(line.start)?,
+                self.flat(line.end)?,
+            )),
+            Geometry::LineZ(line) => G::Line(geo_types::Line::new(
+                self.coord(line.start)?,
+                self.coord(line.end)?,
+            )),
+            Geometry::LineString(line_string) => G::LineString(self.flat_line_string(line_string)?),
+            Geometry::LineStringZ(line_string) => G::LineString(self.line_string(line_string)?),
+            Geometry::Polygon(polygon) => G::Polygon(self.flat_polygon(polygon)?),
+            Geometry::PolygonZ(polygon) => G::Polygon(self.polygon(polygon)?),
+            Geometry::MultiPoint(multi) => G::MultiPoint(
+                multi
+                    .iter()
+                    .map(|point| self.flat(point.0).map(Point))
+                    .collect::<Option<_>>()?,
+            ),
+            Geometry::MultiPointZ(multi) => G::MultiPoint(
+                multi
+                    .iter()
+                    .map(|point| self.coord(point.0).map(Point))
+                    .collect::<Option<_>>()?,
+            ),
+            Geometry::MultiLineString(multi) => G::MultiLineString(MultiLineString(
+                multi
+                    .iter()
+                    .map(|line_string| self.flat_line_string(line_string))
+                    .collect::<Option<_>>()?,
+            )),
+            Geometry::MultiLineStringZ(multi) => G::MultiLineString(MultiLineString(
+                multi
+                    .iter()
+                    .map(|line_string| self.line_string(line_string))
+                    .collect::<Option<_>>()?,
+            )),
+            Geometry::MultiPolygon(multi) => G::MultiPolygon(MultiPolygon(
+                multi
+                    .iter()
+                    .map(|polygon| self.flat_polygon(polygon))
+                    .collect::<Option<_>>()?,
+            )),
+            Geometry::MultiPolygonZ(multi) => G::MultiPolygon(MultiPolygon(
+                multi
+                    .iter()
+                    .map(|polygon| self.polygon(polygon))
+                    .collect::<Option<_>>()?,
+            )),
+            Geometry::GeometryCollection(collection) => {
+                G::GeometryCollection(self.geometry_collection(collection)?)
+            }
+            // under perspective a rectangle needn't stay one
+            Geometry::Rect(rect) => G::Polygon(self.flat_polygon(&rect.to_polygon())?),
+        })
+    }
+
+    fn geometry_collection(
+        &mut self,
+        collection: &GeometryCollection<T>,
+    ) -> Option<geo_types::GeometryCollection<T>> {
+        collection.iter().map(|g| self.geometry(g)).collect()
+    }
+
+    fn finish<G>(self, geometry: Option<G>) -> Option<Projected<G, T>> {
+        Some(Projected {
+            geometry: geometry?,
+            depths: self.depths,
+        })
+    }
+}
+
+macro_rules! impl_project_to_screen {
+    ($($type:ident => $output:ty, |$projector:ident, $geometry:ident| $body:expr;)+) => {
+        $(
+            impl<T: CoordFloat> ProjectToScreen<T> for $type<T> {
+                type Output = $output;
+
+                fn project_to_screen(
+                    &self,
+                    view_proj: &[[T; 4]; 4],
+                    viewport: &Viewport<T>,
+                ) -> Option<Projected<Self::Output, T>> {
+                    let mut $projector = Projector::new(view_proj, viewport);
+                    let $geometry = self;
+                    let projected = $body;
+                    $projector.finish(projected)
+                }
+            }
+        )+
+    };
+}
+
+impl_project_to_screen! {
+    PointZ => Point<T>, |p, point| p.coord(point.0).map(Point);
+    LineZ => geo_types::Line<T>, |p, line| {
+        let start = p.coord(line.start);
+        start.zip(p.coord(line.end)).map(|(start, end)| geo_types::Line::new(start, end))
+    };
+    LineStringZ => LineString<T>, |p, line_string| p.line_string(line_string);
+    PolygonZ => Polygon<T>, |p, polygon| p.polygon(polygon);
+    MultiPointZ => MultiPoint<T>, |p, multi| {
+        multi.iter().map(|point| p.coord(point.0).map(Point)).collect::<Option<_>>()
+    };
+    MultiLineStringZ => MultiLineString<T>, |p, multi| {
+        multi.iter().map(|l| p.line_string(l)).collect::<Option<_>>().map(MultiLineString)
+    };
+    MultiPolygonZ => MultiPolygon<T>, |p, multi| {
+        multi.iter().map(|polygon| p.polygon(polygon)).collect::<Option<_>>().map(MultiPolygon)
+    };
+    Geometry => geo_types::Geometry<T>, |p, geometry| p.geometry(geometry);
+    GeometryCollection => geo_types::GeometryCollection<T>, |p, collection| {
+        p.geometry_collection(collection)
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::AffineTransform3D;
+    use crate::Frustum;
+    use alloc::vec;
+
+    /// Looking down -z with a 90° field of view, seeing from 1 to 100 units away.
+    fn perspective() -> [[f64; 4]; 4] {
+        let (near, far) = (1., 100.);
+        [
+            [1., 0., 0., 0.],
+            [0., 1., 0., 0.],
+            [
+                0.,
+                0.,
+                (far + near) / (near - far),
+                2. * far * near / (near - far),
+            ],
+            [0., 0., -1., 0.],
+        ]
+    }
+
+    #[test]
+    fn perspective_projection() {
+        let camera = perspective();
+        let viewport = Viewport {
+            x: 100.,
+            y: 0.,
+            width: 200.,
+            height: 100.,
+        };
+
+        // the same point on the screen, further away
+        let near = PointZ::new(1., 1., -2.)
+            .project_to_screen(&camera, &viewport)
+            .unwrap();
+        let far = PointZ::new(5., 5., -10.)
+            .project_to_screen(&camera, &viewport)
+            .unwrap();
+        assert_eq!(near.geometry, Point::new(250., 25.));
+        assert_eq!(far.geometry, near.geometry);
+        assert!(near.depths[0] < far.depths[0]);
+        let on_near_plane = PointZ::new(0., 0., -1.).project_to_screen(&camera, &viewport);
+        assert!(on_near_plane.unwrap().depths[0].abs() < 1e-12);
+
+        let polygon: PolygonZ = polygon_z![
+            (x: -1., y: -1., z: -2.),
+            (x: 1., y: -1., z: -2.),
+            (x: 1., y: 1., z: -4.),
+        ];
+        let projected = polygon.project_to_screen(&camera, &viewport).unwrap();
+        assert_eq!(projected.depths.len(), 4);
+        assert_eq!(
+            projected.geometry.exterior().0[1],
+            Coord { x: 250., y: 75. }
+        );
+        assert_eq!(projected.nearest(), Some(projected.depths[0]));
+
+        // a line reaching behind the camera
+        let line = LineZ::new((0., 0., -5.), (0., 0., 5.));
+        assert!(line.project_to_screen(&camera, &viewport).is_none());
+        let frustum = Frustum::from_matrix(camera).unwrap();
+        assert!(!frustum.contains_coord(line.end));
+    }
+
+    #[test]
+    fn geometries() {
+        let camera = AffineTransform3D::translate(0., 0., 10.)
+            .scaled(0.1, 0.1, -0.1)
+            .homogeneous();
+        let viewport = Viewport::new(100., 100.);
+        let collection = GeometryCollection::from(vec![
+            Geometry::PointZ(PointZ::new(10., 10., -10.)),
+            Geometry::Point(Point::new(-10., -10.)),
+            Geometry::Rect(geo_types::Rect::new((0., 0.), (10., 10.))),
+        ]);
+        let projected = collection.project_to_screen(&camera, &viewport).unwrap();
+        assert_eq!(projected.depths, [0.5, 0., 0., 0., 0., 0., 0.]);
+        assert_eq!(
+            projected.geometry.0[..2],
+            [
+                geo_types::Geometry::Point(Point::new(100., 0.)),
+                geo_types::Geometry::Point(Point::new(0., 100.)),
+            ]
+        );
+        assert!(matches!(
+            projected.geometry.0[2],
+            geo_types::Geometry::Polygon(_)
+        ));
+    }
+}
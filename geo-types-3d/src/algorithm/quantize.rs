@@ -0,0 +1,188 @@
+use core::cell::Cell;
+
+use num_traits::Bounded;
+
+use crate::algorithm::MapCoordsZ;
+use crate::{CoordFloat, CoordNum, CoordZ, Cube, Error};
+
+/// Quantize the coordinates of a geometry onto a regular grid of integers, such as
+/// for compact storage or glTF and 3D Tiles meshes.
+///
+/// Each coordinate is stored as the nearest grid cell, `(coord - origin) / scale`
+/// rounded, so it is off by at most half of `scale` on each axis once restored by
+/// [`Dequantize`]. [`fit_grid`] finds the grid spanning a bounding cube with the
+/// full range of an integer type. Planar members of a
+/// [`Geometry`](crate::Geometry) are quantized with z = 0, as with
+/// [`MapCoordsZ`], so z = 0 must be on the grid too.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::{fit_grid, BoundingCube, Dequantize, Quantize};
+/// use geo_types_3d::{wkt, LineStringZ};
+///
+/// let cable: LineStringZ = wkt! { LINESTRING Z(100. 200. 5.,140. 260. 25.,180. 200. 5.) };
+/// let (origin, scale) = fit_grid::<u16, _>(&cable.bounding_cube().unwrap());
+/// let (quantized, error) = cable.quantize::<u16>(origin, scale).unwrap();
+/// assert_eq!(quantized, wkt! { LINESTRING Z(0 0 0,32768 65535 65535,65535 0 0) });
+/// assert!(error.x <= scale.x / 2. && error.y == 0. && error.z == 0.);
+///
+/// let restored: LineStringZ = quantized.dequantize(origin, scale);
+/// assert!((restored.0[1].x - 140.).abs() <= error.x);
+/// ```
+pub trait Quantize<T: CoordFloat> {
+    /// The geometry on the grid of cells `scale` wide from `origin`, and the
+    /// largest error this makes on each axis, or [`Error::CastOverflow`] if any
+    /// cell is out of the range of `U`, as is any NaN.
+    #[allow(clippy::type_complexity)]
+    fn quantize<U: CoordNum>(
+        &self,
+        origin: CoordZ<T>,
+        scale: CoordZ<T>,
+    ) -> Result<(<Self as MapCoordsZ<T, U>>::Output, CoordZ<T>), Error>
+    where
+        Self: MapCoordsZ<T, U>;
+}
+
+impl<T: CoordFloat, G: MapCoordsZ<T, T>> Quantize<T> for G {
+    fn quantize<U: CoordNum>(
+        &self,
+        origin: CoordZ<T>,
+        scale: CoordZ<T>,
+    ) -> Result<(<Self as MapCoordsZ<T, U>>::Output, CoordZ<T>), Error>
+    where
+        Self: MapCoordsZ<T, U>,
+    {
+        let overflow = Cell::new(false);
+        let error = Cell::new(CoordZ::<T>::zero());
+        let cell = |value: T, origin: T, scale: T| {
+            let cell = ((value - origin) / scale).round();
+            let off = (origin + cell * scale - value).abs();
+            (U::from(cell), off)
+        };
+        let quantized = MapCoordsZ::<T, U>::map_coords(self, |c| {
+            let (x, off_x) = cell(c.x, origin.x, scale.x);
+            let (y, off_y) = cell(c.y, origin.y, scale.y);
+            let (z, off_z) = cell(c.z, origin.z, scale.z);
+            let max = error.get();
+            error.set(CoordZ {
+                x: max.x.max(off_x),
+                y: max.y.max(off_y),
+                z: max.z.max(off_z),
+            });
+            match (x, y, z) {
+                (Some(x), Some(y), Some(z)) => CoordZ { x, y, z },
+                _ => {
+                    overflow.set(true);
+                    CoordZ::zero()
+                }
+            }
+        });
+        if overflow.get() {
+            Err(Error::CastOverflow)
+        } else {
+            Ok((quantized, error.get()))
+        }
+    }
+}
+
+/// Restore the coordinates of a geometry [quantized](Quantize) onto a grid, as
+/// `origin + cell * scale`.
+pub trait Dequantize<U: CoordNum> {
+    fn dequantize<T: CoordFloat>(
+        &self,
+        origin: CoordZ<T>,
+        scale: CoordZ<T>,
+    ) -> <Self as MapCoordsZ<U, T>>::Output
+    where
+        Self: MapCoordsZ<U, T>;
+}
+
+impl<U: CoordNum, G: MapCoordsZ<U, U>> Dequantize<U> for G {
+    fn dequantize<T: CoordFloat>(
+        &self,
+        origin: CoordZ<T>,
+        scale: CoordZ<T>,
+    ) -> <Self as MapCoordsZ<U, T>>::Output
+    where
+        Self: MapCoordsZ<U, T>,
+    {
+        let restore = |cell: U, origin: T, scale: T| origin + T::from(cell).unwrap() * scale;
+        MapCoordsZ::<U, T>::map_coords(self, |c| CoordZ {
+            x: restore(c.x, origin.x, scale.x),
+            y: restore(c.y, origin.y, scale.y),
+            z: restore(c.z, origin.z, scale.z),
+        })
+    }
+}
+
+/// The origin and scale of the grid spanning `bounds` with the values of `U` from
+/// zero up, the finest such grid for unsigned integer types.
+///
+/// An axis along which `bounds` is flat gets a scale of 1, so as not to divide by
+/// zero.
+pub fn fit_grid<U: CoordNum + Bounded, T: CoordFloat>(bounds: &Cube<T>) -> (CoordZ<T>, CoordZ<T>) {
+    let cells = T::from(U::max_value()).unwrap();
+    let scale = |extent: T| {
+        if extent > T::zero() {
+            extent / cells
+        } else {
+            T::one()
+        }
+    };
+    let scale = CoordZ {
+        x: scale(bounds.width()),
+        y: scale(bounds.height()),
+        z: scale(bounds.depth()),
+    };
+    (bounds.min(), scale)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::BoundingCube;
+    use crate::{Geometry, GeometryCollection, MultiPointZ, PointZ};
+    use alloc::vec;
+
+    #[test]
+    fn round_trips() {
+        let cloud: MultiPointZ = (0..100)
+            .map(|i| {
+                let i = f64::from(i);
+                PointZ::new(i.sin() * 50., i.cos() * 20., i * 0.37)
+            })
+            .collect();
+        let (origin, scale) = fit_grid::<u32, _>(&cloud.bounding_cube().unwrap());
+        let (quantized, error) = cloud.quantize::<u32>(origin, scale).unwrap();
+        assert!(error.x <= scale.x / 2. && error.y <= scale.y / 2. && error.z <= scale.z / 2.);
+        let restored: MultiPointZ = quantized.dequantize(origin, scale);
+        for (a, b) in cloud.iter().zip(restored.iter()) {
+            let off = (*a - *b).0.abs();
+            assert!(off.x <= error.x && off.y <= error.y && off.z <= error.z);
+        }
+
+        // a flat axis, and cells out of range
+        let flat = PointZ::new(3., 4., 5.);
+        let (origin, scale) = fit_grid::<u16, _>(&flat.bounding_cube());
+        assert_eq!(scale, CoordZ::from((1., 1., 1.)));
+        assert_eq!(
+            flat.quantize::<u16>(origin, scale).unwrap(),
+            (PointZ::new(0, 0, 0), CoordZ::zero())
+        );
+        let far = PointZ::new(3., 4., 5e5);
+        assert!(matches!(
+            far.quantize::<u16>(origin, scale),
+            Err(Error::CastOverflow)
+        ));
+
+        let mixed = Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::Point(geo_types::Point::new(1.25, 2.5)),
+            Geometry::PointZ(PointZ::new(0.5, 0.75, 1.)),
+        ]));
+        let quarter = CoordZ::from((0.25, 0.25, 0.25));
+        let (quantized, error) = mixed.quantize::<i16>(CoordZ::zero(), quarter).unwrap();
+        assert_eq!(error, CoordZ::zero());
+        assert_eq!(quantized.dequantize(CoordZ::zero(), quarter), mixed);
+    }
+}
@@ -0,0 +1,269 @@
+use alloc::vec::Vec;
+
+use crate::algorithm::linalg::{solve, symmetric_eigen};
+use crate::splitmix::SplitMix64;
+use crate::{CoordFloat, CoordZ, MultiPointZ, Plane, Sphere};
+
+impl<T: CoordFloat> MultiPointZ<T> {
+    /// Finds the plane with the most points within `threshold` of it by RANSAC
+    /// (random sample consensus), returning the plane and the indices of those
+    /// points, its inliers.
+    ///
+    /// Each of `iterations` rounds proposes the plane through three points picked at
+    /// random and counts its inliers. The best plane is then refitted to its inliers
+    /// by least squares. Points far from the plane, such as vegetation above the
+    /// ground, don't pull it away as they would a plain least squares fit. The
+    /// random picks are seeded the same way every time, so the same points always
+    /// give the same plane.
+    ///
+    /// Returns `None` if there are fewer than three points, or no three of the
+    /// points picked span a plane.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{MultiPointZ, PointZ};
+    ///
+    /// // a sloping ground plane, with a tree rising out of it
+    /// let mut points: Vec<PointZ> = (0..100)
+    ///     .map(|i| {
+    ///         let (x, y) = ((i % 10) as f64, (i / 10) as f64);
+    ///         PointZ::new(x, y, 0.1 * x)
+    ///     })
+    ///     .collect();
+    /// points.extend((1..30).map(|i| PointZ::new(4., 4., 0.4 + i as f64 * 0.2)));
+    /// let points = MultiPointZ::new(points);
+    ///
+    /// let (ground, inliers) = points.fit_plane_ransac(0.05, 100).unwrap();
+    /// assert_eq!(inliers.len(), 100);
+    /// assert!((ground.signed_distance(points.0[99].0)).abs() < 1e-9);
+    /// ```
+    pub fn fit_plane_ransac(
+        &self,
+        threshold: T,
+        iterations: usize,
+    ) -> Option<(Plane<T>, Vec<usize>)> {
+        let coords: Vec<CoordZ<T>> = self.0.iter().map(|point| point.0).collect();
+        ransac(
+            &coords,
+            threshold,
+            iterations,
+            |[a, b, c]| Plane::from_points(a, b, c),
+            |plane: &Plane<T>, coord| plane.signed_distance(coord),
+            fit_plane,
+        )
+    }
+
+    /// Finds the sphere with the most points within `threshold` of its surface by
+    /// RANSAC, returning the sphere and the indices of those points.
+    ///
+    /// This works as [`MultiPointZ::fit_plane_ransac`] does, proposing spheres
+    /// through four points at a time. Returns `None` if there are fewer than four
+    /// points, or no four of the points picked span a sphere.
+    pub fn fit_sphere_ransac(
+        &self,
+        threshold: T,
+        iterations: usize,
+    ) -> Option<(Sphere<T>, Vec<usize>)> {
+        let coords: Vec<CoordZ<T>> = self.0.iter().map(|point| point.0).collect();
+        ransac(
+            &coords,
+            threshold,
+            iterations,
+            |[a, b, c, d]| Sphere::from_points(a, b, c, d),
+            |sphere: &Sphere<T>, coord| sphere.signed_distance(coord),
+            fit_sphere,
+        )
+    }
+}
+
+/// Runs RANSAC with models built from `N` points by `propose`, then refits the
+/// best model to its inliers with `refit`, keeping the refit if it has at least as
+/// many inliers.
+fn ransac<T: CoordFloat, M, const N: usize>(
+    coords: &[CoordZ<T>],
+    threshold: T,
+    iterations: usize,
+    propose: impl Fn([CoordZ<T>; N]) -> Option<M>,
+    distance: impl Fn(&M, CoordZ<T>) -> T,
+    refit: impl Fn(&[CoordZ<T>]) -> Option<M>,
+) -> Option<(M, Vec<usize>)> {
+    if coords.len() < N {
+        return None;
+    }
+    let inliers = |model: &M| -> (Vec<usize>, T) {
+        let mut ids = Vec::new();
+        let mut squared = T::zero();
+        for (id, &coord) in coords.iter().enumerate() {
+            let d = distance(model, coord).abs();
+            if d <= threshold {
+                ids.push(id);
+                squared = squared + d * d;
+            }
+        }
+        (ids, squared)
+    };
+    // more inliers win, and then a closer fit
+    let better = |a: &(Vec<usize>, T), b: &(Vec<usize>, T)| {
+        a.0.len() > b.0.len() || (a.0.len() == b.0.len() && a.1 < b.1)
+    };
+
+    // seeded the same every time so fits are repeatable
+    let mut random = SplitMix64(0);
+    let mut best: Option<(M, (Vec<usize>, T))> = None;
+    for _ in 0..iterations {
+        let Some(model) = propose(sample(&mut random, coords)) else {
+            continue;
+        };
+        let found = inliers(&model);
+        if best.as_ref().is_none_or(|(_, best)| better(&found, best)) {
+            best = Some((model, found));
+        }
+    }
+
+    let (model, found) = best?;
+    let members: Vec<CoordZ<T>> = found.0.iter().map(|&id| coords[id]).collect();
+    if let Some(refitted) = refit(&members) {
+        let refound = inliers(&refitted);
+        if refound.0.len() >= found.0.len() {
+            return Some((refitted, refound.0));
+        }
+    }
+    Some((model, found.0))
+}
+
+/// The least squares plane through points, normal to the direction in which they
+/// vary least.
+pub(crate) fn fit_plane<T: CoordFloat>(coords: &[CoordZ<T>]) -> Option<Plane<T>> {
+    let count = T::from(coords.len()).unwrap();
+    let centre = coords.iter().fold(CoordZ::zero(), |sum, &c| sum + c) / count;
+    let mut covariance = [[T::zero(); 3]; 3];
+    for &coord in coords {
+        let d = coord - centre;
+        let d = [d.x, d.y, d.z];
+        for (row, &a) in covariance.iter_mut().zip(&d) {
+            for (value, &b) in row.iter_mut().zip(&d) {
+                *value = *value + a * b;
+            }
+        }
+    }
+    let [_, _, (_, [x, y, z])] = symmetric_eigen(covariance);
+    Plane::from_point_normal(centre, CoordZ { x, y, z })
+}
+
+/// The sphere best fitting points, by linear least squares on
+/// `x² + y² + z² = 2 c · p + r² - |c|²`.
+fn fit_sphere<T: CoordFloat>(coords: &[CoordZ<T>]) -> Option<Sphere<T>> {
+    let mut normal = [[T::zero(); 4]; 4];
+    let mut rhs = [T::zero(); 4];
+    for &p in coords {
+        let row = [p.x, p.y, p.z, T::one()];
+        let target = p.x * p.x + p.y * p.y + p.z * p.z;
+        for (i, &a) in row.iter().enumerate() {
+            for (value, &b) in normal[i].iter_mut().zip(&row) {
+                *value = *value + a * b;
+            }
+            rhs[i] = rhs[i] + a * target;
+        }
+    }
+    let [a, b, c, d] = solve(normal, rhs)?;
+    let two = T::one() + T::one();
+    let center = CoordZ {
+        x: a / two,
+        y: b / two,
+        z: c / two,
+    };
+    let radius_2 = d + center.x * center.x + center.y * center.y + center.z * center.z;
+    (radius_2 > T::zero()).then(|| Sphere::new(center, radius_2.sqrt()))
+}
+
+/// `N` distinct items, picked at random. There must be at least `N` items.
+fn sample<C: Copy, const N: usize>(random: &mut SplitMix64, items: &[C]) -> [C; N] {
+    let mut picked = [0usize; N];
+    let mut count = 0;
+    while count < N {
+        let index = (random.next_u64() % items.len() as u64) as usize;
+        if !picked[..count].contains(&index) {
+            picked[count] = index;
+            count += 1;
+        }
+    }
+    picked.map(|index| items[index])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::PointZ;
+
+    #[test]
+    fn plane() {
+        // a tilted plane with small noise, and a tenth of the points scattered above
+        let points: MultiPointZ = (0..400)
+            .map(|i| {
+                let (x, y) = ((i % 20) as f64, (i / 20) as f64);
+                let noise = ((i * 37 % 11) as f64 - 5.) * 0.002;
+                let z = 2. + 0.3 * x - 0.2 * y + noise;
+                let outlier = if i % 10 == 3 { 5. + (i % 7) as f64 } else { 0. };
+                PointZ::new(x, y, z + outlier)
+            })
+            .collect();
+        let (plane, inliers) = points.fit_plane_ransac(0.05, 200).unwrap();
+        assert_eq!(inliers.len(), 360);
+        assert!(inliers.iter().all(|id| id % 10 != 3));
+        // the normal of z = 2 + 0.3x - 0.2y, facing either way
+        let expected = CoordZ {
+            x: -0.3,
+            y: 0.2,
+            z: 1.,
+        } / (1.13f64).sqrt();
+        let alignment =
+            plane.normal.x * expected.x + plane.normal.y * expected.y + plane.normal.z * expected.z;
+        assert_relative_eq!(alignment.abs(), 1., epsilon = 1e-4);
+
+        let line: MultiPointZ = (0..10).map(|i| PointZ::new(i as f64, 0., 0.)).collect();
+        assert!(line.fit_plane_ransac(0.1, 50).is_none());
+        assert!(MultiPointZ::<f64>::empty()
+            .fit_plane_ransac(0.1, 50)
+            .is_none());
+    }
+
+    #[test]
+    fn sphere() {
+        // a dome of radius 5 centred at (1, 2, 3), with clutter on the floor
+        let mut points = Vec::new();
+        for i in 0..20 {
+            for j in 0..10 {
+                let (azimuth, elevation) = (i as f64 * 0.314, j as f64 * 0.15);
+                let noise = ((i * 13 + j * 7) % 5) as f64 * 0.004;
+                let r = 5. + noise;
+                points.push(PointZ::new(
+                    1. + r * elevation.cos() * azimuth.cos(),
+                    2. + r * elevation.cos() * azimuth.sin(),
+                    3. + r * elevation.sin(),
+                ));
+            }
+        }
+        for i in 0..40 {
+            points.push(PointZ::new((i % 8) as f64 - 3., (i / 8) as f64, -5.));
+        }
+        let points = MultiPointZ::new(points);
+        let (sphere, inliers) = points.fit_sphere_ransac(0.05, 300).unwrap();
+        assert_eq!(inliers.len(), 200);
+        assert_relative_eq!(
+            sphere.center,
+            CoordZ {
+                x: 1.,
+                y: 2.,
+                z: 3.
+            },
+            epsilon = 0.02
+        );
+        assert_relative_eq!(sphere.radius, 5.008, epsilon = 0.01);
+
+        let flat: MultiPointZ = (0..10)
+            .map(|i| PointZ::new(i as f64, (i * i) as f64, 0.))
+            .collect();
+        assert!(flat.fit_sphere_ransac(0.1, 50).is_none());
+    }
+}
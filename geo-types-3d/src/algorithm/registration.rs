@@ -0,0 +1,205 @@
+use alloc::vec::Vec;
+
+use crate::algorithm::linalg::symmetric_eigen;
+use crate::algorithm::AffineTransform3D;
+use crate::{CoordFloat, CoordZ, MultiPointZ};
+
+/// The most rounds of matching and fitting [`MultiPointZ::align_to`] runs.
+pub const MAX_ITERATIONS: usize = 100;
+
+impl<T: CoordFloat> MultiPointZ<T> {
+    /// Finds the rotation and translation that best move these points onto `target`,
+    /// by point-to-point iterative closest point (ICP), returning the transform and
+    /// the root mean square distance from each moved point to the closest point of
+    /// `target`.
+    ///
+    /// Each round matches every point to its nearest target point and fits the
+    /// rigid transform minimising the squared distances between matches, until the
+    /// error stops improving or after [`MAX_ITERATIONS`] rounds. ICP finds the
+    /// nearest local fit, so the points should already be roughly aligned, as scans
+    /// or tracks of the same place from different sessions usually are. The
+    /// clouds needn't have the same number of points.
+    ///
+    /// Returns `None` if either set of points is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::algorithm::{AffineOpsZ, AffineTransform3D};
+    /// use geo_types_3d::{MultiPointZ, PointZ};
+    ///
+    /// // a survey of a bumpy patch of ground, and a second survey of it taken from
+    /// // a slightly different position
+    /// let survey: MultiPointZ = (0..400)
+    ///     .map(|i| {
+    ///         let (x, y) = ((i % 20) as f64, (i / 20) as f64);
+    ///         PointZ::new(x, y, (x / 3.).sin() * (y / 4.).cos() * 3.)
+    ///     })
+    ///     .collect();
+    /// let offset = AffineTransform3D::rotate_z(4.).translated(0.6, -0.4, 0.3);
+    /// let resurvey = survey.affine_transform(&offset);
+    ///
+    /// let (transform, rms) = resurvey.align_to(&survey).unwrap();
+    /// assert!(rms < 1e-6);
+    /// let corner = transform.apply(resurvey.0[399].0) - survey.0[399].0;
+    /// assert!(corner.x.abs() + corner.y.abs() + corner.z.abs() < 1e-6);
+    /// ```
+    pub fn align_to(&self, target: &MultiPointZ<T>) -> Option<(AffineTransform3D<T>, T)> {
+        if self.0.is_empty() || target.0.is_empty() {
+            return None;
+        }
+        let tree = target.kdtree();
+        let mut transform = AffineTransform3D::identity();
+        let mut best = (transform, T::infinity());
+        let mut matches = Vec::with_capacity(self.0.len());
+        for _ in 0..MAX_ITERATIONS {
+            matches.clear();
+            let mut squared = T::zero();
+            for point in &self.0 {
+                let moved = transform.apply(point.0);
+                let (_, nearest) = tree.nearest(&moved.into()).expect("non-empty target");
+                let delta = nearest.0 - moved;
+                squared = squared + delta.x * delta.x + delta.y * delta.y + delta.z * delta.z;
+                matches.push((point.0, nearest.0));
+            }
+            let rms = (squared / T::from(self.0.len()).unwrap()).sqrt();
+            if rms >= best.1 {
+                break;
+            }
+            let improvement = best.1 - rms;
+            best = (transform, rms);
+            if rms <= T::epsilon() || improvement <= rms * T::epsilon().sqrt() {
+                break;
+            }
+            transform = fit_rigid(&matches);
+        }
+        Some(best)
+    }
+}
+
+/// The rotation and translation best moving the first point of each pair onto the
+/// second, in the least squares sense, by Horn's closed-form quaternion method.
+fn fit_rigid<T: CoordFloat>(pairs: &[(CoordZ<T>, CoordZ<T>)]) -> AffineTransform3D<T> {
+    let count = T::from(pairs.len()).unwrap();
+    let (sum_from, sum_to) = pairs
+        .iter()
+        .fold((CoordZ::zero(), CoordZ::zero()), |(a, b), &(from, to)| {
+            (a + from, b + to)
+        });
+    let (centre_from, centre_to) = (sum_from / count, sum_to / count);
+
+    // the cross-covariance of the centred points
+    let mut s = [[T::zero(); 3]; 3];
+    for &(from, to) in pairs {
+        let (a, b) = (from - centre_from, to - centre_to);
+        let (a, b) = ([a.x, a.y, a.z], [b.x, b.y, b.z]);
+        for (row, &a) in s.iter_mut().zip(&a) {
+            for (value, &b) in row.iter_mut().zip(&b) {
+                *value = *value + a * b;
+            }
+        }
+    }
+    let [[xx, xy, xz], [yx, yy, yz], [zx, zy, zz]] = s;
+    // the rotation is the quaternion of the largest eigenvalue of this matrix
+    let n = [
+        [xx + yy + zz, yz - zy, zx - xz, xy - yx],
+        [yz - zy, xx - yy - zz, xy + yx, zx + xz],
+        [zx - xz, xy + yx, -xx + yy - zz, yz + zy],
+        [xy - yx, zx + xz, yz + zy, -xx - yy + zz],
+    ];
+    let [(_, [w, x, y, z]), ..] = symmetric_eigen(n);
+
+    let two = T::one() + T::one();
+    let rotation = [
+        [
+            T::one() - two * (y * y + z * z),
+            two * (x * y - w * z),
+            two * (x * z + w * y),
+        ],
+        [
+            two * (x * y + w * z),
+            T::one() - two * (x * x + z * z),
+            two * (y * z - w * x),
+        ],
+        [
+            two * (x * z - w * y),
+            two * (y * z + w * x),
+            T::one() - two * (x * x + y * y),
+        ],
+    ];
+    let turned = AffineTransform3D::new(rotation.map(|[a, b, c]| [a, b, c, T::zero()]));
+    let offset = centre_to - turned.apply(centre_from);
+    turned.translated(offset.x, offset.y, offset.z)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::AffineOpsZ;
+    use crate::PointZ;
+
+    fn cloud() -> MultiPointZ {
+        // points on an irregular, non-symmetric surface
+        (0..600)
+            .map(|i| {
+                let (x, y) = ((i % 30) as f64 * 0.5, (i / 30) as f64 * 0.7);
+                PointZ::new(x, y, (x * 0.4).sin() * 2. + (y * 0.3).cos() * x * 0.2)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn recovers_rigid_motion() {
+        let target = cloud();
+        let motion = AffineTransform3D::rotate_x(2.)
+            .rotated_y(-3.)
+            .rotated_z(5.)
+            .translated(0.3, -0.2, 0.4);
+        let source = target.affine_transform(&motion.inverse().unwrap());
+
+        let (transform, rms) = source.align_to(&target).unwrap();
+        assert!(rms < 1e-6, "rms {rms}");
+        for (found, expected) in transform.matrix().iter().zip(motion.matrix()) {
+            for (found, expected) in found.iter().zip(expected) {
+                assert_relative_eq!(*found, expected, epsilon = 1e-6);
+            }
+        }
+
+        // a noisy partial scan, starting closer, still lands close to its place
+        let nudge = AffineTransform3D::rotate_z(1.).translated(0.1, 0.05, 0.1);
+        let source = target.affine_transform(&nudge.inverse().unwrap());
+        let partial: MultiPointZ = source
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| i % 3 == 0 && i % 30 < 20)
+            .map(|(i, point)| {
+                let noise = ((i * 7919 % 13) as f64 - 6.) * 0.002;
+                PointZ::new(point.x() + noise, point.y() - noise, point.z() + noise)
+            })
+            .collect();
+        let (transform, rms) = partial.align_to(&target).unwrap();
+        assert!(rms < 0.05, "rms {rms}");
+        let corner = transform.apply(source.0[0].0) - target.0[0].0;
+        assert!(corner.x.abs() + corner.y.abs() + corner.z.abs() < 0.05);
+
+        assert!(MultiPointZ::<f64>::empty().align_to(&target).is_none());
+        assert!(target.align_to(&MultiPointZ::empty()).is_none());
+    }
+
+    #[test]
+    fn fits_exact_rotations() {
+        let from = [(1., 0., 0.), (0., 2., 0.), (0., 0., 3.), (1., 1., 1.)];
+        let motion = AffineTransform3D::rotate_z(120.).translated(5., 0., -1.);
+        let pairs: Vec<_> = from
+            .iter()
+            .map(|&c| {
+                let c = CoordZ::from(c);
+                (c, motion.apply(c))
+            })
+            .collect();
+        let fitted = fit_rigid(&pairs);
+        for &(from, to) in &pairs {
+            assert_relative_eq!(fitted.apply(from), to, epsilon = 1e-9);
+        }
+    }
+}
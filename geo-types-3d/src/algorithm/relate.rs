@@ -0,0 +1,294 @@
+use alloc::vec::Vec;
+
+use geo::{GeoFloat, Relate};
+use geo_types::Coord;
+
+pub use geo::algorithm::relate::IntersectionMatrix;
+
+use super::ransac::fit_plane;
+use crate::{
+    CoordZ, Error, Geometry, GeometryCollection, LineStringZ, LineZ, MultiLineStringZ, MultiPointZ,
+    MultiPolygonZ, PointZ, PolygonZ,
+};
+
+/// Topological relationships between geometries that lie in a common plane, as
+/// described by the [DE-9IM] intersection matrix.
+///
+/// The plane is fitted to the coordinates of both geometries, and may have any
+/// orientation in space. Every coordinate has to lie within a tolerance of that
+/// plane, otherwise the relationship fails with [`Error::NotCoplanar`]. The
+/// geometries are then projected onto the coordinate plane the fitted plane is most
+/// parallel to, and related there with [`geo::Relate`], so the matrix and the
+/// predicates mean just what they do in `geo`. Members of collections without z are
+/// taken to lie at z = 0.
+///
+/// [DE-9IM]: https://en.wikipedia.org/wiki/DE-9IM
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::RelateZ;
+/// use geo_types_3d::{line_string_z, polygon_z, PolygonZ};
+///
+/// // a wall of a building, and a window and a crack in it
+/// let wall: PolygonZ = polygon_z![
+///     (x: 0., y: 0., z: 0.),
+///     (x: 4., y: 4., z: 0.),
+///     (x: 4., y: 4., z: 3.),
+///     (x: 0., y: 0., z: 3.),
+/// ];
+/// let window: PolygonZ = polygon_z![
+///     (x: 1., y: 1., z: 1.),
+///     (x: 2., y: 2., z: 1.),
+///     (x: 2., y: 2., z: 2.),
+///     (x: 1., y: 1., z: 2.),
+/// ];
+/// let crack = line_string_z![(x: 3., y: 3., z: -1.), (x: 3., y: 3., z: 1.)];
+///
+/// assert!(window.within(&wall).unwrap());
+/// assert!(crack.crosses(&wall).unwrap());
+/// assert!(!crack.touches(&window).unwrap());
+/// let matrix = window.relate(&wall).unwrap();
+/// assert!(matrix.matches("2FF1FF212").unwrap());
+/// ```
+pub trait RelateZ<T: GeoFloat>: Clone + Into<Geometry<T>> {
+    /// The intersection matrix of the two geometries, accepting coordinates up to a
+    /// small distance (relative to the size of the geometries) from the common
+    /// plane.
+    fn relate(&self, other: &impl RelateZ<T>) -> Result<IntersectionMatrix, Error> {
+        let geometries = [self.clone().into(), other.clone().into()];
+        let coords = coords(&geometries);
+        let tolerance = default_tolerance(&coords);
+        relate(&geometries, &coords, tolerance)
+    }
+
+    /// The intersection matrix of the two geometries, accepting coordinates up to
+    /// `tolerance` from the common plane.
+    fn relate_with_tolerance(
+        &self,
+        other: &impl RelateZ<T>,
+        tolerance: T,
+    ) -> Result<IntersectionMatrix, Error> {
+        let geometries = [self.clone().into(), other.clone().into()];
+        relate(&geometries, &coords(&geometries), tolerance)
+    }
+
+    /// Whether the geometries meet only on their boundaries.
+    fn touches(&self, other: &impl RelateZ<T>) -> Result<bool, Error> {
+        Ok(self.relate(other)?.is_touches())
+    }
+
+    /// Whether the geometries cross, as a line crossing a polygon or another line.
+    fn crosses(&self, other: &impl RelateZ<T>) -> Result<bool, Error> {
+        Ok(self.relate(other)?.is_crosses())
+    }
+
+    /// Whether this geometry lies within the other.
+    fn within(&self, other: &impl RelateZ<T>) -> Result<bool, Error> {
+        Ok(self.relate(other)?.is_within())
+    }
+
+    /// Whether the geometries, of the same dimension, share some but not all of
+    /// their interiors.
+    fn overlaps(&self, other: &impl RelateZ<T>) -> Result<bool, Error> {
+        Ok(self.relate(other)?.is_overlaps())
+    }
+}
+
+impl<T: GeoFloat> RelateZ<T> for PointZ<T> {}
+impl<T: GeoFloat> RelateZ<T> for LineZ<T> {}
+impl<T: GeoFloat> RelateZ<T> for LineStringZ<T> {}
+impl<T: GeoFloat> RelateZ<T> for PolygonZ<T> {}
+impl<T: GeoFloat> RelateZ<T> for MultiPointZ<T> {}
+impl<T: GeoFloat> RelateZ<T> for MultiLineStringZ<T> {}
+impl<T: GeoFloat> RelateZ<T> for MultiPolygonZ<T> {}
+impl<T: GeoFloat> RelateZ<T> for GeometryCollection<T> {}
+impl<T: GeoFloat> RelateZ<T> for Geometry<T> {}
+
+fn relate<T: GeoFloat>(
+    geometries: &[Geometry<T>; 2],
+    coords: &[CoordZ<T>],
+    tolerance: T,
+) -> Result<IntersectionMatrix, Error> {
+    // drop the axis the plane's normal is closest to, or z if there is no plane
+    let mut kept = [0, 1];
+    if let Some(plane) = (!coords.is_empty()).then(|| fit_plane(coords)).flatten() {
+        if coords
+            .iter()
+            .any(|&c| plane.signed_distance(c).abs() > tolerance)
+        {
+            return Err(Error::NotCoplanar);
+        }
+        let normal = [plane.normal.x, plane.normal.y, plane.normal.z].map(|n| n.abs());
+        let dropped = (0..3)
+            .rev()
+            .max_by(|&a, &b| normal[a].total_cmp(&normal[b]))
+            .unwrap_or(2);
+        kept = [(dropped + 1) % 3, (dropped + 2) % 3];
+    }
+    let mut project = |c: CoordZ<T>| {
+        let c = [c.x, c.y, c.z];
+        Coord {
+            x: c[kept[0]],
+            y: c[kept[1]],
+        }
+    };
+    let [a, b] = geometries;
+    Ok(to_geo(a, &mut project).relate(&to_geo(b, &mut project)))
+}
+
+/// A distance from the plane that is small relative to the size of the
+/// geometries.
+fn default_tolerance<T: GeoFloat>(coords: &[CoordZ<T>]) -> T {
+    let mut size = T::zero();
+    if let Some(&first) = coords.first() {
+        let (mut min, mut max) = (first, first);
+        for c in coords {
+            min = CoordZ::from((min.x.min(c.x), min.y.min(c.y), min.z.min(c.z)));
+            max = CoordZ::from((max.x.max(c.x), max.y.max(c.y), max.z.max(c.z)));
+        }
+        size = (max.x - min.x).max(max.y - min.y).max(max.z - min.z);
+    }
+    size.max(T::one()) * T::epsilon().sqrt()
+}
+
+fn coords<T: GeoFloat>(geometries: &[Geometry<T>]) -> Vec<CoordZ<T>> {
+    let mut coords = Vec::new();
+    for geometry in geometries {
+        to_geo(geometry, &mut |c| {
+            coords.push(c);
+            Coord { x: c.x, y: c.y }
+        });
+    }
+    coords
+}
+
+/// Converts a geometry to a `geo` one, mapping each coordinate with `f`.
+fn to_geo<T: GeoFloat, F: FnMut(CoordZ<T>) -> Coord<T>>(
+    geometry: &Geometry<T>,
+    f: &mut F,
+) -> geo::Geometry<T> {
+    match geometry {
+        Geometry::Point(point) => geo::Point(f(flat(point.0))).into(),
+        Geometry::Line(line) => geo::Line::new(f(flat(line.start)), f(flat(line.end))).into(),
+        Geometry::LineString(line) => geo::LineString(flat_map(&line.0, f)).into(),
+        Geometry::Polygon(polygon) => flat_polygon(polygon, f).into(),
+        Geometry::MultiPoint(points) => {
+            geo::MultiPoint(points.0.iter().map(|p| geo::Point(f(flat(p.0)))).collect()).into()
+        }
+        Geometry::MultiLineString(lines) => geo::MultiLineString(
+            lines
+                .0
+                .iter()
+                .map(|l| geo::LineString(flat_map(&l.0, f)))
+                .collect(),
+        )
+        .into(),
+        Geometry::MultiPolygon(polygons) => {
+            geo::MultiPolygon(polygons.0.iter().map(|p| flat_polygon(p, f)).collect()).into()
+        }
+        Geometry::Rect(rect) => flat_polygon(&rect.to_polygon(), f).into(),
+        Geometry::PointZ(point) => geo::Point(f(point.0)).into(),
+        Geometry::LineZ(line) => geo::Line::new(f(line.start), f(line.end)).into(),
+        Geometry::LineStringZ(line) => line_string(line, f).into(),
+        Geometry::PolygonZ(polygon) => geo::Geometry::Polygon(self::polygon(polygon, f)),
+        Geometry::MultiPointZ(points) => {
+            geo::MultiPoint(points.0.iter().map(|p| geo::Point(f(p.0))).collect()).into()
+        }
+        Geometry::MultiLineStringZ(lines) => {
+            geo::MultiLineString(lines.0.iter().map(|l| line_string(l, f)).collect()).into()
+        }
+        Geometry::MultiPolygonZ(polygons) => {
+            geo::MultiPolygon(polygons.0.iter().map(|p| polygon(p, f)).collect()).into()
+        }
+        Geometry::GeometryCollection(collection) => geo::Geometry::GeometryCollection(
+            geo::GeometryCollection(collection.0.iter().map(|g| to_geo(g, f)).collect()),
+        ),
+    }
+}
+
+/// A coordinate without z, at z = 0.
+fn flat<T: GeoFloat>(coord: Coord<T>) -> CoordZ<T> {
+    CoordZ::from((coord.x, coord.y, T::zero()))
+}
+
+fn flat_map<T: GeoFloat>(
+    coords: &[Coord<T>],
+    f: &mut impl FnMut(CoordZ<T>) -> Coord<T>,
+) -> Vec<Coord<T>> {
+    coords.iter().map(|&c| f(flat(c))).collect()
+}
+
+fn flat_polygon<T: GeoFloat>(
+    polygon: &geo::Polygon<T>,
+    f: &mut impl FnMut(CoordZ<T>) -> Coord<T>,
+) -> geo::Polygon<T> {
+    let exterior = geo::LineString(flat_map(&polygon.exterior().0, f));
+    let interiors = polygon
+        .interiors()
+        .iter()
+        .map(|ring| geo::LineString(flat_map(&ring.0, f)))
+        .collect();
+    geo::Polygon::new(exterior, interiors)
+}
+
+fn line_string<T: GeoFloat>(
+    line: &LineStringZ<T>,
+    f: &mut impl FnMut(CoordZ<T>) -> Coord<T>,
+) -> geo::LineString<T> {
+    geo::LineString(line.0.iter().map(|&c| f(c)).collect())
+}
+
+fn polygon<T: GeoFloat>(
+    polygon: &PolygonZ<T>,
+    f: &mut impl FnMut(CoordZ<T>) -> Coord<T>,
+) -> geo::Polygon<T> {
+    let exterior = line_string(polygon.exterior(), f);
+    let interiors = polygon
+        .interiors()
+        .iter()
+        .map(|ring| line_string(ring, f))
+        .collect();
+    geo::Polygon::new(exterior, interiors)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn relate() {
+        // two squares on a slope, sharing an edge, and one overlapping both
+        let square = |x: f64| -> PolygonZ {
+            polygon_z![
+                (x: x, y: 0., z: x),
+                (x: x + 2., y: 0., z: x + 2.),
+                (x: x + 2., y: 2., z: x + 2.),
+                (x: x, y: 2., z: x),
+            ]
+        };
+        let (a, b, c) = (square(0.), square(2.), square(1.));
+        assert!(a.touches(&b).unwrap());
+        assert!(!a.overlaps(&b).unwrap());
+        assert!(a.overlaps(&c).unwrap());
+        assert!(!a.within(&c).unwrap());
+
+        // a path along the slope, crossing the squares, and its points
+        let path: LineStringZ = line_string_z![(x: -1., y: 1., z: -1.), (x: 3., y: 1., z: 3.)];
+        assert!(path.crosses(&a).unwrap());
+        assert!(PointZ::new(1., 1., 1.).within(&a).unwrap());
+        assert!(PointZ::new(2., 1., 2.).touches(&a).unwrap());
+        let matrix = Geometry::from(path.clone()).relate(&b).unwrap();
+        assert!(matrix.matches("1010F0212").unwrap());
+
+        // a point off the slope
+        assert!(matches!(
+            PointZ::new(1., 1., 5.).within(&a),
+            Err(Error::NotCoplanar)
+        ));
+        assert!(PointZ::new(1., 1., 1.001)
+            .relate_with_tolerance(&a, 0.01)
+            .unwrap()
+            .is_within());
+    }
+}
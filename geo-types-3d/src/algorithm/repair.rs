@@ -0,0 +1,380 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algorithm::linalg::{cross, dot, norm};
+use crate::index::KdTree;
+use crate::{CoordFloat, CoordZ, PointZ, TinZ};
+
+impl<T: CoordFloat> TinZ<T> {
+    /// Merges the vertices within `tolerance` of one another, returning how many
+    /// were merged away.
+    ///
+    /// Each vertex takes in the later ones near it, in order. Triangles left with
+    /// two corners at the same vertex are dropped, and the vertices that remain
+    /// keep their order.
+    ///
+    /// Surfaces read from STL and the like store each triangle on its own, so
+    /// this comes first in cleaning them up, followed by
+    /// [`remove_degenerate`](Self::remove_degenerate),
+    /// [`recompute_normals`](Self::recompute_normals) and
+    /// [`fill_small_holes`](Self::fill_small_holes).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{Triangle, TinZ};
+    ///
+    /// // two triangles of a square, stored apart, one of them upside down
+    /// let mut tin: TinZ = [
+    ///     Triangle::from([(0., 0., 0.), (1., 0., 0.), (1., 1., 0.)]),
+    ///     Triangle::from([(0., 0., 0.), (0., 1., 0.), (1., 1., 0.)]),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    /// assert_eq!(tin.vertices().len(), 6);
+    ///
+    /// assert_eq!(tin.weld(1e-9), 2);
+    /// assert_eq!(tin.vertices().len(), 4);
+    /// assert_eq!(tin.recompute_normals(), 1);
+    /// assert_eq!(tin.triangle_indices(), [[0, 1, 2], [0, 2, 3]]);
+    /// ```
+    pub fn weld(&mut self, tolerance: T) -> usize {
+        let tree = KdTree::new(self.vertices().iter().map(|&vertex| PointZ(vertex)));
+        let mut merged = vec![None; self.vertices().len()];
+        let mut vertices = Vec::new();
+        for (v, &vertex) in self.vertices().iter().enumerate() {
+            if merged[v].is_some() {
+                continue;
+            }
+            for (near, _) in tree.within_radius(&PointZ(vertex), tolerance) {
+                merged[near].get_or_insert(vertices.len());
+            }
+            merged[v] = Some(vertices.len());
+            vertices.push(vertex);
+        }
+        let removed = self.vertices().len() - vertices.len();
+        let triangles = self
+            .triangle_indices()
+            .iter()
+            .map(|triangle| triangle.map(|v| merged[v].expect("every vertex is merged")))
+            .filter(|&[a, b, c]| a != b && b != c && c != a)
+            .collect();
+        *self = TinZ::new(vertices, triangles);
+        removed
+    }
+
+    /// Drops the triangles without area, whose corners are repeated or lie on a
+    /// line, returning how many were dropped. The vertices are left as they are.
+    pub fn remove_degenerate(&mut self) -> usize {
+        let triangles: Vec<_> = self
+            .triangle_indices()
+            .iter()
+            .copied()
+            .filter(|&triangle| {
+                let [a, b, c] = triangle.map(|v| self.vertices()[v]);
+                let (ab, ac) = (b - a, c - a);
+                let longest = dot(ab, ab).max(dot(ac, ac)).max(dot(c - b, c - b));
+                norm(cross(ab, ac)) > T::epsilon() * longest
+            })
+            .collect();
+        let removed = self.len() - triangles.len();
+        *self = TinZ::new(self.vertices().to_vec(), triangles);
+        removed
+    }
+
+    /// Turns triangles over so that neighbours wind the same way, and each
+    /// connected piece of the surface faces up, anticlockwise when seen from
+    /// above, returning how many triangles were turned.
+    ///
+    /// Triangles are neighbours when they share an edge that no third triangle
+    /// shares. A piece standing on its edge, such as a vertical wall, faces
+    /// whichever way its first triangle does.
+    pub fn recompute_normals(&mut self) -> usize {
+        let mut triangles = self.triangle_indices().to_vec();
+        let edges = edges(&triangles);
+        let mut seen = vec![false; triangles.len()];
+        let mut turned = vec![false; triangles.len()];
+        for first in 0..triangles.len() {
+            if seen[first] {
+                continue;
+            }
+            seen[first] = true;
+            let (mut piece, mut stack) = (vec![first], vec![first]);
+            while let Some(t) = stack.pop() {
+                for [p, q] in sides(triangles[t]) {
+                    let &[a, b] = edges[&sorted(p, q)].as_slice() else {
+                        continue;
+                    };
+                    let other = if a == t { b } else { a };
+                    if seen[other] {
+                        continue;
+                    }
+                    seen[other] = true;
+                    // neighbours winding the same way cross the edge in opposite
+                    // directions
+                    if sides(triangles[other]).contains(&[p, q]) {
+                        triangles[other].swap(1, 2);
+                        turned[other] = true;
+                    }
+                    piece.push(other);
+                    stack.push(other);
+                }
+            }
+
+            let up = piece.iter().fold(T::zero(), |up, &t| {
+                let [a, b, c] = triangles[t].map(|v| self.vertices()[v]);
+                up + cross(b - a, c - a).z
+            });
+            if up < T::zero() {
+                for &t in &piece {
+                    triangles[t].swap(1, 2);
+                    turned[t] = !turned[t];
+                }
+            }
+        }
+        let count = turned.iter().filter(|&&turned| turned).count();
+        *self = TinZ::new(self.vertices().to_vec(), triangles);
+        count
+    }
+
+    /// Fills the holes in the surface that can be closed with triangles no side
+    /// of which is longer than `max_edge`, returning how many were filled.
+    ///
+    /// Holes are the loops of edges with a triangle on one side only that run
+    /// clockwise seen from above, so the triangles should face up, as
+    /// [`TinZ::recompute_normals`] leaves them. The outline of the surface runs
+    /// anticlockwise and is never filled. Each hole is triangulated as seen from
+    /// above, between its own vertices.
+    pub fn fill_small_holes(&mut self, max_edge: T) -> usize {
+        let mut triangles = self.triangle_indices().to_vec();
+        let edges = edges(&triangles);
+        // the edges with a triangle on one side, in the direction of that triangle
+        let mut next: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for &triangle in &triangles {
+            for [p, q] in sides(triangle) {
+                if edges[&sorted(p, q)].len() == 1 {
+                    next.entry(p).or_default().push(q);
+                }
+            }
+        }
+
+        let mut traced = BTreeSet::new();
+        let mut filled = 0;
+        for &start in next.keys() {
+            if traced.contains(&start) {
+                continue;
+            }
+            let Some(hole) = trace(&next, start) else {
+                continue;
+            };
+            traced.extend(hole.iter().copied());
+            let ring: Vec<_> = hole.iter().map(|&v| self.vertices()[v]).collect();
+            if signed_area(&ring) >= T::zero() {
+                continue;
+            }
+            // anticlockwise, so that the ears are the convex corners
+            let ring: Vec<_> = hole.into_iter().rev().collect();
+            let Some(patch) = ear_clip(self.vertices(), ring) else {
+                continue;
+            };
+            let short = patch
+                .iter()
+                .flat_map(|&triangle| sides(triangle))
+                .all(|[p, q]| {
+                    let side = self.vertices()[q] - self.vertices()[p];
+                    norm(side) <= max_edge
+                });
+            if short {
+                triangles.extend(patch);
+                filled += 1;
+            }
+        }
+        *self = TinZ::new(self.vertices().to_vec(), triangles);
+        filled
+    }
+}
+
+/// The triangles at each edge, by the indices of its ends in order.
+fn edges(triangles: &[[usize; 3]]) -> BTreeMap<[usize; 2], Vec<usize>> {
+    let mut edges: BTreeMap<[usize; 2], Vec<usize>> = BTreeMap::new();
+    for (index, &triangle) in triangles.iter().enumerate() {
+        for [p, q] in sides(triangle) {
+            edges.entry(sorted(p, q)).or_default().push(index);
+        }
+    }
+    edges
+}
+
+/// The loop of boundary edges through `start`, or `None` if it doesn't close up
+/// or a vertex on it has more than one way on.
+fn trace(next: &BTreeMap<usize, Vec<usize>>, start: usize) -> Option<Vec<usize>> {
+    let mut ring = vec![start];
+    loop {
+        let &[v] = next.get(ring.last()?)?.as_slice() else {
+            return None;
+        };
+        if v == start {
+            return Some(ring);
+        }
+        if ring.contains(&v) {
+            return None;
+        }
+        ring.push(v);
+    }
+}
+
+/// Twice the area enclosed by `ring` seen from above, positive if it runs
+/// anticlockwise.
+fn signed_area<T: CoordFloat>(ring: &[CoordZ<T>]) -> T {
+    let shifted = ring.iter().cycle().skip(1);
+    ring.iter()
+        .zip(shifted)
+        .fold(T::zero(), |area, (a, b)| area + a.x * b.y - b.x * a.y)
+}
+
+/// Triangulates the anticlockwise polygon through the vertices `ring` as seen
+/// from above, by cutting off ears, or `None` if it can't be cut up.
+fn ear_clip<T: CoordFloat>(
+    vertices: &[CoordZ<T>],
+    mut ring: Vec<usize>,
+) -> Option<Vec<[usize; 3]>> {
+    let turn = |a: usize, b: usize, c: usize| {
+        let (a, b, c) = (vertices[a], vertices[b], vertices[c]);
+        cross(b - a, c - a).z
+    };
+    let mut triangles = Vec::new();
+    while ring.len() > 3 {
+        let n = ring.len();
+        let ear = (0..n).find(|&i| {
+            let [a, b, c] = [ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]];
+            turn(a, b, c) > T::zero()
+                && ring.iter().all(|&v| {
+                    [a, b, c].contains(&v)
+                        || turn(a, b, v) < T::zero()
+                        || turn(b, c, v) < T::zero()
+                        || turn(c, a, v) < T::zero()
+                })
+        })?;
+        triangles.push([ring[(ear + n - 1) % n], ring[ear], ring[(ear + 1) % n]]);
+        ring.remove(ear);
+    }
+    triangles.push([ring[0], ring[1], ring[2]]);
+    Some(triangles)
+}
+
+/// The sides of a triangle, each as the indices of its ends in the direction
+/// the triangle winds.
+fn sides([a, b, c]: [usize; 3]) -> [[usize; 2]; 3] {
+    [[a, b], [b, c], [c, a]]
+}
+
+fn sorted(p: usize, q: usize) -> [usize; 2] {
+    [p.min(q), p.max(q)]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Triangle;
+
+    /// A 6 × 6 grid of unit cells, two triangles each, but for `missing` cells.
+    fn grid(missing: &[(usize, usize)]) -> TinZ<f64> {
+        let mut tin = TinZ::empty();
+        for j in 0..=6 {
+            for i in 0..=6 {
+                let (x, y) = (i as f64, j as f64);
+                tin.push_vertex(CoordZ { x, y, z: 0. });
+            }
+        }
+        for j in 0..6 {
+            for i in 0..6 {
+                if !missing.contains(&(i, j)) {
+                    let corner = j * 7 + i;
+                    tin.push([corner, corner + 1, corner + 8]);
+                    tin.push([corner, corner + 8, corner + 7]);
+                }
+            }
+        }
+        tin
+    }
+
+    fn facing_up(tin: &TinZ<f64>) -> bool {
+        tin.triangles().all(|t| cross(t.1 - t.0, t.2 - t.0).z > 0.)
+    }
+
+    #[test]
+    fn weld_and_remove_degenerate() {
+        let whole = grid(&[]);
+        let nudge = CoordZ::from((1e-7, 0., 0.));
+        let mut soup: TinZ = whole
+            .triangles()
+            .map(|t| Triangle(t.0, t.1 + nudge, t.2))
+            .collect();
+        assert_eq!(soup.vertices().len(), 216);
+        // the corners that weren't nudged, of which 48 of the 49 are used
+        assert_eq!(soup.clone().weld(0.), 216 - 48 - 42);
+        assert_eq!(soup.weld(1e-6), 216 - 49);
+        assert_eq!(soup.len(), 72);
+        for (welded, t) in soup.triangles().zip(whole.triangles()) {
+            for (a, b) in welded.to_array().into_iter().zip(t.to_array()) {
+                assert!(norm(a - b) <= 2e-7);
+            }
+        }
+
+        // a triangle on one vertex, and two on a line
+        let mut tin = grid(&[]);
+        tin.push([0, 1, 1]);
+        tin.push([0, 1, 2]);
+        tin.push([0, 8, 16]);
+        tin.push([0, 7, 8]);
+        assert_eq!(tin.remove_degenerate(), 3);
+        assert_eq!(tin.len(), 73);
+        assert_eq!(tin.vertices().len(), 49);
+    }
+
+    #[test]
+    fn recompute_normals() {
+        let whole = grid(&[]);
+        let mut tin = whole.clone();
+        assert_eq!(tin.recompute_normals(), 0);
+
+        // turn some triangles over, then all of them
+        let turn = |which: &dyn Fn(usize) -> bool| {
+            let triangles = whole
+                .triangle_indices()
+                .iter()
+                .enumerate()
+                .map(|(i, &[a, b, c])| if which(i) { [a, c, b] } else { [a, b, c] })
+                .collect();
+            TinZ::new(whole.vertices().to_vec(), triangles)
+        };
+        let mut mixed = turn(&|i| i % 3 == 0);
+        assert_eq!(mixed.recompute_normals(), 24);
+        assert_eq!(mixed, whole);
+        let mut upside_down = turn(&|_| true);
+        assert_eq!(upside_down.recompute_normals(), 72);
+        assert_eq!(upside_down, whole);
+    }
+
+    #[test]
+    fn fill_small_holes() {
+        // a missing cell, and three more in an L
+        let mut tin = grid(&[(1, 1), (3, 3), (4, 3), (3, 4)]);
+        assert_eq!(tin.len(), 64);
+        assert_eq!(tin.clone().fill_small_holes(1.2), 0);
+        assert_eq!(tin.fill_small_holes(10.), 2);
+        assert_eq!(tin.len(), 72);
+        assert!(facing_up(&tin));
+        let area: f64 = tin
+            .triangles()
+            .map(|t| cross(t.1 - t.0, t.2 - t.0).z / 2.)
+            .sum();
+        assert!((area - 36.).abs() < 1e-9);
+
+        // the outline is left alone, and a notch in it isn't a hole
+        let mut notched = grid(&[(0, 0), (5, 2)]);
+        assert_eq!(notched.fill_small_holes(10.), 0);
+        assert_eq!(notched.len(), 68);
+    }
+}
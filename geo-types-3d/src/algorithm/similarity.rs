@@ -0,0 +1,170 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algorithm::linalg::distance;
+use crate::{CoordFloat, CoordZ, LineStringZ, Trajectory};
+
+/// How alike two paths are, as the distance between their vertices once matched
+/// up in order.
+///
+/// Both measures walk the two sequences of vertices from start to end, each step
+/// moving on along one or both, and pair every vertex with at least one of the
+/// other's. Unlike the distance between the geometries, they tell paths apart by
+/// their direction as well as their shape. Only the vertices are compared, not
+/// the times of [trajectories](Trajectory), so resample trajectories first to
+/// compare them by time as well.
+///
+/// Both distances are zero between two empty paths, and infinite between an empty
+/// path and one that isn't.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::TrajectorySimilarity;
+/// use geo_types_3d::LineStringZ;
+///
+/// let climb: LineStringZ = vec![(0., 0., 0.), (1., 0., 1.), (2., 0., 2.)].into();
+/// // the same climb, recorded twice as often at the start
+/// let resampled: LineStringZ =
+///     vec![(0., 0., 0.), (0.5, 0., 0.5), (1., 0., 1.), (2., 0., 2.)].into();
+/// assert_eq!(climb.frechet_distance(&resampled), 0.5f64.sqrt());
+///
+/// let descent: LineStringZ = vec![(0., 0., 2.), (1., 0., 1.), (2., 0., 0.)].into();
+/// assert!(climb.dtw_distance(&descent, None) > climb.dtw_distance(&resampled, None));
+/// ```
+pub trait TrajectorySimilarity<T: CoordFloat> {
+    /// The dynamic time warping distance: the least sum of the distances between
+    /// matched vertices.
+    ///
+    /// With a `band`, the Sakoe-Chiba band, vertices are only matched to those
+    /// within `band` positions of their own, which is faster on long paths and
+    /// stops one path lingering at a vertex of the other. The band is widened to
+    /// the difference in the numbers of vertices if it is narrower, so that the
+    /// ends can still be matched.
+    fn dtw_distance(&self, other: &Self, band: Option<usize>) -> T;
+
+    /// The discrete Fréchet distance: the least, over all matchings, of the
+    /// largest distance between matched vertices.
+    ///
+    /// Where [dynamic time warping] reflects how far apart the paths are overall,
+    /// this is the length of the shortest leash with which a dog could follow one
+    /// path while its owner follows the other, vertex by vertex.
+    ///
+    /// [dynamic time warping]: TrajectorySimilarity::dtw_distance
+    fn frechet_distance(&self, other: &Self) -> T;
+}
+
+impl<T: CoordFloat> TrajectorySimilarity<T> for LineStringZ<T> {
+    fn dtw_distance(&self, other: &Self, band: Option<usize>) -> T {
+        warp(&self.0, &other.0, band, |sum, distance| sum + distance)
+    }
+
+    fn frechet_distance(&self, other: &Self) -> T {
+        warp(&self.0, &other.0, None, |max, distance| max.max(distance))
+    }
+}
+
+impl<T: CoordFloat> TrajectorySimilarity<T> for Trajectory<T> {
+    fn dtw_distance(&self, other: &Self, band: Option<usize>) -> T {
+        self.path().dtw_distance(other.path(), band)
+    }
+
+    fn frechet_distance(&self, other: &Self) -> T {
+        self.path().frechet_distance(other.path())
+    }
+}
+
+/// The least cost of matching `a` to `b`, where `combine` adds the distance
+/// between two matched vertices to the cost of the matching up to them.
+fn warp<T: CoordFloat>(
+    a: &[CoordZ<T>],
+    b: &[CoordZ<T>],
+    band: Option<usize>,
+    combine: impl Fn(T, T) -> T,
+) -> T {
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => return T::zero(),
+        (true, false) | (false, true) => return T::infinity(),
+        (false, false) => {}
+    }
+    let band = band.map_or(usize::MAX, |band| band.max(a.len().abs_diff(b.len())));
+    // the cost of the best matching up to each vertex of `b`, for the previous and
+    // the current vertex of `a`
+    let mut previous: Vec<T> = vec![T::infinity(); b.len()];
+    let mut current = previous.clone();
+    for (i, &p) in a.iter().enumerate() {
+        current.fill(T::infinity());
+        let first = i.saturating_sub(band);
+        let last = i.saturating_add(band).min(b.len() - 1);
+        for j in first..=last {
+            let best = match (i, j) {
+                (0, 0) => T::zero(),
+                (0, _) => current[j - 1],
+                (_, 0) => previous[j],
+                _ => previous[j].min(previous[j - 1]).min(current[j - 1]),
+            };
+            if best.is_finite() {
+                current[j] = combine(best, distance(p, b[j]));
+            }
+        }
+        core::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len() - 1]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dtw() {
+        let a: LineStringZ = vec![(0., 0., 0.), (1., 0., 0.), (2., 0., 0.), (3., 0., 0.)].into();
+        // the same path, pausing at its second vertex and lifted by one
+        let b: LineStringZ = vec![
+            (0., 0., 1.),
+            (1., 0., 1.),
+            (1., 0., 1.),
+            (1., 0., 1.),
+            (2., 0., 1.),
+            (3., 0., 1.),
+        ]
+        .into();
+        assert_eq!(a.dtw_distance(&b, None), 6.);
+        assert_eq!(a.dtw_distance(&a, None), 0.);
+        assert_eq!(a.dtw_distance(&b, None), b.dtw_distance(&a, None));
+        // a band too narrow to let `b` pause is widened to the difference in length
+        assert_eq!(a.dtw_distance(&b, Some(0)), 6.);
+
+        // without a band `c` lingers at the start of `a`, and with none to spare it
+        // has to keep pace
+        let c: LineStringZ = vec![(0., 0., 0.), (0., 0., 0.), (0., 0., 0.), (3., 0., 0.)].into();
+        assert_eq!(a.dtw_distance(&c, None), 2.);
+        assert_eq!(a.dtw_distance(&c, Some(0)), 3.);
+        assert_eq!(c.dtw_distance(&a, Some(1)), 2.);
+
+        let empty = LineStringZ::<f64>::empty();
+        assert_eq!(empty.dtw_distance(&empty, None), 0.);
+        assert_eq!(a.dtw_distance(&empty, None), f64::INFINITY);
+    }
+
+    #[test]
+    fn frechet() {
+        let a: LineStringZ = vec![(0., 0., 0.), (1., 0., 0.), (2., 0., 0.)].into();
+        let b: LineStringZ = vec![(0., 1., 0.), (2., 1., 0.)].into();
+        assert_eq!(a.frechet_distance(&b), 2f64.sqrt());
+        assert_eq!(a.frechet_distance(&a), 0.);
+
+        // going back the other way is as far as the paths are long
+        let reversed: LineStringZ = a.0.iter().rev().copied().collect::<Vec<_>>().into();
+        assert_eq!(a.frechet_distance(&reversed), 2.);
+
+        let trajectory = |path: &LineStringZ| {
+            Trajectory::try_new(path.clone(), (0..path.0.len()).map(|i| i as f64).collect())
+                .unwrap()
+        };
+        assert_eq!(
+            trajectory(&a).frechet_distance(&trajectory(&b)),
+            2f64.sqrt()
+        );
+    }
+}
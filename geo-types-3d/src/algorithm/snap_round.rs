@@ -0,0 +1,279 @@
+use alloc::collections::BTreeSet;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use super::linalg::Axes;
+use super::node::{segments, sweep};
+use super::ransac::fit_plane;
+use crate::{CoordFloat, CoordZ, Error, LineStringZ, MultiLineStringZ};
+
+impl<T: CoordFloat> MultiLineStringZ<T> {
+    /// Nodes linework lying in a common plane by snap rounding onto a grid of
+    /// squares `grid_size` across, so that vertices and crossings closer together
+    /// than the grid become exactly equal, ready for
+    /// [`MultiLineStringZ::polygonize`] or boolean operations.
+    ///
+    /// The linework is projected onto the coordinate plane most parallel to the
+    /// plane fitted to it, as for [`BooleanOpsZ`](crate::algorithm::BooleanOpsZ),
+    /// and the grid laid out there, with a grid point at the origin. Each square
+    /// containing a vertex, or a crossing of two segments, becomes a hot pixel,
+    /// and every segment passing through a hot pixel is bent to run through its
+    /// centre, so that no segment moves more than half a square's diagonal. The
+    /// line strings are then split wherever they meet, and stretches where two of
+    /// them now run together are kept once.
+    ///
+    /// Snapped coordinates are put back on the fitted plane; fails with
+    /// [`Error::NotCoplanar`] if any coordinate is further than `grid_size` from
+    /// it. Linework on a single line keeps its heights, snapped in XY, or in YZ if
+    /// the line is vertical. Line strings with fewer than two coordinates, or that
+    /// collapse into a single square, are left out.
+    ///
+    /// # Panics
+    ///
+    /// If `grid_size` isn't positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{line_string_z, MultiLineStringZ};
+    ///
+    /// // the edges of a field, digitised separately, whose ends don't quite meet
+    /// let edges: MultiLineStringZ = MultiLineStringZ::new(vec![
+    ///     line_string_z![(x: 0., y: 0., z: 3.), (x: 4., y: 0., z: 3.)],
+    ///     line_string_z![(x: 4.0001, y: 0., z: 3.), (x: 2., y: 3., z: 3.)],
+    ///     line_string_z![(x: 2., y: 3.0002, z: 3.), (x: 0.0001, y: -0.0001, z: 3.)],
+    /// ]);
+    /// assert!(edges.polygonize().unwrap().polygons.0.is_empty());
+    ///
+    /// let snapped = edges.snap_round(0.01).unwrap();
+    /// let result = snapped.polygonize().unwrap();
+    /// assert_eq!(result.polygons.0.len(), 1);
+    /// assert!(result.dangles.0.is_empty());
+    /// ```
+    pub fn snap_round(&self, grid_size: T) -> Result<MultiLineStringZ<T>, Error> {
+        assert!(grid_size > T::zero(), "the grid size must be positive");
+        let lines: Vec<&LineStringZ<T>> = self.0.iter().filter(|line| line.0.len() >= 2).collect();
+        let coords: Vec<CoordZ<T>> = lines
+            .iter()
+            .flat_map(|line| line.0.iter().copied())
+            .collect();
+        let plane = (!coords.is_empty()).then(|| fit_plane(&coords)).flatten();
+        if let Some(plane) = &plane {
+            if coords
+                .iter()
+                .any(|&c| plane.signed_distance(c).abs() > grid_size)
+            {
+                return Err(Error::NotCoplanar);
+            }
+        }
+        let axes = match &plane {
+            Some(plane) => Axes::facing(plane.normal),
+            // linework on a vertical line has nothing to snap in XY
+            None if coords
+                .windows(2)
+                .all(|pair| pair[0].x == pair[1].x && pair[0].y == pair[1].y) =>
+            {
+                Axes::new(0)
+            }
+            None => Axes::new(2),
+        };
+        let projected: Vec<LineStringZ<T>> = lines
+            .iter()
+            .map(|line| LineStringZ(line.0.iter().map(|&c| axes.project(c)).collect()))
+            .collect();
+        let projected: Vec<&LineStringZ<T>> = projected.iter().collect();
+        let segments = segments(&projected);
+
+        // hot pixels, taking the dropped coordinate of the first vertex or crossing
+        // in them
+        let key = |c: CoordZ<T>| ((c.x / grid_size).round(), (c.y / grid_size).round());
+        let mut pixels: Vec<((T, T), T)> = projected
+            .iter()
+            .flat_map(|line| line.0.iter().map(|&c| (key(c), c.z)))
+            .collect();
+        sweep(&segments, |a, b| {
+            for (t, u) in a.crossings(b) {
+                let inside = |v: T| v > T::zero() && v < T::one();
+                if inside(t) && inside(u) {
+                    let (crossing, other) = (a.at(t), b.at(u));
+                    let w = (crossing.z + other.z) / (T::one() + T::one());
+                    pixels.push((key(crossing), w));
+                }
+            }
+        });
+        pixels.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+        pixels.dedup_by(|a, b| a.0 == b.0);
+        let normal = plane
+            .as_ref()
+            .map(|plane| (axes.project(plane.normal), plane.offset));
+        let centres: Vec<CoordZ<T>> = pixels
+            .iter()
+            .map(|&((i, j), w)| {
+                let (x, y) = (i * grid_size, j * grid_size);
+                let z = normal.map_or(w, |(n, offset)| (offset - n.x * x - n.y * y) / n.z);
+                axes.unproject(CoordZ { x, y, z })
+            })
+            .collect();
+
+        // each line string as the hot pixels it passes through, in order
+        let half = grid_size / (T::one() + T::one());
+        let mut routes: Vec<Vec<usize>> = vec![Vec::new(); projected.len()];
+        for segment in &segments {
+            let (low, high) = (
+                (segment.start.x.min(segment.end.x) / grid_size).round(),
+                (segment.start.x.max(segment.end.x) / grid_size).round(),
+            );
+            let first = pixels.partition_point(|pixel| pixel.0 .0 < low);
+            let last = pixels.partition_point(|pixel| pixel.0 .0 <= high);
+            let mut hits: Vec<(T, usize)> = (first..last)
+                .filter_map(|index| {
+                    let (i, j) = pixels[index].0;
+                    let centre = (i * grid_size, j * grid_size);
+                    along(segment.start, segment.end, centre, half).map(|t| (t, index))
+                })
+                .collect();
+            hits.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+            let route = &mut routes[segment.line];
+            for (_, index) in hits {
+                if route.last() != Some(&index) {
+                    route.push(index);
+                }
+            }
+        }
+
+        // split where the routes meet, keeping each stretch once
+        let mut uses = vec![0_usize; pixels.len()];
+        for &index in routes.iter().flatten() {
+            uses[index] += 1;
+        }
+        let mut seen = BTreeSet::new();
+        let mut pieces = Vec::new();
+        for route in routes.iter().filter(|route| route.len() >= 2) {
+            let mut start = 0;
+            for end in 1..route.len() {
+                if end + 1 < route.len() && uses[route[end]] < 2 {
+                    continue;
+                }
+                let piece = &route[start..=end];
+                start = end;
+                let reversed: Vec<usize> = piece.iter().rev().copied().collect();
+                if seen.insert(reversed.clone().min(piece.to_vec())) {
+                    pieces.push(LineStringZ(
+                        piece.iter().map(|&index| centres[index]).collect(),
+                    ));
+                }
+            }
+        }
+        Ok(MultiLineStringZ::new(pieces))
+    }
+}
+
+/// How far along the segment from `start` to `end` it passes through the square
+/// `2 * half` across around `centre`, as the fraction of the way to the middle of
+/// its stretch inside, or `None` if it misses it.
+fn along<T: CoordFloat>(start: CoordZ<T>, end: CoordZ<T>, centre: (T, T), half: T) -> Option<T> {
+    let (mut low, mut high) = (T::zero(), T::one());
+    for (from, to, centre) in [(start.x, end.x, centre.0), (start.y, end.y, centre.1)] {
+        let (min, max) = (centre - half - from, centre + half - from);
+        let delta = to - from;
+        if delta == T::zero() {
+            if min > T::zero() || max < T::zero() {
+                return None;
+            }
+        } else {
+            let (a, b) = (min / delta, max / delta);
+            low = low.max(a.min(b));
+            high = high.min(a.max(b));
+        }
+    }
+    (low <= high).then(|| (low + high) / (T::one() + T::one()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn line(coords: &[(f64, f64, f64)]) -> LineStringZ {
+        LineStringZ::from(coords.to_vec())
+    }
+
+    #[test]
+    fn hot_pixels() {
+        // a vertex just off a line drags the line through it
+        let lines = MultiLineStringZ::new(vec![
+            line(&[(0., 0., 0.), (10., 0., 0.)]),
+            line(&[(5., 0.3, 0.), (5., 4., 0.)]),
+        ]);
+        assert_eq!(
+            lines.snap_round(1.).unwrap(),
+            MultiLineStringZ::new(vec![
+                line(&[(0., 0., 0.), (5., 0., 0.)]),
+                line(&[(5., 0., 0.), (10., 0., 0.)]),
+                line(&[(5., 0., 0.), (5., 4., 0.)]),
+            ])
+        );
+
+        // a crossing off the grid, on a slope where z = x / 2, with a line that
+        // collapses onto part of another and one that collapses altogether
+        let lines = MultiLineStringZ::new(vec![
+            line(&[(0., 0., 0.), (3., 2.9, 1.5)]),
+            line(&[(0., 3., 0.), (3., 0.2, 1.5)]),
+            line(&[(0.1, 0.1, 0.05), (1.1, 1.1, 0.55)]),
+            line(&[(0.1, 0.1, 0.05), (0.2, 0.2, 0.1)]),
+        ]);
+        let snapped = lines.snap_round(1.).unwrap();
+        let xy = |piece: &LineStringZ| -> Vec<(f64, f64)> {
+            piece.0.iter().map(|c| (c.x, c.y)).collect()
+        };
+        assert_eq!(
+            snapped.0.iter().map(xy).collect::<Vec<_>>(),
+            [
+                vec![(0., 0.), (1., 1.)],
+                vec![(1., 1.), (2., 2.)],
+                vec![(2., 2.), (3., 3.)],
+                vec![(0., 3.), (2., 2.)],
+                vec![(2., 2.), (3., 0.)],
+            ]
+        );
+        for c in snapped.0.iter().flat_map(|line| line.0.iter()) {
+            assert_relative_eq!(c.z, c.x / 2., epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn planes() {
+        // a vertical wall, snapped in YZ
+        let wall = MultiLineStringZ::new(vec![
+            line(&[(5., 0., 0.), (5., 4., 0.), (5., 4., 3.)]),
+            line(&[(5., 4.04, 2.98), (5., 0.01, 3.), (5., 0., 0.02)]),
+        ]);
+        let snapped = wall.snap_round(0.1).unwrap();
+        let result = snapped.polygonize().unwrap();
+        assert_eq!(result.polygons.0.len(), 1);
+        for c in result.polygons.0[0].exterior().coords() {
+            assert_relative_eq!(c.x, 5., epsilon = 1e-12);
+        }
+
+        // a single vertical line
+        let mast = MultiLineStringZ::new(vec![line(&[(1., 1., 0.), (1., 1., 10.2)])]);
+        assert_eq!(
+            mast.snap_round(1.).unwrap(),
+            MultiLineStringZ::new(vec![line(&[(1., 1., 0.), (1., 1., 10.)])])
+        );
+
+        let bent = MultiLineStringZ::new(vec![line(&[
+            (0., 0., 0.),
+            (1., 0., 0.),
+            (1., 1., 5.),
+            (0., 1., 0.),
+        ])]);
+        assert!(matches!(bent.snap_round(0.1), Err(Error::NotCoplanar)));
+        assert!(bent.snap_round(10.).is_ok());
+        assert!(MultiLineStringZ::<f64>::empty()
+            .snap_round(1.)
+            .unwrap()
+            .0
+            .is_empty());
+    }
+}
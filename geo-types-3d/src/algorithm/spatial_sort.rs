@@ -0,0 +1,247 @@
+use crate::algorithm::BoundingCube;
+use crate::{
+    CoordFloat, CoordZ, Cube, GeometryCollection, MultiLineStringZ, MultiPointZ, MultiPolygonZ,
+};
+
+/// The number of bits each axis is quantized to, so that the three fill a `u64`.
+const BITS: u32 = 21;
+
+/// The Morton code, or Z-order key, of `coord` within `bounds`.
+///
+/// Each axis is quantized to 21 bits across `bounds`, and their bits are
+/// interleaved, x lowest. Coordinates outside `bounds` are clamped to it.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::morton_key;
+/// use geo_types_3d::{coordZ, Cube};
+///
+/// let bounds = Cube::new((0., 0., 0.), (1., 1., 1.));
+/// assert_eq!(morton_key(coordZ! { x: 0., y: 0., z: 0. }, &bounds), 0);
+/// assert_eq!(morton_key(coordZ! { x: 1., y: 0., z: 0. }, &bounds), 0x1249249249249249);
+/// assert_eq!(morton_key(coordZ! { x: 1., y: 1., z: 1. }, &bounds), 0x7fffffffffffffff);
+/// ```
+pub fn morton_key<T: CoordFloat>(coord: CoordZ<T>, bounds: &Cube<T>) -> u64 {
+    let [x, y, z] = quantize(coord, bounds);
+    spread(x) | spread(y) << 1 | spread(z) << 2
+}
+
+/// The index of `coord` along a 3D Hilbert curve through `bounds`.
+///
+/// Each axis is quantized to 21 bits across `bounds`, as for [`morton_key`].
+/// Unlike Morton order, consecutive cells along the curve always share a face, so
+/// sorting by this key keeps neighbours closer together.
+pub fn hilbert_key<T: CoordFloat>(coord: CoordZ<T>, bounds: &Cube<T>) -> u64 {
+    hilbert_index(quantize(coord, bounds), BITS)
+}
+
+/// Sort the members of a collection along a space-filling curve, so that members
+/// close in space are mostly close in order, such as before bulk loading an index
+/// or writing a spatially clustered file.
+///
+/// Members are placed by the centre of their bounding cube within the bounds of
+/// the whole collection. Empty members go last, and the sort is stable.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::SpatialSort;
+/// use geo_types_3d::{MultiPointZ, PointZ};
+///
+/// let mut points = MultiPointZ::from(vec![
+///     (0., 0., 0.),
+///     (9., 9., 9.),
+///     (0., 1., 0.),
+///     (9., 8., 9.),
+/// ]);
+/// points.hilbert_sort();
+/// assert_eq!(points.0[..2], [PointZ::new(0., 0., 0.), PointZ::new(0., 1., 0.)]);
+/// ```
+pub trait SpatialSort<T: CoordFloat> {
+    /// Sorts the members in Hilbert order, by [`hilbert_key`].
+    fn hilbert_sort(&mut self);
+
+    /// Sorts the members in Morton order, by [`morton_key`].
+    fn morton_sort(&mut self);
+}
+
+impl<T: CoordFloat, G: BoundingCube<T>> SpatialSort<T> for [G] {
+    fn hilbert_sort(&mut self) {
+        sort_by(self, hilbert_key)
+    }
+
+    fn morton_sort(&mut self) {
+        sort_by(self, morton_key)
+    }
+}
+
+macro_rules! impl_spatial_sort {
+    ($($type: ident),+) => {
+        $(
+        impl<T: CoordFloat> SpatialSort<T> for $type<T> {
+            fn hilbert_sort(&mut self) {
+                self.0.hilbert_sort()
+            }
+
+            fn morton_sort(&mut self) {
+                self.0.morton_sort()
+            }
+        }
+        )+
+    };
+}
+
+impl_spatial_sort!(
+    MultiPointZ,
+    MultiLineStringZ,
+    MultiPolygonZ,
+    GeometryCollection
+);
+
+fn sort_by<T: CoordFloat, G: BoundingCube<T>>(
+    geometries: &mut [G],
+    key: impl Fn(CoordZ<T>, &Cube<T>) -> u64,
+) {
+    let Some(bounds) = geometries
+        .iter()
+        .filter_map(|geometry| geometry.bounding_cube().into())
+        .reduce(|a, b| a.union(&b))
+    else {
+        return;
+    };
+    geometries.sort_by_cached_key(|geometry| {
+        let cube: Option<Cube<T>> = geometry.bounding_cube().into();
+        cube.map_or((true, 0), |cube| (false, key(cube.center(), &bounds)))
+    });
+}
+
+/// The cell of `coord` on each axis, among `2^BITS` across `bounds`.
+fn quantize<T: CoordFloat>(coord: CoordZ<T>, bounds: &Cube<T>) -> [u32; 3] {
+    let cells = T::from((1u32 << BITS) - 1).unwrap();
+    let (min, max) = (bounds.min(), bounds.max());
+    let axis = |value: T, min: T, max: T| {
+        let extent = max - min;
+        if extent <= T::zero() {
+            return 0;
+        }
+        let cell = ((value - min) / extent * cells).round();
+        cell.max(T::zero()).min(cells).to_u32().unwrap_or(0)
+    };
+    [
+        axis(coord.x, min.x, max.x),
+        axis(coord.y, min.y, max.y),
+        axis(coord.z, min.z, max.z),
+    ]
+}
+
+/// Spreads the low 21 bits of `value` out to every third bit.
+fn spread(value: u32) -> u64 {
+    let mut x = u64::from(value) & 0x1f_ffff;
+    x = (x | x << 32) & 0x001f_0000_0000_ffff;
+    x = (x | x << 16) & 0x001f_0000_ff00_00ff;
+    x = (x | x << 8) & 0x100f_00f0_0f00_f00f;
+    x = (x | x << 4) & 0x10c3_0c30_c30c_30c3;
+    x = (x | x << 2) & 0x1249_2492_4924_9249;
+    x
+}
+
+/// The Hilbert index of a cell of a grid with `bits` bits on each axis, by
+/// Skilling's transpose method.
+fn hilbert_index(mut x: [u32; 3], bits: u32) -> u64 {
+    let m = 1u32 << (bits - 1);
+    // undo the rotations and reflections of the curve, from the top level down
+    let mut q = m;
+    while q > 1 {
+        let p = q - 1;
+        for i in 0..3 {
+            if x[i] & q != 0 {
+                x[0] ^= p;
+            } else {
+                let t = (x[0] ^ x[i]) & p;
+                x[0] ^= t;
+                x[i] ^= t;
+            }
+        }
+        q >>= 1;
+    }
+    // Gray encode
+    for i in 1..3 {
+        x[i] ^= x[i - 1];
+    }
+    let mut t = 0;
+    let mut q = m;
+    while q > 1 {
+        if x[2] & q != 0 {
+            t ^= q - 1;
+        }
+        q >>= 1;
+    }
+    for axis in &mut x {
+        *axis ^= t;
+    }
+    // interleave the transposed bits, most significant first
+    let mut index = 0u64;
+    for bit in (0..bits).rev() {
+        for axis in x {
+            index = index << 1 | u64::from(axis >> bit & 1);
+        }
+    }
+    index
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Geometry, PointZ};
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn hilbert_curve_is_continuous() {
+        // every cell of a 4 x 4 x 4 grid has its own index, and each shares a face
+        // with the next
+        let mut cells: Vec<(u64, [u32; 3])> = (0..64)
+            .map(|i| {
+                let cell = [i & 3, i >> 2 & 3, i >> 4];
+                (hilbert_index(cell, 2), cell)
+            })
+            .collect();
+        cells.sort();
+        for (i, pair) in cells.windows(2).enumerate() {
+            assert_eq!(pair[0].0, i as u64);
+            let steps: u32 = (0..3).map(|a| pair[0].1[a].abs_diff(pair[1].1[a])).sum();
+            assert_eq!(steps, 1);
+        }
+    }
+
+    #[test]
+    fn sorts_collections() {
+        let mut points: Vec<PointZ> = (0..8)
+            .map(|i| PointZ::new(f64::from(i & 1), f64::from(i >> 1 & 1), f64::from(i >> 2)))
+            .rev()
+            .collect();
+        points.morton_sort();
+        assert_eq!(points[0], PointZ::new(0., 0., 0.));
+        assert_eq!(points[1], PointZ::new(1., 0., 0.));
+        assert_eq!(points[7], PointZ::new(1., 1., 1.));
+
+        let mut collection = GeometryCollection(vec![
+            Geometry::from(GeometryCollection::<f64>::empty()),
+            PointZ::new(5., 5., 5.).into(),
+            Geometry::Point(geo_types::Point::new(0., 0.)),
+        ]);
+        collection.hilbert_sort();
+        assert_eq!(
+            collection.0[0],
+            Geometry::Point(geo_types::Point::new(0., 0.))
+        );
+        assert_eq!(collection.0[2], GeometryCollection::empty().into());
+
+        // a single point, and nothing at all
+        let mut single = MultiPointZ::from(vec![(1., 2., 3.)]);
+        single.hilbert_sort();
+        assert_eq!(single.0.len(), 1);
+        MultiPointZ::<f64>::new(vec![]).morton_sort();
+    }
+}
@@ -0,0 +1,251 @@
+use alloc::vec::Vec;
+
+use geo_types::{Coord, Line, LineString, Point, Polygon, Rect};
+
+use crate::algorithm::linalg::{newell_normal, norm};
+use crate::algorithm::BoundingCube;
+use crate::{
+    CoordFloat, CoordZ, Cube, Geometry, GeometryVisitor, LineStringZ, LineZ, PointZ, PolygonZ,
+};
+
+/// A summary of a geometry, such as to check imported data or to log what a
+/// pipeline is handling.
+///
+/// Coordinates are counted as stored, so the closing coordinate of each ring
+/// counts, and those of collections are counted together. Planar members count
+/// towards the vertices, rings, length and area, and are bounded at z = 0 as by
+/// [`BoundingCube`], but have no z of their own to summarise.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::GeometryStats;
+/// use geo_types_3d::{wkt, Geometry, GeometryCollection};
+///
+/// let geometry = Geometry::GeometryCollection(GeometryCollection(vec![
+///     wkt! { LINESTRING Z(0. 0. 0.,3. 4. 0.,3. 4. 2.) }.into(),
+///     wkt! { POLYGON Z((0. 0. 5.,2. 0. 5.,2. 2. 5.,0. 2. 5.,0. 0. 5.)) }.into(),
+/// ]));
+/// let stats = GeometryStats::from(&geometry);
+/// assert_eq!((stats.vertices, stats.rings), (8, 1));
+/// assert_eq!((stats.length, stats.area), (7., 4.));
+/// assert_eq!((stats.min_z, stats.max_z), (Some(0.), Some(5.)));
+/// assert_eq!(stats.nan_coords, 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GeometryStats<T: CoordFloat = f64> {
+    /// The number of coordinates.
+    pub vertices: usize,
+    /// The number of polygon rings, exterior and interior.
+    pub rings: usize,
+    /// The bounds of the geometry, or `None` if it is empty.
+    pub bounding_cube: Option<Cube<T>>,
+    /// The lowest z, or `None` without any 3D coordinates whose z is a number.
+    pub min_z: Option<T>,
+    /// The highest z, or `None` without any 3D coordinates whose z is a number.
+    pub max_z: Option<T>,
+    /// The mean z, or `None` without any 3D coordinates whose z is a number.
+    pub mean_z: Option<T>,
+    /// The total length of the lines and line strings, in 3D.
+    pub length: T,
+    /// The total area of the polygons, each measured in its own plane, less that
+    /// of their holes.
+    pub area: T,
+    /// The number of coordinates with an ordinate that is NaN.
+    pub nan_coords: usize,
+}
+
+impl<T: CoordFloat> From<&Geometry<T>> for GeometryStats<T> {
+    fn from(geometry: &Geometry<T>) -> Self {
+        let mut summary = Summary {
+            stats: GeometryStats {
+                vertices: 0,
+                rings: 0,
+                bounding_cube: geometry.bounding_cube(),
+                min_z: None,
+                max_z: None,
+                mean_z: None,
+                length: T::zero(),
+                area: T::zero(),
+                nan_coords: 0,
+            },
+            z_sum: T::zero(),
+            z_count: 0,
+        };
+        geometry.accept(&mut summary);
+        let mut stats = summary.stats;
+        if summary.z_count > 0 {
+            stats.mean_z = T::from(summary.z_count).map(|count| summary.z_sum / count);
+        }
+        stats
+    }
+}
+
+/// Gathers the statistics of each member of a geometry.
+struct Summary<T: CoordFloat> {
+    stats: GeometryStats<T>,
+    z_sum: T,
+    z_count: usize,
+}
+
+impl<T: CoordFloat> Summary<T> {
+    fn coord(&mut self, coord: CoordZ<T>, has_z: bool) {
+        self.stats.vertices += 1;
+        if coord.x.is_nan() || coord.y.is_nan() || coord.z.is_nan() {
+            self.stats.nan_coords += 1;
+        }
+        if !has_z || coord.z.is_nan() {
+            return;
+        }
+        let z = coord.z;
+        self.stats.min_z = Some(self.stats.min_z.map_or(z, |min| min.min(z)));
+        self.stats.max_z = Some(self.stats.max_z.map_or(z, |max| max.max(z)));
+        self.z_sum = self.z_sum + z;
+        self.z_count += 1;
+    }
+
+    fn line(&mut self, coords: &[CoordZ<T>], has_z: bool) {
+        for &coord in coords {
+            self.coord(coord, has_z);
+        }
+        for pair in coords.windows(2) {
+            let d = pair[1] - pair[0];
+            self.stats.length = self.stats.length + (d.x * d.x + d.y * d.y + d.z * d.z).sqrt();
+        }
+    }
+
+    fn polygon<'a>(&mut self, rings: impl Iterator<Item = &'a [CoordZ<T>]>, has_z: bool)
+    where
+        T: 'a,
+    {
+        let two = T::one() + T::one();
+        for (i, ring) in rings.enumerate() {
+            self.stats.rings += 1;
+            for &coord in ring {
+                self.coord(coord, has_z);
+            }
+            // half the length of the ring's Newell normal, less for holes
+            let normal = newell_normal(ring);
+            let area = norm(normal) / two;
+            self.stats.area = if i == 0 {
+                self.stats.area + area
+            } else {
+                self.stats.area - area
+            };
+        }
+    }
+}
+
+impl<T: CoordFloat> GeometryVisitor<T> for Summary<T> {
+    fn visit_point(&mut self, point: &Point<T>) {
+        self.coord(flat(point.0), false);
+    }
+
+    fn visit_point_z(&mut self, point: &PointZ<T>) {
+        self.coord(point.0, true);
+    }
+
+    fn visit_line(&mut self, line: &Line<T>) {
+        self.line(&[flat(line.start), flat(line.end)], false);
+    }
+
+    fn visit_line_z(&mut self, line: &LineZ<T>) {
+        self.line(&[line.start, line.end], true);
+    }
+
+    fn visit_line_string(&mut self, line_string: &LineString<T>) {
+        self.line(&flat_ring(line_string), false);
+    }
+
+    fn visit_line_string_z(&mut self, line_string: &LineStringZ<T>) {
+        self.line(&line_string.0, true);
+    }
+
+    fn visit_polygon(&mut self, polygon: &Polygon<T>) {
+        let rings: Vec<Vec<CoordZ<T>>> = core::iter::once(polygon.exterior())
+            .chain(polygon.interiors())
+            .map(flat_ring)
+            .collect();
+        self.polygon(rings.iter().map(Vec::as_slice), false);
+    }
+
+    fn visit_polygon_z(&mut self, polygon: &PolygonZ<T>) {
+        self.polygon(polygon.rings().map(|ring| ring.0.as_slice()), true);
+    }
+
+    fn visit_rect(&mut self, rect: &Rect<T>) {
+        self.visit_polygon(&rect.to_polygon())
+    }
+}
+
+fn flat<T: CoordFloat>(coord: Coord<T>) -> CoordZ<T> {
+    CoordZ {
+        x: coord.x,
+        y: coord.y,
+        z: T::zero(),
+    }
+}
+
+fn flat_ring<T: CoordFloat>(line_string: &LineString<T>) -> Vec<CoordZ<T>> {
+    line_string.0.iter().copied().map(flat).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{GeometryCollection, MultiPointZ};
+    use alloc::vec;
+
+    #[test]
+    fn walls_and_holes() {
+        // a wall in the plane x = 1, 4 wide and 3 high, with a window of 1 by 1
+        let wall: PolygonZ = polygon_z!(
+            exterior: [
+                (x: 1., y: 0., z: 0.),
+                (x: 1., y: 4., z: 0.),
+                (x: 1., y: 4., z: 3.),
+                (x: 1., y: 0., z: 3.),
+            ],
+            interiors: [[
+                (x: 1., y: 1., z: 1.),
+                (x: 1., y: 1., z: 2.),
+                (x: 1., y: 2., z: 2.),
+                (x: 1., y: 2., z: 1.),
+            ]],
+        );
+        let stats = GeometryStats::from(&Geometry::PolygonZ(wall));
+        assert_eq!(stats.vertices, 10);
+        assert_eq!(stats.rings, 2);
+        assert_eq!(stats.area, 11.);
+        assert_eq!(stats.length, 0.);
+        assert_eq!(stats.mean_z, Some(1.3));
+        assert_eq!(
+            stats.bounding_cube,
+            Some(Cube::new((1., 0., 0.), (1., 4., 3.)))
+        );
+
+        // planar members have no z, and NaN z is counted but not summarised
+        let mixed = Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::Rect(Rect::new((0., 0.), (2., 3.))),
+            Geometry::MultiPointZ(MultiPointZ::from(vec![
+                (0., 0., 2.),
+                (0., 0., f64::NAN),
+                (0., 0., 4.),
+            ])),
+        ]));
+        let stats = GeometryStats::from(&mixed);
+        assert_eq!((stats.vertices, stats.rings), (8, 1));
+        assert_eq!(stats.area, 6.);
+        assert_eq!(stats.nan_coords, 1);
+        assert_eq!(
+            (stats.min_z, stats.max_z, stats.mean_z),
+            (Some(2.), Some(4.), Some(3.))
+        );
+
+        let empty = GeometryStats::from(&Geometry::LineStringZ(LineStringZ::<f64>::empty()));
+        assert_eq!(empty.vertices, 0);
+        assert_eq!(empty.bounding_cube, None);
+        assert_eq!(empty.mean_z, None);
+    }
+}
@@ -0,0 +1,710 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algorithm::linalg::{cross, distance, dot};
+use crate::{CoordFloat, CoordZ, LineStringZ, MultiLineStringZ, MultiPolygonZ, PolygonZ};
+
+/// The straight skeleton of building footprints, and the hipped roofs built on
+/// it, for generating buildings from their outlines.
+///
+/// The footprint is projected onto XY and its edges moved inwards at the same
+/// speed, as in the construction of Aichholzer and Aurenhammer. Each vertex of
+/// the shrinking outline traces an arc of the skeleton, until two of them meet
+/// where an edge shrinks away, or a reflex vertex runs into an opposite edge and
+/// splits the outline in two. Holes take part like the exterior, their edges
+/// moving away from the hole, as around a courtyard.
+///
+/// Heights are measured from the footprint's height, the mean z of its exterior,
+/// so that a roof starts at the top of the walls. Footprints with no area are
+/// skipped.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::StraightSkeleton;
+/// use geo_types_3d::{wkt, PolygonZ};
+///
+/// // a house 10 by 6, with walls 3 high
+/// let footprint: PolygonZ = wkt! { POLYGON Z((0. 0. 3.,10. 0. 3.,10. 6. 3.,0. 6. 3.,0. 0. 3.)) };
+///
+/// // two hips from each corner, and the ridge between them
+/// assert_eq!(footprint.straight_skeleton().0.len(), 5);
+///
+/// let roof = footprint.generate_hipped_roof(45.);
+/// assert_eq!(roof.0.len(), 4);
+/// let top = roof.0.iter().flat_map(|face| face.exterior().coords()).map(|c| c.z).fold(0., f64::max);
+/// assert!((top - 6.).abs() < 1e-9);
+/// ```
+pub trait StraightSkeleton {
+    type Scalar: CoordFloat;
+
+    /// The arcs of the straight skeleton, without the footprint's own edges, each
+    /// point's z raised by its distance from the edges, as for a roof pitched at
+    /// 45°.
+    fn straight_skeleton(&self) -> MultiLineStringZ<Self::Scalar>;
+
+    /// A hipped roof sloping up from every edge at `pitch` degrees from the
+    /// horizontal, as a polygon for each edge.
+    fn generate_hipped_roof(&self, pitch: Self::Scalar) -> MultiPolygonZ<Self::Scalar>;
+}
+
+impl<T: CoordFloat> StraightSkeleton for PolygonZ<T> {
+    type Scalar = T;
+
+    fn straight_skeleton(&self) -> MultiLineStringZ<T> {
+        Skeleton::new(self).map_or_else(MultiLineStringZ::empty, |skeleton| {
+            MultiLineStringZ::new(skeleton.arcs(T::one()))
+        })
+    }
+
+    fn generate_hipped_roof(&self, pitch: T) -> MultiPolygonZ<T> {
+        Skeleton::new(self).map_or_else(MultiPolygonZ::empty, |skeleton| {
+            MultiPolygonZ::new(skeleton.faces(pitch.to_radians().tan()))
+        })
+    }
+}
+
+impl<T: CoordFloat> StraightSkeleton for MultiPolygonZ<T> {
+    type Scalar = T;
+
+    fn straight_skeleton(&self) -> MultiLineStringZ<T> {
+        MultiLineStringZ::new(
+            self.0
+                .iter()
+                .flat_map(|polygon| polygon.straight_skeleton().0)
+                .collect(),
+        )
+    }
+
+    fn generate_hipped_roof(&self, pitch: T) -> MultiPolygonZ<T> {
+        MultiPolygonZ::new(
+            self.0
+                .iter()
+                .flat_map(|polygon| polygon.generate_hipped_roof(pitch).0)
+                .collect(),
+        )
+    }
+}
+
+/// An edge of the footprint, with the interior on its left.
+struct Edge<T: CoordFloat> {
+    start: usize,
+    end: usize,
+    /// The unit normal pointing into the interior, with zero z.
+    normal: CoordZ<T>,
+    /// How far along the normal the edge lies from the origin.
+    offset: T,
+}
+
+/// A vertex of the shrinking outline, between the edges `left` and `right`.
+#[derive(Clone, Copy)]
+struct Vertex<T: CoordFloat> {
+    /// The node where it started.
+    origin: usize,
+    /// The velocity in XY, with zero z.
+    velocity: CoordZ<T>,
+    left: usize,
+    right: usize,
+}
+
+/// The next thing to happen to the outline.
+enum Event {
+    /// The edge after vertex `index` of a loop shrinks away.
+    Edge { lp: usize, index: usize },
+    /// Vertex `index` of a loop runs into the edge after vertex `target` of loop
+    /// `target_lp`.
+    Split {
+        lp: usize,
+        index: usize,
+        target_lp: usize,
+        target: usize,
+    },
+}
+
+/// The straight skeleton of one footprint, with nodes in XY and the time the
+/// outline reached them as z.
+struct Skeleton<T: CoordFloat> {
+    base: T,
+    edges: Vec<Edge<T>>,
+    nodes: Vec<CoordZ<T>>,
+    /// The arcs between nodes, each with the edge whose face it bounds.
+    arcs: Vec<(usize, usize, usize)>,
+    /// Nodes closer than this are the same.
+    eps: T,
+}
+
+impl<T: CoordFloat> Skeleton<T> {
+    fn new(polygon: &PolygonZ<T>) -> Option<Self> {
+        let exterior = ring(polygon.exterior(), true)?;
+        let rings: Vec<Vec<CoordZ<T>>> = core::iter::once(exterior)
+            .chain(
+                polygon
+                    .interiors()
+                    .iter()
+                    .filter_map(|hole| ring(hole, false)),
+            )
+            .collect();
+        // the walls' height, without counting the closing coordinate twice
+        let walls = match polygon.exterior().0.as_slice() {
+            [first, rest @ .., last] if first == last => rest.len() + 1,
+            coords => coords.len(),
+        };
+        let base = polygon.exterior().0[..walls]
+            .iter()
+            .fold(T::zero(), |sum, coord| sum + coord.z)
+            / T::from(walls).unwrap_or_else(T::one);
+        let (mut min, mut max) = (
+            CoordZ::from((T::infinity(), T::infinity(), T::zero())),
+            CoordZ::from((T::neg_infinity(), T::neg_infinity(), T::zero())),
+        );
+        for &coord in rings.iter().flatten() {
+            min = min.component_min(coord);
+            max = max.component_max(coord);
+        }
+        let size = (max.x - min.x).max(max.y - min.y);
+
+        let mut skeleton = Self {
+            base,
+            edges: Vec::new(),
+            nodes: Vec::new(),
+            arcs: Vec::new(),
+            eps: size * T::epsilon().sqrt(),
+        };
+        let mut loops = Vec::new();
+        for ring in &rings {
+            let first = skeleton.edges.len();
+            let n = ring.len();
+            for (i, &coord) in ring.iter().enumerate() {
+                let delta = ring[(i + 1) % n] - coord;
+                let length = delta.x.hypot(delta.y);
+                let normal = CoordZ {
+                    x: -delta.y / length,
+                    y: delta.x / length,
+                    z: T::zero(),
+                };
+                skeleton.nodes.push(coord);
+                skeleton.edges.push(Edge {
+                    start: first + i,
+                    end: first + (i + 1) % n,
+                    normal,
+                    offset: dot(normal, coord),
+                });
+            }
+            let mut lp: Vec<Vertex<T>> = (0..n)
+                .map(|i| Vertex {
+                    origin: first + i,
+                    velocity: CoordZ::zero(),
+                    left: first + (i + n - 1) % n,
+                    right: first + i,
+                })
+                .collect();
+            for vertex in &mut lp {
+                vertex.velocity = skeleton.velocity(vertex.left, vertex.right)?;
+            }
+            loops.push(lp);
+        }
+        skeleton.shrink(loops);
+        Some(skeleton)
+    }
+
+    /// Moves the outline inwards until nothing is left of it.
+    fn shrink(&mut self, mut loops: Vec<Vec<Vertex<T>>>) {
+        let mut now = T::zero();
+        let vertices: usize = loops.iter().map(Vec::len).sum();
+        // a guard against degenerate input, each event removing a vertex or a
+        // reflex vertex
+        for _ in 0..4 * vertices * vertices + 16 {
+            let Some((time, event)) = self.next_event(&loops, now) else {
+                break;
+            };
+            now = time;
+            match event {
+                Event::Edge { lp, index } => {
+                    let n = loops[lp].len();
+                    let (u, w) = (loops[lp][index], loops[lp][(index + 1) % n]);
+                    let (pu, pw) = (self.position(&u, now), self.position(&w, now));
+                    let two = T::one() + T::one();
+                    let node = self.node((pu + pw) / two, now);
+                    self.trace(&u, node);
+                    self.trace(&w, node);
+                    let merged = Vertex {
+                        origin: node,
+                        velocity: CoordZ::zero(),
+                        left: u.left,
+                        right: w.right,
+                    };
+                    let mut lp_vertices = core::mem::take(&mut loops[lp]);
+                    if index + 1 == n {
+                        lp_vertices.pop();
+                        lp_vertices[0] = merged;
+                    } else {
+                        lp_vertices[index] = merged;
+                        lp_vertices.remove(index + 1);
+                    }
+                    loops[lp] = lp_vertices;
+                    self.settle(&mut loops, lp, now);
+                }
+                Event::Split {
+                    lp,
+                    index,
+                    target_lp,
+                    target,
+                } => {
+                    let v = loops[lp][index];
+                    let node = self.node(self.position(&v, now), now);
+                    self.trace(&v, node);
+                    let edge = loops[target_lp][target].right;
+                    let v1 = Vertex {
+                        origin: node,
+                        velocity: CoordZ::zero(),
+                        left: v.left,
+                        right: edge,
+                    };
+                    let v2 = Vertex {
+                        right: v.right,
+                        left: edge,
+                        ..v1
+                    };
+                    // the loops, starting from the vertex and from the far end of
+                    // the edge it runs into
+                    let mut around = loops[lp].clone();
+                    around.rotate_left(index);
+                    if target_lp == lp {
+                        let far = (target + loops[lp].len() - index) % loops[lp].len();
+                        let mut first = vec![v1];
+                        first.extend_from_slice(&around[far + 1..]);
+                        let mut second = vec![v2];
+                        second.extend_from_slice(&around[1..=far]);
+                        loops[lp] = first;
+                        loops.push(second);
+                        let last = loops.len() - 1;
+                        self.settle(&mut loops, last, now);
+                        self.settle(&mut loops, lp, now);
+                    } else {
+                        let mut other = loops[target_lp].clone();
+                        let turn = (target + 1) % other.len();
+                        other.rotate_left(turn);
+                        let mut joined = vec![v1];
+                        joined.extend(other);
+                        joined.push(v2);
+                        joined.extend_from_slice(&around[1..]);
+                        loops[lp] = joined;
+                        loops.remove(target_lp);
+                        let lp = if target_lp < lp { lp - 1 } else { lp };
+                        self.settle(&mut loops, lp, now);
+                    }
+                }
+            }
+            loops.retain(|lp| !lp.is_empty());
+        }
+        for lp in loops.iter_mut() {
+            self.finish(core::mem::take(lp), now);
+        }
+    }
+
+    /// The earliest event at or after `now`.
+    fn next_event(&self, loops: &[Vec<Vertex<T>>], now: T) -> Option<(T, Event)> {
+        let mut best: Option<(T, Event)> = None;
+        let mut consider = |time: T, event: Event| {
+            let time = time.max(now);
+            if best.as_ref().is_none_or(|(best, _)| time < *best) {
+                best = Some((time, event));
+            }
+        };
+        for (lp, vertices) in loops.iter().enumerate() {
+            let n = vertices.len();
+            for index in 0..n {
+                let (u, w) = (&vertices[index], &vertices[(index + 1) % n]);
+                let (pu, pw) = (self.position(u, now), self.position(w, now));
+                let along = self.direction(u.right);
+                let length = dot(along, pw - pu);
+                let rate = dot(along, w.velocity - u.velocity);
+                if rate < T::zero() {
+                    consider(now - length / rate, Event::Edge { lp, index });
+                }
+            }
+        }
+        for (lp, vertices) in loops.iter().enumerate() {
+            for (index, v) in vertices.iter().enumerate() {
+                if cross(self.direction(v.left), self.direction(v.right)).z >= T::zero() {
+                    continue;
+                }
+                let position = self.position(v, now);
+                for (target_lp, targets) in loops.iter().enumerate() {
+                    let n = targets.len();
+                    for target in 0..n {
+                        let (a, b) = (&targets[target], &targets[(target + 1) % n]);
+                        let edge = &self.edges[a.right];
+                        if a.right == v.left || a.right == v.right {
+                            continue;
+                        }
+                        // how far the vertex is ahead of the edge, and how fast it
+                        // is closing in
+                        let ahead = dot(edge.normal, position) - edge.offset - now;
+                        let rate = dot(edge.normal, v.velocity) - T::one();
+                        if ahead < -self.eps || rate >= T::zero() {
+                            continue;
+                        }
+                        let time = now - ahead.max(T::zero()) / rate;
+                        let hit = self.position(v, time);
+                        let along = self.direction(a.right);
+                        let (start, end) = (
+                            dot(along, self.position(a, time)),
+                            dot(along, self.position(b, time)),
+                        );
+                        let at = dot(along, hit);
+                        if at >= start - self.eps && at <= end + self.eps {
+                            consider(
+                                time,
+                                Event::Split {
+                                    lp,
+                                    index,
+                                    target_lp,
+                                    target,
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// Gives the new vertices of a changed loop their velocities, or finishes
+    /// the loop if it has closed up.
+    ///
+    /// A new vertex between edges that have met head on has nowhere to go: the
+    /// edges now lie along one another, and are joined by a ridge from the vertex
+    /// as far as the nearer of its neighbours, where the rest of the further edge
+    /// carries on.
+    fn settle(&mut self, loops: &mut [Vec<Vertex<T>>], lp: usize, now: T) {
+        loop {
+            let vertices = &mut loops[lp];
+            if vertices.len() <= 2 {
+                let vertices = core::mem::take(vertices);
+                self.finish(vertices, now);
+                return;
+            }
+            let Some(i) = vertices
+                .iter()
+                .position(|vertex| vertex.velocity == CoordZ::zero())
+            else {
+                return;
+            };
+            let vertex = vertices[i];
+            if let Some(velocity) = self.velocity(vertex.left, vertex.right) {
+                vertices[i].velocity = velocity;
+                continue;
+            }
+            let n = vertices.len();
+            let mut around = core::mem::take(vertices);
+            around.rotate_left(i);
+            let (next, previous) = (around[1], around[n - 1]);
+            let here = self.position(&vertex, now);
+            let (to_previous, to_next) = (
+                distance(self.position(&previous, now), here),
+                distance(self.position(&next, now), here),
+            );
+            let (ends, merged, rest) = if (to_previous - to_next).abs() <= self.eps {
+                let (left, right) = (previous.left, next.right);
+                (vec![previous, next], (left, right), &around[2..n - 1])
+            } else if to_previous < to_next {
+                (
+                    vec![previous],
+                    (previous.left, vertex.right),
+                    &around[1..n - 1],
+                )
+            } else {
+                (vec![next], (vertex.left, next.right), &around[2..])
+            };
+            let node = self.node(self.position(&ends[0], now), now);
+            for end in &ends {
+                self.trace(end, node);
+            }
+            self.trace(&vertex, node);
+            let mut unzipped = vec![Vertex {
+                origin: node,
+                velocity: CoordZ::zero(),
+                left: merged.0,
+                right: merged.1,
+            }];
+            unzipped.extend_from_slice(rest);
+            loops[lp] = unzipped;
+        }
+    }
+
+    /// Ends a loop that has shrunk to a line or a point, joining its vertices
+    /// where they are.
+    fn finish(&mut self, vertices: Vec<Vertex<T>>, now: T) {
+        let nodes: Vec<usize> = vertices
+            .iter()
+            .map(|vertex| {
+                let node = self.node(self.position(vertex, now), now);
+                self.trace(vertex, node);
+                node
+            })
+            .collect();
+        for (i, vertex) in vertices.iter().enumerate() {
+            let next = nodes[(i + 1) % nodes.len()];
+            if nodes[i] != next {
+                self.arcs.push((nodes[i], next, vertex.right));
+            }
+        }
+    }
+
+    /// Records the arc traced by a vertex on its way to `node`.
+    fn trace(&mut self, vertex: &Vertex<T>, node: usize) {
+        if vertex.origin != node {
+            self.arcs.push((vertex.origin, node, vertex.left));
+            self.arcs.push((vertex.origin, node, vertex.right));
+        }
+    }
+
+    /// The node at a point in XY, reusing any existing one there.
+    fn node(&mut self, at: CoordZ<T>, time: T) -> usize {
+        if let Some(index) = self
+            .nodes
+            .iter()
+            .position(|node| (node.x - at.x).hypot(node.y - at.y) <= self.eps)
+        {
+            return index;
+        }
+        self.nodes.push(CoordZ { z: time, ..at });
+        self.nodes.len() - 1
+    }
+
+    /// Where a vertex is in XY at `time`, with zero z.
+    fn position(&self, vertex: &Vertex<T>, time: T) -> CoordZ<T> {
+        let origin = self.nodes[vertex.origin];
+        let elapsed = time - origin.z;
+        CoordZ {
+            z: T::zero(),
+            ..origin
+        } + vertex.velocity * elapsed
+    }
+
+    /// The unit direction of an edge.
+    fn direction(&self, edge: usize) -> CoordZ<T> {
+        let normal = self.edges[edge].normal;
+        CoordZ {
+            x: normal.y,
+            y: -normal.x,
+            z: T::zero(),
+        }
+    }
+
+    /// The velocity keeping a vertex on both edges as they move inwards, or `None`
+    /// if they face one another.
+    fn velocity(&self, left: usize, right: usize) -> Option<CoordZ<T>> {
+        let (a, b) = (self.edges[left].normal, self.edges[right].normal);
+        let det = cross(a, b).z;
+        if det.abs() > T::epsilon().sqrt() {
+            Some(CoordZ {
+                x: (b.y - a.y) / det,
+                y: (a.x - b.x) / det,
+                z: T::zero(),
+            })
+        } else if dot(a, b) > T::zero() {
+            Some(a)
+        } else {
+            None
+        }
+    }
+
+    /// The arcs as line strings, without repeats.
+    fn arcs(&self, slope: T) -> Vec<LineStringZ<T>> {
+        let mut pairs: Vec<(usize, usize)> = self
+            .arcs
+            .iter()
+            .map(|&(a, b, _)| (a.min(b), a.max(b)))
+            .collect();
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+            .into_iter()
+            .map(|(a, b)| LineStringZ(vec![self.lift(a, slope), self.lift(b, slope)]))
+            .collect()
+    }
+
+    /// The face swept by each edge, as a polygon sloping up from it.
+    fn faces(&self, slope: T) -> Vec<PolygonZ<T>> {
+        self.edges
+            .iter()
+            .enumerate()
+            .filter_map(|(index, edge)| {
+                let mut bounds: Vec<(usize, usize)> = self
+                    .arcs
+                    .iter()
+                    .filter(|&&(a, b, face)| face == index && a != b)
+                    .map(|&(a, b, _)| (a, b))
+                    .collect();
+                let mut ring = vec![edge.start, edge.end];
+                let mut current = edge.end;
+                while let Some(next) = bounds
+                    .iter()
+                    .position(|&(a, b)| a == current || b == current)
+                {
+                    let (a, b) = bounds.swap_remove(next);
+                    current = if a == current { b } else { a };
+                    if current == edge.start {
+                        break;
+                    }
+                    ring.push(current);
+                }
+                ring.push(edge.start);
+                (ring.len() >= 4).then(|| {
+                    PolygonZ::new(
+                        LineStringZ(
+                            ring.into_iter()
+                                .map(|node| self.lift(node, slope))
+                                .collect(),
+                        ),
+                        Vec::new(),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn lift(&self, node: usize, slope: T) -> CoordZ<T> {
+        let node = self.nodes[node];
+        CoordZ {
+            x: node.x,
+            y: node.y,
+            z: self.base + node.z * slope,
+        }
+    }
+}
+
+/// A ring's vertices in XY, with zero z, without repeats or the closing
+/// coordinate, turning anticlockwise if it is an exterior, or `None` if it has no
+/// area.
+fn ring<T: CoordFloat>(ring: &LineStringZ<T>, exterior: bool) -> Option<Vec<CoordZ<T>>> {
+    let mut coords: Vec<CoordZ<T>> = Vec::with_capacity(ring.0.len());
+    for &coord in &ring.0 {
+        let coord = CoordZ {
+            z: T::zero(),
+            ..coord
+        };
+        if coords.last() != Some(&coord) {
+            coords.push(coord);
+        }
+    }
+    while coords.len() > 1 && coords.first() == coords.last() {
+        coords.pop();
+    }
+    let area = (0..coords.len()).fold(T::zero(), |sum, i| {
+        sum + cross(coords[i], coords[(i + 1) % coords.len()]).z
+    });
+    if coords.len() < 3 || area == T::zero() {
+        return None;
+    }
+    if (area > T::zero()) != exterior {
+        coords.reverse();
+    }
+    Some(coords)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Checks that every vertex of each face rises at `slope` from the face's
+    /// edge, and that the faces cover `area` in XY.
+    fn check(roof: &MultiPolygonZ, base: f64, slope: f64, area: f64) {
+        let mut total = 0.;
+        for face in &roof.0 {
+            let coords = &face.exterior().0;
+            let (a, b) = (coords[0], coords[1]);
+            let length = (b.x - a.x).hypot(b.y - a.y);
+            for c in coords {
+                let distance = ((b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)) / length;
+                assert_relative_eq!(c.z, base + distance * slope, epsilon = 1e-9);
+            }
+            total += coords
+                .windows(2)
+                .map(|pair| pair[0].x * pair[1].y - pair[1].x * pair[0].y)
+                .sum::<f64>()
+                / 2.;
+        }
+        assert_relative_eq!(total, area, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn convex() {
+        let house: PolygonZ = wkt! { POLYGON Z((0. 0. 3.,4. 0. 3.,4. 2. 3.,0. 2. 3.,0. 0. 3.)) };
+        let roof = house.generate_hipped_roof(45.);
+        assert_eq!(roof.0.len(), 4);
+        check(&roof, 3., 1., 8.);
+        // the ridge, running between the hips
+        let ridge = house.straight_skeleton().0[4].clone();
+        assert_eq!(ridge, LineStringZ::from(vec![(3., 1., 4.), (1., 1., 4.)]));
+
+        // a pyramid, clockwise, on a steeper pitch
+        let tower: PolygonZ = wkt! { POLYGON Z((0. 0. 0.,0. 2. 0.,2. 2. 0.,2. 0. 0.,0. 0. 0.)) };
+        let roof = tower.generate_hipped_roof(60.);
+        assert_eq!(roof.0.len(), 4);
+        check(&roof, 0., 3f64.sqrt(), 4.);
+        assert_eq!(tower.straight_skeleton().0.len(), 4);
+
+        let flat: PolygonZ = wkt! { POLYGON Z((0. 0. 0.,1. 1. 0.,2. 2. 0.,0. 0. 0.)) };
+        assert!(flat.generate_hipped_roof(30.).0.is_empty());
+    }
+
+    #[test]
+    fn reflex() {
+        // an L, whose inner corner splits the outline
+        let l: PolygonZ = wkt! {
+            POLYGON Z((0. 0. 2.,6. 0. 2.,6. 2. 2.,2. 2. 2.,2. 6. 2.,0. 6. 2.,0. 0. 2.))
+        };
+        let roof = l.generate_hipped_roof(30.);
+        assert_eq!(roof.0.len(), 6);
+        check(&roof, 2., 30f64.to_radians().tan(), 20.);
+
+        // a T, and a U whose arms are split off one after the other
+        let t: PolygonZ = wkt! {
+            POLYGON Z((0. 4. 0.,3. 4. 0.,3. 0. 0.,5. 0. 0.,5. 4. 0.,8. 4. 0.,8. 6. 0.,0. 6. 0.,0. 4. 0.))
+        };
+        check(&t.generate_hipped_roof(45.), 0., 1., 24.);
+        let u: PolygonZ = wkt! {
+            POLYGON Z((0. 0. 0.,7. 0. 0.,7. 5. 0.,5. 5. 0.,5. 2. 0.,2. 2. 0.,2. 5. 0.,0. 5. 0.,0. 0. 0.))
+        };
+        check(&u.generate_hipped_roof(45.), 0., 1., 26.);
+
+        // a comb, whose teeth collapse into ridges as they are split off
+        let comb: PolygonZ = wkt! {
+            POLYGON Z((0. 0. 0.,10. 0. 0.,10. 5. 0.,9. 5. 0.,9. 2. 0.,7. 2. 0.,7. 5. 0.,6. 5. 0.,
+                6. 2. 0.,3. 2. 0.,3. 6. 0.,2. 6. 0.,2. 2. 0.,1. 2. 0.,1. 5. 0.,0. 5. 0.,0. 0. 0.))
+        };
+        check(&comb.generate_hipped_roof(45.), 0., 1., 33.);
+    }
+
+    #[test]
+    fn courtyard() {
+        let block: MultiPolygonZ = wkt! {
+            MULTIPOLYGON Z(
+                ((0. 0. 5.,10. 0. 5.,10. 10. 5.,0. 10. 5.,0. 0. 5.),(3. 3. 5.,7. 3. 5.,7. 7. 5.,3. 7. 5.,3. 3. 5.)),
+                ((20. 0. 5.,22. 0. 5.,22. 2. 5.,20. 2. 5.,20. 0. 5.))
+            )
+        };
+        let roof = block.generate_hipped_roof(45.);
+        assert_eq!(roof.0.len(), 12);
+        check(&roof, 5., 1., 88.);
+        assert!(roof
+            .0
+            .iter()
+            .flat_map(|face| face.exterior().coords())
+            .all(|c| c.z <= 6.5 + 1e-9));
+
+        // two wings of different widths around two holes
+        let holes: PolygonZ = wkt! {
+            POLYGON Z(
+                (0. 0. 0.,12. 0. 0.,12. 8. 0.,0. 8. 0.,0. 0. 0.),
+                (2. 2. 0.,4. 2. 0.,4. 6. 0.,2. 6. 0.,2. 2. 0.),
+                (7. 3. 0.,10. 3. 0.,9. 5. 0.,7. 3. 0.)
+            )
+        };
+        check(&holes.generate_hipped_roof(45.), 0., 1., 85.);
+    }
+}
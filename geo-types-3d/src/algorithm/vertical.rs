@@ -0,0 +1,340 @@
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+use crate::algorithm::{BoundingCube, MapCoordsZ};
+use crate::{CoordFloat, CoordNum, CoordZ, Cube, Error};
+
+/// Stretch, shift and rescale the heights of a geometry, leaving x and y alone,
+/// such as to show terrain or buildings in a 3D view.
+///
+/// Planar members of a [`Geometry`](crate::Geometry) are left as they are.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::VerticalOps;
+/// use geo_types_3d::{line_string_z, LineStringZ};
+///
+/// // a valley profile with heights above sea level, over a few kilometres
+/// let profile: LineStringZ = line_string_z![
+///     (x: 0., y: 0., z: 420.),
+///     (x: 1500., y: 0., z: 380.),
+///     (x: 3000., y: 0., z: 460.),
+/// ];
+/// // heights above the valley floor, exaggerated ten times
+/// let shown = profile.shift_z(-380.).exaggerate_z(10.);
+/// assert_eq!(shown.0[0].z, 400.);
+/// assert_eq!(shown.0[2].z, 800.);
+///
+/// let unit = profile.normalize_z_to(0. ..=1.);
+/// assert_eq!(unit.0[0].z, 0.5);
+/// assert_eq!((unit.0[1].z, unit.0[2].z), (0., 1.));
+/// ```
+pub trait VerticalOps<T: CoordNum> {
+    /// Multiplies every z by `factor`, returning a new geometry.
+    #[must_use]
+    fn exaggerate_z(&self, factor: T) -> Self;
+
+    /// Adds `offset` to every z, returning a new geometry, such as to move heights
+    /// from one vertical datum to another.
+    #[must_use]
+    fn shift_z(&self, offset: T) -> Self;
+
+    /// Rescales z linearly so that the lowest and highest z of the geometry span
+    /// `range`, returning a new geometry.
+    ///
+    /// A geometry that is level is moved to the start of `range`, and an empty one
+    /// is returned unchanged. Planar members of a [`Geometry`](crate::Geometry)
+    /// count as lying at z = 0, as for [`BoundingCube`].
+    #[must_use]
+    fn normalize_z_to(&self, range: RangeInclusive<T>) -> Self
+    where
+        T: CoordFloat,
+        Self: BoundingCube<T>;
+
+    /// Converts heights above the ellipsoid, as measured by GNSS, into heights
+    /// above the geoid of `datum`, as on maps, returning a new geometry.
+    ///
+    /// The coordinates must be longitudes and latitudes in degrees.
+    #[must_use]
+    fn to_orthometric(&self, datum: &impl VerticalDatum<T>) -> Self
+    where
+        T: CoordFloat;
+
+    /// Converts heights above the geoid of `datum` into heights above the
+    /// ellipsoid, returning a new geometry. The inverse of
+    /// [`to_orthometric`](VerticalOps::to_orthometric).
+    #[must_use]
+    fn to_ellipsoidal(&self, datum: &impl VerticalDatum<T>) -> Self
+    where
+        T: CoordFloat;
+}
+
+impl<T: CoordNum, G: MapCoordsZ<T, T, Output = G>> VerticalOps<T> for G {
+    fn exaggerate_z(&self, factor: T) -> Self {
+        self.map_coords(|coord| CoordZ {
+            z: coord.z * factor,
+            ..coord
+        })
+    }
+
+    fn shift_z(&self, offset: T) -> Self {
+        self.map_coords(|coord| CoordZ {
+            z: coord.z + offset,
+            ..coord
+        })
+    }
+
+    fn normalize_z_to(&self, range: RangeInclusive<T>) -> Self
+    where
+        T: CoordFloat,
+        Self: BoundingCube<T>,
+    {
+        let bounds: Option<Cube<T>> = self.bounding_cube().into();
+        let Some(bounds) = bounds else {
+            return self.map_coords(|coord| coord);
+        };
+        let (low, high) = (bounds.min().z, bounds.max().z);
+        let (start, end) = range.into_inner();
+        let scale = if high > low {
+            (end - start) / (high - low)
+        } else {
+            T::zero()
+        };
+        self.map_coords(|coord| CoordZ {
+            z: start + (coord.z - low) * scale,
+            ..coord
+        })
+    }
+
+    fn to_orthometric(&self, datum: &impl VerticalDatum<T>) -> Self
+    where
+        T: CoordFloat,
+    {
+        self.map_coords(|coord| CoordZ {
+            z: coord.z - datum.geoid_height(coord.x, coord.y),
+            ..coord
+        })
+    }
+
+    fn to_ellipsoidal(&self, datum: &impl VerticalDatum<T>) -> Self
+    where
+        T: CoordFloat,
+    {
+        self.map_coords(|coord| CoordZ {
+            z: coord.z + datum.geoid_height(coord.x, coord.y),
+            ..coord
+        })
+    }
+}
+
+/// A geoid model, giving the height of the geoid above the ellipsoid, to convert
+/// between ellipsoidal and orthometric heights with
+/// [`VerticalOps::to_orthometric`] and [`VerticalOps::to_ellipsoidal`].
+///
+/// Heights from GNSS are above the ellipsoid, while those on maps and in most
+/// elevation models are above the geoid, tens of metres apart. Implement this
+/// for a model of your own, use a [`GeoidGrid`], such as of EGM96, or pass a
+/// closure of the longitude and latitude.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::VerticalOps;
+/// use geo_types_3d::PointZ;
+///
+/// // around Amsterdam the geoid lies about 42 m above the ellipsoid
+/// let geoid = |_lon: f64, _lat: f64| 42.;
+/// let fix = PointZ::new(4.9, 52.37, 45.);
+/// assert_eq!(fix.to_orthometric(&geoid).z(), 3.);
+/// assert_eq!(fix.to_orthometric(&geoid).to_ellipsoidal(&geoid), fix);
+/// ```
+pub trait VerticalDatum<T: CoordFloat> {
+    /// The height of the geoid above the ellipsoid at a longitude and latitude,
+    /// in degrees.
+    fn geoid_height(&self, lon: T, lat: T) -> T;
+}
+
+impl<T: CoordFloat, F: Fn(T, T) -> T> VerticalDatum<T> for F {
+    fn geoid_height(&self, lon: T, lat: T) -> T {
+        self(lon, lat)
+    }
+}
+
+/// A geoid model given by its heights on a regular grid of longitudes and
+/// latitudes, interpolated bilinearly between them.
+///
+/// Beyond the grid, the heights at its edges are used. A grid going all the way
+/// round in longitude, such as the 15' grid of EGM96 with 1440 columns, wraps
+/// around the antimeridian.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoidGrid<T: CoordFloat = f64> {
+    west: T,
+    south: T,
+    spacing: T,
+    columns: usize,
+    heights: Vec<T>,
+}
+
+impl<T: CoordFloat> GeoidGrid<T> {
+    /// Creates a grid whose south-west corner is at `west`, `south`, with `spacing`
+    /// degrees between rows and between columns, from the `heights` of its rows
+    /// from south to north, each of `columns` heights from west to east.
+    ///
+    /// Fails if `heights` isn't `rows` rows of `columns` heights, or is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::algorithm::{GeoidGrid, VerticalDatum};
+    ///
+    /// let grid = GeoidGrid::try_new(4., 52., 1., 2, 2, vec![40., 42., 44., 46.]).unwrap();
+    /// assert_eq!(grid.geoid_height(4.5, 52.5), 43.);
+    /// assert_eq!(grid.geoid_height(0., 60.), 44.);
+    /// ```
+    pub fn try_new(
+        west: T,
+        south: T,
+        spacing: T,
+        columns: usize,
+        rows: usize,
+        heights: Vec<T>,
+    ) -> Result<Self, Error> {
+        let expected = columns * rows;
+        if expected == 0 || heights.len() != expected {
+            return Err(Error::MismatchedLength {
+                expected: expected.max(1),
+                found: heights.len(),
+            });
+        }
+        Ok(Self {
+            west,
+            south,
+            spacing,
+            columns,
+            heights,
+        })
+    }
+
+    fn rows(&self) -> usize {
+        self.heights.len() / self.columns
+    }
+}
+
+impl<T: CoordFloat> VerticalDatum<T> for GeoidGrid<T> {
+    fn geoid_height(&self, lon: T, lat: T) -> T {
+        let full_turn = T::from(360).unwrap();
+        let columns = T::from(self.columns).unwrap();
+        let last = |count: usize| T::from(count - 1).unwrap();
+        // the position in the grid, in cells, and the index of the cell
+        let cell = |position: T| position.floor().to_usize().unwrap_or(0);
+        let (x, west, east) = if columns * self.spacing >= full_turn {
+            let offset = lon - self.west;
+            let x = (offset - (offset / full_turn).floor() * full_turn) / self.spacing;
+            let west = cell(x) % self.columns;
+            (x, west, (west + 1) % self.columns)
+        } else {
+            let x = ((lon - self.west) / self.spacing)
+                .max(T::zero())
+                .min(last(self.columns));
+            let west = cell(x);
+            (x, west, (west + 1).min(self.columns - 1))
+        };
+        let y = ((lat - self.south) / self.spacing)
+            .max(T::zero())
+            .min(last(self.rows()));
+        let south = cell(y);
+        let north = (south + 1).min(self.rows() - 1);
+        let (fx, fy) = (x - x.floor(), y - y.floor());
+        let height = |row: usize, column: usize| self.heights[row * self.columns + column];
+        let lerp = |a: T, b: T, t: T| a + (b - a) * t;
+        lerp(
+            lerp(height(south, west), height(south, east), fx),
+            lerp(height(north, west), height(north, east), fx),
+            fy,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Geometry, GeometryCollection, MultiPointZ, PointZ, PolygonZ};
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn collections_and_integers() {
+        let roof: PolygonZ = polygon_z![
+            (x: 0., y: 0., z: 10.),
+            (x: 4., y: 0., z: 10.),
+            (x: 4., y: 4., z: 14.),
+        ];
+        let scene = Geometry::GeometryCollection(GeometryCollection(vec![
+            roof.into(),
+            PointZ::new(1., 1., 2.).into(),
+            Geometry::Point(geo_types::Point::new(5., 5.)),
+        ]));
+        // the planar point counts as lying at z = 0
+        let Geometry::GeometryCollection(normalized) = scene.normalize_z_to(0. ..=7.) else {
+            unreachable!()
+        };
+        let Geometry::PolygonZ(roof) = &normalized.0[0] else {
+            unreachable!()
+        };
+        assert_eq!(
+            roof.exterior().0.iter().map(|c| c.z).collect::<Vec<_>>(),
+            [5., 5., 7., 5.]
+        );
+        assert_eq!(normalized.0[1], PointZ::new(1., 1., 1.).into());
+        assert_eq!(
+            normalized.0[2],
+            Geometry::Point(geo_types::Point::new(5., 5.))
+        );
+
+        // level geometries move to the start of the range
+        let level = PointZ::new(3., 4., 5.);
+        assert_eq!(level.normalize_z_to(-1. ..=1.), PointZ::new(3., 4., -1.));
+
+        // shifts and exaggerations work on integer grids too
+        let cell = PointZ::new(3, 4, 5);
+        assert_eq!(cell.shift_z(-5).exaggerate_z(2), PointZ::new(3, 4, 0));
+        assert_eq!(cell.exaggerate_z(3).z(), 15);
+    }
+
+    #[test]
+    fn geoid_grid() {
+        // a global grid of 90 degrees, wrapping around the antimeridian
+        let heights = vec![
+            0., 10., 20., 30., // at 45 S
+            40., 50., 60., 70., // at 45 N
+        ];
+        let grid = GeoidGrid::try_new(-180., -45., 90., 4, 2, heights).unwrap();
+        assert_eq!(grid.geoid_height(-180., -45.), 0.);
+        assert_eq!(grid.geoid_height(45., 0.), 45.);
+        // halfway from 90 E across the antimeridian back to 180 W
+        assert_eq!(grid.geoid_height(135., -45.), 15.);
+        assert_eq!(grid.geoid_height(-225., -45.), 15.);
+        // beyond the last row
+        assert_eq!(grid.geoid_height(-90., 80.), 50.);
+
+        let fixes: MultiPointZ = vec![(-180., -45., 100.), (45., 0., 100.)].into();
+        let heights: Vec<f64> = fixes.to_orthometric(&grid).iter().map(|p| p.z()).collect();
+        assert_eq!(heights, [100., 55.]);
+
+        // a regional grid is clamped at its edges
+        let regional = GeoidGrid::try_new(0., 0., 1., 2, 1, vec![1., 3.]).unwrap();
+        assert_eq!(regional.geoid_height(-5., 0.), 1.);
+        assert_eq!(regional.geoid_height(0.25, 7.), 1.5);
+        assert_eq!(regional.geoid_height(9., 0.), 3.);
+
+        assert!(matches!(
+            GeoidGrid::try_new(0., 0., 1., 2, 2, vec![1., 3., 5.]),
+            Err(Error::MismatchedLength {
+                expected: 4,
+                found: 3
+            })
+        ));
+        assert!(GeoidGrid::<f64>::try_new(0., 0., 1., 0, 2, vec![]).is_err());
+    }
+}
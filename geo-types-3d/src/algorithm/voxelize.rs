@@ -0,0 +1,529 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algorithm::linalg::newell_normal;
+use crate::algorithm::BoundingCube;
+use crate::{CoordFloat, CoordZ, Cube, LineStringZ, MultiPointZ, MultiPolygonZ, PolygonZ};
+
+/// The integer coordinates of a cell of an [`OccupancyGrid`], counted from its origin.
+pub type Cell = [usize; 3];
+
+/// Convert a geometry into an [`OccupancyGrid`] of cubic cells.
+///
+/// Points occupy the cells they fall in. A [`MultiPolygonZ`] is taken to be the
+/// closed boundary of a solid, and occupies the cells whose centres lie inside it,
+/// so faces should meet edge to edge with no gaps. A [`Cube`] occupies the cells
+/// whose centres lie inside it.
+///
+/// The grid starts at the minimum corner of the geometry's bounding cube and
+/// extends far enough to cover it.
+///
+/// # Panics
+///
+/// If `cell_size` isn't positive and finite.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::Voxelize;
+/// use geo_types_3d::Cube;
+///
+/// let block = Cube::new((0., 0., 0.), (4., 2., 1.));
+/// let grid = block.voxelize(0.5);
+/// assert_eq!(grid.dims(), [9, 5, 3]);
+/// assert_eq!(grid.volume(), 8.);
+///
+/// // marching cubes smooths the corners off the voxels
+/// let mesh = grid.to_mesh();
+/// assert!(mesh.0.iter().all(|triangle| triangle.exterior().0.len() == 4));
+/// ```
+pub trait Voxelize<T: CoordFloat> {
+    fn voxelize(&self, cell_size: T) -> OccupancyGrid<T>;
+}
+
+/// A box of cubic cells, each either occupied or empty, such as the output of
+/// [`Voxelize`].
+///
+/// Cell `[i, j, k]` spans from `origin + [i, j, k] * cell_size` to one cell size
+/// further along each axis. The occupancy is stored one bit per cell.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OccupancyGrid<T: CoordFloat = f64> {
+    origin: CoordZ<T>,
+    cell_size: T,
+    dims: [usize; 3],
+    bits: Vec<u64>,
+}
+
+impl<T: CoordFloat> OccupancyGrid<T> {
+    /// Creates a grid of `dims` empty cells.
+    ///
+    /// # Panics
+    ///
+    /// If `cell_size` isn't positive and finite.
+    pub fn new(origin: CoordZ<T>, cell_size: T, dims: [usize; 3]) -> Self {
+        assert!(
+            cell_size > T::zero() && cell_size.is_finite(),
+            "cell size must be positive and finite"
+        );
+        let cells = dims[0] * dims[1] * dims[2];
+        Self {
+            origin,
+            cell_size,
+            dims,
+            bits: vec![0; cells.div_ceil(64)],
+        }
+    }
+
+    /// Creates a grid large enough to hold `cube`, or an empty one for `None`.
+    fn covering(cube: Option<Cube<T>>, cell_size: T) -> Self {
+        let Some(cube) = cube else {
+            return Self::new(CoordZ::zero(), cell_size, [0; 3]);
+        };
+        let extent = cube.max() - cube.min();
+        let count = |length: T| (length / cell_size).floor().to_usize().unwrap_or(0) + 1;
+        let dims = [count(extent.x), count(extent.y), count(extent.z)];
+        Self::new(cube.min(), cell_size, dims)
+    }
+
+    pub fn origin(&self) -> CoordZ<T> {
+        self.origin
+    }
+
+    pub fn cell_size(&self) -> T {
+        self.cell_size
+    }
+
+    /// The number of cells along each axis.
+    pub fn dims(&self) -> [usize; 3] {
+        self.dims
+    }
+
+    /// The number of occupied cells.
+    pub fn occupied(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// The total volume of the occupied cells.
+    pub fn volume(&self) -> T {
+        let cell_size = self.cell_size;
+        T::from(self.occupied()).unwrap() * cell_size * cell_size * cell_size
+    }
+
+    fn index(&self, [i, j, k]: Cell) -> Option<usize> {
+        let [nx, ny, nz] = self.dims;
+        (i < nx && j < ny && k < nz).then(|| i + nx * (j + ny * k))
+    }
+
+    /// Whether `cell` is occupied. Cells outside the grid are empty.
+    pub fn is_occupied(&self, cell: Cell) -> bool {
+        self.index(cell)
+            .is_some_and(|index| self.bits[index / 64] & (1 << (index % 64)) != 0)
+    }
+
+    /// Marks `cell` as occupied or empty.
+    ///
+    /// # Panics
+    ///
+    /// If `cell` lies outside the grid.
+    pub fn set(&mut self, cell: Cell, occupied: bool) {
+        let index = self.index(cell).expect("cell lies outside the grid");
+        if occupied {
+            self.bits[index / 64] |= 1 << (index % 64);
+        } else {
+            self.bits[index / 64] &= !(1 << (index % 64));
+        }
+    }
+
+    /// The cell containing `coord`, if it lies within the grid.
+    pub fn cell_at(&self, coord: CoordZ<T>) -> Option<Cell> {
+        let offset = (coord - self.origin) / self.cell_size;
+        let index = |value: T| {
+            if value >= T::zero() {
+                value.floor().to_usize()
+            } else {
+                None
+            }
+        };
+        let cell = [index(offset.x)?, index(offset.y)?, index(offset.z)?];
+        self.index(cell).map(|_| cell)
+    }
+
+    /// The centre of `cell`.
+    pub fn cell_centre(&self, [i, j, k]: Cell) -> CoordZ<T> {
+        let half = T::from(0.5).unwrap();
+        let at = |index: usize| (T::from(index).unwrap() + half) * self.cell_size;
+        self.origin
+            + CoordZ {
+                x: at(i),
+                y: at(j),
+                z: at(k),
+            }
+    }
+
+    /// The space `cell` takes up.
+    pub fn cell_bounds(&self, cell: Cell) -> Cube<T> {
+        let half = self.cell_size / (T::one() + T::one());
+        let offset = CoordZ {
+            x: half,
+            y: half,
+            z: half,
+        };
+        let centre = self.cell_centre(cell);
+        Cube::new(centre - offset, centre + offset)
+    }
+
+    /// Iterates over the occupied cells, x varying fastest, then y, then z.
+    pub fn occupied_cells(&self) -> impl Iterator<Item = Cell> + '_ {
+        let [nx, ny, _] = self.dims;
+        self.bits.iter().enumerate().flat_map(move |(word, &bits)| {
+            (0..64)
+                .filter(move |bit| bits & (1 << bit) != 0)
+                .map(move |bit| {
+                    let index = word * 64 + bit;
+                    [index % nx, index / nx % ny, index / (nx * ny)]
+                })
+        })
+    }
+
+    /// Extracts the surface of the occupied cells with marching cubes, as triangles
+    /// facing out of the solid.
+    ///
+    /// The surface passes halfway between the centres of occupied cells and their
+    /// empty neighbours, with the grid surrounded by empty cells, so it is closed.
+    /// Where two occupied cells meet only at an edge or a corner, the surface
+    /// keeps them apart.
+    pub fn to_mesh(&self) -> MultiPolygonZ<T> {
+        // the corners of a cube of cell centres are numbered with x as bit 0, y as
+        // bit 1 and z as bit 2; each face lists its corners anticlockwise as seen
+        // from outside the cube
+        const FACES: [[usize; 4]; 6] = [
+            [0, 2, 3, 1],
+            [4, 5, 7, 6],
+            [0, 4, 6, 2],
+            [1, 3, 7, 5],
+            [0, 1, 5, 4],
+            [2, 6, 7, 3],
+        ];
+        let occupied = |i: isize, j: isize, k: isize| {
+            i >= 0 && j >= 0 && k >= 0 && self.is_occupied([i as usize, j as usize, k as usize])
+        };
+        let mut triangles = Vec::new();
+        let [nx, ny, nz] = self.dims.map(|n| n as isize);
+        for k in -1..nz {
+            for j in -1..ny {
+                for i in -1..nx {
+                    let inside: [bool; 8] = core::array::from_fn(|corner| {
+                        occupied(
+                            i + (corner & 1) as isize,
+                            j + ((corner >> 1) & 1) as isize,
+                            k + ((corner >> 2) & 1) as isize,
+                        )
+                    });
+                    if inside.iter().all(|&b| b) || !inside.iter().any(|&b| b) {
+                        continue;
+                    }
+
+                    // On each face, join every edge where the surface enters the
+                    // solid, going anticlockwise, to the next edge where it leaves.
+                    // Edges are (corner, corner) pairs.
+                    let mut next = [None; 64];
+                    for face in FACES {
+                        let mut crossings = Vec::with_capacity(4);
+                        for n in 0..4 {
+                            let (a, b) = (face[n], face[(n + 1) % 4]);
+                            if inside[a] != inside[b] {
+                                crossings.push((a.min(b) * 8 + a.max(b), inside[b]));
+                            }
+                        }
+                        for (n, &(edge, entering)) in crossings.iter().enumerate() {
+                            if entering {
+                                next[edge] = Some(crossings[(n + 1) % crossings.len()].0);
+                            }
+                        }
+                    }
+
+                    // chain the segments into loops around the cube
+                    let base = CoordZ {
+                        x: i as f64,
+                        y: j as f64,
+                        z: k as f64,
+                    };
+                    for start in 0..64 {
+                        let mut loop_coords = Vec::new();
+                        let mut edge = start;
+                        while let Some(to) = next[edge].take() {
+                            loop_coords.push(self.edge_midpoint(base, edge));
+                            edge = to;
+                        }
+                        fan(&loop_coords, &mut triangles);
+                    }
+                }
+            }
+        }
+        MultiPolygonZ(triangles)
+    }
+
+    /// The midpoint of an edge of the cube of cell centres whose lowest corner is
+    /// the centre of cell `base`.
+    fn edge_midpoint(&self, base: CoordZ<f64>, edge: usize) -> CoordZ<T> {
+        let (a, b) = (edge / 8, edge % 8);
+        let along = |bit: usize, base: f64| {
+            let offset = (((a >> bit) & 1) + ((b >> bit) & 1)) as f64 / 2.;
+            T::from(base + offset + 0.5).unwrap() * self.cell_size
+        };
+        self.origin
+            + CoordZ {
+                x: along(0, base.x),
+                y: along(1, base.y),
+                z: along(2, base.z),
+            }
+    }
+}
+
+/// Splits a loop into triangles, around its centroid where it has more than three
+/// corners.
+fn fan<T: CoordFloat>(coords: &[CoordZ<T>], triangles: &mut Vec<PolygonZ<T>>) {
+    let mut push = |a, b, c| triangles.push(PolygonZ::new(LineStringZ(vec![a, b, c]), vec![]));
+    match coords.len() {
+        0..=2 => {}
+        3 => push(coords[0], coords[1], coords[2]),
+        n => {
+            let sum = coords.iter().fold(CoordZ::zero(), |sum, &c| sum + c);
+            let centre = sum / T::from(n).unwrap();
+            for i in 0..n {
+                push(centre, coords[i], coords[(i + 1) % n]);
+            }
+        }
+    }
+}
+
+impl<T: CoordFloat> Voxelize<T> for MultiPointZ<T> {
+    fn voxelize(&self, cell_size: T) -> OccupancyGrid<T> {
+        let mut grid = OccupancyGrid::covering(self.bounding_cube(), cell_size);
+        for point in &self.0 {
+            if let Some(cell) = grid.cell_at(point.0) {
+                grid.set(cell, true);
+            }
+        }
+        grid
+    }
+}
+
+impl<T: CoordFloat> Voxelize<T> for Cube<T> {
+    fn voxelize(&self, cell_size: T) -> OccupancyGrid<T> {
+        let mut grid = OccupancyGrid::covering(Some(*self), cell_size);
+        let [nx, ny, nz] = grid.dims;
+        for k in 0..nz {
+            for j in 0..ny {
+                for i in 0..nx {
+                    if self.contains_coord(grid.cell_centre([i, j, k])) {
+                        grid.set([i, j, k], true);
+                    }
+                }
+            }
+        }
+        grid
+    }
+}
+
+impl<T: CoordFloat> Voxelize<T> for MultiPolygonZ<T> {
+    fn voxelize(&self, cell_size: T) -> OccupancyGrid<T> {
+        let mut grid = OccupancyGrid::covering(self.bounding_cube(), cell_size);
+        let [nx, ny, nz] = grid.dims;
+
+        // the heights at which a vertical line through the centre of each column of
+        // cells crosses the boundary
+        let mut crossings: Vec<Vec<T>> = vec![Vec::new(); nx * ny];
+        for polygon in &self.0 {
+            let exterior = &polygon.exterior().0;
+            let Some(&start) = exterior.first() else {
+                continue;
+            };
+            let normal = newell_normal(exterior);
+            let length = (normal.x * normal.x + normal.y * normal.y + normal.z * normal.z).sqrt();
+            if normal.z.abs() <= length * T::epsilon().sqrt() {
+                // vertical faces are never crossed by vertical lines
+                continue;
+            }
+            let Some(cube) = polygon.bounding_cube() else {
+                continue;
+            };
+            let (min, max) = (cube.min(), cube.max());
+            let half = T::from(0.5).unwrap();
+            let column = |value: T, origin: T, count: usize| {
+                let index = ((value - origin) / cell_size - half).ceil();
+                index
+                    .max(T::zero())
+                    .min(T::from(count).unwrap())
+                    .to_usize()
+                    .unwrap()
+            };
+            for j in column(min.y, grid.origin.y, ny)..column(max.y, grid.origin.y, ny) + 1 {
+                for i in column(min.x, grid.origin.x, nx)..column(max.x, grid.origin.x, nx) + 1 {
+                    if i >= nx || j >= ny {
+                        continue;
+                    }
+                    let centre = grid.cell_centre([i, j, 0]);
+                    let rings = core::iter::once(polygon.exterior()).chain(polygon.interiors());
+                    let inside = rings.fold(false, |inside, ring| {
+                        inside ^ crosses_ring(&ring.0, centre.x, centre.y)
+                    });
+                    if inside {
+                        let z = start.z
+                            - (normal.x * (centre.x - start.x) + normal.y * (centre.y - start.y))
+                                / normal.z;
+                        crossings[i + nx * j].push(z);
+                    }
+                }
+            }
+        }
+
+        for (column, heights) in crossings.iter_mut().enumerate() {
+            heights.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+            let (i, j) = (column % nx, column / nx);
+            for pair in heights.chunks_exact(2) {
+                for k in 0..nz {
+                    let z = grid.cell_centre([i, j, k]).z;
+                    if pair[0] <= z && z < pair[1] {
+                        grid.set([i, j, k], true);
+                    }
+                }
+            }
+        }
+        grid
+    }
+}
+
+/// Whether a ray from `(x, y)` towards +x crosses the XY projection of `ring` an odd
+/// number of times.
+fn crosses_ring<T: CoordFloat>(ring: &[CoordZ<T>], x: T, y: T) -> bool {
+    let mut inside = false;
+    for (i, a) in ring.iter().enumerate() {
+        let b = ring[(i + 1) % ring.len()];
+        if (a.y > y) != (b.y > y) && x < a.x + (y - a.y) * (b.x - a.x) / (b.y - a.y) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::PointZ;
+
+    /// Whether every edge of the triangles is shared with one other triangle,
+    /// running the opposite way.
+    fn is_closed(mesh: &MultiPolygonZ<f64>) -> bool {
+        let edges: Vec<_> = mesh
+            .0
+            .iter()
+            .flat_map(|triangle| triangle.exterior().lines())
+            .collect();
+        edges.iter().all(|edge| {
+            edges
+                .iter()
+                .filter(|other| other.start == edge.end && other.end == edge.start)
+                .count()
+                == 1
+        })
+    }
+
+    /// The volume enclosed by outward-facing triangles.
+    fn enclosed_volume(mesh: &MultiPolygonZ<f64>) -> f64 {
+        mesh.0
+            .iter()
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|i| triangle.exterior().0[i]);
+                (a.x * (b.y * c.z - b.z * c.y) - a.y * (b.x * c.z - b.z * c.x)
+                    + a.z * (b.x * c.y - b.y * c.x))
+                    / 6.
+            })
+            .sum()
+    }
+
+    #[test]
+    fn voxelize() {
+        let points = MultiPointZ(vec![
+            PointZ::new(0., 0., 0.),
+            PointZ::new(0.5, 0.2, 0.1),
+            PointZ::new(3., 1., 2.),
+        ]);
+        let grid = points.voxelize(1.);
+        assert_eq!(grid.dims(), [4, 2, 3]);
+        assert_eq!(
+            grid.occupied_cells().collect::<Vec<_>>(),
+            [[0, 0, 0], [3, 1, 2]]
+        );
+        assert!(MultiPointZ::<f64>(vec![])
+            .voxelize(1.)
+            .occupied_cells()
+            .next()
+            .is_none());
+
+        // a single voxel becomes an octahedron
+        let mut single = OccupancyGrid::new(CoordZ::zero(), 2., [1, 1, 1]);
+        single.set([0, 0, 0], true);
+        let mesh = single.to_mesh();
+        assert_eq!(mesh.0.len(), 8);
+        assert!(is_closed(&mesh));
+        assert_relative_eq!(enclosed_volume(&mesh), 4. / 3., epsilon = 1e-12);
+
+        // a box made of six faces, 4 × 2 × 2
+        let corner = |x: f64, y: f64, z: f64| CoordZ { x, y, z };
+        let face = |a, b, c, d| polygon_z![exterior: [a, b, c, d], interiors: []];
+        let (lo, hi) = (corner(0., 0., 0.), corner(4., 2., 2.));
+        let solid = MultiPolygonZ(vec![
+            face(
+                lo,
+                corner(0., 2., 0.),
+                corner(4., 2., 0.),
+                corner(4., 0., 0.),
+            ),
+            face(
+                corner(0., 0., 2.),
+                corner(4., 0., 2.),
+                hi,
+                corner(0., 2., 2.),
+            ),
+            face(
+                lo,
+                corner(4., 0., 0.),
+                corner(4., 0., 2.),
+                corner(0., 0., 2.),
+            ),
+            face(
+                corner(0., 2., 0.),
+                corner(0., 2., 2.),
+                hi,
+                corner(4., 2., 0.),
+            ),
+            face(
+                lo,
+                corner(0., 0., 2.),
+                corner(0., 2., 2.),
+                corner(0., 2., 0.),
+            ),
+            face(
+                corner(4., 0., 0.),
+                corner(4., 2., 0.),
+                hi,
+                corner(4., 0., 2.),
+            ),
+        ]);
+        let grid = solid.voxelize(0.5);
+        assert_eq!(grid.volume(), 16.);
+        assert!(grid.is_occupied([0, 0, 0]) && grid.is_occupied([7, 3, 3]));
+        assert!(!grid.is_occupied([8, 0, 0]));
+        assert_eq!(grid.cell_at(corner(3.9, 0.1, 1.9)), Some([7, 0, 3]));
+        assert_eq!(grid.cell_bounds([7, 0, 3]).max(), corner(4., 0.5, 2.));
+
+        let mesh = grid.to_mesh();
+        assert!(is_closed(&mesh));
+        let volume = enclosed_volume(&mesh);
+        assert!(volume > 10. && volume < 16.);
+    }
+}
@@ -0,0 +1,164 @@
+//! Helpers to run planar algorithms from [`geo`] on the XY projection of 3D geometries
+//! and to restore z afterwards.
+
+use core::cell::OnceCell;
+
+use geo_types::{Coord, LineString, MultiLineString, MultiPolygon, Polygon};
+
+use crate::index::Bvh;
+use crate::{
+    CoordFloat, CoordNum, CoordZ, LineStringZ, LineZ, MultiLineStringZ, MultiPolygonZ, PolygonZ,
+};
+
+pub(crate) fn coord_xy<T: CoordNum>(coord: CoordZ<T>) -> Coord<T> {
+    Coord {
+        x: coord.x,
+        y: coord.y,
+    }
+}
+
+pub(crate) fn line_string_xy<T: CoordNum>(line_string: &LineStringZ<T>) -> LineString<T> {
+    LineString(line_string.0.iter().copied().map(coord_xy).collect())
+}
+
+pub(crate) fn multi_line_string_xy<T: CoordNum>(
+    multi_line_string: &MultiLineStringZ<T>,
+) -> MultiLineString<T> {
+    MultiLineString(multi_line_string.0.iter().map(line_string_xy).collect())
+}
+
+pub(crate) fn polygon_xy<T: CoordNum>(polygon: &PolygonZ<T>) -> Polygon<T> {
+    Polygon::new(
+        line_string_xy(polygon.exterior()),
+        polygon.interiors().iter().map(line_string_xy).collect(),
+    )
+}
+
+pub(crate) fn multi_polygon_xy<T: CoordNum>(multi_polygon: &MultiPolygonZ<T>) -> MultiPolygon<T> {
+    MultiPolygon(multi_polygon.0.iter().map(polygon_xy).collect())
+}
+
+/// Lifts planar geometries back to 3D, taking z from a set of source segments.
+///
+/// Each coordinate gets the z of the closest point (in XY) on the closest source
+/// segment, linearly interpolated between the segment's end points. Beyond the end of
+/// a line, or around a corner, this is the z of the nearest source vertex.
+///
+/// The closest segment is found through a [`Bvh`] over the segments' XY
+/// projections, built on the first lookup after segments were added.
+#[derive(Debug, Clone)]
+pub(crate) struct NearestZ<T: CoordNum> {
+    segments: alloc::vec::Vec<(CoordZ<T>, CoordZ<T>)>,
+    index: OnceCell<Bvh<T, LineZ<T>>>,
+}
+
+impl<T: CoordFloat> NearestZ<T> {
+    pub fn new() -> Self {
+        Self {
+            segments: alloc::vec::Vec::new(),
+            index: OnceCell::new(),
+        }
+    }
+
+    /// Adds the segments of `line_string`. A single coordinate is added as a
+    /// degenerate segment.
+    pub fn add_line_string(&mut self, line_string: &LineStringZ<T>) {
+        self.index.take();
+        match line_string.0.as_slice() {
+            [] => {}
+            [coord] => self.segments.push((*coord, *coord)),
+            coords => self
+                .segments
+                .extend(coords.windows(2).map(|pair| (pair[0], pair[1]))),
+        }
+    }
+
+    pub fn add_polygon(&mut self, polygon: &PolygonZ<T>) {
+        self.add_line_string(polygon.exterior());
+        for interior in polygon.interiors() {
+            self.add_line_string(interior);
+        }
+    }
+
+    /// The interpolated z at `coord`, or zero if there are no source segments.
+    pub fn z_at(&self, coord: Coord<T>) -> T {
+        let index = self.index.get_or_init(|| {
+            let flat = |c: CoordZ<T>| CoordZ { z: T::zero(), ..c };
+            Bvh::new(
+                self.segments
+                    .iter()
+                    .map(|&(a, b)| LineZ::new(flat(a), flat(b)))
+                    .collect(),
+            )
+        });
+        let at = CoordZ {
+            x: coord.x,
+            y: coord.y,
+            z: T::zero(),
+        };
+        let nearest = index.nearest_with(at, |segment| {
+            let (dist2, _) = closest_point(coord, segment.start, segment.end);
+            Some(dist2.sqrt())
+        });
+        match nearest {
+            Some((id, _)) => {
+                let (a, b) = self.segments[id];
+                let (_, t) = closest_point(coord, a, b);
+                a.z + (b.z - a.z) * t
+            }
+            None => T::zero(),
+        }
+    }
+
+    pub fn lift_line_string(&self, line_string: &LineString<T>) -> LineStringZ<T> {
+        LineStringZ(
+            line_string
+                .0
+                .iter()
+                .map(|c| CoordZ {
+                    x: c.x,
+                    y: c.y,
+                    z: self.z_at(*c),
+                })
+                .collect(),
+        )
+    }
+
+    pub fn lift_polygon(&self, polygon: &Polygon<T>) -> PolygonZ<T> {
+        PolygonZ::new(
+            self.lift_line_string(polygon.exterior()),
+            polygon
+                .interiors()
+                .iter()
+                .map(|ring| self.lift_line_string(ring))
+                .collect(),
+        )
+    }
+
+    pub fn lift_multi_polygon(&self, multi_polygon: &MultiPolygon<T>) -> MultiPolygonZ<T> {
+        MultiPolygonZ(
+            multi_polygon
+                .0
+                .iter()
+                .map(|polygon| self.lift_polygon(polygon))
+                .collect(),
+        )
+    }
+}
+
+/// The squared XY distance from `coord` to the segment `a`–`b`, and the position
+/// of the closest point along the segment, from 0 at `a` to 1 at `b`.
+fn closest_point<T: CoordFloat>(coord: Coord<T>, a: CoordZ<T>, b: CoordZ<T>) -> (T, T) {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let len2 = dx * dx + dy * dy;
+    let t = if len2 > T::zero() {
+        (((coord.x - a.x) * dx + (coord.y - a.y) * dy) / len2)
+            .max(T::zero())
+            .min(T::one())
+    } else {
+        T::zero()
+    };
+    let (px, py) = (a.x + dx * t, a.y + dy * t);
+    let dist2 = (coord.x - px) * (coord.x - px) + (coord.y - py) * (coord.y - py);
+    (dist2, t)
+}
@@ -1,36 +1,53 @@
 use crate::{
-    Coord, CoordFloat, Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint,
-    MultiPolygon, Point, Polygon, Rect, Triangle,
+    CoordFloat, CoordZ, Cube, Geometry, GeometryCollection, LineStringZ, LineZ, MultiLineStringZ,
+    MultiPointZ, MultiPolygonZ, PointZ, PolygonZ, Triangle,
 };
 use std::mem;
 
-impl<'a, T> arbitrary::Arbitrary<'a> for Coord<T>
+impl<'a, T> arbitrary::Arbitrary<'a> for CoordZ<T>
 where
     T: arbitrary::Arbitrary<'a> + CoordFloat,
 {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        Ok(coord! {
+        Ok(coordZ! {
             x: u.arbitrary::<T>()?,
             y: u.arbitrary::<T>()?,
+            z: u.arbitrary::<T>()?,
         })
     }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (mem::size_of::<T>() * 3, Some(mem::size_of::<T>() * 3))
+    }
+}
+
+impl<'a, T> arbitrary::Arbitrary<'a> for PointZ<T>
+where
+    T: arbitrary::Arbitrary<'a> + CoordFloat,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary::<CoordZ<T>>().map(Self)
+    }
 }
 
-impl<'a, T> arbitrary::Arbitrary<'a> for Point<T>
+impl<'a, T> arbitrary::Arbitrary<'a> for LineZ<T>
 where
     T: arbitrary::Arbitrary<'a> + CoordFloat,
 {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        u.arbitrary::<Coord<T>>().map(Self)
+        Ok(Self::new(
+            u.arbitrary::<CoordZ<T>>()?,
+            u.arbitrary::<CoordZ<T>>()?,
+        ))
     }
 }
 
-impl<'a, T> arbitrary::Arbitrary<'a> for LineString<T>
+impl<'a, T> arbitrary::Arbitrary<'a> for LineStringZ<T>
 where
     T: arbitrary::Arbitrary<'a> + CoordFloat,
 {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        let coords = u.arbitrary::<Vec<Coord<T>>>()?;
+        let coords = u.arbitrary::<Vec<CoordZ<T>>>()?;
         if coords.len() < 2 {
             Err(arbitrary::Error::IncorrectFormat)
         } else {
@@ -39,46 +56,46 @@ where
     }
 
     fn size_hint(_depth: usize) -> (usize, Option<usize>) {
-        (mem::size_of::<T>() * 2, None)
+        (mem::size_of::<T>() * 3 * 2, None)
     }
 }
 
-impl<'a, T> arbitrary::Arbitrary<'a> for Polygon<T>
+impl<'a, T> arbitrary::Arbitrary<'a> for PolygonZ<T>
 where
     T: arbitrary::Arbitrary<'a> + CoordFloat,
 {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
         Ok(Self::new(
-            u.arbitrary::<LineString<T>>()?,
-            u.arbitrary::<Vec<LineString<T>>>()?,
+            u.arbitrary::<LineStringZ<T>>()?,
+            u.arbitrary::<Vec<LineStringZ<T>>>()?,
         ))
     }
 }
 
-impl<'a, T> arbitrary::Arbitrary<'a> for MultiPoint<T>
+impl<'a, T> arbitrary::Arbitrary<'a> for MultiPointZ<T>
 where
     T: arbitrary::Arbitrary<'a> + CoordFloat,
 {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        u.arbitrary::<Vec<Point<T>>>().map(Self)
+        u.arbitrary::<Vec<PointZ<T>>>().map(Self)
     }
 }
 
-impl<'a, T> arbitrary::Arbitrary<'a> for MultiLineString<T>
+impl<'a, T> arbitrary::Arbitrary<'a> for MultiLineStringZ<T>
 where
     T: arbitrary::Arbitrary<'a> + CoordFloat,
 {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        u.arbitrary::<Vec<LineString<T>>>().map(Self)
+        u.arbitrary::<Vec<LineStringZ<T>>>().map(Self)
     }
 }
 
-impl<'a, T> arbitrary::Arbitrary<'a> for MultiPolygon<T>
+impl<'a, T> arbitrary::Arbitrary<'a> for MultiPolygonZ<T>
 where
     T: arbitrary::Arbitrary<'a> + CoordFloat,
 {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        u.arbitrary::<Vec<Polygon<T>>>().map(Self)
+        u.arbitrary::<Vec<PolygonZ<T>>>().map(Self)
     }
 }
 
@@ -87,18 +104,18 @@ where
     T: arbitrary::Arbitrary<'a> + CoordFloat,
 {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        u.arbitrary()
+        u.arbitrary::<Vec<Geometry<T>>>().map(Self)
     }
 }
 
-impl<'a, T> arbitrary::Arbitrary<'a> for Rect<T>
+impl<'a, T> arbitrary::Arbitrary<'a> for Cube<T>
 where
     T: arbitrary::Arbitrary<'a> + CoordFloat,
 {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
         Ok(Self::new(
-            u.arbitrary::<Coord<T>>()?,
-            u.arbitrary::<Coord<T>>()?,
+            u.arbitrary::<CoordZ<T>>()?,
+            u.arbitrary::<CoordZ<T>>()?,
         ))
     }
 }
@@ -109,9 +126,9 @@ where
 {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
         Ok(Self(
-            u.arbitrary::<Coord<T>>()?,
-            u.arbitrary::<Coord<T>>()?,
-            u.arbitrary::<Coord<T>>()?,
+            u.arbitrary::<CoordZ<T>>()?,
+            u.arbitrary::<CoordZ<T>>()?,
+            u.arbitrary::<CoordZ<T>>()?,
         ))
     }
 }
@@ -121,18 +138,28 @@ where
     T: arbitrary::Arbitrary<'a> + CoordFloat,
 {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        let n = u.int_in_range(0..=8)?;
+        let n = u.int_in_range(0..=15)?;
 
         Ok(match n {
             0 => Self::Point(u.arbitrary()?),
-            1 => Self::LineString(u.arbitrary()?),
-            2 => Self::Polygon(u.arbitrary()?),
-            3 => Self::MultiPoint(u.arbitrary()?),
-            4 => Self::MultiLineString(u.arbitrary()?),
-            5 => Self::MultiPolygon(u.arbitrary()?),
-            6 => Self::GeometryCollection(u.arbitrary()?),
-            7 => Self::Triangle(u.arbitrary()?),
-            8 => Self::Rect(u.arbitrary()?),
+            1 => Self::PointZ(u.arbitrary()?),
+            2 => Self::Line(geo_types::Line::new(
+                u.arbitrary::<geo_types::Coord<T>>()?,
+                u.arbitrary::<geo_types::Coord<T>>()?,
+            )),
+            3 => Self::LineZ(u.arbitrary()?),
+            4 => Self::LineString(u.arbitrary()?),
+            5 => Self::LineStringZ(u.arbitrary()?),
+            6 => Self::Polygon(u.arbitrary()?),
+            7 => Self::PolygonZ(u.arbitrary()?),
+            8 => Self::MultiPoint(u.arbitrary()?),
+            9 => Self::MultiPointZ(u.arbitrary()?),
+            10 => Self::MultiLineString(u.arbitrary()?),
+            11 => Self::MultiLineStringZ(u.arbitrary()?),
+            12 => Self::MultiPolygon(u.arbitrary()?),
+            13 => Self::MultiPolygonZ(u.arbitrary()?),
+            14 => Self::GeometryCollection(u.arbitrary()?),
+            15 => Self::Rect(u.arbitrary()?),
             _ => unreachable!(),
         })
     }
@@ -0,0 +1,115 @@
+//! Reproducible inputs for the benchmarks in `benches/`.
+//!
+//! The generators are seeded, so every run, and every variant of a benchmark,
+//! measures the same geometries. They don't depend on the `rand` feature, so the
+//! benchmarks build with the default features.
+//!
+//! The benchmarks measure each operation on coordinates laid out as an array of
+//! [`CoordZ`] structures, as in the geometry types, and as a structure of arrays, as
+//! in [`LineStringZSoA`](crate::LineStringZSoA). Run them with `cargo bench`,
+//! adding `--features multithreading` for the variants spread over the rayon thread
+//! pool.
+//!
+//! # Examples
+//!
+//! ```
+//! use geo_types_3d::bench_helpers::{coords, point_cloud};
+//! use geo_types_3d::LineStringZSoA;
+//!
+//! let cloud = point_cloud(1_000, 42);
+//! assert_eq!(cloud, point_cloud(1_000, 42));
+//!
+//! let soa: LineStringZSoA = coords(1_000, 42).into_iter().collect();
+//! assert_eq!(soa.xs()[10], cloud.0[10].x());
+//! ```
+
+use alloc::vec::Vec;
+
+use num_traits::Float;
+
+use crate::splitmix::SplitMix64;
+use crate::{
+    CoordZ, Geometry, GeometryCollection, LineStringZ, MultiLineStringZ, MultiPointZ, PointZ,
+    PolygonZ,
+};
+
+/// `count` coordinates spread through a 1 km × 1 km × 100 m box with one corner at
+/// the origin.
+pub fn coords(count: usize, seed: u64) -> Vec<CoordZ<f64>> {
+    let mut random = SplitMix64(seed);
+    (0..count)
+        .map(|_| {
+            CoordZ::from((
+                random.next_f64() * 1_000.,
+                random.next_f64() * 1_000.,
+                random.next_f64() * 100.,
+            ))
+        })
+        .collect()
+}
+
+/// A cloud of `count` points, as placed by [`coords`].
+pub fn point_cloud(count: usize, seed: u64) -> MultiPointZ<f64> {
+    coords(count, seed).into_iter().map(PointZ).collect()
+}
+
+/// `count` line strings of `vertices` vertices each, wandering from the
+/// coordinates placed by [`coords`] in steps of up to 10 m across and 1 m up or
+/// down.
+pub fn line_strings(count: usize, vertices: usize, seed: u64) -> MultiLineStringZ<f64> {
+    let mut random = SplitMix64(seed);
+    coords(count, seed.wrapping_add(1))
+        .into_iter()
+        .map(|mut at| {
+            (0..vertices)
+                .map(|_| {
+                    let coord = at;
+                    at += CoordZ::from((
+                        random.next_f64() * 20. - 10.,
+                        random.next_f64() * 20. - 10.,
+                        random.next_f64() * 2. - 1.,
+                    ));
+                    coord
+                })
+                .collect::<LineStringZ<f64>>()
+        })
+        .collect()
+}
+
+/// `count` polygons side by side along the x axis, each a closed ring of
+/// `vertices` vertices around a unit circle, climbing as it goes around.
+pub fn polygons(count: usize, vertices: usize) -> GeometryCollection<f64> {
+    (0..count)
+        .map(|i| {
+            let ring: LineStringZ<f64> = (0..vertices)
+                .map(|j| {
+                    let angle = j as f64 / vertices as f64 * core::f64::consts::TAU;
+                    (i as f64 + Float::cos(angle), Float::sin(angle), 100. + angle)
+                })
+                .collect();
+            Geometry::PolygonZ(PolygonZ::new(ring, Vec::new()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::BoundingCube;
+    use crate::Cube;
+
+    #[test]
+    fn generators() {
+        let coords = coords(500, 3);
+        let cube = MultiPointZ::from(coords.clone()).bounding_cube().unwrap();
+        assert!(Cube::new((0., 0., 0.), (1_000., 1_000., 100.)).contains_coord(cube.min()));
+        assert!(cube.max().x > 900. && cube.max().z > 90.);
+        assert_ne!(coords, super::coords(500, 4));
+
+        let lines = line_strings(20, 50, 3);
+        assert_eq!(lines.0.len(), 20);
+        assert!(lines.iter().all(|line| line.0.len() == 50));
+        let polygons = polygons(3, 16);
+        assert!(matches!(&polygons.0[2], Geometry::PolygonZ(p) if p.exterior().is_closed()));
+    }
+}
@@ -0,0 +1,442 @@
+//! A C API for exchanging geometries with C, C++ and any language with a C foreign
+//! function interface.
+//!
+//! Geometries are `f64` [`Geometry`] values behind an opaque [`Geo3dGeometry`]
+//! pointer, built from coordinates or well-known binary and read back the same
+//! ways. Every pointer returned must be released with the matching `_free`
+//! function. Functions that can fail return a null pointer or `false`, and
+//! [`geo3d_last_error`] then describes what went wrong on the calling thread.
+//!
+//! The header `include/geo_types_3d.h` is generated with [cbindgen], from the
+//! crate directory:
+//!
+//! ```sh
+//! cbindgen --config cbindgen.toml --output include/geo_types_3d.h
+//! ```
+//!
+//! and a static or shared library built with
+//! `cargo rustc --release --features capi --crate-type staticlib` (or `cdylib`).
+//!
+//! ```c
+//! #include "geo_types_3d.h"
+//!
+//! const double coords[] = {0, 0, 100, 3, 4, 100, 3, 4, 112};
+//! Geo3dGeometry *track = geo3d_line_string_z_new(coords, 3);
+//! size_t len;
+//! uint8_t *wkb = geo3d_geometry_to_wkb(track, &len);
+//! geo3d_bytes_free(wkb, len);
+//! geo3d_geometry_free(track);
+//! ```
+//!
+//! [cbindgen]: https://github.com/mozilla/cbindgen
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CString};
+use std::ptr;
+
+use crate::algorithm::AxisOrder;
+use crate::conversion::wkb::{read_wkb, write_wkb};
+use crate::{CoordVisitor, CoordZ, Error, Geometry, LineStringZ, PointZ, PolygonZ};
+
+/// A geometry, owned by the library.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Geo3dGeometry(Geometry);
+
+/// The type of a [`Geo3dGeometry`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Geo3dGeometryType {
+    Point,
+    PointZ,
+    Line,
+    LineZ,
+    LineString,
+    LineStringZ,
+    Polygon,
+    PolygonZ,
+    MultiPoint,
+    MultiPointZ,
+    MultiLineString,
+    MultiLineStringZ,
+    MultiPolygon,
+    MultiPolygonZ,
+    GeometryCollection,
+    Rect,
+    /// Returned for a null geometry.
+    Null,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_error(message: impl ToString) {
+    // an interior nul can't cross into C, so it ends the message early
+    let message = message.to_string();
+    let message = message.split('\0').next().unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = CString::new(message).ok());
+}
+
+fn into_raw(geometry: Geometry) -> *mut Geo3dGeometry {
+    Box::into_raw(Box::new(Geo3dGeometry(geometry)))
+}
+
+/// The geometry behind `geometry`, or `None` with the error set if it is null.
+///
+/// # Safety
+///
+/// `geometry` must be null or come from this library and not have been freed.
+unsafe fn geometry_ref<'a>(geometry: *const Geo3dGeometry) -> Option<&'a Geo3dGeometry> {
+    // SAFETY: passed on from the caller.
+    let geometry = unsafe { geometry.as_ref() };
+    if geometry.is_none() {
+        set_error(Error::InvalidArgument {
+            reason: "null geometry",
+        });
+    }
+    geometry
+}
+
+/// The coordinates of `count` `x, y, z` triples at `coords`.
+///
+/// # Safety
+///
+/// `coords` must point to `3 * count` doubles, or may be null if `count` is zero.
+unsafe fn coords_from_raw(coords: *const f64, count: usize) -> Result<Vec<CoordZ>, Error> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+    if coords.is_null() {
+        return Err(Error::InvalidArgument {
+            reason: "null coordinates",
+        });
+    }
+    let len = count.checked_mul(3).ok_or(Error::InvalidArgument {
+        reason: "too many coordinates",
+    })?;
+    // SAFETY: the caller guarantees `coords` points to `3 * count` doubles.
+    let values = unsafe { std::slice::from_raw_parts(coords, len) };
+    Ok(values
+        .chunks_exact(3)
+        .map(|xyz| CoordZ::from((xyz[0], xyz[1], xyz[2])))
+        .collect())
+}
+
+/// The message describing the last error on this thread, or null if there was
+/// none. The message stays valid until the next failing call on this thread.
+#[no_mangle]
+pub extern "C" fn geo3d_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// A new point.
+#[no_mangle]
+pub extern "C" fn geo3d_point_z_new(x: f64, y: f64, z: f64) -> *mut Geo3dGeometry {
+    into_raw(Geometry::PointZ(PointZ::new(x, y, z)))
+}
+
+/// A new line string through the `count` `x, y, z` triples at `coords`, or null
+/// if `coords` is null.
+///
+/// # Safety
+///
+/// `coords` must point to `3 * count` doubles, or may be null if `count` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn geo3d_line_string_z_new(
+    coords: *const f64,
+    count: usize,
+) -> *mut Geo3dGeometry {
+    // SAFETY: passed on from the caller.
+    match unsafe { coords_from_raw(coords, count) } {
+        Ok(coords) => into_raw(Geometry::LineStringZ(LineStringZ(coords))),
+        Err(error) => {
+            set_error(error);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// A new polygon with `ring_count` rings, the first one exterior. Ring `i` has
+/// `ring_sizes[i]` coordinates, and the rings' `x, y, z` triples follow each
+/// other at `coords`. Open rings are closed.
+///
+/// # Safety
+///
+/// `ring_sizes` must point to `ring_count` sizes, and `coords` to three doubles
+/// for each coordinate of every ring. Either may be null if its count is zero.
+#[no_mangle]
+pub unsafe extern "C" fn geo3d_polygon_z_new(
+    coords: *const f64,
+    ring_sizes: *const usize,
+    ring_count: usize,
+) -> *mut Geo3dGeometry {
+    let rings = || -> Result<Vec<LineStringZ>, Error> {
+        if ring_count == 0 {
+            return Ok(Vec::new());
+        }
+        if ring_sizes.is_null() {
+            return Err(Error::InvalidArgument {
+                reason: "null ring sizes",
+            });
+        }
+        // SAFETY: the caller guarantees `ring_sizes` points to `ring_count` sizes.
+        let sizes = unsafe { std::slice::from_raw_parts(ring_sizes, ring_count) };
+        let total = sizes
+            .iter()
+            .try_fold(0usize, |total, &size| total.checked_add(size))
+            .ok_or(Error::InvalidArgument {
+                reason: "too many coordinates",
+            })?;
+        // SAFETY: the caller guarantees `coords` holds every ring's coordinates.
+        let mut coords = unsafe { coords_from_raw(coords, total)? }.into_iter();
+        Ok(sizes
+            .iter()
+            .map(|&size| LineStringZ(coords.by_ref().take(size).collect()))
+            .collect())
+    };
+    match rings() {
+        Ok(mut rings) => {
+            let exterior = if rings.is_empty() {
+                LineStringZ::empty()
+            } else {
+                rings.remove(0)
+            };
+            into_raw(Geometry::PolygonZ(PolygonZ::new(exterior, rings)))
+        }
+        Err(error) => {
+            set_error(error);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// A new geometry read from the `len` bytes of well-known binary at `wkb`, or
+/// null if they aren't valid well-known binary.
+///
+/// # Safety
+///
+/// `wkb` must point to `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn geo3d_geometry_from_wkb(wkb: *const u8, len: usize) -> *mut Geo3dGeometry {
+    if wkb.is_null() {
+        set_error(Error::InvalidArgument {
+            reason: "null well-known binary",
+        });
+        return ptr::null_mut();
+    }
+    // SAFETY: the caller guarantees `wkb` points to `len` bytes.
+    let bytes = unsafe { std::slice::from_raw_parts(wkb, len) };
+    match read_wkb(bytes, AxisOrder::LonLat) {
+        Ok(geometry) => into_raw(geometry),
+        Err(error) => {
+            set_error(error);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// The geometry as ISO well-known binary, little-endian, with its length stored
+/// in `len`, or null if `geometry` or `len` is null. Release it with
+/// [`geo3d_bytes_free`].
+///
+/// # Safety
+///
+/// `geometry` must be null or come from this library and not have been freed,
+/// and `len` must be null or point to a `size_t`.
+#[no_mangle]
+pub unsafe extern "C" fn geo3d_geometry_to_wkb(
+    geometry: *const Geo3dGeometry,
+    len: *mut usize,
+) -> *mut u8 {
+    // SAFETY: the caller guarantees `geometry` is null or live.
+    let Some(geometry) = (unsafe { geometry_ref(geometry) }) else {
+        return ptr::null_mut();
+    };
+    // SAFETY: the caller guarantees `len` is null or writable.
+    let Some(len) = (unsafe { len.as_mut() }) else {
+        set_error(Error::InvalidArgument {
+            reason: "null length",
+        });
+        return ptr::null_mut();
+    };
+    let bytes = write_wkb(&geometry.0).into_boxed_slice();
+    *len = bytes.len();
+    Box::into_raw(bytes).cast()
+}
+
+/// Releases bytes returned by [`geo3d_geometry_to_wkb`].
+///
+/// # Safety
+///
+/// `bytes` and `len` must be as returned by [`geo3d_geometry_to_wkb`], and
+/// `bytes` not already freed. Null is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn geo3d_bytes_free(bytes: *mut u8, len: usize) {
+    if !bytes.is_null() {
+        // SAFETY: the bytes were a boxed slice of `len` bytes.
+        drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(bytes, len)) });
+    }
+}
+
+/// Releases a geometry.
+///
+/// # Safety
+///
+/// `geometry` must come from this library and not already be freed. Null is
+/// ignored.
+#[no_mangle]
+pub unsafe extern "C" fn geo3d_geometry_free(geometry: *mut Geo3dGeometry) {
+    if !geometry.is_null() {
+        // SAFETY: the geometry was boxed by `into_raw`.
+        drop(unsafe { Box::from_raw(geometry) });
+    }
+}
+
+/// The type of the geometry, or [`Geo3dGeometryType::Null`] if `geometry` is
+/// null.
+///
+/// # Safety
+///
+/// `geometry` must be null or come from this library and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn geo3d_geometry_type(geometry: *const Geo3dGeometry) -> Geo3dGeometryType {
+    // SAFETY: the caller guarantees `geometry` is null or live.
+    let Some(geometry) = (unsafe { geometry_ref(geometry) }) else {
+        return Geo3dGeometryType::Null;
+    };
+    match &geometry.0 {
+        Geometry::Point(_) => Geo3dGeometryType::Point,
+        Geometry::PointZ(_) => Geo3dGeometryType::PointZ,
+        Geometry::Line(_) => Geo3dGeometryType::Line,
+        Geometry::LineZ(_) => Geo3dGeometryType::LineZ,
+        Geometry::LineString(_) => Geo3dGeometryType::LineString,
+        Geometry::LineStringZ(_) => Geo3dGeometryType::LineStringZ,
+        Geometry::Polygon(_) => Geo3dGeometryType::Polygon,
+        Geometry::PolygonZ(_) => Geo3dGeometryType::PolygonZ,
+        Geometry::MultiPoint(_) => Geo3dGeometryType::MultiPoint,
+        Geometry::MultiPointZ(_) => Geo3dGeometryType::MultiPointZ,
+        Geometry::MultiLineString(_) => Geo3dGeometryType::MultiLineString,
+        Geometry::MultiLineStringZ(_) => Geo3dGeometryType::MultiLineStringZ,
+        Geometry::MultiPolygon(_) => Geo3dGeometryType::MultiPolygon,
+        Geometry::MultiPolygonZ(_) => Geo3dGeometryType::MultiPolygonZ,
+        Geometry::GeometryCollection(_) => Geo3dGeometryType::GeometryCollection,
+        Geometry::Rect(_) => Geo3dGeometryType::Rect,
+    }
+}
+
+/// Copies the geometry's coordinates, as `x, y, z` triples, to `coords`, which
+/// has room for `capacity` coordinates, and returns how many coordinates the
+/// geometry has, or zero if `geometry` is null. Nothing is copied if they don't
+/// all fit, so a first call with a `capacity` of zero gives the size to allocate.
+/// Planar members have a z of 0.
+///
+/// # Safety
+///
+/// `geometry` must be null or come from this library and not have been freed,
+/// and `coords` must have room for `3 * capacity` doubles, or may be null if
+/// `capacity` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn geo3d_geometry_coords(
+    geometry: *const Geo3dGeometry,
+    coords: *mut f64,
+    capacity: usize,
+) -> usize {
+    /// Collects the ordinates of every coordinate.
+    struct Ordinates(Vec<f64>);
+
+    impl CoordVisitor<f64> for Ordinates {
+        fn visit_coord_z(&mut self, coord: &CoordZ) {
+            self.0.extend_from_slice(&[coord.x, coord.y, coord.z]);
+        }
+    }
+
+    // SAFETY: the caller guarantees `geometry` is null or live.
+    let Some(geometry) = (unsafe { geometry_ref(geometry) }) else {
+        return 0;
+    };
+    let mut ordinates = Ordinates(Vec::new());
+    geometry.0.accept_coords(&mut ordinates);
+    let count = ordinates.0.len() / 3;
+    if count <= capacity && !coords.is_null() {
+        // SAFETY: the caller guarantees room for `3 * capacity` doubles.
+        let out = unsafe { std::slice::from_raw_parts_mut(coords, ordinates.0.len()) };
+        out.copy_from_slice(&ordinates.0);
+    }
+    count
+}
+
+impl From<Geometry> for Geo3dGeometry {
+    fn from(geometry: Geometry) -> Self {
+        Self(geometry)
+    }
+}
+
+impl From<Geo3dGeometry> for Geometry {
+    fn from(geometry: Geo3dGeometry) -> Self {
+        geometry.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::ffi::CStr;
+
+    #[test]
+    fn exchange() {
+        unsafe {
+            let coords = [0., 0., 100., 3., 4., 100., 3., 4., 112.];
+            let track = geo3d_line_string_z_new(coords.as_ptr(), 3);
+            assert_eq!(geo3d_geometry_type(track), Geo3dGeometryType::LineStringZ);
+            let mut len = 0;
+            let wkb = geo3d_geometry_to_wkb(track, &mut len);
+            assert_eq!(len, 1 + 4 + 4 + 9 * 8);
+
+            let copy = geo3d_geometry_from_wkb(wkb, len);
+            assert_eq!(geo3d_geometry_coords(copy, ptr::null_mut(), 0), 3);
+            let mut out = [0.; 9];
+            assert_eq!(geo3d_geometry_coords(copy, out.as_mut_ptr(), 3), 3);
+            assert_eq!(out, coords);
+            geo3d_bytes_free(wkb, len);
+            geo3d_geometry_free(copy);
+            geo3d_geometry_free(track);
+
+            // a square with a hole, its rings left open
+            let rings = [
+                0., 0., 1., 4., 0., 1., 4., 4., 1., 0., 4., 1., //
+                1., 1., 1., 2., 1., 1., 2., 2., 1.,
+            ];
+            let square = geo3d_polygon_z_new(rings.as_ptr(), [4, 3].as_ptr(), 2);
+            let Geometry::PolygonZ(polygon) = &(*square).0 else {
+                unreachable!()
+            };
+            assert_eq!(polygon.exterior().0.len(), 5);
+            assert_eq!(polygon.interiors()[0].0.len(), 4);
+            geo3d_geometry_free(square);
+
+            let point = geo3d_point_z_new(1., 2., 3.);
+            assert_eq!(geo3d_geometry_coords(point, out.as_mut_ptr(), 3), 1);
+            assert_eq!(out[..3], [1., 2., 3.]);
+            geo3d_geometry_free(point);
+
+            assert!(geo3d_geometry_from_wkb([1, 2].as_ptr(), 2).is_null());
+            let message = CStr::from_ptr(geo3d_last_error()).to_str().unwrap();
+            assert!(message.starts_with("Invalid well-known binary"));
+            assert!(geo3d_line_string_z_new(ptr::null(), 2).is_null());
+            let message = CStr::from_ptr(geo3d_last_error()).to_str().unwrap();
+            assert!(message.starts_with("Invalid argument"));
+            assert!(geo3d_line_string_z_new([0.].as_ptr(), usize::MAX).is_null());
+
+            assert_eq!(geo3d_geometry_type(ptr::null()), Geo3dGeometryType::Null);
+            assert_eq!(geo3d_geometry_coords(ptr::null(), ptr::null_mut(), 0), 0);
+            assert!(geo3d_geometry_to_wkb(ptr::null(), &mut len).is_null());
+            let point = geo3d_point_z_new(1., 2., 3.);
+            assert!(geo3d_geometry_to_wkb(point, ptr::null_mut()).is_null());
+            geo3d_geometry_free(point);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Discrete global grid cells for 3D points, for coarse spatial joins and bucketing.
+//!
+//! Points are placed in a horizontal cell from their longitude (x) and latitude (y)
+//! in degrees, with one of the feature-gated grid systems:
+//!
+//! - `geohash`: [`PointZ::geohash`], using the [geohash] crate
+//! - `h3`: [`PointZ::to_h3`], using the [h3o] crate
+//! - `s2`: [`PointZ::to_s2_cell`], computing an [`S2CellId`] without further
+//!   dependencies
+//!
+//! A [`CellBucket`] adds a band of elevation to any of these cells. Points whose
+//! buckets are equal are close together, and points within one band height of each
+//! other vertically fall in the same or in neighbouring bands.
+//!
+//! [geohash]: https://docs.rs/geohash
+//! [h3o]: https://docs.rs/h3o
+//!
+//! # Examples
+//!
+//! ```
+//! use geo_types_3d::cells::CellBucket;
+//! use geo_types_3d::PointZ;
+//!
+//! // altitude bands 100 m high, over whatever horizontal cell suits
+//! let drone = PointZ::new(13.4050, 52.5200, 130.);
+//! let bucket = CellBucket::new("u33dc0", drone.z(), 100.);
+//! assert_eq!(bucket.band, 1);
+//! assert_eq!(bucket.offset(-1).band, 0);
+//! ```
+
+use crate::CoordFloat;
+#[cfg(any(feature = "geohash", feature = "h3"))]
+use crate::{Error, PointZ};
+#[cfg(feature = "geohash")]
+use alloc::string::String;
+
+/// A horizontal cell combined with a band of elevation.
+///
+/// Band `n` covers elevations from `n * band_height` up to, but not including,
+/// `(n + 1) * band_height`, so band 0 starts at z = 0 and negative elevations fall in
+/// negative bands. Buckets order by cell first, then band.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CellBucket<C> {
+    pub cell: C,
+    pub band: i64,
+}
+
+impl<C> CellBucket<C> {
+    /// The bucket of `cell` containing elevation `z`.
+    ///
+    /// # Panics
+    ///
+    /// If `band_height` isn't positive.
+    pub fn new<T: CoordFloat>(cell: C, z: T, band_height: T) -> Self {
+        assert!(band_height > T::zero(), "band height must be positive");
+        let band = (z / band_height).floor().to_i64().unwrap_or(0);
+        Self { cell, band }
+    }
+
+    /// The bucket of the same cell, `bands` bands higher, or lower where negative.
+    pub fn offset(self, bands: i64) -> Self {
+        Self {
+            band: self.band.saturating_add(bands),
+            ..self
+        }
+    }
+}
+
+#[cfg(feature = "geohash")]
+impl<T: CoordFloat> PointZ<T> {
+    /// The geohash of the cell containing the point, `precision` characters long.
+    ///
+    /// Fails with [`Error::InvalidCell`] if the point isn't a valid longitude and
+    /// latitude, or `precision` isn't between 1 and 12.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::PointZ;
+    ///
+    /// let point = PointZ::new(-120.6623, 35.3003, 80.);
+    /// assert_eq!(point.geohash(5).unwrap(), "9q60y");
+    /// ```
+    pub fn geohash(&self, precision: usize) -> Result<String, Error> {
+        let coord = geo_types::Coord {
+            x: self.x().to_f64().unwrap_or(f64::NAN),
+            y: self.y().to_f64().unwrap_or(f64::NAN),
+        };
+        geohash::encode(coord, precision).map_err(|err| Error::InvalidCell {
+            reason: match err {
+                geohash::GeohashError::InvalidLength(_) => "precision out of range",
+                _ => "coordinate is not a valid longitude and latitude",
+            },
+        })
+    }
+}
+
+#[cfg(feature = "h3")]
+impl<T: CoordFloat> PointZ<T> {
+    /// The H3 cell containing the point, at `resolution` 0 (coarsest) to 15.
+    ///
+    /// Fails with [`Error::InvalidCell`] if the point isn't finite, or `resolution`
+    /// is above 15.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::PointZ;
+    ///
+    /// let point = PointZ::new(2.3522, 48.8566, 35.);
+    /// let cell = point.to_h3(9).unwrap();
+    /// assert_eq!(u8::from(cell.resolution()), 9);
+    /// ```
+    pub fn to_h3(&self, resolution: u8) -> Result<h3o::CellIndex, Error> {
+        let resolution = h3o::Resolution::try_from(resolution).map_err(|_| Error::InvalidCell {
+            reason: "resolution out of range",
+        })?;
+        let lat = self.y().to_f64().unwrap_or(f64::NAN);
+        let lng = self.x().to_f64().unwrap_or(f64::NAN);
+        let lat_lng = h3o::LatLng::new(lat, lng).map_err(|_| Error::InvalidCell {
+            reason: "coordinate is not a valid longitude and latitude",
+        })?;
+        Ok(lat_lng.to_cell(resolution))
+    }
+}
+
+#[cfg(feature = "s2")]
+mod s2;
+#[cfg(feature = "s2")]
+pub use s2::S2CellId;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::PointZ;
+
+    #[test]
+    fn buckets() {
+        let bucket = CellBucket::new(7u64, -0.5, 10.);
+        assert_eq!(bucket.band, -1);
+        assert_eq!(CellBucket::new(7u64, 10., 10.).band, 1);
+        assert_eq!(bucket.offset(2), CellBucket { cell: 7, band: 1 });
+        assert!(bucket < CellBucket::new(8u64, -100., 10.));
+
+        let point = PointZ::new(2.3522, 48.8566, 35.);
+        #[cfg(feature = "geohash")]
+        {
+            assert_eq!(point.geohash(7).unwrap(), "u09tvw0");
+            assert!(point.geohash(13).is_err());
+            assert!(PointZ::new(200., 0., 0.).geohash(5).is_err());
+        }
+        #[cfg(feature = "h3")]
+        {
+            let cell = point.to_h3(5).unwrap();
+            assert_eq!(
+                point.to_h3(9).unwrap().parent(cell.resolution()),
+                Some(cell)
+            );
+            assert!(point.to_h3(16).is_err());
+            assert!(PointZ::new(f64::NAN, 0., 0.).to_h3(5).is_err());
+        }
+        #[cfg(feature = "s2")]
+        {
+            let cell = point.to_s2_cell().unwrap();
+            assert_eq!(cell.level(), 30);
+            assert_eq!(cell.parent(10).level(), 10);
+        }
+        let _ = point;
+    }
+}
@@ -0,0 +1,154 @@
+use num_traits::Float;
+
+use crate::{CoordFloat, Error, PointZ};
+
+/// The id of a cell of the [S2] grid, which projects the sphere onto the six faces
+/// of a cube and divides each face into a quadtree of 30 levels.
+///
+/// Ids are the 64-bit values S2 libraries use, so cells can be stored and compared
+/// with those computed elsewhere. The ids of the cells of one level follow a
+/// Hilbert curve over each face, so nearby cells mostly have nearby ids.
+///
+/// [S2]: http://s2geometry.io
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::cells::S2CellId;
+/// use geo_types_3d::PointZ;
+///
+/// let cell = PointZ::new(0., 0., 0.).to_s2_cell().unwrap();
+/// assert_eq!(cell, S2CellId(0x1000000000000001));
+/// assert_eq!(cell.face(), 0);
+/// assert_eq!(cell.parent(1), S2CellId(0x1400000000000000));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct S2CellId(pub u64);
+
+impl S2CellId {
+    /// The finest level, of cells about a centimetre across.
+    pub const MAX_LEVEL: u8 = 30;
+
+    /// The face of the cube the cell lies on, from 0 to 5.
+    pub fn face(self) -> u8 {
+        (self.0 >> 61) as u8
+    }
+
+    /// The level of the cell, from 0 for a whole face to [`S2CellId::MAX_LEVEL`].
+    pub fn level(self) -> u8 {
+        Self::MAX_LEVEL - (self.0.trailing_zeros() / 2) as u8
+    }
+
+    /// The cell at `level` containing this one.
+    ///
+    /// # Panics
+    ///
+    /// If `level` is finer than the level of this cell.
+    pub fn parent(self, level: u8) -> Self {
+        assert!(level <= self.level(), "parent must be at a coarser level");
+        let lowest = 1u64 << (2 * u32::from(Self::MAX_LEVEL - level));
+        Self((self.0 & lowest.wrapping_neg()) | lowest)
+    }
+
+    /// Whether `other` is this cell or lies within it.
+    pub fn contains(self, other: Self) -> bool {
+        let lowest = self.0 & self.0.wrapping_neg();
+        other.0 >= self.0 - (lowest - 1) && other.0 <= self.0 + (lowest - 1)
+    }
+}
+
+impl<T: CoordFloat> PointZ<T> {
+    /// The leaf S2 cell containing the point, from its longitude (x) and latitude (y)
+    /// in degrees. Use [`S2CellId::parent`] for coarser cells.
+    ///
+    /// Fails with [`Error::InvalidCell`] if the point isn't finite.
+    pub fn to_s2_cell(&self) -> Result<S2CellId, Error> {
+        let lng = self.x().to_f64().unwrap_or(f64::NAN);
+        let lat = self.y().to_f64().unwrap_or(f64::NAN);
+        if !lng.is_finite() || !lat.is_finite() {
+            return Err(Error::InvalidCell {
+                reason: "coordinate is not a valid longitude and latitude",
+            });
+        }
+        let (lng, lat) = (Float::to_radians(lng), Float::to_radians(lat));
+        let (sin_lat, cos_lat) = Float::sin_cos(lat);
+        let (sin_lng, cos_lng) = Float::sin_cos(lng);
+        let xyz = [cos_lat * cos_lng, cos_lat * sin_lng, sin_lat];
+
+        // the face is the axis the point is furthest along
+        let axis = (0..3)
+            .max_by(|&a, &b| Float::abs(xyz[a]).total_cmp(&Float::abs(xyz[b])))
+            .unwrap();
+        let face = axis + if xyz[axis] < 0. { 3 } else { 0 };
+        let [x, y, z] = xyz;
+        let (u, v) = match face {
+            0 => (y / x, z / x),
+            1 => (-x / y, z / y),
+            2 => (-x / z, -y / z),
+            3 => (z / x, y / x),
+            4 => (z / y, -x / y),
+            _ => (-y / z, -x / z),
+        };
+        let (i, j) = (to_ij(u), to_ij(v));
+
+        // walk down the quadtree along the Hilbert curve
+        const IJ_TO_POS: [[u64; 4]; 4] = [[0, 1, 3, 2], [0, 3, 1, 2], [2, 3, 1, 0], [2, 1, 3, 0]];
+        const POS_TO_ORIENTATION: [usize; 4] = [1, 0, 0, 3];
+        let mut orientation = face & 1;
+        let mut id = (face as u64) << 61;
+        for k in (0..u32::from(S2CellId::MAX_LEVEL)).rev() {
+            let ij = (((i >> k) & 1) << 1 | ((j >> k) & 1)) as usize;
+            let pos = IJ_TO_POS[orientation][ij];
+            id |= pos << (2 * k + 1);
+            orientation ^= POS_TO_ORIENTATION[pos as usize];
+        }
+        Ok(S2CellId(id | 1))
+    }
+}
+
+/// Maps a face coordinate to a leaf cell index, with S2's quadratic projection
+/// evening out the cell sizes.
+fn to_ij(uv: f64) -> u32 {
+    let st = if uv >= 0. {
+        0.5 * Float::sqrt(1. + 3. * uv)
+    } else {
+        1. - 0.5 * Float::sqrt(1. - 3. * uv)
+    };
+    let cells = f64::from(1u32 << S2CellId::MAX_LEVEL);
+    Float::floor(st * cells).clamp(0., cells - 1.) as u32
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cells() {
+        // the centres of the six faces
+        let centres = [
+            (0., 0.),
+            (90., 0.),
+            (0., 90.),
+            (180., 0.),
+            (-90., 0.),
+            (0., -90.),
+        ];
+        for (face, (lng, lat)) in centres.into_iter().enumerate() {
+            let cell = PointZ::new(lng, lat, 0.).to_s2_cell().unwrap();
+            assert_eq!(cell.face() as usize, face);
+            assert_eq!(cell.level(), 30);
+        }
+
+        let paris = PointZ::new(2.3522, 48.8566, 35.).to_s2_cell().unwrap();
+        let nearby = PointZ::new(2.3523, 48.8566, 35.).to_s2_cell().unwrap();
+        assert_ne!(paris, nearby);
+        assert_eq!(paris.parent(12), nearby.parent(12));
+        assert!(paris.parent(12).contains(nearby));
+        assert!(!paris.contains(nearby));
+        assert_eq!(paris.parent(0).level(), 0);
+        assert_eq!(paris.parent(12).parent(5), paris.parent(5));
+        assert!(PointZ::new(f64::NAN, 0., 0.).to_s2_cell().is_err());
+        assert!(PointZ::new(0., f64::INFINITY, 0.).to_s2_cell().is_err());
+    }
+}
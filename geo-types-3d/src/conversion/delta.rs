@@ -0,0 +1,216 @@
+//! A compact binary encoding of line strings, such as to store large sets of
+//! trajectories, as a lighter alternative to TWKB.
+//!
+//! Each ordinate is rounded to a fixed number of decimal places, `precision` for x
+//! and y and `z_precision` for z, and stored as the difference from the previous
+//! coordinate, zig-zag encoded into a LEB128 varint. Nearby coordinates of a track
+//! so take one or two bytes per ordinate. A short header holds the precisions and
+//! the number of coordinates, so encoded line strings can be concatenated into one
+//! buffer and read back one after another.
+//!
+//! # Examples
+//!
+//! ```
+//! use geo_types_3d::conversion::delta::{compress_into, decompress};
+//! use geo_types_3d::line_string_z;
+//!
+//! // two GPS tracks, to the centimetre horizontally and the decimetre vertically
+//! let tracks = [
+//!     line_string_z![
+//!         (x: 5.0, y: 10.0, z: 102.4),
+//!         (x: 5.01, y: 10.02, z: 102.9),
+//!         (x: 5.03, y: 10.03, z: 103.1),
+//!     ],
+//!     line_string_z![(x: -3.5, y: 0.25, z: 0.)],
+//! ];
+//! let mut buffer = Vec::new();
+//! for track in &tracks {
+//!     compress_into(track, 2, 1, &mut buffer).unwrap();
+//! }
+//! assert_eq!(buffer.len(), 22);
+//!
+//! let mut bytes = &buffer[..];
+//! for track in &tracks {
+//!     assert_eq!(&decompress::<f64>(&mut bytes).unwrap(), track);
+//! }
+//! assert!(bytes.is_empty());
+//! ```
+
+use crate::{CoordFloat, CoordZ, Error, LineStringZ};
+
+/// Encodes a line string, keeping `precision` decimal places of x and y and
+/// `z_precision` decimal places of z.
+///
+/// Fails with [`Error::InvalidCompressed`] if an ordinate isn't finite or is too
+/// large to encode at the precision asked for.
+pub fn compress<T: CoordFloat>(
+    line_string: &LineStringZ<T>,
+    precision: u32,
+    z_precision: u32,
+) -> Result<Vec<u8>, Error> {
+    let mut buffer = Vec::new();
+    compress_into(line_string, precision, z_precision, &mut buffer)?;
+    Ok(buffer)
+}
+
+/// Like [`compress`], but appends to `buffer`. On error, `buffer` is left as it
+/// was.
+pub fn compress_into<T: CoordFloat>(
+    line_string: &LineStringZ<T>,
+    precision: u32,
+    z_precision: u32,
+    buffer: &mut Vec<u8>,
+) -> Result<(), Error> {
+    let start = buffer.len();
+    let result = write_line_string(line_string, precision, z_precision, buffer);
+    if result.is_err() {
+        buffer.truncate(start);
+    }
+    result
+}
+
+fn write_line_string<T: CoordFloat>(
+    line_string: &LineStringZ<T>,
+    precision: u32,
+    z_precision: u32,
+    buffer: &mut Vec<u8>,
+) -> Result<(), Error> {
+    write_varint(u64::from(precision), buffer);
+    write_varint(u64::from(z_precision), buffer);
+    write_varint(line_string.0.len() as u64, buffer);
+    let scales = [scale(precision), scale(precision), scale(z_precision)];
+    let mut previous = [0i64; 3];
+    for coord in &line_string.0 {
+        let values = [coord.x, coord.y, coord.z];
+        for i in 0..3 {
+            let value = values[i]
+                .to_f64()
+                .map(|value| (value * scales[i]).round())
+                .filter(|value| value.abs() < (1u64 << 62) as f64)
+                .ok_or(Error::InvalidCompressed {
+                    reason: "ordinate is not finite or too large",
+                })? as i64;
+            let delta = value - previous[i];
+            write_varint(((delta << 1) ^ (delta >> 63)) as u64, buffer);
+            previous[i] = value;
+        }
+    }
+    Ok(())
+}
+
+/// Decodes a line string encoded by [`compress`] from the start of `bytes`, and
+/// advances `bytes` past it.
+///
+/// Fails with [`Error::InvalidCompressed`] if `bytes` ends partway through the
+/// line string, or holds values out of range.
+pub fn decompress<T: CoordFloat>(bytes: &mut &[u8]) -> Result<LineStringZ<T>, Error> {
+    let precision = read_precision(bytes)?;
+    let z_precision = read_precision(bytes)?;
+    let count = read_varint(bytes)?;
+    // every coordinate takes at least three bytes
+    if count > (bytes.len() / 3) as u64 {
+        return Err(Error::InvalidCompressed {
+            reason: "input ends partway through the line string",
+        });
+    }
+    let scales = [scale(precision), scale(precision), scale(z_precision)];
+    let mut coords = Vec::with_capacity(count as usize);
+    let mut current = [0i64; 3];
+    for _ in 0..count {
+        let mut values = [T::zero(); 3];
+        for i in 0..3 {
+            let bits = read_varint(bytes)?;
+            let delta = ((bits >> 1) as i64) ^ -((bits & 1) as i64);
+            current[i] = current[i].wrapping_add(delta);
+            values[i] = T::from(current[i] as f64 / scales[i]).ok_or(Error::InvalidCompressed {
+                reason: "ordinate out of range",
+            })?;
+        }
+        let [x, y, z] = values;
+        coords.push(CoordZ { x, y, z });
+    }
+    Ok(LineStringZ(coords))
+}
+
+fn scale(precision: u32) -> f64 {
+    10f64.powi(precision.min(i32::MAX as u32) as i32)
+}
+
+fn read_precision(bytes: &mut &[u8]) -> Result<u32, Error> {
+    u32::try_from(read_varint(bytes)?).map_err(|_| Error::InvalidCompressed {
+        reason: "precision out of range",
+    })
+}
+
+fn write_varint(mut value: u64, buffer: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buffer.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, Error> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let Some((&byte, rest)) = bytes.split_first() else {
+            return Err(Error::InvalidCompressed {
+                reason: "input ends partway through a value",
+            });
+        };
+        *bytes = rest;
+        if shift >= 64 {
+            return Err(Error::InvalidCompressed {
+                reason: "value too long",
+            });
+        }
+        value |= u64::from(byte & 0x7f) << shift;
+        shift += 7;
+        if byte < 0x80 {
+            return Ok(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let track: LineStringZ = line_string_z![
+            (x: 8.54, y: 47.37, z: 408.),
+            (x: 8.5412, y: 47.3705, z: 409.5),
+            (x: -170.5412, y: -47.3705, z: -0.25),
+        ];
+        for (precision, z_precision) in [(5, 2), (6, 0), (9, 3)] {
+            let encoded = compress(&track, precision, z_precision).unwrap();
+            let mut bytes = &encoded[..];
+            let decoded: LineStringZ = decompress(&mut bytes).unwrap();
+            assert!(bytes.is_empty());
+            for (a, b) in decoded.coords().zip(track.coords()) {
+                assert_relative_eq!(a.x, b.x, epsilon = 1e-9);
+                assert_relative_eq!(a.y, b.y, epsilon = 1e-9);
+                assert_relative_eq!(a.z, b.z, epsilon = 0.5 / scale(z_precision));
+            }
+        }
+        let empty = compress(&LineStringZ::<f64>::empty(), 5, 2).unwrap();
+        assert_eq!(empty, [5, 2, 0]);
+        assert_eq!(
+            decompress::<f64>(&mut &empty[..]).unwrap(),
+            LineStringZ::empty()
+        );
+
+        // truncated input, and values that don't fit
+        let encoded = compress(&track, 5, 2).unwrap();
+        for end in 0..encoded.len() {
+            assert!(decompress::<f64>(&mut &encoded[..end]).is_err());
+        }
+        assert!(decompress::<f64>(&mut &[0xff; 12][..]).is_err());
+        let mut buffer = vec![1];
+        let infinite = line_string_z![(x: 0., y: f64::INFINITY, z: 0.)];
+        assert!(compress_into(&infinite, 5, 2, &mut buffer).is_err());
+        assert_eq!(buffer, [1]);
+    }
+}
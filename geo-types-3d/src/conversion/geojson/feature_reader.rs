@@ -0,0 +1,325 @@
+use std::io::{self, BufRead, BufReader, Read};
+use std::marker::PhantomData;
+
+use geojson::{Error, Feature, JsonObject, JsonValue, Result};
+
+use crate::algorithm::{AxisOrder, SwapXY};
+use crate::{CoordFloat, Geometry, Tagged};
+
+/// Reads the features of GeoJSON from any [`Read`], one at a time, as
+/// `(geometry, properties)` pairs.
+///
+/// Unlike [`read_geometries`](super::read_geometries), only one feature is held in
+/// memory at a time, so files far larger than memory can be read. The input may be
+/// a `FeatureCollection`, whose features are read from its `features` array as they
+/// come, or any number of features or geometries one after another, as in
+/// newline-delimited GeoJSON or [GeoJSON text sequences]. A bare geometry has empty
+/// properties, and features without a geometry are skipped.
+///
+/// The reader is buffered, so there is no need to wrap `reader` in a
+/// [`BufReader`]. Reading stops after the first error.
+///
+/// [GeoJSON text sequences]: https://www.rfc-editor.org/rfc/rfc8142
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::conversion::geojson::FeatureReader;
+/// use geo_types_3d::{Geometry, PointZ};
+///
+/// let ndjson = br#"{"type": "Feature", "properties": {"id": 1}, "geometry": {"type": "Point", "coordinates": [1, 2, 3]}}
+/// {"type": "Feature", "properties": {"id": 2}, "geometry": {"type": "Point", "coordinates": [4, 5, 6]}}
+/// "#;
+/// let mut features = FeatureReader::<_, f64>::new(&ndjson[..]);
+/// let (geometry, properties) = features.next().unwrap().unwrap();
+/// assert_eq!(geometry, Geometry::PointZ(PointZ::new(1.0, 2.0, 3.0)));
+/// assert_eq!(properties["id"], 1);
+/// assert_eq!(features.count(), 1);
+/// ```
+#[derive(Debug)]
+pub struct FeatureReader<R, T = f64> {
+    reader: BufReader<R>,
+    state: State,
+    axis_order: AxisOrder,
+    coords: PhantomData<T>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum State {
+    /// Between top level values.
+    Top,
+    /// Inside the `features` array of a feature collection.
+    Features,
+    Done,
+}
+
+impl<R: Read, T: CoordFloat> FeatureReader<R, T> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            state: State::Top,
+            axis_order: AxisOrder::LonLat,
+            coords: PhantomData,
+        }
+    }
+
+    /// Sets the order of the horizontal axes of the input. GeoJSON is always in
+    /// longitude/latitude order, but files that put latitude first, as some
+    /// EPSG:4326 exports do, can be read with [`AxisOrder::LatLon`], which swaps
+    /// their geometries into longitude/latitude order as they are read.
+    pub fn with_axis_order(mut self, axis_order: AxisOrder) -> Self {
+        self.axis_order = axis_order;
+        self
+    }
+
+    fn advance(&mut self) -> Result<Option<(Geometry<T>, JsonObject)>> {
+        loop {
+            match (self.state, self.peek()?) {
+                (State::Done, _) | (State::Top, None) => return Ok(None),
+                (State::Top, Some(b'{')) => {
+                    self.reader.consume(1);
+                    let (object, features) = self.members()?;
+                    if features {
+                        self.state = State::Features;
+                    } else if let Some(item) = top_level(object)? {
+                        return Ok(Some(item));
+                    }
+                }
+                (State::Features, Some(b',')) => self.reader.consume(1),
+                (State::Features, Some(b']')) => {
+                    // the rest of the collection's members are of no interest
+                    self.reader.consume(1);
+                    while self.peek()? == Some(b',') {
+                        self.reader.consume(1);
+                        self.key()?;
+                        self.json()?;
+                    }
+                    self.expect(b'}')?;
+                    self.state = State::Top;
+                }
+                (State::Features, Some(b'{')) => {
+                    if let Some(item) = feature(Feature::try_from(self.json()?)?)? {
+                        return Ok(Some(item));
+                    }
+                }
+                (_, byte) => return Err(unexpected(byte)),
+            }
+        }
+    }
+
+    /// Reads the members of an object up to its closing brace, or up to the
+    /// opening bracket of a `features` array, saying which.
+    fn members(&mut self) -> Result<(JsonObject, bool)> {
+        let mut object = JsonObject::new();
+        if self.peek()? == Some(b'}') {
+            self.reader.consume(1);
+            return Ok((object, false));
+        }
+        loop {
+            let key = self.key()?;
+            if key == "features" && self.peek()? == Some(b'[') {
+                self.reader.consume(1);
+                return Ok((object, true));
+            }
+            object.insert(key, self.json()?);
+            match self.peek()? {
+                Some(b',') => self.reader.consume(1),
+                Some(b'}') => {
+                    self.reader.consume(1);
+                    return Ok((object, false));
+                }
+                byte => return Err(unexpected(byte)),
+            }
+        }
+    }
+
+    /// Reads a member's name and the colon after it.
+    fn key(&mut self) -> Result<String> {
+        if self.peek()? != Some(b'"') {
+            return Err(unexpected(self.peek()?));
+        }
+        let key = match self.json()? {
+            JsonValue::String(key) => key,
+            _ => unreachable!("a JSON value starting with a quote is a string"),
+        };
+        self.expect(b':')?;
+        Ok(key)
+    }
+
+    /// Reads one JSON value.
+    fn json(&mut self) -> Result<JsonValue> {
+        match self.peek()? {
+            Some(b'{' | b'[' | b'"') => {
+                // serde_json reads these up to their last byte and no further
+                let mut values = serde_json::Deserializer::from_reader(&mut self.reader)
+                    .into_iter::<JsonValue>();
+                match values.next() {
+                    Some(value) => Ok(value?),
+                    None => Err(unexpected(None)),
+                }
+            }
+            _ => {
+                // but would read a byte past the end of a number or literal, which
+                // would be lost, so those are picked out here
+                let mut scalar = Vec::new();
+                while let Some(&byte) = self.reader.fill_buf()?.first() {
+                    if matches!(byte, b',' | b'}' | b']') || byte.is_ascii_whitespace() {
+                        break;
+                    }
+                    scalar.push(byte);
+                    self.reader.consume(1);
+                }
+                Ok(serde_json::from_slice(&scalar)?)
+            }
+        }
+    }
+
+    /// The next byte that isn't whitespace, or a record separator between GeoJSON
+    /// text sequences, without consuming it.
+    fn peek(&mut self) -> Result<Option<u8>> {
+        loop {
+            match self.reader.fill_buf()?.first() {
+                Some(&byte) if byte.is_ascii_whitespace() || byte == 0x1E => self.reader.consume(1),
+                byte => return Ok(byte.copied()),
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<()> {
+        match self.peek()? {
+            Some(byte) if byte == expected => {
+                self.reader.consume(1);
+                Ok(())
+            }
+            byte => Err(unexpected(byte)),
+        }
+    }
+}
+
+impl<R: Read, T: CoordFloat> Iterator for FeatureReader<R, T> {
+    type Item = Result<(Geometry<T>, JsonObject)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.advance();
+        if !matches!(next, Ok(Some(_))) {
+            self.state = State::Done;
+        }
+        let axis_order = self.axis_order;
+        next.transpose().map(|feature| {
+            feature.map(|(geometry, properties)| (geometry.ensure_lon_lat(axis_order), properties))
+        })
+    }
+}
+
+/// A feature or geometry read at the top level.
+pub(super) fn top_level<T: CoordFloat>(
+    object: JsonObject,
+) -> Result<Option<(Geometry<T>, JsonObject)>> {
+    match object.get("type").and_then(JsonValue::as_str) {
+        Some("Feature") => feature(Feature::try_from(object)?),
+        Some("FeatureCollection") => Err(Error::ExpectedProperty("features".into())),
+        _ => {
+            let geometry = geojson::Geometry::try_from(object)?;
+            Ok(Some((Geometry::try_from(geometry)?, JsonObject::new())))
+        }
+    }
+}
+
+fn feature<T: CoordFloat>(feature: Feature) -> Result<Option<(Geometry<T>, JsonObject)>> {
+    if feature.geometry.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(Tagged::try_from(feature)?.into_parts()))
+}
+
+fn unexpected(byte: Option<u8>) -> Error {
+    let message = match byte {
+        Some(byte) => format!("unexpected character {:?}", byte as char),
+        None => "unexpected end of input".into(),
+    };
+    Error::Io(io::Error::new(io::ErrorKind::InvalidData, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversion::geojson::read_geometries;
+    use crate::{LineStringZ, PointZ};
+
+    type Features = Vec<(Geometry<f64>, JsonObject)>;
+
+    fn read(json: &str) -> Result<Features> {
+        FeatureReader::new(json.as_bytes()).collect()
+    }
+
+    #[test]
+    fn feature_collection() {
+        let json = r#"{
+            "type": "FeatureCollection",
+            "bbox": [0, 0, 0, 4, 4, 1],
+            "features": [
+                {"type": "Feature", "id": 7, "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 2, 3]}},
+                {"type": "Feature", "properties": null, "geometry": null},
+                {"type": "Feature", "properties": null, "geometry": {"type": "LineString", "coordinates": [[0, 0], [4, 4, 1]]}}
+            ],
+            "name": "after the features",
+            "count": 3
+        }
+        {"type": "Point", "coordinates": [9, 9, 9]}"#;
+        let features = read(json).unwrap();
+        assert_eq!(features.len(), 3);
+        assert_eq!(features[0].0, Geometry::PointZ(PointZ::new(1., 2., 3.)));
+        assert_eq!(features[0].1["name"], "a");
+        assert_eq!(
+            features[1].0,
+            Geometry::LineStringZ(LineStringZ::from(vec![(0., 0., 0.), (4., 4., 1.)]))
+        );
+        assert!(features[1].1.is_empty());
+        assert_eq!(features[2].0, Geometry::PointZ(PointZ::new(9., 9., 9.)));
+
+        // the same as reading the whole document at once
+        let collection = &json[..json.rfind('{').unwrap()];
+        let all = read_geometries::<f64>(&mut collection.as_bytes().to_vec()).unwrap();
+        assert_eq!(all.0, vec![features[0].0.clone(), features[1].0.clone()]);
+    }
+
+    #[test]
+    fn sequences() {
+        // newline-delimited, with numbers and literals next to delimiters
+        let ndjson = "{\"type\":\"Feature\",\"id\":1,\"properties\":{\"a\":true},\"geometry\":{\"type\":\"Point\",\"coordinates\":[1,2,3]}}\n\
+            {\"id\":-2.5e1,\"geometry\":{\"type\":\"Point\",\"coordinates\":[4,5]},\"properties\":null,\"type\":\"Feature\"}\n";
+        let features = read(ndjson).unwrap();
+        assert_eq!(features.len(), 2);
+        assert_eq!(features[1].0, Geometry::PointZ(PointZ::new(4., 5., 0.)));
+
+        // RFC 8142 text sequences
+        let sequence = "\x1e{\"type\":\"Point\",\"coordinates\":[1,2,3]}\n\x1e{\"type\":\"Point\",\"coordinates\":[4,5,6]}\n";
+        assert_eq!(read(sequence).unwrap().len(), 2);
+        let swapped: Features = FeatureReader::new(sequence.as_bytes())
+            .with_axis_order(AxisOrder::LatLon)
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(swapped[1].0, Geometry::PointZ(PointZ::new(5., 4., 6.)));
+        assert!(read("").unwrap().is_empty());
+        assert!(read("{\"type\":\"FeatureCollection\",\"features\":[]}")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        let mut reader = FeatureReader::<_, f64>::new(
+            &b"{\"type\":\"Point\",\"coordinates\":[1,2]} [1, 2] {\"type\":\"Point\",\"coordinates\":[1,2]}"[..],
+        );
+        assert!(reader.next().unwrap().is_ok());
+        assert!(matches!(reader.next(), Some(Err(Error::Io(_)))));
+        assert!(reader.next().is_none());
+
+        assert!(
+            read("{\"type\":\"FeatureCollection\",\"features\":[{\"type\":\"Feature\"").is_err()
+        );
+        assert!(read("{\"type\":\"FeatureCollection\"}").is_err());
+        assert!(read("{\"type\":\"Point\",\"coordinates\":[1]}").is_err());
+        assert!(read("{\"type\":\"Point\",\"coordinates\":[1,2],}").is_err());
+    }
+}
@@ -0,0 +1,223 @@
+use core::fmt;
+use std::io::Write;
+
+use geojson::{JsonObject, Result};
+
+use super::write::GeometryWriter;
+use crate::algorithm::BoundingCube;
+use crate::{CoordFloat, Cube, Geometry};
+
+/// Writes features to any [`Write`] one at a time, as a GeoJSON
+/// `FeatureCollection`.
+///
+/// Each feature is written as soon as it is given, so a collection of any size can
+/// be written without holding it in memory. Geometries are written as by
+/// [`write_geometry`](super::write_geometry).
+///
+/// With [`with_bbox`](Self::with_bbox), each feature gets a 3D `bbox` member,
+/// `[minx, miny, minz, maxx, maxy, maxz]`, and so does the collection, after its
+/// features. Planar geometries are bounded at `z = 0`, and empty ones get no
+/// `bbox`.
+///
+/// Nothing closes the collection but [`finish`](Self::finish), which must be
+/// called once all the features are written.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::conversion::geojson::FeatureWriter;
+/// use geo_types_3d::{Geometry, PointZ};
+///
+/// let mut writer = FeatureWriter::new(Vec::new()).with_bbox(true);
+/// let mut properties = geojson::JsonObject::new();
+/// properties.insert("id".into(), 1.into());
+/// writer.write_feature(&Geometry::PointZ(PointZ::new(1.0, 2.0, 3.0)), &properties).unwrap();
+/// let out = writer.finish().unwrap();
+/// assert_eq!(
+///     String::from_utf8(out).unwrap(),
+///     r#"{"type":"FeatureCollection","features":[{"type":"Feature","bbox":[1.0,2.0,3.0,1.0,2.0,3.0],"properties":{"id":1},"geometry":{"type":"Point","coordinates":[1.0,2.0,3.0]}}],"bbox":[1.0,2.0,3.0,1.0,2.0,3.0]}"#
+/// );
+/// ```
+pub struct FeatureWriter<W: Write, T: CoordFloat = f64> {
+    writer: GeometryWriter<W>,
+    bbox: bool,
+    features: usize,
+    bounds: Option<Cube<T>>,
+}
+
+impl<W: Write, T: CoordFloat> FeatureWriter<W, T> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: GeometryWriter::new(writer),
+            bbox: false,
+            features: 0,
+            bounds: None,
+        }
+    }
+
+    /// Whether to write the `bbox` of each feature and of the collection.
+    pub fn with_bbox(mut self, bbox: bool) -> Self {
+        self.bbox = bbox;
+        self
+    }
+
+    /// The number of features written so far.
+    pub fn len(&self) -> usize {
+        self.features
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.features == 0
+    }
+
+    /// Writes a feature with `geometry` and `properties`.
+    pub fn write_feature(&mut self, geometry: &Geometry<T>, properties: &JsonObject) -> Result<()> {
+        let start: &[u8] = if self.features == 0 {
+            br#"{"type":"FeatureCollection","features":[{"type":"Feature""#
+        } else {
+            br#",{"type":"Feature""#
+        };
+        self.writer.writer.write_all(start)?;
+        if let Some(cube) = feature_members(&mut self.writer, geometry, properties, self.bbox)? {
+            self.bounds = Some(match self.bounds {
+                Some(bounds) => bounds.union(&cube),
+                None => cube,
+            });
+        }
+        self.features += 1;
+        Ok(())
+    }
+
+    /// Closes the collection, writing its `bbox` if asked to, and returns the
+    /// underlying writer, flushed.
+    pub fn finish(mut self) -> Result<W> {
+        let end: &[u8] = if self.features == 0 {
+            br#"{"type":"FeatureCollection","features":[]"#
+        } else {
+            b"]"
+        };
+        self.writer.writer.write_all(end)?;
+        if let Some(bounds) = self.bounds {
+            bbox(&mut self.writer, &bounds)?;
+        }
+        self.writer.writer.write_all(b"}")?;
+        self.writer.writer.flush()?;
+        Ok(self.writer.writer)
+    }
+}
+
+/// Writes the members of a feature after its `type`, and the brace closing it,
+/// returning the feature's bounds if they were written as its `bbox`.
+pub(super) fn feature_members<W: Write, T: CoordFloat>(
+    writer: &mut GeometryWriter<W>,
+    geometry: &Geometry<T>,
+    properties: &JsonObject,
+    with_bbox: bool,
+) -> Result<Option<Cube<T>>> {
+    let cube = if with_bbox {
+        geometry.bounding_cube()
+    } else {
+        None
+    };
+    if let Some(cube) = &cube {
+        bbox(writer, cube)?;
+    }
+    writer.writer.write_all(br#","properties":"#)?;
+    serde_json::to_writer(&mut writer.writer, properties)?;
+    writer.writer.write_all(br#","geometry":"#)?;
+    writer.geometry(geometry)?;
+    writer.writer.write_all(b"}")?;
+    Ok(cube)
+}
+
+fn bbox<W: Write, T: CoordFloat>(writer: &mut GeometryWriter<W>, cube: &Cube<T>) -> Result<()> {
+    let (min, max) = (cube.min(), cube.max());
+    writer.writer.write_all(br#","bbox":["#)?;
+    for (i, value) in [min.x, min.y, min.z, max.x, max.y, max.z]
+        .into_iter()
+        .enumerate()
+    {
+        if i > 0 {
+            writer.writer.write_all(b",")?;
+        }
+        writer.float(value)?;
+    }
+    writer.writer.write_all(b"]")?;
+    Ok(())
+}
+
+impl<W: Write, T: CoordFloat> fmt::Debug for FeatureWriter<W, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FeatureWriter")
+            .field("bbox", &self.bbox)
+            .field("features", &self.features)
+            .field("bounds", &self.bounds)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversion::geojson::FeatureReader;
+    use crate::{LineStringZ, PointZ};
+
+    #[test]
+    fn round_trip() {
+        let features = vec![
+            (
+                Geometry::PointZ(PointZ::new(1., 2., 3.)),
+                serde_json::json!({"name": "a", "tags": [1, 2]}),
+            ),
+            (
+                Geometry::LineStringZ(LineStringZ::from(vec![(-1., 5., 0.), (4., 0., 10.)])),
+                serde_json::json!({}),
+            ),
+            (
+                Geometry::Point(geo_types::Point::new(0., 9.)),
+                serde_json::json!({"flat": true}),
+            ),
+            (
+                Geometry::LineStringZ(LineStringZ::new(vec![])),
+                serde_json::json!({}),
+            ),
+        ];
+        let mut writer = FeatureWriter::new(Vec::new()).with_bbox(true);
+        for (geometry, properties) in &features {
+            writer
+                .write_feature(geometry, properties.as_object().unwrap())
+                .unwrap();
+        }
+        assert_eq!(writer.len(), 4);
+        let out = writer.finish().unwrap();
+
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json["bbox"], serde_json::json!([-1., 0., 0., 4., 9., 10.]));
+        assert_eq!(
+            json["features"][1]["bbox"],
+            serde_json::json!([-1., 0., 0., 4., 5., 10.])
+        );
+        assert_eq!(
+            json["features"][2]["bbox"],
+            serde_json::json!([0., 9., 0., 0., 9., 0.])
+        );
+        assert!(json["features"][3].get("bbox").is_none());
+
+        let read: Vec<_> = FeatureReader::<_, f64>::new(&out[..])
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(read.len(), 4);
+        assert_eq!(read[0].0, features[0].0);
+        assert_eq!(&read[0].1, features[0].1.as_object().unwrap());
+        assert_eq!(read[1].0, features[1].0);
+    }
+
+    #[test]
+    fn empty() {
+        let out = FeatureWriter::<_, f64>::new(Vec::new())
+            .with_bbox(true)
+            .finish()
+            .unwrap();
+        assert_eq!(out, br#"{"type":"FeatureCollection","features":[]}"#);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{CoordFloat};
+use crate::algorithm::BoundingCube;
+use crate::{CoordFloat, Cube};
 
 use geojson::{Feature, FeatureCollection};
 
@@ -17,6 +18,16 @@ where
     }
 }
 
+/// The measure is written as a fourth ordinate.
+impl<T> From<&crate::PointZM<T>> for geojson::Value
+where
+    T: CoordFloat,
+{
+    fn from(point: &crate::PointZM<T>) -> Self {
+        geojson::Value::Point(create_point_zm_type(&point.0))
+    }
+}
+
 // #[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
 impl<T> From<&crate::MultiPointZ<T>> for geojson::Value
 where
@@ -45,6 +56,16 @@ where
     }
 }
 
+/// Measures are written as a fourth ordinate.
+impl<T> From<&crate::LineStringZM<T>> for geojson::Value
+where
+    T: CoordFloat,
+{
+    fn from(line_string: &crate::LineStringZM<T>) -> Self {
+        geojson::Value::LineString(line_string.0.iter().map(create_point_zm_type).collect())
+    }
+}
+
 // #[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
 impl<T> From<&crate::LineZ<T>> for geojson::Value
 where
@@ -153,6 +174,128 @@ where
     }
 }
 
+/// The data becomes the properties of the feature.
+impl<G> From<&crate::Tagged<G, geojson::JsonObject>> for Feature
+where
+    for<'a> &'a G: Into<geojson::Value>,
+{
+    fn from(tagged: &crate::Tagged<G, geojson::JsonObject>) -> Self {
+        Feature {
+            bbox: None,
+            geometry: Some(geojson::Geometry::new((&tagged.geometry).into())),
+            id: None,
+            properties: Some(tagged.data.clone()),
+            foreign_members: None,
+        }
+    }
+}
+
+impl<G> From<&crate::TaggedCollection<G, geojson::JsonObject>> for FeatureCollection
+where
+    for<'a> &'a G: Into<geojson::Value>,
+{
+    fn from(collection: &crate::TaggedCollection<G, geojson::JsonObject>) -> Self {
+        FeatureCollection {
+            bbox: None,
+            features: collection.iter().map(Feature::from).collect(),
+            foreign_members: None,
+        }
+    }
+}
+
+/// Options for building [`Feature`]s and [`FeatureCollection`]s from geometries.
+///
+/// The [`From`] conversions use the default options.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::conversion::geojson::FeatureOptions;
+/// use geo_types_3d::{Geometry, GeometryCollection, PointZ};
+///
+/// let collection = GeometryCollection::new_from(vec![
+///     Geometry::PointZ(PointZ::new(1.0, 2.0, 3.0)),
+///     Geometry::PointZ(PointZ::new(4.0, 0.0, 1.0)),
+/// ]);
+/// let features = FeatureOptions { bbox: true }.geometry_collection(&collection);
+/// assert_eq!(features.bbox, Some(vec![1.0, 0.0, 1.0, 4.0, 2.0, 3.0]));
+/// assert_eq!(features.features[0].bbox, Some(vec![1.0, 2.0, 3.0, 1.0, 2.0, 3.0]));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FeatureOptions {
+    /// Whether to fill in the `bbox` of each feature, and of the collection, with
+    /// its 3D bounding box `[minx, miny, minz, maxx, maxy, maxz]`. Planar geometries
+    /// are bounded at `z = 0`, and empty ones get no `bbox`.
+    pub bbox: bool,
+}
+
+impl FeatureOptions {
+    /// Builds a feature like [`Feature::from`], whose properties are the data.
+    pub fn feature<T, G>(&self, tagged: &crate::Tagged<G, geojson::JsonObject>) -> Feature
+    where
+        T: CoordFloat,
+        G: BoundingCube<T>,
+        for<'a> &'a G: Into<geojson::Value>,
+    {
+        Feature {
+            bbox: self.bbox(|| tagged.geometry.bounding_cube().into()),
+            ..Feature::from(tagged)
+        }
+    }
+
+    /// Builds a feature collection like [`FeatureCollection::from`], with a feature
+    /// for each tagged geometry.
+    pub fn feature_collection<T, G>(
+        &self,
+        collection: &crate::TaggedCollection<G, geojson::JsonObject>,
+    ) -> FeatureCollection
+    where
+        T: CoordFloat,
+        G: BoundingCube<T>,
+        for<'a> &'a G: Into<geojson::Value>,
+    {
+        FeatureCollection {
+            bbox: self.bbox(|| collection.bounding_cube()),
+            features: collection
+                .iter()
+                .map(|tagged| self.feature(tagged))
+                .collect(),
+            foreign_members: None,
+        }
+    }
+
+    /// Builds a feature collection like [`FeatureCollection::from`], with a feature
+    /// without properties for each member of `collection`.
+    pub fn geometry_collection<T: CoordFloat>(
+        &self,
+        collection: &crate::GeometryCollection<T>,
+    ) -> FeatureCollection {
+        let features = collection
+            .iter()
+            .map(|geometry| Feature {
+                bbox: self.bbox(|| geometry.bounding_cube()),
+                ..geojson::Geometry::new(geojson::Value::from(geometry)).into()
+            })
+            .collect();
+        FeatureCollection {
+            bbox: self.bbox(|| collection.bounding_cube()),
+            features,
+            foreign_members: None,
+        }
+    }
+
+    fn bbox<T: CoordFloat>(&self, cube: impl FnOnce() -> Option<Cube<T>>) -> Option<geojson::Bbox> {
+        if !self.bbox {
+            return None;
+        }
+        let (min, max) = cube().map(|cube| (cube.min(), cube.max()))?;
+        [min.x, min.y, min.z, max.x, max.y, max.z]
+            .into_iter()
+            .map(|value| value.to_f64())
+            .collect()
+    }
+}
+
 // #[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
 impl<'a, T> From<&'a crate::Geometry<T>> for geojson::Value
 where
@@ -162,20 +305,30 @@ where
     fn from(geojson: &'a crate::Geometry<T>) -> Self {
         match *geojson {
             crate::Geometry::Point(ref point) => geojson::Value::from(point),
+            crate::Geometry::PointZ(ref point) => geojson::Value::from(point),
+            crate::Geometry::MultiPoint(ref multi_point) => geojson::Value::from(multi_point),
             crate::Geometry::MultiPointZ(ref multi_point) => geojson::Value::from(multi_point),
             crate::Geometry::LineString(ref line_string) => geojson::Value::from(line_string),
+            crate::Geometry::LineStringZ(ref line_string) => geojson::Value::from(line_string),
             crate::Geometry::Line(ref line) => geojson::Value::from(line),
+            crate::Geometry::LineZ(ref line) => geojson::Value::from(line),
             // crate::Geometry::Triangle(_) => geojson::Value::Polygon(vec![]),
             crate::Geometry::Rect(ref rect) => geojson::Value::from(rect),
             crate::Geometry::GeometryCollection(ref gc) => geojson::Value::from(gc),
             crate::Geometry::MultiLineString(ref multi_line_string) => {
                 geojson::Value::from(multi_line_string)
             }
+            crate::Geometry::MultiLineStringZ(ref multi_line_string) => {
+                geojson::Value::from(multi_line_string)
+            }
             crate::Geometry::Polygon(ref polygon) => geojson::Value::from(polygon),
+            crate::Geometry::PolygonZ(ref polygon) => geojson::Value::from(polygon),
             crate::Geometry::MultiPolygon(ref multi_polygon) => {
                 geojson::Value::from(multi_polygon)
             }
-            _ => panic!("Not valid geojson {:?}", geojson), // TODO: handle this
+            crate::Geometry::MultiPolygonZ(ref multi_polygon) => {
+                geojson::Value::from(multi_polygon)
+            }
         }
     }
 }
@@ -186,8 +339,17 @@ where
 {
     let x: f64 = point.x().to_f64().unwrap();
     let y: f64 = point.y().to_f64().unwrap();
+    let z: f64 = point.z().to_f64().unwrap();
 
-    vec![x, y]
+    vec![x, y, z]
+}
+
+fn create_point_zm_type<T>(coord: &crate::CoordZM<T>) -> PointType
+where
+    T: CoordFloat,
+{
+    let (x, y, z, m) = coord.x_y_z_m();
+    [x, y, z, m].iter().map(|v| v.to_f64().unwrap()).collect()
 }
 
 fn create_line_string_type<T>(line_string: &crate::LineStringZ<T>) -> LineStringType
@@ -353,7 +515,7 @@ mod tests {
         if let Value::LineString(c) = geojson_line_point {
             assert_almost_eq!(p1.x(), c[0][0], 1e-6);
             assert_almost_eq!(p1.y(), c[0][1], 1e-6);
-            assert_almost_eq!(p1.z(), c[0][1], 1e-6);
+            assert_almost_eq!(p1.z(), c[0][2], 1e-6);
             assert_almost_eq!(p2.x(), c[1][0], 1e-6);
             assert_almost_eq!(p2.y(), c[1][1], 1e-6);
             assert_almost_eq!(p2.z(), c[1][2], 1e-6);
@@ -567,7 +729,7 @@ mod tests {
         let p1 = crate::PointZ::new(100.0f64, 0.0f64, 0.0f64);
         let actual = serde_json::Value::from(geojson::GeoJson::from(&p1));
         let expected: serde_json::Value =
-            serde_json::json!({"coordinates": [100.0, 0.0, 0.0], "type": "PointZ"});
+            serde_json::json!({"coordinates": [100.0, 0.0, 0.0], "type": "Point"});
         assert_eq!(expected, actual);
     }
 
@@ -586,10 +748,55 @@ mod tests {
         let expected: serde_json::Value = serde_json::json!({
             "type": "GeometryCollection",
             "geometries": [
-                {"coordinates": [100.0, 0.0, 0.0], "type": "PointZ"},
-                {"coordinates": [200.0, 0.0, 0.0], "type": "PointZ"},
+                {"coordinates": [100.0, 0.0, 0.0], "type": "Point"},
+                {"coordinates": [200.0, 0.0, 0.0], "type": "Point"},
             ]
         });
         assert_eq!(expected, serde_json::Value::from(actual));
     }
+
+    #[test]
+    fn feature_options_bbox() {
+        let mut properties = geojson::JsonObject::new();
+        properties.insert("name".into(), "line".into());
+        let collection: crate::TaggedCollection<crate::Geometry<f64>, _> = vec![
+            (
+                crate::Geometry::LineStringZ(LineStringZ::from(vec![(-1., 5., 0.), (4., 0., 10.)])),
+                properties,
+            ),
+            (
+                crate::Geometry::Point(geo_types::Point::new(0., 9.)),
+                geojson::JsonObject::new(),
+            ),
+            (
+                crate::Geometry::LineStringZ(LineStringZ::new(vec![])),
+                geojson::JsonObject::new(),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let features = super::FeatureOptions { bbox: true }.feature_collection(&collection);
+        assert_eq!(features.bbox, Some(vec![-1., 0., 0., 4., 9., 10.]));
+        assert_eq!(
+            features.features[0].bbox,
+            Some(vec![-1., 0., 0., 4., 5., 10.])
+        );
+        assert_eq!(features.features[0].property("name").unwrap(), "line");
+        assert_eq!(
+            features.features[1].bbox,
+            Some(vec![0., 9., 0., 0., 9., 0.])
+        );
+        assert_eq!(features.features[2].bbox, None);
+
+        // the defaults are those of the `From` conversions
+        let plain = super::FeatureOptions::default().feature_collection(&collection);
+        assert_eq!(plain, geojson::FeatureCollection::from(&collection));
+        assert_eq!(plain.bbox, None);
+
+        let empty = super::FeatureOptions { bbox: true }
+            .geometry_collection(&GeometryCollection::<f64>::new_from(vec![]));
+        assert_eq!(empty.bbox, None);
+        assert!(empty.features.is_empty());
+    }
 }
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+// `geojson::Error` is defined upstream and can't be boxed from here.
+#![allow(clippy::result_large_err)]
+
 use geo_types::CoordFloat;
 
 use geojson::{GeoJson, Result};
@@ -74,8 +77,20 @@ macro_rules! try_from_owned_value {
     };
 }
 
+mod feature_reader;
+mod feature_writer;
 pub(crate) mod from_geo_types;
+mod ndjson;
+mod read;
 pub(crate) mod to_geo_types;
+mod write;
+
+pub use feature_reader::FeatureReader;
+pub use feature_writer::FeatureWriter;
+pub use from_geo_types::FeatureOptions;
+pub use ndjson::{NdjsonReader, NdjsonWriter};
+pub use read::{parse, read_geometries, read_geometries_with_limits};
+pub use write::{geometry_to_string, write_geometry};
 
 /// A shortcut for producing `geo_types` [GeometryCollection](../geo_types/struct.GeometryCollection.html) objects
 /// from arbitrary valid GeoJSON input.
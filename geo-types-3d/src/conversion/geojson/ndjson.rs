@@ -0,0 +1,361 @@
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::marker::PhantomData;
+
+#[cfg(feature = "multithreading")]
+use rayon::prelude::*;
+
+use geojson::{JsonObject, JsonValue, Result};
+
+use super::feature_reader::top_level;
+use super::feature_writer::feature_members;
+use super::write::GeometryWriter;
+use crate::algorithm::{AxisOrder, SwapXY};
+use crate::{CoordFloat, Geometry};
+
+type Item<T> = Result<(Geometry<T>, JsonObject)>;
+
+/// A decoded line: a feature or, for a feature without a geometry, nothing.
+type Decoded<T> = Result<Option<(Geometry<T>, JsonObject)>>;
+
+/// Decodes a chunk of lines.
+type Decode<T> = fn(Vec<String>) -> Vec<Decoded<T>>;
+
+/// Reads newline-delimited GeoJSON, one feature per line, from any [`Read`], as
+/// `(geometry, properties)` pairs.
+///
+/// [`FeatureReader`](super::FeatureReader) reads the same input, but splits it
+/// into features as it parses them. With the lines already apart, decoding them
+/// can be spread over threads with [`with_parallel`](Self::with_parallel), for
+/// the bulk exports of data providers where decoding is the bottleneck. Features
+/// are returned in the order of their lines either way.
+///
+/// Each line is a feature or a geometry, the latter with empty properties. Blank
+/// lines, and the record separators of [GeoJSON text sequences], are skipped, as
+/// are features without a geometry. The `@context` and other foreign members of
+/// [GeoJSON-LD] are ignored. Reading stops after the first error.
+///
+/// [GeoJSON text sequences]: https://www.rfc-editor.org/rfc/rfc8142
+/// [GeoJSON-LD]: https://geojson.org/geojson-ld/
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::conversion::geojson::NdjsonReader;
+/// use geo_types_3d::{Geometry, PointZ};
+///
+/// let ndjson = br#"{"type": "Feature", "properties": {"id": 1}, "geometry": {"type": "Point", "coordinates": [1, 2, 3]}}
+/// {"type": "Feature", "properties": {"id": 2}, "geometry": {"type": "Point", "coordinates": [4, 5, 6]}}
+/// "#;
+/// let mut features = NdjsonReader::<_, f64>::new(&ndjson[..]);
+/// let (geometry, properties) = features.next().unwrap().unwrap();
+/// assert_eq!(geometry, Geometry::PointZ(PointZ::new(1.0, 2.0, 3.0)));
+/// assert_eq!(properties["id"], 1);
+/// assert_eq!(features.count(), 1);
+/// ```
+#[derive(Debug)]
+pub struct NdjsonReader<R, T: CoordFloat = f64> {
+    reader: BufReader<R>,
+    axis_order: AxisOrder,
+    chunk_size: usize,
+    decode: Decode<T>,
+    decoded: VecDeque<Decoded<T>>,
+    done: bool,
+}
+
+impl<R: Read, T: CoordFloat> NdjsonReader<R, T> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            axis_order: AxisOrder::LonLat,
+            chunk_size: 1,
+            decode: |lines| lines.iter().map(|line| decode(line)).collect(),
+            decoded: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Sets the order of the horizontal axes of the input, as
+    /// [`FeatureReader::with_axis_order`](super::FeatureReader::with_axis_order)
+    /// does.
+    pub fn with_axis_order(mut self, axis_order: AxisOrder) -> Self {
+        self.axis_order = axis_order;
+        self
+    }
+
+    /// Decodes the lines on the rayon thread pool, `chunk_size` lines at a time.
+    ///
+    /// At most `chunk_size` lines and their features are held at a time.
+    ///
+    /// # Panics
+    ///
+    /// If `chunk_size` is zero.
+    #[cfg(feature = "multithreading")]
+    pub fn with_parallel(mut self, chunk_size: usize) -> Self
+    where
+        T: Send,
+    {
+        assert!(chunk_size > 0, "chunks must hold at least one line");
+        self.chunk_size = chunk_size;
+        self.decode = |lines| lines.par_iter().map(|line| decode(line)).collect();
+        self
+    }
+
+    /// Reads and decodes the next chunk of lines.
+    fn fill(&mut self) {
+        let mut lines = Vec::with_capacity(self.chunk_size);
+        while lines.len() < self.chunk_size {
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => {
+                    self.done = true;
+                    break;
+                }
+                Ok(_) => {
+                    if !line.trim_matches(is_separator).is_empty() {
+                        lines.push(line);
+                    }
+                }
+                Err(error) => {
+                    self.done = true;
+                    self.decoded.extend((self.decode)(lines));
+                    self.decoded.push_back(Err(error.into()));
+                    return;
+                }
+            }
+        }
+        self.decoded.extend((self.decode)(lines));
+    }
+}
+
+impl<R: Read, T: CoordFloat> Iterator for NdjsonReader<R, T> {
+    type Item = Item<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.decoded.pop_front() {
+                Some(Ok(None)) => continue,
+                Some(Ok(Some((geometry, properties)))) => {
+                    return Some(Ok((geometry.ensure_lon_lat(self.axis_order), properties)))
+                }
+                Some(Err(error)) => {
+                    self.done = true;
+                    self.decoded.clear();
+                    return Some(Err(error));
+                }
+                None if self.done => return None,
+                None => self.fill(),
+            }
+        }
+    }
+}
+
+fn is_separator(c: char) -> bool {
+    c.is_ascii_whitespace() || c == '\u{1e}'
+}
+
+fn decode<T: CoordFloat>(line: &str) -> Decoded<T> {
+    top_level(serde_json::from_str(line.trim_matches(is_separator))?)
+}
+
+/// Writes features to any [`Write`] as newline-delimited GeoJSON, one feature per
+/// line.
+///
+/// Features are written as by [`FeatureWriter`](super::FeatureWriter), each on its
+/// own line and ending with a newline, so the output can be appended to, split and
+/// concatenated freely. With [`with_context`](Self::with_context), each feature
+/// starts with a JSON-LD `@context`, making the output [GeoJSON-LD].
+///
+/// [GeoJSON-LD]: https://geojson.org/geojson-ld/
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::conversion::geojson::NdjsonWriter;
+/// use geo_types_3d::{Geometry, PointZ};
+///
+/// let mut writer = NdjsonWriter::new(Vec::new());
+/// let mut properties = geojson::JsonObject::new();
+/// properties.insert("id".into(), 1.into());
+/// writer.write_feature(&Geometry::PointZ(PointZ::new(1.0, 2.0, 3.0)), &properties).unwrap();
+/// let out = writer.finish().unwrap();
+/// assert_eq!(
+///     String::from_utf8(out).unwrap(),
+///     "{\"type\":\"Feature\",\"properties\":{\"id\":1},\"geometry\":{\"type\":\"Point\",\"coordinates\":[1.0,2.0,3.0]}}\n"
+/// );
+/// ```
+pub struct NdjsonWriter<W: Write, T: CoordFloat = f64> {
+    writer: GeometryWriter<W>,
+    bbox: bool,
+    context: Option<JsonValue>,
+    features: usize,
+    coords: PhantomData<T>,
+}
+
+impl<W: Write, T: CoordFloat> NdjsonWriter<W, T> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: GeometryWriter::new(writer),
+            bbox: false,
+            context: None,
+            features: 0,
+            coords: PhantomData,
+        }
+    }
+
+    /// Whether to write the `bbox` of each feature.
+    pub fn with_bbox(mut self, bbox: bool) -> Self {
+        self.bbox = bbox;
+        self
+    }
+
+    /// Sets the JSON-LD `@context` written with each feature, such as
+    /// `"https://geojson.org/geojson-ld/geojson-context.jsonld"`.
+    pub fn with_context(mut self, context: impl Into<JsonValue>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+
+    /// The number of features written so far.
+    pub fn len(&self) -> usize {
+        self.features
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.features == 0
+    }
+
+    /// Writes a feature with `geometry` and `properties`, and the newline after it.
+    pub fn write_feature(&mut self, geometry: &Geometry<T>, properties: &JsonObject) -> Result<()> {
+        self.writer.writer.write_all(b"{")?;
+        if let Some(context) = &self.context {
+            self.writer.writer.write_all(br#""@context":"#)?;
+            serde_json::to_writer(&mut self.writer.writer, context)?;
+            self.writer.writer.write_all(b",")?;
+        }
+        self.writer.writer.write_all(br#""type":"Feature""#)?;
+        feature_members(&mut self.writer, geometry, properties, self.bbox)?;
+        self.writer.writer.write_all(b"\n")?;
+        self.features += 1;
+        Ok(())
+    }
+
+    /// Returns the underlying writer, flushed.
+    pub fn finish(mut self) -> Result<W> {
+        self.writer.writer.flush()?;
+        Ok(self.writer.writer)
+    }
+}
+
+impl<W: Write, T: CoordFloat> core::fmt::Debug for NdjsonWriter<W, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NdjsonWriter")
+            .field("bbox", &self.bbox)
+            .field("context", &self.context)
+            .field("features", &self.features)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversion::geojson::FeatureReader;
+    use crate::{LineStringZ, PointZ};
+
+    type Features = Vec<(Geometry<f64>, JsonObject)>;
+
+    fn features() -> Features {
+        (0..100)
+            .map(|n: u32| {
+                let i = f64::from(n);
+                let geometry = if n % 2 == 0 {
+                    Geometry::PointZ(PointZ::new(i, -i, 10. * i))
+                } else {
+                    Geometry::LineStringZ(LineStringZ::from(vec![(i, 0., 0.), (0., i, 1.)]))
+                };
+                let properties = serde_json::json!({"id": i, "name": format!("feature {i}")});
+                (geometry, properties.as_object().unwrap().clone())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        let features = features();
+        let mut writer = NdjsonWriter::new(Vec::new())
+            .with_bbox(true)
+            .with_context("https://geojson.org/geojson-ld/geojson-context.jsonld");
+        for (geometry, properties) in &features {
+            writer.write_feature(geometry, properties).unwrap();
+        }
+        assert_eq!(writer.len(), 100);
+        let mut out = writer.finish().unwrap();
+        assert_eq!(out.iter().filter(|&&byte| byte == b'\n').count(), 100);
+        let first: serde_json::Value =
+            serde_json::from_slice(out.split(|&b| b == b'\n').next().unwrap()).unwrap();
+        assert_eq!(first["bbox"], serde_json::json!([0., 0., 0., 0., 0., 0.]));
+        assert!(first["@context"].is_string());
+
+        let read: Features = NdjsonReader::new(&out[..]).collect::<Result<_>>().unwrap();
+        assert_eq!(read, features);
+        let streamed: Features = FeatureReader::new(&out[..]).collect::<Result<_>>().unwrap();
+        assert_eq!(streamed, features);
+
+        // blank lines, a feature without a geometry and a bare geometry
+        out.extend_from_slice(
+            b"\n\r\n{\"type\":\"Feature\",\"properties\":null,\"geometry\":null}\n",
+        );
+        out.extend_from_slice(b"\x1e{\"type\":\"Point\",\"coordinates\":[1,2,3]}");
+        let read: Features = NdjsonReader::new(&out[..]).collect::<Result<_>>().unwrap();
+        assert_eq!(read.len(), 101);
+        assert_eq!(read[100].0, Geometry::PointZ(PointZ::new(1., 2., 3.)));
+
+        let swapped: Features = NdjsonReader::new(&out[..])
+            .with_axis_order(AxisOrder::LatLon)
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(swapped[100].0, Geometry::PointZ(PointZ::new(2., 1., 3.)));
+    }
+
+    #[test]
+    fn errors() {
+        let mut reader = NdjsonReader::<_, f64>::new(
+            &b"{\"type\":\"Point\",\"coordinates\":[1,2]}\n{\"type\":\"Point\"\n{\"type\":\"Point\",\"coordinates\":[1,2]}\n"[..],
+        );
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+        assert!(
+            NdjsonReader::<_, f64>::new(&b"{\"type\":\"Point\",\"coordinates\":[1]}"[..])
+                .next()
+                .unwrap()
+                .is_err()
+        );
+    }
+
+    #[cfg(feature = "multithreading")]
+    #[test]
+    fn parallel() {
+        let features = features();
+        let mut writer = NdjsonWriter::new(Vec::new());
+        for (geometry, properties) in &features {
+            writer.write_feature(geometry, properties).unwrap();
+        }
+        let mut out = writer.finish().unwrap();
+        for chunk_size in [1, 7, 100, 1000] {
+            let read: Features = NdjsonReader::new(&out[..])
+                .with_parallel(chunk_size)
+                .collect::<Result<_>>()
+                .unwrap();
+            assert_eq!(read, features);
+        }
+
+        // the features before a bad line in the same chunk, then the error
+        out.extend_from_slice(b"[]\n");
+        out.extend_from_slice(&out.clone());
+        let mut reader = NdjsonReader::<_, f64>::new(&out[..]).with_parallel(64);
+        assert_eq!(reader.by_ref().take_while(Result::is_ok).count(), 100);
+        assert!(reader.next().is_none());
+    }
+}
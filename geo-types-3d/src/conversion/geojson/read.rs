@@ -0,0 +1,447 @@
+use geojson::{GeoJson, Result};
+
+use crate::conversion::{Budget, ParseLimits};
+use crate::{CoordFloat, Error, GeometryCollection};
+
+/// Parses a GeoJSON document.
+///
+/// With the `simd-json` feature the document is parsed with [simd-json], which uses
+/// `bytes` as scratch space and leaves it modified. Otherwise it is parsed with
+/// `serde_json` and `bytes` is left untouched.
+///
+/// [simd-json]: https://docs.rs/simd-json
+pub fn parse(bytes: &mut [u8]) -> Result<GeoJson> {
+    #[cfg(feature = "simd-json")]
+    {
+        simd_json::serde::from_slice(bytes).map_err(simd::malformed)
+    }
+    #[cfg(not(feature = "simd-json"))]
+    {
+        serde_json::from_slice(bytes).map_err(geojson::Error::MalformedJson)
+    }
+}
+
+/// Reads the geometries of a GeoJSON document, skipping features without a
+/// geometry.
+///
+/// This gives the same result as parsing the document and converting it with
+/// [`GeometryCollection::try_from`], but with the `simd-json` feature the geometries
+/// are decoded straight from the parsed document, without building the
+/// intermediate [`GeoJson`] value and its feature properties. Like [`parse`], this
+/// may modify `bytes`.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::conversion::geojson::read_geometries;
+/// use geo_types_3d::{Geometry, PointZ};
+///
+/// let mut json = br#"{
+///     "type": "FeatureCollection",
+///     "features": [
+///         {"type": "Feature", "properties": {"id": 1}, "geometry": {"type": "Point", "coordinates": [1, 2, 3]}},
+///         {"type": "Feature", "properties": {"id": 2}, "geometry": null}
+///     ]
+/// }"#.to_vec();
+/// let geometries = read_geometries::<f64>(&mut json).unwrap();
+/// assert_eq!(geometries.0, vec![Geometry::PointZ(PointZ::new(1.0, 2.0, 3.0))]);
+/// ```
+pub fn read_geometries<T: CoordFloat>(bytes: &mut [u8]) -> Result<GeometryCollection<T>> {
+    #[cfg(feature = "simd-json")]
+    {
+        let value = simd_json::to_borrowed_value(bytes).map_err(simd::malformed)?;
+        simd::geometries(&value)
+    }
+    #[cfg(not(feature = "simd-json"))]
+    {
+        GeometryCollection::try_from(&parse(bytes)?)
+    }
+}
+
+/// Reads the geometries of a GeoJSON document like [`read_geometries`], failing
+/// with [`Error::LimitExceeded`] if they go beyond `limits`.
+///
+/// Use this on untrusted input. The limits are checked on the parsed document
+/// before any geometry is decoded; the size of the document itself is up to the
+/// caller. Other errors are returned as [`Error::GeoJson`].
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::conversion::geojson::read_geometries_with_limits;
+/// use geo_types_3d::conversion::ParseLimits;
+/// use geo_types_3d::Error;
+///
+/// let limits = ParseLimits {
+///     max_coordinates: 2,
+///     ..ParseLimits::default()
+/// };
+/// let mut json = br#"{"type": "LineString", "coordinates": [[0, 0, 0], [1, 1, 1], [2, 2, 2]]}"#.to_vec();
+/// assert!(matches!(
+///     read_geometries_with_limits::<f64>(&mut json, &limits),
+///     Err(Error::LimitExceeded { limit: "coordinates", max: 2 })
+/// ));
+/// ```
+pub fn read_geometries_with_limits<T: CoordFloat>(
+    bytes: &mut [u8],
+    limits: &ParseLimits,
+) -> core::result::Result<GeometryCollection<T>, Error> {
+    let mut budget = Budget::new(limits);
+    #[cfg(feature = "simd-json")]
+    {
+        let value = simd_json::to_borrowed_value(bytes).map_err(simd::malformed)?;
+        simd::check(&value, &mut budget)?;
+        Ok(simd::geometries(&value)?)
+    }
+    #[cfg(not(feature = "simd-json"))]
+    {
+        let geojson = parse(bytes)?;
+        check(&geojson, &mut budget)?;
+        Ok(GeometryCollection::try_from(&geojson)?)
+    }
+}
+
+#[cfg(not(feature = "simd-json"))]
+fn check(geojson: &GeoJson, budget: &mut Budget) -> core::result::Result<(), Error> {
+    match geojson {
+        GeoJson::Geometry(geometry) => check_geometry(geometry, budget, 0),
+        GeoJson::Feature(feature) => feature
+            .geometry
+            .iter()
+            .try_for_each(|geometry| check_geometry(geometry, budget, 0)),
+        GeoJson::FeatureCollection(collection) => collection
+            .features
+            .iter()
+            .filter_map(|feature| feature.geometry.as_ref())
+            .try_for_each(|geometry| check_geometry(geometry, budget, 0)),
+    }
+}
+
+/// Charges a geometry to `budget`, without recursing past the depth limit.
+#[cfg(not(feature = "simd-json"))]
+fn check_geometry(
+    geometry: &geojson::Geometry,
+    budget: &mut Budget,
+    depth: usize,
+) -> core::result::Result<(), Error> {
+    use geojson::Value;
+
+    let mut polygon = |rings: &[Vec<geojson::Position>]| {
+        budget.rings(rings.len())?;
+        rings
+            .iter()
+            .try_for_each(|ring| budget.coordinates(ring.len()))
+    };
+    match &geometry.value {
+        Value::Point(_) => budget.coordinates(1),
+        Value::MultiPoint(positions) | Value::LineString(positions) => {
+            budget.coordinates(positions.len())
+        }
+        Value::MultiLineString(lines) => lines
+            .iter()
+            .try_for_each(|line| budget.coordinates(line.len())),
+        Value::Polygon(rings) => polygon(rings),
+        Value::MultiPolygon(polygons) => polygons.iter().try_for_each(|rings| polygon(rings)),
+        Value::GeometryCollection(members) => {
+            budget.depth(depth + 1)?;
+            members
+                .iter()
+                .try_for_each(|member| check_geometry(member, budget, depth + 1))
+        }
+    }
+}
+
+#[cfg(feature = "simd-json")]
+mod simd {
+    use geojson::{Error, Result};
+    use simd_json::prelude::*;
+    use simd_json::BorrowedValue;
+
+    use crate::conversion::Budget;
+    use crate::{
+        CoordFloat, CoordZ, Geometry, GeometryCollection, LineStringZ, MultiLineStringZ,
+        MultiPointZ, MultiPolygonZ, PointZ, PolygonZ,
+    };
+
+    pub(super) fn malformed(err: simd_json::Error) -> Error {
+        Error::MalformedJson(serde::de::Error::custom(err))
+    }
+
+    pub(super) fn geometries<T: CoordFloat>(
+        value: &BorrowedValue,
+    ) -> Result<GeometryCollection<T>> {
+        let mut geometries = Vec::new();
+        match type_of(value)? {
+            "FeatureCollection" => {
+                let features = value
+                    .get("features")
+                    .and_then(|features| features.as_array())
+                    .ok_or_else(|| Error::ExpectedArrayValue("features".into()))?;
+                for feature in features {
+                    if let Some(geometry) = feature_geometry(feature)? {
+                        geometries.push(geometry);
+                    }
+                }
+            }
+            "Feature" => geometries.extend(feature_geometry(value)?),
+            _ => geometries.push(geometry(value)?),
+        }
+        Ok(GeometryCollection(geometries))
+    }
+
+    /// Charges the geometries of a document to `budget`, without recursing past the
+    /// depth limit. Malformed geometries are left for the decoder to reject.
+    pub(super) fn check(
+        value: &BorrowedValue,
+        budget: &mut Budget,
+    ) -> core::result::Result<(), crate::Error> {
+        match value.get("type").and_then(|ty| ty.as_str()) {
+            Some("FeatureCollection") => members(value.get("features"))
+                .iter()
+                .filter_map(|feature| feature.get("geometry"))
+                .try_for_each(|geometry| check_geometry(geometry, budget, 0)),
+            Some("Feature") => value
+                .get("geometry")
+                .map_or(Ok(()), |geometry| check_geometry(geometry, budget, 0)),
+            _ => check_geometry(value, budget, 0),
+        }
+    }
+
+    fn check_geometry(
+        value: &BorrowedValue,
+        budget: &mut Budget,
+        depth: usize,
+    ) -> core::result::Result<(), crate::Error> {
+        let coordinates = || members(value.get("coordinates"));
+        let polygon = |budget: &mut Budget, rings: &[BorrowedValue]| {
+            budget.rings(rings.len())?;
+            rings
+                .iter()
+                .try_for_each(|ring| budget.coordinates(members(Some(ring)).len()))
+        };
+        match value.get("type").and_then(|ty| ty.as_str()) {
+            Some("Point") => budget.coordinates(1),
+            Some("MultiPoint" | "LineString") => budget.coordinates(coordinates().len()),
+            Some("MultiLineString") => coordinates()
+                .iter()
+                .try_for_each(|line| budget.coordinates(members(Some(line)).len())),
+            Some("Polygon") => polygon(budget, coordinates()),
+            Some("MultiPolygon") => coordinates()
+                .iter()
+                .try_for_each(|rings| polygon(budget, members(Some(rings)))),
+            Some("GeometryCollection") => {
+                budget.depth(depth + 1)?;
+                members(value.get("geometries"))
+                    .iter()
+                    .try_for_each(|member| check_geometry(member, budget, depth + 1))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The elements of an array, or none if `value` is missing or not an array.
+    fn members<'v, 'a>(value: Option<&'v BorrowedValue<'a>>) -> &'v [BorrowedValue<'a>] {
+        value
+            .and_then(|value| value.as_array())
+            .map_or(&[], |array| array.as_slice())
+    }
+
+    fn feature_geometry<T: CoordFloat>(feature: &BorrowedValue) -> Result<Option<Geometry<T>>> {
+        match feature.get("geometry") {
+            None => Err(Error::ExpectedProperty("geometry".into())),
+            Some(geometry) if geometry.is_null() => Ok(None),
+            Some(value) => geometry(value).map(Some),
+        }
+    }
+
+    fn type_of<'v>(value: &'v BorrowedValue) -> Result<&'v str> {
+        value
+            .get("type")
+            .and_then(|ty| ty.as_str())
+            .ok_or_else(|| Error::ExpectedProperty("type".into()))
+    }
+
+    fn geometry<T: CoordFloat>(value: &BorrowedValue) -> Result<Geometry<T>> {
+        let ty = type_of(value)?;
+        if ty == "GeometryCollection" {
+            let members = value
+                .get("geometries")
+                .and_then(|geometries| geometries.as_array())
+                .ok_or_else(|| Error::ExpectedArrayValue("geometries".into()))?;
+            return Ok(Geometry::GeometryCollection(GeometryCollection(
+                members.iter().map(geometry).collect::<Result<_>>()?,
+            )));
+        }
+        let coordinates = value
+            .get("coordinates")
+            .ok_or_else(|| Error::ExpectedProperty("coordinates".into()))?;
+        Ok(match ty {
+            "Point" => Geometry::PointZ(PointZ(position(coordinates)?)),
+            "MultiPoint" => Geometry::MultiPointZ(MultiPointZ(
+                array(coordinates)?
+                    .iter()
+                    .map(|coords| position(coords).map(PointZ))
+                    .collect::<Result<_>>()?,
+            )),
+            "LineString" => Geometry::LineStringZ(line_string(coordinates)?),
+            "MultiLineString" => {
+                Geometry::MultiLineStringZ(MultiLineStringZ(collect(coordinates, line_string)?))
+            }
+            "Polygon" => Geometry::PolygonZ(polygon(coordinates)?),
+            "MultiPolygon" => {
+                Geometry::MultiPolygonZ(MultiPolygonZ(collect(coordinates, polygon)?))
+            }
+            other => return Err(Error::GeometryUnknownType(other.into())),
+        })
+    }
+
+    fn array<'v, 'a>(value: &'v BorrowedValue<'a>) -> Result<&'v [BorrowedValue<'a>]> {
+        value
+            .as_array()
+            .map(|array| array.as_slice())
+            .ok_or_else(|| Error::ExpectedArrayValue("coordinates".into()))
+    }
+
+    fn collect<T, F>(value: &BorrowedValue, f: F) -> Result<Vec<T>>
+    where
+        F: Fn(&BorrowedValue) -> Result<T>,
+    {
+        array(value)?.iter().map(f).collect()
+    }
+
+    /// Positions without an elevation are read with `z = 0`.
+    fn position<T: CoordFloat>(value: &BorrowedValue) -> Result<CoordZ<T>> {
+        let position = array(value)?;
+        if position.len() < 2 {
+            return Err(Error::PositionTooShort(position.len()));
+        }
+        let ordinate = |value: &BorrowedValue| {
+            value
+                .cast_f64()
+                .and_then(T::from)
+                .ok_or(Error::ExpectedF64Value)
+        };
+        Ok(CoordZ {
+            x: ordinate(&position[0])?,
+            y: ordinate(&position[1])?,
+            z: position.get(2).map_or(Ok(T::zero()), ordinate)?,
+        })
+    }
+
+    fn line_string<T: CoordFloat>(value: &BorrowedValue) -> Result<LineStringZ<T>> {
+        collect(value, position).map(LineStringZ)
+    }
+
+    fn polygon<T: CoordFloat>(value: &BorrowedValue) -> Result<PolygonZ<T>> {
+        let mut rings = collect(value, line_string)?.into_iter();
+        let exterior = rings.next().unwrap_or_else(|| LineStringZ(Vec::new()));
+        Ok(PolygonZ::new(exterior, rings.collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const COLLECTION: &str = r#"{
+        "type": "FeatureCollection",
+        "features": [
+            {"type": "Feature", "properties": null, "geometry": {"type": "Point", "coordinates": [1.5, 2, 3]}},
+            {"type": "Feature", "properties": {"name": "flat"}, "geometry": {"type": "LineString", "coordinates": [[0, 0], [1, 1]]}},
+            {"type": "Feature", "properties": {}, "geometry": null},
+            {"type": "Feature", "properties": {}, "geometry": {
+                "type": "Polygon",
+                "coordinates": [[[0, 0, 1], [4, 0, 1], [4, 4, 1], [0, 0, 1]], [[1, 1, 1], [2, 1, 1], [2, 2, 1], [1, 1, 1]]]
+            }},
+            {"type": "Feature", "properties": {}, "geometry": {
+                "type": "GeometryCollection",
+                "geometries": [
+                    {"type": "MultiPoint", "coordinates": [[1, 2, 3], [4, 5, 6]]},
+                    {"type": "MultiLineString", "coordinates": [[[1, 2, 3], [4, 5, 6]]]},
+                    {"type": "MultiPolygon", "coordinates": [[[[0, 0, 0], [1, 0, 0], [1, 1, 0], [0, 0, 0]]]]}
+                ]
+            }}
+        ]
+    }"#;
+
+    #[test]
+    fn read_matches_conversion() {
+        let expected: GeometryCollection<f64> =
+            GeometryCollection::try_from(&GeoJson::from_str(COLLECTION).unwrap()).unwrap();
+        let mut bytes = COLLECTION.as_bytes().to_vec();
+        assert_eq!(read_geometries::<f64>(&mut bytes).unwrap(), expected);
+        assert_eq!(expected.len(), 4);
+
+        let mut bytes = COLLECTION.as_bytes().to_vec();
+        assert_eq!(
+            parse(&mut bytes).unwrap(),
+            GeoJson::from_str(COLLECTION).unwrap()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut malformed = br#"{"type": "Point", "coordinates": [1, "#.to_vec();
+        assert!(matches!(
+            read_geometries::<f64>(&mut malformed),
+            Err(geojson::Error::MalformedJson(_))
+        ));
+        let mut malformed = br#"{"type": "Point", "coordinates": [1, "#.to_vec();
+        assert!(parse(&mut malformed).is_err());
+
+        let mut short = br#"{"type": "Point", "coordinates": [1]}"#.to_vec();
+        assert!(read_geometries::<f64>(&mut short).is_err());
+    }
+
+    #[test]
+    fn limits() {
+        let read = |json: &str, limits: &ParseLimits| {
+            read_geometries_with_limits::<f64>(&mut json.as_bytes().to_vec(), limits)
+        };
+        let strict = |max_coordinates, max_depth, max_rings| ParseLimits {
+            max_coordinates,
+            max_depth,
+            max_rings,
+        };
+        assert_eq!(
+            read(COLLECTION, &ParseLimits::default()).unwrap(),
+            GeometryCollection::try_from(&GeoJson::from_str(COLLECTION).unwrap()).unwrap()
+        );
+        // 1 + 2 + 8 + 2 + 2 + 4 coordinates, one collection, two rings
+        assert!(read(COLLECTION, &strict(19, 1, 2)).is_ok());
+        assert!(matches!(
+            read(COLLECTION, &strict(18, 1, 2)),
+            Err(Error::LimitExceeded {
+                limit: "coordinates",
+                max: 18
+            })
+        ));
+        assert!(matches!(
+            read(COLLECTION, &strict(19, 0, 2)),
+            Err(Error::LimitExceeded { max: 0, .. })
+        ));
+        assert!(matches!(
+            read(COLLECTION, &strict(19, 1, 1)),
+            Err(Error::LimitExceeded { max: 1, .. })
+        ));
+
+        let nested = format!(
+            "{}{}",
+            r#"{"type": "GeometryCollection", "geometries": ["#.repeat(50),
+            "]}".repeat(50)
+        );
+        assert!(read(&nested, &ParseLimits::UNLIMITED).is_ok());
+        assert!(matches!(
+            read(&nested, &ParseLimits::default()),
+            Err(Error::LimitExceeded { max: 32, .. })
+        ));
+        assert!(matches!(
+            read(
+                r#"{"type": "Point", "coordinates": [1]}"#,
+                &ParseLimits::default()
+            ),
+            Err(Error::GeoJson(_))
+        ));
+    }
+}
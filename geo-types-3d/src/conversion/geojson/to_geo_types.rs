@@ -19,6 +19,23 @@ where
 }
 try_from_owned_value!(crate::PointZ<T>);
 
+/// Reads the measure from the fourth ordinate. Positions without one are read
+/// with an `m` of NaN, and without an elevation with `z = 0`.
+impl<T> TryFrom<&Value> for crate::PointZM<T>
+where
+    T: CoordFloat,
+{
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self> {
+        match value {
+            Value::Point(point_type) => Ok(crate::PointZM(create_geo_coordinate_zm(point_type))),
+            other => Err(mismatch_geom_err("Point", other)),
+        }
+    }
+}
+try_from_owned_value!(crate::PointZM<T>);
+
 #[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
 impl<T> TryFrom<&Value> for crate::MultiPointZ<T>
 where
@@ -58,6 +75,27 @@ where
 }
 try_from_owned_value!(crate::LineStringZ<T>);
 
+/// Reads measures like [`PointZM`](crate::PointZM) does.
+impl<T> TryFrom<&Value> for crate::LineStringZM<T>
+where
+    T: CoordFloat,
+{
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self> {
+        match value {
+            Value::LineString(line_string_type) => Ok(crate::LineStringZM(
+                line_string_type
+                    .iter()
+                    .map(|point_type| create_geo_coordinate_zm(point_type))
+                    .collect(),
+            )),
+            other => Err(mismatch_geom_err("LineString", other)),
+        }
+    }
+}
+try_from_owned_value!(crate::LineStringZM<T>);
+
 #[cfg_attr(docsrs, doc(cfg(feature = "geo-types")))]
 impl<T> TryFrom<&Value> for crate::MultiLineStringZ<T>
 where
@@ -302,6 +340,44 @@ where
     }
 }
 
+/// The properties of the feature become the data, and a feature without
+/// properties gets an empty object. Fails for features without a geometry.
+impl<T> TryFrom<Feature> for crate::Tagged<crate::Geometry<T>, geojson::JsonObject>
+where
+    T: CoordFloat,
+{
+    type Error = Error;
+
+    fn try_from(mut feature: Feature) -> Result<Self> {
+        let geometry = match feature.geometry.take() {
+            Some(geometry) => geometry.try_into()?,
+            None => return Err(Error::FeatureHasNoGeometry(feature)),
+        };
+        Ok(crate::Tagged::new(
+            geometry,
+            feature.properties.unwrap_or_default(),
+        ))
+    }
+}
+
+impl<T> TryFrom<FeatureCollection>
+    for crate::TaggedCollection<crate::Geometry<T>, geojson::JsonObject>
+where
+    T: CoordFloat,
+{
+    type Error = Error;
+
+    fn try_from(collection: FeatureCollection) -> Result<Self> {
+        collection
+            .features
+            .into_iter()
+            .map(crate::Tagged::try_from)
+            .collect::<Result<Vec<_>>>()
+            .map(crate::TaggedCollection)
+    }
+}
+
+/// Positions without an elevation are read with `z = 0`.
 fn create_geo_coordinate<T>(point_type: &PointType) -> crate::CoordZ<T>
 where
     T: CoordFloat,
@@ -309,21 +385,30 @@ where
     crate::CoordZ {
         x: T::from(point_type[0]).unwrap(),
         y: T::from(point_type[1]).unwrap(),
-        z: T::from(point_type[2]).unwrap(),
+        z: point_type
+            .get(2)
+            .map_or_else(T::zero, |z| T::from(*z).unwrap()),
     }
 }
 
-fn create_geo_point<T>(point_type: &PointType) -> crate::PointZ<T>
+fn create_geo_coordinate_zm<T>(point_type: &PointType) -> crate::CoordZM<T>
 where
     T: CoordFloat,
 {
-    crate::PointZ::new(
-        T::from(point_type[0]).unwrap(),
-        T::from(point_type[1]).unwrap(),
-        T::from(point_type[2]).unwrap(),
+    create_geo_coordinate(point_type).with_m(
+        point_type
+            .get(3)
+            .map_or_else(T::nan, |m| T::from(*m).unwrap()),
     )
 }
 
+fn create_geo_point<T>(point_type: &PointType) -> crate::PointZ<T>
+where
+    T: CoordFloat,
+{
+    crate::PointZ(create_geo_coordinate(point_type))
+}
+
 fn create_geo_line_string<T>(line_type: &LineStringType) -> crate::LineStringZ<T>
 where
     T: CoordFloat,
@@ -392,7 +477,7 @@ fn mismatch_geom_err(expected_type: &'static str, found: &Value) -> Error {
 
 #[cfg(test)]
 mod tests {
-    use geojson::{Geometry, Value};
+    use geojson::{Error, FeatureCollection, Geometry, Value};
     use serde_json::json;
 
     use std::convert::TryInto;
@@ -696,10 +781,10 @@ mod tests {
             crate::Geometry::GeometryCollection(crate::GeometryCollection(vec![
                 crate::Geometry::PolygonZ(crate::PolygonZ::new(
                     crate::LineStringZ::new(vec![
-                        crate::coordZ!(x: 1.0, y: 1.0, z: 1.0),
-                        crate::coordZ!(x: 2.0, y: 2.0, z: 2.0),
-                        crate::coordZ!(x: 3.0, y: 1.0, z: 3.0),
-                        crate::coordZ!(x: 1.0, y: 1.0, z: 1.0),
+                        crate::coordZ!(x: 1.0, y: 1.0, z: 0.0),
+                        crate::coordZ!(x: 2.0, y: 2.0, z: 0.0),
+                        crate::coordZ!(x: 3.0, y: 1.0, z: 0.0),
+                        crate::coordZ!(x: 1.0, y: 1.0, z: 0.0),
                     ]),
                     vec![],
                 )),
@@ -775,4 +860,47 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn tagged_features_test() {
+        let json = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 2, 3]}},
+                {"type": "Feature", "properties": null, "geometry": {"type": "LineString", "coordinates": [[0, 0, 0], [1, 1, 1]]}}
+            ]
+        }"#;
+        let collection: FeatureCollection = json.parse().unwrap();
+        let tagged: crate::TaggedCollection<crate::Geometry<f64>, geojson::JsonObject> =
+            collection.clone().try_into().unwrap();
+        assert_eq!(tagged.0[0].geometry, crate::PointZ::new(1., 2., 3.).into());
+        assert_eq!(tagged.0[0].data["name"], "a");
+        assert!(tagged.0[1].data.is_empty());
+
+        let written = FeatureCollection::from(&tagged);
+        assert_eq!(written.features[0], collection.features[0]);
+        assert_eq!(written.features[1].geometry, collection.features[1].geometry);
+
+        let mut pointless = collection.features[0].clone();
+        pointless.geometry = None;
+        assert!(matches!(
+            crate::Tagged::<crate::Geometry<f64>, _>::try_from(pointless),
+            Err(Error::FeatureHasNoGeometry(_))
+        ));
+    }
+
+    #[test]
+    fn measured_conversion_test() {
+        let value = Value::LineString(vec![vec![0., 0., 1., 10.], vec![3., 4., 1.]]);
+        let line_string: crate::LineStringZM<f64> = (&value).try_into().unwrap();
+        assert_eq!(line_string.0[0], crate::CoordZ { x: 0., y: 0., z: 1. }.with_m(10.));
+        assert!(line_string.0[1].m.is_nan());
+
+        let point = crate::PointZM::new(1., 2., 3., 4.);
+        let value = Value::from(&point);
+        assert_eq!(value, Value::Point(vec![1., 2., 3., 4.]));
+        assert_eq!(crate::PointZM::try_from(value).unwrap(), point);
+        let flat: crate::PointZ<f64> = (&Value::Point(vec![1., 2., 3., 4.])).try_into().unwrap();
+        assert_eq!(flat, point.xyz());
+    }
 }
@@ -0,0 +1,256 @@
+use std::io::Write;
+
+use geo_types::{Coord, LineString, Polygon};
+use geojson::{Error, Result};
+
+use crate::{CoordFloat, CoordZ, Geometry, LineStringZ, PolygonZ};
+
+/// Writes a geometry as a GeoJSON geometry object.
+///
+/// The output is the same as serializing [`geojson::Value::from`] with `serde_json`,
+/// but the coordinates are written directly, formatting floats with [ryu], instead
+/// of first being copied into a [`geojson::Value`]. Like `serde_json`, non-finite
+/// ordinates are written as `null`. `f32` ordinates are written in the shortest form
+/// that reads back as the same `f32`, not as the `f64` they widen to.
+///
+/// [ryu]: https://docs.rs/ryu
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::conversion::geojson::write_geometry;
+/// use geo_types_3d::{Geometry, PointZ};
+///
+/// let mut out = Vec::new();
+/// write_geometry(&Geometry::PointZ(PointZ::new(1.0, 2.5, 3.0)), &mut out).unwrap();
+/// assert_eq!(out, br#"{"type":"Point","coordinates":[1.0,2.5,3.0]}"#);
+/// ```
+pub fn write_geometry<T: CoordFloat, W: Write>(geometry: &Geometry<T>, writer: W) -> Result<()> {
+    GeometryWriter::new(writer)
+        .geometry(geometry)
+        .map_err(Error::Io)
+}
+
+/// Formats a geometry as a GeoJSON geometry object, like [`write_geometry`].
+pub fn geometry_to_string<T: CoordFloat>(geometry: &Geometry<T>) -> String {
+    let mut out = Vec::new();
+    write_geometry(geometry, &mut out).expect("writing to a Vec can't fail");
+    String::from_utf8(out).expect("GeoJSON output is valid UTF-8")
+}
+
+pub(super) struct GeometryWriter<W> {
+    pub(super) writer: W,
+    floats: ryu::Buffer,
+}
+
+impl<W: Write> GeometryWriter<W> {
+    pub(super) fn new(writer: W) -> Self {
+        Self {
+            writer,
+            floats: ryu::Buffer::new(),
+        }
+    }
+
+    pub(super) fn geometry<T: CoordFloat>(
+        &mut self,
+        geometry: &Geometry<T>,
+    ) -> std::io::Result<()> {
+        let ty = match geometry {
+            Geometry::Point(_) | Geometry::PointZ(_) => "Point",
+            Geometry::MultiPoint(_) | Geometry::MultiPointZ(_) => "MultiPoint",
+            Geometry::Line(_)
+            | Geometry::LineZ(_)
+            | Geometry::LineString(_)
+            | Geometry::LineStringZ(_) => "LineString",
+            Geometry::MultiLineString(_) | Geometry::MultiLineStringZ(_) => "MultiLineString",
+            Geometry::Polygon(_) | Geometry::PolygonZ(_) | Geometry::Rect(_) => "Polygon",
+            Geometry::MultiPolygon(_) | Geometry::MultiPolygonZ(_) => "MultiPolygon",
+            Geometry::GeometryCollection(collection) => {
+                self.writer
+                    .write_all(br#"{"type":"GeometryCollection","geometries":["#)?;
+                for (i, member) in collection.iter().enumerate() {
+                    if i > 0 {
+                        self.writer.write_all(b",")?;
+                    }
+                    self.geometry(member)?;
+                }
+                return self.writer.write_all(b"]}");
+            }
+        };
+        write!(self.writer, r#"{{"type":"{ty}","coordinates":"#)?;
+        match geometry {
+            Geometry::Point(point) => self.coord(point.0)?,
+            Geometry::PointZ(point) => self.coord_z(point.0)?,
+            Geometry::MultiPoint(multi_point) => {
+                self.list(&multi_point.0, |w, point| w.coord(point.0))?
+            }
+            Geometry::MultiPointZ(multi_point) => {
+                self.list(&multi_point.0, |w, point| w.coord_z(point.0))?
+            }
+            Geometry::Line(line) => self.list(&[line.start, line.end], |w, c| w.coord(*c))?,
+            Geometry::LineZ(line) => self.list(&[line.start, line.end], |w, c| w.coord_z(*c))?,
+            Geometry::LineString(line_string) => self.line_string(line_string)?,
+            Geometry::LineStringZ(line_string) => self.line_string_z(line_string)?,
+            Geometry::MultiLineString(multi) => self.list(&multi.0, Self::line_string)?,
+            Geometry::MultiLineStringZ(multi) => self.list(&multi.0, Self::line_string_z)?,
+            Geometry::Polygon(polygon) => self.polygon(polygon)?,
+            Geometry::PolygonZ(polygon) => self.polygon_z(polygon)?,
+            Geometry::Rect(rect) => self.polygon(&rect.to_polygon())?,
+            Geometry::MultiPolygon(multi) => self.list(&multi.0, Self::polygon)?,
+            Geometry::MultiPolygonZ(multi) => self.list(&multi.0, Self::polygon_z)?,
+            Geometry::GeometryCollection(_) => unreachable!(),
+        }
+        self.writer.write_all(b"}")
+    }
+
+    fn list<I>(
+        &mut self,
+        items: &[I],
+        mut item: impl FnMut(&mut Self, &I) -> std::io::Result<()>,
+    ) -> std::io::Result<()> {
+        self.writer.write_all(b"[")?;
+        for (i, value) in items.iter().enumerate() {
+            if i > 0 {
+                self.writer.write_all(b",")?;
+            }
+            item(self, value)?;
+        }
+        self.writer.write_all(b"]")
+    }
+
+    fn line_string<T: CoordFloat>(&mut self, line_string: &LineString<T>) -> std::io::Result<()> {
+        self.list(&line_string.0, |w, c| w.coord(*c))
+    }
+
+    fn line_string_z<T: CoordFloat>(
+        &mut self,
+        line_string: &LineStringZ<T>,
+    ) -> std::io::Result<()> {
+        self.list(&line_string.0, |w, c| w.coord_z(*c))
+    }
+
+    fn polygon<T: CoordFloat>(&mut self, polygon: &Polygon<T>) -> std::io::Result<()> {
+        self.writer.write_all(b"[")?;
+        self.line_string(polygon.exterior())?;
+        for interior in polygon.interiors() {
+            self.writer.write_all(b",")?;
+            self.line_string(interior)?;
+        }
+        self.writer.write_all(b"]")
+    }
+
+    fn polygon_z<T: CoordFloat>(&mut self, polygon: &PolygonZ<T>) -> std::io::Result<()> {
+        self.writer.write_all(b"[")?;
+        self.line_string_z(polygon.exterior())?;
+        for interior in polygon.interiors() {
+            self.writer.write_all(b",")?;
+            self.line_string_z(interior)?;
+        }
+        self.writer.write_all(b"]")
+    }
+
+    fn coord<T: CoordFloat>(&mut self, coord: Coord<T>) -> std::io::Result<()> {
+        self.writer.write_all(b"[")?;
+        self.float(coord.x)?;
+        self.writer.write_all(b",")?;
+        self.float(coord.y)?;
+        self.writer.write_all(b"]")
+    }
+
+    fn coord_z<T: CoordFloat>(&mut self, coord: CoordZ<T>) -> std::io::Result<()> {
+        self.writer.write_all(b"[")?;
+        self.float(coord.x)?;
+        self.writer.write_all(b",")?;
+        self.float(coord.y)?;
+        self.writer.write_all(b",")?;
+        self.float(coord.z)?;
+        self.writer.write_all(b"]")
+    }
+
+    pub(super) fn float<T: CoordFloat>(&mut self, value: T) -> std::io::Result<()> {
+        if !value.is_finite() {
+            return self.writer.write_all(b"null");
+        }
+        // single precision values are formatted as such, so that `0.1f32` is
+        // written as `0.1` rather than as the `f64` it widens to
+        let formatted = match value.to_f32() {
+            Some(single) if T::epsilon().to_f32() == Some(f32::EPSILON) => {
+                self.floats.format_finite(single)
+            }
+            _ => match value.to_f64() {
+                Some(double) => self.floats.format_finite(double),
+                None => return self.writer.write_all(b"null"),
+            },
+        };
+        self.writer.write_all(formatted.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeometryCollection, MultiPolygonZ, PointZ, Triangle};
+
+    fn assert_matches_geojson(geometry: Geometry<f64>) {
+        let expected =
+            serde_json::to_value(geojson::Geometry::new(geojson::Value::from(&geometry))).unwrap();
+        let written: serde_json::Value =
+            serde_json::from_str(&geometry_to_string(&geometry)).unwrap();
+        assert_eq!(written, expected);
+    }
+
+    #[test]
+    fn same_as_geojson_value() {
+        let polygon = Triangle::new(
+            CoordZ {
+                x: 0.,
+                y: 0.,
+                z: 1.,
+            },
+            CoordZ {
+                x: 1.,
+                y: 0.,
+                z: 2.,
+            },
+            CoordZ {
+                x: 0.,
+                y: 1.,
+                z: 3.,
+            },
+        )
+        .to_polygon();
+        let geometries = vec![
+            Geometry::PointZ(PointZ::new(1e-7, -2.5, 1e21)),
+            Geometry::Point(geo_types::Point::new(1., 2.)),
+            Geometry::LineStringZ(polygon.exterior().clone()),
+            Geometry::PolygonZ(polygon.clone()),
+            Geometry::MultiPolygonZ(MultiPolygonZ(vec![polygon.clone(), polygon])),
+            Geometry::Rect(geo_types::Rect::new((0., 0.), (1., 2.))),
+            Geometry::Line(geo_types::Line::new((0., 0.), (1., 2.))),
+        ];
+        for geometry in geometries.clone() {
+            assert_matches_geojson(geometry);
+        }
+        assert_matches_geojson(Geometry::GeometryCollection(GeometryCollection(geometries)));
+    }
+
+    #[test]
+    fn round_trip() {
+        let point = Geometry::PointZ(PointZ::new(0.1, 0.2, 0.30000000000000004));
+        let mut json = geometry_to_string(&point).into_bytes();
+        assert_eq!(
+            crate::conversion::geojson::read_geometries::<f64>(&mut json).unwrap()[0],
+            point
+        );
+        let single = Geometry::PointZ(PointZ::new(0.1f32, 16_777_216., -3.4e38));
+        assert_eq!(
+            geometry_to_string(&single),
+            r#"{"type":"Point","coordinates":[0.1,16777216.0,-3.4e38]}"#
+        );
+        let nan = Geometry::PointZ(PointZ::new(f64::NAN, 0., 0.));
+        assert_eq!(
+            geometry_to_string(&nan),
+            r#"{"type":"Point","coordinates":[null,0.0,0.0]}"#
+        );
+    }
+}
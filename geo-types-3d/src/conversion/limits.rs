@@ -0,0 +1,85 @@
+use crate::Error;
+
+/// Bounds on the size of the geometries a parser accepts.
+///
+/// Parsers that take limits check them before decoding any geometry, and fail with
+/// [`Error::LimitExceeded`] when the input goes beyond them. This keeps hostile
+/// input from exhausting memory or the stack. The defaults are generous enough for
+/// any real dataset; [`ParseLimits::UNLIMITED`] turns the checks off.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::conversion::ParseLimits;
+///
+/// let limits = ParseLimits {
+///     max_coordinates: 1_000,
+///     ..ParseLimits::default()
+/// };
+/// assert_eq!(limits.max_depth, ParseLimits::default().max_depth);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseLimits {
+    /// The most coordinates in the whole input.
+    pub max_coordinates: usize,
+    /// The deepest nesting of geometry collections. A collection that isn't inside
+    /// another is at depth 1.
+    pub max_depth: usize,
+    /// The most rings, exterior and interior, in any one polygon.
+    pub max_rings: usize,
+}
+
+impl ParseLimits {
+    pub const UNLIMITED: Self = Self {
+        max_coordinates: usize::MAX,
+        max_depth: usize::MAX,
+        max_rings: usize::MAX,
+    };
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self {
+            max_coordinates: 100_000_000,
+            max_depth: 32,
+            max_rings: 100_000,
+        }
+    }
+}
+
+/// Tracks what a parser has used of its [`ParseLimits`].
+#[derive(Debug)]
+pub(crate) struct Budget {
+    limits: ParseLimits,
+    coordinates: usize,
+}
+
+impl Budget {
+    pub(crate) fn new(limits: &ParseLimits) -> Self {
+        Self {
+            limits: *limits,
+            coordinates: 0,
+        }
+    }
+
+    pub(crate) fn coordinates(&mut self, count: usize) -> Result<(), Error> {
+        self.coordinates = self.coordinates.saturating_add(count);
+        check("coordinates", self.coordinates, self.limits.max_coordinates)
+    }
+
+    pub(crate) fn depth(&self, depth: usize) -> Result<(), Error> {
+        check("levels of nesting", depth, self.limits.max_depth)
+    }
+
+    pub(crate) fn rings(&self, count: usize) -> Result<(), Error> {
+        check("rings in a polygon", count, self.limits.max_rings)
+    }
+}
+
+fn check(limit: &'static str, used: usize, max: usize) -> Result<(), Error> {
+    if used > max {
+        Err(Error::LimitExceeded { limit, max })
+    } else {
+        Ok(())
+    }
+}
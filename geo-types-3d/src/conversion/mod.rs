@@ -1,3 +1,19 @@
+#[cfg(feature = "std")]
+pub mod delta;
+#[cfg(feature = "std")]
 pub mod geojson;
+#[cfg(feature = "std")]
 pub mod flatgeobuf;
-pub mod geozero;
\ No newline at end of file
+#[cfg(feature = "std")]
+pub mod geozero;
+#[cfg(feature = "http")]
+pub mod ogc_api;
+#[cfg(feature = "std")]
+pub mod polyline;
+#[cfg(feature = "pyo3")]
+pub mod python;
+pub mod wkb;
+
+mod limits;
+pub(crate) use limits::Budget;
+pub use limits::ParseLimits;
@@ -0,0 +1,462 @@
+//! A small client for [OGC API - Features] servers, fetching the items of a
+//! collection as features with their properties, following the server's paging.
+//!
+//! Items are requested as GeoJSON, in which positions may carry a z, and read as
+//! [`Geometry`] and a [`JsonObject`] of properties, as by
+//! [`FeatureReader`](super::geojson::FeatureReader). Items without a geometry are
+//! skipped. [`Items`] requests a page at a time as it is iterated, following each
+//! page's `next` link until a page has none, or has no features. With the `tokio`
+//! feature, `Items::into_stream` fetches the pages on tokio's blocking thread pool
+//! for async code.
+//!
+//! Each page is read into memory before it is decoded, and a page over 10 MB fails,
+//! so a large collection should be fetched in smaller pages with
+//! [`Items::limit`].
+//!
+//! [OGC API - Features]: https://ogcapi.ogc.org/features/
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use geo_types_3d::conversion::ogc_api::Client;
+//! use geo_types_3d::{Cube, Geometry, GeometryCollection, TaggedCollection};
+//!
+//! let client = Client::new("https://demo.pygeoapi.io/stable");
+//! let buildings: TaggedCollection<Geometry, _> = client
+//!     .items("buildings")
+//!     .limit(500)
+//!     .bbox(&Cube::new((7.0, 50.0, 0.0), (7.1, 50.1, 100.0)))
+//!     .collect::<Result<_, _>>()
+//!     .unwrap();
+//! let heights: Vec<_> = buildings.data().map(|properties| properties["height"].clone()).collect();
+//! let geometries = GeometryCollection(buildings.unzip().0);
+//! ```
+
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+use geojson::{Feature, FeatureCollection, JsonObject, JsonValue};
+use ureq::Agent;
+
+use crate::{CoordFloat, Cube, Error, Geometry, Tagged};
+
+/// A client for the OGC API - Features server at a landing page URL.
+#[derive(Clone, Debug)]
+pub struct Client {
+    agent: Agent,
+    base: String,
+}
+
+impl Client {
+    /// A client for the server whose landing page is at `base`, such as
+    /// `https://example.com/ogcapi`.
+    pub fn new(base: impl Into<String>) -> Self {
+        Self::with_agent(base, Agent::new_with_defaults())
+    }
+
+    /// Like [`new`](Self::new), but making requests with `agent`, for its proxy,
+    /// timeouts or TLS settings.
+    pub fn with_agent(base: impl Into<String>, agent: Agent) -> Self {
+        let mut base = base.into();
+        while base.ends_with('/') {
+            base.pop();
+        }
+        Self { agent, base }
+    }
+
+    /// The items of the collection with the identifier `collection`, fetched as
+    /// they are iterated.
+    pub fn items<T: CoordFloat>(&self, collection: &str) -> Items<T> {
+        Items {
+            agent: self.agent.clone(),
+            next: Some(format!("{}/collections/{collection}/items", self.base)),
+            query: Vec::new(),
+            page: VecDeque::new(),
+            coords: PhantomData,
+        }
+    }
+}
+
+/// The items of a collection, as `Tagged` geometries with their properties,
+/// fetched a page at a time. Iteration stops after the first error.
+///
+/// The query methods apply to the first request, and the server carries them
+/// into its `next` links.
+#[derive(Debug)]
+pub struct Items<T: CoordFloat = f64> {
+    agent: Agent,
+    /// The URL of the next page, if there is one.
+    next: Option<String>,
+    query: Vec<(String, String)>,
+    page: VecDeque<Feature>,
+    coords: PhantomData<T>,
+}
+
+impl<T: CoordFloat> Items<T> {
+    /// Asks for pages of at most `limit` items.
+    pub fn limit(self, limit: usize) -> Self {
+        self.param("limit", limit.to_string())
+    }
+
+    /// Asks for the items intersecting `cube`, in longitude, latitude and height.
+    pub fn bbox(self, cube: &Cube<T>) -> Self {
+        let (min, max) = (cube.min(), cube.max());
+        let bbox = [min.x, min.y, min.z, max.x, max.y, max.z]
+            .iter()
+            .map(|value| value.to_f64().unwrap_or(f64::NAN).to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        self.param("bbox", bbox)
+    }
+
+    /// Adds a query parameter, such as a `datetime` or a property filter.
+    pub fn param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push((key.into(), value.into()));
+        self
+    }
+
+    /// Fetches the next page into `self.page`.
+    fn fetch(&mut self, url: &str) -> Result<(), Error> {
+        let mut response = self
+            .agent
+            .get(url)
+            .header("Accept", "application/geo+json, application/json")
+            .query_pairs(self.query.drain(..).collect::<Vec<_>>())
+            .call()?;
+        let body = response.body_mut().read_to_vec()?;
+        let collection: FeatureCollection =
+            serde_json::from_slice(&body).map_err(geojson::Error::from)?;
+
+        if !collection.features.is_empty() {
+            self.next = next_link(&collection).map(|href| resolve(url, href));
+        }
+        self.page.extend(collection.features);
+        Ok(())
+    }
+}
+
+impl<T: CoordFloat> Items<T> {
+    /// The next item of the current page, without fetching another one.
+    fn next_buffered(&mut self) -> Option<Result<Tagged<Geometry<T>, JsonObject>, Error>> {
+        while let Some(feature) = self.page.pop_front() {
+            if feature.geometry.is_some() {
+                let item = Tagged::try_from(feature).map_err(Error::from);
+                if item.is_err() {
+                    self.next = None;
+                    self.page.clear();
+                }
+                return Some(item);
+            }
+        }
+        None
+    }
+}
+
+impl<T: CoordFloat> Iterator for Items<T> {
+    type Item = Result<Tagged<Geometry<T>, JsonObject>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.next_buffered() {
+                return Some(item);
+            }
+            let url = self.next.take()?;
+            if let Err(error) = self.fetch(&url) {
+                return Some(Err(error));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod stream {
+    use core::future::Future;
+    use core::pin::Pin;
+    use core::task::{Context, Poll};
+
+    use futures_core::Stream;
+    use geojson::JsonObject;
+    use tokio::task::JoinHandle;
+
+    use super::Items;
+    use crate::{CoordFloat, Error, Geometry, Tagged};
+
+    type Item<T> = Result<Tagged<Geometry<T>, JsonObject>, Error>;
+
+    impl<T: CoordFloat + Send + 'static> Items<T> {
+        /// Turns the items into a [`Stream`] for async code.
+        ///
+        /// Pages are fetched on tokio's blocking thread pool, so the stream must be
+        /// polled within a tokio runtime.
+        pub fn into_stream(self) -> ItemStream<T> {
+            ItemStream {
+                state: State::Idle(self),
+            }
+        }
+    }
+
+    /// A [`Stream`] over the items of a collection, fetching a page at a time
+    /// without blocking the executor. Created by [`Items::into_stream`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use geo_types_3d::conversion::ogc_api::Client;
+    /// use std::future::poll_fn;
+    /// use std::pin::Pin;
+    ///
+    /// # async fn heights() -> Result<(), geo_types_3d::Error> {
+    /// let mut stream = Client::new("https://demo.pygeoapi.io/stable")
+    ///     .items::<f64>("buildings")
+    ///     .limit(500)
+    ///     .into_stream();
+    /// while let Some(item) =
+    ///     poll_fn(|cx| futures_core::Stream::poll_next(Pin::new(&mut stream), cx)).await
+    /// {
+    ///     println!("{}", item?.data["height"]);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct ItemStream<T: CoordFloat = f64> {
+        state: State<T>,
+    }
+
+    // nothing in the stream is structurally pinned
+    impl<T: CoordFloat> Unpin for ItemStream<T> {}
+
+    #[derive(Debug)]
+    enum State<T: CoordFloat> {
+        Idle(Items<T>),
+        Fetching(JoinHandle<(Items<T>, Option<Item<T>>)>),
+        Done,
+    }
+
+    impl<T: CoordFloat + Send + 'static> Stream for ItemStream<T> {
+        type Item = Item<T>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            loop {
+                match &mut self.state {
+                    State::Idle(items) => {
+                        if let Some(item) = items.next_buffered() {
+                            return Poll::Ready(Some(item));
+                        }
+                        let State::Idle(mut items) =
+                            core::mem::replace(&mut self.state, State::Done)
+                        else {
+                            unreachable!()
+                        };
+                        if items.next.is_none() {
+                            return Poll::Ready(None);
+                        }
+                        let fetch = tokio::task::spawn_blocking(move || {
+                            let item = items.next();
+                            (items, item)
+                        });
+                        self.state = State::Fetching(fetch);
+                    }
+                    State::Fetching(fetch) => {
+                        let (items, item) = match Pin::new(fetch).poll(cx) {
+                            Poll::Pending => return Poll::Pending,
+                            Poll::Ready(Ok(fetched)) => fetched,
+                            Poll::Ready(Err(err)) => std::panic::resume_unwind(err.into_panic()),
+                        };
+                        self.state = State::Idle(items);
+                        return Poll::Ready(item);
+                    }
+                    State::Done => return Poll::Ready(None),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+pub use stream::ItemStream;
+
+/// The `href` of the `next` link of a page.
+fn next_link(collection: &FeatureCollection) -> Option<&str> {
+    let links = collection.foreign_members.as_ref()?.get("links")?;
+    links
+        .as_array()?
+        .iter()
+        .find_map(|link| match (link.get("rel"), link.get("href")) {
+            (Some(JsonValue::String(rel)), Some(JsonValue::String(href))) if rel == "next" => {
+                Some(href.as_str())
+            }
+            _ => None,
+        })
+}
+
+/// Resolves `href` against the URL of the page it was found on.
+fn resolve(page: &str, href: &str) -> String {
+    if href.contains("://") {
+        return href.to_string();
+    }
+    let path = page.split(['?', '#']).next().unwrap_or(page);
+    let path_start = path.find("://").map_or(0, |scheme| {
+        path[scheme + 3..]
+            .find('/')
+            .map_or(path.len(), |slash| scheme + 3 + slash)
+    });
+    if href.starts_with('/') {
+        format!("{}{href}", &path[..path_start])
+    } else if href.starts_with('?') {
+        format!("{path}{href}")
+    } else {
+        let directory = path[path_start..]
+            .rfind('/')
+            .map_or(path.len(), |slash| path_start + slash + 1);
+        format!("{}{href}", &path[..directory])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{PointZ, TaggedCollection};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serves `pages` in turn, one per connection, returning the requested paths.
+    fn serve(pages: Vec<String>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for page in pages {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                requests.push(line.split(' ').nth(1).unwrap().to_string());
+                while line != "\r\n" {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                }
+                let page = page.replace(
+                    "{base}",
+                    &format!("http://{}", listener.local_addr().unwrap()),
+                );
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/geo+json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{page}",
+                    page.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (base, server)
+    }
+
+    fn feature(id: u32) -> String {
+        format!(
+            r#"{{"type":"Feature","id":{id},"properties":{{"height":{id}}},"geometry":{{"type":"Point","coordinates":[7.0,50.0,{id}]}}}}"#
+        )
+    }
+
+    #[test]
+    fn paging() {
+        let (base, server) = serve(vec![
+            format!(
+                r#"{{"type":"FeatureCollection","features":[{},{}],"links":[{{"rel":"self","href":"{{base}}/collections/masts/items"}},{{"rel":"next","href":"{{base}}/collections/masts/items?offset=2&limit=2"}}]}}"#,
+                feature(1),
+                feature(2)
+            ),
+            format!(
+                r#"{{"type":"FeatureCollection","features":[{},{{"type":"Feature","properties":null,"geometry":null}}],"links":[{{"rel":"next","href":"items?offset=4&limit=2"}}]}}"#,
+                feature(3)
+            ),
+            r#"{"type":"FeatureCollection","features":[],"links":[{"rel":"next","href":"items?offset=6&limit=2"}]}"#.to_string(),
+        ]);
+        let client = Client::new(format!("{base}/"));
+        let masts: TaggedCollection<Geometry, JsonObject> = client
+            .items("masts")
+            .limit(2)
+            .bbox(&Cube::new((7., 50., 0.), (8., 51., 100.5)))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(masts.len(), 3);
+        assert_eq!(
+            masts.0[2].geometry,
+            Geometry::PointZ(PointZ::new(7., 50., 3.))
+        );
+        assert_eq!(masts.0[2].data["height"], 3);
+        assert_eq!(
+            server.join().unwrap(),
+            [
+                "/collections/masts/items?limit=2&bbox=7%2C50%2C0%2C8%2C51%2C100.5",
+                "/collections/masts/items?offset=2&limit=2",
+                "/collections/masts/items?offset=4&limit=2",
+            ]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let (base, server) = serve(vec![r#"{"type":"Feature"}"#.to_string()]);
+        let mut items = Client::new(base).items::<f64>("masts");
+        assert!(matches!(items.next(), Some(Err(Error::GeoJson(_)))));
+        assert!(items.next().is_none());
+        server.join().unwrap();
+
+        let mut items = Client::new("http://127.0.0.1:1").items::<f64>("masts");
+        assert!(matches!(items.next(), Some(Err(Error::Http(_)))));
+        assert!(items.next().is_none());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn stream() {
+        use futures_core::Stream;
+        use std::future::poll_fn;
+        use std::pin::Pin;
+
+        let (base, server) = serve(vec![
+            format!(
+                r#"{{"type":"FeatureCollection","features":[{},{}],"links":[{{"rel":"next","href":"items?offset=2"}}]}}"#,
+                feature(1),
+                feature(2)
+            ),
+            format!(
+                r#"{{"type":"FeatureCollection","features":[{}]}}"#,
+                feature(3)
+            ),
+        ]);
+        let mut stream = Client::new(base).items::<f64>("masts").into_stream();
+        let mut heights = Vec::new();
+        while let Some(item) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+            heights.push(item.unwrap().data["height"].clone());
+        }
+        assert_eq!(heights, [1, 2, 3]);
+        assert_eq!(
+            server.join().unwrap(),
+            [
+                "/collections/masts/items",
+                "/collections/masts/items?offset=2"
+            ]
+        );
+    }
+
+    #[test]
+    fn relative_links() {
+        let page = "https://example.com/ogc/collections/masts/items?limit=2";
+        assert_eq!(
+            resolve(page, "items?offset=2"),
+            "https://example.com/ogc/collections/masts/items?offset=2"
+        );
+        assert_eq!(
+            resolve(page, "/other/items"),
+            "https://example.com/other/items"
+        );
+        assert_eq!(
+            resolve(page, "?offset=2"),
+            "https://example.com/ogc/collections/masts/items?offset=2"
+        );
+        assert_eq!(resolve(page, "http://mirror.org/a"), "http://mirror.org/a");
+    }
+}
@@ -0,0 +1,175 @@
+//! Google's [encoded polyline] format, extended with a third value for elevation.
+//!
+//! Each coordinate is written as latitude (y), longitude (x) and then z, each
+//! rounded to a fixed number of decimal places, stored as the difference from the
+//! previous coordinate and packed into printable ASCII. Tracks encoded with a
+//! `z_precision` are not readable by decoders that only expect two values.
+//!
+//! [encoded polyline]: https://developers.google.com/maps/documentation/utilities/polylinealgorithm
+//!
+//! # Examples
+//!
+//! ```
+//! use geo_types_3d::conversion::polyline::{decode_polyline_z, encode_polyline_z};
+//! use geo_types_3d::line_string_z;
+//!
+//! // a GPS track with altitudes to the centimetre
+//! let track = line_string_z![
+//!     (x: -120.2, y: 38.5, z: 1204.37),
+//!     (x: -120.95, y: 40.7, z: 1187.02),
+//!     (x: -126.453, y: 43.252, z: 1190.),
+//! ];
+//! let encoded = encode_polyline_z(&track, 5, 2).unwrap();
+//! assert_eq!(encoded, "_p~iF~ps|UifjF_ulLnnqClkB_mqNvxq`@sQ");
+//!
+//! let decoded = decode_polyline_z(&encoded, 5, 2).unwrap();
+//! assert_eq!(decoded, track);
+//! ```
+
+use crate::{CoordFloat, CoordZ, Error, LineStringZ};
+
+/// Encodes a line string, keeping `precision` decimal places of x and y and
+/// `z_precision` decimal places of z.
+///
+/// Fails with [`Error::InvalidPolyline`] if an ordinate isn't finite or is too
+/// large to encode at the precision asked for.
+pub fn encode_polyline_z<T: CoordFloat>(
+    line_string: &LineStringZ<T>,
+    precision: u32,
+    z_precision: u32,
+) -> Result<String, Error> {
+    let scales = [scale(precision), scale(precision), scale(z_precision)];
+    let mut encoded = String::new();
+    let mut previous = [0i64; 3];
+    for coord in &line_string.0 {
+        let values = [coord.y, coord.x, coord.z];
+        for i in 0..3 {
+            let value = values[i]
+                .to_f64()
+                .map(|value| (value * scales[i]).round())
+                .filter(|value| value.abs() < (1u64 << 62) as f64)
+                .ok_or(Error::InvalidPolyline {
+                    reason: "ordinate is not finite or too large",
+                })? as i64;
+            write_value(value - previous[i], &mut encoded);
+            previous[i] = value;
+        }
+    }
+    Ok(encoded)
+}
+
+/// Decodes a line string encoded with [`encode_polyline_z`] at the same precisions.
+///
+/// Fails with [`Error::InvalidPolyline`] on characters outside the encoding, or
+/// if the input ends partway through a coordinate.
+pub fn decode_polyline_z<T: CoordFloat>(
+    encoded: &str,
+    precision: u32,
+    z_precision: u32,
+) -> Result<LineStringZ<T>, Error> {
+    let scales = [scale(precision), scale(precision), scale(z_precision)];
+    let mut bytes = encoded.bytes();
+    let mut coords = Vec::new();
+    let mut current = [0i64; 3];
+    while bytes.len() > 0 {
+        let mut values = [T::zero(); 3];
+        for i in 0..3 {
+            let delta = read_value(&mut bytes)?.ok_or(Error::InvalidPolyline {
+                reason: "input ends partway through a coordinate",
+            })?;
+            current[i] = current[i].wrapping_add(delta);
+            values[i] = T::from(current[i] as f64 / scales[i]).ok_or(Error::InvalidPolyline {
+                reason: "ordinate out of range",
+            })?;
+        }
+        let [y, x, z] = values;
+        coords.push(CoordZ { x, y, z });
+    }
+    Ok(LineStringZ(coords))
+}
+
+fn scale(precision: u32) -> f64 {
+    10f64.powi(precision.min(i32::MAX as u32) as i32)
+}
+
+fn write_value(value: i64, encoded: &mut String) {
+    // zig-zag the sign into the lowest bit, then emit 5 bits per character
+    let mut bits = ((value << 1) ^ (value >> 63)) as u64;
+    while bits >= 0x20 {
+        encoded.push((((bits & 0x1f) | 0x20) as u8 + 63) as char);
+        bits >>= 5;
+    }
+    encoded.push((bits as u8 + 63) as char);
+}
+
+/// Reads one value, or `None` at the end of the input.
+fn read_value(bytes: &mut std::str::Bytes) -> Result<Option<i64>, Error> {
+    let mut bits = 0u64;
+    let mut shift = 0;
+    loop {
+        let Some(byte) = bytes.next() else {
+            return match shift {
+                0 => Ok(None),
+                _ => Err(Error::InvalidPolyline {
+                    reason: "input ends partway through a value",
+                }),
+            };
+        };
+        let chunk = match byte.checked_sub(63) {
+            Some(chunk) if chunk < 0x40 => u64::from(chunk),
+            _ => {
+                return Err(Error::InvalidPolyline {
+                    reason: "character outside the encoding",
+                })
+            }
+        };
+        if shift >= 64 {
+            return Err(Error::InvalidPolyline {
+                reason: "value too long",
+            });
+        }
+        bits |= (chunk & 0x1f) << shift;
+        shift += 5;
+        if chunk < 0x20 {
+            return Ok(Some(((bits >> 1) as i64) ^ -((bits & 1) as i64)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let track: LineStringZ = line_string_z![
+            (x: 8.54, y: 47.37, z: 408.),
+            (x: 8.5412, y: 47.3705, z: 409.5),
+            (x: 8.5412, y: 47.3705, z: -0.25),
+        ];
+        for (precision, z_precision) in [(5, 2), (6, 0), (7, 3)] {
+            let encoded = encode_polyline_z(&track, precision, z_precision).unwrap();
+            assert!(encoded.bytes().all(|b| (63..127).contains(&b)));
+            let decoded: LineStringZ = decode_polyline_z(&encoded, precision, z_precision).unwrap();
+            for (a, b) in decoded.coords().zip(track.coords()) {
+                assert_relative_eq!(a.x, b.x, epsilon = 1e-9);
+                assert_relative_eq!(a.y, b.y, epsilon = 1e-9);
+                assert_relative_eq!(a.z, b.z, epsilon = 0.5 / scale(z_precision));
+            }
+        }
+        let empty = encode_polyline_z(&LineStringZ::<f64>::empty(), 5, 2).unwrap();
+        assert_eq!(empty, "");
+        assert_eq!(
+            decode_polyline_z::<f64>("", 5, 2).unwrap(),
+            LineStringZ::empty()
+        );
+
+        // a plain two-value polyline stops partway through a coordinate
+        assert!(decode_polyline_z::<f64>("_p~iF~ps|U", 5, 2).is_err());
+        assert!(decode_polyline_z::<f64>("_p~iF~ps|U_", 5, 2).is_err());
+        assert!(decode_polyline_z::<f64>("_p~iF ps|U_kkF", 5, 2).is_err());
+        assert!(decode_polyline_z::<f64>(&"~".repeat(20), 5, 2).is_err());
+        let infinite = line_string_z![(x: 0., y: f64::INFINITY, z: 0.)];
+        assert!(encode_polyline_z(&infinite, 5, 2).is_err());
+    }
+}
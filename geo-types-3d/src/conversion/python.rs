@@ -0,0 +1,216 @@
+//! Conversions between geometries and Python objects with [pyo3], through the
+//! [`__geo_interface__`](https://gist.github.com/sgillies/2217756) protocol.
+//!
+//! [`PointZ`], [`LineStringZ`] and [`PolygonZ`] convert into GeoJSON-like dicts with
+//! three ordinates per coordinate, which `shapely.geometry.shape` turns into
+//! shapely geometries, as does [`to_shapely`]. They can be extracted from such a
+//! dict, or from any object with a `__geo_interface__` attribute, shapely
+//! geometries included. Coordinates without z are rejected rather than given a
+//! made-up one.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use geo_types_3d::conversion::python::to_shapely;
+//! use geo_types_3d::{wkt, LineStringZ};
+//! use pyo3::prelude::*;
+//!
+//! Python::attach(|py| -> PyResult<()> {
+//!     let cable: LineStringZ = wkt! { LINESTRING Z(0. 0. 10.,5. 5. 12.) };
+//!     let shapely_line = to_shapely(py, &cable)?;
+//!     assert!(shapely_line.getattr("has_z")?.extract::<bool>()?);
+//!     let round_trip: LineStringZ = shapely_line.extract()?;
+//!     assert_eq!(round_trip, cable);
+//!     Ok(())
+//! })
+//! .unwrap();
+//! ```
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3::Borrowed;
+
+use crate::{CoordFloat, CoordZ, LineStringZ, PointZ, PolygonZ};
+
+/// Converts `geometry` into a shapely geometry, with `shapely.geometry.shape`.
+///
+/// Fails if shapely isn't installed.
+pub fn to_shapely<'py, G>(py: Python<'py>, geometry: G) -> PyResult<Bound<'py, PyAny>>
+where
+    G: IntoPyObject<'py, Target = PyDict, Output = Bound<'py, PyDict>, Error = PyErr>,
+{
+    let shape = py.import("shapely.geometry")?.getattr("shape")?;
+    shape.call1((geometry.into_pyobject(py)?,))
+}
+
+type Position = (f64, f64, f64);
+
+fn position<T: CoordFloat>(coord: &CoordZ<T>) -> Position {
+    let ordinate = |value: T| value.to_f64().unwrap_or(f64::NAN);
+    (ordinate(coord.x), ordinate(coord.y), ordinate(coord.z))
+}
+
+fn positions<T: CoordFloat>(line_string: &LineStringZ<T>) -> Vec<Position> {
+    line_string.0.iter().map(position).collect()
+}
+
+fn geo_interface<'py, C: IntoPyObject<'py>>(
+    py: Python<'py>,
+    kind: &str,
+    coordinates: C,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("type", kind)?;
+    dict.set_item("coordinates", coordinates)?;
+    Ok(dict)
+}
+
+macro_rules! impl_into_pyobject {
+    ($type:ident, $kind:literal, |$geometry:ident| $coordinates:expr) => {
+        impl<'py, T: CoordFloat> IntoPyObject<'py> for &$type<T> {
+            type Target = PyDict;
+            type Output = Bound<'py, PyDict>;
+            type Error = PyErr;
+
+            fn into_pyobject(self, py: Python<'py>) -> PyResult<Self::Output> {
+                let $geometry = self;
+                geo_interface(py, $kind, $coordinates)
+            }
+        }
+
+        impl<'py, T: CoordFloat> IntoPyObject<'py> for $type<T> {
+            type Target = PyDict;
+            type Output = Bound<'py, PyDict>;
+            type Error = PyErr;
+
+            fn into_pyobject(self, py: Python<'py>) -> PyResult<Self::Output> {
+                (&self).into_pyobject(py)
+            }
+        }
+    };
+}
+
+impl_into_pyobject!(PointZ, "Point", |point| position(&point.0));
+impl_into_pyobject!(LineStringZ, "LineString", |line_string| positions(
+    line_string
+));
+impl_into_pyobject!(PolygonZ, "Polygon", |polygon| {
+    core::iter::once(polygon.exterior())
+        .chain(polygon.interiors())
+        .map(positions)
+        .collect::<Vec<_>>()
+});
+
+/// The coordinates of the GeoJSON-like mapping `object`, or `object.__geo_interface__`,
+/// checking that its type is `kind`.
+fn coordinates<'py>(object: &Bound<'py, PyAny>, kind: &str) -> PyResult<Bound<'py, PyAny>> {
+    let mapping = if object.hasattr("__geo_interface__")? {
+        object.getattr("__geo_interface__")?
+    } else {
+        object.clone()
+    };
+    let found: String = mapping.get_item("type")?.extract()?;
+    if found != kind {
+        return Err(PyValueError::new_err(format!(
+            "expected a {kind} geometry, found {found}"
+        )));
+    }
+    mapping.get_item("coordinates")
+}
+
+fn extract_coord<T: CoordFloat>(position: Vec<f64>) -> PyResult<CoordZ<T>> {
+    let [x, y, z] = position[..] else {
+        return Err(PyValueError::new_err(format!(
+            "expected coordinates with x, y and z, found {} ordinates",
+            position.len()
+        )));
+    };
+    match (T::from(x), T::from(y), T::from(z)) {
+        (Some(x), Some(y), Some(z)) => Ok(CoordZ { x, y, z }),
+        _ => Err(PyValueError::new_err("ordinate out of range")),
+    }
+}
+
+fn extract_line_string<T: CoordFloat>(positions: Vec<Vec<f64>>) -> PyResult<LineStringZ<T>> {
+    positions
+        .into_iter()
+        .map(extract_coord)
+        .collect::<PyResult<Vec<_>>>()
+        .map(LineStringZ)
+}
+
+impl<'a, 'py, T: CoordFloat> FromPyObject<'a, 'py> for PointZ<T> {
+    type Error = PyErr;
+
+    fn extract(object: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        extract_coord(coordinates(&object, "Point")?.extract()?).map(PointZ)
+    }
+}
+
+impl<'a, 'py, T: CoordFloat> FromPyObject<'a, 'py> for LineStringZ<T> {
+    type Error = PyErr;
+
+    fn extract(object: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        extract_line_string(coordinates(&object, "LineString")?.extract()?)
+    }
+}
+
+impl<'a, 'py, T: CoordFloat> FromPyObject<'a, 'py> for PolygonZ<T> {
+    type Error = PyErr;
+
+    fn extract(object: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        let rings: Vec<Vec<Vec<f64>>> = coordinates(&object, "Polygon")?.extract()?;
+        let mut rings = rings.into_iter().map(extract_line_string);
+        let exterior = rings.next().transpose()?.unwrap_or_else(LineStringZ::empty);
+        Ok(PolygonZ::new(exterior, rings.collect::<PyResult<_>>()?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn geo_interface() {
+        Python::initialize();
+        Python::attach(|py| {
+            let point = PointZ::new(1., 2., 3.);
+            let dict = point.into_pyobject(py).unwrap();
+            assert_eq!(dict.get_item("type").unwrap().unwrap().to_string(), "Point");
+            assert_eq!(dict.extract::<PointZ>().unwrap(), point);
+
+            // a stand-in for a shapely geometry
+            let polygon: PolygonZ = wkt! {
+                POLYGON Z((0. 0. 1.,4. 0. 1.,4. 4. 1.,0. 0. 1.),(1. 1. 1.,2. 1. 1.,2. 2. 1.,1. 1. 1.))
+            };
+            let locals = PyDict::new(py);
+            locals.set_item("mapping", &polygon).unwrap();
+            py.run(
+                c"class Shape:\n    __geo_interface__ = mapping\nshape = Shape()",
+                Some(&locals),
+                None,
+            )
+            .unwrap();
+            let shape = locals.get_item("shape").unwrap().unwrap();
+            assert_eq!(shape.extract::<PolygonZ>().unwrap(), polygon);
+            assert!(shape.extract::<LineStringZ>().is_err());
+
+            let flat = py
+                .eval(
+                    c"{'type': 'LineString', 'coordinates': [(0, 0), (1, 1)]}",
+                    None,
+                    None,
+                )
+                .unwrap();
+            let error = flat.extract::<LineStringZ>().unwrap_err();
+            assert!(error.is_instance_of::<PyValueError>(py));
+            flat.set_item("coordinates", [(0, 0, 0), (1, 1, 2)])
+                .unwrap();
+            assert_eq!(
+                flat.extract::<LineStringZ>().unwrap(),
+                wkt! { LINESTRING Z(0. 0. 0.,1. 1. 2.) }
+            );
+        });
+    }
+}
@@ -0,0 +1,646 @@
+//! Well-known binary (WKB), the binary encoding of geometries used by PostGIS,
+//! GEOS, GDAL and most spatial databases.
+//!
+//! [`write_wkb`] writes ISO WKB, little-endian, with z as type codes 1001 to 1007.
+//! [`read_wkb`] reads either byte order, and both the ISO type codes and the
+//! extended WKB of PostGIS, whose z and m flags are in the high bits of the type
+//! and which may carry an SRID. The SRID and m ordinates are dropped. Geometries
+//! without z are read into the planar variants of [`Geometry`].
+//!
+//! Measured points and line strings are written and read on their own, as type
+//! codes 3001 and 3002, by [`write_wkb_point_zm`], [`write_wkb_line_string_zm`],
+//! [`read_wkb_point_zm`] and [`read_wkb_line_string_zm`].
+//!
+//! # Examples
+//!
+//! ```
+//! use geo_types_3d::algorithm::AxisOrder;
+//! use geo_types_3d::conversion::wkb::{read_wkb, write_wkb};
+//! use geo_types_3d::{Geometry, PointZ};
+//!
+//! let point = Geometry::PointZ(PointZ::new(1., 2., 3.));
+//! let bytes = write_wkb(&point);
+//! assert_eq!(bytes.len(), 1 + 4 + 3 * 8);
+//! assert_eq!(read_wkb::<f64>(&bytes, AxisOrder::LonLat).unwrap(), point);
+//! ```
+
+use alloc::vec::Vec;
+
+use geo_types::{Coord, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon};
+
+use crate::algorithm::AxisOrder;
+use crate::conversion::{Budget, ParseLimits};
+use crate::{
+    CoordFloat, CoordNum, CoordZ, CoordZM, Error, Geometry, GeometryCollection, LineStringZ,
+    LineStringZM, MultiLineStringZ, MultiPointZ, MultiPolygonZ, PointZ, PointZM, PolygonZ,
+};
+
+/// Writes `geometry` as ISO well-known binary, little-endian.
+///
+/// A [`Rect`](geo_types::Rect) is written as a polygon.
+pub fn write_wkb<T: CoordFloat>(geometry: &Geometry<T>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    write_wkb_into(geometry, &mut bytes);
+    bytes
+}
+
+/// Reads a geometry from well-known binary, with the default [`ParseLimits`].
+///
+/// Well-known binary puts the axes in the order of the coordinate reference
+/// system, which for EPSG:4326 as defined is latitude first. Such input can be
+/// read with [`AxisOrder::LatLon`], which swaps x and y into longitude/latitude
+/// order as coordinates are read.
+pub fn read_wkb<T: CoordFloat>(bytes: &[u8], axis_order: AxisOrder) -> Result<Geometry<T>, Error> {
+    read_wkb_with_limits(bytes, &ParseLimits::default(), axis_order)
+}
+
+/// Reads a geometry from well-known binary, failing with [`Error::LimitExceeded`]
+/// if it goes beyond `limits`, or with [`Error::InvalidWkb`] if the bytes aren't
+/// one well-known binary geometry.
+///
+/// Swaps x and y as [`read_wkb`] does if `axis_order` is [`AxisOrder::LatLon`].
+pub fn read_wkb_with_limits<T: CoordFloat>(
+    bytes: &[u8],
+    limits: &ParseLimits,
+    axis_order: AxisOrder,
+) -> Result<Geometry<T>, Error> {
+    let mut reader = Reader {
+        bytes,
+        budget: Budget::new(limits),
+        axis_order,
+    };
+    let geometry = reader.geometry(0, None)?;
+    if !reader.bytes.is_empty() {
+        return Err(invalid("trailing bytes after the geometry"));
+    }
+    Ok(geometry)
+}
+
+/// Writes `point` as ISO well-known binary, little-endian, with type code 3001.
+pub fn write_wkb_point_zm<T: CoordFloat>(point: &PointZM<T>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    header::<CoordZM<T>>(&mut bytes, 1);
+    point.0.write(&mut bytes);
+    bytes
+}
+
+/// Writes `line_string` as ISO well-known binary, little-endian, with type code
+/// 3002.
+pub fn write_wkb_line_string_zm<T: CoordFloat>(line_string: &LineStringZM<T>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    header::<CoordZM<T>>(&mut bytes, 2);
+    coords(&mut bytes, &line_string.0);
+    bytes
+}
+
+/// Reads a measured point from well-known binary.
+///
+/// Points without m are read with an `m` of NaN, and without z with `z = 0`.
+/// Fails with [`Error::InvalidWkb`] if the bytes aren't one point.
+pub fn read_wkb_point_zm<T: CoordFloat>(bytes: &[u8]) -> Result<PointZM<T>, Error> {
+    read_measured(bytes, 1, |reader, header| {
+        reader.coord_zm(header).map(PointZM)
+    })
+}
+
+/// Reads a measured line string from well-known binary, with the default
+/// [`ParseLimits`].
+///
+/// Reads measures like [`read_wkb_point_zm`] does. Fails with
+/// [`Error::InvalidWkb`] if the bytes aren't one line string.
+pub fn read_wkb_line_string_zm<T: CoordFloat>(bytes: &[u8]) -> Result<LineStringZM<T>, Error> {
+    read_measured(bytes, 2, |reader, header| {
+        let size = 8 * (2 + usize::from(header.z) + usize::from(header.m));
+        let count = reader.count(header, size)?;
+        reader.budget.coordinates(count)?;
+        (0..count)
+            .map(|_| reader.coord_zm(header))
+            .collect::<Result<_, _>>()
+            .map(LineStringZM)
+    })
+}
+
+/// Reads one geometry of type `kind` with `read`, which is passed its header.
+fn read_measured<G>(
+    bytes: &[u8],
+    kind: u32,
+    read: impl FnOnce(&mut Reader, Header) -> Result<G, Error>,
+) -> Result<G, Error> {
+    let mut reader = Reader {
+        bytes,
+        budget: Budget::new(&ParseLimits::default()),
+        axis_order: AxisOrder::LonLat,
+    };
+    let header = reader.header()?;
+    if header.kind != kind {
+        return Err(invalid("geometry of another type"));
+    }
+    let geometry = read(&mut reader, header)?;
+    if !reader.bytes.is_empty() {
+        return Err(invalid("trailing bytes after the geometry"));
+    }
+    Ok(geometry)
+}
+
+/// Writes the ISO well-known binary of `geometry`, little-endian, to `out`.
+pub(crate) fn write_wkb_into<T: CoordNum>(geometry: &Geometry<T>, out: &mut Vec<u8>) {
+    fn members<T: CoordNum>(out: &mut Vec<u8>, members: impl Iterator<Item = Geometry<T>>) {
+        // the count comes first, filled in once the members are written
+        let start = out.len();
+        out.extend_from_slice(&[0; 4]);
+        let mut count = 0u32;
+        for member in members {
+            write_wkb_into(&member, out);
+            count += 1;
+        }
+        out[start..start + 4].copy_from_slice(&count.to_le_bytes());
+    }
+
+    match geometry {
+        Geometry::Point(point) => {
+            header::<Coord<T>>(out, 1);
+            point.0.write(out);
+        }
+        Geometry::PointZ(point) => {
+            header::<CoordZ<T>>(out, 1);
+            point.0.write(out);
+        }
+        Geometry::Line(line) => {
+            header::<Coord<T>>(out, 2);
+            coords(out, &[line.start, line.end]);
+        }
+        Geometry::LineZ(line) => {
+            header::<CoordZ<T>>(out, 2);
+            coords(out, &[line.start, line.end]);
+        }
+        Geometry::LineString(line_string) => {
+            header::<Coord<T>>(out, 2);
+            coords(out, &line_string.0);
+        }
+        Geometry::LineStringZ(line_string) => {
+            header::<CoordZ<T>>(out, 2);
+            coords(out, &line_string.0);
+        }
+        Geometry::Polygon(polygon) => {
+            header::<Coord<T>>(out, 3);
+            let count = 1 + polygon.interiors().len() as u32;
+            out.extend_from_slice(&count.to_le_bytes());
+            for ring in core::iter::once(polygon.exterior()).chain(polygon.interiors()) {
+                coords(out, &ring.0);
+            }
+        }
+        Geometry::PolygonZ(polygon) => {
+            header::<CoordZ<T>>(out, 3);
+            let count = 1 + polygon.interiors().len() as u32;
+            out.extend_from_slice(&count.to_le_bytes());
+            for ring in core::iter::once(polygon.exterior()).chain(polygon.interiors()) {
+                coords(out, &ring.0);
+            }
+        }
+        Geometry::MultiPoint(multi_point) => {
+            header::<Coord<T>>(out, 4);
+            members(out, multi_point.iter().map(|&point| Geometry::Point(point)));
+        }
+        Geometry::MultiPointZ(multi_point) => {
+            header::<CoordZ<T>>(out, 4);
+            members(
+                out,
+                multi_point.iter().map(|&point| Geometry::PointZ(point)),
+            );
+        }
+        Geometry::MultiLineString(multi_line_string) => {
+            header::<Coord<T>>(out, 5);
+            members(
+                out,
+                multi_line_string.iter().cloned().map(Geometry::LineString),
+            );
+        }
+        Geometry::MultiLineStringZ(multi_line_string) => {
+            header::<CoordZ<T>>(out, 5);
+            members(
+                out,
+                multi_line_string.iter().cloned().map(Geometry::LineStringZ),
+            );
+        }
+        Geometry::MultiPolygon(multi_polygon) => {
+            header::<Coord<T>>(out, 6);
+            members(out, multi_polygon.iter().cloned().map(Geometry::Polygon));
+        }
+        Geometry::MultiPolygonZ(multi_polygon) => {
+            header::<CoordZ<T>>(out, 6);
+            members(out, multi_polygon.iter().cloned().map(Geometry::PolygonZ));
+        }
+        Geometry::GeometryCollection(collection) => {
+            header::<Coord<T>>(out, 7);
+            members(out, collection.iter().cloned());
+        }
+        Geometry::Rect(rect) => write_wkb_into(&Geometry::Polygon(rect.to_polygon()), out),
+    }
+}
+
+/// A coordinate written with two, three or four ordinates.
+trait Ordinates: Copy {
+    /// The thousands of the ISO type codes of geometries of these coordinates.
+    const THOUSANDS: u32;
+
+    fn write(&self, out: &mut Vec<u8>);
+}
+
+fn ordinate<T: CoordNum>(out: &mut Vec<u8>, value: T) {
+    out.extend_from_slice(&value.to_f64().unwrap_or(f64::NAN).to_le_bytes());
+}
+
+impl<T: CoordNum> Ordinates for Coord<T> {
+    const THOUSANDS: u32 = 0;
+
+    fn write(&self, out: &mut Vec<u8>) {
+        ordinate(out, self.x);
+        ordinate(out, self.y);
+    }
+}
+
+impl<T: CoordNum> Ordinates for CoordZ<T> {
+    const THOUSANDS: u32 = 1000;
+
+    fn write(&self, out: &mut Vec<u8>) {
+        ordinate(out, self.x);
+        ordinate(out, self.y);
+        ordinate(out, self.z);
+    }
+}
+
+impl<T: CoordNum> Ordinates for CoordZM<T> {
+    const THOUSANDS: u32 = 3000;
+
+    fn write(&self, out: &mut Vec<u8>) {
+        ordinate(out, self.x);
+        ordinate(out, self.y);
+        ordinate(out, self.z);
+        ordinate(out, self.m);
+    }
+}
+
+fn header<C: Ordinates>(out: &mut Vec<u8>, kind: u32) {
+    out.push(1);
+    out.extend_from_slice(&(kind + C::THOUSANDS).to_le_bytes());
+}
+
+fn coords<C: Ordinates>(out: &mut Vec<u8>, coords: &[C]) {
+    out.extend_from_slice(&(coords.len() as u32).to_le_bytes());
+    for coord in coords {
+        coord.write(out);
+    }
+}
+
+fn invalid(reason: &'static str) -> Error {
+    Error::InvalidWkb { reason }
+}
+
+/// The type of a geometry, from its header.
+#[derive(Clone, Copy)]
+struct Header {
+    kind: u32,
+    z: bool,
+    m: bool,
+    little_endian: bool,
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    budget: Budget,
+    axis_order: AxisOrder,
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let Some((head, rest)) = self.bytes.split_first_chunk::<N>() else {
+            return Err(invalid("input ends partway through the geometry"));
+        };
+        self.bytes = rest;
+        Ok(*head)
+    }
+
+    fn u32(&mut self, header: Header) -> Result<u32, Error> {
+        let bytes = self.take()?;
+        Ok(if header.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn ordinate<T: CoordFloat>(&mut self, header: Header) -> Result<T, Error> {
+        let bytes = self.take()?;
+        let value = if header.little_endian {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        };
+        T::from(value).ok_or(Error::CastOverflow)
+    }
+
+    /// A count of items at least `size` bytes each, checked against the bytes
+    /// left so that a corrupt count can't make us allocate without bound.
+    fn count(&mut self, header: Header, size: usize) -> Result<usize, Error> {
+        let count = self.u32(header)? as usize;
+        if count > self.bytes.len() / size {
+            return Err(invalid("count larger than the input"));
+        }
+        Ok(count)
+    }
+
+    fn header(&mut self) -> Result<Header, Error> {
+        let [order] = self.take()?;
+        let little_endian = match order {
+            0 => false,
+            1 => true,
+            _ => return Err(invalid("unknown byte order")),
+        };
+        let mut header = Header {
+            kind: 0,
+            z: false,
+            m: false,
+            little_endian,
+        };
+        let code = self.u32(header)?;
+        // the flags of extended WKB, then the thousands of ISO WKB
+        if code & 0x2000_0000 != 0 {
+            self.u32(header)?;
+        }
+        let iso = code & 0x0fff_ffff;
+        header.kind = iso % 1000;
+        header.z = code & 0x8000_0000 != 0 || matches!(iso / 1000, 1 | 3);
+        header.m = code & 0x4000_0000 != 0 || matches!(iso / 1000, 2 | 3);
+        if !(1..=7).contains(&header.kind) || iso / 1000 > 3 {
+            return Err(invalid("unsupported geometry type"));
+        }
+        Ok(header)
+    }
+
+    /// The horizontal ordinates, as x and y in longitude/latitude order.
+    fn x_y<T: CoordFloat>(&mut self, header: Header) -> Result<(T, T), Error> {
+        let first = self.ordinate(header)?;
+        let second = self.ordinate(header)?;
+        Ok(match self.axis_order {
+            AxisOrder::LonLat => (first, second),
+            AxisOrder::LatLon => (second, first),
+        })
+    }
+
+    fn coord<T: CoordFloat>(&mut self, header: Header) -> Result<CoordZ<T>, Error> {
+        let (x, y) = self.x_y(header)?;
+        let z = if header.z {
+            self.ordinate(header)?
+        } else {
+            T::zero()
+        };
+        if header.m {
+            self.ordinate::<f64>(header)?;
+        }
+        Ok(CoordZ { x, y, z })
+    }
+
+    /// A coordinate with m, which is NaN if the geometry has none.
+    fn coord_zm<T: CoordFloat>(&mut self, header: Header) -> Result<CoordZM<T>, Error> {
+        let (x, y) = self.x_y(header)?;
+        let z = if header.z {
+            self.ordinate(header)?
+        } else {
+            T::zero()
+        };
+        let m = if header.m {
+            self.ordinate(header)?
+        } else {
+            T::nan()
+        };
+        Ok(CoordZM { x, y, z, m })
+    }
+
+    fn coords<T: CoordFloat>(&mut self, header: Header) -> Result<Vec<CoordZ<T>>, Error> {
+        let size = 8 * (2 + usize::from(header.z) + usize::from(header.m));
+        let count = self.count(header, size)?;
+        self.budget.coordinates(count)?;
+        (0..count).map(|_| self.coord(header)).collect()
+    }
+
+    fn rings<T: CoordFloat>(&mut self, header: Header) -> Result<Vec<LineStringZ<T>>, Error> {
+        let count = self.count(header, 4)?;
+        self.budget.rings(count)?;
+        (0..count)
+            .map(|_| self.coords(header).map(LineStringZ))
+            .collect()
+    }
+
+    /// A geometry at `depth` collections down, which must be of type `kind` if
+    /// given.
+    fn geometry<T: CoordFloat>(
+        &mut self,
+        depth: usize,
+        kind: Option<u32>,
+    ) -> Result<Geometry<T>, Error> {
+        let header = self.header()?;
+        if kind.is_some_and(|kind| kind != header.kind) {
+            return Err(invalid("member of a multi-geometry of another type"));
+        }
+        let planar = |coord: CoordZ<T>| Coord {
+            x: coord.x,
+            y: coord.y,
+        };
+        let polygon = |mut rings: Vec<LineStringZ<T>>| {
+            let exterior = if rings.is_empty() {
+                LineStringZ::empty()
+            } else {
+                rings.remove(0)
+            };
+            PolygonZ::new(exterior, rings)
+        };
+        let flatten = |line_string: LineStringZ<T>| -> LineString<T> {
+            line_string.0.into_iter().map(planar).collect()
+        };
+        let flatten_polygon = |polygon: PolygonZ<T>| {
+            let (exterior, interiors) = polygon.into_inner();
+            Polygon::new(
+                flatten(exterior),
+                interiors.into_iter().map(flatten).collect(),
+            )
+        };
+        Ok(match (header.kind, header.z) {
+            (1, _) => {
+                let coord = self.coord(header)?;
+                if header.z {
+                    Geometry::PointZ(PointZ(coord))
+                } else {
+                    Geometry::Point(Point(planar(coord)))
+                }
+            }
+            (2, true) => Geometry::LineStringZ(LineStringZ(self.coords(header)?)),
+            (2, false) => Geometry::LineString(flatten(LineStringZ(self.coords(header)?))),
+            (3, true) => Geometry::PolygonZ(polygon(self.rings(header)?)),
+            (3, false) => Geometry::Polygon(flatten_polygon(polygon(self.rings(header)?))),
+            (kind, z) => {
+                let members = self.members(header, depth)?;
+                let mismatch = || invalid("member of a multi-geometry of another dimension");
+                match (kind, z) {
+                    (4, true) => Geometry::MultiPointZ(MultiPointZ(
+                        members
+                            .into_iter()
+                            .map(|member| PointZ::try_from(member).map_err(|_| mismatch()))
+                            .collect::<Result<_, _>>()?,
+                    )),
+                    (4, false) => Geometry::MultiPoint(MultiPoint(
+                        members
+                            .into_iter()
+                            .map(|member| match member {
+                                Geometry::Point(point) => Ok(point),
+                                _ => Err(mismatch()),
+                            })
+                            .collect::<Result<_, _>>()?,
+                    )),
+                    (5, true) => Geometry::MultiLineStringZ(MultiLineStringZ(
+                        members
+                            .into_iter()
+                            .map(|member| LineStringZ::try_from(member).map_err(|_| mismatch()))
+                            .collect::<Result<_, _>>()?,
+                    )),
+                    (5, false) => Geometry::MultiLineString(MultiLineString(
+                        members
+                            .into_iter()
+                            .map(|member| match member {
+                                Geometry::LineString(line_string) => Ok(line_string),
+                                _ => Err(mismatch()),
+                            })
+                            .collect::<Result<_, _>>()?,
+                    )),
+                    (6, true) => Geometry::MultiPolygonZ(MultiPolygonZ(
+                        members
+                            .into_iter()
+                            .map(|member| PolygonZ::try_from(member).map_err(|_| mismatch()))
+                            .collect::<Result<_, _>>()?,
+                    )),
+                    (6, false) => Geometry::MultiPolygon(MultiPolygon(
+                        members
+                            .into_iter()
+                            .map(|member| match member {
+                                Geometry::Polygon(polygon) => Ok(polygon),
+                                _ => Err(mismatch()),
+                            })
+                            .collect::<Result<_, _>>()?,
+                    )),
+                    _ => Geometry::GeometryCollection(GeometryCollection(members)),
+                }
+            }
+        })
+    }
+
+    fn members<T: CoordFloat>(
+        &mut self,
+        header: Header,
+        depth: usize,
+    ) -> Result<Vec<Geometry<T>>, Error> {
+        // the smallest member, an empty line string, takes 9 bytes
+        let count = self.count(header, 9)?;
+        // the members of multi-geometries are points, line strings or polygons,
+        // so only collections nest
+        let kind = match header.kind {
+            7 => {
+                self.budget.depth(depth + 1)?;
+                None
+            }
+            multi => Some(multi - 3),
+        };
+        (0..count)
+            .map(|_| self.geometry(depth + usize::from(kind.is_none()), kind))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn round_trip() {
+        let geometries: Vec<Geometry> = vec![
+            wkt! { POINT Z(1. 2. 3.) }.into(),
+            wkt! { LINESTRING Z(0. 0. 0.,1. 1. 1.) }.into(),
+            wkt! { POLYGON Z((0. 0. 1.,4. 0. 1.,4. 4. 1.,0. 0. 1.),(1. 1. 1.,2. 1. 1.,2. 2. 1.,1. 1. 1.)) }
+                .into(),
+            wkt! { MULTIPOINT Z((1. 2. 3.),(4. 5. 6.)) }.into(),
+            wkt! { MULTILINESTRING Z((0. 0. 0.,1. 1. 1.),(2. 2. 2.,3. 3. 3.)) }.into(),
+            wkt! { MULTIPOLYGON Z(((0. 0. 1.,4. 0. 1.,4. 4. 1.,0. 0. 1.))) }.into(),
+            Geometry::Point(Point::new(1., 2.)),
+            Geometry::LineString(geo_types::wkt! { LINESTRING(0. 0.,1. 1.) }),
+            Geometry::MultiPolygon(geo_types::wkt! { MULTIPOLYGON(((0. 0.,4. 0.,4. 4.,0. 0.))) }),
+        ];
+        let collection = Geometry::GeometryCollection(GeometryCollection(geometries.clone()));
+        for geometry in geometries.iter().chain([&collection]) {
+            assert_eq!(
+                &read_wkb::<f64>(&write_wkb(geometry), AxisOrder::LonLat).unwrap(),
+                geometry
+            );
+        }
+
+        // big-endian extended WKB with an SRID and m: POINT ZM(1 2 3 4), SRID 4326
+        let mut ewkb = vec![0];
+        ewkb.extend_from_slice(&(0xE000_0001u32).to_be_bytes());
+        ewkb.extend_from_slice(&4326u32.to_be_bytes());
+        for value in [1f64, 2., 3., 4.] {
+            ewkb.extend_from_slice(&value.to_be_bytes());
+        }
+        assert_eq!(
+            read_wkb::<f64>(&ewkb, AxisOrder::LonLat).unwrap(),
+            Geometry::PointZ(PointZ::new(1., 2., 3.))
+        );
+
+        // truncated, trailing and corrupt input
+        let bytes = write_wkb(&collection);
+        for end in 0..bytes.len() {
+            assert!(read_wkb::<f64>(&bytes[..end], AxisOrder::LonLat).is_err());
+        }
+        assert!(read_wkb::<f64>(&[&bytes[..], &[0]].concat(), AxisOrder::LonLat).is_err());
+        assert!(read_wkb::<f64>(&[1, 255, 255, 255, 255, 0, 0, 0, 0], AxisOrder::LonLat).is_err());
+        assert!(read_wkb::<f64>(&[1, 2, 0, 0, 0, 255, 255, 255, 255], AxisOrder::LonLat).is_err());
+
+        // latitude first, as in EPSG:4326
+        let lat_lon = write_wkb(&wkt! { LINESTRING Z(52.37 4.9 2.,48.86 2.35 35.) }.into());
+        assert_eq!(
+            read_wkb::<f64>(&lat_lon, AxisOrder::LatLon).unwrap(),
+            wkt! { LINESTRING Z(4.9 52.37 2.,2.35 48.86 35.) }.into()
+        );
+
+        // measured geometries, and measures missing from the input
+        let point = PointZM::new(1., 2., 3., 4.);
+        let bytes = write_wkb_point_zm(&point);
+        assert_eq!(bytes[1..5], 3001u32.to_le_bytes());
+        assert_eq!(read_wkb_point_zm::<f64>(&bytes).unwrap(), point);
+        assert_eq!(
+            read_wkb::<f64>(&bytes, AxisOrder::LonLat).unwrap(),
+            Geometry::PointZ(PointZ::new(1., 2., 3.))
+        );
+        let line_string = wkt! { LINESTRING ZM(0. 0. 0. 0.,3. 4. 0. 5.) };
+        let bytes = write_wkb_line_string_zm(&line_string);
+        assert_eq!(bytes[1..5], 3002u32.to_le_bytes());
+        assert_eq!(read_wkb_line_string_zm::<f64>(&bytes).unwrap(), line_string);
+        assert!(read_wkb_point_zm::<f64>(&bytes).is_err());
+        let flat =
+            read_wkb_point_zm::<f64>(&write_wkb(&wkt! { POINT Z(1. 2. 3.) }.into())).unwrap();
+        assert_eq!(flat.xyz(), PointZ::new(1., 2., 3.));
+        assert!(flat.m().is_nan());
+
+        let mut nested = Geometry::PointZ(PointZ::new(0., 0., 0.));
+        for _ in 0..40 {
+            nested = Geometry::GeometryCollection(GeometryCollection(vec![nested]));
+        }
+        assert!(matches!(
+            read_wkb::<f64>(&write_wkb(&nested), AxisOrder::LonLat),
+            Err(Error::LimitExceeded { .. })
+        ));
+        assert!(read_wkb_with_limits::<f64>(
+            &write_wkb(&nested),
+            &ParseLimits::UNLIMITED,
+            AxisOrder::LonLat
+        )
+        .is_ok());
+    }
+}
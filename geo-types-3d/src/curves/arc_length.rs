@@ -0,0 +1,137 @@
+use alloc::vec::Vec;
+
+use super::{fraction, Curve};
+use crate::algorithm::linalg::distance;
+use crate::{CoordFloat, CoordZ, LineStringZ};
+
+/// A [`Curve`] reparameterized by the distance along it.
+///
+/// The curve is approximated by chords between evenly spaced parameters, and
+/// distances are interpolated along them, so more segments give more accurate lengths
+/// and spacing.
+#[derive(Debug, Clone)]
+pub struct ArcLength<'a, C, T: CoordFloat> {
+    curve: &'a C,
+    /// The distance along the curve at each parameter `i / segments`.
+    distances: Vec<T>,
+}
+
+impl<'a, C: Curve<T>, T: CoordFloat> ArcLength<'a, C, T> {
+    pub fn new(curve: &'a C, segments: usize) -> Self {
+        let segments = segments.max(1);
+        let mut distances = Vec::with_capacity(segments + 1);
+        let mut previous = curve.point_at(T::zero());
+        let mut total = T::zero();
+        distances.push(total);
+        for i in 1..=segments {
+            let point = curve.point_at(fraction(i, segments));
+            total = total + distance(previous, point);
+            distances.push(total);
+            previous = point;
+        }
+        Self { curve, distances }
+    }
+
+    /// The length of the curve.
+    pub fn length(&self) -> T {
+        self.distances[self.distances.len() - 1]
+    }
+
+    /// The parameter of the point `distance` along the curve. Distances beyond the
+    /// ends are clamped.
+    pub fn parameter_at(&self, distance: T) -> T {
+        if distance.is_nan() || distance <= T::zero() {
+            return T::zero();
+        }
+        if distance >= self.length() {
+            return T::one();
+        }
+        // the first tabulated distance beyond the one wanted
+        let i = self.distances.partition_point(|&d| d <= distance);
+        let (before, after) = (self.distances[i - 1], self.distances[i]);
+        let part = if after > before {
+            (distance - before) / (after - before)
+        } else {
+            T::zero()
+        };
+        (T::from(i - 1).unwrap() + part) / T::from(self.distances.len() - 1).unwrap()
+    }
+
+    /// The point `distance` along the curve.
+    pub fn point_at_distance(&self, distance: T) -> CoordZ<T> {
+        self.curve.point_at(self.parameter_at(distance))
+    }
+
+    /// Samples the curve at `segments + 1` points evenly spaced along it, from its
+    /// start to its end.
+    pub fn sample_even(&self, segments: usize) -> LineStringZ<T> {
+        let segments = segments.max(1);
+        let length = self.length();
+        (0..=segments)
+            .map(|i| self.point_at_distance(length * fraction(i, segments)))
+            .collect()
+    }
+
+    /// Samples the curve every `spacing` along it, from its start. The end of the
+    /// curve is always included, so the last gap may be shorter.
+    ///
+    /// # Panics
+    ///
+    /// If `spacing` isn't positive.
+    pub fn sample_spaced(&self, spacing: T) -> LineStringZ<T> {
+        assert!(spacing > T::zero(), "spacing must be positive");
+        let length = self.length();
+        let mut coords = Vec::new();
+        let mut i = 0;
+        loop {
+            let distance = spacing * T::from(i).unwrap();
+            if distance >= length {
+                break;
+            }
+            coords.push(self.point_at_distance(distance));
+            i += 1;
+        }
+        coords.push(self.curve.point_at(T::one()));
+        LineStringZ(coords)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::curves::CubicBezierZ;
+
+    #[test]
+    fn even_spacing() {
+        // a straight curve at 3t² along the diagonal, speeding up towards the end
+        let curve = CubicBezierZ::new(
+            coordZ! { x: 0., y: 0., z: 0. },
+            coordZ! { x: 0., y: 0., z: 0. },
+            coordZ! { x: 1., y: 1., z: 1. },
+            coordZ! { x: 3., y: 3., z: 3. },
+        );
+        let by_distance = curve.arc_length(10_000);
+        let length = 27f64.sqrt();
+        assert_relative_eq!(by_distance.length(), length, epsilon = 1e-9);
+
+        let sampled = by_distance.sample_even(6);
+        for (pair, uniform) in sampled.lines().zip(curve.sample(6).lines()) {
+            let gap = distance(pair.start, pair.end);
+            assert_relative_eq!(gap, length / 6., epsilon = 1e-6);
+            assert!(distance(uniform.start, uniform.end) != gap);
+        }
+
+        let spaced = by_distance.sample_spaced(2.);
+        assert_eq!(spaced.0.len(), 4);
+        assert_relative_eq!(distance(spaced.0[0], spaced.0[1]), 2., epsilon = 1e-6);
+        assert_eq!(spaced.0[3], curve.end);
+
+        assert_eq!(by_distance.parameter_at(-1.), 0.);
+        assert_eq!(by_distance.parameter_at(10.), 1.);
+        assert_relative_eq!(
+            by_distance.parameter_at(length / 2.),
+            0.5f64.sqrt(),
+            epsilon = 1e-6
+        );
+    }
+}
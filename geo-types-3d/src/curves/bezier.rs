@@ -0,0 +1,162 @@
+use alloc::vec::Vec;
+
+use super::{locate, Curve};
+use crate::{CoordFloat, CoordNum, CoordZ};
+
+/// A cubic Bézier curve from `start` to `end`, pulled towards the two control points.
+///
+/// The curve leaves `start` heading for `control_1` and arrives at `end` from the
+/// direction of `control_2`, and stays within the hull of the four points.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::coordZ;
+/// use geo_types_3d::curves::{CubicBezierZ, Curve};
+///
+/// let curve = CubicBezierZ::new(
+///     coordZ! { x: 0., y: 0., z: 0. },
+///     coordZ! { x: 0., y: 4., z: 0. },
+///     coordZ! { x: 4., y: 4., z: 4. },
+///     coordZ! { x: 4., y: 0., z: 4. },
+/// );
+/// assert_eq!(curve.point_at(0.5), coordZ! { x: 2., y: 3., z: 2. });
+/// assert_eq!(curve.derivative_at(0.), coordZ! { x: 0., y: 12., z: 0. });
+/// ```
+#[derive(Eq, PartialEq, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CubicBezierZ<T: CoordNum = f64> {
+    pub start: CoordZ<T>,
+    pub control_1: CoordZ<T>,
+    pub control_2: CoordZ<T>,
+    pub end: CoordZ<T>,
+}
+
+impl<T: CoordNum> CubicBezierZ<T> {
+    pub fn new(
+        start: CoordZ<T>,
+        control_1: CoordZ<T>,
+        control_2: CoordZ<T>,
+        end: CoordZ<T>,
+    ) -> Self {
+        Self {
+            start,
+            control_1,
+            control_2,
+            end,
+        }
+    }
+}
+
+impl<T: CoordFloat> CubicBezierZ<T> {
+    /// The rate of change of the point with the parameter at `t`, pointing along
+    /// the curve.
+    pub fn derivative_at(&self, t: T) -> CoordZ<T> {
+        let t = t.max(T::zero()).min(T::one());
+        let s = T::one() - t;
+        let three = T::from(3).unwrap();
+        let two = T::one() + T::one();
+        ((self.control_1 - self.start) * (s * s)
+            + (self.control_2 - self.control_1) * (two * s * t)
+            + (self.end - self.control_2) * (t * t))
+            * three
+    }
+
+    /// Splits the curve at `t` into two curves that together trace it.
+    pub fn split(&self, t: T) -> (Self, Self) {
+        let t = t.max(T::zero()).min(T::one());
+        // de Casteljau's construction
+        let a = self.start.lerp(self.control_1, t);
+        let b = self.control_1.lerp(self.control_2, t);
+        let c = self.control_2.lerp(self.end, t);
+        let ab = a.lerp(b, t);
+        let bc = b.lerp(c, t);
+        let middle = ab.lerp(bc, t);
+        (
+            Self::new(self.start, a, ab, middle),
+            Self::new(middle, bc, c, self.end),
+        )
+    }
+}
+
+impl<T: CoordFloat> Curve<T> for CubicBezierZ<T> {
+    fn point_at(&self, t: T) -> CoordZ<T> {
+        let t = t.max(T::zero()).min(T::one());
+        let s = T::one() - t;
+        let three = T::from(3).unwrap();
+        self.start * (s * s * s)
+            + self.control_1 * (three * s * s * t)
+            + self.control_2 * (three * s * t * t)
+            + self.end * (t * t * t)
+    }
+}
+
+/// Cubic Bézier curves joined end to end, such as a path exported from a drawing
+/// tool.
+///
+/// Each curve takes an equal share of the parameter, whatever its length.
+/// Evaluating an empty path panics.
+#[derive(Eq, PartialEq, Clone, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BezierPathZ<T: CoordNum = f64>(pub Vec<CubicBezierZ<T>>);
+
+impl<T: CoordNum> BezierPathZ<T> {
+    pub fn new(curves: Vec<CubicBezierZ<T>>) -> Self {
+        Self(curves)
+    }
+
+    /// Whether each curve starts where the one before it ends.
+    pub fn is_connected(&self) -> bool {
+        self.0.windows(2).all(|pair| pair[0].end == pair[1].start)
+    }
+}
+
+impl<T: CoordFloat> Curve<T> for BezierPathZ<T> {
+    fn point_at(&self, t: T) -> CoordZ<T> {
+        assert!(!self.0.is_empty(), "cannot evaluate an empty path");
+        let (index, t) = locate(t, self.0.len());
+        self.0[index].point_at(t)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn bezier() {
+        let curve = CubicBezierZ::new(
+            coordZ! { x: 0., y: 0., z: 0. },
+            coordZ! { x: 1., y: 2., z: 0. },
+            coordZ! { x: 3., y: 2., z: 1. },
+            coordZ! { x: 4., y: 0., z: 3. },
+        );
+        assert_eq!(curve.point_at(0.), curve.start);
+        assert_eq!(curve.point_at(1.), curve.end);
+        assert_eq!(
+            curve.derivative_at(0.),
+            (curve.control_1 - curve.start) * 3.
+        );
+        assert_eq!(curve.derivative_at(1.), (curve.end - curve.control_2) * 3.);
+
+        // the derivative matches a finite difference
+        let h = 1e-6;
+        let slope = (curve.point_at(0.3 + h) - curve.point_at(0.3 - h)) / (2. * h);
+        assert_relative_eq!(curve.derivative_at(0.3), slope, epsilon = 1e-6);
+
+        let (left, right) = curve.split(0.25);
+        assert_eq!(left.end, right.start);
+        for t in [0., 0.3, 0.7, 1.] {
+            assert_relative_eq!(left.point_at(t), curve.point_at(t / 4.), epsilon = 1e-12);
+            let on_right = curve.point_at(0.25 + t * 0.75);
+            assert_relative_eq!(right.point_at(t), on_right, epsilon = 1e-12);
+        }
+
+        let path = BezierPathZ(vec![left, right]);
+        assert!(path.is_connected());
+        assert_eq!(path.point_at(0.5), curve.point_at(0.25));
+        assert_relative_eq!(path.point_at(0.75), right.point_at(0.5), epsilon = 1e-12);
+        assert_eq!(path.sample(4).0.len(), 5);
+    }
+}
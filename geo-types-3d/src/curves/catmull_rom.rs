@@ -0,0 +1,167 @@
+use alloc::vec::Vec;
+
+use super::{locate, Curve};
+use crate::algorithm::linalg::distance;
+use crate::{CoordFloat, CoordNum, CoordZ};
+
+/// A spline passing through each of its points in turn, with a continuous tangent.
+///
+/// `alpha` sets how the spline is parameterized between its points: 0 gives the
+/// uniform spline, 0.5 the centripetal spline, which never forms cusps or loops
+/// within a segment, and 1 the chordal spline. The first and last segments are shaped
+/// as if the points continued beyond the ends in a straight line.
+///
+/// Each segment between two points takes an equal share of the parameter, whatever
+/// its length. Evaluating a spline without points panics.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::coordZ;
+/// use geo_types_3d::curves::{CatmullRomZ, Curve};
+///
+/// let points = vec![
+///     coordZ! { x: 0., y: 0., z: 0. },
+///     coordZ! { x: 1., y: 1., z: 0. },
+///     coordZ! { x: 2., y: 0., z: 1. },
+///     coordZ! { x: 3., y: 1., z: 1. },
+/// ];
+/// let spline: CatmullRomZ = CatmullRomZ::centripetal(points.clone());
+/// let line_string = spline.sample(30);
+/// for (i, point) in points.iter().enumerate() {
+///     let gap = line_string.0[10 * i] - *point;
+///     assert!(gap.x.abs() + gap.y.abs() + gap.z.abs() < 1e-12);
+/// }
+/// ```
+#[derive(Eq, PartialEq, Clone, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CatmullRomZ<T: CoordNum = f64> {
+    pub points: Vec<CoordZ<T>>,
+    pub alpha: T,
+}
+
+impl<T: CoordNum> CatmullRomZ<T> {
+    pub fn new(points: Vec<CoordZ<T>>, alpha: T) -> Self {
+        Self { points, alpha }
+    }
+}
+
+impl<T: CoordFloat> CatmullRomZ<T> {
+    /// A uniform spline, with `alpha` 0.
+    pub fn uniform(points: Vec<CoordZ<T>>) -> Self {
+        Self::new(points, T::zero())
+    }
+
+    /// A centripetal spline, with `alpha` 0.5.
+    pub fn centripetal(points: Vec<CoordZ<T>>) -> Self {
+        Self::new(points, T::from(0.5).unwrap())
+    }
+
+    /// The four points shaping segment `index`: the ends of the segment and a
+    /// neighbour on either side.
+    fn neighbourhood(&self, index: usize) -> [CoordZ<T>; 4] {
+        let points = &self.points;
+        let (p1, p2) = (points[index], points[index + 1]);
+        let p0 = match index {
+            0 => p1 + (p1 - p2),
+            _ => points[index - 1],
+        };
+        let p3 = match points.get(index + 2) {
+            Some(&p3) => p3,
+            None => p2 + (p2 - p1),
+        };
+        [p0, p1, p2, p3]
+    }
+}
+
+impl<T: CoordFloat> Curve<T> for CatmullRomZ<T> {
+    fn point_at(&self, t: T) -> CoordZ<T> {
+        assert!(
+            !self.points.is_empty(),
+            "cannot evaluate a spline without points"
+        );
+        if self.points.len() == 1 {
+            return self.points[0];
+        }
+        let (index, u) = locate(t, self.points.len() - 1);
+        let [p0, p1, p2, p3] = self.neighbourhood(index);
+        if p1 == p2 {
+            return p1;
+        }
+
+        // the knot spacing, substituting the middle one where a neighbour coincides
+        let knot = |a: CoordZ<T>, b| distance(a, b).powf(self.alpha);
+        let middle = knot(p1, p2);
+        let before = Some(knot(p0, p1))
+            .filter(|&k| k > T::zero())
+            .unwrap_or(middle);
+        let after = Some(knot(p2, p3))
+            .filter(|&k| k > T::zero())
+            .unwrap_or(middle);
+        let (t0, t1) = (T::zero(), before);
+        let (t2, t3) = (t1 + middle, t1 + middle + after);
+        let t = t1 + middle * u;
+
+        // Barry and Goldman's pyramidal formulation
+        let a1 = blend(p0, p1, t0, t1, t);
+        let a2 = blend(p1, p2, t1, t2, t);
+        let a3 = blend(p2, p3, t2, t3, t);
+        let b1 = blend(a1, a2, t0, t2, t);
+        let b2 = blend(a2, a3, t1, t3, t);
+        blend(b1, b2, t1, t2, t)
+    }
+}
+
+/// Interpolates from `a` at `from` to `b` at `to`.
+fn blend<T: CoordFloat>(a: CoordZ<T>, b: CoordZ<T>, from: T, to: T, t: T) -> CoordZ<T> {
+    (a * (to - t) + b * (t - from)) / (to - from)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::curves::CubicBezierZ;
+    use alloc::vec;
+
+    #[test]
+    fn catmull_rom() {
+        let points = vec![
+            coordZ! { x: 0., y: 0., z: 0. },
+            coordZ! { x: 1., y: 3., z: 1. },
+            coordZ! { x: 1.2, y: 3., z: 1. },
+            coordZ! { x: 4., y: 0., z: 2. },
+        ];
+        for spline in [
+            CatmullRomZ::uniform(points.clone()),
+            CatmullRomZ::centripetal(points.clone()),
+            CatmullRomZ::new(points.clone(), 1.),
+        ] {
+            for (i, point) in points.iter().enumerate() {
+                assert_relative_eq!(spline.point_at(i as f64 / 3.), *point, epsilon = 1e-12);
+            }
+        }
+
+        // the uniform spline is a Bézier curve with controls a sixth of the way along
+        // the chords between the neighbours
+        let spline = CatmullRomZ::uniform(points.clone());
+        let [p0, p1, p2, p3] = spline.neighbourhood(1);
+        let bezier = CubicBezierZ::new(p1, p1 + (p2 - p0) / 6., p2 - (p3 - p1) / 6., p2);
+        for t in [0.1, 0.5, 0.8] {
+            let on_spline = spline.point_at((1. + t) / 3.);
+            assert_relative_eq!(on_spline, bezier.point_at(t), epsilon = 1e-12);
+        }
+
+        // the ends continue straight, and repeated points don't divide by zero
+        let line = CatmullRomZ::centripetal(vec![
+            coordZ! { x: 0., y: 0., z: 0. },
+            coordZ! { x: 0., y: 0., z: 0. },
+            coordZ! { x: 2., y: 2., z: 2. },
+        ]);
+        assert_eq!(line.point_at(0.25), coordZ! { x: 0., y: 0., z: 0. });
+        for point in line.sample(10) {
+            assert!(point.x > -1e-12 && point.x < 2. + 1e-12);
+            assert_relative_eq!(point.x, point.y, epsilon = 1e-12);
+            assert_relative_eq!(point.x, point.z, epsilon = 1e-12);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Smooth parametric paths through 3D space, such as animation and camera paths or
+//! smoothed alignments of roads and railways.
+//!
+//! Every curve implements [`Curve`], giving the point at a parameter that runs from 0
+//! at the start of the curve to 1 at its end. The parameter doesn't advance at a
+//! constant speed along the curve, so [`ArcLength`] reparameterizes a curve by the
+//! distance along it, to move along it evenly or sample it at even spacing.
+//!
+//! # Examples
+//!
+//! ```
+//! use geo_types_3d::coordZ;
+//! use geo_types_3d::curves::{CatmullRomZ, Curve};
+//!
+//! // a flight path through three waypoints
+//! let path: CatmullRomZ = CatmullRomZ::centripetal(vec![
+//!     coordZ! { x: 0., y: 0., z: 100. },
+//!     coordZ! { x: 40., y: 0., z: 120. },
+//!     coordZ! { x: 40., y: 30., z: 150. },
+//! ]);
+//! let waypoint = path.point_at(0.5) - coordZ! { x: 40., y: 0., z: 120. };
+//! assert!(waypoint.x.abs() + waypoint.y.abs() + waypoint.z.abs() < 1e-9);
+//!
+//! // one point every 5 units along the path
+//! let by_distance = path.arc_length(1_000);
+//! let line_string = by_distance.sample_spaced(5.);
+//! let length = by_distance.length();
+//! assert!(length > 50. && length < 100.);
+//! assert_eq!(line_string.0.len(), (length / 5.).ceil() as usize + 1);
+//! ```
+
+mod arc_length;
+mod bezier;
+mod catmull_rom;
+
+pub use arc_length::ArcLength;
+pub use bezier::{BezierPathZ, CubicBezierZ};
+pub use catmull_rom::CatmullRomZ;
+
+use crate::{CoordFloat, CoordZ, LineStringZ};
+
+/// A path through 3D space, parameterized from 0 at its start to 1 at its end.
+pub trait Curve<T: CoordFloat> {
+    /// The point at parameter `t`. Parameters outside `0..=1` are clamped.
+    fn point_at(&self, t: T) -> CoordZ<T>;
+
+    /// Samples the curve at `segments + 1` parameters, evenly spaced from 0 to 1.
+    ///
+    /// The samples are further apart where the curve moves faster; see
+    /// [`Curve::arc_length`] to space them evenly along the curve instead.
+    fn sample(&self, segments: usize) -> LineStringZ<T> {
+        let segments = segments.max(1);
+        (0..=segments)
+            .map(|i| self.point_at(fraction(i, segments)))
+            .collect()
+    }
+
+    /// Reparameterizes the curve by distance, approximating it by `segments` chords.
+    fn arc_length(&self, segments: usize) -> ArcLength<'_, Self, T>
+    where
+        Self: Sized,
+    {
+        ArcLength::new(self, segments)
+    }
+}
+
+fn fraction<T: CoordFloat>(i: usize, count: usize) -> T {
+    T::from(i).unwrap() / T::from(count).unwrap()
+}
+
+/// Splits a parameter over a path of `count` equal pieces into the index of a piece
+/// and the parameter within it.
+fn locate<T: CoordFloat>(t: T, count: usize) -> (usize, T) {
+    let t = t.max(T::zero()).min(T::one()) * T::from(count).unwrap();
+    let index = t.floor().to_usize().unwrap_or(0).min(count - 1);
+    (index, t - T::from(index).unwrap())
+}
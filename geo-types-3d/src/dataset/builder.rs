@@ -0,0 +1,528 @@
+use alloc::vec::Vec;
+
+use core::ops::Range;
+
+use super::format::{expand_extent, pad, GeometryKind, Layout, EMPTY_EXTENT};
+#[cfg(feature = "std")]
+use super::format::{read_f64, COORD_LEN};
+use super::rtree::{sort_entries, write_tree};
+use super::Dataset;
+use crate::geometry::inner_type_name;
+use crate::{CoordNum, CoordZ, Error, Geometry, PolygonZ};
+
+/// Encodes geometries into the dataset container format.
+///
+/// Only the `Z` geometry types can be stored; coordinates are converted to `f64`.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::dataset::{Dataset, DatasetBuilder, GeometryKind};
+/// use geo_types_3d::{line_string_z, Geometry, PointZ};
+///
+/// let mut builder = DatasetBuilder::new();
+/// builder.push(&Geometry::PointZ(PointZ::new(1.0, 2.0, 3.0))).unwrap();
+/// builder
+///     .push(&Geometry::LineStringZ(line_string_z![
+///         (x: 0.0, y: 0.0, z: 0.0),
+///         (x: 1.0, y: 1.0, z: 1.0),
+///     ]))
+///     .unwrap();
+///
+/// let dataset = Dataset::from_bytes(builder.to_bytes()).unwrap();
+/// assert_eq!(dataset.len(), 2);
+/// assert_eq!(dataset.get(1).unwrap().kind(), GeometryKind::LineStringZ);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DatasetBuilder {
+    kinds: Vec<u8>,
+    feature_offsets: Vec<u64>,
+    group_offsets: Vec<u64>,
+    part_offsets: Vec<u64>,
+    coords: Vec<f64>,
+    bboxes: Vec<[f64; 6]>,
+    extent: [f64; 6],
+    deleted: usize,
+    index: bool,
+}
+
+impl Default for DatasetBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DatasetBuilder {
+    /// Creates an empty builder which writes a spatial index.
+    pub fn new() -> Self {
+        Self {
+            kinds: Vec::new(),
+            feature_offsets: alloc::vec![0],
+            group_offsets: alloc::vec![0],
+            part_offsets: alloc::vec![0],
+            coords: Vec::new(),
+            bboxes: Vec::new(),
+            extent: EMPTY_EXTENT,
+            deleted: 0,
+            index: true,
+        }
+    }
+
+    /// Creates a builder holding a copy of every feature of `dataset`.
+    ///
+    /// Deleted features are copied as deleted, so ids are preserved.
+    pub fn from_dataset<B: AsRef<[u8]>>(dataset: &Dataset<B>) -> Self {
+        let mut builder = Self::new().with_index(dataset.has_index());
+        for id in 0..dataset.id_bound() {
+            let Some(view) = dataset.get(id) else {
+                builder.push_deleted();
+                continue;
+            };
+            let mut feature = FeatureEncoder::new(&mut builder);
+            for group in view.groups() {
+                for part in group {
+                    feature.part_f64(part.iter().map(|c| [c.x, c.y, c.z]));
+                }
+                feature.close_group();
+            }
+            feature.finish(view.kind());
+        }
+        builder
+    }
+
+    /// Sets whether the spatial index, the bounding box of every feature and a packed
+    /// Hilbert R-tree over them, is written. Without it, range queries have to scan
+    /// the coordinates of every feature.
+    pub fn with_index(mut self, index: bool) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// The number of features pushed so far, not counting removed ones.
+    pub fn len(&self) -> usize {
+        self.kinds.len() - self.deleted
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// One past the largest feature id handed out so far.
+    pub fn id_bound(&self) -> usize {
+        self.kinds.len()
+    }
+
+    /// Marks the feature `id` as deleted. Its id is not reused, and its
+    /// coordinates are kept until [`DatasetBuilder::compact`] is called.
+    ///
+    /// Returns `false` if there is no such feature or it was already removed.
+    pub fn remove(&mut self, id: usize) -> bool {
+        if !self.contains(id) {
+            return false;
+        }
+        self.kinds[id] = 0;
+        self.bboxes[id] = EMPTY_EXTENT;
+        self.deleted += 1;
+        true
+    }
+
+    /// Whether `id` is a feature that has not been removed.
+    pub fn contains(&self, id: usize) -> bool {
+        self.kinds.get(id).is_some_and(|&kind| kind != 0)
+    }
+
+    /// Drops removed features and their coordinates, and recomputes the extent.
+    ///
+    /// Remaining features are renumbered in order; the returned vector maps each
+    /// old id to its new id, or `None` for removed features.
+    pub fn compact(&mut self) -> Vec<Option<usize>> {
+        let mut compacted = Self::new().with_index(self.index);
+        let ids = (0..self.id_bound())
+            .map(|id| self.copy_feature(id, &mut compacted))
+            .collect();
+        *self = compacted;
+        ids
+    }
+
+    /// Appends a geometry and returns its id in the dataset.
+    ///
+    /// Fails with [`Error::UnsupportedGeometry`] for 2D geometries, `Rect`s and
+    /// geometry collections.
+    pub fn push<T: CoordNum>(&mut self, geometry: &Geometry<T>) -> Result<usize, Error> {
+        let mut feature = FeatureEncoder::new(self);
+        let kind = match geometry {
+            Geometry::PointZ(point) => {
+                feature.part([point.0]);
+                GeometryKind::PointZ
+            }
+            Geometry::LineZ(line) => {
+                feature.part([line.start, line.end]);
+                GeometryKind::LineZ
+            }
+            Geometry::LineStringZ(line_string) => {
+                feature.part(line_string.0.iter().copied());
+                GeometryKind::LineStringZ
+            }
+            Geometry::PolygonZ(polygon) => {
+                feature.polygon(polygon);
+                GeometryKind::PolygonZ
+            }
+            Geometry::MultiPointZ(multi_point) => {
+                feature.part(multi_point.0.iter().map(|point| point.0));
+                GeometryKind::MultiPointZ
+            }
+            Geometry::MultiLineStringZ(multi_line_string) => {
+                for line_string in &multi_line_string.0 {
+                    feature.part(line_string.0.iter().copied());
+                }
+                GeometryKind::MultiLineStringZ
+            }
+            Geometry::MultiPolygonZ(multi_polygon) => {
+                for polygon in &multi_polygon.0 {
+                    feature.polygon(polygon);
+                    feature.close_group();
+                }
+                return Ok(feature.finish(GeometryKind::MultiPolygonZ));
+            }
+            other => {
+                return Err(Error::UnsupportedGeometry {
+                    found: inner_type_name(other),
+                })
+            }
+        };
+        feature.close_group();
+        Ok(feature.finish(kind))
+    }
+
+    /// Encodes the pushed features into a dataset buffer.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let entries = if self.index {
+            sort_entries(&self.bboxes, self.extent)
+        } else {
+            Vec::new()
+        };
+        let layout = self.layout(entries.len());
+        let mut out = Vec::with_capacity(layout.byte_len());
+        layout.write_header(&mut out);
+        out.extend_from_slice(&self.kinds);
+        pad(&mut out);
+        for offsets in [
+            &self.feature_offsets,
+            &self.group_offsets,
+            &self.part_offsets,
+        ] {
+            for offset in offsets {
+                out.extend_from_slice(&offset.to_le_bytes());
+            }
+        }
+        for value in &self.coords {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        if self.index {
+            for value in self.bboxes.iter().flatten() {
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+            write_tree(&mut out, &entries, &self.bboxes);
+        }
+        debug_assert_eq!(out.len(), layout.byte_len());
+        out
+    }
+
+    /// Encodes the pushed features and opens the result as an in-memory [`Dataset`].
+    pub fn finish(self) -> Dataset<Vec<u8>> {
+        Dataset::from_bytes(self.to_bytes()).expect("builder output is a valid dataset")
+    }
+
+    /// Encodes the pushed features into `writer`.
+    #[cfg(feature = "std")]
+    pub fn write_to<W: std::io::Write>(&self, mut writer: W) -> Result<(), Error> {
+        writer.write_all(&self.to_bytes())?;
+        Ok(())
+    }
+
+    fn layout(&self, tree_entries: usize) -> Layout {
+        Layout::new(
+            self.kinds.len(),
+            self.group_offsets.len() - 1,
+            self.part_offsets.len() - 1,
+            self.coords.len() / 3,
+            self.index,
+            self.index.then_some(tree_entries),
+            self.extent,
+        )
+        .map(|mut layout| {
+            layout.deleted_count = self.deleted;
+            layout
+        })
+        .expect("in-memory sections fit in usize")
+    }
+
+    fn push_deleted(&mut self) {
+        self.kinds.push(0);
+        let group_count = (self.group_offsets.len() - 1) as u64;
+        self.feature_offsets.push(group_count);
+        self.bboxes.push(EMPTY_EXTENT);
+        self.deleted += 1;
+    }
+
+    fn groups(&self, id: usize) -> Range<usize> {
+        self.feature_offsets[id] as usize..self.feature_offsets[id + 1] as usize
+    }
+
+    fn parts(&self, group: usize) -> Range<usize> {
+        self.group_offsets[group] as usize..self.group_offsets[group + 1] as usize
+    }
+
+    fn coords(&self, part: usize) -> &[f64] {
+        let (start, end) = (self.part_offsets[part], self.part_offsets[part + 1]);
+        &self.coords[start as usize * 3..end as usize * 3]
+    }
+
+    /// Appends feature `id` to `into`, returning its new id unless it was removed.
+    fn copy_feature(&self, id: usize, into: &mut DatasetBuilder) -> Option<usize> {
+        let kind = GeometryKind::from_u8(self.kinds[id])?;
+        let mut feature = FeatureEncoder::new(into);
+        for group in self.groups(id) {
+            for part in self.parts(group) {
+                feature.part_f64(
+                    self.coords(part)
+                        .chunks_exact(3)
+                        .map(|c| [c[0], c[1], c[2]]),
+                );
+            }
+            feature.close_group();
+        }
+        Some(feature.finish(kind))
+    }
+
+    /// Serializes `geometry` into a compact, self-delimiting record without
+    /// pushing it: the kind byte, then a `u32` group count, and for each group a
+    /// `u32` part count followed by each part's `u32` coordinate count and
+    /// coordinates.
+    ///
+    /// Fails like [`DatasetBuilder::push`], leaving `out` untouched.
+    #[cfg(feature = "std")]
+    pub(crate) fn encode_record<T: CoordNum>(
+        geometry: &Geometry<T>,
+        out: &mut Vec<u8>,
+    ) -> Result<(), Error> {
+        let mut record = RecordWriter(out);
+        match geometry {
+            Geometry::PointZ(point) => {
+                record.header(GeometryKind::PointZ, 1);
+                record.group(1);
+                record.part([point.0]);
+            }
+            Geometry::LineZ(line) => {
+                record.header(GeometryKind::LineZ, 1);
+                record.group(1);
+                record.part([line.start, line.end]);
+            }
+            Geometry::LineStringZ(line_string) => {
+                record.header(GeometryKind::LineStringZ, 1);
+                record.group(1);
+                record.part(line_string.0.iter().copied());
+            }
+            Geometry::PolygonZ(polygon) => {
+                record.header(GeometryKind::PolygonZ, 1);
+                record.polygon(polygon);
+            }
+            Geometry::MultiPointZ(multi_point) => {
+                record.header(GeometryKind::MultiPointZ, 1);
+                record.group(1);
+                record.part(multi_point.0.iter().map(|point| point.0));
+            }
+            Geometry::MultiLineStringZ(multi_line_string) => {
+                record.header(GeometryKind::MultiLineStringZ, 1);
+                record.group(multi_line_string.0.len());
+                for line_string in &multi_line_string.0 {
+                    record.part(line_string.0.iter().copied());
+                }
+            }
+            Geometry::MultiPolygonZ(multi_polygon) => {
+                record.header(GeometryKind::MultiPolygonZ, multi_polygon.0.len());
+                for polygon in &multi_polygon.0 {
+                    record.polygon(polygon);
+                }
+            }
+            other => {
+                return Err(Error::UnsupportedGeometry {
+                    found: inner_type_name(other),
+                })
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends a feature serialized by [`DatasetBuilder::encode_record`].
+    ///
+    /// A malformed record fails with [`Error::InvalidDataset`] and leaves the
+    /// builder untouched.
+    #[cfg(feature = "std")]
+    pub(crate) fn push_record(&mut self, record: &[u8]) -> Result<usize, Error> {
+        let (coords, parts, groups) = (
+            self.coords.len(),
+            self.part_offsets.len(),
+            self.group_offsets.len(),
+        );
+        let mut feature = FeatureEncoder::new(self);
+        if let Some(kind) = feature.record(record) {
+            return Ok(feature.finish(kind));
+        }
+        // drop the parts decoded before the record turned out to be malformed
+        self.coords.truncate(coords);
+        self.part_offsets.truncate(parts);
+        self.group_offsets.truncate(groups);
+        Err(Error::InvalidDataset {
+            reason: "malformed feature record",
+        })
+    }
+}
+
+/// Writes the record format of [`DatasetBuilder::encode_record`].
+#[cfg(feature = "std")]
+struct RecordWriter<'a>(&'a mut Vec<u8>);
+
+#[cfg(feature = "std")]
+impl RecordWriter<'_> {
+    fn count(&mut self, n: usize) {
+        let n = u32::try_from(n).expect("record counts fit in u32");
+        self.0.extend_from_slice(&n.to_le_bytes());
+    }
+
+    fn header(&mut self, kind: GeometryKind, group_count: usize) {
+        self.0.push(kind as u8);
+        self.count(group_count);
+    }
+
+    fn group(&mut self, part_count: usize) {
+        self.count(part_count);
+    }
+
+    fn part<T, I>(&mut self, coords: I)
+    where
+        T: CoordNum,
+        I: IntoIterator<Item = CoordZ<T>>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let coords = coords.into_iter();
+        self.count(coords.len());
+        for c in coords {
+            for value in [c.x, c.y, c.z] {
+                self.0.extend_from_slice(&to_f64(value).to_le_bytes());
+            }
+        }
+    }
+
+    fn polygon<T: CoordNum>(&mut self, polygon: &PolygonZ<T>) {
+        self.group(1 + polygon.interiors().len());
+        self.part(polygon.exterior().0.iter().copied());
+        for interior in polygon.interiors() {
+            self.part(interior.0.iter().copied());
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+struct RecordReader<'a>(&'a [u8]);
+
+#[cfg(feature = "std")]
+impl<'a> RecordReader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if len > self.0.len() {
+            return None;
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Some(head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take(4)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+/// Appends the parts of a single feature, keeping track of its bounding box.
+struct FeatureEncoder<'a> {
+    builder: &'a mut DatasetBuilder,
+    bbox: [f64; 6],
+}
+
+impl<'a> FeatureEncoder<'a> {
+    fn new(builder: &'a mut DatasetBuilder) -> Self {
+        Self {
+            builder,
+            bbox: EMPTY_EXTENT,
+        }
+    }
+
+    fn part<T: CoordNum>(&mut self, coords: impl IntoIterator<Item = CoordZ<T>>) {
+        self.part_f64(
+            coords
+                .into_iter()
+                .map(|c| [to_f64(c.x), to_f64(c.y), to_f64(c.z)]),
+        )
+    }
+
+    fn part_f64(&mut self, coords: impl IntoIterator<Item = [f64; 3]>) {
+        for [x, y, z] in coords {
+            expand_extent(&mut self.bbox, &[x, y, z, x, y, z]);
+            self.builder.coords.extend_from_slice(&[x, y, z]);
+        }
+        let coord_count = (self.builder.coords.len() / 3) as u64;
+        self.builder.part_offsets.push(coord_count);
+    }
+
+    fn polygon<T: CoordNum>(&mut self, polygon: &PolygonZ<T>) {
+        self.part(polygon.exterior().0.iter().copied());
+        for interior in polygon.interiors() {
+            self.part(interior.0.iter().copied());
+        }
+    }
+
+    /// Decodes the groups and parts of a record, returning its kind unless the
+    /// record is malformed.
+    #[cfg(feature = "std")]
+    fn record(&mut self, record: &[u8]) -> Option<GeometryKind> {
+        let mut reader = RecordReader(record);
+        let kind = reader.u8().and_then(GeometryKind::from_u8)?;
+        for _ in 0..reader.u32()? {
+            for _ in 0..reader.u32()? {
+                let coords = reader
+                    .u32()
+                    .and_then(|n| reader.take((n as usize).checked_mul(COORD_LEN)?))?;
+                self.part_f64(
+                    coords
+                        .chunks_exact(COORD_LEN)
+                        .map(|c| [read_f64(c, 0), read_f64(c, 8), read_f64(c, 16)]),
+                );
+            }
+            self.close_group();
+        }
+        reader.0.is_empty().then_some(kind)
+    }
+
+    fn close_group(&mut self) {
+        let part_count = (self.builder.part_offsets.len() - 1) as u64;
+        self.builder.group_offsets.push(part_count);
+    }
+
+    fn finish(self, kind: GeometryKind) -> usize {
+        let builder = self.builder;
+        builder.kinds.push(kind as u8);
+        let group_count = (builder.group_offsets.len() - 1) as u64;
+        builder.feature_offsets.push(group_count);
+        builder.bboxes.push(self.bbox);
+        expand_extent(&mut builder.extent, &self.bbox);
+        builder.kinds.len() - 1
+    }
+}
+
+fn to_f64<T: CoordNum>(value: T) -> f64 {
+    value.to_f64().unwrap()
+}
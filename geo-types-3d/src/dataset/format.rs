@@ -0,0 +1,334 @@
+//! Low level layout of the dataset container.
+//!
+//! All integers and floats are stored little-endian. The file starts with a fixed 128 byte
+//! header:
+//!
+//! | offset | size | field                                               |
+//! |--------|------|-----------------------------------------------------|
+//! | 0      | 8    | magic, `b"GEO3DSET"`                                |
+//! | 8      | 2    | format version                                      |
+//! | 10     | 2    | flags, see below                                    |
+//! | 12     | 4    | reserved, zero                                      |
+//! | 16     | 8    | feature count                                       |
+//! | 24     | 8    | group count                                         |
+//! | 32     | 8    | part count                                          |
+//! | 40     | 8    | coordinate count                                    |
+//! | 48     | 48   | extent: min x, y, z then max x, y, z as `f64`       |
+//! | 96     | 8    | deleted feature count                               |
+//! | 104    | 8    | R-tree leaf count                                   |
+//! | 112    | 16   | reserved, zero                                      |
+//!
+//! Bit 0 of the flags is set when the bounding boxes are stored, and bit 1 when the
+//! R-tree is too. The R-tree is only written along with the bounding boxes.
+//!
+//! The header is followed by these sections, each padded to a multiple of 8 bytes:
+//!
+//! 1. kinds: one [`GeometryKind`] byte per feature, `0` for a deleted feature
+//! 2. feature offsets: `feature count + 1` `u64`s into the groups
+//! 3. group offsets: `group count + 1` `u64`s into the parts
+//! 4. part offsets: `part count + 1` `u64`s into the coordinate arena
+//! 5. coordinate arena: `coordinate count` `x, y, z` triples of `f64`
+//! 6. optional bounding boxes: one box (min x, y, z, max x, y, z) per feature, in id
+//!    order, or an empty box for a deleted feature
+//! 7. optional R-tree: the `u64` ids of the leaves in Hilbert order, then the node
+//!    boxes of each level from the leaves up to the root. Its layout is derived from
+//!    the leaf count alone, see below.
+//!
+//! A part is a run of coordinates (a point set, line string or ring) and a group is
+//! a run of parts (a polygon). Only [`GeometryKind::MultiPolygonZ`] features have
+//! more than one group.
+//!
+//! The R-tree is packed: its leaves are every live feature with coordinates, sorted
+//! by the Hilbert key of their box centre within the extent, and each node covers
+//! the next 16 entries of the level below. A level of `n` entries has `⌈n / 16⌉`
+//! nodes above it, up to a level with a single root; a tree with one leaf or none has
+//! no nodes. Queries descend only into the nodes whose box intersects the query.
+//!
+//! Deleted features keep their id, and may keep their coordinates, until the dataset
+//! is compacted. Any bytes following the last section are ignored by readers; the
+//! [`DatasetWriter`](super::DatasetWriter) uses them as an append journal.
+
+use core::ops::Range;
+
+use super::rtree::TreeLayout;
+use crate::Error;
+
+pub(crate) const MAGIC: [u8; 8] = *b"GEO3DSET";
+pub(crate) const VERSION: u16 = 1;
+pub(crate) const HEADER_LEN: usize = 128;
+pub(crate) const FLAG_INDEX: u16 = 1;
+pub(crate) const FLAG_TREE: u16 = 2;
+
+pub(crate) const COORD_LEN: usize = 3 * 8;
+pub(crate) const BBOX_LEN: usize = 6 * 8;
+
+/// The geometry type of a feature stored in a [`Dataset`](super::Dataset).
+///
+/// The discriminant is the byte written to the kinds section. `0` marks a deleted
+/// feature.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[repr(u8)]
+pub enum GeometryKind {
+    PointZ = 1,
+    LineZ = 2,
+    LineStringZ = 3,
+    PolygonZ = 4,
+    MultiPointZ = 5,
+    MultiLineStringZ = 6,
+    MultiPolygonZ = 7,
+}
+
+impl GeometryKind {
+    pub(crate) fn from_u8(byte: u8) -> Option<Self> {
+        Some(match byte {
+            1 => Self::PointZ,
+            2 => Self::LineZ,
+            3 => Self::LineStringZ,
+            4 => Self::PolygonZ,
+            5 => Self::MultiPointZ,
+            6 => Self::MultiLineStringZ,
+            7 => Self::MultiPolygonZ,
+            _ => return None,
+        })
+    }
+}
+
+/// Byte ranges of every section, computed once from the header.
+#[derive(Clone, Debug)]
+pub(crate) struct Layout {
+    pub feature_count: usize,
+    pub group_count: usize,
+    pub part_count: usize,
+    pub coord_count: usize,
+    pub deleted_count: usize,
+    pub extent: [f64; 6],
+    pub kinds: Range<usize>,
+    pub feature_offsets: Range<usize>,
+    pub group_offsets: Range<usize>,
+    pub part_offsets: Range<usize>,
+    pub coords: Range<usize>,
+    pub index: Option<Range<usize>>,
+    pub tree: Option<TreeLayout>,
+}
+
+impl Layout {
+    /// Computes the section ranges for the given counts. `tree_entries` is the leaf
+    /// count of the R-tree, if there is one. Returns `None` on overflow.
+    pub fn new(
+        feature_count: usize,
+        group_count: usize,
+        part_count: usize,
+        coord_count: usize,
+        has_index: bool,
+        tree_entries: Option<usize>,
+        extent: [f64; 6],
+    ) -> Option<Self> {
+        let mut cursor = HEADER_LEN;
+        let mut section = |len: usize| -> Option<Range<usize>> {
+            let start = cursor;
+            let end = start.checked_add(padded(len)?)?;
+            cursor = end;
+            Some(start..start + len)
+        };
+        let kinds = section(feature_count)?;
+        let feature_offsets = section(feature_count.checked_add(1)?.checked_mul(8)?)?;
+        let group_offsets = section(group_count.checked_add(1)?.checked_mul(8)?)?;
+        let part_offsets = section(part_count.checked_add(1)?.checked_mul(8)?)?;
+        let coords = section(coord_count.checked_mul(COORD_LEN)?)?;
+        let index = if has_index {
+            Some(section(feature_count.checked_mul(BBOX_LEN)?)?)
+        } else {
+            None
+        };
+        // every part of the tree is a multiple of 8 bytes long, so it needs no padding
+        let tree = match tree_entries {
+            Some(entries) => Some(TreeLayout::new(cursor, entries)?),
+            None => None,
+        };
+        Some(Self {
+            feature_count,
+            group_count,
+            part_count,
+            coord_count,
+            deleted_count: 0,
+            extent,
+            kinds,
+            feature_offsets,
+            group_offsets,
+            part_offsets,
+            coords,
+            index,
+            tree,
+        })
+    }
+
+    /// Reads and validates the header of `bytes`.
+    ///
+    /// This only checks the header and the buffer length, so it runs in constant
+    /// time apart from the single pass over the kinds section.
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let invalid = |reason| Error::InvalidDataset { reason };
+        if bytes.len() < HEADER_LEN {
+            return Err(invalid("buffer is shorter than the header"));
+        }
+        if bytes[0..8] != MAGIC {
+            return Err(invalid("missing magic bytes"));
+        }
+        if read_u16(bytes, 8) != VERSION {
+            return Err(invalid("unsupported format version"));
+        }
+        let flags = read_u16(bytes, 10);
+        if flags & !(FLAG_INDEX | FLAG_TREE) != 0 {
+            return Err(invalid("unknown header flags"));
+        }
+        if flags & FLAG_TREE != 0 && flags & FLAG_INDEX == 0 {
+            return Err(invalid("R-tree without bounding boxes"));
+        }
+        let count =
+            |at| usize::try_from(read_u64(bytes, at)).map_err(|_| invalid("count overflows usize"));
+        let mut extent = [0.; 6];
+        for (i, value) in extent.iter_mut().enumerate() {
+            *value = read_f64(bytes, 48 + i * 8);
+        }
+        let feature_count = count(16)?;
+        let tree_entries = match flags & FLAG_TREE {
+            0 => None,
+            _ => Some(count(104)?),
+        };
+        if tree_entries.is_some_and(|entries| entries > feature_count) {
+            return Err(invalid("R-tree has more leaves than features"));
+        }
+        let mut layout = Self::new(
+            feature_count,
+            count(24)?,
+            count(32)?,
+            count(40)?,
+            flags & FLAG_INDEX != 0,
+            tree_entries,
+            extent,
+        )
+        .ok_or(invalid("section sizes overflow usize"))?;
+        if layout.byte_len() > bytes.len() {
+            return Err(invalid("buffer is shorter than the header describes"));
+        }
+        layout.deleted_count = count(96)?;
+        let mut deleted = 0;
+        for &kind in &bytes[layout.kinds.clone()] {
+            match kind {
+                0 => deleted += 1,
+                kind if GeometryKind::from_u8(kind).is_some() => {}
+                _ => return Err(invalid("unknown geometry kind")),
+            }
+        }
+        if deleted != layout.deleted_count {
+            return Err(invalid("deleted feature count does not match the kinds"));
+        }
+        Ok(layout)
+    }
+
+    /// Total length in bytes of a buffer with this layout.
+    pub fn byte_len(&self) -> usize {
+        if let Some(tree) = &self.tree {
+            return tree.end();
+        }
+        let last = self.index.as_ref().unwrap_or(&self.coords);
+        last.start + padded(last.end - last.start).expect("checked in Layout::new")
+    }
+
+    pub fn write_header(&self, out: &mut alloc::vec::Vec<u8>) {
+        out.extend_from_slice(&MAGIC);
+        out.extend_from_slice(&VERSION.to_le_bytes());
+        let mut flags = 0;
+        if self.index.is_some() {
+            flags |= FLAG_INDEX;
+        }
+        if self.tree.is_some() {
+            flags |= FLAG_TREE;
+        }
+        out.extend_from_slice(&flags.to_le_bytes());
+        out.extend_from_slice(&[0; 4]);
+        for count in [
+            self.feature_count,
+            self.group_count,
+            self.part_count,
+            self.coord_count,
+        ] {
+            out.extend_from_slice(&(count as u64).to_le_bytes());
+        }
+        for value in self.extent {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        out.extend_from_slice(&(self.deleted_count as u64).to_le_bytes());
+        let tree_entries = self.tree.as_ref().map_or(0, |tree| tree.entries.len() / 8);
+        out.extend_from_slice(&(tree_entries as u64).to_le_bytes());
+        out.resize(out.len() + 16, 0);
+    }
+}
+
+/// An extent that every finite box will enlarge.
+pub(crate) const EMPTY_EXTENT: [f64; 6] = [
+    f64::INFINITY,
+    f64::INFINITY,
+    f64::INFINITY,
+    f64::NEG_INFINITY,
+    f64::NEG_INFINITY,
+    f64::NEG_INFINITY,
+];
+
+pub(crate) fn expand_extent(extent: &mut [f64; 6], other: &[f64; 6]) {
+    for i in 0..3 {
+        extent[i] = extent[i].min(other[i]);
+        extent[i + 3] = extent[i + 3].max(other[i + 3]);
+    }
+}
+
+pub(crate) fn extent_intersects(a: &[f64; 6], b: &[f64; 6]) -> bool {
+    (0..3).all(|i| a[i] <= b[i + 3] && b[i] <= a[i + 3])
+}
+
+pub(crate) fn padded(len: usize) -> Option<usize> {
+    Some(len.checked_add(7)? & !7)
+}
+
+pub(crate) fn pad(out: &mut alloc::vec::Vec<u8>) {
+    out.resize((out.len() + 7) & !7, 0);
+}
+
+pub(crate) fn read_u16(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([bytes[at], bytes[at + 1]])
+}
+
+pub(crate) fn read_u64(bytes: &[u8], at: usize) -> u64 {
+    let mut buf = [0; 8];
+    buf.copy_from_slice(&bytes[at..at + 8]);
+    u64::from_le_bytes(buf)
+}
+
+pub(crate) fn read_f64(bytes: &[u8], at: usize) -> f64 {
+    f64::from_bits(read_u64(bytes, at))
+}
+
+/// Resolves entry `i` of an offsets section to the range `offsets[i]..offsets[i + 1]`.
+pub(crate) fn offset_range(offsets: &[u8], i: usize, limit: usize) -> Range<usize> {
+    match i.checked_add(1) {
+        Some(j) => offset_span(offsets, i, j, limit),
+        None => 0..0,
+    }
+}
+
+/// Resolves the entries `i` and `j` of an offsets section to the range
+/// `offsets[i]..offsets[j]`.
+///
+/// Offsets are validated lazily: a decreasing pair, a value past `limit` or an entry
+/// outside the section yields an empty range instead of a panic.
+pub(crate) fn offset_span(offsets: &[u8], i: usize, j: usize, limit: usize) -> Range<usize> {
+    let read = |i: usize| {
+        let at = i.checked_mul(8)?;
+        let bytes = offsets.get(at..at.checked_add(8)?)?;
+        usize::try_from(read_u64(bytes, 0)).ok()
+    };
+    match (read(i), read(j)) {
+        (Some(start), Some(end)) if start <= end && end <= limit => start..end,
+        _ => 0..0,
+    }
+}
@@ -0,0 +1,576 @@
+//! A single-file container for large collections of 3D geometries.
+//!
+//! A dataset is one contiguous buffer holding a small header, a shared coordinate
+//! arena, offset tables describing how the coordinates form geometries, and an
+//! optional spatial index: a bounding box per feature and a packed Hilbert R-tree
+//! over them. The layout is described in detail in the [`format`](mod@format) module.
+//!
+//! Because every section has a fixed position and fixed-width little-endian values,
+//! a [`Dataset`] can be opened directly on top of a memory-mapped file (see
+//! `Dataset::open`, behind the `mmap` feature) or any other byte buffer. Opening only
+//! validates the header, and features are exposed as [`FeatureView`]s which decode
+//! coordinates lazily without copying or allocating.
+//!
+//! Datasets are written in one go with a [`DatasetBuilder`], or incrementally with a
+//! [`DatasetWriter`].
+//!
+//! With the `tokio` feature, `Dataset::read_async` and `Dataset::into_stream` read
+//! features as a `Stream`, and `AsyncDatasetWriter` writes them from async code.
+//!
+//! # Examples
+//!
+//! ```
+//! use geo_types_3d::dataset::DatasetBuilder;
+//! use geo_types_3d::{Cube, Geometry, PointZ};
+//!
+//! let mut builder = DatasetBuilder::new();
+//! for i in 0..100 {
+//!     let i = i as f64;
+//!     builder.push(&Geometry::PointZ(PointZ::new(i, i, i))).unwrap();
+//! }
+//! let dataset = builder.finish();
+//!
+//! let bounds = Cube::new((10.0, 10.0, 10.0), (19.5, 19.5, 19.5));
+//! let mut ids: Vec<usize> = dataset.query(&bounds).map(|feature| feature.id()).collect();
+//! ids.sort();
+//! assert_eq!(ids, (10..20).collect::<Vec<_>>());
+//! ```
+
+use core::fmt;
+use core::ops::Range;
+
+use crate::{Cube, Error};
+
+mod builder;
+pub mod format;
+mod rtree;
+#[cfg(feature = "tokio")]
+mod stream;
+mod view;
+#[cfg(feature = "std")]
+mod writer;
+
+pub use builder::DatasetBuilder;
+pub use format::GeometryKind;
+#[cfg(feature = "tokio")]
+pub use stream::{AsyncDatasetWriter, FeatureStream};
+pub use view::{CoordsIter, CoordsView, FeatureView, GroupsIter, PartsIter, PartsView};
+#[cfg(feature = "std")]
+pub use writer::DatasetWriter;
+
+use format::Layout;
+use rtree::Search;
+use view::{extent_to_cube, Data};
+
+/// A read-only collection of geometries backed by a buffer in the dataset format.
+///
+/// `B` is usually a `Vec<u8>`, a `&[u8]` or, with the `mmap` feature, a
+/// [`memmap2::Mmap`](https://docs.rs/memmap2).
+pub struct Dataset<B = alloc::vec::Vec<u8>> {
+    bytes: B,
+    layout: Layout,
+}
+
+impl<B: AsRef<[u8]>> Dataset<B> {
+    /// Opens a dataset on top of `bytes`.
+    ///
+    /// Only the header and geometry kinds are validated here. Offsets are checked
+    /// when features are accessed: a corrupt offset table produces empty views (or
+    /// an error from [`FeatureView::to_geometry`]) but never a panic.
+    pub fn from_bytes(bytes: B) -> Result<Self, Error> {
+        let layout = Layout::parse(bytes.as_ref())?;
+        Ok(Self { bytes, layout })
+    }
+
+    /// The number of features in the dataset, not counting deleted ones.
+    pub fn len(&self) -> usize {
+        self.layout.feature_count - self.layout.deleted_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// One past the largest feature id. This is larger than [`Dataset::len`] if
+    /// features were deleted and the dataset has not been compacted since.
+    pub fn id_bound(&self) -> usize {
+        self.layout.feature_count
+    }
+
+    /// Whether the dataset stores a bounding box per feature.
+    ///
+    /// Datasets written by this version of the crate store an R-tree along with
+    /// them, which [`Dataset::query`] searches.
+    pub fn has_index(&self) -> bool {
+        self.layout.index.is_some()
+    }
+
+    /// The bounding cube of all features, or `None` if the dataset has no coordinates.
+    pub fn extent(&self) -> Option<Cube<f64>> {
+        extent_to_cube(self.layout.extent)
+    }
+
+    /// Returns the feature with the given id, or `None` if it doesn't exist or was
+    /// deleted.
+    pub fn get(&self, id: usize) -> Option<FeatureView<'_>> {
+        let data = self.data();
+        (id < self.id_bound() && data.kind(id).is_some()).then(|| FeatureView::new(data, id))
+    }
+
+    /// Iterates over all features in id order.
+    pub fn iter(&self) -> Features<'_> {
+        Features {
+            data: self.data(),
+            ids: 0..self.id_bound(),
+        }
+    }
+
+    /// Iterates over the features whose bounding cube intersects `bounds`.
+    ///
+    /// With an R-tree, only the nodes intersecting `bounds` are visited, and features
+    /// come in the tree's Hilbert order. Otherwise every feature is tested in id
+    /// order, by its stored bounding box if there is one or else by scanning its
+    /// coordinates.
+    pub fn query(&self, bounds: &Cube<f64>) -> Query<'_> {
+        let data = self.data();
+        Query {
+            data,
+            search: Search::query(data, cube_to_extent(bounds)),
+        }
+    }
+
+    /// The underlying buffer.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_ref()
+    }
+
+    pub fn into_inner(self) -> B {
+        self.bytes
+    }
+
+    /// The length of the dataset proper, excluding any trailing bytes.
+    #[cfg(feature = "std")]
+    pub(crate) fn container_len(&self) -> usize {
+        self.layout.byte_len()
+    }
+
+    fn data(&self) -> Data<'_> {
+        Data {
+            bytes: self.bytes.as_ref(),
+            layout: &self.layout,
+        }
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl Dataset<memmap2::Mmap> {
+    /// Memory-maps the dataset file at `path`.
+    ///
+    /// The file must not be modified or truncated while the dataset is open.
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        let file = std::fs::File::open(path)?;
+        // SAFETY: the map is read-only and all reads go through bounds-checked slices.
+        // Concurrent modification of the file by another process is documented as
+        // unsupported above.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Self::from_bytes(map)
+    }
+}
+
+impl<B: AsRef<[u8]>> fmt::Debug for Dataset<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dataset")
+            .field("len", &self.len())
+            .field("has_index", &self.has_index())
+            .field("extent", &self.extent())
+            .finish()
+    }
+}
+
+impl<'a, B: AsRef<[u8]>> IntoIterator for &'a Dataset<B> {
+    type Item = FeatureView<'a>;
+    type IntoIter = Features<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the features of a [`Dataset`].
+#[derive(Clone)]
+pub struct Features<'a> {
+    data: Data<'a>,
+    ids: Range<usize>,
+}
+
+impl<'a> Iterator for Features<'a> {
+    type Item = FeatureView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = self.data;
+        let id = self.ids.find(|&id| data.kind(id).is_some())?;
+        Some(FeatureView::new(data, id))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.ids.size_hint().1)
+    }
+}
+
+impl DoubleEndedIterator for Features<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let data = self.data;
+        let id = self.ids.rfind(|&id| data.kind(id).is_some())?;
+        Some(FeatureView::new(data, id))
+    }
+}
+
+impl fmt::Debug for Features<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Features").field("ids", &self.ids).finish()
+    }
+}
+
+/// Iterator over the features matching a [`Dataset::query`].
+#[derive(Clone)]
+pub struct Query<'a> {
+    data: Data<'a>,
+    search: Search,
+}
+
+impl<'a> Iterator for Query<'a> {
+    type Item = FeatureView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.search.next(self.data)?;
+        Some(FeatureView::new(self.data, id))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.search.upper_bound(self.data)))
+    }
+}
+
+impl fmt::Debug for Query<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Query")
+            .field("search", &self.search)
+            .finish()
+    }
+}
+
+pub(crate) fn cube_to_extent(bounds: &Cube<f64>) -> [f64; 6] {
+    let (min, max) = (bounds.min(), bounds.max());
+    [min.x, min.y, min.z, max.x, max.y, max.z]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Geometry, LineZ, MultiLineStringZ, MultiPointZ, MultiPolygonZ, PointZ};
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    fn geometries() -> Vec<Geometry<f64>> {
+        let square = |offset: f64| {
+            polygon_z!(
+                exterior: [
+                    (x: offset, y: offset, z: 1.),
+                    (x: offset + 2., y: offset, z: 1.),
+                    (x: offset + 2., y: offset + 2., z: 1.),
+                    (x: offset, y: offset + 2., z: 1.),
+                ],
+                interiors: [
+                    [
+                        (x: offset + 0.5, y: offset + 0.5, z: 1.),
+                        (x: offset + 1., y: offset + 0.5, z: 1.),
+                        (x: offset + 1., y: offset + 1., z: 1.),
+                    ],
+                ],
+            )
+        };
+        vec![
+            Geometry::PointZ(PointZ::new(1., 2., 3.)),
+            Geometry::LineZ(LineZ::new(
+                coordZ! { x: 0., y: 0., z: 0. },
+                coordZ! { x: 1., y: 1., z: 1. },
+            )),
+            Geometry::LineStringZ(line_string_z![
+                (x: 10., y: 10., z: 10.),
+                (x: 11., y: 12., z: 13.),
+                (x: 14., y: 15., z: 16.),
+            ]),
+            Geometry::LineStringZ(line_string_z![]),
+            Geometry::PolygonZ(square(0.)),
+            Geometry::MultiPointZ(MultiPointZ::from(vec![(5., 5., 5.), (6., 6., 6.)])),
+            Geometry::MultiLineStringZ(MultiLineStringZ::new(vec![
+                line_string_z![(x: 0., y: 0., z: 0.), (x: 1., y: 0., z: 0.)],
+                line_string_z![(x: 0., y: 1., z: 0.), (x: 1., y: 1., z: 0.)],
+            ])),
+            Geometry::MultiPolygonZ(MultiPolygonZ::new(vec![square(20.), square(30.)])),
+        ]
+    }
+
+    fn build(index: bool) -> Dataset {
+        let mut builder = DatasetBuilder::new().with_index(index);
+        for geometry in geometries() {
+            builder.push(&geometry).unwrap();
+        }
+        builder.finish()
+    }
+
+    #[test]
+    fn round_trip() {
+        for index in [true, false] {
+            let dataset = build(index);
+            assert_eq!(dataset.has_index(), index);
+            assert_eq!(dataset.len(), geometries().len());
+            let decoded: Vec<_> = dataset
+                .iter()
+                .map(|feature| feature.to_geometry().unwrap())
+                .collect();
+            assert_eq!(decoded, geometries());
+        }
+    }
+
+    #[test]
+    fn views() {
+        let dataset = build(true);
+        let polygon = dataset.get(4).unwrap();
+        assert_eq!(polygon.kind(), GeometryKind::PolygonZ);
+        assert_eq!(polygon.parts().len(), 2);
+        assert_eq!(polygon.parts().get(0).unwrap().len(), 5);
+        assert_eq!(polygon.coords().len(), 9);
+
+        let multi_polygon = dataset.get(7).unwrap();
+        assert_eq!(multi_polygon.groups().len(), 2);
+        assert_eq!(multi_polygon.parts().len(), 4);
+        let second = multi_polygon.groups().nth(1).unwrap();
+        assert_eq!(
+            second.get(0).unwrap().get(0),
+            Some(coordZ! { x: 30., y: 30., z: 1. })
+        );
+
+        let empty = dataset.get(3).unwrap();
+        assert!(empty.coords().is_empty());
+        assert_eq!(empty.bounding_cube(), None);
+
+        assert!(dataset.get(8).is_none());
+    }
+
+    #[test]
+    fn query() {
+        for index in [true, false] {
+            let dataset = build(index);
+            let ids = |bounds: Cube<f64>| {
+                let mut ids: Vec<_> = dataset.query(&bounds).map(|feature| feature.id()).collect();
+                ids.sort();
+                ids
+            };
+            assert_eq!(
+                ids(Cube::new((0.5, 0.5, 0.), (1.5, 1.5, 1.5))),
+                vec![1, 4, 6]
+            );
+            assert_eq!(ids(Cube::new((25., 25., 0.), (35., 35., 5.))), vec![7]);
+            assert_eq!(
+                ids(Cube::new((100., 100., 100.), (200., 200., 200.))),
+                Vec::<usize>::new()
+            );
+        }
+    }
+
+    #[test]
+    fn tree_query() {
+        // 2000 points on a 20 × 20 × 5 grid give a tree with three levels of nodes
+        let mut builder = DatasetBuilder::new();
+        for i in 0..2000 {
+            let (x, y, z) = (i % 20, i / 20 % 20, i / 400);
+            let point = PointZ::new(x as f64, y as f64, z as f64);
+            builder.push(&Geometry::PointZ(point)).unwrap();
+        }
+        builder.remove(7);
+        let dataset = builder.finish();
+        assert_eq!(dataset.layout.tree.as_ref().unwrap().levels.len(), 3);
+
+        for bounds in [
+            Cube::new((2.5, 2.5, 0.), (4., 6., 1.)),
+            Cube::new((-1., -1., -1.), (7.5, 0.5, 0.5)),
+            Cube::new((0., 0., 0.), (19., 19., 4.)),
+            Cube::new((19.5, 0., 0.), (30., 30., 30.)),
+        ] {
+            let mut ids: Vec<_> = dataset.query(&bounds).map(|feature| feature.id()).collect();
+            ids.sort();
+            let expected: Vec<_> = dataset
+                .iter()
+                .filter(|feature| {
+                    let cube = feature.bounding_cube().unwrap();
+                    format::extent_intersects(&cube_to_extent(&cube), &cube_to_extent(&bounds))
+                })
+                .map(|feature| feature.id())
+                .collect();
+            assert_eq!(ids, expected);
+        }
+
+        // a small query only tests the nodes and leaves along a few paths
+        let bounds = cube_to_extent(&Cube::new((2.5, 2.5, 0.), (3.5, 3.5, 0.)));
+        let mut search = Search::query(dataset.data(), bounds);
+        let mut steps = 0;
+        while search.step(dataset.data()).is_some() {
+            steps += 1;
+        }
+        assert!(steps < 200, "{steps} steps");
+    }
+
+    #[test]
+    fn corrupt_tree_does_not_panic() {
+        let mut bytes = build(true).into_inner();
+        let layout = Layout::parse(&bytes).unwrap();
+        let tree = layout.tree.unwrap();
+        for byte in &mut bytes[tree.entries.start..tree.end()] {
+            *byte = 0xff;
+        }
+        let dataset = Dataset::from_bytes(bytes).unwrap();
+        let everything = Cube::new((-1e9, -1e9, -1e9), (1e9, 1e9, 1e9));
+        assert_eq!(dataset.query(&everything).count(), 0);
+
+        // a tree with more leaves than features is rejected on open
+        let mut bytes = dataset.into_inner();
+        bytes[104..112].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(matches!(
+            Dataset::from_bytes(bytes),
+            Err(Error::InvalidDataset { .. })
+        ));
+    }
+
+    #[test]
+    fn extent() {
+        let dataset = build(true);
+        assert_eq!(
+            dataset.extent(),
+            Some(Cube::new((0., 0., 0.), (32., 32., 16.)))
+        );
+        assert_eq!(DatasetBuilder::new().finish().extent(), None);
+    }
+
+    #[test]
+    fn borrowed_buffer() {
+        let bytes = build(true).into_inner();
+        let dataset = Dataset::from_bytes(&bytes[..]).unwrap();
+        assert_eq!(
+            dataset.get(0).unwrap().to_geometry().unwrap(),
+            Geometry::PointZ(PointZ::new(1., 2., 3.))
+        );
+    }
+
+    #[test]
+    fn unsupported_geometry() {
+        let mut builder = DatasetBuilder::new();
+        let point = Geometry::Point(crate::Point::new(1., 2.));
+        assert!(matches!(
+            builder.push(&point),
+            Err(Error::UnsupportedGeometry { .. })
+        ));
+        assert!(builder.is_empty());
+    }
+
+    #[test]
+    fn invalid_buffers() {
+        let bytes = build(true).into_inner();
+        let invalid = |bytes: &[u8]| {
+            matches!(
+                Dataset::from_bytes(bytes),
+                Err(Error::InvalidDataset { .. })
+            )
+        };
+        assert!(invalid(&bytes[..16]));
+        assert!(invalid(&bytes[..bytes.len() - 8]));
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        assert!(invalid(&bad_magic));
+
+        let mut bad_kind = bytes.clone();
+        bad_kind[format::HEADER_LEN] = 42;
+        assert!(invalid(&bad_kind));
+    }
+
+    #[test]
+    fn corrupt_offsets_do_not_panic() {
+        let mut bytes = build(false).into_inner();
+        let layout = Layout::parse(&bytes).unwrap();
+        for byte in &mut bytes[layout.part_offsets.clone()] {
+            *byte = 0xff;
+        }
+        let dataset = Dataset::from_bytes(bytes).unwrap();
+        for feature in &dataset {
+            assert!(feature.coords().is_empty());
+            // points and lines can't be decoded without coordinates, but must not panic
+            let _ = feature.to_geometry();
+        }
+        let everything = Cube::new((-1e9, -1e9, -1e9), (1e9, 1e9, 1e9));
+        assert_eq!(dataset.query(&everything).count(), 0);
+    }
+
+    #[test]
+    fn removed_features() {
+        let mut builder = DatasetBuilder::new();
+        for geometry in geometries() {
+            builder.push(&geometry).unwrap();
+        }
+        assert!(builder.remove(1));
+        assert!(builder.remove(4));
+        assert!(!builder.remove(4));
+        assert!(!builder.remove(100));
+        assert_eq!(builder.len(), geometries().len() - 2);
+
+        let dataset = Dataset::from_bytes(builder.to_bytes()).unwrap();
+        assert_eq!(dataset.len(), geometries().len() - 2);
+        assert_eq!(dataset.id_bound(), geometries().len());
+        assert!(dataset.get(1).is_none());
+        assert_eq!(dataset.iter().count(), dataset.len());
+        let ids = |bounds: Cube<f64>| {
+            let mut ids: Vec<_> = dataset.query(&bounds).map(|feature| feature.id()).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(ids(Cube::new((0.5, 0.5, 0.), (1.5, 1.5, 1.5))), vec![6]);
+
+        // copying keeps ids, compacting renumbers
+        let mut copy = DatasetBuilder::from_dataset(&dataset);
+        assert_eq!(copy.id_bound(), geometries().len());
+        let ids = copy.compact();
+        assert_eq!(ids[..5], [Some(0), None, Some(1), Some(2), None]);
+        let compacted = copy.finish();
+        assert_eq!(compacted.id_bound(), compacted.len());
+        let mut expected = geometries();
+        expected.remove(4);
+        expected.remove(1);
+        let decoded: Vec<_> = compacted
+            .iter()
+            .map(|feature| feature.to_geometry().unwrap())
+            .collect();
+        assert_eq!(decoded, expected);
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn open_mmap() {
+        let path =
+            std::env::temp_dir().join(format!("geo-types-3d-{}.dataset", std::process::id()));
+        let mut builder = DatasetBuilder::new();
+        for geometry in geometries() {
+            builder.push(&geometry).unwrap();
+        }
+        builder
+            .write_to(std::fs::File::create(&path).unwrap())
+            .unwrap();
+        let dataset = Dataset::open(&path).unwrap();
+        assert_eq!(dataset.len(), geometries().len());
+        assert_eq!(
+            dataset.get(2).unwrap().to_geometry().unwrap(),
+            geometries()[2]
+        );
+        drop(dataset);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,225 @@
+//! The packed Hilbert R-tree of the dataset format.
+//!
+//! The leaves are the ids of every live, non-empty feature, sorted by the Hilbert
+//! key of the centre of their bounding box within the dataset extent. Each node
+//! above them holds the bounding box of [`NODE_SIZE`] consecutive entries of the
+//! level below, so node `i` of a level covers entries `i * NODE_SIZE` to
+//! `(i + 1) * NODE_SIZE` of the level underneath. Levels are added until one holds
+//! a single root node.
+//!
+//! Because the tree is packed, only the number of leaves has to be stored: the size
+//! of every level follows from it.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::format::{expand_extent, extent_intersects, read_f64, read_u64, BBOX_LEN, EMPTY_EXTENT};
+use super::view::{extent_to_cube, Data};
+use crate::algorithm::hilbert_key;
+use crate::CoordZ;
+
+/// The number of children of each node.
+pub(crate) const NODE_SIZE: usize = 16;
+
+/// Byte ranges of the leaves and node levels of a packed R-tree.
+#[derive(Clone, Debug)]
+pub(crate) struct TreeLayout {
+    /// One `u64` feature id per leaf.
+    pub entries: Range<usize>,
+    /// The node boxes of each level, lowest first, so the last holds the root.
+    pub levels: Vec<Range<usize>>,
+}
+
+impl TreeLayout {
+    /// Lays out a tree over `entry_count` leaves starting at byte `start`. Returns
+    /// `None` on overflow.
+    pub fn new(start: usize, entry_count: usize) -> Option<Self> {
+        let entries = start..start.checked_add(entry_count.checked_mul(8)?)?;
+        let mut end = entries.end;
+        let mut levels = Vec::new();
+        let mut count = entry_count;
+        while count > 1 {
+            count = count.div_ceil(NODE_SIZE);
+            let level = end..end.checked_add(count.checked_mul(BBOX_LEN)?)?;
+            end = level.end;
+            levels.push(level);
+        }
+        Some(Self { entries, levels })
+    }
+
+    pub fn end(&self) -> usize {
+        self.levels.last().unwrap_or(&self.entries).end
+    }
+
+    /// The number of leaves for `level` 0, or nodes above.
+    fn len(&self, level: usize) -> usize {
+        match level {
+            0 => self.entries.len() / 8,
+            level => self.levels[level - 1].len() / BBOX_LEN,
+        }
+    }
+
+    fn node(&self, bytes: &[u8], level: usize, node: usize) -> [f64; 6] {
+        let at = self.levels[level - 1].start + node * BBOX_LEN;
+        let mut bbox = [0.; 6];
+        for (i, value) in bbox.iter_mut().enumerate() {
+            *value = read_f64(bytes, at + i * 8);
+        }
+        bbox
+    }
+}
+
+/// Sorts the features with a non-empty box in Hilbert order, returning their ids.
+pub(crate) fn sort_entries(bboxes: &[[f64; 6]], extent: [f64; 6]) -> Vec<usize> {
+    let Some(extent) = extent_to_cube(extent) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<(u64, usize)> = bboxes
+        .iter()
+        .enumerate()
+        .filter(|(_, bbox)| extent_to_cube(**bbox).is_some())
+        .map(|(id, bbox)| {
+            let centre = CoordZ {
+                x: (bbox[0] + bbox[3]) / 2.,
+                y: (bbox[1] + bbox[4]) / 2.,
+                z: (bbox[2] + bbox[5]) / 2.,
+            };
+            (hilbert_key(centre, &extent), id)
+        })
+        .collect();
+    entries.sort_unstable();
+    entries.into_iter().map(|(_, id)| id).collect()
+}
+
+/// Appends the leaves and node boxes of the tree over `entries`, laid out as
+/// [`TreeLayout`] describes.
+pub(crate) fn write_tree(out: &mut Vec<u8>, entries: &[usize], bboxes: &[[f64; 6]]) {
+    for &id in entries {
+        out.extend_from_slice(&(id as u64).to_le_bytes());
+    }
+    let mut level: Vec<[f64; 6]> = entries.iter().map(|&id| bboxes[id]).collect();
+    while level.len() > 1 {
+        level = level
+            .chunks(NODE_SIZE)
+            .map(|children| {
+                let mut node = EMPTY_EXTENT;
+                for child in children {
+                    expand_extent(&mut node, child);
+                }
+                node
+            })
+            .collect();
+        for value in level.iter().flatten() {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+}
+
+/// The ids matching a bounding box query, either from a tree search or from a scan
+/// in id order.
+///
+/// It borrows nothing, so that streams can keep it next to the dataset they own.
+#[derive(Clone, Debug)]
+pub(crate) enum Search {
+    Scan {
+        ids: Range<usize>,
+        bounds: Option<[f64; 6]>,
+    },
+    Tree {
+        bounds: [f64; 6],
+        /// The nodes left to visit on each level of the current path, as
+        /// `(level, nodes)`; level 0 holds the leaves.
+        stack: Vec<(usize, Range<usize>)>,
+    },
+}
+
+impl Search {
+    /// Visits every live feature in id order.
+    #[cfg(feature = "tokio")]
+    pub fn all(data: Data<'_>) -> Self {
+        Self::Scan {
+            ids: 0..data.layout.feature_count,
+            bounds: None,
+        }
+    }
+
+    /// Visits the features whose bounding box intersects `bounds`, through the tree
+    /// if the dataset has one.
+    pub fn query(data: Data<'_>, bounds: [f64; 6]) -> Self {
+        if !extent_intersects(&data.layout.extent, &bounds) {
+            return Self::Scan {
+                ids: 0..0,
+                bounds: None,
+            };
+        }
+        match &data.layout.tree {
+            Some(tree) => {
+                let top = tree.levels.len();
+                Self::Tree {
+                    bounds,
+                    stack: vec![(top, 0..tree.len(top))],
+                }
+            }
+            None => Self::Scan {
+                ids: 0..data.layout.feature_count,
+                bounds: Some(bounds),
+            },
+        }
+    }
+
+    /// The next matching id. `data` must be the dataset the search was created for.
+    pub fn next(&mut self, data: Data<'_>) -> Option<usize> {
+        loop {
+            if let Some(id) = self.step(data)? {
+                return Some(id);
+            }
+        }
+    }
+
+    /// Tests a single feature or node, returning the feature id if it matched.
+    /// Returns `None` once the search is over.
+    pub fn step(&mut self, data: Data<'_>) -> Option<Option<usize>> {
+        match self {
+            Self::Scan { ids, bounds } => {
+                let id = ids.next()?;
+                let matches = data.kind(id).is_some()
+                    && bounds.is_none_or(|bounds| extent_intersects(&data.bbox(id), &bounds));
+                Some(matches.then_some(id))
+            }
+            Self::Tree { bounds, stack } => {
+                let tree = data.layout.tree.as_ref()?;
+                let (level, nodes) = stack.last_mut()?;
+                let level = *level;
+                let Some(node) = nodes.next() else {
+                    stack.pop();
+                    return Some(None);
+                };
+                if level == 0 {
+                    let at = tree.entries.start + node * 8;
+                    // ids are checked here rather than on open, like offsets
+                    let id = usize::try_from(read_u64(data.bytes, at)).ok();
+                    return Some(id.filter(|&id| {
+                        id < data.layout.feature_count
+                            && data.kind(id).is_some()
+                            && extent_intersects(&data.bbox(id), bounds)
+                    }));
+                }
+                if extent_intersects(&tree.node(data.bytes, level, node), bounds) {
+                    let start = node * NODE_SIZE;
+                    let end = (start + NODE_SIZE).min(tree.len(level - 1));
+                    stack.push((level - 1, start..end));
+                }
+                Some(None)
+            }
+        }
+    }
+
+    /// An upper bound on the number of ids left.
+    pub fn upper_bound(&self, data: Data<'_>) -> usize {
+        match self {
+            Self::Scan { ids, .. } => ids.len(),
+            Self::Tree { .. } => data.layout.tree.as_ref().map_or(0, |tree| tree.len(0)),
+        }
+    }
+}
@@ -0,0 +1,326 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::path::Path;
+use std::sync::Arc;
+
+use futures_core::Stream;
+use tokio::sync::Mutex;
+
+use super::rtree::Search;
+use super::{cube_to_extent, Dataset, DatasetWriter};
+use crate::{CoordNum, Cube, Error, Geometry};
+
+/// The number of features or index nodes a [`FeatureStream`] tests before
+/// yielding to the executor.
+const BATCH: usize = 256;
+
+impl Dataset<Vec<u8>> {
+    /// Reads the dataset file at `path` without blocking the executor.
+    ///
+    /// Any journal a [`DatasetWriter`] has appended to the file is ignored, as with
+    /// the other ways of opening a dataset.
+    pub async fn read_async<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let mut bytes = tokio::fs::read(path).await?;
+        let len = Dataset::from_bytes(&bytes[..])?.container_len();
+        bytes.truncate(len);
+        Dataset::from_bytes(bytes)
+    }
+}
+
+impl<B: AsRef<[u8]>> Dataset<B> {
+    /// Turns the dataset into a [`Stream`] of `(id, geometry)` pairs in id order.
+    pub fn into_stream(self) -> FeatureStream<B> {
+        FeatureStream {
+            search: Search::all(self.data()),
+            dataset: self,
+        }
+    }
+
+    /// Like [`Dataset::into_stream`], but only yields the features whose bounding
+    /// cube intersects `bounds`, in the order of [`Dataset::query`].
+    pub fn into_query_stream(self, bounds: &Cube<f64>) -> FeatureStream<B> {
+        FeatureStream {
+            search: Search::query(self.data(), cube_to_extent(bounds)),
+            dataset: self,
+        }
+    }
+}
+
+/// A [`Stream`] over the decoded features of a [`Dataset`].
+///
+/// The stream yields back to the executor after every few hundred features, so
+/// streaming a large dataset doesn't starve other tasks. Dropping the stream at any
+/// point is safe.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::dataset::DatasetBuilder;
+/// use geo_types_3d::{Geometry, PointZ};
+/// use std::future::poll_fn;
+/// use std::pin::Pin;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut builder = DatasetBuilder::new();
+/// builder.push(&Geometry::PointZ(PointZ::new(1.0, 2.0, 3.0))).unwrap();
+///
+/// let mut stream = builder.finish().into_stream();
+/// let next = poll_fn(|cx| futures_core::Stream::poll_next(Pin::new(&mut stream), cx)).await;
+/// let (id, geometry) = next.unwrap().unwrap();
+/// assert_eq!(id, 0);
+/// assert_eq!(geometry, Geometry::PointZ(PointZ::new(1.0, 2.0, 3.0)));
+/// # });
+/// ```
+pub struct FeatureStream<B = Vec<u8>> {
+    dataset: Dataset<B>,
+    search: Search,
+}
+
+impl<B: AsRef<[u8]>> FeatureStream<B> {
+    pub fn into_inner(self) -> Dataset<B> {
+        self.dataset
+    }
+}
+
+impl<B: AsRef<[u8]> + Unpin> Stream for FeatureStream<B> {
+    type Item = Result<(usize, Geometry<f64>), Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let data = this.dataset.data();
+        for _ in 0..BATCH {
+            let Some(step) = this.search.step(data) else {
+                return Poll::Ready(None);
+            };
+            let Some(id) = step else {
+                continue;
+            };
+            let feature = this.dataset.get(id).expect("live feature");
+            return Poll::Ready(Some(feature.to_geometry().map(|geometry| (id, geometry))));
+        }
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.search.upper_bound(self.dataset.data())))
+    }
+}
+
+impl<B: AsRef<[u8]>> core::fmt::Debug for FeatureStream<B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FeatureStream")
+            .field("search", &self.search)
+            .finish()
+    }
+}
+
+/// A [`DatasetWriter`] for async code.
+///
+/// Every operation runs on tokio's blocking thread pool. Operations are
+/// cancellation safe: once started, an operation runs to completion even if the
+/// future awaiting it is dropped, so the journal never contains a partially written
+/// record, and later operations wait for it to finish.
+///
+/// # Examples
+///
+/// ```no_run
+/// use geo_types_3d::dataset::AsyncDatasetWriter;
+/// use geo_types_3d::{Geometry, PointZ};
+///
+/// # async fn collect() -> Result<(), geo_types_3d::Error> {
+/// let mut writer = AsyncDatasetWriter::create("telemetry.g3d").await?;
+/// writer
+///     .push_feature(Geometry::PointZ(PointZ::new(1.0, 2.0, 3.0)))
+///     .await?;
+/// writer.sync().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AsyncDatasetWriter {
+    inner: Arc<Mutex<DatasetWriter>>,
+}
+
+impl AsyncDatasetWriter {
+    /// Creates an empty dataset at `path`, see [`DatasetWriter::create`].
+    pub async fn create<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let writer = blocking(move || DatasetWriter::create(path)).await?;
+        Ok(Self::new(writer))
+    }
+
+    /// Opens the dataset at `path`, see [`DatasetWriter::open`].
+    pub async fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let writer = blocking(move || DatasetWriter::open(path)).await?;
+        Ok(Self::new(writer))
+    }
+
+    /// Wraps a synchronous writer.
+    pub fn new(writer: DatasetWriter) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(writer)),
+        }
+    }
+
+    /// Rebuilds the index every `records` journal records, see
+    /// [`DatasetWriter::with_rebuild_interval`].
+    pub async fn set_rebuild_interval(&mut self, records: usize) {
+        self.inner.lock().await.set_rebuild_interval(records);
+    }
+
+    /// The number of live features.
+    pub async fn len(&self) -> usize {
+        self.inner.lock().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.inner.lock().await.is_empty()
+    }
+
+    pub async fn push_feature<T>(&mut self, geometry: Geometry<T>) -> Result<usize, Error>
+    where
+        T: CoordNum + Send + 'static,
+    {
+        self.run(move |writer| writer.push_feature(&geometry)).await
+    }
+
+    pub async fn delete(&mut self, id: usize) -> Result<bool, Error> {
+        self.run(move |writer| writer.delete(id)).await
+    }
+
+    pub async fn flush(&mut self) -> Result<(), Error> {
+        self.run(DatasetWriter::flush).await
+    }
+
+    pub async fn sync(&mut self) -> Result<(), Error> {
+        self.run(DatasetWriter::sync).await
+    }
+
+    pub async fn rebuild_index(&mut self) -> Result<(), Error> {
+        self.run(DatasetWriter::rebuild_index).await
+    }
+
+    pub async fn compact(&mut self) -> Result<Vec<Option<usize>>, Error> {
+        self.run(DatasetWriter::compact).await
+    }
+
+    /// Waits for pending operations and returns the synchronous writer.
+    pub async fn into_inner(self) -> DatasetWriter {
+        let mut inner = self.inner;
+        loop {
+            match Arc::try_unwrap(inner) {
+                Ok(mutex) => return mutex.into_inner(),
+                Err(shared) => {
+                    // a cancelled operation still holds a reference
+                    drop(shared.lock().await);
+                    tokio::task::yield_now().await;
+                    inner = shared;
+                }
+            }
+        }
+    }
+
+    async fn run<R, F>(&mut self, op: F) -> Result<R, Error>
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut DatasetWriter) -> Result<R, Error> + Send + 'static,
+    {
+        let inner = self.inner.clone();
+        // take the lock before spawning so operations run in call order
+        let mut writer = inner.lock_owned().await;
+        blocking(move || op(&mut writer)).await
+    }
+}
+
+async fn blocking<R, F>(f: F) -> Result<R, Error>
+where
+    R: Send + 'static,
+    F: FnOnce() -> Result<R, Error> + Send + 'static,
+{
+    match tokio::task::spawn_blocking(f).await {
+        Ok(result) => result,
+        Err(err) => std::panic::resume_unwind(err.into_panic()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dataset::DatasetBuilder;
+    use crate::PointZ;
+    use std::future::{poll_fn, Future};
+
+    async fn collect<B: AsRef<[u8]> + Unpin>(mut stream: FeatureStream<B>) -> Vec<usize> {
+        let mut ids = Vec::new();
+        while let Some(item) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+            ids.push(item.unwrap().0);
+        }
+        ids
+    }
+
+    fn point(i: usize) -> Geometry<f64> {
+        let i = i as f64;
+        Geometry::PointZ(PointZ::new(i, i, i))
+    }
+
+    #[tokio::test]
+    async fn stream_skips_deleted_features() {
+        let mut builder = DatasetBuilder::new();
+        for i in 0..1000 {
+            builder.push(&point(i)).unwrap();
+        }
+        builder.remove(3);
+        let dataset = builder.finish();
+
+        let ids = collect(
+            Dataset::from_bytes(dataset.as_bytes())
+                .unwrap()
+                .into_stream(),
+        )
+        .await;
+        assert_eq!(ids.len(), 999);
+        assert!(!ids.contains(&3));
+
+        let bounds = Cube::new((1.5, 1.5, 1.5), (600., 600., 600.));
+        let mut ids = collect(dataset.into_query_stream(&bounds)).await;
+        ids.sort();
+        assert_eq!(ids, (2..=600).filter(|&id| id != 3).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn write_and_read_back() {
+        let path = std::env::temp_dir().join(format!(
+            "geo-types-3d-{}-async-writer.g3d",
+            std::process::id()
+        ));
+        let mut writer = AsyncDatasetWriter::create(&path).await.unwrap();
+        for i in 0..10 {
+            assert_eq!(writer.push_feature(point(i)).await.unwrap(), i);
+        }
+        assert!(writer.delete(0).await.unwrap());
+        assert_eq!(writer.len().await, 9);
+
+        // the journal isn't visible before the index is rebuilt
+        assert!(Dataset::read_async(&path).await.unwrap().is_empty());
+        writer.rebuild_index().await.unwrap();
+        let dataset = Dataset::read_async(&path).await.unwrap();
+        assert_eq!(
+            collect(dataset.into_stream()).await,
+            (1..10).collect::<Vec<_>>()
+        );
+
+        // an operation that was started completes even if its future is dropped
+        let mut push = Box::pin(writer.push_feature(point(10)));
+        let _ = poll_fn(|cx| Poll::Ready(push.as_mut().poll(cx))).await;
+        drop(push);
+        // one that was never polled doesn't run
+        drop(writer.push_feature(point(11)));
+        let writer = writer.into_inner().await;
+        assert_eq!(writer.id_bound(), 11);
+        drop(writer);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
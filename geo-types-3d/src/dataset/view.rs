@@ -0,0 +1,415 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Range;
+use core::slice::ChunksExact;
+
+use super::format::{
+    offset_range, offset_span, read_f64, GeometryKind, Layout, BBOX_LEN, COORD_LEN, EMPTY_EXTENT,
+};
+use crate::{
+    CoordZ, Cube, Error, Geometry, LineStringZ, LineZ, MultiLineStringZ, MultiPointZ,
+    MultiPolygonZ, PointZ, PolygonZ,
+};
+
+/// A borrowed dataset buffer together with its parsed layout.
+#[derive(Copy, Clone)]
+pub(crate) struct Data<'a> {
+    pub bytes: &'a [u8],
+    pub layout: &'a Layout,
+}
+
+impl<'a> Data<'a> {
+    /// The kind of `feature`, or `None` if it was deleted.
+    pub fn kind(&self, feature: usize) -> Option<GeometryKind> {
+        GeometryKind::from_u8(self.bytes[self.layout.kinds.start + feature])
+    }
+
+    fn section(&self, range: &Range<usize>) -> &'a [u8] {
+        &self.bytes[range.clone()]
+    }
+
+    fn feature_groups(&self, feature: usize) -> Range<usize> {
+        let offsets = self.section(&self.layout.feature_offsets);
+        offset_range(offsets, feature, self.layout.group_count)
+    }
+
+    fn group_parts(&self, groups: Range<usize>) -> Range<usize> {
+        let offsets = self.section(&self.layout.group_offsets);
+        offset_span(offsets, groups.start, groups.end, self.layout.part_count)
+    }
+
+    fn coords(&self, parts: Range<usize>) -> CoordsView<'a> {
+        let offsets = self.section(&self.layout.part_offsets);
+        let coords = offset_span(offsets, parts.start, parts.end, self.layout.coord_count);
+        let arena = self.section(&self.layout.coords);
+        CoordsView {
+            bytes: &arena[coords.start * COORD_LEN..coords.end * COORD_LEN],
+        }
+    }
+
+    /// The stored bounding box of `feature`, if the dataset has an index.
+    pub fn indexed_bbox(&self, feature: usize) -> Option<[f64; 6]> {
+        let index = self.layout.index.as_ref()?;
+        let at = index.start + feature * BBOX_LEN;
+        let mut bbox = [0.; 6];
+        for (i, value) in bbox.iter_mut().enumerate() {
+            *value = read_f64(self.bytes, at + i * 8);
+        }
+        Some(bbox)
+    }
+
+    pub fn bbox(&self, feature: usize) -> [f64; 6] {
+        match self.indexed_bbox(feature) {
+            Some(bbox) => bbox,
+            None => FeatureView::new(*self, feature).coords().extent(),
+        }
+    }
+}
+
+/// A zero-copy view of a single feature stored in a [`Dataset`](super::Dataset).
+///
+/// Coordinates are decoded lazily from the underlying buffer while iterating; use
+/// [`FeatureView::to_geometry`] to copy the feature into an owned [`Geometry`].
+#[derive(Copy, Clone)]
+pub struct FeatureView<'a> {
+    data: Data<'a>,
+    id: usize,
+}
+
+impl<'a> FeatureView<'a> {
+    pub(crate) fn new(data: Data<'a>, id: usize) -> Self {
+        Self { data, id }
+    }
+
+    /// The position of this feature in the dataset.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// The geometry type of this feature.
+    pub fn kind(&self) -> GeometryKind {
+        self.data
+            .kind(self.id)
+            .expect("views are only created for live features")
+    }
+
+    /// All coordinates of this feature, in storage order.
+    pub fn coords(&self) -> CoordsView<'a> {
+        self.data.coords(self.parts().parts)
+    }
+
+    /// All parts (point sets, line strings and rings) of this feature.
+    pub fn parts(&self) -> PartsView<'a> {
+        PartsView {
+            data: self.data,
+            parts: self.data.group_parts(self.data.feature_groups(self.id)),
+        }
+    }
+
+    /// The groups of this feature. Each polygon of a [`GeometryKind::MultiPolygonZ`]
+    /// is a group of rings; every other kind has exactly one group.
+    pub fn groups(&self) -> GroupsIter<'a> {
+        GroupsIter {
+            data: self.data,
+            groups: self.data.feature_groups(self.id),
+        }
+    }
+
+    /// The bounding cube of this feature, read from the index when the dataset has one.
+    ///
+    /// Returns `None` if the feature has no coordinates.
+    pub fn bounding_cube(&self) -> Option<Cube<f64>> {
+        extent_to_cube(self.data.bbox(self.id))
+    }
+
+    /// Decodes this feature into an owned [`Geometry`].
+    ///
+    /// Fails if the offsets stored for this feature don't describe a geometry of its
+    /// kind, e.g. a `PointZ` without a coordinate.
+    pub fn to_geometry(&self) -> Result<Geometry<f64>, Error> {
+        let parts = self.parts();
+        let single_part = || match parts.len() {
+            1 => Ok(parts.get(0).expect("one part")),
+            _ => Err(Error::InvalidDataset {
+                reason: "feature does not have exactly one part",
+            }),
+        };
+        Ok(match self.kind() {
+            GeometryKind::PointZ => match single_part()?.get(0) {
+                Some(coord) => Geometry::PointZ(PointZ(coord)),
+                None => {
+                    return Err(Error::InvalidDataset {
+                        reason: "point feature without a coordinate",
+                    })
+                }
+            },
+            GeometryKind::LineZ => {
+                let coords = single_part()?;
+                match (coords.get(0), coords.get(1)) {
+                    (Some(start), Some(end)) if coords.len() == 2 => {
+                        Geometry::LineZ(LineZ::new(start, end))
+                    }
+                    _ => {
+                        return Err(Error::InvalidDataset {
+                            reason: "line feature without exactly two coordinates",
+                        })
+                    }
+                }
+            }
+            GeometryKind::LineStringZ => Geometry::LineStringZ(single_part()?.to_line_string()),
+            GeometryKind::PolygonZ => Geometry::PolygonZ(parts.to_polygon()),
+            GeometryKind::MultiPointZ => {
+                Geometry::MultiPointZ(MultiPointZ(single_part()?.iter().map(PointZ).collect()))
+            }
+            GeometryKind::MultiLineStringZ => Geometry::MultiLineStringZ(MultiLineStringZ(
+                parts.iter().map(|part| part.to_line_string()).collect(),
+            )),
+            GeometryKind::MultiPolygonZ => Geometry::MultiPolygonZ(MultiPolygonZ(
+                self.groups().map(|group| group.to_polygon()).collect(),
+            )),
+        })
+    }
+}
+
+impl fmt::Debug for FeatureView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FeatureView")
+            .field("id", &self.id)
+            .field("kind", &self.kind())
+            .finish()
+    }
+}
+
+/// A run of parts, e.g. the rings of a polygon.
+#[derive(Clone)]
+pub struct PartsView<'a> {
+    data: Data<'a>,
+    parts: Range<usize>,
+}
+
+impl<'a> PartsView<'a> {
+    pub fn len(&self) -> usize {
+        self.parts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    /// Returns the coordinates of the `i`th part.
+    pub fn get(&self, i: usize) -> Option<CoordsView<'a>> {
+        if i >= self.len() {
+            return None;
+        }
+        let part = self.parts.start + i;
+        Some(self.data.coords(part..part + 1))
+    }
+
+    pub fn iter(&self) -> PartsIter<'a> {
+        PartsIter {
+            data: self.data,
+            parts: self.parts.clone(),
+        }
+    }
+
+    /// Builds a polygon using the first part as exterior and the rest as interiors.
+    pub fn to_polygon(&self) -> PolygonZ<f64> {
+        let mut rings = self.iter().map(|part| part.to_line_string());
+        let exterior = rings.next().unwrap_or_else(|| LineStringZ::new(Vec::new()));
+        PolygonZ::new(exterior, rings.collect())
+    }
+}
+
+impl<'a> IntoIterator for PartsView<'a> {
+    type Item = CoordsView<'a>;
+    type IntoIter = PartsIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Debug for PartsView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Iterator over the parts of a [`PartsView`].
+#[derive(Clone)]
+pub struct PartsIter<'a> {
+    data: Data<'a>,
+    parts: Range<usize>,
+}
+
+impl<'a> Iterator for PartsIter<'a> {
+    type Item = CoordsView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let part = self.parts.next()?;
+        Some(self.data.coords(part..part + 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.parts.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for PartsIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let part = self.parts.next_back()?;
+        Some(self.data.coords(part..part + 1))
+    }
+}
+
+impl ExactSizeIterator for PartsIter<'_> {}
+impl FusedIterator for PartsIter<'_> {}
+
+impl fmt::Debug for PartsIter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PartsIter")
+            .field("parts", &self.parts)
+            .finish()
+    }
+}
+
+/// Iterator over the groups of a [`FeatureView`].
+#[derive(Clone)]
+pub struct GroupsIter<'a> {
+    data: Data<'a>,
+    groups: Range<usize>,
+}
+
+impl<'a> Iterator for GroupsIter<'a> {
+    type Item = PartsView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let group = self.groups.next()?;
+        Some(PartsView {
+            data: self.data,
+            parts: self.data.group_parts(group..group + 1),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.groups.size_hint()
+    }
+}
+
+impl ExactSizeIterator for GroupsIter<'_> {}
+impl FusedIterator for GroupsIter<'_> {}
+
+impl fmt::Debug for GroupsIter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GroupsIter")
+            .field("groups", &self.groups)
+            .finish()
+    }
+}
+
+/// A zero-copy run of coordinates in the dataset's coordinate arena.
+#[derive(Copy, Clone)]
+pub struct CoordsView<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> CoordsView<'a> {
+    pub fn len(&self) -> usize {
+        self.bytes.len() / COORD_LEN
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Decodes the `i`th coordinate.
+    pub fn get(&self, i: usize) -> Option<CoordZ<f64>> {
+        let at = i.checked_mul(COORD_LEN)?;
+        self.bytes.get(at..at + COORD_LEN).map(decode_coord)
+    }
+
+    pub fn iter(&self) -> CoordsIter<'a> {
+        CoordsIter {
+            chunks: self.bytes.chunks_exact(COORD_LEN),
+        }
+    }
+
+    /// Copies the coordinates into an owned [`LineStringZ`].
+    pub fn to_line_string(&self) -> LineStringZ<f64> {
+        LineStringZ::new(self.iter().collect())
+    }
+
+    /// Min x, y, z followed by max x, y, z.
+    pub(crate) fn extent(&self) -> [f64; 6] {
+        self.iter().fold(EMPTY_EXTENT, |mut extent, c| {
+            extent[0] = extent[0].min(c.x);
+            extent[1] = extent[1].min(c.y);
+            extent[2] = extent[2].min(c.z);
+            extent[3] = extent[3].max(c.x);
+            extent[4] = extent[4].max(c.y);
+            extent[5] = extent[5].max(c.z);
+            extent
+        })
+    }
+}
+
+impl<'a> IntoIterator for CoordsView<'a> {
+    type Item = CoordZ<f64>;
+    type IntoIter = CoordsIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Debug for CoordsView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Iterator decoding the coordinates of a [`CoordsView`].
+#[derive(Clone, Debug)]
+pub struct CoordsIter<'a> {
+    chunks: ChunksExact<'a, u8>,
+}
+
+impl Iterator for CoordsIter<'_> {
+    type Item = CoordZ<f64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next().map(decode_coord)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for CoordsIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.chunks.next_back().map(decode_coord)
+    }
+}
+
+impl ExactSizeIterator for CoordsIter<'_> {}
+impl FusedIterator for CoordsIter<'_> {}
+
+fn decode_coord(bytes: &[u8]) -> CoordZ<f64> {
+    CoordZ {
+        x: read_f64(bytes, 0),
+        y: read_f64(bytes, 8),
+        z: read_f64(bytes, 16),
+    }
+}
+
+pub(crate) fn extent_to_cube(extent: [f64; 6]) -> Option<Cube<f64>> {
+    if !(0..3).all(|i| extent[i] <= extent[i + 3]) {
+        return None;
+    }
+    Some(Cube::new(
+        (extent[0], extent[1], extent[2]),
+        (extent[3], extent[4], extent[5]),
+    ))
+}
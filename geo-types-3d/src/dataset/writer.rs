@@ -0,0 +1,425 @@
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use super::{Dataset, DatasetBuilder};
+use crate::{CoordNum, Error, Geometry};
+
+const TAG_FEATURE: u8 = 1;
+const TAG_DELETE: u8 = 2;
+const RECORD_HEADER_LEN: usize = 5;
+
+/// Persists a dataset incrementally, for processes that collect geometries
+/// continuously.
+///
+/// The file starts with a regular dataset snapshot, which is what
+/// [`Dataset`] readers see. Every [`push_feature`](Self::push_feature) and
+/// [`delete`](Self::delete) is appended to a journal after the snapshot, so it is
+/// durable as soon as it is flushed. The journal is folded into a new snapshot,
+/// with a freshly built R-tree, by [`rebuild_index`](Self::rebuild_index),
+/// either explicitly or every [`with_rebuild_interval`](Self::with_rebuild_interval)
+/// records. Snapshots are written to a temporary file and renamed into place, so
+/// readers never observe a partially written snapshot.
+///
+/// Deleted features keep their id until [`compact`](Self::compact) drops them.
+///
+/// # Examples
+///
+/// ```no_run
+/// use geo_types_3d::dataset::DatasetWriter;
+/// use geo_types_3d::{Geometry, PointZ};
+///
+/// let mut writer = DatasetWriter::create("telemetry.g3d")?
+///     .with_rebuild_interval(10_000);
+/// for i in 0..100 {
+///     let i = i as f64;
+///     writer.push_feature(&Geometry::PointZ(PointZ::new(i, i, 100.0)))?;
+/// }
+/// writer.delete(3)?;
+/// writer.sync()?;
+/// # Ok::<(), geo_types_3d::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct DatasetWriter {
+    path: PathBuf,
+    journal: BufWriter<File>,
+    builder: DatasetBuilder,
+    pending: usize,
+    rebuild_interval: Option<usize>,
+    record: Vec<u8>,
+}
+
+impl DatasetWriter {
+    /// Creates an empty dataset at `path`, replacing any existing file.
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let builder = DatasetBuilder::new();
+        write_snapshot(&path, &builder)?;
+        Self::new(path, builder)
+    }
+
+    /// Opens the dataset at `path` and replays its journal.
+    ///
+    /// A record cut short by a crash at the end of the journal is discarded and
+    /// the file truncated to the last complete record.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let bytes = fs::read(&path)?;
+        let snapshot = Dataset::from_bytes(&bytes[..])?;
+        let mut builder = DatasetBuilder::from_dataset(&snapshot);
+        let snapshot_len = snapshot.container_len();
+
+        let mut pending = 0;
+        let mut journal = &bytes[snapshot_len..];
+        while journal.len() >= RECORD_HEADER_LEN {
+            let len = u32::from_le_bytes([journal[1], journal[2], journal[3], journal[4]]);
+            let Some(payload) = journal[RECORD_HEADER_LEN..].get(..len as usize) else {
+                break;
+            };
+            match journal[0] {
+                TAG_FEATURE => {
+                    builder.push_record(payload)?;
+                }
+                TAG_DELETE if payload.len() == 8 => {
+                    let mut id = [0; 8];
+                    id.copy_from_slice(payload);
+                    builder.remove(u64::from_le_bytes(id) as usize);
+                }
+                _ => {
+                    return Err(Error::InvalidDataset {
+                        reason: "malformed journal record",
+                    })
+                }
+            }
+            pending += 1;
+            journal = &journal[RECORD_HEADER_LEN + payload.len()..];
+        }
+        if !journal.is_empty() {
+            let valid_len = bytes.len() - journal.len();
+            OpenOptions::new()
+                .write(true)
+                .open(&path)?
+                .set_len(valid_len as u64)?;
+        }
+
+        let mut writer = Self::new(path, builder)?;
+        writer.pending = pending;
+        Ok(writer)
+    }
+
+    fn new(path: PathBuf, builder: DatasetBuilder) -> Result<Self, Error> {
+        Ok(Self {
+            journal: BufWriter::new(OpenOptions::new().append(true).open(&path)?),
+            path,
+            builder,
+            pending: 0,
+            rebuild_interval: None,
+            record: Vec::new(),
+        })
+    }
+
+    /// Rebuilds the snapshot and index automatically once `records` journal records
+    /// have accumulated.
+    pub fn with_rebuild_interval(mut self, records: usize) -> Self {
+        self.set_rebuild_interval(records);
+        self
+    }
+
+    /// Like [`with_rebuild_interval`](Self::with_rebuild_interval), for a writer
+    /// that is already in use.
+    pub fn set_rebuild_interval(&mut self, records: usize) {
+        self.rebuild_interval = Some(records);
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The number of live features, including those only in the journal.
+    pub fn len(&self) -> usize {
+        self.builder.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.builder.is_empty()
+    }
+
+    /// One past the largest feature id handed out so far.
+    pub fn id_bound(&self) -> usize {
+        self.builder.id_bound()
+    }
+
+    /// The number of journal records not yet folded into the snapshot.
+    pub fn pending_records(&self) -> usize {
+        self.pending
+    }
+
+    /// Appends a geometry and returns its id.
+    ///
+    /// Fails with [`Error::UnsupportedGeometry`] for geometries that
+    /// [`DatasetBuilder::push`] can't store. The feature is only added once its
+    /// journal record has been written, so a failed write leaves the writer as it was.
+    pub fn push_feature<T: CoordNum>(&mut self, geometry: &Geometry<T>) -> Result<usize, Error> {
+        self.record.clear();
+        DatasetBuilder::encode_record(geometry, &mut self.record)?;
+        self.append(TAG_FEATURE)?;
+        let id = self.builder.push_record(&self.record)?;
+        self.rebuild_if_due()?;
+        Ok(id)
+    }
+
+    /// Deletes the feature `id`. Returns `false` if there was no such live feature.
+    pub fn delete(&mut self, id: usize) -> Result<bool, Error> {
+        if !self.builder.contains(id) {
+            return Ok(false);
+        }
+        self.record.clear();
+        self.record.extend_from_slice(&(id as u64).to_le_bytes());
+        self.append(TAG_DELETE)?;
+        self.builder.remove(id);
+        self.rebuild_if_due()?;
+        Ok(true)
+    }
+
+    /// Flushes buffered journal records to the operating system.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        self.journal.flush()?;
+        Ok(())
+    }
+
+    /// Flushes buffered journal records and waits until they reach the disk.
+    pub fn sync(&mut self) -> Result<(), Error> {
+        self.journal.flush()?;
+        self.journal.get_ref().sync_all()?;
+        Ok(())
+    }
+
+    /// Writes a new snapshot with a fresh index, making all features visible to
+    /// readers, and clears the journal. Feature ids are preserved.
+    pub fn rebuild_index(&mut self) -> Result<(), Error> {
+        self.journal.flush()?;
+        write_snapshot(&self.path, &self.builder)?;
+        self.journal = BufWriter::new(OpenOptions::new().append(true).open(&self.path)?);
+        self.pending = 0;
+        Ok(())
+    }
+
+    /// Drops deleted features and rebuilds the snapshot.
+    ///
+    /// Remaining features are renumbered; the returned vector maps each old id to
+    /// its new id, or `None` for deleted features.
+    pub fn compact(&mut self) -> Result<Vec<Option<usize>>, Error> {
+        let ids = self.builder.compact();
+        self.rebuild_index()?;
+        Ok(ids)
+    }
+
+    /// Writes `self.record` to the journal, leaving the builder untouched.
+    fn append(&mut self, tag: u8) -> Result<(), Error> {
+        let len = u32::try_from(self.record.len()).map_err(|_| Error::InvalidDataset {
+            reason: "feature record too large for the journal",
+        })?;
+        self.journal.write_all(&[tag])?;
+        self.journal.write_all(&len.to_le_bytes())?;
+        self.journal.write_all(&self.record)?;
+        self.pending += 1;
+        Ok(())
+    }
+
+    fn rebuild_if_due(&mut self) -> Result<(), Error> {
+        if self
+            .rebuild_interval
+            .is_some_and(|interval| self.pending >= interval)
+        {
+            self.rebuild_index()?;
+        }
+        Ok(())
+    }
+}
+
+fn write_snapshot(path: &Path, builder: &DatasetBuilder) -> Result<(), Error> {
+    let mut tmp = OsString::from(path.as_os_str());
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    let mut file = File::create(&tmp)?;
+    builder.write_to(&mut file)?;
+    file.sync_all()?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{MultiLineStringZ, PointZ};
+
+    struct TempPath(PathBuf);
+
+    impl TempPath {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir()
+                .join(format!("geo-types-3d-{}-{name}.g3d", std::process::id()));
+            let _ = fs::remove_file(&path);
+            Self(path)
+        }
+    }
+
+    impl Drop for TempPath {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    fn point(i: usize) -> Geometry<f64> {
+        let i = i as f64;
+        Geometry::PointZ(PointZ::new(i, i, i))
+    }
+
+    fn snapshot(path: &Path) -> Dataset {
+        let bytes = fs::read(path).unwrap();
+        let len = Dataset::from_bytes(&bytes[..]).unwrap().container_len();
+        Dataset::from_bytes(bytes[..len].to_vec()).unwrap()
+    }
+
+    #[test]
+    fn journal_is_replayed() {
+        let path = TempPath::new("journal");
+        let mut writer = DatasetWriter::create(&path.0).unwrap();
+        for i in 0..3 {
+            assert_eq!(writer.push_feature(&point(i)).unwrap(), i);
+        }
+        let line = Geometry::LineStringZ(line_string_z![
+            (x: 0., y: 0., z: 0.),
+            (x: 1., y: 2., z: 3.),
+        ]);
+        writer.push_feature(&line).unwrap();
+        assert!(writer.delete(1).unwrap());
+        assert!(!writer.delete(1).unwrap());
+        drop(writer);
+
+        // readers only see the snapshot until the index is rebuilt
+        assert!(snapshot(&path.0).is_empty());
+
+        let mut writer = DatasetWriter::open(&path.0).unwrap();
+        assert_eq!(writer.len(), 3);
+        assert_eq!(writer.pending_records(), 5);
+        writer.rebuild_index().unwrap();
+        assert_eq!(writer.pending_records(), 0);
+
+        let dataset = snapshot(&path.0);
+        assert_eq!(dataset.len(), 3);
+        assert!(dataset.get(1).is_none());
+        assert_eq!(dataset.get(2).unwrap().to_geometry().unwrap(), point(2));
+        assert_eq!(dataset.get(3).unwrap().to_geometry().unwrap(), line);
+    }
+
+    #[test]
+    fn rebuild_interval() {
+        let path = TempPath::new("interval");
+        let mut writer = DatasetWriter::create(&path.0)
+            .unwrap()
+            .with_rebuild_interval(4);
+        for i in 0..6 {
+            writer.push_feature(&point(i)).unwrap();
+        }
+        assert_eq!(writer.pending_records(), 2);
+        writer.flush().unwrap();
+        assert_eq!(snapshot(&path.0).len(), 4);
+
+        let writer = DatasetWriter::open(&path.0).unwrap();
+        assert_eq!(writer.len(), 6);
+    }
+
+    #[test]
+    fn compaction_renumbers() {
+        let path = TempPath::new("compact");
+        let mut writer = DatasetWriter::create(&path.0).unwrap();
+        for i in 0..4 {
+            writer.push_feature(&point(i)).unwrap();
+        }
+        writer.delete(0).unwrap();
+        writer.delete(2).unwrap();
+        let ids = writer.compact().unwrap();
+        assert_eq!(ids, vec![None, Some(0), None, Some(1)]);
+        assert_eq!(writer.id_bound(), 2);
+
+        let dataset = snapshot(&path.0);
+        assert_eq!(dataset.id_bound(), 2);
+        let decoded: Vec<_> = dataset
+            .iter()
+            .map(|feature| feature.to_geometry().unwrap())
+            .collect();
+        assert_eq!(decoded, vec![point(1), point(3)]);
+    }
+
+    #[test]
+    fn torn_record_is_discarded() {
+        let path = TempPath::new("torn");
+        let mut writer = DatasetWriter::create(&path.0).unwrap();
+        writer.push_feature(&point(0)).unwrap();
+        writer.push_feature(&point(1)).unwrap();
+        drop(writer);
+
+        let complete_len = fs::metadata(&path.0).unwrap().len();
+        let mut file = OpenOptions::new().append(true).open(&path.0).unwrap();
+        file.write_all(&[TAG_FEATURE, 200, 0, 0, 0, 1, 2, 3])
+            .unwrap();
+        drop(file);
+
+        let mut writer = DatasetWriter::open(&path.0).unwrap();
+        assert_eq!(writer.len(), 2);
+        assert_eq!(fs::metadata(&path.0).unwrap().len(), complete_len);
+        writer.push_feature(&point(2)).unwrap();
+        drop(writer);
+        assert_eq!(DatasetWriter::open(&path.0).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn malformed_record_is_rejected() {
+        let mut builder = DatasetBuilder::new();
+        builder.push(&point(0)).unwrap();
+        let line = line_string_z![(x: 0., y: 0., z: 0.), (x: 1., y: 2., z: 3.)];
+        let lines = Geometry::MultiLineStringZ(MultiLineStringZ::new(vec![line.clone(), line]));
+        let mut record = Vec::new();
+        DatasetBuilder::encode_record(&lines, &mut record).unwrap();
+
+        // the first part decodes, the second is one coordinate short
+        let truncated = &record[..record.len() - 24];
+        assert!(matches!(
+            builder.push_record(truncated),
+            Err(Error::InvalidDataset { .. })
+        ));
+        assert_eq!(builder.push_record(&record).unwrap(), 1);
+
+        let dataset = builder.finish();
+        assert_eq!(dataset.len(), 2);
+        assert_eq!(dataset.get(1).unwrap().to_geometry().unwrap(), lines);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn failed_append_leaves_builder_unchanged() {
+        let path = TempPath::new("failed");
+        let mut writer = DatasetWriter::create(&path.0).unwrap();
+        writer.push_feature(&point(0)).unwrap();
+        writer.flush().unwrap();
+
+        // writes to /dev/full fail with ENOSPC; an unbuffered writer surfaces that
+        // from `append` itself
+        let full = OpenOptions::new().write(true).open("/dev/full").unwrap();
+        let journal = std::mem::replace(&mut writer.journal, BufWriter::with_capacity(0, full));
+        assert!(writer.push_feature(&point(1)).is_err());
+        assert!(writer.delete(0).is_err());
+        assert_eq!(writer.len(), 1);
+        assert_eq!(writer.id_bound(), 1);
+        assert_eq!(writer.pending_records(), 1);
+
+        writer.journal = journal;
+        assert_eq!(writer.push_feature(&point(1)).unwrap(), 1);
+        drop(writer);
+        let writer = DatasetWriter::open(&path.0).unwrap();
+        assert_eq!(writer.len(), 2);
+        assert_eq!(writer.id_bound(), 2);
+    }
+}
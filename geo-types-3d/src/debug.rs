@@ -15,6 +15,60 @@ impl<T: CoordNum> Debug for PointZ<T> {
     }
 }
 
+impl<T: CoordNum> Debug for CoordZM<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "COORD ZM")?;
+        write_coord_zm_seq(f, [*self].iter())
+    }
+}
+
+impl<T: CoordNum> Debug for PointZM<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "POINT ZM")?;
+        write_coord_zm_seq(f, [self.0].iter())
+    }
+}
+
+impl<T: CoordNum> Debug for LineStringZM<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "LINESTRING ZM")?;
+        if self.0.is_empty() {
+            write!(f, " ")?;
+        }
+        write_coord_zm_seq(f, self.0.iter())
+    }
+}
+
+impl<T: CoordNum> Debug for CircularStringZ<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "CIRCULARSTRING Z")?;
+        if self.0.is_empty() {
+            write!(f, " ")?;
+        }
+        write_coord_seq(f, self.0.iter())
+    }
+}
+
+impl<T: CoordNum> Debug for CompoundCurveZ<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "COMPOUNDCURVE Z")?;
+        let mut separator = "(";
+        for segment in &self.0 {
+            write!(f, "{separator}")?;
+            match segment {
+                CurveSegmentZ::LineString(line_string) => write_coord_seq(f, line_string.0.iter())?,
+                CurveSegmentZ::CircularString(circular_string) => write!(f, "{circular_string:?}")?,
+            }
+            separator = ",";
+        }
+        if separator == "(" {
+            write!(f, " EMPTY")
+        } else {
+            write!(f, ")")
+        }
+    }
+}
+
 impl<T: CoordNum> Debug for LineZ<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "LINE Z")?;
@@ -86,12 +140,12 @@ impl<T: CoordNum> Debug for MultiPolygonZ<T> {
     }
 }
 
-// impl<T: CoordNum> Debug for Cube<T> {
-//     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-//         write!(f, "RECT")?;
-//         write_coord_seq(f, [self.min(), self.max()].iter())
-//     }
-// }
+impl<T: CoordNum> Debug for Cube<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "CUBE")?;
+        write_coord_seq(f, [self.min(), self.max()].iter())
+    }
+}
 
 impl<T: CoordNum> Debug for Triangle<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
@@ -100,6 +154,23 @@ impl<T: CoordNum> Debug for Triangle<T> {
     }
 }
 
+impl<T: CoordNum> Debug for TinZ<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "TIN Z")?;
+        let mut triangles = self.triangles();
+        let Some(first) = triangles.next() else {
+            return write!(f, " EMPTY");
+        };
+        write!(f, "(")?;
+        write_triangle_ring(f, first)?;
+        for triangle in triangles {
+            write!(f, ",")?;
+            write_triangle_ring(f, triangle)?;
+        }
+        write!(f, ")")
+    }
+}
+
 impl<T: CoordNum> Debug for Geometry<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
@@ -153,6 +224,31 @@ fn write_coord_seq<'a, T: CoordNum + 'a>(
     write!(f, ")")
 }
 
+fn write_coord_zm_seq<'a, T: CoordNum + 'a>(
+    f: &mut Formatter<'_>,
+    coords: impl Iterator<Item = &'a CoordZM<T>>,
+) -> core::fmt::Result {
+    let mut separator = "(";
+    for c in coords {
+        write!(f, "{separator}{:?} {:?} {:?} {:?}", c.x, c.y, c.z, c.m)?;
+        separator = ",";
+    }
+    if separator == "(" {
+        write!(f, "EMPTY")
+    } else {
+        write!(f, ")")
+    }
+}
+
+fn write_triangle_ring<T: CoordNum>(
+    f: &mut Formatter<'_>,
+    triangle: Triangle<T>,
+) -> core::fmt::Result {
+    write!(f, "(")?;
+    write_coord_seq(f, [triangle.0, triangle.1, triangle.2, triangle.0].iter())?;
+    write!(f, ")")
+}
+
 fn write_polygon_inner<T: CoordNum>(
     f: &mut Formatter<'_>,
     polygon: &PolygonZ<T>,
@@ -386,15 +482,42 @@ mod tests {
             format!("{multi_polygon:?}")
         );
     }
-    // #[test]
-    // fn rect() {
-    //     let rect = Cube::new((1, 2), (3, 4));
-    //     assert_eq!("RECT(1 2,3 4)", format!("{rect:?}"));
+    #[test]
+    fn cube() {
+        let cube = Cube::new((1, 2, 3), (4, 5, 6));
+        assert_eq!("CUBE(1 2 3,4 5 6)", format!("{cube:?}"));
 
-    //     let rect = Cube::new((3, 4), (1, 2));
-    //     // output is always (min, max)
-    //     assert_eq!("RECT(1 2,3 4)", format!("{rect:?}"));
-    // }
+        let cube = Cube::new((4, 5, 6), (1, 2, 3));
+        // output is always (min, max)
+        assert_eq!("CUBE(1 2 3,4 5 6)", format!("{cube:?}"));
+    }
+    #[test]
+    fn curves() {
+        let circular_string: CircularStringZ<i32> = CircularStringZ::empty();
+        assert_eq!("CIRCULARSTRING Z EMPTY", format!("{circular_string:?}"));
+        let curve: CompoundCurveZ<i32> = CompoundCurveZ::empty();
+        assert_eq!("COMPOUNDCURVE Z EMPTY", format!("{curve:?}"));
+
+        let curve = wkt! { COMPOUNDCURVE Z((0 0 0,2 0 0),CIRCULARSTRING Z(2 0 0,3 1 1,4 0 2)) };
+        assert_eq!(
+            "COMPOUNDCURVE Z((0 0 0,2 0 0),CIRCULARSTRING Z(2 0 0,3 1 1,4 0 2))",
+            format!("{curve:?}")
+        );
+    }
+    #[test]
+    fn tin() {
+        let tin: TinZ<i32> = TinZ::empty();
+        assert_eq!("TIN Z EMPTY", format!("{tin:?}"));
+
+        let tin = TinZ::new(
+            vec![(0, 0, 0).into(), (1, 0, 1).into(), (0, 1, 2).into(), (1, 1, 3).into()],
+            vec![[0, 1, 2], [1, 3, 2]],
+        );
+        assert_eq!(
+            "TIN Z(((0 0 0,1 0 1,0 1 2,0 0 0)),((1 0 1,1 1 3,0 1 2,1 0 1)))",
+            format!("{tin:?}")
+        );
+    }
     // #[test]
     // fn triangle() {
     //     let rect = Triangle::new((1, 2, 3).into(), (4, 5, 6).into(), (7, 8, 9).into());
@@ -1,15 +1,101 @@
 use core::fmt;
 
+use crate::Dimension;
+
 #[derive(Debug)]
 pub enum Error {
     MismatchedGeometry {
         expected: &'static str,
         found: &'static str,
     },
+    UnsupportedGeometry {
+        found: &'static str,
+    },
+    InvalidDataset {
+        reason: &'static str,
+    },
+    NotCoplanar,
+    MismatchedLength {
+        expected: usize,
+        found: usize,
+    },
+    InvalidOffsets,
+    CapacityExceeded {
+        capacity: usize,
+    },
+    LimitExceeded {
+        limit: &'static str,
+        max: usize,
+    },
+    InvalidPolyline {
+        reason: &'static str,
+    },
+    InvalidCompressed {
+        reason: &'static str,
+    },
+    InvalidCell {
+        reason: &'static str,
+    },
+    InvalidWkb {
+        reason: &'static str,
+    },
+    InvalidRing {
+        index: usize,
+        reason: &'static str,
+    },
+    InvalidTrajectory {
+        reason: &'static str,
+    },
+    MismatchedDimension {
+        index: usize,
+        expected: Dimension,
+        found: Dimension,
+    },
+    CastOverflow,
+    InvalidArgument {
+        reason: &'static str,
+    },
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    #[cfg(feature = "std")]
+    GeoJson(alloc::boxed::Box<geojson::Error>),
+    #[cfg(feature = "http")]
+    Http(alloc::boxed::Box<ureq::Error>),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::GeoJson(err) => Some(err),
+            #[cfg(feature = "http")]
+            Error::Http(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for Error {}
+impl From<geojson::Error> for Error {
+    fn from(err: geojson::Error) -> Self {
+        Error::GeoJson(err.into())
+    }
+}
+
+#[cfg(feature = "http")]
+impl From<ureq::Error> for Error {
+    fn from(err: ureq::Error) -> Self {
+        Error::Http(err.into())
+    }
+}
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -17,6 +103,51 @@ impl fmt::Display for Error {
             Error::MismatchedGeometry { expected, found } => {
                 write!(f, "Expected a {expected}, but found a {found}")
             }
+            Error::UnsupportedGeometry { found } => {
+                write!(f, "Geometries of type {found} are not supported here")
+            }
+            Error::InvalidDataset { reason } => {
+                write!(f, "Invalid dataset: {reason}")
+            }
+            Error::NotCoplanar => write!(f, "Geometries do not lie in a common plane"),
+            Error::MismatchedLength { expected, found } => {
+                write!(f, "Expected {expected} values, but found {found}")
+            }
+            Error::InvalidOffsets => write!(f, "Offsets do not describe slices of the buffer"),
+            Error::CapacityExceeded { capacity } => {
+                write!(f, "Capacity of {capacity} coordinates exceeded")
+            }
+            Error::InvalidPolyline { reason } => {
+                write!(f, "Invalid encoded polyline: {reason}")
+            }
+            Error::InvalidCompressed { reason } => {
+                write!(f, "Invalid compressed line string: {reason}")
+            }
+            Error::InvalidCell { reason } => write!(f, "Invalid grid cell: {reason}"),
+            Error::InvalidWkb { reason } => write!(f, "Invalid well-known binary: {reason}"),
+            Error::InvalidRing { index, reason } => write!(f, "Invalid ring {index}: {reason}"),
+            Error::InvalidTrajectory { reason } => write!(f, "Invalid trajectory: {reason}"),
+            Error::MismatchedDimension {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Expected {expected} coordinates, but member {index} has {found} coordinates"
+            ),
+            Error::LimitExceeded { limit, max } => {
+                write!(f, "Input exceeds the limit of {max} {limit}")
+            }
+            Error::CastOverflow => {
+                write!(f, "Coordinate out of the range of the target scalar type")
+            }
+            Error::InvalidArgument { reason } => write!(f, "Invalid argument: {reason}"),
+            #[cfg(feature = "std")]
+            Error::Io(err) => write!(f, "I/O error: {err}"),
+            #[cfg(feature = "std")]
+            Error::GeoJson(err) => write!(f, "GeoJSON error: {err}"),
+            #[cfg(feature = "http")]
+            Error::Http(err) => write!(f, "HTTP error: {err}"),
         }
     }
 }
@@ -0,0 +1,207 @@
+//! [rkyv] remote derives for the planar `geo_types` members of
+//! [`Geometry`](super::Geometry), which don't implement `Archive` themselves.
+//! Each type here stands in for the `geo_types` type it's named after, through
+//! `#[rkyv(with = ...)]`.
+
+use alloc::vec::Vec;
+
+use geo_types::{Coord, Line, LineString, MultiLineString, MultiPoint, MultiPolygon, Point};
+use geo_types::{Polygon, Rect};
+use rkyv::with::Map;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::CoordNum;
+
+#[derive(Archive, Serialize, Deserialize)]
+#[rkyv(remote = Coord<T>)]
+pub(crate) struct CoordDef<T: CoordNum> {
+    x: T,
+    y: T,
+}
+
+impl<T: CoordNum> From<CoordDef<T>> for Coord<T> {
+    fn from(CoordDef { x, y }: CoordDef<T>) -> Self {
+        Coord { x, y }
+    }
+}
+
+#[derive(Archive, Serialize, Deserialize)]
+#[rkyv(remote = Point<T>)]
+pub(crate) struct PointDef<T: CoordNum>(#[rkyv(with = CoordDef<T>)] Coord<T>);
+
+impl<T: CoordNum> From<PointDef<T>> for Point<T> {
+    fn from(PointDef(coord): PointDef<T>) -> Self {
+        Point(coord)
+    }
+}
+
+#[derive(Archive, Serialize, Deserialize)]
+#[rkyv(remote = Line<T>)]
+pub(crate) struct LineDef<T: CoordNum> {
+    #[rkyv(with = CoordDef<T>)]
+    start: Coord<T>,
+    #[rkyv(with = CoordDef<T>)]
+    end: Coord<T>,
+}
+
+impl<T: CoordNum> From<LineDef<T>> for Line<T> {
+    fn from(LineDef { start, end }: LineDef<T>) -> Self {
+        Line { start, end }
+    }
+}
+
+#[derive(Archive, Serialize, Deserialize)]
+#[rkyv(remote = LineString<T>)]
+pub(crate) struct LineStringDef<T: CoordNum>(#[rkyv(with = Map<CoordDef<T>>)] Vec<Coord<T>>);
+
+impl<T: CoordNum> From<LineStringDef<T>> for LineString<T> {
+    fn from(LineStringDef(coords): LineStringDef<T>) -> Self {
+        LineString(coords)
+    }
+}
+
+#[derive(Archive, Serialize, Deserialize)]
+#[rkyv(remote = Polygon<T>)]
+pub(crate) struct PolygonDef<T: CoordNum> {
+    #[rkyv(getter = Polygon::exterior, with = LineStringDef<T>)]
+    exterior: LineString<T>,
+    #[rkyv(getter = interiors, with = Map<LineStringDef<T>>)]
+    interiors: Vec<LineString<T>>,
+}
+
+// The getter has to give a `Vec`, where `Polygon` only lends a slice.
+fn interiors<T: CoordNum>(polygon: &Polygon<T>) -> Vec<LineString<T>> {
+    polygon.interiors().to_vec()
+}
+
+impl<T: CoordNum> From<PolygonDef<T>> for Polygon<T> {
+    fn from(
+        PolygonDef {
+            exterior,
+            interiors,
+        }: PolygonDef<T>,
+    ) -> Self {
+        Polygon::new(exterior, interiors)
+    }
+}
+
+#[derive(Archive, Serialize, Deserialize)]
+#[rkyv(remote = MultiPoint<T>)]
+pub(crate) struct MultiPointDef<T: CoordNum>(#[rkyv(with = Map<PointDef<T>>)] Vec<Point<T>>);
+
+impl<T: CoordNum> From<MultiPointDef<T>> for MultiPoint<T> {
+    fn from(MultiPointDef(points): MultiPointDef<T>) -> Self {
+        MultiPoint(points)
+    }
+}
+
+#[derive(Archive, Serialize, Deserialize)]
+#[rkyv(remote = MultiLineString<T>)]
+pub(crate) struct MultiLineStringDef<T: CoordNum>(
+    #[rkyv(with = Map<LineStringDef<T>>)] Vec<LineString<T>>,
+);
+
+impl<T: CoordNum> From<MultiLineStringDef<T>> for MultiLineString<T> {
+    fn from(MultiLineStringDef(line_strings): MultiLineStringDef<T>) -> Self {
+        MultiLineString(line_strings)
+    }
+}
+
+#[derive(Archive, Serialize, Deserialize)]
+#[rkyv(remote = MultiPolygon<T>)]
+pub(crate) struct MultiPolygonDef<T: CoordNum>(#[rkyv(with = Map<PolygonDef<T>>)] Vec<Polygon<T>>);
+
+impl<T: CoordNum> From<MultiPolygonDef<T>> for MultiPolygon<T> {
+    fn from(MultiPolygonDef(polygons): MultiPolygonDef<T>) -> Self {
+        MultiPolygon(polygons)
+    }
+}
+
+#[derive(Archive, Serialize, Deserialize)]
+#[rkyv(remote = Rect<T>)]
+pub(crate) struct RectDef<T: CoordNum> {
+    #[rkyv(getter = rect_min, with = CoordDef<T>)]
+    min: Coord<T>,
+    #[rkyv(getter = rect_max, with = CoordDef<T>)]
+    max: Coord<T>,
+}
+
+fn rect_min<T: CoordNum>(rect: &Rect<T>) -> Coord<T> {
+    rect.min()
+}
+
+fn rect_max<T: CoordNum>(rect: &Rect<T>) -> Coord<T> {
+    rect.max()
+}
+
+impl<T: CoordNum> From<RectDef<T>> for Rect<T> {
+    fn from(RectDef { min, max }: RectDef<T>) -> Self {
+        Rect::new(min, max)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{wkt, Geometry, GeometryCollection, PointZ, Tagged, TinZ};
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use rkyv::rancor::Error;
+
+    #[test]
+    fn round_trip() {
+        let planar = geo_types::wkt! {
+            GEOMETRYCOLLECTION(
+                POINT(1. 2.),
+                LINESTRING(0. 0.,1. 1.),
+                POLYGON((0. 0.,4. 0.,4. 4.,0. 0.),(1. 1.,2. 1.,2. 2.,1. 1.)),
+                MULTIPOINT(5. 5.,6. 6.),
+                MULTILINESTRING((0. 0.,1. 1.)),
+                MULTIPOLYGON(((0. 0.,4. 0.,4. 4.,0. 0.)))
+            )
+        };
+        let mut geometries: Vec<Geometry> = planar
+            .into_iter()
+            .map(|geometry| match geometry {
+                geo_types::Geometry::Point(g) => Geometry::Point(g),
+                geo_types::Geometry::LineString(g) => Geometry::LineString(g),
+                geo_types::Geometry::Polygon(g) => Geometry::Polygon(g),
+                geo_types::Geometry::MultiPoint(g) => Geometry::MultiPoint(g),
+                geo_types::Geometry::MultiLineString(g) => Geometry::MultiLineString(g),
+                geo_types::Geometry::MultiPolygon(g) => Geometry::MultiPolygon(g),
+                _ => unreachable!(),
+            })
+            .collect();
+        geometries.push(Geometry::Rect(geo_types::Rect::new((0., 1.), (2., 3.))));
+        geometries.push(Geometry::Line(geo_types::Line::new((0., 1.), (2., 3.))));
+        geometries.push(Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::from(wkt! { POINT Z(1. 2. 3.) }),
+            Geometry::from(wkt! { MULTIPOLYGON Z(((0. 0. 1.,4. 0. 1.,4. 4. 1.,0. 0. 1.))) }),
+        ])));
+        let geometry = Geometry::GeometryCollection(GeometryCollection(geometries));
+
+        let bytes = rkyv::to_bytes::<Error>(&geometry).unwrap();
+        let archived = rkyv::access::<rkyv::Archived<Geometry>, Error>(&bytes).unwrap();
+        let deserialized: Geometry = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(deserialized, geometry);
+        assert!(rkyv::access::<rkyv::Archived<Geometry>, Error>(&bytes[1..]).is_err());
+
+        let tagged = Tagged::new(PointZ::new(1f32, 2., 3.), 7u32);
+        let bytes = rkyv::to_bytes::<Error>(&tagged).unwrap();
+        let archived =
+            rkyv::access::<rkyv::Archived<Tagged<PointZ<f32>, u32>>, Error>(&bytes).unwrap();
+        assert_eq!(archived.geometry.0.z, 3.);
+        assert_eq!(archived.data, 7);
+
+        let tin = TinZ::new(
+            vec![
+                (0., 0., 0.).into(),
+                (1., 0., 0.).into(),
+                (0., 1., 1.).into(),
+            ],
+            vec![[0, 1, 2]],
+        );
+        let bytes = rkyv::to_bytes::<Error>(&tin).unwrap();
+        let deserialized: TinZ = rkyv::from_bytes::<_, Error>(&bytes).unwrap();
+        assert_eq!(deserialized.vertices(), tin.vertices());
+    }
+}
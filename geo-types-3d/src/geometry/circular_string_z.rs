@@ -0,0 +1,265 @@
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+use crate::algorithm::linalg::{cross, dot, norm, normalize};
+use crate::{CoordFloat, CoordNum, CoordZ, LineStringZ};
+
+/// A curve made of circular arcs, as used by CAD and engineering formats.
+///
+/// Each arc runs from one coordinate through the next to the one after, and
+/// consecutive arcs share an end point, so a non-empty circular string has an odd
+/// number of coordinates, at least three. An arc through three points lies in
+/// their plane, so arcs may be tilted and z follows the arc rather than being
+/// interpolated. Three collinear points make a straight segment.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::wkt;
+///
+/// // a half circle of radius 1, standing upright in the xz plane
+/// let arch = wkt! { CIRCULARSTRING Z(-1. 0. 0.,0. 0. 1.,1. 0. 0.) };
+/// assert!((arch.length() - std::f64::consts::PI).abs() < 1e-12);
+///
+/// let line_string = arch.to_line_string(0.01);
+/// assert_eq!(line_string.0.first(), arch.0.first());
+/// assert_eq!(line_string.0.last(), arch.0.last());
+/// assert!(line_string.0.iter().all(|c| (c.x.hypot(c.z) - 1.).abs() < 1e-12));
+/// ```
+#[derive(Eq, PartialEq, Clone, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct CircularStringZ<T: CoordNum = f64>(pub Vec<CoordZ<T>>);
+
+impl<T: CoordNum> CircularStringZ<T> {
+    pub fn new(coords: Vec<CoordZ<T>>) -> Self {
+        Self(coords)
+    }
+
+    pub fn empty() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Whether this is empty, or has an odd number of coordinates, at least three.
+    pub fn is_valid(&self) -> bool {
+        self.0.is_empty() || (self.0.len() >= 3 && self.0.len() % 2 == 1)
+    }
+
+    /// The start, middle and end points of each arc. A trailing coordinate that
+    /// doesn't complete an arc is ignored.
+    pub fn arcs(&self) -> impl ExactSizeIterator<Item = [CoordZ<T>; 3]> + '_ {
+        let arcs = self.0.len().saturating_sub(1) / 2;
+        (0..arcs).map(move |i| [self.0[2 * i], self.0[2 * i + 1], self.0[2 * i + 2]])
+    }
+
+    /// Whether the first and last coordinates are equal. Empty circular strings
+    /// are closed.
+    pub fn is_closed(&self) -> bool {
+        self.0.first() == self.0.last()
+    }
+}
+
+impl<T: CoordFloat> CircularStringZ<T> {
+    /// The total length of the arcs.
+    pub fn length(&self) -> T {
+        self.arcs()
+            .map(|arc| Arc::new(arc).length())
+            .fold(T::zero(), |total, length| total + length)
+    }
+
+    /// Approximates the arcs by chords that stray at most `tolerance` from them.
+    ///
+    /// # Panics
+    ///
+    /// If `tolerance` isn't positive.
+    pub fn to_line_string(&self, tolerance: T) -> LineStringZ<T> {
+        assert!(tolerance > T::zero(), "tolerance must be positive");
+        let mut coords = Vec::new();
+        for arc in self.arcs() {
+            coords.pop();
+            Arc::new(arc).linearize(tolerance, &mut coords);
+        }
+        LineStringZ(coords)
+    }
+}
+
+/// One arc of a circular string, resolved into its circle.
+enum Arc<T: CoordFloat> {
+    /// The three points are collinear, or coincide.
+    Straight([CoordZ<T>; 3]),
+    Circle {
+        start: CoordZ<T>,
+        end: CoordZ<T>,
+        centre: CoordZ<T>,
+        radius: T,
+        /// Unit vectors spanning the plane of the arc, the first pointing to the
+        /// start, the second a quarter turn further in the direction of the arc.
+        axes: [CoordZ<T>; 2],
+        sweep: T,
+    },
+}
+
+impl<T: CoordFloat> Arc<T> {
+    fn new([a, b, c]: [CoordZ<T>; 3]) -> Self {
+        let two = T::one() + T::one();
+        if a == c && a != b {
+            return Self::full_circle(a, b);
+        }
+        let (u, v) = (b - a, c - a);
+        let normal = cross(u, v);
+        let normal_2 = dot(normal, normal);
+        let scale = dot(u, u).max(dot(v, v));
+        if normal_2 <= scale * scale * T::epsilon() {
+            return Arc::Straight([a, b, c]);
+        }
+        let centre =
+            a + (cross(normal, u) * dot(v, v) + cross(v, normal) * dot(u, u)) / (two * normal_2);
+        let radius = norm(a - centre);
+        let x_axis = (a - centre) / radius;
+        let y_axis = cross(normal, x_axis) / normal_2.sqrt();
+        let mut sweep = dot(c - centre, y_axis).atan2(dot(c - centre, x_axis));
+        if sweep < T::zero() {
+            sweep = sweep + two * pi();
+        }
+        Arc::Circle {
+            start: a,
+            end: c,
+            centre,
+            radius,
+            axes: [x_axis, y_axis],
+            sweep,
+        }
+    }
+
+    /// A closed arc is a full circle, with the middle point opposite the ends. The
+    /// three points don't fix its plane, so the circle is taken to be as level as
+    /// possible.
+    fn full_circle(a: CoordZ<T>, b: CoordZ<T>) -> Self {
+        let two = T::one() + T::one();
+        let centre = a.midpoint(b);
+        let radius = norm(a - centre);
+        let x_axis = (a - centre) / radius;
+        let up = CoordZ {
+            x: T::zero(),
+            y: T::zero(),
+            z: T::one(),
+        };
+        let mut y_axis = cross(up, x_axis);
+        if norm(y_axis) <= T::epsilon() {
+            // a vertical diameter: stand the circle in the xz plane
+            y_axis = CoordZ {
+                x: T::one(),
+                y: T::zero(),
+                z: T::zero(),
+            };
+        }
+        Arc::Circle {
+            start: a,
+            end: a,
+            centre,
+            radius,
+            axes: [x_axis, normalize(y_axis)],
+            sweep: two * pi(),
+        }
+    }
+
+    fn length(&self) -> T {
+        match *self {
+            Arc::Straight([a, b, c]) => norm(b - a) + norm(c - b),
+            Arc::Circle { radius, sweep, .. } => radius * sweep,
+        }
+    }
+
+    /// Appends the vertices of the linearized arc to `coords`, both ends included.
+    fn linearize(&self, tolerance: T, coords: &mut Vec<CoordZ<T>>) {
+        match *self {
+            Arc::Straight([a, b, c]) => {
+                coords.push(a);
+                // only keep the middle point where it doesn't lie between the others
+                if dot(b - a, c - b) < T::zero() {
+                    coords.push(b);
+                }
+                coords.push(c);
+            }
+            Arc::Circle {
+                start,
+                end,
+                centre,
+                radius,
+                axes: [x_axis, y_axis],
+                sweep,
+            } => {
+                let two = T::one() + T::one();
+                // the largest angle whose chord stays within tolerance of the arc
+                let step = if tolerance < radius {
+                    two * (T::one() - tolerance / radius).acos()
+                } else {
+                    pi()
+                };
+                let segments = (sweep / step).ceil().max(T::one());
+                let count = segments.to_usize().unwrap_or(1);
+                coords.push(start);
+                for i in 1..count {
+                    let angle = sweep * T::from(i).unwrap() / segments;
+                    coords.push(centre + (x_axis * angle.cos() + y_axis * angle.sin()) * radius);
+                }
+                coords.push(end);
+            }
+        }
+    }
+}
+
+fn pi<T: CoordFloat>() -> T {
+    T::from(PI).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::coordZ;
+    use alloc::vec;
+
+    #[test]
+    fn arcs() {
+        // a tilted quarter circle of radius 2 around (1, 1, 1), then a straight run
+        let s = 2f64.sqrt();
+        let curve = CircularStringZ(vec![
+            coordZ! { x: 3., y: 1., z: 1. },
+            coordZ! { x: 1. + s, y: 2., z: 2. },
+            coordZ! { x: 1., y: 1. + s, z: 1. + s },
+            coordZ! { x: 1., y: 2. + s, z: 2. + s },
+            coordZ! { x: 1., y: 3. + s, z: 3. + s },
+        ]);
+        assert!(curve.is_valid());
+        assert_eq!(curve.arcs().len(), 2);
+        assert_relative_eq!(curve.length(), PI + 2. * s, epsilon = 1e-12);
+
+        let tolerance = 1e-3;
+        let line_string = curve.to_line_string(tolerance);
+        assert_eq!(line_string.0.first(), curve.0.first());
+        assert_eq!(line_string.0.last(), curve.0.last());
+        let centre = coordZ! { x: 1., y: 1., z: 1. };
+        let (arc, straight) = line_string.0.split_at(line_string.0.len() - 1);
+        for pair in arc.windows(2) {
+            assert_relative_eq!(norm(pair[0] - centre), 2., epsilon = 1e-12);
+            let midpoint = (pair[0] + pair[1]) / 2.;
+            assert!(2. - norm(midpoint - centre) <= tolerance);
+        }
+        assert_eq!(straight.len(), 1);
+        assert!(curve.to_line_string(1e-6).0.len() > line_string.0.len());
+
+        // the middle point of a full circle fixes its size
+        let circle = CircularStringZ(vec![
+            coordZ! { x: 0., y: 0., z: 5. },
+            coordZ! { x: 2., y: 0., z: 5. },
+            coordZ! { x: 0., y: 0., z: 5. },
+        ]);
+        assert_relative_eq!(circle.length(), 2. * PI, epsilon = 1e-12);
+        assert!(circle.to_line_string(0.1).is_closed());
+        assert!(!CircularStringZ(circle.0[..2].to_vec()).is_valid());
+    }
+}
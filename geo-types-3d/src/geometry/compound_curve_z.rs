@@ -0,0 +1,151 @@
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+
+use crate::{CircularStringZ, CoordFloat, CoordNum, CoordZ, LineStringZ};
+
+/// One piece of a [`CompoundCurveZ`].
+#[derive(Eq, PartialEq, Clone, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub enum CurveSegmentZ<T: CoordNum = f64> {
+    LineString(LineStringZ<T>),
+    CircularString(CircularStringZ<T>),
+}
+
+impl<T: CoordNum> CurveSegmentZ<T> {
+    pub fn first(&self) -> Option<&CoordZ<T>> {
+        match self {
+            CurveSegmentZ::LineString(line_string) => line_string.0.first(),
+            CurveSegmentZ::CircularString(circular_string) => circular_string.0.first(),
+        }
+    }
+
+    pub fn last(&self) -> Option<&CoordZ<T>> {
+        match self {
+            CurveSegmentZ::LineString(line_string) => line_string.0.last(),
+            CurveSegmentZ::CircularString(circular_string) => circular_string.0.last(),
+        }
+    }
+}
+
+impl<T: CoordNum> From<LineStringZ<T>> for CurveSegmentZ<T> {
+    fn from(line_string: LineStringZ<T>) -> Self {
+        CurveSegmentZ::LineString(line_string)
+    }
+}
+
+impl<T: CoordNum> From<CircularStringZ<T>> for CurveSegmentZ<T> {
+    fn from(circular_string: CircularStringZ<T>) -> Self {
+        CurveSegmentZ::CircularString(circular_string)
+    }
+}
+
+/// A curve joining straight and circular pieces end to end, each piece starting
+/// where the one before it ends.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::wkt;
+///
+/// // a slot: two straight sides joined by half circles
+/// let slot = wkt! {
+///     COMPOUNDCURVE Z(
+///         (0. 0. 0.,4. 0. 0.),
+///         CIRCULARSTRING Z(4. 0. 0.,5. 1. 0.,4. 2. 0.),
+///         (4. 2. 0.,0. 2. 0.),
+///         CIRCULARSTRING Z(0. 2. 0.,-1. 1. 0.,0. 0. 0.)
+///     )
+/// };
+/// assert!(slot.is_connected() && slot.is_closed());
+/// assert!((slot.length() - (8. + 2. * std::f64::consts::PI)).abs() < 1e-12);
+/// assert!(slot.to_line_string(0.01).is_closed());
+/// ```
+#[derive(Eq, PartialEq, Clone, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct CompoundCurveZ<T: CoordNum = f64>(pub Vec<CurveSegmentZ<T>>);
+
+impl<T: CoordNum> CompoundCurveZ<T> {
+    pub fn new(segments: Vec<CurveSegmentZ<T>>) -> Self {
+        Self(segments)
+    }
+
+    pub fn empty() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Whether each piece starts where the one before it ends.
+    pub fn is_connected(&self) -> bool {
+        self.0
+            .windows(2)
+            .all(|pair| pair[0].last() == pair[1].first())
+    }
+
+    /// Whether the curve ends where it starts. Empty curves are closed.
+    pub fn is_closed(&self) -> bool {
+        self.0.first().and_then(CurveSegmentZ::first) == self.0.last().and_then(CurveSegmentZ::last)
+    }
+}
+
+impl<T: CoordFloat> CompoundCurveZ<T> {
+    pub fn length(&self) -> T {
+        self.0
+            .iter()
+            .map(|segment| match segment {
+                CurveSegmentZ::LineString(line_string) => line_string
+                    .lines()
+                    .map(|line| {
+                        let delta = line.delta();
+                        (delta.x * delta.x + delta.y * delta.y + delta.z * delta.z).sqrt()
+                    })
+                    .fold(T::zero(), |total, length| total + length),
+                CurveSegmentZ::CircularString(circular_string) => circular_string.length(),
+            })
+            .fold(T::zero(), |total, length| total + length)
+    }
+
+    /// Approximates the curve by a line string, linearizing the circular pieces as
+    /// [`CircularStringZ::to_line_string`] does. Where one piece starts at the end
+    /// of the one before, the shared coordinate appears once.
+    pub fn to_line_string(&self, tolerance: T) -> LineStringZ<T> {
+        let mut coords: Vec<CoordZ<T>> = Vec::new();
+        for segment in &self.0 {
+            let piece = match segment {
+                CurveSegmentZ::LineString(line_string) => line_string.0.clone(),
+                CurveSegmentZ::CircularString(circular_string) => {
+                    circular_string.to_line_string(tolerance).0
+                }
+            };
+            let shared = coords.last().is_some() && coords.last() == piece.first();
+            coords.extend(piece.into_iter().skip(shared as usize));
+        }
+        LineStringZ(coords)
+    }
+}
+
+impl<T: CoordNum> From<CircularStringZ<T>> for CompoundCurveZ<T> {
+    fn from(circular_string: CircularStringZ<T>) -> Self {
+        Self(alloc::vec![circular_string.into()])
+    }
+}
+
+impl<T: CoordNum> From<LineStringZ<T>> for CompoundCurveZ<T> {
+    fn from(line_string: LineStringZ<T>) -> Self {
+        Self(alloc::vec![line_string.into()])
+    }
+}
+
+impl<T: CoordNum, S: Into<CurveSegmentZ<T>>> FromIterator<S> for CompoundCurveZ<T> {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        Self(iter.into_iter().map(Into::into).collect())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{coordZ, CoordNum, PointZ};
+use crate::{coordZ, CoordFloat, CoordNum, PointZ};
 
 /// A lightweight struct used to store coordinates on the 3-dimensional
 /// Cartesian plane.
@@ -10,7 +10,10 @@ use crate::{coordZ, CoordNum, PointZ};
 ///
 /// This type implements the [vector space] operations:
 /// [`Add`], [`Sub`], [`Neg`], [`Zero`],
-/// [`Mul<T>`][`Mul`], and [`Div<T>`][`Div`] traits.
+/// [`Mul<T>`][`Mul`], and [`Div<T>`][`Div`] traits, with multiplication by a
+/// scalar on either side, along with the component-wise [`Rem<T>`][`Rem`],
+/// [`abs`](CoordZ::abs), [`component_min`](CoordZ::component_min) and
+/// [`component_max`](CoordZ::component_max).
 ///
 /// # Semantics
 ///
@@ -22,6 +25,11 @@ use crate::{coordZ, CoordNum, PointZ};
 /// [vector space]: //en.wikipedia.org/wiki/Vector_space
 #[derive(Eq, PartialEq, Clone, Copy, Hash, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub struct CoordZ<T: CoordNum = f64> {
     /// Typically, `x` is the horizontal position, or longitude for geographic coordinates,
     /// but its interpretation can vary across coordinate systems.
@@ -109,7 +117,10 @@ impl<T: CoordNum> CoordZ<T> {
     }
 }
 
-use core::ops::{Add, Div, Mul, Neg, Sub};
+use core::iter::Sum;
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
 
 /// Negate a coordinate.
 ///
@@ -210,6 +221,9 @@ impl<T: CoordNum> Sub for CoordZ<T> {
 /// assert_eq!(q.x, 5.0);
 /// assert_eq!(q.y, 10.0);
 /// assert_eq!(q.z, 12.0);
+///
+/// // the scalar may also come first
+/// assert_eq!(4. * p, q);
 /// ```
 impl<T: CoordNum> Mul<T> for CoordZ<T> {
     type Output = Self;
@@ -251,7 +265,163 @@ impl<T: CoordNum> Div<T> for CoordZ<T> {
     }
 }
 
-use num_traits::Zero;
+// Multiply a scalar by a coordinate. The scalar types are listed, as the orphan
+// rule doesn't allow implementing `Mul<CoordZ<T>>` for any `T`.
+macro_rules! impl_left_scalar_mul {
+    ($($scalar:ty),*) => {
+        $(
+            impl Mul<CoordZ<$scalar>> for $scalar {
+                type Output = CoordZ<$scalar>;
+
+                #[inline]
+                fn mul(self, rhs: CoordZ<$scalar>) -> CoordZ<$scalar> {
+                    rhs * self
+                }
+            }
+        )*
+    };
+}
+
+impl_left_scalar_mul!(f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// The remainder of each ordinate divided by a scalar, such as to wrap
+/// coordinates into a repeating tile.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::coordZ;
+///
+/// let p = coordZ! { x: 5.5, y: 10., z: -3. };
+/// let q = p % 4.;
+///
+/// assert_eq!(q, coordZ! { x: 1.5, y: 2., z: -3. });
+/// ```
+impl<T: CoordNum> Rem<T> for CoordZ<T> {
+    type Output = Self;
+
+    #[inline]
+    fn rem(self, rhs: T) -> Self {
+        coordZ! {
+            x: self.x % rhs,
+            y: self.y % rhs,
+            z: self.z % rhs,
+        }
+    }
+}
+
+/// Take the remainder of each ordinate divided by a scalar in place.
+impl<T: CoordNum> RemAssign<T> for CoordZ<T> {
+    #[inline]
+    fn rem_assign(&mut self, rhs: T) {
+        *self = *self % rhs;
+    }
+}
+
+/// Add a coordinate to another in place.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::coordZ;
+///
+/// let mut p = coordZ! { x: 1.25, y: 2.5, z: 3.0 };
+/// p += coordZ! { x: 1.5, y: 2.5, z: 1.0 };
+///
+/// assert_eq!(p, coordZ! { x: 2.75, y: 5.0, z: 4.0 });
+/// ```
+impl<T: CoordNum> AddAssign for CoordZ<T> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+/// Subtract a coordinate from another in place.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::coordZ;
+///
+/// let mut p = coordZ! { x: 1.5, y: 2.5, z: 4.0 };
+/// p -= coordZ! { x: 1.25, y: 2.5, z: 1.0 };
+///
+/// assert_eq!(p, coordZ! { x: 0.25, y: 0.0, z: 3.0 });
+/// ```
+impl<T: CoordNum> SubAssign for CoordZ<T> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+/// Multiply coordinate wise by a scalar in place.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::coordZ;
+///
+/// let mut p = coordZ! { x: 1.25, y: 2.5, z: 3.0 };
+/// p *= 4.;
+///
+/// assert_eq!(p, coordZ! { x: 5.0, y: 10.0, z: 12.0 });
+/// ```
+impl<T: CoordNum> MulAssign<T> for CoordZ<T> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+/// Divide coordinate wise by a scalar in place.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::coordZ;
+///
+/// let mut p = coordZ! { x: 5., y: 10., z: 15. };
+/// p /= 4.;
+///
+/// assert_eq!(p, coordZ! { x: 1.25, y: 2.5, z: 3.75 });
+/// ```
+impl<T: CoordNum> DivAssign<T> for CoordZ<T> {
+    #[inline]
+    fn div_assign(&mut self, rhs: T) {
+        *self = *self / rhs;
+    }
+}
+
+/// Add up coordinates, starting from the origin.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{coordZ, CoordZ};
+///
+/// let coords = [
+///     coordZ! { x: 1., y: 2., z: 3. },
+///     coordZ! { x: 4., y: 5., z: 6. },
+/// ];
+/// let total: CoordZ = coords.iter().sum();
+///
+/// assert_eq!(total, coordZ! { x: 5., y: 7., z: 9. });
+/// ```
+impl<T: CoordNum> Sum for CoordZ<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+impl<'a, T: CoordNum> Sum<&'a CoordZ<T>> for CoordZ<T> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+use num_traits::{Signed, Zero};
 /// Create a coordinate at the origin.
 ///
 /// # Examples
@@ -265,6 +435,11 @@ use num_traits::Zero;
 /// assert_eq!(p.x, 0.);
 /// assert_eq!(p.y, 0.);
 /// assert_eq!(p.z, 0.);
+/// assert!(p.is_zero());
+///
+/// // all three ordinates must be zero
+/// let q = CoordZ { z: 1., ..p };
+/// assert!(!q.is_zero());
 /// ```
 impl<T: CoordNum> CoordZ<T> {
     #[inline]
@@ -277,6 +452,109 @@ impl<T: CoordNum> CoordZ<T> {
     }
 }
 
+impl<T: CoordNum> CoordZ<T> {
+    /// The smaller of each ordinate of the two coordinates, as the lowest corner
+    /// of the box spanning them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::coordZ;
+    ///
+    /// let p = coordZ! { x: 1., y: 5., z: 3. };
+    /// let q = coordZ! { x: 4., y: 2., z: 3. };
+    ///
+    /// assert_eq!(p.component_min(q), coordZ! { x: 1., y: 2., z: 3. });
+    /// assert_eq!(p.component_max(q), coordZ! { x: 4., y: 5., z: 3. });
+    /// ```
+    #[inline]
+    pub fn component_min(self, other: Self) -> Self {
+        let min = |a: T, b: T| if b < a { b } else { a };
+        coordZ! {
+            x: min(self.x, other.x),
+            y: min(self.y, other.y),
+            z: min(self.z, other.z),
+        }
+    }
+
+    /// The larger of each ordinate of the two coordinates, as the highest corner
+    /// of the box spanning them.
+    #[inline]
+    pub fn component_max(self, other: Self) -> Self {
+        let max = |a: T, b: T| if b > a { b } else { a };
+        coordZ! {
+            x: max(self.x, other.x),
+            y: max(self.y, other.y),
+            z: max(self.z, other.z),
+        }
+    }
+
+    /// The absolute value of each ordinate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::coordZ;
+    ///
+    /// let p = coordZ! { x: -1.5, y: 2., z: -3. };
+    ///
+    /// assert_eq!(p.abs(), coordZ! { x: 1.5, y: 2., z: 3. });
+    /// ```
+    #[inline]
+    pub fn abs(self) -> Self
+    where
+        T: Signed,
+    {
+        coordZ! {
+            x: self.x.abs(),
+            y: self.y.abs(),
+            z: self.z.abs(),
+        }
+    }
+}
+
+impl<T: CoordFloat> CoordZ<T> {
+    /// The coordinate a fraction `t` of the way from this coordinate to `other`,
+    /// exactly this one at `t = 0` and `other` at `t = 1`. Other values of `t`
+    /// give coordinates on the line beyond them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::coordZ;
+    ///
+    /// let p = coordZ! { x: 0., y: 10., z: 100. };
+    /// let q = coordZ! { x: 4., y: 20., z: 300. };
+    ///
+    /// assert_eq!(p.lerp(q, 0.25), coordZ! { x: 1., y: 12.5, z: 150. });
+    /// assert_eq!(p.lerp(q, 1.), q);
+    /// ```
+    #[inline]
+    pub fn lerp(self, other: Self, t: T) -> Self {
+        if t == T::one() {
+            return other;
+        }
+        self + (other - self) * t
+    }
+
+    /// The coordinate halfway between this coordinate and `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::coordZ;
+    ///
+    /// let p = coordZ! { x: 0., y: 10., z: 100. };
+    /// let q = coordZ! { x: 4., y: 20., z: 300. };
+    ///
+    /// assert_eq!(p.midpoint(q), coordZ! { x: 2., y: 15., z: 200. });
+    /// ```
+    #[inline]
+    pub fn midpoint(self, other: Self) -> Self {
+        (self + other) / (T::one() + T::one())
+    }
+}
+
 impl<T: CoordNum> Zero for CoordZ<T> {
     #[inline]
     fn zero() -> Self {
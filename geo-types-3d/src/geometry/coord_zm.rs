@@ -0,0 +1,115 @@
+use crate::{coordZM, CoordNum, CoordZ, PointZM};
+
+/// A coordinate with an elevation `z` and a measure `m`.
+///
+/// The measure is a value carried along with each position, most often a distance
+/// along a linear feature such as a road or pipeline, used for linear referencing.
+/// It plays no part in the geometry: dropping it with [`CoordZM::xyz`] gives the
+/// [`CoordZ`] at the same position.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{coordZ, coordZM};
+///
+/// let coord = coordZM! { x: 1.0, y: 2.0, z: 3.0, m: 120.5 };
+/// assert_eq!(coord.xyz(), coordZ! { x: 1.0, y: 2.0, z: 3.0 });
+/// assert_eq!(coord.xyz().with_m(120.5), coord);
+/// ```
+#[derive(Eq, PartialEq, Clone, Copy, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct CoordZM<T: CoordNum = f64> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    /// The measure at this position.
+    pub m: T,
+}
+
+impl<T: CoordNum> CoordZM<T> {
+    /// The position of this coordinate, without its measure.
+    #[inline]
+    pub fn xyz(&self) -> CoordZ<T> {
+        CoordZ {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        }
+    }
+
+    #[inline]
+    pub fn x_y_z_m(&self) -> (T, T, T, T) {
+        (self.x, self.y, self.z, self.m)
+    }
+}
+
+impl<T: CoordNum> CoordZ<T> {
+    /// Adds a measure to this coordinate.
+    #[inline]
+    pub fn with_m(self, m: T) -> CoordZM<T> {
+        coordZM! {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+            m: m,
+        }
+    }
+}
+
+impl<T: CoordNum> From<(T, T, T, T)> for CoordZM<T> {
+    #[inline]
+    fn from(coords: (T, T, T, T)) -> Self {
+        coordZM! {
+            x: coords.0,
+            y: coords.1,
+            z: coords.2,
+            m: coords.3,
+        }
+    }
+}
+
+impl<T: CoordNum> From<[T; 4]> for CoordZM<T> {
+    #[inline]
+    fn from(coords: [T; 4]) -> Self {
+        coordZM! {
+            x: coords[0],
+            y: coords[1],
+            z: coords[2],
+            m: coords[3],
+        }
+    }
+}
+
+impl<T: CoordNum> From<PointZM<T>> for CoordZM<T> {
+    #[inline]
+    fn from(point: PointZM<T>) -> Self {
+        point.0
+    }
+}
+
+impl<T: CoordNum> From<CoordZM<T>> for (T, T, T, T) {
+    #[inline]
+    fn from(coord: CoordZM<T>) -> Self {
+        coord.x_y_z_m()
+    }
+}
+
+impl<T: CoordNum> From<CoordZM<T>> for [T; 4] {
+    #[inline]
+    fn from(coord: CoordZM<T>) -> Self {
+        [coord.x, coord.y, coord.z, coord.m]
+    }
+}
+
+impl<T: CoordNum> From<CoordZM<T>> for CoordZ<T> {
+    /// Drops the measure.
+    #[inline]
+    fn from(coord: CoordZM<T>) -> Self {
+        coord.xyz()
+    }
+}
@@ -0,0 +1,375 @@
+use crate::{coordZ, CoordFloat, CoordNum, CoordZ};
+
+/// An _axis-aligned_ bounded 3D box whose volume is
+/// defined by minimum and maximum `CoordZ`s.
+///
+/// The constructors and setters ensure the maximum
+/// `CoordZ` is greater than or equal to the minimum.
+/// Thus, a `Cube`s width, height, depth and volume are guaranteed to
+/// be greater than or equal to zero.
+///
+/// Despite its name, the three sides of a `Cube` need not be equal: it is
+/// the 3D counterpart of [`Rect`](geo_types::Rect).
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{coordZ, Cube};
+///
+/// let cube = Cube::new(
+///     coordZ! { x: 0., y: 4., z: 1. },
+///     coordZ! { x: 3., y: 10., z: 3. },
+/// );
+///
+/// assert_eq!(3., cube.width());
+/// assert_eq!(6., cube.height());
+/// assert_eq!(2., cube.depth());
+/// assert_eq!(
+///     coordZ! { x: 1.5, y: 7., z: 2. },
+///     cube.center()
+/// );
+/// ```
+#[derive(Eq, PartialEq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct Cube<T: CoordNum = f64> {
+    min: CoordZ<T>,
+    max: CoordZ<T>,
+}
+
+impl<T: CoordNum> Cube<T> {
+    /// Creates a new cube from two corner coordinates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{coordZ, Cube};
+    ///
+    /// let cube = Cube::new(
+    ///     coordZ! { x: 10., y: 20., z: 5. },
+    ///     coordZ! { x: 30., y: 10., z: 0. }
+    /// );
+    /// assert_eq!(cube.min(), coordZ! { x: 10., y: 10., z: 0. });
+    /// assert_eq!(cube.max(), coordZ! { x: 30., y: 20., z: 5. });
+    /// ```
+    pub fn new<C>(c1: C, c2: C) -> Self
+    where
+        C: Into<CoordZ<T>>,
+    {
+        let c1 = c1.into();
+        let c2 = c2.into();
+        let (min_x, max_x) = if c1.x < c2.x {
+            (c1.x, c2.x)
+        } else {
+            (c2.x, c1.x)
+        };
+        let (min_y, max_y) = if c1.y < c2.y {
+            (c1.y, c2.y)
+        } else {
+            (c2.y, c1.y)
+        };
+        let (min_z, max_z) = if c1.z < c2.z {
+            (c1.z, c2.z)
+        } else {
+            (c2.z, c1.z)
+        };
+        Self {
+            min: coordZ! { x: min_x, y: min_y, z: min_z },
+            max: coordZ! { x: max_x, y: max_y, z: max_z },
+        }
+    }
+
+    /// Returns the minimum `CoordZ` of the `Cube`.
+    pub fn min(self) -> CoordZ<T> {
+        self.min
+    }
+
+    /// Set the `Cube`’s minimum coordinate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min`’s x/y/z value is greater than the maximum coordinate’s x/y/z value.
+    pub fn set_min<C>(&mut self, min: C)
+    where
+        C: Into<CoordZ<T>>,
+    {
+        self.min = min.into();
+        self.assert_valid_bounds();
+    }
+
+    /// Returns the maximum `CoordZ` of the `Cube`.
+    pub fn max(self) -> CoordZ<T> {
+        self.max
+    }
+
+    /// Set the `Cube`’s maximum coordinate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max`’s x/y/z value is less than the minimum coordinate’s x/y/z value.
+    pub fn set_max<C>(&mut self, max: C)
+    where
+        C: Into<CoordZ<T>>,
+    {
+        self.max = max.into();
+        self.assert_valid_bounds();
+    }
+
+    /// Returns the extent of the `Cube` along the x axis.
+    pub fn width(self) -> T {
+        self.max().x - self.min().x
+    }
+
+    /// Returns the extent of the `Cube` along the y axis.
+    pub fn height(self) -> T {
+        self.max().y - self.min().y
+    }
+
+    /// Returns the extent of the `Cube` along the z axis.
+    pub fn depth(self) -> T {
+        self.max().z - self.min().z
+    }
+
+    /// Returns the volume enclosed by the `Cube`.
+    pub fn volume(self) -> T {
+        self.width() * self.height() * self.depth()
+    }
+
+    /// Returns true if `coord` lies inside the `Cube` or on its boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{coordZ, Cube};
+    ///
+    /// let cube = Cube::new((0., 0., 0.), (1., 1., 1.));
+    ///
+    /// assert!(cube.contains_coord(coordZ! { x: 1., y: 0.5, z: 0. }));
+    /// assert!(!cube.contains_coord(coordZ! { x: 1., y: 0.5, z: 2. }));
+    /// ```
+    pub fn contains_coord(self, coord: CoordZ<T>) -> bool {
+        self.min.x <= coord.x
+            && coord.x <= self.max.x
+            && self.min.y <= coord.y
+            && coord.y <= self.max.y
+            && self.min.z <= coord.z
+            && coord.z <= self.max.z
+    }
+
+    /// Returns true if the two cubes share at least one point, including
+    /// when they only touch along a face, edge or corner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::Cube;
+    ///
+    /// let a = Cube::new((0., 0., 0.), (1., 1., 1.));
+    /// let b = Cube::new((1., 1., 1.), (2., 2., 2.));
+    /// let c = Cube::new((1.5, 0., 0.), (2., 1., 1.));
+    ///
+    /// assert!(a.intersects(&b));
+    /// assert!(!a.intersects(&c));
+    /// ```
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+            && self.min.z <= other.max.z
+            && other.min.z <= self.max.z
+    }
+
+    /// Returns the smallest `Cube` containing both `self` and `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        let min = |a: T, b: T| if b < a { b } else { a };
+        let max = |a: T, b: T| if b > a { b } else { a };
+        Self {
+            min: coordZ! {
+                x: min(self.min.x, other.min.x),
+                y: min(self.min.y, other.min.y),
+                z: min(self.min.z, other.min.z),
+            },
+            max: coordZ! {
+                x: max(self.max.x, other.max.x),
+                y: max(self.max.y, other.max.y),
+                z: max(self.max.z, other.max.z),
+            },
+        }
+    }
+
+    fn assert_valid_bounds(&self) {
+        if !self.has_valid_bounds() {
+            panic!("{}", CUBE_INVALID_BOUNDS_ERROR);
+        }
+    }
+
+    fn has_valid_bounds(&self) -> bool {
+        self.min.x <= self.max.x && self.min.y <= self.max.y && self.min.z <= self.max.z
+    }
+}
+
+impl<T: CoordFloat> Cube<T> {
+    /// Returns the center `CoordZ` of the `Cube`.
+    pub fn center(self) -> CoordZ<T> {
+        let two = T::one() + T::one();
+        coordZ! {
+            x: (self.max.x + self.min.x) / two,
+            y: (self.max.y + self.min.y) / two,
+            z: (self.max.z + self.min.z) / two,
+        }
+    }
+}
+
+impl<T: CoordNum> From<Cube<T>> for geo_types::Rect<T> {
+    /// The XY footprint of the cube, dropping z. See
+    /// [`LiftRect`](crate::algorithm::LiftRect) for the other way.
+    fn from(cube: Cube<T>) -> Self {
+        geo_types::Rect::new(
+            geo_types::coord! { x: cube.min.x, y: cube.min.y },
+            geo_types::coord! { x: cube.max.x, y: cube.max.y },
+        )
+    }
+}
+
+static CUBE_INVALID_BOUNDS_ERROR: &str = "Failed to create Cube: 'min' coordinate's x/y/z value must be smaller or equal to the 'max' x/y/z value";
+
+#[cfg(any(feature = "approx", test))]
+mod approx_integration {
+    use super::*;
+    use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+    impl<T> RelativeEq for Cube<T>
+    where
+        T: CoordNum + RelativeEq<Epsilon = T>,
+    {
+        #[inline]
+        fn default_max_relative() -> Self::Epsilon {
+            T::default_max_relative()
+        }
+
+        /// Equality assertion within a relative limit.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use geo_types_3d::Cube;
+        ///
+        /// let a = Cube::new((0.0, 0.0, 0.0), (10.0, 10.0, 10.0));
+        /// let b = Cube::new((0.0, 0.0, 0.0), (10.01, 10.0, 10.0));
+        ///
+        /// approx::assert_relative_eq!(a, b, max_relative=0.1);
+        /// approx::assert_relative_ne!(a, b, max_relative=0.0001);
+        /// ```
+        #[inline]
+        fn relative_eq(
+            &self,
+            other: &Self,
+            epsilon: Self::Epsilon,
+            max_relative: Self::Epsilon,
+        ) -> bool {
+            if !self.min.relative_eq(&other.min, epsilon, max_relative) {
+                return false;
+            }
+
+            if !self.max.relative_eq(&other.max, epsilon, max_relative) {
+                return false;
+            }
+
+            true
+        }
+    }
+
+    impl<T> AbsDiffEq for Cube<T>
+    where
+        T: CoordNum + AbsDiffEq<Epsilon = T>,
+    {
+        type Epsilon = T;
+
+        #[inline]
+        fn default_epsilon() -> Self::Epsilon {
+            T::default_epsilon()
+        }
+
+        /// Equality assertion with an absolute limit.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use geo_types_3d::Cube;
+        ///
+        /// let a = Cube::new((0.0, 0.0, 0.0), (10.0, 10.0, 10.0));
+        /// let b = Cube::new((0.0, 0.0, 0.0), (10.01, 10.0, 10.0));
+        ///
+        /// approx::abs_diff_eq!(a, b, epsilon=0.1);
+        /// approx::abs_diff_ne!(a, b, epsilon=0.001);
+        /// ```
+        #[inline]
+        fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+            if !self.min.abs_diff_eq(&other.min, epsilon) {
+                return false;
+            }
+
+            if !self.max.abs_diff_eq(&other.max, epsilon) {
+                return false;
+            }
+
+            true
+        }
+    }
+
+    impl<T> UlpsEq for Cube<T>
+    where
+        T: CoordNum + UlpsEq<Epsilon = T>,
+    {
+        fn default_max_ulps() -> u32 {
+            T::default_max_ulps()
+        }
+
+        fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+            if !self.min.ulps_eq(&other.min, epsilon, max_ulps) {
+                return false;
+            }
+            if !self.max.ulps_eq(&other.max, epsilon, max_ulps) {
+                return false;
+            }
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::coordZ;
+
+    #[test]
+    fn cube() {
+        let cube = Cube::new((10, 10, 10), (20, 20, 20));
+        assert_eq!(cube.min, coordZ! { x: 10, y: 10, z: 10 });
+        assert_eq!(cube.max, coordZ! { x: 20, y: 20, z: 20 });
+        assert_eq!(cube.volume(), 1000);
+
+        let cube = Cube::new((20, 20, 0), (10, 10, 5));
+        assert_eq!(cube.min, coordZ! { x: 10, y: 10, z: 0 });
+        assert_eq!(cube.max, coordZ! { x: 20, y: 20, z: 5 });
+    }
+
+    #[test]
+    #[should_panic]
+    fn cube_set_min_panics() {
+        let mut cube = Cube::new((0., 0., 0.), (1., 1., 1.));
+        cube.set_min((0., 0., 2.));
+    }
+
+    #[test]
+    fn cube_union() {
+        let a = Cube::new((0., 0., 0.), (1., 1., 1.));
+        let b = Cube::new((-1., 0.5, 2.), (0.5, 3., 4.));
+        assert_eq!(a.union(&b), Cube::new((-1., 0., 0.), (1., 3., 4.)));
+    }
+}
@@ -0,0 +1,366 @@
+use core::fmt;
+
+use geo_types::{
+    Coord, Line, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon, Rect,
+};
+
+use crate::algorithm::{LiftRect, MapCoordsZ, MapCoordsZInPlace};
+use crate::{
+    CircularStringZ, CompoundCurveZ, CoordNum, CoordZ, Cube, Error, Geometry, GeometryCollection,
+    LineStringZ, LineStringZM, LineZ, MultiLineStringZ, MultiPointZ, MultiPolygonZ, PointZ,
+    PointZM, PolygonZ, TinZ, Triangle,
+};
+
+/// The coordinate dimensions of a geometry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub enum Dimension {
+    /// Planar coordinates, like the geometries from [`geo_types`].
+    XY,
+    /// Coordinates with elevation, like [`PointZ`] or [`PolygonZ`].
+    XYZ,
+    /// Coordinates with elevation and a measure, like [`PointZM`].
+    XYZM,
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Dimension::XY => f.write_str("XY"),
+            Dimension::XYZ => f.write_str("XYZ"),
+            Dimension::XYZM => f.write_str("XYZM"),
+        }
+    }
+}
+
+/// Geometry types whose coordinate dimensions are known from the type alone.
+pub trait HasDimension {
+    const DIMENSION: Dimension;
+}
+
+macro_rules! has_dimension_impl {
+    ($dimension: ident: $($type: ident),+) => {
+        $(
+        impl<T: CoordNum> HasDimension for $type<T> {
+            const DIMENSION: Dimension = Dimension::$dimension;
+        }
+        )+
+    }
+}
+
+has_dimension_impl!(XY: Point, Line, LineString, Polygon, MultiPoint, MultiLineString, MultiPolygon, Rect);
+has_dimension_impl!(XYZ: PointZ, LineZ, LineStringZ, PolygonZ, MultiPointZ, MultiLineStringZ, MultiPolygonZ, Cube, Triangle, CircularStringZ, CompoundCurveZ, TinZ);
+has_dimension_impl!(XYZM: PointZM, LineStringZM);
+
+impl<T: CoordNum> Geometry<T> {
+    /// The coordinate dimensions of this geometry.
+    ///
+    /// Returns `None` for a collection that is empty or that mixes planar and 3D
+    /// members.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{Dimension, Geometry, GeometryCollection, PointZ};
+    ///
+    /// let point = Geometry::PointZ(PointZ::new(1.0, 2.0, 3.0));
+    /// assert_eq!(point.dimension(), Some(Dimension::XYZ));
+    ///
+    /// let mixed = GeometryCollection::from(vec![
+    ///     point,
+    ///     Geometry::Point(geo_types::Point::new(1.0, 2.0)),
+    /// ]);
+    /// assert_eq!(Geometry::from(mixed).dimension(), None);
+    /// ```
+    pub fn dimension(&self) -> Option<Dimension> {
+        match self {
+            Geometry::Point(_)
+            | Geometry::Line(_)
+            | Geometry::LineString(_)
+            | Geometry::Polygon(_)
+            | Geometry::MultiPoint(_)
+            | Geometry::MultiLineString(_)
+            | Geometry::MultiPolygon(_)
+            | Geometry::Rect(_) => Some(Dimension::XY),
+            Geometry::PointZ(_)
+            | Geometry::LineZ(_)
+            | Geometry::LineStringZ(_)
+            | Geometry::PolygonZ(_)
+            | Geometry::MultiPointZ(_)
+            | Geometry::MultiLineStringZ(_)
+            | Geometry::MultiPolygonZ(_) => Some(Dimension::XYZ),
+            Geometry::GeometryCollection(collection) => {
+                let mut members = collection.iter().map(Geometry::dimension);
+                let first = members.next()??;
+                members
+                    .all(|dimension| dimension == Some(first))
+                    .then_some(first)
+            }
+        }
+    }
+
+    /// Whether this geometry has 3D coordinates: it is a Z variant, or a collection
+    /// with a Z variant among its (nested) members.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{Geometry, GeometryCollection, PointZ};
+    ///
+    /// let planar = Geometry::Point(geo_types::Point::new(1.0, 2.0));
+    /// assert!(!planar.has_z());
+    ///
+    /// let mixed = GeometryCollection::from(vec![planar, PointZ::new(1.0, 2.0, 3.0).into()]);
+    /// assert!(Geometry::from(mixed).has_z());
+    /// ```
+    pub fn has_z(&self) -> bool {
+        match self {
+            Geometry::GeometryCollection(collection) => collection.iter().any(Geometry::has_z),
+            geometry => geometry.dimension() == Some(Dimension::XYZ),
+        }
+    }
+
+    /// This geometry with its planar members upgraded to their Z variants at
+    /// `default_z`, such as to load 2D and 3D data into one dataset.
+    ///
+    /// A [`Rect`] becomes a [`PolygonZ`] as by
+    /// [`LiftRect::to_polygon_z`](crate::algorithm::LiftRect::to_polygon_z). The z of
+    /// 3D members is left alone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{wkt, Dimension, Geometry};
+    ///
+    /// let planar = Geometry::LineString(geo_types::wkt! { LINESTRING(0. 0.,1. 1.) });
+    /// let lifted = planar.to_z_variant(5.);
+    /// assert_eq!(lifted, wkt! { LINESTRING Z(0. 0. 5.,1. 1. 5.) }.into());
+    /// assert_eq!(lifted.dimension(), Some(Dimension::XYZ));
+    /// ```
+    pub fn to_z_variant(&self, default_z: T) -> Geometry<T> {
+        let line_string = |line_string: &LineString<T>| lift(line_string, default_z);
+        let polygon = |polygon: &Polygon<T>| {
+            PolygonZ::new(
+                line_string(polygon.exterior()),
+                polygon.interiors().iter().map(line_string).collect(),
+            )
+        };
+        match self {
+            Geometry::Point(g) => Geometry::PointZ(PointZ(lift_coord(g.0, default_z))),
+            Geometry::Line(g) => Geometry::LineZ(LineZ::new(
+                lift_coord(g.start, default_z),
+                lift_coord(g.end, default_z),
+            )),
+            Geometry::LineString(g) => Geometry::LineStringZ(line_string(g)),
+            Geometry::Polygon(g) => Geometry::PolygonZ(polygon(g)),
+            Geometry::MultiPoint(g) => Geometry::MultiPointZ(MultiPointZ(
+                g.0.iter()
+                    .map(|point| PointZ(lift_coord(point.0, default_z)))
+                    .collect(),
+            )),
+            Geometry::MultiLineString(g) => {
+                Geometry::MultiLineStringZ(MultiLineStringZ(g.0.iter().map(line_string).collect()))
+            }
+            Geometry::MultiPolygon(g) => {
+                Geometry::MultiPolygonZ(MultiPolygonZ(g.0.iter().map(polygon).collect()))
+            }
+            Geometry::Rect(g) => Geometry::PolygonZ(g.to_polygon_z(default_z)),
+            Geometry::GeometryCollection(collection) => {
+                Geometry::GeometryCollection(GeometryCollection(
+                    collection
+                        .iter()
+                        .map(|member| member.to_z_variant(default_z))
+                        .collect(),
+                ))
+            }
+            geometry => geometry.clone(),
+        }
+    }
+
+    /// This geometry with `func` applied to the z of every 3D coordinate. Planar
+    /// members are left alone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{Geometry, PointZ};
+    ///
+    /// // feet to metres
+    /// let mast = Geometry::PointZ(PointZ::new(4.0, 52.0, 100.0));
+    /// assert_eq!(mast.map_z(|z| z * 0.3048), PointZ::new(4.0, 52.0, 30.48).into());
+    /// ```
+    pub fn map_z(&self, func: impl Fn(T) -> T + Copy) -> Geometry<T> {
+        self.map_coords(|coord| CoordZ {
+            z: func(coord.z),
+            ..coord
+        })
+    }
+
+    /// Upgrades the planar members of this geometry to their Z variants and sets
+    /// the z of every coordinate to `z`, such as to normalize 2.5D data known to
+    /// lie at one height.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{Geometry, GeometryCollection, MultiPointZ, PointZ};
+    ///
+    /// let mut floor = Geometry::from(GeometryCollection::from(vec![
+    ///     Geometry::Point(geo_types::Point::new(1.0, 2.0)),
+    ///     PointZ::new(3.0, 4.0, 2.9).into(),
+    /// ]));
+    /// floor.set_constant_z(3.0);
+    /// assert_eq!(
+    ///     floor,
+    ///     GeometryCollection::from(vec![PointZ::new(1.0, 2.0, 3.0), PointZ::new(3.0, 4.0, 3.0)])
+    ///         .into()
+    /// );
+    /// ```
+    pub fn set_constant_z(&mut self, z: T) {
+        *self = self.to_z_variant(z);
+        self.map_coords_in_place(|coord| CoordZ { z, ..coord });
+    }
+}
+
+fn lift_coord<T: CoordNum>(coord: Coord<T>, z: T) -> CoordZ<T> {
+    CoordZ {
+        x: coord.x,
+        y: coord.y,
+        z,
+    }
+}
+
+fn lift<T: CoordNum>(line_string: &LineString<T>, z: T) -> LineStringZ<T> {
+    LineStringZ(
+        line_string
+            .0
+            .iter()
+            .map(|&coord| lift_coord(coord, z))
+            .collect(),
+    )
+}
+
+impl<T: CoordNum> GeometryCollection<T> {
+    /// Checks that every member, including the members of nested collections, has
+    /// the given coordinate dimensions.
+    ///
+    /// Empty collections pass for either dimension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{Dimension, Error, Geometry, GeometryCollection, PointZ};
+    ///
+    /// let mut collection = GeometryCollection::from(vec![PointZ::new(1.0, 2.0, 3.0)]);
+    /// assert!(collection.assert_uniform(Dimension::XYZ).is_ok());
+    ///
+    /// collection.0.push(Geometry::Point(geo_types::Point::new(1.0, 2.0)));
+    /// assert!(matches!(
+    ///     collection.assert_uniform(Dimension::XYZ),
+    ///     Err(Error::MismatchedDimension { index: 1, .. })
+    /// ));
+    /// ```
+    pub fn assert_uniform(&self, dimension: Dimension) -> Result<(), Error> {
+        for (index, member) in self.iter().enumerate() {
+            if let Some(found) = mismatch(member, dimension) {
+                return Err(Error::MismatchedDimension {
+                    index,
+                    expected: dimension,
+                    found,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The dimensions of the first (nested) member that doesn't have `dimension`.
+fn mismatch<T: CoordNum>(geometry: &Geometry<T>, dimension: Dimension) -> Option<Dimension> {
+    match geometry {
+        Geometry::GeometryCollection(collection) => collection
+            .iter()
+            .find_map(|member| mismatch(member, dimension)),
+        geometry => geometry.dimension().filter(|found| *found != dimension),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::BoundingCube;
+    use alloc::vec;
+
+    #[test]
+    fn nested_collections() {
+        let planar = Geometry::Point(Point::new(0., 0.));
+        let nested = Geometry::GeometryCollection(GeometryCollection::from(vec![
+            Geometry::from(PointZ::new(0., 0., 0.)),
+            planar.clone(),
+        ]));
+        let collection =
+            GeometryCollection::from(vec![Geometry::from(PointZ::new(1., 1., 1.)), nested]);
+        assert_eq!(Geometry::from(collection.clone()).dimension(), None);
+        match collection.assert_uniform(Dimension::XYZ) {
+            Err(Error::MismatchedDimension {
+                index,
+                expected,
+                found,
+            }) => {
+                assert_eq!(index, 1);
+                assert_eq!(expected, Dimension::XYZ);
+                assert_eq!(found, Dimension::XY);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        let empty = GeometryCollection::<f64>::empty();
+        assert!(empty.assert_uniform(Dimension::XY).is_ok());
+        assert_eq!(Geometry::from(empty).dimension(), None);
+        assert_eq!(
+            GeometryCollection::from(vec![planar])
+                .assert_uniform(Dimension::XY)
+                .ok(),
+            Some(())
+        );
+    }
+
+    #[test]
+    fn z_variants() {
+        let rect = Geometry::Rect(Rect::new((0., 0.), (1., 1.)));
+        let nested = Geometry::from(GeometryCollection::from(vec![
+            Geometry::from(GeometryCollection::from(vec![rect.clone()])),
+            Geometry::from(LineZ::new((0., 0., 1.), (1., 1., 2.))),
+        ]));
+        assert!(!rect.has_z());
+        assert!(nested.has_z());
+        assert!(!Geometry::from(GeometryCollection::<f64>::empty()).has_z());
+        let second = |geometry: &Geometry| match geometry {
+            Geometry::GeometryCollection(collection) => collection.0[1].clone(),
+            _ => unreachable!(),
+        };
+
+        let lifted = nested.to_z_variant(7.);
+        assert_eq!(lifted.dimension(), Some(Dimension::XYZ));
+        assert_eq!(
+            second(&lifted),
+            Geometry::from(LineZ::new((0., 0., 1.), (1., 1., 2.)))
+        );
+
+        // planar members are left to map_z, but not to set_constant_z
+        assert_eq!(rect.map_z(|z| z + 1.), rect);
+        let mut flattened = nested.map_z(|z| z * 10.);
+        assert_eq!(
+            second(&flattened),
+            Geometry::from(LineZ::new((0., 0., 10.), (1., 1., 20.)))
+        );
+        flattened.set_constant_z(0.5);
+        assert_eq!(flattened.dimension(), Some(Dimension::XYZ));
+        let bounds = flattened.bounding_cube().unwrap();
+        assert_eq!((bounds.min().z, bounds.max().z), (0.5, 0.5));
+    }
+}
@@ -0,0 +1,153 @@
+use num_traits::Float;
+
+use crate::algorithm::linalg::dot;
+use crate::{CoordFloat, CoordZ, Cube, MultiPointZ, PointZ};
+
+/// An axis-aligned ellipsoid, given by its centre and its semi-axes along x, y and
+/// z.
+///
+/// Points of the ellipsoid are those `p` with
+/// `((p.x - c.x) / a.x)² + ((p.y - c.y) / a.y)² + ((p.z - c.z) / a.z)² ≤ 1`, for
+/// centre `c` and semi-axes `a`. With equal semi-axes it is a
+/// [`Sphere`](crate::Sphere).
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{coordZ, Cube, EllipsoidZ};
+///
+/// // a flattened ellipsoid, 8 across and 2 high
+/// let ellipsoid = EllipsoidZ::new(
+///     coordZ! { x: 0., y: 0., z: 0. },
+///     coordZ! { x: 4., y: 4., z: 1. },
+/// );
+/// assert!(ellipsoid.contains_coord(coordZ! { x: 3.9, y: 0., z: 0. }));
+/// assert!(!ellipsoid.contains_coord(coordZ! { x: 0., y: 0., z: 1.1 }));
+/// assert!(ellipsoid.intersects(&Cube::new((3., 0., 0.), (5., 1., 1.))));
+/// assert!(!ellipsoid.intersects(&Cube::new((3., 3., 0.), (5., 5., 1.))));
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct EllipsoidZ<T: CoordFloat = f64> {
+    pub center: CoordZ<T>,
+    pub semi_axes: CoordZ<T>,
+}
+
+impl<T: CoordFloat> EllipsoidZ<T> {
+    pub fn new(center: CoordZ<T>, semi_axes: CoordZ<T>) -> Self {
+        Self { center, semi_axes }
+    }
+
+    /// Whether `coord` lies inside the ellipsoid or on its surface.
+    pub fn contains_coord(&self, coord: CoordZ<T>) -> bool {
+        let unit = self.unit_coord(coord);
+        dot(unit, unit) <= T::one()
+    }
+
+    /// Whether the ellipsoid and `cube` have any point in common.
+    pub fn intersects(&self, cube: &Cube<T>) -> bool {
+        self.contains_coord(closest(cube, self.center))
+    }
+
+    /// `count` points spread evenly over the surface of the ellipsoid, along a
+    /// spiral from the top to the bottom.
+    ///
+    /// The points are those of [`Sphere::surface_points`](crate::Sphere::surface_points)
+    /// stretched along the semi-axes, so they bunch up where the ellipsoid is most
+    /// curved.
+    pub fn surface_points(&self, count: usize) -> MultiPointZ<T> {
+        fibonacci_sphere(count)
+            .map(|unit| PointZ(self.world_coord(unit)))
+            .collect()
+    }
+
+    /// The coordinates of `coord` in the space where the ellipsoid is the unit
+    /// sphere.
+    fn unit_coord(&self, coord: CoordZ<T>) -> CoordZ<T> {
+        let (d, a) = (coord - self.center, self.semi_axes);
+        CoordZ {
+            x: d.x / a.x,
+            y: d.y / a.y,
+            z: d.z / a.z,
+        }
+    }
+
+    /// The inverse of [`unit_coord`](Self::unit_coord).
+    fn world_coord(&self, unit: CoordZ<T>) -> CoordZ<T> {
+        let a = self.semi_axes;
+        self.center
+            + CoordZ {
+                x: unit.x * a.x,
+                y: unit.y * a.y,
+                z: unit.z * a.z,
+            }
+    }
+}
+
+/// The point of `cube` closest to `coord`.
+pub(crate) fn closest<T: CoordFloat>(cube: &Cube<T>, coord: CoordZ<T>) -> CoordZ<T> {
+    let (min, max) = (cube.min(), cube.max());
+    CoordZ {
+        x: coord.x.max(min.x).min(max.x),
+        y: coord.y.max(min.y).min(max.y),
+        z: coord.z.max(min.z).min(max.z),
+    }
+}
+
+/// `count` points of the unit sphere on a Fibonacci spiral, each covering about the
+/// same area.
+pub(crate) fn fibonacci_sphere<T: CoordFloat>(count: usize) -> impl Iterator<Item = CoordZ<T>> {
+    let n = T::from(count).unwrap();
+    let two = T::one() + T::one();
+    // the golden angle, π(3 - √5)
+    let step = T::from(core::f64::consts::PI * (3. - Float::sqrt(5f64))).unwrap();
+    (0..count).map(move |i| {
+        let i = T::from(i).unwrap();
+        let z = T::one() - (two * i + T::one()) / n;
+        let r = (T::one() - z * z).max(T::zero()).sqrt();
+        let (sin, cos) = (step * i).sin_cos();
+        CoordZ {
+            x: r * cos,
+            y: r * sin,
+            z,
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::BoundingCube;
+
+    #[test]
+    fn ellipsoid() {
+        let ellipsoid: EllipsoidZ =
+            EllipsoidZ::new(CoordZ::from((1., 2., 3.)), CoordZ::from((3., 2., 1.)));
+        assert_eq!(
+            ellipsoid.bounding_cube(),
+            Cube::new((-2., 0., 2.), (4., 4., 4.))
+        );
+        assert!(ellipsoid.contains_coord(CoordZ::from((3.9, 2., 3.))));
+        assert!(!ellipsoid.contains_coord(CoordZ::from((3.9, 2., 3.5))));
+
+        // the corner of the bounding cube is outside, but a cube reaching in isn't
+        assert!(!ellipsoid.intersects(&Cube::new((3.5, 3.5, 3.5), (5., 5., 5.))));
+        assert!(ellipsoid.intersects(&Cube::new((3.5, 1., 2.5), (5., 1.5, 2.8))));
+        assert!(ellipsoid.intersects(&Cube::new((-9., -9., -9.), (9., 9., 9.))));
+
+        let points = ellipsoid.surface_points(200);
+        assert_eq!(points.len(), 200);
+        for point in &points {
+            let unit = ellipsoid.unit_coord(point.0);
+            assert!((dot(unit, unit) - 1.).abs() < 1e-12);
+        }
+        // as many points above the centre as below it
+        assert_eq!(points.iter().filter(|p| p.z() > 3.).count(), 100);
+        assert!(ellipsoid.surface_points(0).is_empty());
+    }
+}
@@ -0,0 +1,183 @@
+use crate::algorithm::{AffineTransform3D, BoundingCube};
+use crate::{CoordFloat, CoordZ, Cube, Plane};
+
+/// The volume seen by a camera: the points inside six planes, whose normals face
+/// inwards.
+///
+/// The planes are, in order, the left, right, bottom, top, near and far sides of
+/// the view. A perspective camera sees a truncated pyramid, an orthographic one a
+/// box.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{coordZ, Cube, Frustum};
+///
+/// // looking down -z with a 90° field of view, seeing from 1 to 100 units away
+/// let (near, far) = (1., 100.);
+/// let frustum = Frustum::from_matrix([
+///     [1., 0., 0., 0.],
+///     [0., 1., 0., 0.],
+///     [0., 0., (far + near) / (near - far), 2. * far * near / (near - far)],
+///     [0., 0., -1., 0.],
+/// ])
+/// .unwrap();
+/// assert!(frustum.contains_coord(coordZ! { x: 5., y: -5., z: -10. }));
+/// assert!(!frustum.contains_coord(coordZ! { x: 15., y: -5., z: -10. }));
+/// assert!(frustum.intersects(&Cube::new((8., 0., -11.), (12., 1., -9.))));
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct Frustum<T: CoordFloat = f64> {
+    pub planes: [Plane<T>; 6],
+}
+
+impl<T: CoordFloat> Frustum<T> {
+    pub fn new(planes: [Plane<T>; 6]) -> Self {
+        Self { planes }
+    }
+
+    /// The frustum of a view-projection matrix, given by its rows, which takes
+    /// `[x, y, z, 1]` in world coordinates to clip coordinates `[x, y, z, w]`, seen
+    /// if `-w ≤ x, y, z ≤ w`, as in OpenGL.
+    ///
+    /// Returns `None` if a row of the matrix gives no plane, as when it is zero.
+    pub fn from_matrix(matrix: [[T; 4]; 4]) -> Option<Self> {
+        let [x, y, z, w] = matrix;
+        let plane = |sign: T, row: [T; 4]| {
+            let [a, b, c, d] = core::array::from_fn(|i| w[i] + sign * row[i]);
+            let normal = CoordZ { x: a, y: b, z: c };
+            let length = (a * a + b * b + c * c).sqrt();
+            (length > T::zero() && length.is_finite())
+                .then(|| Plane::new(normal / length, -d / length))
+        };
+        let (plus, minus) = (T::one(), -T::one());
+        Some(Self::new([
+            plane(plus, x)?,
+            plane(minus, x)?,
+            plane(plus, y)?,
+            plane(minus, y)?,
+            plane(plus, z)?,
+            plane(minus, z)?,
+        ]))
+    }
+
+    /// The box of an orthographic camera's affine view-projection, which takes the
+    /// points it sees into the cube from `(-1, -1, -1)` to `(1, 1, 1)`, or `None` if
+    /// it flattens space.
+    pub fn from_affine(transform: &AffineTransform3D<T>) -> Option<Self> {
+        Self::from_matrix(transform.homogeneous())
+    }
+
+    /// Whether `coord` lies inside the frustum or on its sides.
+    pub fn contains_coord(&self, coord: CoordZ<T>) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance(coord) >= T::zero())
+    }
+
+    /// Whether `cube` may be seen, being on the inner side of every plane.
+    ///
+    /// The test is conservative: a cube outside the frustum but near one of its
+    /// edges or corners, not wholly beyond any one plane, may still be taken to
+    /// intersect it. That is the usual trade for culling, which must never drop
+    /// anything that is seen.
+    pub fn intersects(&self, cube: &Cube<T>) -> bool {
+        let (min, max) = (cube.min(), cube.max());
+        self.planes.iter().all(|plane| {
+            // the corner of the cube furthest along the normal
+            let pick = |normal: T, min: T, max: T| if normal >= T::zero() { max } else { min };
+            let n = plane.normal;
+            let corner = CoordZ {
+                x: pick(n.x, min.x, max.x),
+                y: pick(n.y, min.y, max.y),
+                z: pick(n.z, min.z, max.z),
+            };
+            plane.signed_distance(corner) >= T::zero()
+        })
+    }
+
+    /// The geometries whose bounding cubes [intersect](Self::intersects) the
+    /// frustum, leaving out those that can't be seen. Empty geometries are left
+    /// out.
+    pub fn cull<'a, G: BoundingCube<T>>(
+        &'a self,
+        geometries: &'a [G],
+    ) -> impl Iterator<Item = &'a G> + 'a {
+        geometries.iter().filter(|geometry| {
+            let cube: Option<Cube<T>> = geometry.bounding_cube().into();
+            cube.is_some_and(|cube| self.intersects(&cube))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Geometry, LineStringZ, PointZ};
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    fn perspective() -> Frustum<f64> {
+        let (near, far) = (1., 100.);
+        Frustum::from_matrix([
+            [1., 0., 0., 0.],
+            [0., 2., 0., 0.],
+            [
+                0.,
+                0.,
+                (far + near) / (near - far),
+                2. * far * near / (near - far),
+            ],
+            [0., 0., -1., 0.],
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn perspective_frustum() {
+        let frustum = perspective();
+        let coord = |x, y, z| CoordZ { x, y, z };
+        assert!(frustum.contains_coord(coord(0., 0., -10.)));
+        assert!(frustum.contains_coord(coord(9.9, 4.9, -10.)));
+        assert!(!frustum.contains_coord(coord(0., 5.1, -10.)));
+        assert!(!frustum.contains_coord(coord(0., 0., 10.)));
+        assert!(!frustum.contains_coord(coord(0., 0., -0.5)));
+        assert!(!frustum.contains_coord(coord(0., 0., -101.)));
+        let near = frustum.planes[4];
+        assert!((near.signed_distance(coord(0., 0., -1.))).abs() < 1e-12);
+
+        assert!(frustum.intersects(&Cube::new((-1., -1., -11.), (1., 1., -9.))));
+        assert!(frustum.intersects(&Cube::new((9., 0., -11.), (12., 1., -9.))));
+        assert!(!frustum.intersects(&Cube::new((14., 0., -11.), (16., 1., -9.))));
+        assert!(!frustum.intersects(&Cube::new((-1., -1., 1.), (1., 1., 3.))));
+        // the whole frustum inside a cube
+        assert!(frustum.intersects(&Cube::new((-500., -500., -500.), (500., 500., 500.))));
+
+        let geometries = vec![
+            Geometry::PointZ(PointZ::new(0., 0., -50.)),
+            Geometry::PointZ(PointZ::new(0., 0., 50.)),
+            Geometry::LineStringZ(LineStringZ::from(vec![(-30., 0., -20.), (30., 0., -20.)])),
+            Geometry::LineStringZ(LineStringZ::new(vec![])),
+        ];
+        let seen: Vec<_> = frustum.cull(&geometries).collect();
+        assert_eq!(seen, [&geometries[0], &geometries[2]]);
+    }
+
+    #[test]
+    fn orthographic_frustum() {
+        // sees x and y within 10 of the origin, and z from 0 down to -20
+        let camera = AffineTransform3D::translate(0., 0., 10.).scaled(0.1, 0.1, -0.1);
+        let frustum = Frustum::from_affine(&camera).unwrap();
+        assert!(frustum.contains_coord(CoordZ::from((9.9, -9.9, -19.9))));
+        assert!(!frustum.contains_coord(CoordZ::from((9.9, -9.9, 0.1))));
+        assert!(!frustum.contains_coord(CoordZ::from((10.1, 0., -5.))));
+
+        assert!(Frustum::from_affine(&AffineTransform3D::scale(1., 0., 1.)).is_none());
+    }
+}
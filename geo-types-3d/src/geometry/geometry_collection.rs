@@ -1,10 +1,13 @@
-use crate::{CoordNum, Geometry};
+use crate::{CoordNum, Geometry, LineStringZ, PointZ, PolygonZ};
 
 // use alloc::vec;
 use alloc::vec::Vec;
 use core::iter::FromIterator;
 use core::ops::{Index, IndexMut};
 
+#[cfg(feature = "multithreading")]
+use rayon::prelude::*;
+
 /// A collection of [`Geometry`](enum.Geometry.html) types.
 ///
 /// It can be created from a `Vec` of Geometries, or from an Iterator which yields Geometries.
@@ -71,7 +74,33 @@ use core::ops::{Index, IndexMut};
 ///
 #[derive(Eq, PartialEq, Clone, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct GeometryCollection<T: CoordNum = f64>(pub Vec<Geometry<T>>);
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    // `Geometry` holds `GeometryCollection` in turn, so the bounds the derive would
+    // put on the field recurse forever, and are spelled out here instead.
+    rkyv(
+        archive_bounds(T: rkyv::Archive),
+        serialize_bounds(
+            T: rkyv::Serialize<__S>,
+            __S: rkyv::ser::Writer + rkyv::ser::Allocator,
+            __S::Error: rkyv::rancor::Source,
+        ),
+        deserialize_bounds(
+            T::Archived: rkyv::Deserialize<T, __D>,
+            __D::Error: rkyv::rancor::Source,
+        ),
+        bytecheck(bounds(
+            T::Archived: rkyv::bytecheck::CheckBytes<__C>,
+            __C: rkyv::validation::ArchiveContext,
+            __C::Error: rkyv::rancor::Source,
+        )),
+    )
+)]
+pub struct GeometryCollection<T: CoordNum = f64>(
+    #[cfg_attr(feature = "rkyv", rkyv(omit_bounds))] pub Vec<Geometry<T>>,
+);
 
 // Implementing Default by hand because T does not have Default restriction
 // todo: consider adding Default as a CoordNum requirement
@@ -111,11 +140,16 @@ impl<T: CoordNum> GeometryCollection<T> {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Add a geometry to the end of this GeometryCollection
+    pub fn push(&mut self, geometry: impl Into<Geometry<T>>) {
+        self.0.push(geometry.into());
+    }
 }
 
-/// **DO NOT USE!** Deprecated since 0.7.5.
-///
-/// Use `GeometryCollection::from(vec![geom])` instead.
+// **DO NOT USE!** Deprecated since 0.7.5.
+//
+// Use `GeometryCollection::from(vec![geom])` instead.
 // impl<T: CoordNum, IG: Into<Geometry<T>>> From<IG> for GeometryCollection<T> {
 //     fn from(x: IG) -> Self {
 //         Self(vec![x.into()])
@@ -136,6 +170,12 @@ impl<T: CoordNum, IG: Into<Geometry<T>>> FromIterator<IG> for GeometryCollection
     }
 }
 
+impl<T: CoordNum, IG: Into<Geometry<T>>> Extend<IG> for GeometryCollection<T> {
+    fn extend<I: IntoIterator<Item = IG>>(&mut self, iter: I) {
+        self.0.extend(iter.into_iter().map(|g| g.into()));
+    }
+}
+
 impl<T: CoordNum> Index<usize> for GeometryCollection<T> {
     type Output = Geometry<T>;
 
@@ -240,6 +280,36 @@ impl<'a, T: CoordNum> Iterator for IterMutHelper<'a, T> {
     }
 }
 
+#[cfg(feature = "multithreading")]
+impl<T: CoordNum + Send> IntoParallelIterator for GeometryCollection<T> {
+    type Item = Geometry<T>;
+    type Iter = rayon::vec::IntoIter<Geometry<T>>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.0.into_par_iter()
+    }
+}
+
+#[cfg(feature = "multithreading")]
+impl<'a, T: CoordNum + Sync> IntoParallelIterator for &'a GeometryCollection<T> {
+    type Item = &'a Geometry<T>;
+    type Iter = rayon::slice::Iter<'a, Geometry<T>>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.0.par_iter()
+    }
+}
+
+#[cfg(feature = "multithreading")]
+impl<'a, T: CoordNum + Send + Sync> IntoParallelIterator for &'a mut GeometryCollection<T> {
+    type Item = &'a mut Geometry<T>;
+    type Iter = rayon::slice::IterMut<'a, Geometry<T>>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.0.par_iter_mut()
+    }
+}
+
 impl<'a, T: CoordNum> GeometryCollection<T> {
     pub fn iter(&'a self) -> IterHelper<'a, T> {
         self.into_iter()
@@ -248,6 +318,75 @@ impl<'a, T: CoordNum> GeometryCollection<T> {
     pub fn iter_mut(&'a mut self) -> IterMutHelper<'a, T> {
         self.into_iter()
     }
+
+    /// An iterator over the members of type `G`, skipping all others.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{Geometry, GeometryCollection, LineStringZ, PointZ};
+    ///
+    /// let gc = GeometryCollection::from(vec![
+    ///     Geometry::PointZ(PointZ::new(1., 2., 3.)),
+    ///     Geometry::LineStringZ(LineStringZ::from(vec![(0., 0., 0.), (1., 1., 1.)])),
+    ///     Geometry::PointZ(PointZ::new(4., 5., 6.)),
+    /// ]);
+    ///
+    /// let points: Vec<&PointZ> = gc.members().collect();
+    /// assert_eq!(points, [&PointZ::new(1., 2., 3.), &PointZ::new(4., 5., 6.)]);
+    /// ```
+    pub fn members<G>(&'a self) -> impl Iterator<Item = &'a G>
+    where
+        G: 'a,
+        &'a G: TryFrom<&'a Geometry<T>>,
+    {
+        self.0
+            .iter()
+            .filter_map(|geometry| geometry.try_into().ok())
+    }
+
+    /// Copies out the members of type `G`, skipping all others.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{polygon_z, Geometry, GeometryCollection, PointZ, PolygonZ};
+    ///
+    /// let square = polygon_z![
+    ///     (x: 0., y: 0., z: 1.),
+    ///     (x: 1., y: 0., z: 1.),
+    ///     (x: 1., y: 1., z: 1.),
+    ///     (x: 0., y: 1., z: 1.),
+    /// ];
+    /// let gc = GeometryCollection::from(vec![
+    ///     Geometry::PointZ(PointZ::new(1., 2., 3.)),
+    ///     Geometry::PolygonZ(square.clone()),
+    /// ]);
+    ///
+    /// assert_eq!(gc.extract::<PolygonZ>(), vec![square]);
+    /// ```
+    pub fn extract<G>(&'a self) -> Vec<G>
+    where
+        G: Clone + 'a,
+        &'a G: TryFrom<&'a Geometry<T>>,
+    {
+        self.members().cloned().collect()
+    }
+
+    /// An iterator over the [`PointZ`] members.
+    pub fn points(&'a self) -> impl Iterator<Item = &'a PointZ<T>> {
+        self.members()
+    }
+
+    /// An iterator over the [`LineStringZ`] members.
+    pub fn line_strings(&'a self) -> impl Iterator<Item = &'a LineStringZ<T>> {
+        self.members()
+    }
+
+    /// An iterator over the [`PolygonZ`] members.
+    pub fn polygons(&'a self) -> impl Iterator<Item = &'a PolygonZ<T>> {
+        self.members()
+    }
 }
 
 // #[cfg(any(feature = "approx", test))]
@@ -353,7 +492,7 @@ impl<'a, T: CoordNum> GeometryCollection<T> {
 mod tests {
     use alloc::vec;
 
-    use crate::{wkt, GeometryCollection, PointZ};
+    use crate::{wkt, Geometry, GeometryCollection, MultiPolygonZ, PointZ};
 
     #[test]
     fn from_vec() {
@@ -368,4 +507,37 @@ mod tests {
         let empty_2 = wkt! { GEOMETRYCOLLECTION EMPTY };
         assert_eq!(empty, empty_2);
     }
+
+    #[test]
+    fn push_and_extend() {
+        let mut gc = GeometryCollection::<i32>::empty();
+        gc.push(PointZ::new(1, 2, 3));
+        gc.extend([wkt! { LINESTRING Z (0 0 0,1 1 1) }]);
+        gc.extend(vec![PointZ::new(4, 5, 6)]);
+        assert_eq!(gc.len(), 3);
+        assert_eq!(gc[1], wkt! { LINESTRING Z (0 0 0,1 1 1) }.into());
+        assert_eq!(gc[2], PointZ::new(4, 5, 6).into());
+    }
+
+    #[test]
+    fn typed_members() {
+        let gc = GeometryCollection::<i32>::from(vec![
+            Geometry::PointZ(PointZ::new(1, 2, 3)),
+            Geometry::Point(geo_types::Point::new(1, 2)),
+            Geometry::LineStringZ(wkt! { LINESTRING Z (0 0 0,1 1 1) }),
+            Geometry::PolygonZ(wkt! { POLYGON Z ((0 0 0,2 0 0,1 2 0,0 0 0)) }),
+            Geometry::PointZ(PointZ::new(4, 5, 6)),
+        ]);
+        assert_eq!(gc.points().count(), 2);
+        assert_eq!(
+            gc.line_strings().collect::<alloc::vec::Vec<_>>(),
+            [&wkt! { LINESTRING Z (0 0 0,1 1 1) }]
+        );
+        assert_eq!(gc.polygons().count(), 1);
+        assert_eq!(
+            gc.extract::<PointZ<i32>>(),
+            vec![PointZ::new(1, 2, 3), PointZ::new(4, 5, 6)]
+        );
+        assert!(gc.extract::<MultiPolygonZ<i32>>().is_empty());
+    }
 }
@@ -4,6 +4,9 @@ use alloc::vec::Vec;
 use core::iter::FromIterator;
 use core::ops::{Index, IndexMut};
 
+#[cfg(feature = "multithreading")]
+use rayon::prelude::*;
+
 /// An ordered collection of [`Coord`]s, representing a path between locations.
 /// To be valid, a `LineString` must be empty, or have two or more coords.
 ///
@@ -131,6 +134,11 @@ use core::ops::{Index, IndexMut};
 
 #[derive(Eq, PartialEq, Clone, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub struct LineStringZ<T: CoordNum = f64>(pub Vec<CoordZ<T>>);
 
 /// A [`Point`] iterator returned by the `points` method
@@ -202,12 +210,12 @@ impl<T: CoordNum> LineStringZ<T> {
 
     /// Return an iterator yielding the coordinates of a [`LineString`] as [`Point`]s
     #[deprecated(note = "Use points() instead")]
-    pub fn points_iter(&self) -> PointsIter<T> {
+    pub fn points_iter(&self) -> PointsIter<'_, T> {
         PointsIter(self.0.iter())
     }
 
     /// Return an iterator yielding the coordinates of a [`LineString`] as [`Point`]s
-    pub fn points(&self) -> PointsIter<T> {
+    pub fn points(&self) -> PointsIter<'_, T> {
         PointsIter(self.0.iter())
     }
 
@@ -221,6 +229,42 @@ impl<T: CoordNum> LineStringZ<T> {
         self.0.iter_mut()
     }
 
+    /// Add a coordinate to the end of the [`LineString`]
+    pub fn push(&mut self, coord: impl Into<CoordZ<T>>) {
+        self.0.push(coord.into());
+    }
+
+    /// Add a coordinate to the ring, keeping it [closed](Self::close): the
+    /// coordinate goes before the closing one, which is added if the ring isn't
+    /// closed yet.
+    ///
+    /// This builds up the rings of a [`PolygonZ`](crate::PolygonZ) one coordinate
+    /// at a time, where [`push`](Self::push) would leave the new coordinate after
+    /// the closing one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{LineStringZ, PolygonZ};
+    ///
+    /// let mut polygon = PolygonZ::new(LineStringZ::from(vec![(0., 0., 0.), (4., 0., 0.)]), vec![]);
+    /// polygon.exterior_mut(|exterior| exterior.push_to_ring((4., 4., 1.)));
+    ///
+    /// assert_eq!(
+    ///     polygon.exterior(),
+    ///     &LineStringZ::from(vec![(0., 0., 0.), (4., 0., 0.), (4., 4., 1.), (0., 0., 0.)])
+    /// );
+    /// ```
+    pub fn push_to_ring(&mut self, coord: impl Into<CoordZ<T>>) {
+        let coord = coord.into();
+        if self.0.len() > 1 && self.is_closed() {
+            self.0.insert(self.0.len() - 1, coord);
+        } else {
+            self.0.push(coord);
+            self.close();
+        }
+    }
+
     /// Return the coordinates of a [`LineString`] as a [`Vec`] of [`Point`]s
     pub fn into_points(self) -> Vec<PointZ<T>> {
         self.0.into_iter().map(PointZ::from).collect()
@@ -418,6 +462,42 @@ impl<'a, T: CoordNum> IntoIterator for &'a mut LineStringZ<T> {
     }
 }
 
+#[cfg(feature = "multithreading")]
+impl<T: CoordNum + Send> IntoParallelIterator for LineStringZ<T> {
+    type Item = CoordZ<T>;
+    type Iter = rayon::vec::IntoIter<CoordZ<T>>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.0.into_par_iter()
+    }
+}
+
+#[cfg(feature = "multithreading")]
+impl<'a, T: CoordNum + Sync> IntoParallelIterator for &'a LineStringZ<T> {
+    type Item = &'a CoordZ<T>;
+    type Iter = rayon::slice::Iter<'a, CoordZ<T>>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.0.par_iter()
+    }
+}
+
+#[cfg(feature = "multithreading")]
+impl<'a, T: CoordNum + Send + Sync> IntoParallelIterator for &'a mut LineStringZ<T> {
+    type Item = &'a mut CoordZ<T>;
+    type Iter = rayon::slice::IterMut<'a, CoordZ<T>>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.0.par_iter_mut()
+    }
+}
+
+impl<T: CoordNum, IC: Into<CoordZ<T>>> Extend<IC> for LineStringZ<T> {
+    fn extend<I: IntoIterator<Item = IC>>(&mut self, iter: I) {
+        self.0.extend(iter.into_iter().map(|c| c.into()));
+    }
+}
+
 impl<T: CoordNum> Index<usize> for LineStringZ<T> {
     type Output = CoordZ<T>;
 
@@ -604,6 +684,15 @@ mod test {
     use crate::{coordZ, wkt};
     use approx::{AbsDiffEq, RelativeEq};
 
+    #[cfg(feature = "multithreading")]
+    #[test]
+    fn test_par_iter() {
+        let mut ls: LineStringZ<i32> = wkt! { LINESTRING Z (0 0 0,2 0 1,1 2 3) };
+        ls.par_iter_mut().for_each(|c| c.z += 1);
+        assert_eq!(ls.par_iter().map(|c| c.z).sum::<i32>(), 7);
+        assert_eq!(ls.into_par_iter().count(), 3);
+    }
+
     #[test]
     fn test_exact_size() {
         // see https://github.com/georust/geo/issues/762
@@ -698,4 +787,25 @@ mod test {
         let empty_2 = wkt! { LINESTRING Z EMPTY };
         assert_eq!(empty, empty_2);
     }
+
+    #[test]
+    fn push_and_extend() {
+        let mut line_string = LineStringZ::<i32>::empty();
+        line_string.push((0, 0, 0));
+        line_string.extend([coordZ! { x: 2, y: 0, z: 1 }, coordZ! { x: 2, y: 2, z: 2 }]);
+        assert_eq!(line_string, wkt! { LINESTRING Z (0 0 0,2 0 1,2 2 2) });
+
+        let mut ring = LineStringZ::<i32>::empty();
+        for coord in [(0, 0, 0), (2, 0, 1), (2, 2, 2)] {
+            ring.push_to_ring(coord);
+            assert!(ring.is_closed());
+        }
+        assert_eq!(ring, wkt! { LINESTRING Z (0 0 0,2 0 1,2 2 2,0 0 0) });
+
+        line_string.push_to_ring((0, 2, 3));
+        assert_eq!(
+            line_string,
+            wkt! { LINESTRING Z (0 0 0,2 0 1,2 2 2,0 2 3,0 0 0) }
+        );
+    }
 }
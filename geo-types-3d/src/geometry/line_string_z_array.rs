@@ -0,0 +1,258 @@
+use crate::{CoordNum, CoordZ, Error, LineStringZ, LineStringZSlice, LineZ};
+use core::convert::TryFrom;
+use core::hash::{Hash, Hasher};
+use core::ops::{Index, IndexMut};
+
+/// A line string holding at most `N` coordinates inline, without allocating.
+///
+/// Useful on hot paths and in embedded code where the number of vertices is small
+/// and bounded, such as the rings of building footprints or the segments of a
+/// sensor track. Converts to a [`LineStringZ`] with [`From`], and from one with
+/// [`TryFrom`]. The algorithm traits are implemented for it directly; for anything
+/// else, borrow it as a [`LineStringZSlice`] with [`as_view`](Self::as_view).
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{coordZ, LineStringZ, LineStringZArray};
+///
+/// let mut ring: LineStringZArray<f64, 5> = LineStringZArray::new();
+/// ring.try_push(coordZ! { x: 0., y: 0., z: 2. }).unwrap();
+/// ring.try_push(coordZ! { x: 1., y: 0., z: 2. }).unwrap();
+/// ring.try_push(coordZ! { x: 1., y: 1., z: 2. }).unwrap();
+/// ring.try_close().unwrap();
+/// assert!(ring.is_closed());
+/// assert_eq!(ring.len(), 4);
+///
+/// let heap: LineStringZ = ring.into();
+/// assert_eq!(heap.0.len(), 4);
+/// ```
+#[derive(Clone, Copy)]
+pub struct LineStringZArray<T: CoordNum, const N: usize> {
+    coords: [CoordZ<T>; N],
+    len: usize,
+}
+
+impl<T: CoordNum, const N: usize> LineStringZArray<T, N> {
+    /// An empty line string.
+    pub fn new() -> Self {
+        Self {
+            coords: [CoordZ::zero(); N],
+            len: 0,
+        }
+    }
+
+    /// The maximum number of coordinates, `N`.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    pub fn as_slice(&self) -> &[CoordZ<T>] {
+        &self.coords[..self.len]
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [CoordZ<T>] {
+        &mut self.coords[..self.len]
+    }
+
+    /// Borrows the coordinates as a [`LineStringZSlice`].
+    pub fn as_view(&self) -> LineStringZSlice<'_, T> {
+        LineStringZSlice(self.as_slice())
+    }
+
+    /// Appends a coordinate, failing with [`Error::CapacityExceeded`] if the line
+    /// string is full.
+    pub fn try_push(&mut self, coord: CoordZ<T>) -> Result<(), Error> {
+        if self.is_full() {
+            return Err(Error::CapacityExceeded { capacity: N });
+        }
+        self.coords[self.len] = coord;
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<CoordZ<T>> {
+        self.len = self.len.checked_sub(1)?;
+        Some(self.coords[self.len])
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Whether the first and last coordinates are equal. Empty line strings are closed.
+    pub fn is_closed(&self) -> bool {
+        self.as_slice().first() == self.as_slice().last()
+    }
+
+    /// Closes the line string by repeating its first coordinate, as
+    /// [`LineStringZ::close`] does. Fails with [`Error::CapacityExceeded`] if there
+    /// is no room for it.
+    pub fn try_close(&mut self) -> Result<(), Error> {
+        match self.as_slice().first() {
+            Some(&first) if !self.is_closed() => self.try_push(first),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn coords(&self) -> impl DoubleEndedIterator<Item = &CoordZ<T>> + ExactSizeIterator {
+        self.as_slice().iter()
+    }
+
+    /// Return an iterator yielding one [`LineZ`] for each line segment.
+    pub fn lines(&self) -> impl ExactSizeIterator<Item = LineZ<T>> + '_ {
+        self.as_view().lines()
+    }
+
+    pub fn to_line_string(&self) -> LineStringZ<T> {
+        self.as_view().to_line_string()
+    }
+}
+
+impl<T: CoordNum, const N: usize> Default for LineStringZArray<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: CoordNum, const N: usize> From<[CoordZ<T>; N]> for LineStringZArray<T, N> {
+    fn from(coords: [CoordZ<T>; N]) -> Self {
+        Self { coords, len: N }
+    }
+}
+
+impl<T: CoordNum, const N: usize> TryFrom<&[CoordZ<T>]> for LineStringZArray<T, N> {
+    type Error = Error;
+
+    /// Fails with [`Error::CapacityExceeded`] if there are more than `N` coordinates.
+    fn try_from(coords: &[CoordZ<T>]) -> Result<Self, Error> {
+        if coords.len() > N {
+            return Err(Error::CapacityExceeded { capacity: N });
+        }
+        let mut array = Self::new();
+        array.coords[..coords.len()].copy_from_slice(coords);
+        array.len = coords.len();
+        Ok(array)
+    }
+}
+
+impl<T: CoordNum, const N: usize> TryFrom<&LineStringZ<T>> for LineStringZArray<T, N> {
+    type Error = Error;
+
+    fn try_from(line_string: &LineStringZ<T>) -> Result<Self, Error> {
+        Self::try_from(line_string.0.as_slice())
+    }
+}
+
+impl<T: CoordNum, const N: usize> From<LineStringZArray<T, N>> for LineStringZ<T> {
+    fn from(array: LineStringZArray<T, N>) -> Self {
+        array.to_line_string()
+    }
+}
+
+impl<T: CoordNum, const N: usize> Index<usize> for LineStringZArray<T, N> {
+    type Output = CoordZ<T>;
+
+    fn index(&self, index: usize) -> &CoordZ<T> {
+        &self.as_slice()[index]
+    }
+}
+
+impl<T: CoordNum, const N: usize> IndexMut<usize> for LineStringZArray<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut CoordZ<T> {
+        &mut self.as_mut_slice()[index]
+    }
+}
+
+impl<'a, T: CoordNum, const N: usize> IntoIterator for &'a LineStringZArray<T, N> {
+    type Item = &'a CoordZ<T>;
+    type IntoIter = ::core::slice::Iter<'a, CoordZ<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+// Only the coordinates in use take part in comparisons, hashing and debug output.
+
+impl<T: CoordNum, const N: usize> PartialEq for LineStringZArray<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: CoordNum + Eq, const N: usize> Eq for LineStringZArray<T, N> {}
+
+impl<T: CoordNum + Hash, const N: usize> Hash for LineStringZArray<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<T: CoordNum, const N: usize> core::fmt::Debug for LineStringZArray<T, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::{BoundingCube, Clip, MapCoordsZ, MapCoordsZInPlace};
+    use crate::{line_string_z, Cube};
+
+    #[test]
+    fn fixed_capacity() {
+        let heap = line_string_z![
+            (x: 0., y: 0., z: 0.),
+            (x: 4., y: 0., z: 4.),
+            (x: 4., y: 4., z: 8.),
+        ];
+        let mut array = LineStringZArray::<f64, 4>::try_from(&heap).unwrap();
+        assert!(LineStringZArray::<f64, 2>::try_from(&heap).is_err());
+        assert_eq!(array.to_line_string(), heap);
+        assert_eq!(array.bounding_cube(), heap.bounding_cube());
+        let cube = Cube::new((1., -1., -1.), (3., 1., 9.));
+        assert_eq!(array.clip(&cube), heap.clip(&cube));
+
+        array.try_close().unwrap();
+        assert!(array.is_full());
+        assert!(matches!(
+            array.try_push(CoordZ::zero()),
+            Err(Error::CapacityExceeded { capacity: 4 })
+        ));
+
+        let doubled: LineStringZArray<f64, 4> = array.map_coords(|c| c * 2.);
+        array.map_coords_in_place(|c| c * 2.);
+        assert_eq!(array, doubled);
+        assert_eq!(
+            array[2],
+            CoordZ {
+                x: 8.,
+                y: 8.,
+                z: 16.
+            }
+        );
+
+        // stale coordinates beyond the length are ignored
+        let mut shorter = array;
+        shorter.pop();
+        shorter.pop();
+        let mut rebuilt = LineStringZArray::<f64, 4>::new();
+        rebuilt.try_push(array[0]).unwrap();
+        rebuilt.try_push(array[1]).unwrap();
+        assert_eq!(shorter, rebuilt);
+    }
+}
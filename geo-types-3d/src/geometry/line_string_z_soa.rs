@@ -0,0 +1,249 @@
+use crate::{CoordNum, CoordZ, Error, LineStringZ};
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+
+/// A [`LineStringZ`] stored as three separate columns of x, y and z ordinates.
+///
+/// This "structure of arrays" layout keeps each ordinate contiguous in memory, so
+/// bulk transforms over very large line strings touch fewer cache lines and can be
+/// vectorised by the compiler. Convert to and from the usual [`LineStringZ`] with
+/// [`From`].
+///
+/// The three columns always have the same length.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{line_string_z, LineStringZ, LineStringZSoA};
+///
+/// let line_string = line_string_z![(x: 0., y: 0., z: 0.), (x: 1., y: 2., z: 3.)];
+/// let mut soa = LineStringZSoA::from(&line_string);
+/// assert_eq!(soa.zs(), &[0., 3.]);
+///
+/// soa.translate(10., 0., 100.);
+/// let moved: LineStringZ = soa.into();
+/// assert_eq!(moved, line_string_z![(x: 10., y: 0., z: 100.), (x: 11., y: 2., z: 103.)]);
+/// ```
+#[derive(Eq, PartialEq, Clone, Hash)]
+pub struct LineStringZSoA<T: CoordNum = f64> {
+    xs: Vec<T>,
+    ys: Vec<T>,
+    zs: Vec<T>,
+}
+
+impl<T: CoordNum> LineStringZSoA<T> {
+    pub fn new() -> Self {
+        Self {
+            xs: Vec::new(),
+            ys: Vec::new(),
+            zs: Vec::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            xs: Vec::with_capacity(capacity),
+            ys: Vec::with_capacity(capacity),
+            zs: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Creates a line string from its columns of ordinates.
+    ///
+    /// Fails with [`Error::MismatchedLength`] unless all three have the same length.
+    pub fn from_columns(xs: Vec<T>, ys: Vec<T>, zs: Vec<T>) -> Result<Self, Error> {
+        for found in [ys.len(), zs.len()] {
+            if found != xs.len() {
+                return Err(Error::MismatchedLength {
+                    expected: xs.len(),
+                    found,
+                });
+            }
+        }
+        Ok(Self { xs, ys, zs })
+    }
+
+    /// Returns the columns of x, y and z ordinates.
+    pub fn into_columns(self) -> (Vec<T>, Vec<T>, Vec<T>) {
+        (self.xs, self.ys, self.zs)
+    }
+
+    /// The number of coordinates.
+    pub fn len(&self) -> usize {
+        self.xs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.xs.is_empty()
+    }
+
+    pub fn xs(&self) -> &[T] {
+        &self.xs
+    }
+
+    pub fn ys(&self) -> &[T] {
+        &self.ys
+    }
+
+    pub fn zs(&self) -> &[T] {
+        &self.zs
+    }
+
+    /// Mutable access to the x, y and z columns at once.
+    ///
+    /// The columns are slices, so their values can change but not their length.
+    pub fn columns_mut(&mut self) -> (&mut [T], &mut [T], &mut [T]) {
+        (&mut self.xs, &mut self.ys, &mut self.zs)
+    }
+
+    pub fn get(&self, index: usize) -> Option<CoordZ<T>> {
+        Some(CoordZ {
+            x: *self.xs.get(index)?,
+            y: self.ys[index],
+            z: self.zs[index],
+        })
+    }
+
+    pub fn push(&mut self, coord: CoordZ<T>) {
+        self.xs.push(coord.x);
+        self.ys.push(coord.y);
+        self.zs.push(coord.z);
+    }
+
+    /// Iterates over the coordinates, assembling each from the three columns.
+    pub fn coords(&self) -> impl ExactSizeIterator<Item = CoordZ<T>> + DoubleEndedIterator + '_ {
+        self.xs
+            .iter()
+            .zip(&self.ys)
+            .zip(&self.zs)
+            .map(|((&x, &y), &z)| CoordZ { x, y, z })
+    }
+
+    /// Moves every coordinate by `(dx, dy, dz)`.
+    pub fn translate(&mut self, dx: T, dy: T, dz: T) {
+        add_to(&mut self.xs, dx);
+        add_to(&mut self.ys, dy);
+        add_to(&mut self.zs, dz);
+    }
+
+    /// Scales every coordinate by `(sx, sy, sz)` about the origin.
+    pub fn scale(&mut self, sx: T, sy: T, sz: T) {
+        multiply(&mut self.xs, sx);
+        multiply(&mut self.ys, sy);
+        multiply(&mut self.zs, sz);
+    }
+
+    /// Applies the affine transform `p' = M p + t`, given as the rows
+    /// `[m0, m1, m2, t]` of a 3 × 4 matrix.
+    pub fn affine_transform(&mut self, matrix: [[T; 4]; 3]) {
+        let [[a, b, c, xoff], [d, e, f, yoff], [g, h, i, zoff]] = matrix;
+        for ((x, y), z) in self.xs.iter_mut().zip(&mut self.ys).zip(&mut self.zs) {
+            let (x0, y0, z0) = (*x, *y, *z);
+            *x = a * x0 + b * y0 + c * z0 + xoff;
+            *y = d * x0 + e * y0 + f * z0 + yoff;
+            *z = g * x0 + h * y0 + i * z0 + zoff;
+        }
+    }
+}
+
+impl<T: CoordNum> Default for LineStringZSoA<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn add_to<T: CoordNum>(column: &mut [T], offset: T) {
+    for value in column {
+        *value = *value + offset;
+    }
+}
+
+fn multiply<T: CoordNum>(column: &mut [T], factor: T) {
+    for value in column {
+        *value = *value * factor;
+    }
+}
+
+impl<T: CoordNum> From<&LineStringZ<T>> for LineStringZSoA<T> {
+    fn from(line_string: &LineStringZ<T>) -> Self {
+        line_string.0.iter().copied().collect()
+    }
+}
+
+impl<T: CoordNum> From<LineStringZ<T>> for LineStringZSoA<T> {
+    fn from(line_string: LineStringZ<T>) -> Self {
+        Self::from(&line_string)
+    }
+}
+
+impl<T: CoordNum> From<&LineStringZSoA<T>> for LineStringZ<T> {
+    fn from(soa: &LineStringZSoA<T>) -> Self {
+        LineStringZ(soa.coords().collect())
+    }
+}
+
+impl<T: CoordNum> From<LineStringZSoA<T>> for LineStringZ<T> {
+    fn from(soa: LineStringZSoA<T>) -> Self {
+        Self::from(&soa)
+    }
+}
+
+impl<T: CoordNum, IC: Into<CoordZ<T>>> FromIterator<IC> for LineStringZSoA<T> {
+    fn from_iter<I: IntoIterator<Item = IC>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut soa = Self::with_capacity(iter.size_hint().0);
+        for coord in iter {
+            soa.push(coord.into());
+        }
+        soa
+    }
+}
+
+impl<T: CoordNum, IC: Into<CoordZ<T>>> Extend<IC> for LineStringZSoA<T> {
+    fn extend<I: IntoIterator<Item = IC>>(&mut self, iter: I) {
+        for coord in iter {
+            self.push(coord.into());
+        }
+    }
+}
+
+impl<T: CoordNum> core::fmt::Debug for LineStringZSoA<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.coords()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn round_trip_and_transforms() {
+        let line_string: LineStringZ = (0..100)
+            .map(|i| (i as f64, (i * 2) as f64, -(i as f64)))
+            .collect();
+        let mut soa = LineStringZSoA::from(&line_string);
+        assert_eq!(soa.len(), 100);
+        assert_eq!(soa.get(7), Some(line_string[7]));
+        assert_eq!(soa.get(100), None);
+        assert_eq!(LineStringZ::from(&soa), line_string);
+
+        soa.scale(2., 1., 1.);
+        soa.translate(0., 0., 1.);
+        // a quarter turn about z
+        soa.affine_transform([[0., -1., 0., 0.], [1., 0., 0., 0.], [0., 0., 1., 0.]]);
+        let c = soa.get(3).unwrap();
+        assert_eq!((c.x, c.y, c.z), (-6., 6., -2.));
+
+        let (xs, ys, zs) = soa.into_columns();
+        assert!(LineStringZSoA::from_columns(xs.clone(), ys.clone(), zs).is_ok());
+        assert!(matches!(
+            LineStringZSoA::from_columns(xs, ys, vec![0.]),
+            Err(Error::MismatchedLength {
+                expected: 100,
+                found: 1
+            })
+        ));
+    }
+}
@@ -0,0 +1,179 @@
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+use core::ops::{Index, IndexMut};
+
+use crate::{CoordFloat, CoordNum, CoordZ, CoordZM, LineStringZ, PointZ};
+
+/// A line string whose coordinates carry a measure. See [`CoordZM`].
+///
+/// Measured line strings are the basis of linear referencing: events along a road
+/// or pipeline are located by measure rather than by position, and
+/// [`point_at_m`](Self::point_at_m) finds the position of a measure.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{wkt, PointZ};
+///
+/// let pipeline = wkt! { LINESTRING ZM(0. 0. 10. 0.,100. 0. 20. 100.,100. 50. 20. 150.) };
+/// assert_eq!(pipeline.point_at_m(50.), Some(PointZ::new(50., 0., 15.)));
+/// assert_eq!(pipeline.point_at_m(125.), Some(PointZ::new(100., 25., 20.)));
+/// assert_eq!(pipeline.point_at_m(200.), None);
+/// ```
+#[derive(Eq, PartialEq, Clone, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct LineStringZM<T: CoordNum = f64>(pub Vec<CoordZM<T>>);
+
+impl<T: CoordNum> LineStringZM<T> {
+    pub fn new(value: Vec<CoordZM<T>>) -> Self {
+        Self(value)
+    }
+
+    pub fn empty() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn coords(&self) -> impl DoubleEndedIterator<Item = &CoordZM<T>> + ExactSizeIterator {
+        self.0.iter()
+    }
+
+    pub fn coords_mut(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = &mut CoordZM<T>> + ExactSizeIterator {
+        self.0.iter_mut()
+    }
+
+    pub fn into_inner(self) -> Vec<CoordZM<T>> {
+        self.0
+    }
+
+    /// Whether the first and last coordinates are equal, measures included. Empty
+    /// line strings are closed.
+    pub fn is_closed(&self) -> bool {
+        self.0.first() == self.0.last()
+    }
+
+    /// The positions of this line string, without their measures.
+    pub fn xyz(&self) -> LineStringZ<T> {
+        self.0.iter().map(CoordZM::xyz).collect()
+    }
+}
+
+impl<T: CoordFloat> LineStringZM<T> {
+    /// The position at measure `m`, interpolated linearly within the first segment
+    /// whose measures span `m`. Returns `None` if no segment does.
+    pub fn point_at_m(&self, m: T) -> Option<PointZ<T>> {
+        if let [only] = self.0.as_slice() {
+            return (only.m == m).then(|| PointZ(only.xyz()));
+        }
+        self.0.windows(2).find_map(|pair| {
+            let (a, b) = (pair[0], pair[1]);
+            if m < a.m.min(b.m) || m > a.m.max(b.m) {
+                return None;
+            }
+            let t = if a.m == b.m {
+                T::zero()
+            } else {
+                (m - a.m) / (b.m - a.m)
+            };
+            let (a, b): (CoordZ<T>, CoordZ<T>) = (a.xyz(), b.xyz());
+            Some(PointZ(a + (b - a) * t))
+        })
+    }
+}
+
+impl<T: CoordNum, IC: Into<CoordZM<T>>> From<Vec<IC>> for LineStringZM<T> {
+    fn from(v: Vec<IC>) -> Self {
+        Self(v.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: CoordNum, IC: Into<CoordZM<T>>> FromIterator<IC> for LineStringZM<T> {
+    fn from_iter<I: IntoIterator<Item = IC>>(iter: I) -> Self {
+        Self(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: CoordNum> From<LineStringZM<T>> for LineStringZ<T> {
+    /// Drops the measures.
+    fn from(line_string: LineStringZM<T>) -> Self {
+        line_string.xyz()
+    }
+}
+
+impl<T: CoordNum> IntoIterator for LineStringZM<T> {
+    type Item = CoordZM<T>;
+    type IntoIter = ::alloc::vec::IntoIter<CoordZM<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T: CoordNum> IntoIterator for &'a LineStringZM<T> {
+    type Item = &'a CoordZM<T>;
+    type IntoIter = ::core::slice::Iter<'a, CoordZM<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T: CoordNum> Index<usize> for LineStringZM<T> {
+    type Output = CoordZM<T>;
+
+    fn index(&self, index: usize) -> &CoordZM<T> {
+        self.0.index(index)
+    }
+}
+
+impl<T: CoordNum> IndexMut<usize> for LineStringZM<T> {
+    fn index_mut(&mut self, index: usize) -> &mut CoordZM<T> {
+        self.0.index_mut(index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{coordZM, wkt, PointZM};
+    use alloc::{format, vec};
+
+    #[test]
+    fn measures() {
+        let line_string = wkt! { LINESTRING ZM(0. 0. 0. 10.,4. 0. 0. 6.,4. 3. 0. 6.) };
+        assert_eq!(line_string.0[1], coordZM! { x: 4., y: 0., z: 0., m: 6. });
+        // measures may decrease, and repeat
+        assert_eq!(line_string.point_at_m(8.), Some(PointZ::new(2., 0., 0.)));
+        assert_eq!(line_string.point_at_m(6.), Some(PointZ::new(4., 0., 0.)));
+        assert_eq!(line_string.point_at_m(11.), None);
+        assert_eq!(LineStringZM::<f64>::empty().point_at_m(0.), None);
+        assert_eq!(
+            LineStringZM(vec![coordZM! { x: 1., y: 2., z: 3., m: 4. }]).point_at_m(4.),
+            Some(PointZ::new(1., 2., 3.))
+        );
+
+        let flat: LineStringZ = line_string.clone().into();
+        assert_eq!(flat.0.len(), 3);
+        let points: LineStringZM = flat
+            .0
+            .iter()
+            .zip(line_string.coords())
+            .map(|(xyz, zm)| PointZM::from(xyz.with_m(zm.m)))
+            .collect();
+        assert_eq!(points, line_string);
+        assert_eq!(
+            format!("{:?}", wkt! { POINT ZM(1. 2. 3. 4.) }),
+            "POINT ZM(1.0 2.0 3.0 4.0)"
+        );
+        assert_eq!(
+            format!("{line_string:?}"),
+            "LINESTRING ZM(0.0 0.0 0.0 10.0,4.0 0.0 0.0 6.0,4.0 3.0 0.0 6.0)"
+        );
+    }
+}
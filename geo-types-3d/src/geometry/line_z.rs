@@ -1,4 +1,4 @@
-use crate::{CoordZ, CoordNum, PointZ};
+use crate::{CoordFloat, CoordZ, CoordNum, PointZ};
 
 /// A line segment made up of exactly two
 /// [`Coord`]s.
@@ -9,6 +9,11 @@ use crate::{CoordZ, CoordNum, PointZ};
 /// `LineString` with the two end points.
 #[derive(Eq, PartialEq, Clone, Copy, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub struct LineZ<T: CoordNum = f64> {
     pub start: CoordZ<T>,
     pub end: CoordZ<T>,
@@ -80,6 +85,22 @@ impl<T: CoordNum> LineZ<T> {
         self.delta().y
     }
 
+    /// Calculate the difference in ‘z’ components (Δz).
+    ///
+    /// Equivalent to:
+    ///
+    /// ```rust
+    /// # use geo_types_3d::LineZ;
+    /// # let line = LineZ::new((4., -12., 1.), (0., 9., 6.));
+    /// # assert_eq!(
+    /// #     line.dz(),
+    /// line.end.z - line.start.z
+    /// # );
+    /// ```
+    pub fn dz(&self) -> T {
+        self.delta().z
+    }
+
     /// Calculate the slope (Δy/Δx).
     ///
     /// Equivalent to:
@@ -153,6 +174,92 @@ impl<T: CoordNum> LineZ<T> {
     }
 }
 
+impl<T: CoordFloat> LineZ<T> {
+    /// The 3D length of the line, the same as [`LengthZ::length`].
+    ///
+    /// [`LengthZ::length`]: crate::algorithm::LengthZ::length
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::LineZ;
+    ///
+    /// let line = LineZ::new((1., 1., 1.), (3., 5., 5.));
+    /// assert_eq!(line.length(), 6.);
+    /// ```
+    pub fn length(&self) -> T {
+        let (dx, dy, dz) = self.delta().x_y_z();
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
+
+    /// The unit vector from the start of the line towards its end, or `None` if
+    /// they are the same.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{CoordZ, LineZ};
+    ///
+    /// let line = LineZ::new((1., 1., 1.), (1., 1., -3.));
+    /// assert_eq!(line.direction(), Some(CoordZ { x: 0., y: 0., z: -1. }));
+    /// assert_eq!(LineZ::new((1., 1., 1.), (1., 1., 1.)).direction(), None);
+    /// ```
+    pub fn direction(&self) -> Option<CoordZ<T>> {
+        let length = self.length();
+        (length > T::zero()).then(|| self.delta() / length)
+    }
+
+    /// The point halfway along the line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{LineZ, PointZ};
+    ///
+    /// let line = LineZ::new((0., 2., 4.), (2., 4., 8.));
+    /// assert_eq!(line.midpoint(), PointZ::new(1., 3., 6.));
+    /// ```
+    pub fn midpoint(&self) -> PointZ<T> {
+        self.point_at(T::from(0.5).unwrap())
+    }
+
+    /// The point a fraction `t` of the way along the line, from its start at
+    /// `t = 0` to its end at `t = 1`. Other values of `t` give points on the
+    /// extension of the line beyond its ends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{LineZ, PointZ};
+    ///
+    /// let line = LineZ::new((0., 0., 10.), (4., 0., 30.));
+    /// assert_eq!(line.point_at(0.25), PointZ::new(1., 0., 15.));
+    /// assert_eq!(line.point_at(1.), line.end_point());
+    /// ```
+    pub fn point_at(&self, t: T) -> PointZ<T> {
+        PointZ::from(self.start.lerp(self.end, t))
+    }
+
+    /// The angle of the line above the horizontal plane, in radians, from `-π/2`
+    /// going straight down to `π/2` going straight up. A line of no length is
+    /// level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::LineZ;
+    ///
+    /// let line: LineZ = LineZ::new((0., 0., 0.), (3., 4., 5.));
+    /// assert_eq!(line.inclination().to_degrees().round(), 45.);
+    /// let down: LineZ = LineZ::new((0., 0., 5.), (0., 0., 0.));
+    /// assert_eq!(down.inclination(), -std::f64::consts::FRAC_PI_2);
+    /// ```
+    pub fn inclination(&self) -> T {
+        let (dx, dy, dz) = self.delta().x_y_z();
+        dz.atan2((dx * dx + dy * dy).sqrt())
+    }
+}
+
 impl<T: CoordNum> From<[(T, T, T); 2]> for LineZ<T> {
     fn from(coord: [(T, T, T); 2]) -> Self {
         LineZ::new(coord[0], coord[1])
@@ -293,7 +400,7 @@ impl_rstar_line!(rstar_0_12);
 mod test {
     use super::*;
     use crate::{coordZ, pointZ};
-    use approx::{AbsDiffEq, RelativeEq};
+    use approx::{assert_relative_eq, AbsDiffEq, RelativeEq};
 
     #[test]
     fn test_abs_diff_eq() {
@@ -374,4 +481,24 @@ mod test {
         assert!(line.relative_eq(&line_start_y, 1e-2, 1e-2));
         assert!(line.relative_ne(&line_start_y, 1e-12, 1e-12));
     }
+
+    #[test]
+    fn test_3d_accessors() {
+        let line = LineZ::new(coordZ! { x: 1., y: 2., z: 3. }, coordZ! { x: 3., y: 1., z: 1. });
+        assert_eq!((line.dx(), line.dy(), line.dz()), (2., -1., -2.));
+        assert_eq!(line.length(), 3.);
+        assert_eq!(
+            line.direction(),
+            Some(coordZ! { x: 2. / 3., y: -1. / 3., z: -2. / 3. })
+        );
+        assert_eq!(line.midpoint(), pointZ! { x: 2., y: 1.5, z: 2. });
+        assert_eq!(line.point_at(0.), line.start_point());
+        assert_eq!(line.point_at(2.), pointZ! { x: 5., y: 0., z: -1. });
+        assert_relative_eq!(line.inclination(), (-2f64).atan2(5f64.sqrt()));
+
+        let level = LineZ::new(coordZ! { x: 1., y: 1., z: 1. }, coordZ! { x: 1., y: 1., z: 1. });
+        assert_eq!(level.length(), 0.);
+        assert_eq!(level.direction(), None);
+        assert_eq!(level.inclination(), 0.);
+    }
 }
@@ -1,25 +1,57 @@
+#[cfg(feature = "rkyv")]
+mod archive;
+pub(crate) mod circular_string_z;
+pub(crate) mod compound_curve_z;
 pub(crate) mod coord_z;
+pub(crate) mod coord_zm;
+pub(crate) mod cube;
+pub(crate) mod dimension;
 pub(crate) mod geometry_collection;
 pub(crate) mod line_z;
 pub(crate) mod line_string_z;
+pub(crate) mod line_string_z_array;
+pub(crate) mod line_string_z_soa;
+pub(crate) mod line_string_zm;
 pub(crate) mod multi_line_string_z;
 pub(crate) mod multi_point_z;
 pub(crate) mod multi_polygon_z;
 pub(crate) mod point_z;
+pub(crate) mod point_zm;
 pub(crate) mod polygon;
+pub(crate) mod ellipsoid_z;
+pub(crate) mod frustum;
+pub(crate) mod obb;
+pub(crate) mod plane;
+pub(crate) mod ray;
+#[cfg(feature = "schemars")]
+mod schema;
+pub(crate) mod sphere;
+pub(crate) mod tagged;
+pub(crate) mod tin_z;
+pub(crate) mod trajectory;
 pub(crate) mod triangle;
+pub(crate) mod typed_collection;
+pub(crate) mod views;
+pub mod visitor;
 
 // re-export all the geometry variants:
+pub use circular_string_z::CircularStringZ;
+pub use compound_curve_z::{CompoundCurveZ, CurveSegmentZ};
 #[allow(deprecated)]
 pub use coord_z::{CoordZ, CoordinateZ};
+pub use coord_zm::CoordZM;
 use geo_types::Rect;
 pub use geometry_collection::GeometryCollection;
 pub use line_z::LineZ;
 pub use line_string_z::LineStringZ;
+pub use line_string_z_array::LineStringZArray;
+pub use line_string_z_soa::LineStringZSoA;
+pub use line_string_zm::LineStringZM;
 pub use multi_line_string_z::MultiLineStringZ;
 pub use multi_point_z::MultiPointZ;
 pub use multi_polygon_z::MultiPolygonZ;
 pub use point_z::PointZ;
+pub use point_zm::PointZM;
 pub use polygon::PolygonZ;
 
 
@@ -30,8 +62,21 @@ use geo_types::MultiLineString;
 use geo_types::MultiPoint;
 use geo_types::MultiPolygon;
 use geo_types::Polygon;
-// pub use cube::Cube;
+pub use cube::Cube;
+pub use ellipsoid_z::EllipsoidZ;
+pub use frustum::Frustum;
+pub use obb::Obb;
+pub use plane::Plane;
+pub use ray::Ray;
+pub use sphere::Sphere;
+pub use tagged::{Tagged, TaggedCollection};
+pub use tin_z::TinZ;
+pub use trajectory::{StayPoint, Trajectory};
 pub use triangle::Triangle;
+pub use dimension::{Dimension, HasDimension};
+pub use typed_collection::TypedCollection;
+pub use views::{InteriorsIter, LineStringZSlice, PolygonZRef};
+pub use visitor::{CoordVisitor, GeometryVisitor};
 
 use crate::{CoordNum, Error};
 
@@ -56,23 +101,60 @@ use core::convert::TryFrom;
 ///
 #[derive(Eq, PartialEq, Clone, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub enum Geometry<T: CoordNum = f64> {
-    Point(Point<T>),
+    #[cfg_attr(feature = "schemars", schemars(with = "schema::Point<T>"))]
+    Point(
+        #[cfg_attr(feature = "rkyv", rkyv(with = archive::PointDef<T>))]
+        Point<T>,
+    ),
     PointZ(PointZ<T>),
-    Line(Line<T>),
+    #[cfg_attr(feature = "schemars", schemars(with = "schema::Line<T>"))]
+    Line(
+        #[cfg_attr(feature = "rkyv", rkyv(with = archive::LineDef<T>))]
+        Line<T>,
+    ),
     LineZ(LineZ<T>),
-    LineString(LineString<T>),
+    #[cfg_attr(feature = "schemars", schemars(with = "schema::LineString<T>"))]
+    LineString(
+        #[cfg_attr(feature = "rkyv", rkyv(with = archive::LineStringDef<T>))]
+        LineString<T>,
+    ),
     LineStringZ(LineStringZ<T>),
-    Polygon(Polygon<T>),
+    #[cfg_attr(feature = "schemars", schemars(with = "schema::Polygon<T>"))]
+    Polygon(
+        #[cfg_attr(feature = "rkyv", rkyv(with = archive::PolygonDef<T>))]
+        Polygon<T>,
+    ),
     PolygonZ(PolygonZ<T>),
-    MultiPoint(MultiPoint<T>),
+    #[cfg_attr(feature = "schemars", schemars(with = "schema::MultiPoint<T>"))]
+    MultiPoint(
+        #[cfg_attr(feature = "rkyv", rkyv(with = archive::MultiPointDef<T>))]
+        MultiPoint<T>,
+    ),
     MultiPointZ(MultiPointZ<T>),
-    MultiLineString(MultiLineString<T>),
+    #[cfg_attr(feature = "schemars", schemars(with = "schema::MultiLineString<T>"))]
+    MultiLineString(
+        #[cfg_attr(feature = "rkyv", rkyv(with = archive::MultiLineStringDef<T>))]
+        MultiLineString<T>,
+    ),
     MultiLineStringZ(MultiLineStringZ<T>),
-    MultiPolygon(MultiPolygon<T>),
+    #[cfg_attr(feature = "schemars", schemars(with = "schema::MultiPolygon<T>"))]
+    MultiPolygon(
+        #[cfg_attr(feature = "rkyv", rkyv(with = archive::MultiPolygonDef<T>))]
+        MultiPolygon<T>,
+    ),
     MultiPolygonZ(MultiPolygonZ<T>),
     GeometryCollection(GeometryCollection<T>),
-    Rect(Rect<T>),
+    #[cfg_attr(feature = "schemars", schemars(with = "schema::Rect<T>"))]
+    Rect(
+        #[cfg_attr(feature = "rkyv", rkyv(with = archive::RectDef<T>))]
+        Rect<T>,
+    ),
     // Triangle(Triangle<T>),
 }
 
@@ -268,6 +350,23 @@ macro_rules! try_from_geometry_impl {
             type Error = Error;
 
             fn try_from(geom: Geometry<T>) -> Result<Self, Self::Error> {
+                match geom {
+                    Geometry::$type(g) => Ok(g),
+                    other => Err(Error::MismatchedGeometry {
+                        expected: type_name::<$type<T>>(),
+                        found: inner_type_name(&other)
+                    })
+                }
+            }
+        }
+
+        /// Borrow the inner type of a Geometry enum.
+        ///
+        /// Fails if the enum case does not match the type you are trying to convert it to.
+        impl <'a, T: CoordNum> TryFrom<&'a Geometry<T>> for &'a $type<T> {
+            type Error = Error;
+
+            fn try_from(geom: &'a Geometry<T>) -> Result<Self, Self::Error> {
                 match geom {
                     Geometry::$type(g) => Ok(g),
                     other => Err(Error::MismatchedGeometry {
@@ -284,6 +383,7 @@ macro_rules! try_from_geometry_impl {
 try_from_geometry_impl!(
     PointZ,
     Line,
+    LineZ,
     LineStringZ,
     PolygonZ,
     MultiPointZ,
@@ -295,7 +395,7 @@ try_from_geometry_impl!(
     // Triangle
 );
 
-fn inner_type_name<T>(geometry: Geometry<T>) -> &'static str
+pub(crate) fn inner_type_name<T>(geometry: &Geometry<T>) -> &'static str
 where
     T: CoordNum,
 {
@@ -4,6 +4,7 @@ use alloc::vec;
 use alloc::vec::Vec;
 #[cfg(any(feature = "approx", test))]
 use core::iter::FromIterator;
+use core::ops::{Index, IndexMut};
 #[cfg(feature = "multithreading")]
 use rayon::prelude::*;
 
@@ -36,6 +37,11 @@ use rayon::prelude::*;
 /// of a closed `MultiLineString` is always empty.
 #[derive(Eq, PartialEq, Clone, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub struct MultiLineStringZ<T: CoordNum = f64>(pub Vec<LineStringZ<T>>);
 
 impl<T: CoordNum> MultiLineStringZ<T> {
@@ -115,6 +121,18 @@ impl<'a, T: CoordNum> IntoIterator for &'a mut MultiLineStringZ<T> {
 }
 
 impl<T: CoordNum> MultiLineStringZ<T> {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn push(&mut self, line_string: impl Into<LineStringZ<T>>) {
+        self.0.push(line_string.into());
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &LineStringZ<T>> {
         self.0.iter()
     }
@@ -124,6 +142,26 @@ impl<T: CoordNum> MultiLineStringZ<T> {
     }
 }
 
+impl<T: CoordNum, ILS: Into<LineStringZ<T>>> Extend<ILS> for MultiLineStringZ<T> {
+    fn extend<I: IntoIterator<Item = ILS>>(&mut self, iter: I) {
+        self.0.extend(iter.into_iter().map(|x| x.into()));
+    }
+}
+
+impl<T: CoordNum> Index<usize> for MultiLineStringZ<T> {
+    type Output = LineStringZ<T>;
+
+    fn index(&self, index: usize) -> &LineStringZ<T> {
+        self.0.index(index)
+    }
+}
+
+impl<T: CoordNum> IndexMut<usize> for MultiLineStringZ<T> {
+    fn index_mut(&mut self, index: usize) -> &mut LineStringZ<T> {
+        self.0.index_mut(index)
+    }
+}
+
 #[cfg(feature = "multithreading")]
 impl<T: CoordNum + Send> IntoParallelIterator for MultiLineStringZ<T> {
     type Item = LineStringZ<T>;
@@ -261,10 +299,10 @@ mod test {
     #[test]
     fn test_multithreading_linestring() {
         let multi: MultiLineStringZ<i32> = wkt! {
-            MULTILINESTRING ((0 0 0, 2 0 0, 1 2 3, 0 0 0), (10 10,12 10,11 12,10 10))
+            MULTILINESTRING Z ((0 0 0,2 0 0,1 2 3,0 0 0), (10 10 0,12 10 0,11 12 0,10 10 0))
         };
         let mut multimut: MultiLineStringZ<i32> = wkt! {
-            MULTILINESTRING ((0 0,2 0,1 2,0 0), (10 10,12 10,11 12,10 10))
+            MULTILINESTRING Z ((0 0 0,2 0 0,1 2 0,0 0 0), (10 10 0,12 10 0,11 12 0,10 10 0))
         };
         multi.par_iter().for_each(|_p| ());
         multimut.par_iter_mut().for_each(|_p| ());
@@ -344,4 +382,19 @@ mod test {
         let empty_2 = wkt! { MULTILINESTRING Z EMPTY };
         assert_eq!(empty, empty_2);
     }
+
+    #[test]
+    fn test_index_and_extend() {
+        let mut multi = MultiLineStringZ::<i32>::empty();
+        assert!(multi.is_empty());
+        multi.extend([
+            wkt! { LINESTRING Z (0 0 0,2 0 1) },
+            wkt! { LINESTRING Z (5 5 5,6 6 6,7 7 7) },
+        ]);
+        assert_eq!(multi.len(), 2);
+        assert_eq!(multi[1][2], wkt! { POINT Z (7 7 7) }.0);
+
+        multi[0][1].z = 3;
+        assert_eq!(multi[0], wkt! { LINESTRING Z (0 0 0,2 0 3) });
+    }
 }
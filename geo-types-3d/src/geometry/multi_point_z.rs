@@ -3,6 +3,7 @@ use crate::{CoordNum, PointZ};
 use alloc::vec;
 use alloc::vec::Vec;
 use core::iter::FromIterator;
+use core::ops::{Index, IndexMut};
 #[cfg(feature = "multithreading")]
 use rayon::prelude::*;
 
@@ -31,6 +32,11 @@ use rayon::prelude::*;
 /// ```
 #[derive(Eq, PartialEq, Clone, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub struct MultiPointZ<T: CoordNum = f64>(pub Vec<PointZ<T>>);
 
 impl<T: CoordNum, IP: Into<PointZ<T>>> From<IP> for MultiPointZ<T> {
@@ -133,6 +139,10 @@ impl<T: CoordNum> MultiPointZ<T> {
         self.0.is_empty()
     }
 
+    pub fn push(&mut self, point: impl Into<PointZ<T>>) {
+        self.0.push(point.into());
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &PointZ<T>> {
         self.0.iter()
     }
@@ -140,6 +150,45 @@ impl<T: CoordNum> MultiPointZ<T> {
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut PointZ<T>> {
         self.0.iter_mut()
     }
+
+    /// The mean of the points, or `None` if there are none. With integer
+    /// coordinates the mean is rounded as by integer division.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{MultiPointZ, PointZ};
+    ///
+    /// let points: MultiPointZ = vec![(0., 0., 0.), (2., 4., 6.), (4., 2., 0.)].into();
+    /// assert_eq!(points.mean(), Some(PointZ::new(2., 2., 2.)));
+    /// assert_eq!(MultiPointZ::<f64>::empty().mean(), None);
+    /// ```
+    pub fn mean(&self) -> Option<PointZ<T>> {
+        if self.0.is_empty() {
+            return None;
+        }
+        Some(self.0.iter().sum::<PointZ<T>>() / T::from(self.0.len())?)
+    }
+}
+
+impl<T: CoordNum, IP: Into<PointZ<T>>> Extend<IP> for MultiPointZ<T> {
+    fn extend<I: IntoIterator<Item = IP>>(&mut self, iter: I) {
+        self.0.extend(iter.into_iter().map(|x| x.into()));
+    }
+}
+
+impl<T: CoordNum> Index<usize> for MultiPointZ<T> {
+    type Output = PointZ<T>;
+
+    fn index(&self, index: usize) -> &PointZ<T> {
+        self.0.index(index)
+    }
+}
+
+impl<T: CoordNum> IndexMut<usize> for MultiPointZ<T> {
+    fn index_mut(&mut self, index: usize) -> &mut PointZ<T> {
+        self.0.index_mut(index)
+    }
 }
 
 #[cfg(any(feature = "approx", test))]
@@ -354,4 +403,20 @@ mod test {
         let empty_2 = wkt! { MULTIPOINT Z EMPTY };
         assert_eq!(empty, empty_2);
     }
+
+    #[test]
+    fn test_index() {
+        let mut multi: MultiPointZ<i32> = wkt! { MULTIPOINT Z ((0 0 0), (1 2 3)) };
+        assert_eq!(multi[1], pointZ! { x: 1, y: 2, z: 3 });
+        multi[0].0.z = 5;
+        assert_eq!(multi, wkt! { MULTIPOINT Z ((0 0 5), (1 2 3)) });
+    }
+
+    #[test]
+    fn test_push_and_extend() {
+        let mut multi = MultiPointZ::<i32>::empty();
+        multi.push((0, 0, 0));
+        multi.extend([pointZ! { x: 1, y: 2, z: 3 }, pointZ! { x: 4, y: 5, z: 6 }]);
+        assert_eq!(multi, wkt! { MULTIPOINT Z ((0 0 0), (1 2 3), (4 5 6)) });
+    }
 }
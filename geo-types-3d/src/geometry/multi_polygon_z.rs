@@ -4,6 +4,7 @@ use alloc::vec;
 use alloc::vec::Vec;
 
 use core::iter::FromIterator;
+use core::ops::{Index, IndexMut};
 #[cfg(feature = "multithreading")]
 use rayon::prelude::*;
 
@@ -29,6 +30,11 @@ use rayon::prelude::*;
 /// predicates that operate on it.
 #[derive(Eq, PartialEq, Clone, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub struct MultiPolygonZ<T: CoordNum = f64>(pub Vec<PolygonZ<T>>);
 
 impl<T: CoordNum, IP: Into<PolygonZ<T>>> From<IP> for MultiPolygonZ<T> {
@@ -117,6 +123,18 @@ impl<T: CoordNum> MultiPolygonZ<T> {
         Self(Vec::new())
     }
 
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn push(&mut self, polygon: impl Into<PolygonZ<T>>) {
+        self.0.push(polygon.into());
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &PolygonZ<T>> {
         self.0.iter()
     }
@@ -126,6 +144,26 @@ impl<T: CoordNum> MultiPolygonZ<T> {
     }
 }
 
+impl<T: CoordNum, IP: Into<PolygonZ<T>>> Extend<IP> for MultiPolygonZ<T> {
+    fn extend<I: IntoIterator<Item = IP>>(&mut self, iter: I) {
+        self.0.extend(iter.into_iter().map(|x| x.into()));
+    }
+}
+
+impl<T: CoordNum> Index<usize> for MultiPolygonZ<T> {
+    type Output = PolygonZ<T>;
+
+    fn index(&self, index: usize) -> &PolygonZ<T> {
+        self.0.index(index)
+    }
+}
+
+impl<T: CoordNum> IndexMut<usize> for MultiPolygonZ<T> {
+    fn index_mut(&mut self, index: usize) -> &mut PolygonZ<T> {
+        self.0.index_mut(index)
+    }
+}
+
 #[cfg(any(feature = "approx", test))]
 mod approx_integration {
     use super::*;
@@ -310,12 +348,12 @@ mod test {
     #[test]
     fn test_par_iter() {
         let multi = MultiPolygonZ::new(vec![
-            polygon![(x: 0, y: 0, z: 0), (x: 2, y: 0, z: 0), (x: 1, y: 2, z: 3), (x:0, y:0, z: 0)],
-            polygon![(x: 10, y: 10, z: 10), (x: 12, y: 10, z: 10), (x: 11, y: 12, z: 0), (x:10, y:10, z: 0)],
+            polygon_z![(x: 0, y: 0, z: 0), (x: 2, y: 0, z: 0), (x: 1, y: 2, z: 3), (x:0, y:0, z: 0)],
+            polygon_z![(x: 10, y: 10, z: 10), (x: 12, y: 10, z: 10), (x: 11, y: 12, z: 0), (x:10, y:10, z: 0)],
         ]);
         let mut multimut = MultiPolygonZ::new(vec![
-            polygon![(x: 0, y: 0, z: 0), (x: 2, y: 0, z: 0), (x: 1, y: 2, z: 3), (x:0, y:0, z: 0)],
-            polygon![(x: 10, y: 10, z: 10), (x: 12, y: 10, z: 10), (x: 11, y: 12, z: 13), (x:10, y:10, z: 10)],
+            polygon_z![(x: 0, y: 0, z: 0), (x: 2, y: 0, z: 0), (x: 1, y: 2, z: 3), (x:0, y:0, z: 0)],
+            polygon_z![(x: 10, y: 10, z: 10), (x: 12, y: 10, z: 10), (x: 11, y: 12, z: 13), (x:10, y:10, z: 10)],
         ]);
         multi.par_iter().for_each(|_p| ());
         let _ = &multimut.par_iter_mut().for_each(|_p| ());
@@ -372,4 +410,19 @@ mod test {
         let empty_2 = wkt! { MULTIPOLYGON Z EMPTY };
         assert_eq!(empty, empty_2);
     }
+
+    #[test]
+    fn test_index_and_extend() {
+        let mut multi = MultiPolygonZ::<i32>::empty();
+        assert!(multi.is_empty());
+        multi.extend([
+            wkt! { POLYGON Z ((0 0 0,2 0 0,1 2 0,0 0 0)) },
+            wkt! { POLYGON Z ((10 10 1,12 10 1,11 12 1,10 10 1)) },
+        ]);
+        assert_eq!(multi.len(), 2);
+        assert_eq!(multi[1].exterior()[0], wkt! { POINT Z (10 10 1) }.0);
+
+        multi[0] = PolygonZ::empty();
+        assert_eq!(multi[0], wkt! { POLYGON Z EMPTY });
+    }
 }
@@ -0,0 +1,183 @@
+use crate::algorithm::linalg::{cross, dot};
+use crate::{CoordFloat, CoordZ};
+
+/// An oriented bounding box: a box that, unlike a [`Cube`](crate::Cube), may be
+/// turned to any orientation.
+///
+/// Points of the box are `center + rotation · local` for local coordinates within
+/// `half_extents` of zero on each axis. The columns of `rotation` are the unit axes
+/// of the box, at right angles to one another; see [`Obb::axes`].
+///
+/// Use [`MultiPointZ::oriented_bounding_box`](crate::MultiPointZ::oriented_bounding_box)
+/// for a box closely fitting a set of points.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{coordZ, Obb};
+///
+/// // a 4 × 2 × 2 box turned 45° about z
+/// let h = 0.5f64.sqrt();
+/// let obb = Obb::new(
+///     coordZ! { x: 0., y: 0., z: 0. },
+///     coordZ! { x: 2., y: 1., z: 1. },
+///     [[h, -h, 0.], [h, h, 0.], [0., 0., 1.]],
+/// );
+/// assert!(obb.contains_coord(coordZ! { x: 1.2, y: 1.2, z: 0. }));
+/// assert!(!obb.contains_coord(coordZ! { x: 1.2, y: -1.2, z: 0. }));
+/// assert_eq!(obb.volume(), 16.);
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct Obb<T: CoordFloat = f64> {
+    pub center: CoordZ<T>,
+    pub half_extents: CoordZ<T>,
+    pub rotation: [[T; 3]; 3],
+}
+
+impl<T: CoordFloat> Obb<T> {
+    pub fn new(center: CoordZ<T>, half_extents: CoordZ<T>, rotation: [[T; 3]; 3]) -> Self {
+        Self {
+            center,
+            half_extents,
+            rotation,
+        }
+    }
+
+    /// The unit axes of the box, the columns of `rotation`.
+    pub fn axes(&self) -> [CoordZ<T>; 3] {
+        let r = &self.rotation;
+        core::array::from_fn(|i| CoordZ {
+            x: r[0][i],
+            y: r[1][i],
+            z: r[2][i],
+        })
+    }
+
+    pub fn volume(&self) -> T {
+        let e = self.half_extents;
+        let eight = T::from(8).unwrap();
+        e.x * e.y * e.z * eight
+    }
+
+    /// The coordinates of `coord` along the axes of the box, from its centre.
+    pub fn to_local(&self, coord: CoordZ<T>) -> CoordZ<T> {
+        let [x, y, z] = self.axes().map(|axis| dot(axis, coord - self.center));
+        CoordZ { x, y, z }
+    }
+
+    /// The point at local coordinates `local` along the axes of the box.
+    pub fn to_world(&self, local: CoordZ<T>) -> CoordZ<T> {
+        let [a, b, c] = self.axes();
+        self.center + a * local.x + b * local.y + c * local.z
+    }
+
+    /// The eight corners of the box.
+    pub fn corners(&self) -> [CoordZ<T>; 8] {
+        let e = self.half_extents;
+        core::array::from_fn(|i| {
+            let sign = |bit: usize, value: T| if i & bit == 0 { -value } else { value };
+            self.to_world(CoordZ {
+                x: sign(1, e.x),
+                y: sign(2, e.y),
+                z: sign(4, e.z),
+            })
+        })
+    }
+
+    /// Whether `coord` lies inside the box or on its surface.
+    pub fn contains_coord(&self, coord: CoordZ<T>) -> bool {
+        let local = self.to_local(coord);
+        let e = self.half_extents;
+        local.x.abs() <= e.x && local.y.abs() <= e.y && local.z.abs() <= e.z
+    }
+
+    /// Whether the two boxes share at least one point, by the separating axis
+    /// theorem.
+    pub fn intersects(&self, other: &Self) -> bool {
+        let (a, b) = (self.axes(), other.axes());
+        let offset = other.center - self.center;
+        let extents = |axes: &[CoordZ<T>; 3], half: CoordZ<T>, direction: CoordZ<T>| {
+            dot(axes[0], direction).abs() * half.x
+                + dot(axes[1], direction).abs() * half.y
+                + dot(axes[2], direction).abs() * half.z
+        };
+        // the faces of either box, and the edges of one against the other
+        let face_axes = a.iter().chain(&b).copied();
+        let edge_axes = a.iter().flat_map(|&a| b.iter().map(move |&b| cross(a, b)));
+        face_axes.chain(edge_axes).all(|direction| {
+            let length_2 = dot(direction, direction);
+            // parallel edges give no axis of their own
+            if length_2 <= T::epsilon() * T::epsilon() {
+                return true;
+            }
+            dot(offset, direction).abs()
+                <= extents(&a, self.half_extents, direction)
+                    + extents(&b, other.half_extents, direction)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn intersects() {
+        let h = 0.5f64.sqrt();
+        let turned = [[h, -h, 0.], [h, h, 0.], [0., 0., 1.]];
+        let identity = [[1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+        let unit = CoordZ {
+            x: 1.,
+            y: 1.,
+            z: 1.,
+        };
+        let diamond = Obb::new(CoordZ::zero(), unit, turned);
+
+        // axis-aligned boxes to the side: the diamond reaches out to √2 along x
+        let beside = |x: f64| Obb::new(CoordZ { x, y: 0., z: 0. }, unit, identity);
+        assert!(diamond.intersects(&beside(2.4)));
+        assert!(!diamond.intersects(&beside(2.45)));
+        assert!(beside(2.4).intersects(&diamond));
+
+        // diagonally, only the corners of the axis-aligned box reach in
+        let corner = |d: f64| Obb::new(CoordZ { x: d, y: d, z: 0. }, unit, identity);
+        assert!(diamond.intersects(&corner(1.7)));
+        assert!(!diamond.intersects(&corner(1.75)));
+
+        // a box twisted about x, its lowest edge above or below the top of the other
+        let tilted = Obb::new(
+            CoordZ {
+                x: 0.,
+                y: 0.,
+                z: 2.6,
+            },
+            unit,
+            [[1., 0., 0.], [0., h, -h], [0., h, h]],
+        );
+        assert!(!diamond.intersects(&tilted));
+        assert!(diamond.intersects(&Obb {
+            center: CoordZ {
+                x: 0.,
+                y: 0.,
+                z: 2.3
+            },
+            ..tilted
+        }));
+
+        for corner in diamond.corners() {
+            assert!(diamond.contains_coord(corner * 0.999));
+            assert!(!diamond.contains_coord(corner * 1.001));
+            assert_relative_eq!(
+                diamond.to_world(diamond.to_local(corner)),
+                corner,
+                epsilon = 1e-12
+            );
+        }
+    }
+}
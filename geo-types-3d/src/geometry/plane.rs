@@ -0,0 +1,69 @@
+use crate::algorithm::linalg::{cross, dot, norm};
+use crate::{CoordFloat, CoordZ};
+
+/// An infinite plane, of the points `p` with `normal · p = offset`.
+///
+/// The normal has unit length, so `offset` is the signed distance of the plane
+/// from the origin along it.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{coordZ, Plane};
+///
+/// // the floor of the second storey of a building
+/// let floor = Plane::from_point_normal(
+///     coordZ! { x: 0., y: 0., z: 3. },
+///     coordZ! { x: 0., y: 0., z: 2. },
+/// )
+/// .unwrap();
+/// assert_eq!(floor.offset, 3.);
+/// assert_eq!(floor.signed_distance(coordZ! { x: 5., y: 2., z: 4.5 }), 1.5);
+/// assert_eq!(floor.project(coordZ! { x: 5., y: 2., z: 4.5 }), coordZ! { x: 5., y: 2., z: 3. });
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct Plane<T: CoordFloat = f64> {
+    pub normal: CoordZ<T>,
+    pub offset: T,
+}
+
+impl<T: CoordFloat> Plane<T> {
+    /// Creates a plane from a unit normal and its offset along it.
+    pub fn new(normal: CoordZ<T>, offset: T) -> Self {
+        Self { normal, offset }
+    }
+
+    /// The plane through `point` at right angles to `normal`, which needn't have
+    /// unit length, or `None` if `normal` is zero.
+    pub fn from_point_normal(point: CoordZ<T>, normal: CoordZ<T>) -> Option<Self> {
+        let length = norm(normal);
+        if length == T::zero() || !length.is_finite() {
+            return None;
+        }
+        let normal = normal / length;
+        Some(Self::new(normal, dot(normal, point)))
+    }
+
+    /// The plane through three points, facing the side from which they run
+    /// anticlockwise, or `None` if they lie on a line.
+    pub fn from_points(a: CoordZ<T>, b: CoordZ<T>, c: CoordZ<T>) -> Option<Self> {
+        Self::from_point_normal(a, cross(b - a, c - a))
+    }
+
+    /// The distance of `coord` from the plane, positive on the side the normal
+    /// faces.
+    pub fn signed_distance(&self, coord: CoordZ<T>) -> T {
+        dot(self.normal, coord) - self.offset
+    }
+
+    /// The closest point of the plane to `coord`.
+    pub fn project(&self, coord: CoordZ<T>) -> CoordZ<T> {
+        coord - self.normal * self.signed_distance(coord)
+    }
+}
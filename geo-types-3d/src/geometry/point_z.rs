@@ -1,5 +1,6 @@
 use crate::{pointZ, CoordFloat, CoordNum, CoordZ};
 
+use core::iter::Sum;
 use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 /// A single point in 3D space.
@@ -34,7 +35,8 @@ use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAss
 ///
 /// Geographic methods in the [`geo`](https://crates.io/crates/geo) crate expect this common
 /// lon/lat/alt order, but different conventions exist in other coordinate systems,
-/// notably EPSG:4326, which uses lat/lon ordering.
+/// notably EPSG:4326, which uses lat/lon ordering. Geometries in lat/lon order can be
+/// brought into lon/lat order with [`SwapXY`](crate::algorithm::SwapXY).
 /// ```
 /// use geo_types_3d::{coordZ, pointZ, PointZ};
 ///
@@ -46,6 +48,11 @@ use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAss
 ///
 #[derive(Eq, PartialEq, Clone, Copy, Hash, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub struct PointZ<T: CoordNum = f64>(pub CoordZ<T>);
 
 impl<T: CoordNum> From<CoordZ<T>> for PointZ<T> {
@@ -441,6 +448,39 @@ impl<T: CoordNum> PointZ<T> {
 }
 
 impl<T: CoordFloat> PointZ<T> {
+    /// The point a fraction `t` of the way from this point to `other`, exactly
+    /// this one at `t = 0` and `other` at `t = 1`, as by [`CoordZ::lerp`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::PointZ;
+    ///
+    /// let p = PointZ::new(0., 10., 100.);
+    /// let q = PointZ::new(4., 20., 300.);
+    ///
+    /// assert_eq!(p.lerp(q, 0.75), PointZ::new(3., 17.5, 250.));
+    /// ```
+    pub fn lerp(self, other: Self, t: T) -> Self {
+        PointZ(self.0.lerp(other.0, t))
+    }
+
+    /// The point halfway between this point and `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::PointZ;
+    ///
+    /// let p = PointZ::new(0., 10., 100.);
+    /// let q = PointZ::new(4., 20., 300.);
+    ///
+    /// assert_eq!(p.midpoint(q), PointZ::new(2., 15., 200.));
+    /// ```
+    pub fn midpoint(self, other: Self) -> Self {
+        PointZ(self.0.midpoint(other.0))
+    }
+
     /// Converts the (x,y,z) components of PointZ to degrees
     ///
     /// # Example
@@ -480,6 +520,155 @@ impl<T: CoordFloat> PointZ<T> {
         let z = z.to_radians();
         PointZ::new(x, y, z)
     }
+
+    /// The bearing and pitch of `other` seen from this point, in degrees, as to
+    /// aim an antenna or a camera at it.
+    ///
+    /// The bearing runs clockwise from the +y axis, taken as north, from 0 up to
+    /// 360, as `geo`'s `Bearing` does for lon/lat. The pitch is the angle above the
+    /// XY plane, from -90 straight down to 90 straight up. Both are 0 if the points
+    /// are the same. For lon/lat points, see
+    /// [`GeodesicPointZ`](crate::algorithm::GeodesicPointZ).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::PointZ;
+    ///
+    /// let antenna: PointZ = PointZ::new(0., 0., 10.);
+    /// let drone = PointZ::new(-100., 0., 110.);
+    /// let (bearing, pitch) = antenna.bearing_and_pitch_to(drone);
+    /// assert_eq!(bearing, 270.);
+    /// assert!((pitch - 45.).abs() < 1e-12);
+    /// ```
+    pub fn bearing_and_pitch_to(self, other: Self) -> (T, T) {
+        let d = other - self;
+        let full = T::from(360).unwrap();
+        let bearing = (d.x().atan2(d.y()).to_degrees() + full) % full;
+        let pitch = d.z().atan2(d.x().hypot(d.y())).to_degrees();
+        (bearing, pitch)
+    }
+
+    /// The point `distance` away from this one, along `bearing` and `pitch` in
+    /// degrees as [`PointZ::bearing_and_pitch_to`] measures them, such as to step a
+    /// simulated drone forward.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::PointZ;
+    ///
+    /// let drone: PointZ = PointZ::new(0., 0., 10.);
+    /// // east, climbing at 30°
+    /// let next = drone.destination(90., 30., 20.);
+    /// assert!((next.x() - 300f64.sqrt()).abs() < 1e-12);
+    /// assert!(next.y().abs() < 1e-12);
+    /// assert!((next.z() - 20.).abs() < 1e-12);
+    ///
+    /// let (bearing, pitch) = drone.bearing_and_pitch_to(next);
+    /// assert!((bearing - 90.).abs() < 1e-12 && (pitch - 30.).abs() < 1e-12);
+    /// ```
+    pub fn destination(self, bearing: T, pitch: T, distance: T) -> Self {
+        let (sin_pitch, cos_pitch) = pitch.to_radians().sin_cos();
+        let (sin_bearing, cos_bearing) = bearing.to_radians().sin_cos();
+        let horizontal = distance * cos_pitch;
+        self + PointZ::new(
+            horizontal * sin_bearing,
+            horizontal * cos_bearing,
+            distance * sin_pitch,
+        )
+    }
+
+    /// The spherical coordinates `(r, θ, φ)` of this point about the origin, in
+    /// radians, such as for the range and direction of a radar return.
+    ///
+    /// As in ISO 80000-2, `r` is the distance from the origin, the polar angle `θ`
+    /// runs from 0 along +z to π along -z, and the azimuth `φ` runs
+    /// counterclockwise from the +x axis towards +y, from -π up to π. The angles are
+    /// 0 where they are undefined, such as at the origin. For the compass bearing
+    /// and pitch seen from another point, see [`PointZ::bearing_and_pitch_to`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::PointZ;
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let p: PointZ = PointZ::new(0., 3., 4.);
+    /// let (r, theta, phi) = p.to_spherical();
+    /// assert_eq!(r, 5.);
+    /// assert_eq!(theta, 0.6f64.asin());
+    /// assert_eq!(phi, FRAC_PI_2);
+    /// ```
+    pub fn to_spherical(self) -> (T, T, T) {
+        let (x, y, z) = self.x_y_z();
+        let rho = x.hypot(y);
+        (rho.hypot(z), rho.atan2(z), y.atan2(x))
+    }
+
+    /// The point at spherical coordinates `(r, θ, φ)` about the origin, in radians,
+    /// as [`PointZ::to_spherical`] measures them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::PointZ;
+    /// use std::f64::consts::{FRAC_PI_2, PI};
+    ///
+    /// // on the horizon, straight along -x
+    /// let p = PointZ::from_spherical(2., FRAC_PI_2, PI);
+    /// assert!((p.x() + 2.).abs() < 1e-12);
+    /// assert!(p.y().abs() < 1e-12 && p.z().abs() < 1e-12);
+    /// ```
+    pub fn from_spherical(r: T, theta: T, phi: T) -> Self {
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        PointZ::new(
+            r * sin_theta * cos_phi,
+            r * sin_theta * sin_phi,
+            r * cos_theta,
+        )
+    }
+
+    /// The cylindrical coordinates `(ρ, φ, z)` of this point about the z axis.
+    ///
+    /// `ρ` is the distance from the z axis, and the azimuth `φ` is in radians as
+    /// [`PointZ::to_spherical`] measures it. `z` is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::PointZ;
+    /// use std::f64::consts::FRAC_PI_4;
+    ///
+    /// let p: PointZ = PointZ::new(1., 1., 7.);
+    /// let (rho, phi, z) = p.to_cylindrical();
+    /// assert_eq!(rho, 2f64.sqrt());
+    /// assert_eq!(phi, FRAC_PI_4);
+    /// assert_eq!(z, 7.);
+    /// ```
+    pub fn to_cylindrical(self) -> (T, T, T) {
+        let (x, y, z) = self.x_y_z();
+        (x.hypot(y), y.atan2(x), z)
+    }
+
+    /// The point at cylindrical coordinates `(ρ, φ, z)` about the z axis, as
+    /// [`PointZ::to_cylindrical`] measures them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::PointZ;
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let p = PointZ::from_cylindrical(3., FRAC_PI_2, -1.);
+    /// assert!(p.x().abs() < 1e-12);
+    /// assert_eq!((p.y(), p.z()), (3., -1.));
+    /// ```
+    pub fn from_cylindrical(rho: T, phi: T, z: T) -> Self {
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        PointZ::new(rho * cos_phi, rho * sin_phi, z)
+    }
 }
 
 impl<T> Neg for PointZ<T>
@@ -547,6 +736,43 @@ impl<T: CoordNum> AddAssign for PointZ<T> {
     }
 }
 
+impl<T: CoordNum> Add<CoordZ<T>> for PointZ<T> {
+    type Output = Self;
+
+    /// Move the given point by a coordinate offset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{coordZ, PointZ};
+    ///
+    /// let p = PointZ::new(1.25, 2.5, 3.5) + coordZ! { x: 1.5, y: 2.5, z: 3.5 };
+    ///
+    /// assert_eq!(p, PointZ::new(2.75, 5.0, 7.0));
+    /// ```
+    fn add(self, rhs: CoordZ<T>) -> Self::Output {
+        PointZ::from(self.0 + rhs)
+    }
+}
+
+impl<T: CoordNum> AddAssign<CoordZ<T>> for PointZ<T> {
+    /// Move the given point by a coordinate offset in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{coordZ, PointZ};
+    ///
+    /// let mut p = PointZ::new(1.25, 2.5, 3.5);
+    /// p += coordZ! { x: 1.5, y: 2.5, z: 3.5 };
+    ///
+    /// assert_eq!(p, PointZ::new(2.75, 5.0, 7.0));
+    /// ```
+    fn add_assign(&mut self, rhs: CoordZ<T>) {
+        self.0 = self.0 + rhs;
+    }
+}
+
 impl<T: CoordNum> Sub for PointZ<T> {
     type Output = Self;
 
@@ -588,6 +814,43 @@ impl<T: CoordNum> SubAssign for PointZ<T> {
     }
 }
 
+impl<T: CoordNum> Sub<CoordZ<T>> for PointZ<T> {
+    type Output = Self;
+
+    /// Move the given point back by a coordinate offset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{coordZ, PointZ};
+    ///
+    /// let p = PointZ::new(1.25, 3.0, 4.0) - coordZ! { x: 1.5, y: 2.5, z: 1.0 };
+    ///
+    /// assert_eq!(p, PointZ::new(-0.25, 0.5, 3.0));
+    /// ```
+    fn sub(self, rhs: CoordZ<T>) -> Self::Output {
+        PointZ::from(self.0 - rhs)
+    }
+}
+
+impl<T: CoordNum> SubAssign<CoordZ<T>> for PointZ<T> {
+    /// Move the given point back by a coordinate offset in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{coordZ, PointZ};
+    ///
+    /// let mut p = PointZ::new(1.25, 3.0, 4.0);
+    /// p -= coordZ! { x: 1.5, y: 2.5, z: 1.0 };
+    ///
+    /// assert_eq!(p, PointZ::new(-0.25, 0.5, 3.0));
+    /// ```
+    fn sub_assign(&mut self, rhs: CoordZ<T>) {
+        self.0 = self.0 - rhs;
+    }
+}
+
 impl<T: CoordNum> Mul<T> for PointZ<T> {
     type Output = Self;
 
@@ -670,6 +933,30 @@ impl<T: CoordNum> DivAssign<T> for PointZ<T> {
     }
 }
 
+impl<T: CoordNum> Sum for PointZ<T> {
+    /// Add up points, as the coordinates from the origin to each.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::PointZ;
+    ///
+    /// let points = [PointZ::new(1., 2., 3.), PointZ::new(4., 5., 6.)];
+    /// let total: PointZ = points.iter().sum();
+    ///
+    /// assert_eq!(total, PointZ::new(5., 7., 9.));
+    /// ```
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        PointZ(iter.map(|point| point.0).sum())
+    }
+}
+
+impl<'a, T: CoordNum> Sum<&'a PointZ<T>> for PointZ<T> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
 #[cfg(any(feature = "approx", test))]
 mod approx_integration {
     use super::*;
@@ -918,6 +1205,46 @@ mod test {
 
     use approx::{AbsDiffEq, RelativeEq};
 
+    #[test]
+    fn bearing_and_pitch() {
+        let origin = PointZ::new(1., 2., 3.);
+        let towards = |x, y, z| origin.bearing_and_pitch_to(origin + PointZ::new(x, y, z));
+        assert_eq!(towards(0., 5., 0.), (0., 0.));
+        assert_eq!(towards(5., 0., 0.), (90., 0.));
+        assert_eq!(towards(0., -5., 0.), (180., 0.));
+        assert_eq!(towards(-5., 0., -5.), (270., -45.));
+        assert_eq!(towards(0., 0., 2.), (0., 90.));
+        assert_eq!(towards(0., 0., 0.), (0., 0.));
+        // and back again
+        for (bearing, pitch) in [(0f64, 0f64), (90., 10.), (200., -60.), (315., 89.)] {
+            let there = origin.destination(bearing, pitch, 7.);
+            assert!(((there - origin).dot(there - origin) - 49.).abs() < 1e-12);
+            let (b, p) = origin.bearing_and_pitch_to(there);
+            assert!((b - bearing).abs() < 1e-9 && (p - pitch).abs() < 1e-9);
+        }
+        // just west of north stays below 360
+        let (bearing, _) = towards(-1e-14, 1., 0.);
+        assert!((0. ..360.).contains(&bearing));
+    }
+
+    #[test]
+    fn spherical_and_cylindrical() {
+        assert_eq!(PointZ::new(0., 0., 0.).to_spherical(), (0., 0., 0.));
+        assert_eq!(PointZ::new(0., 0., -2.).to_spherical().1, core::f64::consts::PI);
+        assert_eq!(PointZ::new(0., 0., 2.).to_cylindrical(), (0., 0., 2.));
+        // and back again
+        for p in [
+            PointZ::new(1., 2., 3.),
+            PointZ::new(-4., 0.5, -6.),
+            PointZ::new(0., -1., 0.),
+        ] {
+            let (r, theta, phi) = p.to_spherical();
+            assert!(PointZ::from_spherical(r, theta, phi).abs_diff_eq(&p, 1e-12));
+            let (rho, phi, z) = p.to_cylindrical();
+            assert!(PointZ::from_cylindrical(rho, phi, z).abs_diff_eq(&p, 1e-12));
+        }
+    }
+
     #[test]
     fn test_abs_diff_eq() {
         let delta = 1e-6;
@@ -0,0 +1,86 @@
+use crate::{CoordNum, CoordZ, CoordZM, PointZ};
+
+/// A single point with an elevation and a measure. See [`CoordZM`].
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{wkt, PointZ, PointZM};
+///
+/// let milepost = wkt! { POINT ZM(10.0 20.0 3.5 1200.0) };
+/// assert_eq!(milepost, PointZM::new(10.0, 20.0, 3.5, 1200.0));
+/// assert_eq!(milepost.m(), 1200.0);
+/// assert_eq!(PointZ::from(milepost), PointZ::new(10.0, 20.0, 3.5));
+/// ```
+#[derive(Eq, PartialEq, Clone, Copy, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct PointZM<T: CoordNum = f64>(pub CoordZM<T>);
+
+impl<T: CoordNum> PointZM<T> {
+    pub fn new(x: T, y: T, z: T, m: T) -> Self {
+        Self(CoordZM { x, y, z, m })
+    }
+
+    pub fn x(self) -> T {
+        self.0.x
+    }
+
+    pub fn y(self) -> T {
+        self.0.y
+    }
+
+    pub fn z(self) -> T {
+        self.0.z
+    }
+
+    pub fn m(self) -> T {
+        self.0.m
+    }
+
+    pub fn set_m(&mut self, m: T) -> &mut Self {
+        self.0.m = m;
+        self
+    }
+
+    /// The position of this point, without its measure.
+    pub fn xyz(self) -> PointZ<T> {
+        PointZ(self.0.xyz())
+    }
+}
+
+impl<T: CoordNum> From<CoordZM<T>> for PointZM<T> {
+    fn from(coord: CoordZM<T>) -> Self {
+        Self(coord)
+    }
+}
+
+impl<T: CoordNum> From<(T, T, T, T)> for PointZM<T> {
+    fn from(coords: (T, T, T, T)) -> Self {
+        Self(coords.into())
+    }
+}
+
+impl<T: CoordNum> From<[T; 4]> for PointZM<T> {
+    fn from(coords: [T; 4]) -> Self {
+        Self(coords.into())
+    }
+}
+
+impl<T: CoordNum> From<PointZM<T>> for PointZ<T> {
+    /// Drops the measure.
+    fn from(point: PointZM<T>) -> Self {
+        point.xyz()
+    }
+}
+
+impl<T: CoordNum> From<PointZM<T>> for CoordZ<T> {
+    /// Drops the measure.
+    fn from(point: PointZM<T>) -> Self {
+        point.0.xyz()
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{CoordFloat, CoordNum, LineStringZ, PointZ, Triangle};
+use crate::algorithm::linalg::newell_normal;
+use crate::{CoordFloat, CoordNum, CoordZ, Error, LineStringZ, Plane, PointZ, Triangle};
 use alloc::vec;
 use alloc::vec::Vec;
 use num_traits::{Float, Signed};
@@ -65,6 +66,11 @@ use num_traits::{Float, Signed};
 /// [`LineString`]: line_string/struct.LineString.html
 #[derive(Eq, PartialEq, Clone, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub struct PolygonZ<T: CoordNum = f64> {
     exterior: LineStringZ<T>,
     interiors: Vec<LineStringZ<T>>,
@@ -132,6 +138,57 @@ impl<T: CoordNum> PolygonZ<T> {
         }
     }
 
+    /// Create a new `PolygonZ` from rings that are already closed, without closing
+    /// them as [`new`](Self::new) does.
+    ///
+    /// Each ring must be closed and have at least four coordinates. Only the
+    /// exterior may be empty, making an [empty](Self::empty) polygon without
+    /// interiors. The [`Error::InvalidRing`] names the first ring that isn't, by its
+    /// position in [`rings`](Self::rings).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{Error, LineStringZ, PolygonZ};
+    ///
+    /// let exterior = LineStringZ::from(vec![(0., 0., 1.), (1., 0., 1.), (1., 1., 2.), (0., 0., 1.)]);
+    /// let open = LineStringZ::from(vec![(0.2, 0.1, 1.), (0.8, 0.1, 1.), (0.8, 0.7, 2.)]);
+    ///
+    /// assert!(PolygonZ::try_new(exterior.clone(), vec![]).is_ok());
+    /// assert!(matches!(
+    ///     PolygonZ::try_new(exterior, vec![open]),
+    ///     Err(Error::InvalidRing { index: 1, .. })
+    /// ));
+    /// ```
+    pub fn try_new(
+        exterior: LineStringZ<T>,
+        interiors: Vec<LineStringZ<T>>,
+    ) -> Result<Self, Error> {
+        if exterior.0.is_empty() && !interiors.is_empty() {
+            return Err(Error::InvalidRing {
+                index: 0,
+                reason: "an empty exterior can't have interiors",
+            });
+        }
+        let polygon = Self {
+            exterior,
+            interiors,
+        };
+        for (index, ring) in polygon.rings().enumerate() {
+            let reason = if index == 0 && ring.0.is_empty() {
+                continue;
+            } else if ring.0.len() < 4 {
+                "a ring needs at least four coordinates"
+            } else if !ring.is_closed() {
+                "a ring must end where it starts"
+            } else {
+                continue;
+            };
+            return Err(Error::InvalidRing { index, reason });
+        }
+        Ok(polygon)
+    }
+
     /// Returns an empty Polygon.
     ///
     /// `geo` represents an empty Polygon as one whose exterior is an empty LineString
@@ -469,6 +526,28 @@ impl<T: CoordNum> PolygonZ<T> {
     pub fn num_interior_rings(&self) -> usize {
         self.interiors.len()
     }
+
+    /// An iterator over all the rings of the polygon, the exterior first and then
+    /// the interiors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{LineStringZ, PolygonZ};
+    ///
+    /// let polygon = PolygonZ::new(
+    ///     LineStringZ::from(vec![(0., 0., 0.), (1., 1., 0.), (1., 0., 0.)]),
+    ///     vec![LineStringZ::from(vec![(0.1, 0.1, 0.), (0.9, 0.9, 0.), (0.9, 0.1, 0.)])],
+    /// );
+    ///
+    /// let mut rings = polygon.rings();
+    /// assert_eq!(rings.next(), Some(polygon.exterior()));
+    /// assert_eq!(rings.next(), Some(&polygon.interiors()[0]));
+    /// assert_eq!(rings.next(), None);
+    /// ```
+    pub fn rings(&self) -> impl DoubleEndedIterator<Item = &LineStringZ<T>> {
+        core::iter::once(&self.exterior).chain(&self.interiors)
+    }
 }
 
 // used to check the sign of a vec of floats
@@ -480,6 +559,33 @@ enum ListSign {
     Mixed,
 }
 
+impl<T: CoordFloat> PolygonZ<T> {
+    /// The unit normal of the polygon by Newell's method, facing the side from
+    /// which its exterior runs anticlockwise, or `None` if it encloses no area.
+    ///
+    /// Newell's method averages over every edge of the exterior, so the normal is
+    /// a good fit even where the exterior isn't quite planar.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{coordZ, polygon_z};
+    ///
+    /// // a wall facing south, drawn anticlockwise as seen from the south
+    /// let wall = polygon_z![
+    ///     (x: 0., y: 0., z: 0.),
+    ///     (x: 4., y: 0., z: 0.),
+    ///     (x: 4., y: 0., z: 3.),
+    ///     (x: 0., y: 0., z: 3.),
+    /// ];
+    /// assert_eq!(wall.normal(), Some(coordZ! { x: 0., y: -1., z: 0. }));
+    /// ```
+    pub fn normal(&self) -> Option<CoordZ<T>> {
+        let normal = newell_normal(&self.exterior.0);
+        Plane::from_point_normal(CoordZ::zero(), normal).map(|plane| plane.normal)
+    }
+}
+
 impl<T: CoordFloat + Signed> PolygonZ<T> {
     /// Determine whether a Polygon is convex
     // For each consecutive pair of edges of the polygon (each triplet of points),
@@ -705,7 +811,7 @@ impl_rstar_polygon!(rstar_0_11);
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::wkt;
+    use crate::{polygon_z, wkt};
 
     #[test]
     fn empty() {
@@ -713,4 +819,70 @@ mod tests {
         let empty_2 = wkt! { POLYGON Z EMPTY };
         assert_eq!(empty, empty_2);
     }
+
+    #[test]
+    fn normal() {
+        // a slightly warped quadrilateral, anticlockwise from above
+        let roof = polygon_z![
+            (x: 0., y: 0., z: 0.),
+            (x: 2., y: 0., z: 2.),
+            (x: 2., y: 2., z: 2.1),
+            (x: 0., y: 2., z: -0.1),
+        ];
+        let normal = roof.normal().unwrap();
+        assert!((normal.x + 0.5f64.sqrt()).abs() < 0.05);
+        assert!((normal.z - 0.5f64.sqrt()).abs() < 0.05);
+        assert!(
+            ((normal.x * normal.x + normal.y * normal.y + normal.z * normal.z) - 1.).abs() < 1e-12
+        );
+        let turned = PolygonZ::new(roof.exterior().0.iter().rev().copied().collect(), vec![]);
+        assert_eq!(turned.normal(), Some(-normal));
+
+        let triangle = Triangle::new(
+            (0., 0., 0.).into(),
+            (1., 0., 0.).into(),
+            (0., 1., 1.).into(),
+        );
+        let expected = CoordZ::from((0., -1., 1.)) / 2f64.sqrt();
+        let normal = triangle.normal().unwrap();
+        let (x, y, z) = (normal - expected).x_y_z();
+        assert!(x.abs() + y.abs() + z.abs() < 1e-12);
+        assert_eq!(triangle.to_polygon().normal(), Some(normal));
+
+        let line = polygon_z![(x: 0., y: 0., z: 0.), (x: 1., y: 1., z: 1.), (x: 2., y: 2., z: 2.)];
+        assert_eq!(line.normal(), None);
+        assert_eq!(PolygonZ::<f64>::empty().normal(), None);
+        assert_eq!(
+            Triangle(line.exterior()[0], line.exterior()[1], line.exterior()[2]).normal(),
+            None
+        );
+    }
+
+    #[test]
+    fn try_new() {
+        let ring = |coords: &[(f64, f64, f64)]| LineStringZ::from(coords.to_vec());
+        let exterior = ring(&[(0., 0., 0.), (4., 0., 0.), (4., 4., 2.), (0., 0., 0.)]);
+        let hole = ring(&[(1., 1., 0.), (3., 1., 0.), (3., 2., 1.), (1., 1., 0.)]);
+
+        let polygon = PolygonZ::try_new(exterior.clone(), vec![hole.clone()]).unwrap();
+        assert_eq!(polygon, PolygonZ::new(exterior.clone(), vec![hole.clone()]));
+        assert_eq!(polygon.rings().count(), 2);
+        assert_eq!(
+            PolygonZ::try_new(ring(&[]), vec![]).unwrap(),
+            PolygonZ::empty()
+        );
+
+        let invalid = |exterior, interiors| match PolygonZ::try_new(exterior, interiors) {
+            Err(Error::InvalidRing { index, .. }) => index,
+            other => panic!("expected an invalid ring, got {other:?}"),
+        };
+        let open = ring(&[(1., 1., 0.), (3., 1., 0.), (3., 2., 1.), (1., 2., 1.)]);
+        assert_eq!(invalid(ring(&[]), vec![hole.clone()]), 0);
+        assert_eq!(invalid(open.clone(), vec![]), 0);
+        assert_eq!(invalid(exterior.clone(), vec![hole.clone(), open]), 2);
+        assert_eq!(
+            invalid(exterior, vec![ring(&[(1., 1., 0.), (1., 1., 0.)])]),
+            1
+        );
+    }
 }
@@ -0,0 +1,73 @@
+use crate::{CoordFloat, CoordZ, Cube};
+
+/// A half-line starting at `origin` and extending along `direction`.
+///
+/// Points on the ray are `origin + t * direction` for `t >= 0`, so distances along
+/// the ray are measured in multiples of `direction`'s length.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{coordZ, Cube, Ray};
+///
+/// let ray = Ray::new(coordZ! { x: -1., y: 0.5, z: 0.5 }, coordZ! { x: 1., y: 0., z: 0. });
+/// let cube = Cube::new((0., 0., 0.), (1., 1., 1.));
+///
+/// assert_eq!(ray.intersect_cube(&cube), Some((1., 2.)));
+/// assert_eq!(ray.at(2.), coordZ! { x: 1., y: 0.5, z: 0.5 });
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct Ray<T: CoordFloat = f64> {
+    pub origin: CoordZ<T>,
+    pub direction: CoordZ<T>,
+}
+
+impl<T: CoordFloat> Ray<T> {
+    pub fn new(origin: CoordZ<T>, direction: CoordZ<T>) -> Self {
+        Self { origin, direction }
+    }
+
+    /// The point at parameter `t` along the ray.
+    pub fn at(&self, t: T) -> CoordZ<T> {
+        CoordZ {
+            x: self.origin.x + self.direction.x * t,
+            y: self.origin.y + self.direction.y * t,
+            z: self.origin.z + self.direction.z * t,
+        }
+    }
+
+    /// The parameters at which the ray enters and leaves `cube`, or `None` if it
+    /// misses the cube.
+    ///
+    /// If the origin lies inside the cube, the ray enters it at `t = 0`.
+    pub fn intersect_cube(&self, cube: &Cube<T>) -> Option<(T, T)> {
+        let (min, max) = (cube.min(), cube.max());
+        let axes = [
+            (self.origin.x, self.direction.x, min.x, max.x),
+            (self.origin.y, self.direction.y, min.y, max.y),
+            (self.origin.z, self.direction.z, min.z, max.z),
+        ];
+        let (mut enter, mut leave) = (T::zero(), T::infinity());
+        for (origin, direction, min, max) in axes {
+            if direction == T::zero() {
+                if origin < min || origin > max {
+                    return None;
+                }
+                continue;
+            }
+            let (a, b) = ((min - origin) / direction, (max - origin) / direction);
+            enter = enter.max(a.min(b));
+            leave = leave.min(a.max(b));
+            if enter > leave {
+                return None;
+            }
+        }
+        Some((enter, leave))
+    }
+}
@@ -0,0 +1,76 @@
+//! JSON schemas for the planar `geo_types` members of [`Geometry`](super::Geometry),
+//! which don't implement `JsonSchema` themselves. Each type here mirrors the serde
+//! representation of the `geo_types` type of the same name, and is used only to
+//! describe it.
+#![allow(dead_code)]
+
+use alloc::vec::Vec;
+
+#[derive(schemars::JsonSchema)]
+pub(crate) struct Coord<T> {
+    x: T,
+    y: T,
+}
+
+#[derive(schemars::JsonSchema)]
+pub(crate) struct Point<T>(Coord<T>);
+
+#[derive(schemars::JsonSchema)]
+pub(crate) struct Line<T> {
+    start: Coord<T>,
+    end: Coord<T>,
+}
+
+#[derive(schemars::JsonSchema)]
+pub(crate) struct LineString<T>(Vec<Coord<T>>);
+
+#[derive(schemars::JsonSchema)]
+pub(crate) struct Polygon<T> {
+    exterior: LineString<T>,
+    interiors: Vec<LineString<T>>,
+}
+
+#[derive(schemars::JsonSchema)]
+pub(crate) struct MultiPoint<T>(Vec<Point<T>>);
+
+#[derive(schemars::JsonSchema)]
+pub(crate) struct MultiLineString<T>(Vec<LineString<T>>);
+
+#[derive(schemars::JsonSchema)]
+pub(crate) struct MultiPolygon<T>(Vec<Polygon<T>>);
+
+#[derive(schemars::JsonSchema)]
+pub(crate) struct Rect<T> {
+    min: Coord<T>,
+    max: Coord<T>,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Cube, Geometry, PointZ, Tagged};
+
+    #[test]
+    fn matches_serde() {
+        let schema = schemars::schema_for!(Geometry);
+        let definitions = &schema.as_value()["$defs"];
+        for (name, fields) in [
+            ("Coord", ["x", "y", ""]),
+            ("CoordZ", ["x", "y", "z"]),
+            ("Rect", ["min", "max", ""]),
+            ("Polygon", ["exterior", "interiors", ""]),
+        ] {
+            let properties = &definitions[name]["properties"];
+            for field in fields.iter().filter(|field| !field.is_empty()) {
+                assert!(properties.get(field).is_some(), "{name}.{field}");
+            }
+        }
+        let variants = schema.as_value()["oneOf"].as_array().unwrap();
+        assert_eq!(variants.len(), 16);
+
+        let cube = schemars::schema_for!(Cube<f32>);
+        assert!(cube.as_value()["properties"].get("max").is_some());
+        let tagged = schemars::schema_for!(Tagged<PointZ<f32>, u32>);
+        let required = &tagged.as_value()["required"];
+        assert!(required[0] == "geometry" && required[1] == "data");
+    }
+}
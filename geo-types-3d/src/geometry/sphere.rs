@@ -0,0 +1,105 @@
+use crate::algorithm::linalg::{distance, distance_2, dot, solve};
+use crate::geometry::ellipsoid_z::{closest, fibonacci_sphere};
+use crate::{CoordFloat, CoordZ, Cube, MultiPointZ, PointZ};
+
+/// A sphere, given by its centre and radius.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{coordZ, Sphere};
+///
+/// let sphere: Sphere = Sphere::from_points(
+///     coordZ! { x: 1., y: 0., z: 0. },
+///     coordZ! { x: -1., y: 0., z: 0. },
+///     coordZ! { x: 0., y: 1., z: 0. },
+///     coordZ! { x: 0., y: 0., z: 1. },
+/// )
+/// .unwrap();
+/// assert!((sphere.radius - 1.).abs() < 1e-12);
+/// assert!((sphere.signed_distance(coordZ! { x: 0., y: 0., z: 3. }) - 2.).abs() < 1e-12);
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct Sphere<T: CoordFloat = f64> {
+    pub center: CoordZ<T>,
+    pub radius: T,
+}
+
+impl<T: CoordFloat> Sphere<T> {
+    pub fn new(center: CoordZ<T>, radius: T) -> Self {
+        Self { center, radius }
+    }
+
+    /// The sphere through four points, or `None` if they lie on a plane.
+    pub fn from_points(a: CoordZ<T>, b: CoordZ<T>, c: CoordZ<T>, d: CoordZ<T>) -> Option<Self> {
+        // the centre is as far from `a` as from each other point
+        let two = T::one() + T::one();
+        let row = |p: CoordZ<T>| {
+            let delta = p - a;
+            [delta.x * two, delta.y * two, delta.z * two]
+        };
+        let rhs = |p: CoordZ<T>| dot(p, p) - dot(a, a);
+        let [x, y, z] = solve([row(b), row(c), row(d)], [rhs(b), rhs(c), rhs(d)])?;
+        let center = CoordZ { x, y, z };
+        Some(Self::new(center, distance(a, center)))
+    }
+
+    /// The distance of `coord` from the surface of the sphere, negative inside it.
+    pub fn signed_distance(&self, coord: CoordZ<T>) -> T {
+        distance(coord, self.center) - self.radius
+    }
+
+    /// Whether `coord` lies inside the sphere or on its surface.
+    pub fn contains_coord(&self, coord: CoordZ<T>) -> bool {
+        distance_2(coord, self.center) <= self.radius * self.radius
+    }
+
+    /// Whether the sphere and `cube` have any point in common.
+    pub fn intersects(&self, cube: &Cube<T>) -> bool {
+        self.contains_coord(closest(cube, self.center))
+    }
+
+    /// `count` points spread evenly over the surface of the sphere, along a spiral
+    /// from the top to the bottom, each standing for about the same area.
+    ///
+    /// For points drawn at random, see
+    /// [`random::points_on_sphere`](crate::random::points_on_sphere), with the
+    /// `rand` feature.
+    pub fn surface_points(&self, count: usize) -> MultiPointZ<T> {
+        fibonacci_sphere(count)
+            .map(|unit| PointZ(self.center + unit * self.radius))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::BoundingCube;
+
+    #[test]
+    fn sphere() {
+        let sphere = Sphere::new(CoordZ::from((1., 1., 1.)), 2f64);
+        assert_eq!(
+            sphere.bounding_cube(),
+            Cube::new((-1., -1., -1.), (3., 3., 3.))
+        );
+        assert!(sphere.contains_coord(CoordZ::from((2., 2., 2.))));
+        assert!(!sphere.contains_coord(CoordZ::from((2.2, 2.2, 2.2))));
+        assert!(sphere.intersects(&Cube::new((2., 2., 2.), (4., 4., 4.))));
+        assert!(!sphere.intersects(&Cube::new((2.2, 2.2, 2.2), (4., 4., 4.))));
+        assert!(sphere.intersects(&Cube::new((0.8, 0.8, 2.9), (1.2, 1.2, 5.))));
+
+        let points = sphere.surface_points(100);
+        assert!(points
+            .iter()
+            .all(|p| sphere.signed_distance(p.0).abs() < 1e-12));
+        assert_eq!(points.iter().filter(|p| p.z() > 1.).count(), 50);
+    }
+}
@@ -0,0 +1,234 @@
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+use core::ops::{Deref, DerefMut};
+
+/// A geometry with user data attached, such as a feature id or attributes.
+///
+/// `Tagged` dereferences to its geometry, and the algorithm traits are implemented
+/// for it by delegating to the geometry and carrying the data along. A tagged
+/// geometry can be stored in an [`Octree`](crate::index::Octree), for example,
+/// and queries then return the data with it. With the `std` feature, geometries
+/// tagged with a [`geojson::JsonObject`] convert to and from GeoJSON features.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::BoundingCube;
+/// use geo_types_3d::index::Octree;
+/// use geo_types_3d::{Cube, PointZ, Tagged};
+///
+/// let mut tree = Octree::new(Cube::new((0., 0., 0.), (10., 10., 10.)));
+/// tree.insert(Tagged::new(PointZ::new(1., 1., 1.), "hydrant"));
+/// tree.insert(Tagged::new(PointZ::new(8., 8., 2.), "bench"));
+///
+/// let found: Vec<&str> = tree
+///     .query(&Cube::new((0., 0., 0.), (5., 5., 5.)))
+///     .map(|(_, tagged)| tagged.data)
+///     .collect();
+/// assert_eq!(found, ["hydrant"]);
+/// ```
+#[derive(Eq, PartialEq, Clone, Copy, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct Tagged<G, D> {
+    pub geometry: G,
+    pub data: D,
+}
+
+impl<G, D> Tagged<G, D> {
+    pub fn new(geometry: G, data: D) -> Self {
+        Self { geometry, data }
+    }
+
+    pub fn into_parts(self) -> (G, D) {
+        (self.geometry, self.data)
+    }
+
+    /// Replaces the geometry, keeping the data.
+    pub fn map_geometry<NG>(self, f: impl FnOnce(G) -> NG) -> Tagged<NG, D> {
+        Tagged::new(f(self.geometry), self.data)
+    }
+
+    /// Replaces the data, keeping the geometry.
+    pub fn map_data<ND>(self, f: impl FnOnce(D) -> ND) -> Tagged<G, ND> {
+        Tagged::new(self.geometry, f(self.data))
+    }
+
+    /// Borrows the geometry and the data.
+    pub fn as_ref(&self) -> Tagged<&G, &D> {
+        Tagged::new(&self.geometry, &self.data)
+    }
+}
+
+impl<G, D> Deref for Tagged<G, D> {
+    type Target = G;
+
+    fn deref(&self) -> &G {
+        &self.geometry
+    }
+}
+
+impl<G, D> DerefMut for Tagged<G, D> {
+    fn deref_mut(&mut self) -> &mut G {
+        &mut self.geometry
+    }
+}
+
+impl<G, D> From<(G, D)> for Tagged<G, D> {
+    fn from((geometry, data): (G, D)) -> Self {
+        Self::new(geometry, data)
+    }
+}
+
+/// A collection of [`Tagged`] geometries.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::BoundingCube;
+/// use geo_types_3d::{Cube, PointZ, TaggedCollection};
+///
+/// let mut stations = TaggedCollection::new();
+/// stations.push(PointZ::new(0., 0., 12.), 1);
+/// stations.push(PointZ::new(5., 3., 40.), 2);
+/// assert_eq!(stations.data().sum::<i32>(), 3);
+/// assert_eq!(
+///     stations.bounding_cube(),
+///     Some(Cube::new((0., 0., 12.), (5., 3., 40.)))
+/// );
+/// ```
+#[derive(Eq, PartialEq, Clone, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct TaggedCollection<G, D>(pub Vec<Tagged<G, D>>);
+
+impl<G, D> TaggedCollection<G, D> {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn push(&mut self, geometry: G, data: D) {
+        self.0.push(Tagged::new(geometry, data))
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Tagged<G, D>> + ExactSizeIterator {
+        self.0.iter()
+    }
+
+    pub fn iter_mut(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = &mut Tagged<G, D>> + ExactSizeIterator {
+        self.0.iter_mut()
+    }
+
+    pub fn geometries(&self) -> impl DoubleEndedIterator<Item = &G> + ExactSizeIterator {
+        self.0.iter().map(|tagged| &tagged.geometry)
+    }
+
+    pub fn data(&self) -> impl DoubleEndedIterator<Item = &D> + ExactSizeIterator {
+        self.0.iter().map(|tagged| &tagged.data)
+    }
+
+    /// Splits the collection into a `Vec` of geometries and a `Vec` of data.
+    pub fn unzip(self) -> (Vec<G>, Vec<D>) {
+        self.0.into_iter().map(Tagged::into_parts).unzip()
+    }
+}
+
+impl<G, D> Default for TaggedCollection<G, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G, D> FromIterator<Tagged<G, D>> for TaggedCollection<G, D> {
+    fn from_iter<I: IntoIterator<Item = Tagged<G, D>>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<G, D> FromIterator<(G, D)> for TaggedCollection<G, D> {
+    fn from_iter<I: IntoIterator<Item = (G, D)>>(iter: I) -> Self {
+        Self(iter.into_iter().map(Tagged::from).collect())
+    }
+}
+
+impl<G, D> Extend<Tagged<G, D>> for TaggedCollection<G, D> {
+    fn extend<I: IntoIterator<Item = Tagged<G, D>>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+impl<G, D> IntoIterator for TaggedCollection<G, D> {
+    type Item = Tagged<G, D>;
+    type IntoIter = ::alloc::vec::IntoIter<Tagged<G, D>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, G, D> IntoIterator for &'a TaggedCollection<G, D> {
+    type Item = &'a Tagged<G, D>;
+    type IntoIter = ::core::slice::Iter<'a, Tagged<G, D>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::{BoundingCube, Clip, MapCoordsZ, MapCoordsZInPlace};
+    use crate::{line_string_z, CoordZ, Cube, LineStringZ};
+
+    #[test]
+    fn delegation() {
+        let track: LineStringZ = line_string_z![
+            (x: -5., y: 5., z: 0.),
+            (x: 5., y: 5., z: 10.),
+            (x: 15., y: 5., z: 10.),
+        ];
+        let mut tagged = Tagged::new(track.clone(), "track 7");
+        assert_eq!(tagged.bounding_cube(), track.bounding_cube());
+        assert_eq!(tagged.lines().count(), 2);
+
+        let tile = Cube::new((0., 0., 0.), (10., 10., 10.));
+        let clipped = tagged.clip(&tile);
+        assert_eq!(clipped.geometry, track.clip(&tile));
+        assert_eq!(clipped.data, "track 7");
+
+        let lift = |c: CoordZ<f64>| CoordZ { z: c.z + 1., ..c };
+        let lifted = tagged.map_coords(lift);
+        tagged.map_coords_in_place(lift);
+        assert_eq!(lifted, tagged);
+
+        let collection: TaggedCollection<_, _> = [(track.clone(), 1), (lifted.geometry, 2)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            collection.bounding_cube(),
+            Some(Cube::new((-5., 5., 0.), (15., 5., 11.)))
+        );
+        let (geometries, data) = collection.unzip();
+        assert_eq!(geometries[0], track);
+        assert_eq!(data, [1, 2]);
+    }
+}
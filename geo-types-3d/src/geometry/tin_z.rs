@@ -0,0 +1,800 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+use crate::algorithm::linalg::{cross, dot, norm};
+#[cfg(feature = "std")]
+use crate::index::Bvh;
+use crate::{CoordFloat, CoordNum, CoordZ, LineStringZ, MultiLineStringZ, MultiPolygonZ, Triangle};
+#[cfg(feature = "std")]
+use crate::{Point, PointZ, PolygonZ, Ray};
+
+/// A triangulated irregular network: a surface, typically terrain, made of
+/// triangles that share their vertices.
+///
+/// The triangles are stored as indices into a list of vertices, anticlockwise
+/// when seen from above for terrain.
+///
+/// With the `std` feature, [`TinZ::raycast`] and [`TinZ::line_of_sight`] find where
+/// rays meet the surface. The first of these queries builds a [bounding volume
+/// hierarchy](crate::index::Bvh) over the triangles, which later queries reuse, so a TIN that is
+/// queried once costs no more than testing every triangle. Changing the TIN
+/// through [`TinZ::push`] discards the hierarchy. [`TinZ::viewshed`] builds on
+/// these to find the parts of the surface an observer can see.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{coordZ, PointZ, Ray, TinZ};
+///
+/// // a ridge running along y, 10 high at x = 5
+/// let ridge = TinZ::new(
+///     vec![
+///         coordZ! { x: 0., y: 0., z: 0. },
+///         coordZ! { x: 5., y: 0., z: 10. },
+///         coordZ! { x: 10., y: 0., z: 0. },
+///         coordZ! { x: 0., y: 10., z: 0. },
+///         coordZ! { x: 5., y: 10., z: 10. },
+///         coordZ! { x: 10., y: 10., z: 0. },
+///     ],
+///     vec![[0, 1, 4], [0, 4, 3], [1, 2, 5], [1, 5, 4]],
+/// );
+///
+/// # #[cfg(feature = "std")]
+/// # {
+/// let down = Ray::new(coordZ! { x: 2.5, y: 5., z: 100. }, coordZ! { x: 0., y: 0., z: -1. });
+/// assert_eq!(ridge.raycast(&down), Some(PointZ::new(2.5, 5., 5.)));
+///
+/// // the ridge hides one valley floor from the other, but not from above
+/// let (west, east) = (PointZ::new(1., 5., 3.), PointZ::new(9., 5., 3.));
+/// assert!(!ridge.line_of_sight(west, east));
+/// assert!(ridge.line_of_sight(west, PointZ::new(9., 5., 20.)));
+/// # }
+/// ```
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct TinZ<T: CoordNum = f64> {
+    vertices: Vec<CoordZ<T>>,
+    triangles: Vec<[usize; 3]>,
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "rkyv", rkyv(with = rkyv::with::Skip))]
+    bvh: OnceLock<Bvh<T, Triangle<T>>>,
+}
+
+impl<T: CoordNum> TinZ<T> {
+    /// Creates a TIN from its vertices and the indices of the corners of each
+    /// triangle.
+    ///
+    /// # Panics
+    ///
+    /// If a triangle refers to a vertex that doesn't exist.
+    pub fn new(vertices: Vec<CoordZ<T>>, triangles: Vec<[usize; 3]>) -> Self {
+        assert!(
+            triangles.iter().flatten().all(|&i| i < vertices.len()),
+            "triangle refers to a vertex that doesn't exist"
+        );
+        Self {
+            vertices,
+            triangles,
+            #[cfg(feature = "std")]
+            bvh: OnceLock::new(),
+        }
+    }
+
+    pub fn empty() -> Self {
+        Self::new(Vec::new(), Vec::new())
+    }
+
+    pub fn vertices(&self) -> &[CoordZ<T>] {
+        &self.vertices
+    }
+
+    /// The indices of the corners of each triangle.
+    pub fn triangle_indices(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+
+    /// The number of triangles.
+    pub fn len(&self) -> usize {
+        self.triangles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+
+    /// The triangle at `index`.
+    pub fn triangle(&self, index: usize) -> Triangle<T> {
+        let [a, b, c] = self.triangles[index].map(|i| self.vertices[i]);
+        Triangle(a, b, c)
+    }
+
+    pub fn triangles(&self) -> impl ExactSizeIterator<Item = Triangle<T>> + '_ {
+        (0..self.triangles.len()).map(|index| self.triangle(index))
+    }
+
+    /// Adds a triangle between existing vertices.
+    ///
+    /// # Panics
+    ///
+    /// If the triangle refers to a vertex that doesn't exist.
+    pub fn push(&mut self, triangle: [usize; 3]) {
+        assert!(
+            triangle.iter().all(|&i| i < self.vertices.len()),
+            "triangle refers to a vertex that doesn't exist"
+        );
+        self.triangles.push(triangle);
+        #[cfg(feature = "std")]
+        self.bvh.take();
+    }
+
+    /// Adds a vertex, returning its index.
+    pub fn push_vertex(&mut self, vertex: CoordZ<T>) -> usize {
+        self.vertices.push(vertex);
+        self.vertices.len() - 1
+    }
+
+    /// The triangles as polygons.
+    pub fn to_multi_polygon(&self) -> MultiPolygonZ<T> {
+        MultiPolygonZ(self.triangles().map(Triangle::to_polygon).collect())
+    }
+}
+
+impl<T: CoordFloat> TinZ<T> {
+    /// The normal at each vertex, smoothed over the triangles meeting there: the
+    /// sum of their normals weighted by their areas, scaled to unit length.
+    ///
+    /// Vertices on no triangle, or where the triangles cancel out, get a zero
+    /// normal. Triangles should wind the same way, as
+    /// [`TinZ::recompute_normals`] leaves them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{coordZ, TinZ};
+    ///
+    /// // a roof of two slopes meeting at a ridge along y, triangulated alike
+    /// let roof: TinZ = TinZ::new(
+    ///     vec![
+    ///         coordZ! { x: 0., y: 0., z: 0. },
+    ///         coordZ! { x: 1., y: 0., z: 1. },
+    ///         coordZ! { x: 2., y: 0., z: 0. },
+    ///         coordZ! { x: 0., y: 1., z: 0. },
+    ///         coordZ! { x: 1., y: 1., z: 1. },
+    ///         coordZ! { x: 2., y: 1., z: 0. },
+    ///     ],
+    ///     vec![[0, 1, 4], [0, 4, 3], [1, 2, 4], [2, 5, 4]],
+    /// );
+    /// let normals = roof.vertex_normals();
+    /// // straight up on the ridge, and square to the slopes at the eaves
+    /// assert!((normals[1].z - 1.).abs() < 1e-12);
+    /// assert!((normals[3].x + 0.5f64.sqrt()).abs() < 1e-12);
+    /// ```
+    pub fn vertex_normals(&self) -> Vec<CoordZ<T>> {
+        let mut normals = vec![CoordZ::zero(); self.vertices.len()];
+        for (&corners, Triangle(a, b, c)) in self.triangles.iter().zip(self.triangles()) {
+            // twice the area in length
+            let normal = cross(b - a, c - a);
+            for v in corners {
+                normals[v] += normal;
+            }
+        }
+        normals
+            .into_iter()
+            .map(|normal| {
+                let length = norm(normal);
+                if length > T::zero() {
+                    normal / length
+                } else {
+                    normal
+                }
+            })
+            .collect()
+    }
+
+    /// The angle in degrees between the normals of the two triangles at each edge
+    /// they share, by the indices of the ends of the edge, lowest first.
+    ///
+    /// The angle is 0 where the surface is flat and grows as it folds, towards 180
+    /// where it folds back on itself. Edges on the boundary or shared by more than
+    /// two triangles, and edges of triangles without area, are left out.
+    pub fn dihedral_angles(&self) -> Vec<([usize; 2], T)> {
+        let mut edges: BTreeMap<[usize; 2], Vec<usize>> = BTreeMap::new();
+        for (index, &[a, b, c]) in self.triangles.iter().enumerate() {
+            for (p, q) in [(a, b), (b, c), (c, a)] {
+                edges.entry([p.min(q), p.max(q)]).or_default().push(index);
+            }
+        }
+        let normals: Vec<_> = self.triangles().map(|t| t.normal()).collect();
+        edges
+            .into_iter()
+            .filter_map(|(edge, triangles)| {
+                let &[s, t] = triangles.as_slice() else {
+                    return None;
+                };
+                let cos = dot(normals[s]?, normals[t]?).max(-T::one()).min(T::one());
+                Some((edge, cos.acos().to_degrees()))
+            })
+            .collect()
+    }
+
+    /// The creases of the surface: the edges where the angle between the normals
+    /// of the triangles on either side is more than `angle_threshold` degrees,
+    /// such as the eaves and ridges of buildings, joined into the longest lines
+    /// they form.
+    ///
+    /// See [`TinZ::dihedral_angles`] for the edges considered. The lines run
+    /// through each vertex where exactly two creases meet and stop where one or
+    /// three or more do; see [`MultiLineStringZ::line_merge`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{coordZ, TinZ};
+    ///
+    /// // a roof of two slopes meeting at a right angle along a ridge
+    /// let roof: TinZ = TinZ::new(
+    ///     vec![
+    ///         coordZ! { x: 0., y: 0., z: 0. },
+    ///         coordZ! { x: 1., y: 0., z: 1. },
+    ///         coordZ! { x: 2., y: 0., z: 0. },
+    ///         coordZ! { x: 0., y: 1., z: 0. },
+    ///         coordZ! { x: 1., y: 1., z: 1. },
+    ///         coordZ! { x: 2., y: 1., z: 0. },
+    ///     ],
+    ///     vec![[0, 1, 4], [0, 4, 3], [1, 2, 4], [2, 5, 4]],
+    /// );
+    /// let ridge = roof.sharp_edges(30.);
+    /// assert_eq!(ridge.0.len(), 1);
+    /// assert!(ridge.0[0].coords().all(|c| c.x == 1. && c.z == 1.));
+    /// assert!(roof.sharp_edges(100.).0.is_empty());
+    /// ```
+    pub fn sharp_edges(&self, angle_threshold: T) -> MultiLineStringZ<T> {
+        let creases: MultiLineStringZ<T> = self
+            .dihedral_angles()
+            .into_iter()
+            .filter(|&(_, angle)| angle > angle_threshold)
+            .map(|([p, q], _)| LineStringZ::new(vec![self.vertices[p], self.vertices[q]]))
+            .collect();
+        creases.line_merge(T::zero(), T::zero())
+    }
+}
+
+impl<T: CoordNum> PartialEq for TinZ<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.vertices == other.vertices && self.triangles == other.triangles
+    }
+}
+
+impl<T: CoordNum + Eq> Eq for TinZ<T> {}
+
+impl<T: CoordNum + Hash> Hash for TinZ<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.vertices.hash(state);
+        self.triangles.hash(state);
+    }
+}
+
+/// Builds a TIN from separate triangles, without sharing their vertices.
+impl<T: CoordNum> FromIterator<Triangle<T>> for TinZ<T> {
+    fn from_iter<I: IntoIterator<Item = Triangle<T>>>(iter: I) -> Self {
+        let mut tin = Self::empty();
+        for triangle in iter {
+            let [a, b, c] = triangle.to_array().map(|vertex| tin.push_vertex(vertex));
+            tin.triangles.push([a, b, c]);
+        }
+        tin
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: CoordFloat> TinZ<T> {
+    /// The first point where `ray` meets the surface, if it does.
+    pub fn raycast(&self, ray: &Ray<T>) -> Option<PointZ<T>> {
+        self.first_hit(ray, T::zero(), T::infinity())
+            .map(|t| PointZ(ray.at(t)))
+    }
+
+    /// Whether the straight line between `a` and `b` passes above or below the
+    /// surface without meeting it.
+    ///
+    /// The ends themselves may lie on the surface, as observers and targets
+    /// standing on terrain do.
+    pub fn line_of_sight(&self, a: PointZ<T>, b: PointZ<T>) -> bool {
+        let ray = Ray::new(a.0, b.0 - a.0);
+        let margin = T::epsilon().sqrt();
+        self.first_hit(&ray, margin, T::one() - margin).is_none()
+    }
+
+    /// The height of the highest point of the surface above or below `point`.
+    pub fn elevation_at(&self, point: Point<T>) -> Option<T> {
+        let top = self.vertices.iter().map(|vertex| vertex.z).reduce(T::max)?;
+        let above = CoordZ {
+            x: point.x(),
+            y: point.y(),
+            z: top + T::one(),
+        };
+        let down = CoordZ {
+            x: T::zero(),
+            y: T::zero(),
+            z: -T::one(),
+        };
+        self.raycast(&Ray::new(above, down)).map(|hit| hit.z())
+    }
+
+    /// The parts of the surface within `radius` of `observer`, measured
+    /// horizontally, that can be seen from `observer_height` above the surface at
+    /// `observer`.
+    ///
+    /// Each triangle is split into up to [`VIEWSHED_STEPS`] × [`VIEWSHED_STEPS`]
+    /// smaller triangles no wider than `radius / VIEWSHED_STEPS` where possible,
+    /// and those whose centres can be seen are returned. Nothing is visible from an
+    /// observer off the surface.
+    ///
+    /// See [`TinZ::par_viewshed`] to spread the work over threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{coordZ, Point, TinZ};
+    ///
+    /// // a ridge running along y, 10 high at x = 5
+    /// let ridge: TinZ = TinZ::new(
+    ///     vec![
+    ///         coordZ! { x: 0., y: 0., z: 0. },
+    ///         coordZ! { x: 5., y: 0., z: 10. },
+    ///         coordZ! { x: 10., y: 0., z: 0. },
+    ///         coordZ! { x: 0., y: 10., z: 0. },
+    ///         coordZ! { x: 5., y: 10., z: 10. },
+    ///         coordZ! { x: 10., y: 10., z: 0. },
+    ///     ],
+    ///     vec![[0, 1, 4], [0, 4, 3], [1, 2, 5], [1, 5, 4]],
+    /// );
+    ///
+    /// // from the western foot of the ridge, the far side is out of sight
+    /// let visible = ridge.viewshed(Point::new(1., 5.), 20., 2.);
+    /// assert!(!visible.0.is_empty());
+    /// let mut corners = visible.iter().flat_map(|p| p.exterior().coords());
+    /// assert!(corners.all(|c| c.x <= 5. + 1e-9));
+    /// ```
+    pub fn viewshed(&self, observer: Point<T>, radius: T, observer_height: T) -> MultiPolygonZ<T> {
+        let Some(eye) = self.eye(observer, observer_height) else {
+            return MultiPolygonZ::empty();
+        };
+        MultiPolygonZ(
+            (0..self.len())
+                .flat_map(|index| self.visible_pieces(index, eye, radius))
+                .collect(),
+        )
+    }
+
+    fn eye(&self, observer: Point<T>, observer_height: T) -> Option<PointZ<T>> {
+        let ground = self.elevation_at(observer)?;
+        Some(PointZ::new(
+            observer.x(),
+            observer.y(),
+            ground + observer_height,
+        ))
+    }
+
+    /// The pieces of a triangle within `radius` of `eye` that can be seen from it.
+    fn visible_pieces(&self, index: usize, eye: PointZ<T>, radius: T) -> Vec<PolygonZ<T>> {
+        let Triangle(a, b, c) = self.triangle(index);
+        let horizontal = |p: CoordZ<T>| (p.x - eye.x()).hypot(p.y - eye.y());
+        let longest = norm(b - a).max(norm(c - b)).max(norm(a - c));
+        let nearest = horizontal(a).min(horizontal(b)).min(horizontal(c));
+        if nearest - longest > radius {
+            return Vec::new();
+        }
+
+        // split the triangle into steps × steps smaller ones of the same shape
+        let step = radius / T::from(VIEWSHED_STEPS).unwrap();
+        let steps = (longest / step)
+            .ceil()
+            .to_usize()
+            .unwrap_or(1)
+            .clamp(1, VIEWSHED_STEPS);
+        let n = T::from(steps).unwrap();
+        let (ab, ac) = (b - a, c - a);
+        let at = |i: usize, j: usize| {
+            a + ab * (T::from(i).unwrap() / n) + ac * (T::from(j).unwrap() / n)
+        };
+        let three = T::from(3).unwrap();
+        let mut pieces = Vec::new();
+        for i in 0..steps {
+            for j in 0..steps - i {
+                let mut corners = vec![[at(i, j), at(i + 1, j), at(i, j + 1)]];
+                if i + j + 1 < steps {
+                    corners.push([at(i + 1, j), at(i + 1, j + 1), at(i, j + 1)]);
+                }
+                for [p, q, r] in corners {
+                    let centre = (p + q + r) / three;
+                    if horizontal(centre) <= radius && self.line_of_sight(eye, PointZ(centre)) {
+                        pieces.push(Triangle(p, q, r).to_polygon());
+                    }
+                }
+            }
+        }
+        pieces
+    }
+
+    /// The parameter of the first point along `ray` meeting the surface, between
+    /// `min` and `max`.
+    fn first_hit(&self, ray: &Ray<T>, min: T, max: T) -> Option<T> {
+        let bvh = self
+            .bvh
+            .get_or_init(|| Bvh::new(self.triangles().collect()));
+        bvh.first_hit(ray, |_, &triangle| {
+            intersect_triangle(ray, triangle).filter(|&t| min <= t && t <= max)
+        })
+        .map(|(_, t)| t)
+    }
+}
+
+#[cfg(feature = "multithreading")]
+impl<T: CoordFloat + Send + Sync> TinZ<T> {
+    /// Like [`TinZ::viewshed`], but spreads the triangles over the rayon thread
+    /// pool.
+    pub fn par_viewshed(
+        &self,
+        observer: Point<T>,
+        radius: T,
+        observer_height: T,
+    ) -> MultiPolygonZ<T> {
+        use rayon::prelude::*;
+
+        let Some(eye) = self.eye(observer, observer_height) else {
+            return MultiPolygonZ::empty();
+        };
+        MultiPolygonZ(
+            (0..self.len())
+                .into_par_iter()
+                .flat_map_iter(|index| self.visible_pieces(index, eye, radius))
+                .collect(),
+        )
+    }
+}
+
+/// The number of steps [`TinZ::viewshed`] divides the radius into, which is
+/// also the most pieces it splits each edge of a triangle into.
+#[cfg(feature = "std")]
+pub const VIEWSHED_STEPS: usize = 100;
+
+/// The parameter at which `ray` meets `triangle`, by the Möller–Trumbore algorithm.
+#[cfg(feature = "std")]
+fn intersect_triangle<T: CoordFloat>(ray: &Ray<T>, triangle: Triangle<T>) -> Option<T> {
+    let Triangle(a, b, c) = triangle;
+    let (ab, ac) = (b - a, c - a);
+    let p = cross(ray.direction, ac);
+    let determinant = dot(ab, p);
+    if determinant.abs() <= T::epsilon() * dot(ab, ab).max(dot(ac, ac)) * norm(ray.direction) {
+        // the ray runs parallel to the triangle
+        return None;
+    }
+    let offset = ray.origin - a;
+    let u = dot(offset, p) / determinant;
+    if u < T::zero() || u > T::one() {
+        return None;
+    }
+    let q = cross(offset, ab);
+    let v = dot(ray.direction, q) / determinant;
+    if v < T::zero() || u + v > T::one() {
+        return None;
+    }
+    let t = dot(ac, q) / determinant;
+    (t >= T::zero()).then_some(t)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn tin() {
+        let mut tin = TinZ::new(
+            vec![
+                CoordZ { x: 0, y: 0, z: 0 },
+                CoordZ { x: 2, y: 0, z: 1 },
+                CoordZ { x: 0, y: 2, z: 2 },
+            ],
+            vec![[0, 1, 2]],
+        );
+        assert_eq!(tin.len(), 1);
+        assert_eq!(
+            tin.triangle(0).to_array(),
+            [tin.vertices()[0], tin.vertices()[1], tin.vertices()[2]]
+        );
+        let d = tin.push_vertex(CoordZ { x: 2, y: 2, z: 3 });
+        tin.push([1, d, 2]);
+        assert_eq!(tin.to_multi_polygon().0.len(), 2);
+
+        let separate: TinZ<i32> = tin.triangles().collect();
+        assert_eq!(separate.vertices().len(), 6);
+        assert_ne!(separate, tin);
+    }
+
+    #[test]
+    fn vertex_normals() {
+        // a square tipped up along x, and a vertex on no triangle
+        let mut tin = TinZ::new(
+            vec![
+                CoordZ::from((0., 0., 0.)),
+                CoordZ::from((1., 0., 1.)),
+                CoordZ::from((1., 1., 1.)),
+                CoordZ::from((0., 1., 0.)),
+                CoordZ::from((5., 5., 5.)),
+            ],
+            vec![[0, 1, 2], [0, 2, 3]],
+        );
+        let slope = CoordZ::from((-1., 0., 1.)) / 2f64.sqrt();
+        let normals = tin.vertex_normals();
+        assert_eq!(normals.len(), 5);
+        for normal in &normals[..4] {
+            assert!(norm(*normal - slope) < 1e-12);
+        }
+        assert_eq!(normals[4], CoordZ::zero());
+
+        // straight up on a ridge between two equal slopes
+        let foot = tin.push_vertex(CoordZ::from((2., 0., 0.)));
+        tin.push([1, foot, 2]);
+        let normal = tin.vertex_normals()[1];
+        assert!(norm(normal - CoordZ::from((0., 0., 1.))) < 1e-12);
+    }
+
+    #[test]
+    fn sharp_edges() {
+        // the surface of a unit box, vertex x + 2y + 4z at (x, y, z)
+        let vertices = (0..8)
+            .map(|i| CoordZ::from(((i & 1) as f64, (i >> 1 & 1) as f64, (i >> 2) as f64)))
+            .collect();
+        let faces = [
+            [0, 2, 3, 1],
+            [4, 5, 7, 6],
+            [0, 1, 5, 4],
+            [2, 6, 7, 3],
+            [0, 4, 6, 2],
+            [1, 3, 7, 5],
+        ];
+        let triangles = faces
+            .iter()
+            .flat_map(|&[a, b, c, d]| [[a, b, c], [a, c, d]])
+            .collect();
+        let cube = TinZ::new(vertices, triangles);
+
+        let angles = cube.dihedral_angles();
+        assert_eq!(angles.len(), 18);
+        assert_eq!(
+            angles
+                .iter()
+                .filter(|(_, angle)| angle.abs() < 1e-9)
+                .count(),
+            6
+        );
+        assert_eq!(
+            angles
+                .iter()
+                .filter(|(_, angle)| (angle - 90.).abs() < 1e-9)
+                .count(),
+            12
+        );
+
+        // the edges stop at the corners, where three meet
+        let edges = cube.sharp_edges(45.);
+        assert_eq!(edges.0.len(), 12);
+        assert!(edges.iter().all(|edge| edge.0.len() == 2));
+        assert!(cube.sharp_edges(91.).0.is_empty());
+
+        // the open top leaves out the edges around it
+        let mut open = TinZ::new(cube.vertices().to_vec(), cube.triangle_indices().to_vec());
+        open.triangles.drain(2..4);
+        assert_eq!(open.dihedral_angles().len(), 13);
+        assert_eq!(open.sharp_edges(45.).0.len(), 8);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn raycast() {
+        // a 20 × 20 grid of cells over a bumpy surface
+        let height = |x: f64, y: f64| (x / 3.).sin() * (y / 4.).cos() * 5.;
+        let mut tin = TinZ::empty();
+        for j in 0..=20 {
+            for i in 0..=20 {
+                let (x, y) = (i as f64, j as f64);
+                tin.push_vertex(CoordZ {
+                    x,
+                    y,
+                    z: height(x, y),
+                });
+            }
+        }
+        for j in 0..20 {
+            for i in 0..20 {
+                let corner = j * 21 + i;
+                tin.push([corner, corner + 1, corner + 22]);
+                tin.push([corner, corner + 22, corner + 21]);
+            }
+        }
+
+        // straight down onto a vertex, and through the middle of a cell
+        let down = CoordZ {
+            x: 0.,
+            y: 0.,
+            z: -1.,
+        };
+        let hit = tin.raycast(&Ray::new(
+            CoordZ {
+                x: 7.,
+                y: 12.,
+                z: 50.,
+            },
+            down,
+        ));
+        assert_relative_eq!(
+            hit.unwrap(),
+            PointZ::new(7., 12., height(7., 12.)),
+            epsilon = 1e-9
+        );
+        let hit = tin
+            .raycast(&Ray::new(
+                CoordZ {
+                    x: 7.5,
+                    y: 12.25,
+                    z: 50.,
+                },
+                down,
+            ))
+            .unwrap();
+        let brute = tin
+            .triangles()
+            .filter_map(|t| {
+                intersect_triangle(
+                    &Ray::new(
+                        CoordZ {
+                            x: 7.5,
+                            y: 12.25,
+                            z: 50.,
+                        },
+                        down,
+                    ),
+                    t,
+                )
+            })
+            .fold(f64::INFINITY, f64::min);
+        assert_relative_eq!(hit.z(), 50. - brute, epsilon = 1e-9);
+
+        // slanting rays agree with testing every triangle
+        for (i, direction) in [(1., 0.3, -0.2), (-0.5, 1., -0.1), (0.2, 0.2, 1.)]
+            .into_iter()
+            .enumerate()
+        {
+            let ray = Ray::new(
+                CoordZ {
+                    x: 1. + i as f64,
+                    y: 2.,
+                    z: 8.,
+                },
+                direction.into(),
+            );
+            let brute = tin
+                .triangles()
+                .filter_map(|t| intersect_triangle(&ray, t))
+                .reduce(f64::min);
+            assert_eq!(tin.raycast(&ray), brute.map(|t| PointZ(ray.at(t))));
+        }
+        assert_eq!(
+            tin.raycast(&Ray::new(
+                CoordZ {
+                    x: 50.,
+                    y: 50.,
+                    z: 0.
+                },
+                down
+            )),
+            None
+        );
+
+        // points above the surface, on either side of a hill
+        let above = |x: f64, y: f64| PointZ::new(x, y, height(x, y) + 1.);
+        assert!(tin.line_of_sight(above(4., 1.5), above(5., 2.5)));
+        assert!(!tin.line_of_sight(above(1., 1.5), above(9., 1.5)));
+        assert!(tin.line_of_sight(PointZ::new(1., 1.5, 20.), PointZ::new(9., 1.5, 20.)));
+        // the ends may lie on the surface
+        let ground = |x: f64, y: f64| PointZ::new(x, y, height(x, y));
+        assert!(tin.line_of_sight(ground(2., 2.), PointZ::new(2., 2., 30.)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn viewshed() {
+        let area = |visible: &MultiPolygonZ| {
+            visible
+                .iter()
+                .map(|p| {
+                    let c: Vec<_> = p.exterior().coords().copied().collect();
+                    norm(cross(c[1] - c[0], c[2] - c[0])) / 2.
+                })
+                .sum::<f64>()
+        };
+
+        // everything within the radius can be seen across a plain
+        let plain = TinZ::new(
+            vec![
+                CoordZ {
+                    x: -10.,
+                    y: -10.,
+                    z: 0.,
+                },
+                CoordZ {
+                    x: 10.,
+                    y: -10.,
+                    z: 0.,
+                },
+                CoordZ {
+                    x: 10.,
+                    y: 10.,
+                    z: 0.,
+                },
+                CoordZ {
+                    x: -10.,
+                    y: 10.,
+                    z: 0.,
+                },
+            ],
+            vec![[0, 1, 2], [0, 2, 3]],
+        );
+        assert_eq!(plain.elevation_at(Point::new(3., 4.)), Some(0.));
+        assert_eq!(plain.elevation_at(Point::new(30., 4.)), None);
+        let visible = plain.viewshed(Point::new(0., 0.), 5., 1.7);
+        assert_relative_eq!(
+            area(&visible),
+            core::f64::consts::PI * 25.,
+            max_relative = 0.02
+        );
+        assert!(plain.viewshed(Point::new(30., 0.), 5., 1.7).0.is_empty());
+
+        // a wall across a plain hides what lies behind it
+        let mut walled = plain.clone();
+        let foot = [(1., -10.), (1., 10.), (1.5, 10.), (1.5, -10.)];
+        let base = walled.vertices().len();
+        for (x, y) in foot {
+            walled.push_vertex(CoordZ { x, y, z: 0. });
+            walled.push_vertex(CoordZ { x, y, z: 5. });
+        }
+        for face in 0..4 {
+            let (p, q) = (base + 2 * face, base + 2 * ((face + 1) % 4));
+            walled.push([p, q, q + 1]);
+            walled.push([p, q + 1, p + 1]);
+        }
+        let visible = walled.viewshed(Point::new(0., 0.), 5., 1.7);
+        assert!(visible
+            .iter()
+            .flat_map(|p| p.exterior().coords())
+            .all(|c| c.x <= 1.5 + 1e-9));
+        // the ground in front of the wall, a circle less the segment beyond x = 1
+        let ground = visible
+            .iter()
+            .filter(|p| p.exterior().coords().all(|c| c.z == 0.))
+            .cloned()
+            .collect();
+        let segment = 25. * (0.2f64).acos() - 24f64.sqrt();
+        assert_relative_eq!(
+            area(&ground),
+            core::f64::consts::PI * 25. - segment,
+            max_relative = 0.02
+        );
+
+        #[cfg(feature = "multithreading")]
+        assert_eq!(walled.par_viewshed(Point::new(0., 0.), 5., 1.7), visible);
+    }
+}
@@ -0,0 +1,346 @@
+use alloc::vec::Vec;
+
+use crate::algorithm::linalg::norm;
+use crate::{CoordFloat, CoordNum, CoordZ, Error, LineStringZ, PointZ};
+
+/// The path of a moving object, such as a drone or a vessel, with the time at
+/// which it was at each vertex.
+///
+/// Times are in any unit, typically seconds, and increase strictly along the
+/// path. Speeds are in units of the coordinates per unit of time.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{line_string_z, PointZ, Trajectory};
+///
+/// // climbing 30 m while flying 40 m east, then hovering
+/// let flight = Trajectory::try_new(
+///     line_string_z![
+///         (x: 0., y: 0., z: 0.),
+///         (x: 40., y: 0., z: 30.),
+///         (x: 40., y: 0., z: 30.),
+///     ],
+///     vec![0., 10., 70.],
+/// )
+/// .unwrap();
+/// assert_eq!(flight.speeds(), [5., 0.]);
+/// assert_eq!(flight.position_at(6.), Some(PointZ::new(24., 0., 18.)));
+///
+/// let every_5s = flight.resample(5.);
+/// assert_eq!(every_5s.len(), 15);
+/// ```
+#[derive(Eq, PartialEq, Clone, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct Trajectory<T: CoordNum = f64> {
+    path: LineStringZ<T>,
+    times: Vec<T>,
+}
+
+/// A place where a [`Trajectory`] stayed, as found by
+/// [`Trajectory::stay_points`].
+#[derive(Eq, PartialEq, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct StayPoint<T: CoordNum = f64> {
+    /// The mean position of the vertices during the stay.
+    pub position: PointZ<T>,
+    pub arrival: T,
+    pub departure: T,
+}
+
+impl<T: CoordFloat> Trajectory<T> {
+    /// Creates a trajectory from its path and the time at each vertex.
+    ///
+    /// Fails with [`Error::MismatchedLength`] if there isn't one time per vertex,
+    /// and [`Error::InvalidTrajectory`] unless the times are finite and strictly
+    /// increasing.
+    pub fn try_new(path: LineStringZ<T>, times: Vec<T>) -> Result<Self, Error> {
+        if times.len() != path.0.len() {
+            return Err(Error::MismatchedLength {
+                expected: path.0.len(),
+                found: times.len(),
+            });
+        }
+        if !times.iter().all(|time| time.is_finite()) {
+            return Err(Error::InvalidTrajectory {
+                reason: "times must be finite",
+            });
+        }
+        if times.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(Error::InvalidTrajectory {
+                reason: "times must increase along the path",
+            });
+        }
+        Ok(Self { path, times })
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            path: LineStringZ::empty(),
+            times: Vec::new(),
+        }
+    }
+
+    pub fn path(&self) -> &LineStringZ<T> {
+        &self.path
+    }
+
+    /// The time at each vertex of the path.
+    pub fn times(&self) -> &[T] {
+        &self.times
+    }
+
+    /// The number of vertices.
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+
+    pub fn into_inner(self) -> (LineStringZ<T>, Vec<T>) {
+        (self.path, self.times)
+    }
+
+    /// The time from the first vertex to the last, zero if there are fewer than
+    /// two.
+    pub fn duration(&self) -> T {
+        match (self.times.first(), self.times.last()) {
+            (Some(&first), Some(&last)) => last - first,
+            _ => T::zero(),
+        }
+    }
+
+    /// The position at `time`, interpolated linearly between the vertices on
+    /// either side, or `None` outside the times of the trajectory.
+    pub fn position_at(&self, time: T) -> Option<PointZ<T>> {
+        // the first vertex after `time`
+        let after = self.times.partition_point(|&t| t <= time);
+        match after {
+            0 => None,
+            after if after == self.len() => {
+                (self.times[after - 1] == time).then(|| PointZ(self.path[after - 1]))
+            }
+            after => {
+                let (t0, t1) = (self.times[after - 1], self.times[after]);
+                let (a, b) = (self.path[after - 1], self.path[after]);
+                Some(PointZ(a + (b - a) * ((time - t0) / (t1 - t0))))
+            }
+        }
+    }
+
+    /// The mean speed along each segment, one fewer than there are vertices.
+    pub fn speeds(&self) -> Vec<T> {
+        self.segments()
+            .map(|((a, t0), (b, t1))| norm(b - a) / (t1 - t0))
+            .collect()
+    }
+
+    /// The rate of change of speed from each segment to the next, over the time
+    /// between their midpoints, two fewer than there are vertices.
+    pub fn accelerations(&self) -> Vec<T> {
+        let two = T::one() + T::one();
+        let speeds = self.speeds();
+        let midpoints: Vec<T> = self
+            .times
+            .windows(2)
+            .map(|pair| (pair[0] + pair[1]) / two)
+            .collect();
+        speeds
+            .windows(2)
+            .zip(midpoints.windows(2))
+            .map(|(speed, midpoint)| (speed[1] - speed[0]) / (midpoint[1] - midpoint[0]))
+            .collect()
+    }
+
+    /// The trajectory sampled every `interval` from its first time, up to its
+    /// last.
+    ///
+    /// # Panics
+    ///
+    /// If `interval` isn't positive and finite.
+    pub fn resample(&self, interval: T) -> Self {
+        assert!(
+            interval > T::zero() && interval.is_finite(),
+            "interval must be positive and finite"
+        );
+        let Some((&start, &end)) = self.times.first().zip(self.times.last()) else {
+            return Self::empty();
+        };
+        let mut path = LineStringZ::empty();
+        let mut times = Vec::new();
+        for step in 0usize.. {
+            let time = start + interval * T::from(step).unwrap();
+            let Some(position) = (time <= end).then(|| self.position_at(time)).flatten() else {
+                break;
+            };
+            path.push(position.0);
+            times.push(time);
+        }
+        Self { path, times }
+    }
+
+    /// The places where the trajectory stayed within `max_distance` of where it
+    /// arrived for at least `min_duration`, in order.
+    ///
+    /// Each stay starts at a vertex and takes in the vertices after it up to the
+    /// first that strays further than `max_distance` from it, where the search
+    /// for the next stay starts.
+    pub fn stay_points(&self, max_distance: T, min_duration: T) -> Vec<StayPoint<T>> {
+        let mut stays = Vec::new();
+        let mut i = 0;
+        while i < self.len() {
+            let arrival = self.path[i];
+            let end = (i + 1..self.len())
+                .find(|&j| norm(self.path[j] - arrival) > max_distance)
+                .unwrap_or(self.len());
+            let departure = self.times[end - 1];
+            if departure - self.times[i] >= min_duration && end - i > 1 {
+                let sum = self.path.0[i..end]
+                    .iter()
+                    .fold(CoordZ::zero(), |sum, &coord| sum + coord);
+                stays.push(StayPoint {
+                    position: PointZ(sum / T::from(end - i).unwrap()),
+                    arrival: self.times[i],
+                    departure,
+                });
+                i = end;
+            } else {
+                i += 1;
+            }
+        }
+        stays
+    }
+
+    /// Splits the trajectory where more than `max_gap` passes between one vertex
+    /// and the next, as where a tracker lost its signal.
+    pub fn split_at_gaps(&self, max_gap: T) -> Vec<Self> {
+        let mut pieces = Vec::new();
+        let mut start = 0;
+        for end in 1..=self.len() {
+            if end == self.len() || self.times[end] - self.times[end - 1] > max_gap {
+                pieces.push(Self {
+                    path: LineStringZ(self.path.0[start..end].to_vec()),
+                    times: self.times[start..end].to_vec(),
+                });
+                start = end;
+            }
+        }
+        pieces
+    }
+
+    /// Each segment, as its ends and their times.
+    fn segments(&self) -> impl Iterator<Item = ((CoordZ<T>, T), (CoordZ<T>, T))> + '_ {
+        self.path
+            .0
+            .windows(2)
+            .zip(self.times.windows(2))
+            .map(|(coords, times)| ((coords[0], times[0]), (coords[1], times[1])))
+    }
+}
+
+impl<T: CoordFloat> Default for Trajectory<T> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    fn trajectory(points: &[(f64, f64, f64, f64)]) -> Trajectory {
+        let (path, times): (Vec<(f64, f64, f64)>, Vec<f64>) =
+            points.iter().map(|&(x, y, z, t)| ((x, y, z), t)).unzip();
+        Trajectory::try_new(path.into(), times).unwrap()
+    }
+
+    #[test]
+    fn try_new() {
+        let path: LineStringZ = vec![(0., 0., 0.), (1., 0., 0.)].into();
+        assert!(matches!(
+            Trajectory::try_new(path.clone(), vec![0.]),
+            Err(Error::MismatchedLength {
+                expected: 2,
+                found: 1
+            })
+        ));
+        assert!(matches!(
+            Trajectory::try_new(path.clone(), vec![1., 1.]),
+            Err(Error::InvalidTrajectory { .. })
+        ));
+        assert!(matches!(
+            Trajectory::try_new(path.clone(), vec![0., f64::NAN]),
+            Err(Error::InvalidTrajectory { .. })
+        ));
+        let trajectory = Trajectory::try_new(path, vec![2., 4.]).unwrap();
+        assert_eq!(trajectory.duration(), 2.);
+        assert_eq!(Trajectory::<f64>::empty().duration(), 0.);
+    }
+
+    #[test]
+    fn motion() {
+        // speeding up from 1 to 3, then stopping
+        let trajectory = trajectory(&[
+            (0., 0., 0., 0.),
+            (2., 0., 0., 2.),
+            (2., 6., 0., 4.),
+            (2., 6., 0., 5.),
+        ]);
+        assert_eq!(trajectory.speeds(), [1., 3., 0.]);
+        assert_eq!(trajectory.accelerations(), [1., -2.]);
+
+        assert_eq!(trajectory.position_at(-1.), None);
+        assert_eq!(trajectory.position_at(0.), Some(PointZ::new(0., 0., 0.)));
+        assert_eq!(trajectory.position_at(3.), Some(PointZ::new(2., 3., 0.)));
+        assert_eq!(trajectory.position_at(5.), Some(PointZ::new(2., 6., 0.)));
+        assert_eq!(trajectory.position_at(5.5), None);
+
+        let resampled = trajectory.resample(1.5);
+        assert_eq!(resampled.times(), [0., 1.5, 3., 4.5]);
+        assert_eq!(resampled.path()[3], CoordZ::from((2., 6., 0.)));
+        assert_eq!(trajectory.resample(5.).len(), 2);
+        assert!(Trajectory::<f64>::empty().resample(1.).is_empty());
+    }
+
+    #[test]
+    fn stays_and_gaps() {
+        // a vessel moored for a while, sailing on, then lost and found again
+        let trajectory = trajectory(&[
+            (0., 0., 0., 0.),
+            (10., 0., 0., 10.),
+            (10.5, 0.5, 0., 100.),
+            (9.5, 0., 0., 800.),
+            (10., -0.5, 0., 1000.),
+            (30., 0., 0., 1020.),
+            (50., 0., 0., 1040.),
+            (90., 0., 0., 3000.),
+        ]);
+        let stays = trajectory.stay_points(1., 600.);
+        assert_eq!(stays.len(), 1);
+        assert_eq!(stays[0].arrival, 10.);
+        assert_eq!(stays[0].departure, 1000.);
+        assert_eq!(stays[0].position, PointZ::new(10., 0., 0.));
+        assert!(trajectory.stay_points(1., 1000.).is_empty());
+
+        let pieces = trajectory.split_at_gaps(600.);
+        assert_eq!(
+            pieces.iter().map(Trajectory::len).collect::<Vec<_>>(),
+            [3, 4, 1]
+        );
+        assert_eq!(pieces[1].times()[0], 800.);
+        assert_eq!(trajectory.split_at_gaps(10_000.), vec![trajectory]);
+    }
+}
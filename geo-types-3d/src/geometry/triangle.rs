@@ -1,4 +1,4 @@
-use crate::{polygon_z, CoordNum, CoordZ, LineZ, PointZ, PolygonZ};
+use crate::{polygon_z, CoordFloat, CoordNum, CoordZ, LineZ, Plane, PointZ, PolygonZ};
 use core::cmp::Ordering;
 
 /// A bounded 2D area whose three vertices are defined by
@@ -10,6 +10,11 @@ use core::cmp::Ordering;
 /// Irrespective of input order the resulting geometry has ccw order and its vertices are yielded in ccw order by iterators
 #[derive(Copy, Clone, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub struct Triangle<T: CoordNum = f64>(pub CoordZ<T>, pub CoordZ<T>, pub CoordZ<T>);
 
 impl<T: CoordNum> Triangle<T> {
@@ -67,6 +72,32 @@ impl<T: CoordNum> Triangle<T> {
     }
 }
 
+impl<T: CoordFloat> Triangle<T> {
+    /// The unit normal of the triangle, facing the side from which its corners run
+    /// anticlockwise, or `None` if they lie on a line.
+    ///
+    /// [`Triangle::new`] puts the corners anticlockwise seen from above, so the
+    /// normal of a triangle made with it faces up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{coordZ, Triangle};
+    ///
+    /// let roof = Triangle::new(
+    ///     coordZ! { x: 0., y: 0., z: 0. },
+    ///     coordZ! { x: 0., y: 2., z: 0. },
+    ///     coordZ! { x: 1., y: 0., z: 1. },
+    /// );
+    /// let normal = roof.normal().unwrap();
+    /// assert!((normal.x + 0.5f64.sqrt()).abs() < 1e-12);
+    /// assert!((normal.z - 0.5f64.sqrt()).abs() < 1e-12);
+    /// ```
+    pub fn normal(&self) -> Option<CoordZ<T>> {
+        Plane::from_points(self.0, self.1, self.2).map(|plane| plane.normal)
+    }
+}
+
 impl<IC: Into<CoordZ<T>> + Copy, T: CoordNum> From<[IC; 3]> for Triangle<T> {
     fn from(array: [IC; 3]) -> Self {
         Self(array[0].into(), array[1].into(), array[2].into())
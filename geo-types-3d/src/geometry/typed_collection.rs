@@ -0,0 +1,168 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::ops::{Deref, Index};
+
+use crate::{CoordNum, Dimension, Error, Geometry, GeometryCollection, HasDimension};
+
+/// A collection whose members all have the same geometry type, and so the same
+/// coordinate dimensions.
+///
+/// Unlike [`GeometryCollection`], which may freely mix planar and 3D members, the
+/// member type is fixed at compile time. Converting a [`GeometryCollection`] checks
+/// every member and fails on the first one of a different type.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{polygon_z, Dimension, GeometryCollection, PolygonZ, TypedCollection};
+///
+/// let collection = GeometryCollection::from(vec![polygon_z![
+///     (x: 0.0, y: 0.0, z: 1.0),
+///     (x: 1.0, y: 0.0, z: 1.0),
+///     (x: 0.0, y: 1.0, z: 1.0),
+/// ]]);
+/// let polygons = TypedCollection::<PolygonZ>::try_from(collection).unwrap();
+/// assert_eq!(polygons.dimension(), Dimension::XYZ);
+/// assert_eq!(polygons[0].exterior().0.len(), 4);
+///
+/// let collection: GeometryCollection = polygons.into();
+/// assert_eq!(collection.len(), 1);
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+pub struct TypedCollection<G>(Vec<G>);
+
+impl<G> Default for TypedCollection<G> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<G> TypedCollection<G> {
+    pub fn new(members: Vec<G>) -> Self {
+        Self(members)
+    }
+
+    pub fn empty() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn push(&mut self, member: G) {
+        self.0.push(member)
+    }
+
+    pub fn into_inner(self) -> Vec<G> {
+        self.0
+    }
+}
+
+impl<G: HasDimension> TypedCollection<G> {
+    /// The coordinate dimensions shared by all members.
+    pub fn dimension(&self) -> Dimension {
+        G::DIMENSION
+    }
+}
+
+impl<G> Deref for TypedCollection<G> {
+    type Target = [G];
+
+    fn deref(&self) -> &[G] {
+        &self.0
+    }
+}
+
+impl<G> Index<usize> for TypedCollection<G> {
+    type Output = G;
+
+    fn index(&self, index: usize) -> &G {
+        self.0.index(index)
+    }
+}
+
+impl<G> From<Vec<G>> for TypedCollection<G> {
+    fn from(members: Vec<G>) -> Self {
+        Self(members)
+    }
+}
+
+impl<G> FromIterator<G> for TypedCollection<G> {
+    fn from_iter<I: IntoIterator<Item = G>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<G> Extend<G> for TypedCollection<G> {
+    fn extend<I: IntoIterator<Item = G>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+impl<G> IntoIterator for TypedCollection<G> {
+    type Item = G;
+    type IntoIter = alloc::vec::IntoIter<G>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, G> IntoIterator for &'a TypedCollection<G> {
+    type Item = &'a G;
+    type IntoIter = core::slice::Iter<'a, G>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T: CoordNum, G: Into<Geometry<T>>> From<TypedCollection<G>> for GeometryCollection<T> {
+    fn from(collection: TypedCollection<G>) -> Self {
+        collection.0.into_iter().collect()
+    }
+}
+
+/// Convert a [`GeometryCollection`] into a [`TypedCollection`].
+///
+/// Fails if any member is not of type `G`.
+impl<T, G> TryFrom<GeometryCollection<T>> for TypedCollection<G>
+where
+    T: CoordNum,
+    G: TryFrom<Geometry<T>, Error = Error>,
+{
+    type Error = Error;
+
+    fn try_from(collection: GeometryCollection<T>) -> Result<Self, Self::Error> {
+        collection.into_iter().map(G::try_from).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{LineStringZ, PointZ};
+    use alloc::vec;
+
+    #[test]
+    fn mixed_members_are_rejected() {
+        let collection = GeometryCollection::from(vec![
+            Geometry::from(PointZ::new(1., 2., 3.)),
+            Geometry::Point(geo_types::Point::new(1., 2.)),
+        ]);
+        let err = TypedCollection::<PointZ>::try_from(collection).unwrap_err();
+        assert!(matches!(err, Error::MismatchedGeometry { .. }));
+
+        let collection =
+            GeometryCollection::from(vec![PointZ::new(1., 2., 3.), PointZ::new(4., 5., 6.)]);
+        let points = TypedCollection::<PointZ>::try_from(collection.clone()).unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points.dimension(), Dimension::XYZ);
+        assert_eq!(GeometryCollection::from(points), collection);
+
+        assert!(TypedCollection::<LineStringZ>::try_from(collection).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+use crate::{CoordNum, CoordZ, Error, LineStringZ, LineZ, PolygonZ};
+
+/// A borrowed line string: a view over a slice of coordinates.
+///
+/// Views let the algorithms run over coordinates that live elsewhere — in a
+/// memory-mapped file, an Arrow buffer or an owned [`LineStringZ`] — without
+/// copying them. Call [`to_line_string`](Self::to_line_string) for an owned copy.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::BoundingCube;
+/// use geo_types_3d::{coordZ, Cube, LineStringZSlice};
+///
+/// let buffer = [
+///     coordZ! { x: 0., y: 0., z: 0. },
+///     coordZ! { x: 1., y: 2., z: 3. },
+///     coordZ! { x: 4., y: 0., z: 1. },
+/// ];
+/// let view = LineStringZSlice::new(&buffer[1..]);
+/// assert_eq!(view.lines().count(), 1);
+/// assert_eq!(
+///     view.bounding_cube(),
+///     Some(Cube::new((1., 0., 1.), (4., 2., 3.)))
+/// );
+/// ```
+#[derive(Eq, PartialEq, Clone, Copy, Hash, Debug)]
+pub struct LineStringZSlice<'a, T: CoordNum = f64>(pub &'a [CoordZ<T>]);
+
+impl<'a, T: CoordNum> LineStringZSlice<'a, T> {
+    pub fn new(coords: &'a [CoordZ<T>]) -> Self {
+        Self(coords)
+    }
+
+    pub fn coords(&self) -> impl DoubleEndedIterator<Item = &'a CoordZ<T>> + ExactSizeIterator {
+        self.0.iter()
+    }
+
+    /// Return an iterator yielding one [`LineZ`] for each line segment.
+    pub fn lines(&self) -> impl ExactSizeIterator<Item = LineZ<T>> + 'a {
+        self.0.windows(2).map(|w| LineZ::new(w[0], w[1]))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether the first and last coordinates are equal. Empty views are closed.
+    pub fn is_closed(&self) -> bool {
+        self.0.first() == self.0.last()
+    }
+
+    pub fn to_line_string(&self) -> LineStringZ<T> {
+        LineStringZ(self.0.to_vec())
+    }
+}
+
+impl<'a, T: CoordNum> From<&'a LineStringZ<T>> for LineStringZSlice<'a, T> {
+    fn from(line_string: &'a LineStringZ<T>) -> Self {
+        Self(&line_string.0)
+    }
+}
+
+impl<'a, T: CoordNum> From<&'a [CoordZ<T>]> for LineStringZSlice<'a, T> {
+    fn from(coords: &'a [CoordZ<T>]) -> Self {
+        Self(coords)
+    }
+}
+
+/// A borrowed polygon: views over its exterior and interior rings.
+///
+/// A view either borrows the rings of a [`PolygonZ`], or is laid over one flat
+/// coordinate buffer split into rings by offsets, the layout used by Arrow and
+/// GeoArrow.
+///
+/// Unlike [`PolygonZ::new`], a view can't close its rings, so they are used as given.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::algorithm::BoundingCube;
+/// use geo_types_3d::{coordZ, Cube, PolygonZRef};
+///
+/// let coords = [
+///     // exterior
+///     coordZ! { x: 0., y: 0., z: 1. },
+///     coordZ! { x: 4., y: 0., z: 1. },
+///     coordZ! { x: 0., y: 4., z: 1. },
+///     coordZ! { x: 0., y: 0., z: 1. },
+///     // hole
+///     coordZ! { x: 1., y: 1., z: 1. },
+///     coordZ! { x: 2., y: 1., z: 1. },
+///     coordZ! { x: 1., y: 2., z: 1. },
+///     coordZ! { x: 1., y: 1., z: 1. },
+/// ];
+/// let polygon = PolygonZRef::from_offsets(&coords, &[0, 4, 8]).unwrap();
+/// assert_eq!(polygon.num_interior_rings(), 1);
+/// assert_eq!(
+///     polygon.bounding_cube(),
+///     Some(Cube::new((0., 0., 1.), (4., 4., 1.)))
+/// );
+/// ```
+#[derive(Eq, PartialEq, Clone, Copy, Hash, Debug)]
+pub struct PolygonZRef<'a, T: CoordNum = f64> {
+    exterior: LineStringZSlice<'a, T>,
+    interiors: Interiors<'a, T>,
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Hash, Debug)]
+enum Interiors<'a, T: CoordNum> {
+    Owned(&'a [LineStringZ<T>]),
+    Offsets {
+        coords: &'a [CoordZ<T>],
+        offsets: &'a [usize],
+    },
+}
+
+impl<'a, T: CoordNum> Interiors<'a, T> {
+    fn len(&self) -> usize {
+        match self {
+            Interiors::Owned(rings) => rings.len(),
+            Interiors::Offsets { offsets, .. } => offsets.len() - 1,
+        }
+    }
+
+    fn get(&self, index: usize) -> LineStringZSlice<'a, T> {
+        match *self {
+            Interiors::Owned(rings) => (&rings[index]).into(),
+            Interiors::Offsets { coords, offsets } => {
+                LineStringZSlice(&coords[offsets[index]..offsets[index + 1]])
+            }
+        }
+    }
+}
+
+impl<'a, T: CoordNum> PolygonZRef<'a, T> {
+    /// A view over an exterior ring and owned interior rings.
+    pub fn new(exterior: LineStringZSlice<'a, T>, interiors: &'a [LineStringZ<T>]) -> Self {
+        Self {
+            exterior,
+            interiors: Interiors::Owned(interiors),
+        }
+    }
+
+    /// A view over rings stored one after another in `coords`: ring `i` spans
+    /// `coords[offsets[i]..offsets[i + 1]]`, and the first ring is the exterior.
+    ///
+    /// Fails with [`Error::InvalidOffsets`] if there is no exterior, or if the
+    /// offsets decrease or run past the end of `coords`.
+    pub fn from_offsets(coords: &'a [CoordZ<T>], offsets: &'a [usize]) -> Result<Self, Error> {
+        let valid = offsets.len() >= 2
+            && offsets.windows(2).all(|pair| pair[0] <= pair[1])
+            && offsets[offsets.len() - 1] <= coords.len();
+        if !valid {
+            return Err(Error::InvalidOffsets);
+        }
+        Ok(Self {
+            exterior: LineStringZSlice(&coords[offsets[0]..offsets[1]]),
+            interiors: Interiors::Offsets {
+                coords,
+                offsets: &offsets[1..],
+            },
+        })
+    }
+
+    pub fn exterior(&self) -> LineStringZSlice<'a, T> {
+        self.exterior
+    }
+
+    pub fn interiors(&self) -> InteriorsIter<'a, T> {
+        InteriorsIter {
+            interiors: self.interiors,
+            next: 0,
+        }
+    }
+
+    pub fn num_interior_rings(&self) -> usize {
+        self.interiors.len()
+    }
+
+    pub fn to_polygon(&self) -> PolygonZ<T> {
+        PolygonZ::new(
+            self.exterior.to_line_string(),
+            self.interiors().map(|ring| ring.to_line_string()).collect(),
+        )
+    }
+}
+
+/// An iterator over the interior rings of a [`PolygonZRef`].
+#[derive(Clone, Debug)]
+pub struct InteriorsIter<'a, T: CoordNum> {
+    interiors: Interiors<'a, T>,
+    next: usize,
+}
+
+impl<'a, T: CoordNum> Iterator for InteriorsIter<'a, T> {
+    type Item = LineStringZSlice<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == self.interiors.len() {
+            return None;
+        }
+        self.next += 1;
+        Some(self.interiors.get(self.next - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.interiors.len() - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T: CoordNum> ExactSizeIterator for InteriorsIter<'_, T> {}
+
+impl<'a, T: CoordNum> From<&'a PolygonZ<T>> for PolygonZRef<'a, T> {
+    fn from(polygon: &'a PolygonZ<T>) -> Self {
+        Self::new(polygon.exterior().into(), polygon.interiors())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::{BoundingCube, Clip, MapCoordsZ};
+    use crate::{polygon_z, Cube};
+    use alloc::vec;
+
+    #[test]
+    fn views_match_owned_geometries() {
+        let polygon = polygon_z![
+            exterior: [
+                (x: 0., y: 0., z: 0.),
+                (x: 10., y: 0., z: 0.),
+                (x: 10., y: 10., z: 10.),
+                (x: 0., y: 10., z: 10.),
+            ],
+            interiors: [
+                [
+                    (x: 1., y: 1., z: 1.),
+                    (x: 2., y: 1., z: 1.),
+                    (x: 2., y: 2., z: 2.),
+                ],
+                [
+                    (x: 8., y: 8., z: 8.),
+                    (x: 9., y: 8., z: 8.),
+                    (x: 9., y: 9., z: 9.),
+                ],
+            ],
+        ];
+        let view = PolygonZRef::from(&polygon);
+        assert_eq!(view.interiors().len(), 2);
+        assert_eq!(view.to_polygon(), polygon);
+        let cube = Cube::new((0., 0., 0.), (5., 5., 5.));
+        assert_eq!(view.clip(&cube), polygon.clip(&cube));
+        assert_eq!(view.bounding_cube(), polygon.bounding_cube());
+
+        // the same polygon, as one flat buffer
+        let mut coords = polygon.exterior().0.clone();
+        let mut offsets = vec![0, coords.len()];
+        for ring in polygon.interiors() {
+            coords.extend(&ring.0);
+            offsets.push(coords.len());
+        }
+        let flat = PolygonZRef::from_offsets(&coords, &offsets).unwrap();
+        assert_eq!(flat.to_polygon(), polygon);
+        let lift = |c: CoordZ<f64>| CoordZ { z: c.z + 1., ..c };
+        assert_eq!(flat.map_coords(lift), polygon.map_coords(lift));
+
+        assert!(PolygonZRef::from_offsets(&coords, &[0]).is_err());
+        assert!(PolygonZRef::from_offsets(&coords, &[0, 5, 4]).is_err());
+        assert!(PolygonZRef::from_offsets(&coords, &[0, coords.len() + 1]).is_err());
+    }
+}
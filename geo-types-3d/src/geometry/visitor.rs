@@ -0,0 +1,384 @@
+use geo_types::{
+    Coord, Line, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon, Rect,
+};
+
+use crate::{
+    CoordNum, CoordZ, Geometry, GeometryCollection, LineStringZ, LineZ, MultiLineStringZ,
+    MultiPointZ, MultiPolygonZ, PointZ, PolygonZ,
+};
+
+/// Visits the members of a [`Geometry`], one method per geometry type.
+///
+/// Every method has a default: single geometries are ignored, and multi-geometries
+/// and collections visit their members, through the `walk_*` functions of this
+/// module. Implement only the methods for the types you care about; visiting
+/// points, for example, also visits the points of multi-points and of nested
+/// collections. An overridden method can still descend into its members by calling
+/// the matching `walk_*` function.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{wkt, Geometry, GeometryCollection, GeometryVisitor, PointZ, PolygonZ};
+///
+/// #[derive(Default)]
+/// struct Census {
+///     points: usize,
+///     holes: usize,
+/// }
+///
+/// impl GeometryVisitor<f64> for Census {
+///     fn visit_point_z(&mut self, _: &PointZ<f64>) {
+///         self.points += 1;
+///     }
+///
+///     fn visit_polygon_z(&mut self, polygon: &PolygonZ<f64>) {
+///         self.holes += polygon.interiors().len();
+///     }
+/// }
+///
+/// let geometry = Geometry::GeometryCollection(GeometryCollection(vec![
+///     wkt! { MULTIPOINT Z((0. 0. 0.),(1. 1. 1.)) }.into(),
+///     Geometry::GeometryCollection(GeometryCollection(vec![
+///         PointZ::new(2., 2., 2.).into(),
+///     ])),
+///     wkt! {
+///         POLYGON Z((0. 0. 0.,9. 0. 0.,9. 9. 0.,0. 0. 0.),(5. 1. 0.,6. 1. 0.,6. 2. 0.,5. 1. 0.))
+///     }
+///     .into(),
+/// ]));
+/// let mut census = Census::default();
+/// geometry.accept(&mut census);
+/// assert_eq!((census.points, census.holes), (3, 1));
+/// ```
+pub trait GeometryVisitor<T: CoordNum> {
+    fn visit_point(&mut self, _point: &Point<T>) {}
+
+    fn visit_point_z(&mut self, _point: &PointZ<T>) {}
+
+    fn visit_line(&mut self, _line: &Line<T>) {}
+
+    fn visit_line_z(&mut self, _line: &LineZ<T>) {}
+
+    fn visit_line_string(&mut self, _line_string: &LineString<T>) {}
+
+    fn visit_line_string_z(&mut self, _line_string: &LineStringZ<T>) {}
+
+    fn visit_polygon(&mut self, _polygon: &Polygon<T>) {}
+
+    fn visit_polygon_z(&mut self, _polygon: &PolygonZ<T>) {}
+
+    fn visit_rect(&mut self, _rect: &Rect<T>) {}
+
+    fn visit_multi_point(&mut self, multi_point: &MultiPoint<T>) {
+        walk_multi_point(self, multi_point)
+    }
+
+    fn visit_multi_point_z(&mut self, multi_point: &MultiPointZ<T>) {
+        walk_multi_point_z(self, multi_point)
+    }
+
+    fn visit_multi_line_string(&mut self, multi_line_string: &MultiLineString<T>) {
+        walk_multi_line_string(self, multi_line_string)
+    }
+
+    fn visit_multi_line_string_z(&mut self, multi_line_string: &MultiLineStringZ<T>) {
+        walk_multi_line_string_z(self, multi_line_string)
+    }
+
+    fn visit_multi_polygon(&mut self, multi_polygon: &MultiPolygon<T>) {
+        walk_multi_polygon(self, multi_polygon)
+    }
+
+    fn visit_multi_polygon_z(&mut self, multi_polygon: &MultiPolygonZ<T>) {
+        walk_multi_polygon_z(self, multi_polygon)
+    }
+
+    fn visit_geometry_collection(&mut self, collection: &GeometryCollection<T>) {
+        walk_geometry_collection(self, collection)
+    }
+}
+
+pub fn walk_multi_point<T: CoordNum, V: GeometryVisitor<T> + ?Sized>(
+    visitor: &mut V,
+    multi_point: &MultiPoint<T>,
+) {
+    multi_point
+        .iter()
+        .for_each(|point| visitor.visit_point(point))
+}
+
+pub fn walk_multi_point_z<T: CoordNum, V: GeometryVisitor<T> + ?Sized>(
+    visitor: &mut V,
+    multi_point: &MultiPointZ<T>,
+) {
+    multi_point
+        .iter()
+        .for_each(|point| visitor.visit_point_z(point))
+}
+
+pub fn walk_multi_line_string<T: CoordNum, V: GeometryVisitor<T> + ?Sized>(
+    visitor: &mut V,
+    multi_line_string: &MultiLineString<T>,
+) {
+    multi_line_string
+        .iter()
+        .for_each(|line_string| visitor.visit_line_string(line_string))
+}
+
+pub fn walk_multi_line_string_z<T: CoordNum, V: GeometryVisitor<T> + ?Sized>(
+    visitor: &mut V,
+    multi_line_string: &MultiLineStringZ<T>,
+) {
+    multi_line_string
+        .iter()
+        .for_each(|line_string| visitor.visit_line_string_z(line_string))
+}
+
+pub fn walk_multi_polygon<T: CoordNum, V: GeometryVisitor<T> + ?Sized>(
+    visitor: &mut V,
+    multi_polygon: &MultiPolygon<T>,
+) {
+    multi_polygon
+        .iter()
+        .for_each(|polygon| visitor.visit_polygon(polygon))
+}
+
+pub fn walk_multi_polygon_z<T: CoordNum, V: GeometryVisitor<T> + ?Sized>(
+    visitor: &mut V,
+    multi_polygon: &MultiPolygonZ<T>,
+) {
+    multi_polygon
+        .0
+        .iter()
+        .for_each(|polygon| visitor.visit_polygon_z(polygon))
+}
+
+pub fn walk_geometry_collection<T: CoordNum, V: GeometryVisitor<T> + ?Sized>(
+    visitor: &mut V,
+    collection: &GeometryCollection<T>,
+) {
+    collection
+        .iter()
+        .for_each(|geometry| geometry.accept(visitor))
+}
+
+/// Visits every coordinate of a [`Geometry`], in order: polygons yield their
+/// exterior ring and then their interior rings, and the corners of a [`Rect`] go
+/// counter-clockwise from its minimum.
+///
+/// Coordinates of the planar geometry types are passed to
+/// [`visit_coord`](Self::visit_coord), which by default forwards them to
+/// [`visit_coord_z`](Self::visit_coord_z) with `z = 0`.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{wkt, CoordVisitor, CoordZ, Geometry};
+///
+/// struct MaxZ(f64);
+///
+/// impl CoordVisitor<f64> for MaxZ {
+///     fn visit_coord_z(&mut self, coord: &CoordZ<f64>) {
+///         self.0 = self.0.max(coord.z);
+///     }
+/// }
+///
+/// let geometry: Geometry = wkt! { LINESTRING Z(0. 0. 1.,1. 1. 4.,2. 0. 2.) }.into();
+/// let mut max = MaxZ(f64::MIN);
+/// geometry.accept_coords(&mut max);
+/// assert_eq!(max.0, 4.);
+/// ```
+pub trait CoordVisitor<T: CoordNum> {
+    fn visit_coord_z(&mut self, coord: &CoordZ<T>);
+
+    fn visit_coord(&mut self, coord: &Coord<T>) {
+        self.visit_coord_z(&CoordZ {
+            x: coord.x,
+            y: coord.y,
+            z: T::zero(),
+        })
+    }
+}
+
+/// Adapts a [`CoordVisitor`] into a [`GeometryVisitor`].
+struct Coords<'v, V: ?Sized>(&'v mut V);
+
+impl<'v, V: ?Sized> Coords<'v, V> {
+    fn planar<T: CoordNum>(&mut self, coords: &[Coord<T>])
+    where
+        V: CoordVisitor<T>,
+    {
+        coords.iter().for_each(|coord| self.0.visit_coord(coord))
+    }
+
+    fn z<T: CoordNum>(&mut self, coords: &[CoordZ<T>])
+    where
+        V: CoordVisitor<T>,
+    {
+        coords.iter().for_each(|coord| self.0.visit_coord_z(coord))
+    }
+}
+
+impl<T: CoordNum, V: CoordVisitor<T> + ?Sized> GeometryVisitor<T> for Coords<'_, V> {
+    fn visit_point(&mut self, point: &Point<T>) {
+        self.0.visit_coord(&point.0)
+    }
+
+    fn visit_point_z(&mut self, point: &PointZ<T>) {
+        self.0.visit_coord_z(&point.0)
+    }
+
+    fn visit_line(&mut self, line: &Line<T>) {
+        self.planar(&[line.start, line.end])
+    }
+
+    fn visit_line_z(&mut self, line: &LineZ<T>) {
+        self.z(&[line.start, line.end])
+    }
+
+    fn visit_line_string(&mut self, line_string: &LineString<T>) {
+        self.planar(&line_string.0)
+    }
+
+    fn visit_line_string_z(&mut self, line_string: &LineStringZ<T>) {
+        self.z(&line_string.0)
+    }
+
+    fn visit_polygon(&mut self, polygon: &Polygon<T>) {
+        self.planar(&polygon.exterior().0);
+        for ring in polygon.interiors() {
+            self.planar(&ring.0);
+        }
+    }
+
+    fn visit_polygon_z(&mut self, polygon: &PolygonZ<T>) {
+        self.z(&polygon.exterior().0);
+        for ring in polygon.interiors() {
+            self.z(&ring.0);
+        }
+    }
+
+    fn visit_rect(&mut self, rect: &Rect<T>) {
+        let (min, max) = (rect.min(), rect.max());
+        self.planar(&[
+            min,
+            Coord { x: max.x, y: min.y },
+            max,
+            Coord { x: min.x, y: max.y },
+        ])
+    }
+}
+
+impl<T: CoordNum> Geometry<T> {
+    /// Calls the method of `visitor` for the type of this geometry.
+    pub fn accept<V: GeometryVisitor<T> + ?Sized>(&self, visitor: &mut V) {
+        match self {
+            Geometry::Point(g) => visitor.visit_point(g),
+            Geometry::PointZ(g) => visitor.visit_point_z(g),
+            Geometry::Line(g) => visitor.visit_line(g),
+            Geometry::LineZ(g) => visitor.visit_line_z(g),
+            Geometry::LineString(g) => visitor.visit_line_string(g),
+            Geometry::LineStringZ(g) => visitor.visit_line_string_z(g),
+            Geometry::Polygon(g) => visitor.visit_polygon(g),
+            Geometry::PolygonZ(g) => visitor.visit_polygon_z(g),
+            Geometry::MultiPoint(g) => visitor.visit_multi_point(g),
+            Geometry::MultiPointZ(g) => visitor.visit_multi_point_z(g),
+            Geometry::MultiLineString(g) => visitor.visit_multi_line_string(g),
+            Geometry::MultiLineStringZ(g) => visitor.visit_multi_line_string_z(g),
+            Geometry::MultiPolygon(g) => visitor.visit_multi_polygon(g),
+            Geometry::MultiPolygonZ(g) => visitor.visit_multi_polygon_z(g),
+            Geometry::GeometryCollection(g) => visitor.visit_geometry_collection(g),
+            Geometry::Rect(g) => visitor.visit_rect(g),
+        }
+    }
+
+    /// Passes every coordinate of this geometry to `visitor`.
+    pub fn accept_coords<V: CoordVisitor<T> + ?Sized>(&self, visitor: &mut V) {
+        self.accept(&mut Coords(visitor))
+    }
+}
+
+impl<T: CoordNum> GeometryCollection<T> {
+    /// Visits each geometry of the collection with `visitor`.
+    pub fn accept<V: GeometryVisitor<T> + ?Sized>(&self, visitor: &mut V) {
+        visitor.visit_geometry_collection(self)
+    }
+
+    /// Passes every coordinate of the collection to `visitor`.
+    pub fn accept_coords<V: CoordVisitor<T> + ?Sized>(&self, visitor: &mut V) {
+        self.accept(&mut Coords(visitor))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::polygon_z;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[derive(Default)]
+    struct Polygons(usize);
+
+    impl GeometryVisitor<f64> for Polygons {
+        fn visit_polygon_z(&mut self, _: &PolygonZ<f64>) {
+            self.0 += 1;
+        }
+
+        // skip the members of collections
+        fn visit_geometry_collection(&mut self, _: &GeometryCollection<f64>) {}
+    }
+
+    struct Collect(Vec<CoordZ<f64>>);
+
+    impl CoordVisitor<f64> for Collect {
+        fn visit_coord_z(&mut self, coord: &CoordZ<f64>) {
+            self.0.push(*coord);
+        }
+    }
+
+    #[test]
+    fn visitors() {
+        let polygon = polygon_z![
+            exterior: [(x: 0., y: 0., z: 1.), (x: 4., y: 0., z: 1.), (x: 0., y: 4., z: 1.)],
+            interiors: [[(x: 1., y: 1., z: 1.), (x: 2., y: 1., z: 1.), (x: 1., y: 2., z: 1.)]],
+        ];
+        let collection = GeometryCollection(vec![
+            Geometry::MultiPolygonZ(MultiPolygonZ(vec![polygon.clone(), polygon.clone()])),
+            Geometry::Rect(Rect::new((0., 0.), (2., 1.))),
+            Geometry::GeometryCollection(GeometryCollection(vec![PointZ::new(7., 8., 9.).into()])),
+        ]);
+
+        let mut polygons = Polygons::default();
+        collection.accept(&mut polygons);
+        // the collection itself is skipped, but the multi-polygon is walked
+        assert_eq!(polygons.0, 0);
+        for geometry in &collection {
+            geometry.accept(&mut polygons);
+        }
+        assert_eq!(polygons.0, 2);
+
+        let mut coords = Collect(Vec::new());
+        collection.accept_coords(&mut coords);
+        let ring_coords = polygon.exterior().0.len() + polygon.interiors()[0].0.len();
+        assert_eq!(coords.0.len(), 2 * ring_coords + 4 + 1);
+        assert_eq!(coords.0[..ring_coords / 2], polygon.exterior().0[..]);
+        assert_eq!(
+            coords.0[2 * ring_coords + 1],
+            CoordZ {
+                x: 2.,
+                y: 0.,
+                z: 0.
+            }
+        );
+        assert_eq!(
+            coords.0.last(),
+            Some(&CoordZ {
+                x: 7.,
+                y: 8.,
+                z: 9.
+            })
+        );
+    }
+}
@@ -0,0 +1,864 @@
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::algorithm::BoundingCube;
+use crate::{CoordFloat, CoordNum, CoordZ, Cube, Ray};
+
+/// The number of geometries a leaf holds at most.
+const LEAF_SIZE: usize = 4;
+
+/// The number of bins candidate surface area heuristic splits are chosen from.
+const BINS: usize = 12;
+
+/// How [`Bvh::with_split`] divides the geometries of a node between its children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Split {
+    /// Minimise the expected cost of ray queries by the surface area heuristic,
+    /// choosing among evenly spaced planes across the longest axis. Slower to
+    /// build, but faster to query where geometries are unevenly spread.
+    #[default]
+    SurfaceArea,
+    /// Halve the geometries at the median of their centres along the longest axis.
+    Median,
+}
+
+/// A bounding volume hierarchy: a static binary tree over geometries by their
+/// [bounding cube](BoundingCube), each node bounding the geometries below it.
+///
+/// Unlike an [`Octree`](super::Octree), the tree adapts its nodes to the geometries
+/// rather than dividing space evenly, which suits large or overlapping geometries
+/// such as the triangles of a mesh. It is built once from all its geometries.
+///
+/// Geometries are identified by their position in the `Vec` the tree is built from.
+/// Empty geometries are kept, but found by no query.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::index::Bvh;
+/// use geo_types_3d::{coordZ, Cube, Ray, Triangle};
+///
+/// // a strip of triangles along x, one unit apart
+/// let triangles: Vec<Triangle> = (0..100)
+///     .map(|i| {
+///         let x = i as f64;
+///         Triangle::new((x, 0., 0.).into(), (x + 0.5, 1., 0.).into(), (x, 1., 1.).into())
+///     })
+///     .collect();
+/// let bvh = Bvh::new(triangles);
+///
+/// let ray = Ray::new(coordZ! { x: 10.1, y: 0.5, z: -5. }, coordZ! { x: 0., y: 0., z: 1. });
+/// let hits = bvh.ray_query(&ray);
+/// assert_eq!(hits.len(), 1);
+/// assert_eq!(hits[0].0, 10);
+///
+/// let nearest = bvh.nearest_neighbors(coordZ! { x: 42.7, y: 0., z: 0. }, 1);
+/// assert_eq!(nearest[0].0, 42);
+///
+/// // neighbouring triangles don't touch
+/// assert!(bvh.overlapping_pairs().is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Bvh<T: CoordNum, G> {
+    geometries: Vec<G>,
+    bounds: Vec<Option<Cube<T>>>,
+    nodes: Vec<Node<T>>,
+    /// The ids of the indexed geometries, ordered so each leaf holds a contiguous
+    /// run.
+    order: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct Node<T: CoordNum> {
+    bounds: Cube<T>,
+    kind: Kind,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    /// Holds `order[start..end]`.
+    Leaf {
+        start: usize,
+        end: usize,
+    },
+    Inner {
+        left: usize,
+        right: usize,
+    },
+}
+
+impl<T: CoordFloat, G: BoundingCube<T>> Bvh<T, G> {
+    /// Builds a hierarchy over `geometries`, split by the surface area heuristic.
+    pub fn new(geometries: Vec<G>) -> Self {
+        Self::with_split(geometries, Split::SurfaceArea)
+    }
+
+    /// Builds a hierarchy over `geometries`, split by `split`.
+    pub fn with_split(geometries: Vec<G>, split: Split) -> Self {
+        let bounds: Vec<Option<Cube<T>>> = geometries
+            .iter()
+            .map(|geometry| geometry.bounding_cube().into())
+            .collect();
+        let mut bvh = Self {
+            order: (0..bounds.len())
+                .filter(|&id| bounds[id].is_some())
+                .collect(),
+            geometries,
+            bounds,
+            nodes: Vec::new(),
+        };
+        if !bvh.order.is_empty() {
+            bvh.build(split, 0, bvh.order.len());
+        }
+        bvh
+    }
+}
+
+impl<T: CoordFloat, G> Bvh<T, G> {
+    /// The number of geometries in the tree.
+    pub fn len(&self) -> usize {
+        self.geometries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.geometries.is_empty()
+    }
+
+    pub fn get(&self, id: usize) -> Option<&G> {
+        self.geometries.get(id)
+    }
+
+    pub fn geometries(&self) -> &[G] {
+        &self.geometries
+    }
+
+    /// The bounds of all the geometries, or `None` if they are all empty.
+    pub fn bounds(&self) -> Option<Cube<T>> {
+        self.nodes.first().map(|root| root.bounds)
+    }
+
+    /// The geometries whose bounding cube intersects `bounds`.
+    pub fn query(&self, bounds: &Cube<T>) -> impl Iterator<Item = (usize, &G)> {
+        let mut ids = Vec::new();
+        let mut stack: Vec<usize> = self.root().into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node.bounds.intersects(bounds) {
+                continue;
+            }
+            match node.kind {
+                Kind::Leaf { start, end } => ids.extend(
+                    self.order[start..end]
+                        .iter()
+                        .filter(|&&id| self.cube(id).intersects(bounds)),
+                ),
+                Kind::Inner { left, right } => stack.extend([right, left]),
+            }
+        }
+        ids.into_iter().map(move |id| (id, &self.geometries[id]))
+    }
+
+    /// The geometries whose bounding cube is hit by `ray`, with the parameter at
+    /// which the ray enters the bounding cube, ordered by that parameter.
+    pub fn ray_query(&self, ray: &Ray<T>) -> Vec<(usize, &G, T)> {
+        let mut hits = Vec::new();
+        self.visit_ray(ray, T::infinity(), |id, _| {
+            if let Some((enter, _)) = ray.intersect_cube(&self.cube(id)) {
+                hits.push((id, &self.geometries[id], enter));
+            }
+            None
+        });
+        hits.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal));
+        hits
+    }
+
+    /// The closest exact hit of `ray` on a geometry, with its parameter.
+    ///
+    /// `intersect` is given each geometry whose bounding cube the ray enters
+    /// before the closest hit found so far, and returns the parameter at which the
+    /// ray meets the geometry itself, if it does. Geometries are tested roughly
+    /// front to back, and parts of the tree beyond the closest hit are skipped.
+    pub fn first_hit(
+        &self,
+        ray: &Ray<T>,
+        mut intersect: impl FnMut(usize, &G) -> Option<T>,
+    ) -> Option<(usize, T)> {
+        let mut best = None;
+        self.visit_ray(ray, T::infinity(), |id, limit| {
+            let t = intersect(id, &self.geometries[id]).filter(|&t| t <= limit)?;
+            best = Some((id, t));
+            Some(t)
+        });
+        best
+    }
+
+    /// The `k` geometries closest to `coord`, nearest first.
+    ///
+    /// Geometries are ranked by the distance from `coord` to their bounding cube,
+    /// which is the exact distance for points. Geometries whose bounding cube
+    /// contains `coord` are at distance zero.
+    pub fn nearest_neighbors(&self, coord: CoordZ<T>, k: usize) -> Vec<(usize, &G)> {
+        let mut found = Vec::with_capacity(k);
+        let mut queue = BinaryHeap::new();
+        if let Some(root) = self.root().filter(|_| k > 0) {
+            queue.push(Candidate {
+                distance: distance_2(coord, &self.nodes[root].bounds),
+                item: Item::Node(root),
+            });
+        }
+        while let Some(Candidate { item, .. }) = queue.pop() {
+            match item {
+                Item::Entry(id) => {
+                    found.push((id, &self.geometries[id]));
+                    if found.len() == k {
+                        break;
+                    }
+                }
+                Item::Node(index) => match self.nodes[index].kind {
+                    Kind::Leaf { start, end } => {
+                        for &id in &self.order[start..end] {
+                            queue.push(Candidate {
+                                distance: distance_2(coord, &self.cube(id)),
+                                item: Item::Entry(id),
+                            });
+                        }
+                    }
+                    Kind::Inner { left, right } => {
+                        for child in [left, right] {
+                            queue.push(Candidate {
+                                distance: distance_2(coord, &self.nodes[child].bounds),
+                                item: Item::Node(child),
+                            });
+                        }
+                    }
+                },
+            }
+        }
+        found
+    }
+
+    /// The geometry closest to `coord` by an exact distance, with the distance.
+    ///
+    /// `distance` is given geometries whose bounding cube is closer than the
+    /// closest geometry found so far, nearest first, and returns the exact
+    /// distance from `coord` to the geometry, or `None` if it doesn't count.
+    pub fn nearest_with(
+        &self,
+        coord: CoordZ<T>,
+        mut distance: impl FnMut(&G) -> Option<T>,
+    ) -> Option<(usize, T)> {
+        let mut best: Option<(usize, T)> = None;
+        let mut queue = BinaryHeap::new();
+        if let Some(root) = self.root() {
+            queue.push(Candidate {
+                distance: distance_2(coord, &self.nodes[root].bounds),
+                item: Item::Node(root),
+            });
+        }
+        while let Some(Candidate {
+            distance: bound,
+            item,
+        }) = queue.pop()
+        {
+            let limit = best.map_or(T::infinity(), |(_, d)| d);
+            if bound >= limit * limit {
+                break;
+            }
+            match item {
+                Item::Entry(id) => {
+                    if let Some(d) = distance(&self.geometries[id]).filter(|&d| d < limit) {
+                        best = Some((id, d));
+                    }
+                }
+                Item::Node(index) => match self.nodes[index].kind {
+                    Kind::Leaf { start, end } => {
+                        for &id in &self.order[start..end] {
+                            queue.push(Candidate {
+                                distance: distance_2(coord, &self.cube(id)),
+                                item: Item::Entry(id),
+                            });
+                        }
+                    }
+                    Kind::Inner { left, right } => {
+                        for child in [left, right] {
+                            queue.push(Candidate {
+                                distance: distance_2(coord, &self.nodes[child].bounds),
+                                item: Item::Node(child),
+                            });
+                        }
+                    }
+                },
+            }
+        }
+        best
+    }
+
+    /// The pairs of distinct geometries whose bounding cubes intersect, each pair
+    /// once with the smaller id first, in no particular order.
+    pub fn overlapping_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        if let Some(root) = self.root() {
+            self.self_pairs(root, &mut pairs);
+        }
+        pairs
+    }
+
+    /// The pairs of a geometry of this tree and a geometry of `other` whose
+    /// bounding cubes intersect, in no particular order.
+    pub fn overlapping_pairs_with<H>(&self, other: &Bvh<T, H>) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        if let (Some(a), Some(b)) = (self.root(), other.root()) {
+            let mut stack = vec![(a, b)];
+            while let Some((a, b)) = stack.pop() {
+                let (node_a, node_b) = (&self.nodes[a], &other.nodes[b]);
+                if !node_a.bounds.intersects(&node_b.bounds) {
+                    continue;
+                }
+                match (node_a.kind, node_b.kind) {
+                    (Kind::Leaf { start, end }, Kind::Leaf { start: s, end: e }) => {
+                        for &i in &self.order[start..end] {
+                            for &j in &other.order[s..e] {
+                                if self.cube(i).intersects(&other.cube(j)) {
+                                    pairs.push((i, j));
+                                }
+                            }
+                        }
+                    }
+                    (Kind::Inner { left, right }, Kind::Leaf { .. }) => {
+                        stack.extend([(left, b), (right, b)])
+                    }
+                    (_, Kind::Inner { left, right }) => stack.extend([(a, left), (a, right)]),
+                }
+            }
+        }
+        pairs
+    }
+
+    /// The closest pair of a geometry of this tree and a geometry of `other`,
+    /// with the distance between them, by branch and bound.
+    ///
+    /// `distance` is given pairs of geometries whose bounding cubes are closer than
+    /// the closest pair found so far, and returns the exact distance between them,
+    /// or `None` if the pair doesn't count. Pairs of nodes are visited nearest
+    /// first, and the search stops once the nearest left is no closer than the
+    /// closest pair, so only a small part of the `n · m` pairs is tested.
+    pub fn closest_pair_with<H>(
+        &self,
+        other: &Bvh<T, H>,
+        mut distance: impl FnMut(&G, &H) -> Option<T>,
+    ) -> Option<(usize, usize, T)> {
+        let (a, b) = (self.root()?, other.root()?);
+        let mut best: Option<(usize, usize, T)> = None;
+        let mut queue = BinaryHeap::from([NodePair {
+            distance: cube_distance_2(&self.nodes[a].bounds, &other.nodes[b].bounds),
+            a,
+            b,
+        }]);
+        while let Some(NodePair {
+            distance: bound,
+            a,
+            b,
+        }) = queue.pop()
+        {
+            let limit = best.map_or(T::infinity(), |(_, _, d)| d);
+            if bound >= limit * limit {
+                break;
+            }
+            let (node_a, node_b) = (&self.nodes[a], &other.nodes[b]);
+            // descend into the larger of two inner nodes
+            let descend_a = match (node_a.kind, node_b.kind) {
+                (Kind::Leaf { start, end }, Kind::Leaf { start: s, end: e }) => {
+                    for &i in &self.order[start..end] {
+                        for &j in &other.order[s..e] {
+                            let limit = best.map_or(T::infinity(), |(_, _, d)| d);
+                            if cube_distance_2(&self.cube(i), &other.cube(j)) >= limit * limit {
+                                continue;
+                            }
+                            let found = distance(&self.geometries[i], &other.geometries[j]);
+                            if let Some(d) = found.filter(|&d| d < limit) {
+                                best = Some((i, j, d));
+                            }
+                        }
+                    }
+                    continue;
+                }
+                (Kind::Inner { .. }, Kind::Leaf { .. }) => true,
+                (Kind::Leaf { .. }, Kind::Inner { .. }) => false,
+                (Kind::Inner { .. }, Kind::Inner { .. }) => {
+                    surface_area(&node_a.bounds) >= surface_area(&node_b.bounds)
+                }
+            };
+            let pairs = match (descend_a, node_a.kind, node_b.kind) {
+                (true, Kind::Inner { left, right }, _) => [(left, b), (right, b)],
+                (false, _, Kind::Inner { left, right }) => [(a, left), (a, right)],
+                _ => unreachable!("only inner nodes are descended into"),
+            };
+            for (a, b) in pairs {
+                let distance = cube_distance_2(&self.nodes[a].bounds, &other.nodes[b].bounds);
+                if distance < limit * limit {
+                    queue.push(NodePair { distance, a, b });
+                }
+            }
+        }
+        best
+    }
+
+    fn root(&self) -> Option<usize> {
+        (!self.nodes.is_empty()).then_some(0)
+    }
+
+    fn cube(&self, id: usize) -> Cube<T> {
+        self.bounds[id].expect("indexed geometry")
+    }
+
+    /// Calls `hit` with each geometry whose bounding cube `ray` enters before
+    /// `max`, and the parameter it must be hit before to count. `hit` returns the
+    /// parameter of a hit, which then limits the search.
+    fn visit_ray(&self, ray: &Ray<T>, mut max: T, mut hit: impl FnMut(usize, T) -> Option<T>) {
+        let mut stack: Vec<usize> = self.root().into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            match ray.intersect_cube(&node.bounds) {
+                Some((enter, _)) if enter <= max => {}
+                _ => continue,
+            }
+            match node.kind {
+                Kind::Leaf { start, end } => {
+                    for &id in &self.order[start..end] {
+                        if let Some(t) = hit(id, max) {
+                            max = max.min(t);
+                        }
+                    }
+                }
+                Kind::Inner { left, right } => {
+                    // visit the nearer child first
+                    let enter = |child: usize| {
+                        ray.intersect_cube(&self.nodes[child].bounds)
+                            .map_or(T::infinity(), |(enter, _)| enter)
+                    };
+                    if enter(left) <= enter(right) {
+                        stack.extend([right, left]);
+                    } else {
+                        stack.extend([left, right]);
+                    }
+                }
+            }
+        }
+    }
+
+    fn self_pairs(&self, index: usize, pairs: &mut Vec<(usize, usize)>) {
+        match self.nodes[index].kind {
+            Kind::Leaf { start, end } => {
+                let ids = &self.order[start..end];
+                for (k, &i) in ids.iter().enumerate() {
+                    for &j in &ids[k + 1..] {
+                        if self.cube(i).intersects(&self.cube(j)) {
+                            pairs.push((i.min(j), i.max(j)));
+                        }
+                    }
+                }
+            }
+            Kind::Inner { left, right } => {
+                self.self_pairs(left, pairs);
+                self.self_pairs(right, pairs);
+                let mut stack = vec![(left, right)];
+                while let Some((a, b)) = stack.pop() {
+                    let (node_a, node_b) = (&self.nodes[a], &self.nodes[b]);
+                    if !node_a.bounds.intersects(&node_b.bounds) {
+                        continue;
+                    }
+                    match (node_a.kind, node_b.kind) {
+                        (Kind::Leaf { start, end }, Kind::Leaf { start: s, end: e }) => {
+                            for &i in &self.order[start..end] {
+                                for &j in &self.order[s..e] {
+                                    if self.cube(i).intersects(&self.cube(j)) {
+                                        pairs.push((i.min(j), i.max(j)));
+                                    }
+                                }
+                            }
+                        }
+                        (Kind::Inner { left, right }, Kind::Leaf { .. }) => {
+                            stack.extend([(left, b), (right, b)])
+                        }
+                        (_, Kind::Inner { left, right }) => stack.extend([(a, left), (a, right)]),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Builds the node holding `order[start..end]`, returning its index.
+    fn build(&mut self, split: Split, start: usize, end: usize) -> usize {
+        let bounds = &self.bounds;
+        let cube = |id: usize| bounds[id].expect("indexed geometry");
+        let members = &mut self.order[start..end];
+        let node_bounds = members
+            .iter()
+            .map(|&id| cube(id))
+            .reduce(|a, b| a.union(&b))
+            .expect("non-empty node");
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            bounds: node_bounds,
+            kind: Kind::Leaf { start, end },
+        });
+        if members.len() <= LEAF_SIZE {
+            return index;
+        }
+
+        // split across the axis along which the centres are most spread out
+        let centres = members
+            .iter()
+            .map(|&id| Cube::new(cube(id).center(), cube(id).center()))
+            .reduce(|a, b| a.union(&b))
+            .expect("non-empty node");
+        let extent = centres.max() - centres.min();
+        let axis = if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        };
+        let along = |c: CoordZ<T>| [c.x, c.y, c.z][axis];
+        let centre = |id: usize| along(cube(id).center());
+        let (low, width) = (along(centres.min()), along(extent));
+
+        let middle = match split {
+            Split::SurfaceArea if width > T::zero() => {
+                let bin = |id: usize| {
+                    let position = (centre(id) - low) / width * T::from(BINS).unwrap();
+                    position.to_usize().unwrap_or(0).min(BINS - 1)
+                };
+                best_bin(members.iter().map(|&id| (bin(id), cube(id)))).map(|boundary| {
+                    let mut left = 0;
+                    for i in 0..members.len() {
+                        if bin(members[i]) < boundary {
+                            members.swap(i, left);
+                            left += 1;
+                        }
+                    }
+                    left
+                })
+            }
+            _ => None,
+        }
+        .unwrap_or_else(|| {
+            let middle = members.len() / 2;
+            members.select_nth_unstable_by(middle, |&a, &b| {
+                centre(a).partial_cmp(&centre(b)).unwrap_or(Ordering::Equal)
+            });
+            middle
+        });
+
+        let left = self.build(split, start, start + middle);
+        let right = self.build(split, start + middle, end);
+        self.nodes[index].kind = Kind::Inner { left, right };
+        index
+    }
+}
+
+/// The bin boundary splitting binned geometries at the least surface area cost,
+/// if any boundary leaves geometries on both sides.
+fn best_bin<T: CoordFloat>(binned: impl Iterator<Item = (usize, Cube<T>)>) -> Option<usize> {
+    let mut counts = [0usize; BINS];
+    let mut bounds: [Option<Cube<T>>; BINS] = [None; BINS];
+    for (bin, cube) in binned {
+        counts[bin] += 1;
+        bounds[bin] = Some(bounds[bin].map_or(cube, |b| b.union(&cube)));
+    }
+    // the cost of each boundary is the area of each side times the geometries in it
+    let sweep = |range: &mut dyn Iterator<Item = usize>| {
+        let mut costs = [T::zero(); BINS];
+        let (mut count, mut side) = (0, None::<Cube<T>>);
+        for bin in range {
+            count += counts[bin];
+            if let Some(cube) = bounds[bin] {
+                side = Some(side.map_or(cube, |s| s.union(&cube)));
+            }
+            costs[bin] = side.map_or(T::zero(), |s| surface_area(&s)) * T::from(count).unwrap();
+        }
+        costs
+    };
+    let left = sweep(&mut (0..BINS));
+    let right = sweep(&mut (0..BINS).rev());
+    let total: usize = counts.iter().sum();
+    (1..BINS)
+        .filter(|&boundary| {
+            let below: usize = counts[..boundary].iter().sum();
+            below > 0 && below < total
+        })
+        .min_by(|&a, &b| {
+            let cost = |boundary: usize| left[boundary - 1] + right[boundary];
+            cost(a).partial_cmp(&cost(b)).unwrap_or(Ordering::Equal)
+        })
+}
+
+fn surface_area<T: CoordFloat>(cube: &Cube<T>) -> T {
+    let extent = cube.max() - cube.min();
+    (extent.x * extent.y + extent.y * extent.z + extent.z * extent.x) * (T::one() + T::one())
+}
+
+/// The squared distance from `coord` to the closest point of `cube`.
+fn distance_2<T: CoordFloat>(coord: CoordZ<T>, cube: &Cube<T>) -> T {
+    let (min, max) = (cube.min(), cube.max());
+    let axis = |value: T, min: T, max: T| {
+        let delta = if value < min {
+            min - value
+        } else if value > max {
+            value - max
+        } else {
+            T::zero()
+        };
+        delta * delta
+    };
+    axis(coord.x, min.x, max.x) + axis(coord.y, min.y, max.y) + axis(coord.z, min.z, max.z)
+}
+
+/// The squared distance between the closest points of two cubes.
+fn cube_distance_2<T: CoordFloat>(a: &Cube<T>, b: &Cube<T>) -> T {
+    let axis = |a_min: T, a_max: T, b_min: T, b_max: T| {
+        let gap = (a_min - b_max).max(b_min - a_max).max(T::zero());
+        gap * gap
+    };
+    let (a_min, a_max, b_min, b_max) = (a.min(), a.max(), b.min(), b.max());
+    axis(a_min.x, a_max.x, b_min.x, b_max.x)
+        + axis(a_min.y, a_max.y, b_min.y, b_max.y)
+        + axis(a_min.z, a_max.z, b_min.z, b_max.z)
+}
+
+enum Item {
+    Node(usize),
+    Entry(usize),
+}
+
+/// An entry in the nearest-neighbour queue, ordered so that the closest candidate
+/// is popped first.
+struct Candidate<T: CoordFloat> {
+    distance: T,
+    item: Item,
+}
+
+impl<T: CoordFloat> Ord for Candidate<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed, as `BinaryHeap` is a max-heap; entries go before nodes at equal
+        // distances
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| {
+                matches!(self.item, Item::Entry(_)).cmp(&matches!(other.item, Item::Entry(_)))
+            })
+    }
+}
+
+impl<T: CoordFloat> PartialOrd for Candidate<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: CoordFloat> PartialEq for Candidate<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: CoordFloat> Eq for Candidate<T> {}
+
+/// A pair of nodes of two trees in the closest-pair queue, ordered so that the
+/// closest pair is popped first.
+struct NodePair<T: CoordFloat> {
+    distance: T,
+    a: usize,
+    b: usize,
+}
+
+impl<T: CoordFloat> Ord for NodePair<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed, as `BinaryHeap` is a max-heap
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<T: CoordFloat> PartialOrd for NodePair<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: CoordFloat> PartialEq for NodePair<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: CoordFloat> Eq for NodePair<T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{LineStringZ, LineZ, PointZ};
+    use alloc::vec;
+
+    /// Diagonal segments scattered through a 10 × 10 × 10 box, clustered towards
+    /// one corner.
+    fn segments() -> Vec<LineZ<f64>> {
+        (0..500)
+            .map(|i| {
+                let f = |k: u32| ((i * k) % 97) as f64 / 97.;
+                let start = (10. * f(7).powi(2), 10. * f(13).powi(2), 10. * f(29));
+                let end = (start.0 + f(31), start.1 + f(37), start.2 + 0.5);
+                LineZ::new(start, end)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn queries_match_brute_force() {
+        let segments = segments();
+        for split in [Split::SurfaceArea, Split::Median] {
+            let bvh = Bvh::with_split(segments.clone(), split);
+            assert_eq!(bvh.len(), 500);
+
+            let bounds = Cube::new((1., 1., 2.), (3., 4., 6.));
+            let mut ids: Vec<usize> = bvh.query(&bounds).map(|(id, _)| id).collect();
+            ids.sort();
+            let expected: Vec<usize> = (0..segments.len())
+                .filter(|&id| segments[id].bounding_cube().intersects(&bounds))
+                .collect();
+            assert_eq!(ids, expected);
+
+            let target = CoordZ {
+                x: 7.,
+                y: 2.,
+                z: 5.,
+            };
+            let distance = |line: &LineZ<f64>| distance_2(target, &line.bounding_cube());
+            let nearest: Vec<f64> = bvh
+                .nearest_neighbors(target, 10)
+                .into_iter()
+                .map(|(_, line)| distance(line))
+                .collect();
+            let mut expected: Vec<f64> = segments.iter().map(distance).collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            assert_eq!(nearest, expected[..10]);
+
+            let ray = Ray::new(
+                CoordZ {
+                    x: 0.,
+                    y: 0.,
+                    z: 0.,
+                },
+                CoordZ {
+                    x: 1.,
+                    y: 1.2,
+                    z: 0.9,
+                },
+            );
+            let by_entry = |a: &(usize, f64), b: &(usize, f64)| {
+                a.1.partial_cmp(&b.1).unwrap().then(a.0.cmp(&b.0))
+            };
+            let mut hits: Vec<(usize, f64)> =
+                bvh.ray_query(&ray).iter().map(|h| (h.0, h.2)).collect();
+            assert!(hits.windows(2).all(|w| w[0].1 <= w[1].1));
+            hits.sort_by(by_entry);
+            let mut expected: Vec<(usize, f64)> = (0..segments.len())
+                .filter_map(|id| {
+                    let (enter, _) = ray.intersect_cube(&segments[id].bounding_cube())?;
+                    Some((id, enter))
+                })
+                .collect();
+            expected.sort_by(by_entry);
+            assert_eq!(hits, expected);
+            // the first hit on a bounding cube, counting every cube as solid
+            let first = bvh.first_hit(&ray, |_, line| {
+                ray.intersect_cube(&line.bounding_cube()).map(|(t, _)| t)
+            });
+            assert_eq!(first.map(|(_, t)| t), hits.first().map(|hit| hit.1));
+
+            let mut pairs = bvh.overlapping_pairs();
+            pairs.sort();
+            let mut expected = Vec::new();
+            for i in 0..segments.len() {
+                for j in i + 1..segments.len() {
+                    if segments[i]
+                        .bounding_cube()
+                        .intersects(&segments[j].bounding_cube())
+                    {
+                        expected.push((i, j));
+                    }
+                }
+            }
+            assert!(!expected.is_empty());
+            assert_eq!(pairs, expected);
+
+            let points = Bvh::new(vec![PointZ::new(2., 2., 2.), PointZ::new(20., 2., 2.)]);
+            let mut pairs = bvh.overlapping_pairs_with(&points);
+            pairs.sort();
+            let expected: Vec<(usize, usize)> = (0..segments.len())
+                .filter(|&id| {
+                    segments[id]
+                        .bounding_cube()
+                        .contains_coord(points.geometries()[0].0)
+                })
+                .map(|id| (id, 0))
+                .collect();
+            assert_eq!(pairs, expected);
+
+            // the closest pair of a segment end and a point on a grid above the box
+            let grid: Vec<PointZ<f64>> = (0..100)
+                .map(|i| PointZ::new((i % 10) as f64 * 1.3, (i / 10) as f64 * 1.1, 12.))
+                .collect();
+            let grid_bvh = Bvh::new(grid.clone());
+            let end_distance = |line: &LineZ<f64>, point: &PointZ<f64>| {
+                let to = |c: CoordZ<f64>| {
+                    let d = c - point.0;
+                    (d.x * d.x + d.y * d.y + d.z * d.z).sqrt()
+                };
+                to(line.start).min(to(line.end))
+            };
+            let (i, j, distance) = bvh
+                .closest_pair_with(&grid_bvh, |line, point| Some(end_distance(line, point)))
+                .unwrap();
+            let mut expected = f64::INFINITY;
+            for line in &segments {
+                for point in &grid {
+                    expected = expected.min(end_distance(line, point));
+                }
+            }
+            assert_eq!(distance, expected);
+            assert_eq!(end_distance(&segments[i], &grid[j]), expected);
+            assert!(bvh
+                .closest_pair_with(&Bvh::<f64, PointZ<f64>>::new(vec![]), |_, _| Some(0.))
+                .is_none());
+        }
+    }
+
+    #[test]
+    fn empty_geometries() {
+        let bvh: Bvh<f64, LineStringZ> = Bvh::new(vec![]);
+        assert!(bvh.is_empty());
+        assert_eq!(bvh.bounds(), None);
+        assert!(bvh.nearest_neighbors(CoordZ::zero(), 3).is_empty());
+
+        let bvh = Bvh::new(vec![
+            LineStringZ::empty(),
+            LineStringZ::from(vec![(0., 0., 0.), (1., 1., 1.)]),
+        ]);
+        assert_eq!(bvh.len(), 2);
+        assert_eq!(bvh.bounds(), Some(Cube::new((0., 0., 0.), (1., 1., 1.))));
+        let nearest: Vec<usize> = bvh
+            .nearest_neighbors(CoordZ::zero(), 3)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(nearest, vec![1]);
+    }
+}
@@ -0,0 +1,355 @@
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::algorithm::linalg::distance_2;
+use crate::{CoordFloat, MultiPointZ, PointZ};
+
+/// Slices shorter than this are built on the current thread by
+/// [`KdTree::par_new`].
+#[cfg(feature = "multithreading")]
+const PARALLEL_THRESHOLD: usize = 4096;
+
+/// A static, balanced 3D kd-tree over a set of points.
+///
+/// The tree is built once, by sorting the points in place, and cannot be changed
+/// afterwards. Points are identified by their index in the input. For geometries
+/// other than points, or to insert and remove points, see
+/// [`Octree`](super::Octree).
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::{MultiPointZ, PointZ};
+///
+/// let points: MultiPointZ = (0..10).map(|i| PointZ::new(i as f64, 0.0, 0.0)).collect();
+/// let tree = points.kdtree();
+///
+/// assert_eq!(tree.nearest(&PointZ::new(3.2, 1.0, 0.0)), Some((3, &PointZ::new(3.0, 0.0, 0.0))));
+///
+/// let ids: Vec<usize> = tree
+///     .nearest_k(&PointZ::new(3.2, 1.0, 0.0), 3)
+///     .into_iter()
+///     .map(|(id, _)| id)
+///     .collect();
+/// assert_eq!(ids, vec![3, 4, 2]);
+///
+/// assert_eq!(tree.within_radius(&PointZ::new(9.0, 0.0, 0.5), 1.5).len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KdTree<T: CoordFloat = f64> {
+    /// `(id, point)` pairs. The median of each slice along its axis sits in the
+    /// middle, with the smaller points before it and the larger points after it.
+    nodes: Vec<(usize, PointZ<T>)>,
+}
+
+impl<T: CoordFloat> KdTree<T> {
+    pub fn new(points: impl IntoIterator<Item = PointZ<T>>) -> Self {
+        let mut nodes: Vec<_> = points.into_iter().enumerate().collect();
+        build(&mut nodes, 0);
+        Self { nodes }
+    }
+
+    /// The number of points in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The point closest to `point`, or `None` if the tree is empty.
+    pub fn nearest(&self, point: &PointZ<T>) -> Option<(usize, &PointZ<T>)> {
+        self.nearest_k(point, 1).pop()
+    }
+
+    /// The `k` points closest to `point`, nearest first.
+    pub fn nearest_k(&self, point: &PointZ<T>, k: usize) -> Vec<(usize, &PointZ<T>)> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        nearest_k(&self.nodes, 0, 0, point, k, &mut best);
+        best.into_sorted_vec()
+            .into_iter()
+            .map(|neighbor| self.node(neighbor.index))
+            .collect()
+    }
+
+    /// The points at most `radius` away from `point`, nearest first.
+    pub fn within_radius(&self, point: &PointZ<T>, radius: T) -> Vec<(usize, &PointZ<T>)> {
+        let mut found = Vec::new();
+        within_radius(&self.nodes, 0, 0, point, radius * radius, &mut found);
+        found.sort();
+        found
+            .into_iter()
+            .map(|neighbor| self.node(neighbor.index))
+            .collect()
+    }
+
+    fn node(&self, index: usize) -> (usize, &PointZ<T>) {
+        let (id, point) = &self.nodes[index];
+        (*id, point)
+    }
+}
+
+#[cfg(feature = "multithreading")]
+impl<T: CoordFloat + Send + Sync> KdTree<T> {
+    /// Like [`KdTree::new`], but builds the tree on the rayon thread pool.
+    pub fn par_new(points: impl IntoIterator<Item = PointZ<T>>) -> Self {
+        let mut nodes: Vec<_> = points.into_iter().enumerate().collect();
+        par_build(&mut nodes, 0);
+        Self { nodes }
+    }
+}
+
+impl<T: CoordFloat> MultiPointZ<T> {
+    /// Builds a [`KdTree`] over the points, for nearest-neighbour and radius queries.
+    pub fn kdtree(&self) -> KdTree<T> {
+        KdTree::new(self.0.iter().copied())
+    }
+}
+
+#[cfg(feature = "multithreading")]
+impl<T: CoordFloat + Send + Sync> MultiPointZ<T> {
+    /// Like [`MultiPointZ::kdtree`], but builds the tree in parallel.
+    pub fn par_kdtree(&self) -> KdTree<T> {
+        KdTree::par_new(self.0.iter().copied())
+    }
+}
+
+fn ordinate<T: CoordFloat>(point: &PointZ<T>, axis: usize) -> T {
+    match axis {
+        0 => point.x(),
+        1 => point.y(),
+        _ => point.z(),
+    }
+}
+
+/// Places the median of `nodes` along the axis for `depth` in the middle, returning
+/// its index.
+fn partition<T: CoordFloat>(nodes: &mut [(usize, PointZ<T>)], depth: usize) -> usize {
+    let axis = depth % 3;
+    let mid = nodes.len() / 2;
+    nodes.select_nth_unstable_by(mid, |a, b| {
+        ordinate(&a.1, axis)
+            .partial_cmp(&ordinate(&b.1, axis))
+            .unwrap_or(Ordering::Equal)
+    });
+    mid
+}
+
+fn build<T: CoordFloat>(nodes: &mut [(usize, PointZ<T>)], depth: usize) {
+    if nodes.len() <= 1 {
+        return;
+    }
+    let mid = partition(nodes, depth);
+    let (left, right) = nodes.split_at_mut(mid);
+    build(left, depth + 1);
+    build(&mut right[1..], depth + 1);
+}
+
+#[cfg(feature = "multithreading")]
+fn par_build<T: CoordFloat + Send + Sync>(nodes: &mut [(usize, PointZ<T>)], depth: usize) {
+    if nodes.len() < PARALLEL_THRESHOLD {
+        return build(nodes, depth);
+    }
+    let mid = partition(nodes, depth);
+    let (left, right) = nodes.split_at_mut(mid);
+    rayon::join(
+        || par_build(left, depth + 1),
+        || par_build(&mut right[1..], depth + 1),
+    );
+}
+
+/// A candidate point, ordered by distance and then by position in the tree.
+struct Neighbor<T> {
+    distance_2: T,
+    index: usize,
+}
+
+impl<T: PartialOrd> Ord for Neighbor<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance_2
+            .partial_cmp(&other.distance_2)
+            .unwrap_or(Ordering::Equal)
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for Neighbor<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PartialOrd> PartialEq for Neighbor<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: PartialOrd> Eq for Neighbor<T> {}
+
+/// Searches `nodes`, which start at `offset` in the tree, keeping the `k` closest
+/// points in the max-heap `best`.
+fn nearest_k<T: CoordFloat>(
+    nodes: &[(usize, PointZ<T>)],
+    offset: usize,
+    depth: usize,
+    target: &PointZ<T>,
+    k: usize,
+    best: &mut BinaryHeap<Neighbor<T>>,
+) {
+    if nodes.is_empty() {
+        return;
+    }
+    let mid = nodes.len() / 2;
+    let point = &nodes[mid].1;
+    best.push(Neighbor {
+        distance_2: distance_2(point.0, target.0),
+        index: offset + mid,
+    });
+    if best.len() > k {
+        best.pop();
+    }
+
+    let axis = depth % 3;
+    let delta = ordinate(target, axis) - ordinate(point, axis);
+    let (left, right) = (&nodes[..mid], &nodes[mid + 1..]);
+    let ((near, near_offset), (far, far_offset)) = if delta < T::zero() {
+        ((left, offset), (right, offset + mid + 1))
+    } else {
+        ((right, offset + mid + 1), (left, offset))
+    };
+    nearest_k(near, near_offset, depth + 1, target, k, best);
+    let worst = best.peek().map(|neighbor| neighbor.distance_2);
+    if best.len() < k || worst.is_some_and(|worst| delta * delta < worst) {
+        nearest_k(far, far_offset, depth + 1, target, k, best);
+    }
+}
+
+fn within_radius<T: CoordFloat>(
+    nodes: &[(usize, PointZ<T>)],
+    offset: usize,
+    depth: usize,
+    target: &PointZ<T>,
+    radius_2: T,
+    found: &mut Vec<Neighbor<T>>,
+) {
+    if nodes.is_empty() {
+        return;
+    }
+    let mid = nodes.len() / 2;
+    let point = &nodes[mid].1;
+    let distance_2 = distance_2(point.0, target.0);
+    if distance_2 <= radius_2 {
+        found.push(Neighbor {
+            distance_2,
+            index: offset + mid,
+        });
+    }
+    let axis = depth % 3;
+    let delta = ordinate(target, axis) - ordinate(point, axis);
+    let reaches = delta * delta <= radius_2;
+    if delta < T::zero() || reaches {
+        within_radius(&nodes[..mid], offset, depth + 1, target, radius_2, found);
+    }
+    if delta >= T::zero() || reaches {
+        within_radius(
+            &nodes[mid + 1..],
+            offset + mid + 1,
+            depth + 1,
+            target,
+            radius_2,
+            found,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    /// A deterministic scatter of points in the unit cube.
+    fn scatter(count: usize) -> MultiPointZ<f64> {
+        let mut state = 0x2545_f491_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % 10_000) as f64 / 10_000.
+        };
+        (0..count)
+            .map(|_| PointZ::new(next(), next(), next()))
+            .collect()
+    }
+
+    #[test]
+    fn queries_match_brute_force() {
+        let points = scatter(2000);
+        let tree = points.kdtree();
+        assert_eq!(tree.len(), 2000);
+
+        let brute = |target: &PointZ<f64>| {
+            let mut ids: Vec<usize> = (0..points.0.len()).collect();
+            ids.sort_by(|&a, &b| {
+                distance_2(points.0[a].0, target.0)
+                    .partial_cmp(&distance_2(points.0[b].0, target.0))
+                    .unwrap()
+            });
+            ids
+        };
+        for target in scatter(20).0 {
+            let expected = brute(&target);
+            let nearest: Vec<usize> = tree
+                .nearest_k(&target, 10)
+                .into_iter()
+                .map(|(id, point)| {
+                    assert_eq!(point, &points.0[id]);
+                    id
+                })
+                .collect();
+            assert_eq!(nearest, expected[..10]);
+            assert_eq!(tree.nearest(&target).unwrap().0, expected[0]);
+
+            let within: Vec<usize> = tree
+                .within_radius(&target, 0.1)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect();
+            let inside = expected
+                .iter()
+                .take_while(|&&id| distance_2(points.0[id].0, target.0) <= 0.01)
+                .count();
+            assert_eq!(within, expected[..inside]);
+        }
+    }
+
+    #[test]
+    fn small_trees() {
+        let empty = KdTree::<f64>::new([]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.nearest(&PointZ::new(0., 0., 0.)), None);
+
+        let points = MultiPointZ(vec![PointZ::new(1., 1., 1.), PointZ::new(1., 1., 1.)]);
+        let tree = points.kdtree();
+        assert_eq!(tree.nearest_k(&PointZ::new(0., 0., 0.), 5).len(), 2);
+        assert_eq!(tree.nearest_k(&PointZ::new(0., 0., 0.), 0), vec![]);
+        assert_eq!(tree.within_radius(&PointZ::new(0., 0., 0.), 1.7).len(), 0);
+        assert_eq!(tree.within_radius(&PointZ::new(0., 0., 0.), 1.8).len(), 2);
+    }
+
+    #[cfg(feature = "multithreading")]
+    #[test]
+    fn parallel_build() {
+        let points = scatter(20_000);
+        let target = PointZ::new(0.5, 0.5, 0.5);
+        assert_eq!(
+            points.par_kdtree().nearest_k(&target, 20),
+            points.kdtree().nearest_k(&target, 20)
+        );
+    }
+}
@@ -0,0 +1,18 @@
+//! In-memory spatial indexes over 3D geometries.
+//!
+//! These are lightweight, dependency-free alternatives to the R*-tree integration
+//! behind the `use-rstar` features, for the common cases of range, nearest-neighbour,
+//! radius and ray queries. A [`Bvh`] suits geometries that are large or overlap, such
+//! as the triangles of a mesh, and finds overlapping pairs of geometries.
+
+mod bvh;
+mod kdtree;
+mod octree;
+#[cfg(feature = "std")]
+mod voxel_grid;
+
+pub use bvh::{Bvh, Split};
+pub use kdtree::KdTree;
+pub use octree::Octree;
+#[cfg(feature = "std")]
+pub use voxel_grid::{Voxel, VoxelGrid};
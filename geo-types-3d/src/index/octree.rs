@@ -0,0 +1,523 @@
+use alloc::boxed::Box;
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::algorithm::BoundingCube;
+use crate::{CoordFloat, CoordZ, Cube, Ray};
+
+/// The number of geometries a node holds before it is split into eight children.
+const NODE_CAPACITY: usize = 8;
+
+/// Nodes this deep are never split, however many geometries they hold.
+const MAX_DEPTH: usize = 16;
+
+/// An octree storing geometries by their [bounding cube](BoundingCube).
+///
+/// Each geometry is stored in the smallest node whose bounds contain its bounding
+/// cube, so geometries straddling a split plane stay in the parent node. Geometries
+/// lying partly or completely outside the bounds of the tree are kept in the root
+/// node: they are still found by every query, but aren't indexed.
+///
+/// Inserted geometries are identified by the id [`Octree::insert`] returns. Ids of
+/// removed geometries are reused by later insertions.
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::index::Octree;
+/// use geo_types_3d::{coordZ, Cube, PointZ, Ray};
+///
+/// let mut tree = Octree::new(Cube::new((0., 0., 0.), (100., 100., 100.)));
+/// for i in 0..100 {
+///     let i = i as f64;
+///     tree.insert(PointZ::new(i, i, 50.));
+/// }
+///
+/// let ids: Vec<usize> = tree
+///     .query(&Cube::new((10., 10., 0.), (12., 12., 100.)))
+///     .map(|(id, _)| id)
+///     .collect();
+/// assert_eq!(ids.len(), 3);
+///
+/// let nearest = tree.nearest_neighbors(coordZ! { x: 20.2, y: 20.2, z: 50. }, 2);
+/// assert_eq!(nearest[0].1, &PointZ::new(20., 20., 50.));
+/// assert_eq!(nearest[1].1, &PointZ::new(21., 21., 50.));
+///
+/// let ray = Ray::new(coordZ! { x: 5., y: 5., z: 0. }, coordZ! { x: 0., y: 0., z: 1. });
+/// let hits = tree.ray_query(&ray);
+/// assert_eq!(hits.len(), 1);
+/// assert_eq!(hits[0].2, 50.);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Octree<T: CoordFloat, G> {
+    root: Node<T>,
+    entries: Vec<Option<Entry<T, G>>>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Entry<T: CoordFloat, G> {
+    /// `None` for empty geometries, which aren't stored in any node.
+    bounds: Option<Cube<T>>,
+    geometry: G,
+}
+
+#[derive(Debug, Clone)]
+struct Node<T: CoordFloat> {
+    bounds: Cube<T>,
+    ids: Vec<usize>,
+    children: Option<Box<[Node<T>; 8]>>,
+}
+
+impl<T: CoordFloat, G: BoundingCube<T>> Octree<T, G> {
+    /// Creates an empty octree covering `bounds`.
+    pub fn new(bounds: Cube<T>) -> Self {
+        Self {
+            root: Node::new(bounds),
+            entries: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Creates an octree covering exactly the given geometries, and inserts them.
+    ///
+    /// The ids of the geometries are their positions in `geometries`.
+    pub fn bulk_load(geometries: Vec<G>) -> Self {
+        let bounds = geometries
+            .iter()
+            .filter_map(|geometry| geometry.bounding_cube().into())
+            .reduce(|acc, cube| acc.union(&cube))
+            .unwrap_or_else(|| Cube::new(CoordZ::zero(), CoordZ::zero()));
+        let mut tree = Self::new(bounds);
+        for geometry in geometries {
+            tree.insert(geometry);
+        }
+        tree
+    }
+
+    /// The bounds the tree was created with.
+    pub fn bounds(&self) -> Cube<T> {
+        self.root.bounds
+    }
+
+    /// The number of geometries in the tree.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts a geometry, returning its id.
+    pub fn insert(&mut self, geometry: G) -> usize {
+        let bounds = geometry.bounding_cube().into();
+        let entry = Some(Entry { bounds, geometry });
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.entries[id] = entry;
+                id
+            }
+            None => {
+                self.entries.push(entry);
+                self.entries.len() - 1
+            }
+        };
+        if let Some(bounds) = bounds {
+            self.root.insert(id, &bounds, &self.entries, 0);
+        }
+        self.len += 1;
+        id
+    }
+
+    /// Removes the geometry with the given id, returning it.
+    pub fn remove(&mut self, id: usize) -> Option<G> {
+        let entry = self.entries.get_mut(id)?.take()?;
+        if let Some(bounds) = &entry.bounds {
+            let removed = self.root.remove(id, bounds);
+            debug_assert!(removed);
+        }
+        self.free.push(id);
+        self.len -= 1;
+        Some(entry.geometry)
+    }
+
+    pub fn get(&self, id: usize) -> Option<&G> {
+        self.entries.get(id)?.as_ref().map(|entry| &entry.geometry)
+    }
+
+    /// Iterates over all geometries and their ids, in id order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &G)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(id, entry)| Some((id, &entry.as_ref()?.geometry)))
+    }
+
+    /// The geometries whose bounding cube intersects `bounds`.
+    pub fn query(&self, bounds: &Cube<T>) -> impl Iterator<Item = (usize, &G)> {
+        let mut ids = Vec::new();
+        self.root.query(bounds, &self.entries, &mut ids);
+        ids.into_iter().map(move |id| (id, self.entry(id)))
+    }
+
+    /// The `k` geometries closest to `coord`, nearest first.
+    ///
+    /// Geometries are ranked by the distance from `coord` to their bounding cube,
+    /// which is the exact distance for points. Geometries whose bounding cube
+    /// contains `coord` are at distance zero.
+    pub fn nearest_neighbors(&self, coord: CoordZ<T>, k: usize) -> Vec<(usize, &G)> {
+        let mut found = Vec::with_capacity(k);
+        if k == 0 {
+            return found;
+        }
+        let mut queue = BinaryHeap::new();
+        queue.push(Candidate {
+            distance: distance_2(coord, &self.root.bounds),
+            item: Item::Node(&self.root),
+        });
+        while let Some(Candidate { item, .. }) = queue.pop() {
+            match item {
+                Item::Entry(id) => {
+                    found.push((id, self.entry(id)));
+                    if found.len() == k {
+                        break;
+                    }
+                }
+                Item::Node(node) => {
+                    for &id in &node.ids {
+                        let bounds = self.entries[id].as_ref().and_then(|entry| entry.bounds);
+                        if let Some(bounds) = bounds {
+                            queue.push(Candidate {
+                                distance: distance_2(coord, &bounds),
+                                item: Item::Entry(id),
+                            });
+                        }
+                    }
+                    for child in node.children.iter().flat_map(|children| children.iter()) {
+                        queue.push(Candidate {
+                            distance: distance_2(coord, &child.bounds),
+                            item: Item::Node(child),
+                        });
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    /// The geometries whose bounding cube is hit by `ray`, with the parameter at
+    /// which the ray enters the bounding cube, ordered by that parameter.
+    pub fn ray_query(&self, ray: &Ray<T>) -> Vec<(usize, &G, T)> {
+        let mut hits = Vec::new();
+        self.root.ray_query(ray, &self.entries, &mut hits);
+        hits.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+        hits.into_iter()
+            .map(|(id, t)| (id, self.entry(id), t))
+            .collect()
+    }
+
+    fn entry(&self, id: usize) -> &G {
+        &self.entries[id].as_ref().expect("live entry").geometry
+    }
+}
+
+impl<T: CoordFloat> Node<T> {
+    fn new(bounds: Cube<T>) -> Self {
+        Self {
+            bounds,
+            ids: Vec::new(),
+            children: None,
+        }
+    }
+
+    fn insert<G>(
+        &mut self,
+        id: usize,
+        bounds: &Cube<T>,
+        entries: &[Option<Entry<T, G>>],
+        depth: usize,
+    ) {
+        if let Some(children) = &mut self.children {
+            if let Some(child) = children
+                .iter_mut()
+                .find(|child| contains(&child.bounds, bounds))
+            {
+                return child.insert(id, bounds, entries, depth + 1);
+            }
+        }
+        self.ids.push(id);
+        if self.children.is_none() && self.ids.len() > NODE_CAPACITY && depth < MAX_DEPTH {
+            self.split(entries, depth);
+        }
+    }
+
+    fn split<G>(&mut self, entries: &[Option<Entry<T, G>>], depth: usize) {
+        let (min, mid, max) = (self.bounds.min(), self.bounds.center(), self.bounds.max());
+        let octant = |i: usize| {
+            let pick = |bit: usize, low: T, mid: T, high: T| {
+                if i & bit == 0 {
+                    (low, mid)
+                } else {
+                    (mid, high)
+                }
+            };
+            let (x0, x1) = pick(1, min.x, mid.x, max.x);
+            let (y0, y1) = pick(2, min.y, mid.y, max.y);
+            let (z0, z1) = pick(4, min.z, mid.z, max.z);
+            Node::new(Cube::new((x0, y0, z0), (x1, y1, z1)))
+        };
+        self.children = Some(Box::new(core::array::from_fn(octant)));
+        for id in core::mem::take(&mut self.ids) {
+            let bounds = entries[id]
+                .as_ref()
+                .and_then(|entry| entry.bounds)
+                .expect("indexed entry");
+            self.insert(id, &bounds, entries, depth);
+        }
+    }
+
+    /// Removes `id` from the node along the path it was inserted on.
+    fn remove(&mut self, id: usize, bounds: &Cube<T>) -> bool {
+        if let Some(position) = self.ids.iter().position(|&other| other == id) {
+            self.ids.swap_remove(position);
+            return true;
+        }
+        let Some(children) = &mut self.children else {
+            return false;
+        };
+        let removed = children
+            .iter_mut()
+            .find(|child| contains(&child.bounds, bounds))
+            .is_some_and(|child| child.remove(id, bounds));
+        if removed {
+            self.merge();
+        }
+        removed
+    }
+
+    /// Pulls the geometries of leaf children back up once they fit in this node.
+    fn merge(&mut self) {
+        let Some(children) = &mut self.children else {
+            return;
+        };
+        let leaves = children.iter().all(|child| child.children.is_none());
+        let count = self.ids.len() + children.iter().map(|child| child.ids.len()).sum::<usize>();
+        if leaves && count <= NODE_CAPACITY {
+            for child in children.iter_mut() {
+                self.ids.append(&mut child.ids);
+            }
+            self.children = None;
+        }
+    }
+
+    fn query<G>(&self, bounds: &Cube<T>, entries: &[Option<Entry<T, G>>], found: &mut Vec<usize>) {
+        for &id in &self.ids {
+            if entry_bounds(entries, id).intersects(bounds) {
+                found.push(id);
+            }
+        }
+        for child in self.children.iter().flat_map(|children| children.iter()) {
+            if child.bounds.intersects(bounds) {
+                child.query(bounds, entries, found);
+            }
+        }
+    }
+
+    fn ray_query<G>(
+        &self,
+        ray: &Ray<T>,
+        entries: &[Option<Entry<T, G>>],
+        hits: &mut Vec<(usize, T)>,
+    ) {
+        for &id in &self.ids {
+            if let Some((enter, _)) = ray.intersect_cube(&entry_bounds(entries, id)) {
+                hits.push((id, enter));
+            }
+        }
+        for child in self.children.iter().flat_map(|children| children.iter()) {
+            if ray.intersect_cube(&child.bounds).is_some() {
+                child.ray_query(ray, entries, hits);
+            }
+        }
+    }
+}
+
+fn entry_bounds<T: CoordFloat, G>(entries: &[Option<Entry<T, G>>], id: usize) -> Cube<T> {
+    entries[id]
+        .as_ref()
+        .and_then(|entry| entry.bounds)
+        .expect("indexed entry")
+}
+
+fn contains<T: CoordFloat>(outer: &Cube<T>, inner: &Cube<T>) -> bool {
+    outer.contains_coord(inner.min()) && outer.contains_coord(inner.max())
+}
+
+/// The squared distance from `coord` to the closest point of `cube`.
+fn distance_2<T: CoordFloat>(coord: CoordZ<T>, cube: &Cube<T>) -> T {
+    let (min, max) = (cube.min(), cube.max());
+    let axis = |value: T, min: T, max: T| {
+        let delta = if value < min {
+            min - value
+        } else if value > max {
+            value - max
+        } else {
+            T::zero()
+        };
+        delta * delta
+    };
+    axis(coord.x, min.x, max.x) + axis(coord.y, min.y, max.y) + axis(coord.z, min.z, max.z)
+}
+
+enum Item<'a, T: CoordFloat> {
+    Node(&'a Node<T>),
+    Entry(usize),
+}
+
+/// An entry in the nearest-neighbour queue, ordered so that the closest candidate
+/// is popped first.
+struct Candidate<'a, T: CoordFloat> {
+    distance: T,
+    item: Item<'a, T>,
+}
+
+impl<T: CoordFloat> Ord for Candidate<'_, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed, as `BinaryHeap` is a max-heap; entries go before nodes at equal
+        // distances, so ties are settled without descending further
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| {
+                matches!(self.item, Item::Entry(_)).cmp(&matches!(other.item, Item::Entry(_)))
+            })
+    }
+}
+
+impl<T: CoordFloat> PartialOrd for Candidate<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: CoordFloat> PartialEq for Candidate<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: CoordFloat> Eq for Candidate<'_, T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{LineZ, PointZ};
+    use alloc::vec;
+
+    fn grid() -> Octree<f64, PointZ<f64>> {
+        let mut tree = Octree::new(Cube::new((0., 0., 0.), (10., 10., 10.)));
+        for x in 0..10 {
+            for y in 0..10 {
+                for z in 0..10 {
+                    tree.insert(PointZ::new(x as f64, y as f64, z as f64));
+                }
+            }
+        }
+        tree
+    }
+
+    #[test]
+    fn queries_match_brute_force() {
+        let tree = grid();
+        assert_eq!(tree.len(), 1000);
+        assert!(tree.root.children.is_some());
+
+        let bounds = Cube::new((2.5, 3., 0.), (5., 4.5, 9.5));
+        let mut ids: Vec<usize> = tree.query(&bounds).map(|(id, _)| id).collect();
+        ids.sort();
+        let expected: Vec<usize> = tree
+            .iter()
+            .filter(|(_, point)| bounds.contains_coord(point.0))
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ids, expected);
+        assert_eq!(ids.len(), 3 * 2 * 10);
+
+        let target = CoordZ {
+            x: 3.2,
+            y: 7.9,
+            z: -4.,
+        };
+        let nearest: Vec<PointZ> = tree
+            .nearest_neighbors(target, 5)
+            .into_iter()
+            .map(|(_, point)| *point)
+            .collect();
+        let mut expected: Vec<PointZ> = tree.iter().map(|(_, point)| *point).collect();
+        let distance = |p: &PointZ| distance_2(target, &Cube::new(p.0, p.0));
+        expected.sort_by(|a, b| distance(a).partial_cmp(&distance(b)).unwrap());
+        assert_eq!(nearest[0], PointZ::new(3., 8., 0.));
+        for (found, expected) in nearest.iter().zip(&expected) {
+            assert_eq!(distance(found), distance(expected));
+        }
+    }
+
+    #[test]
+    fn remove_and_reinsert() {
+        let mut tree = grid();
+        for id in 0..995 {
+            assert!(tree.remove(id).is_some());
+        }
+        assert_eq!(tree.remove(0), None);
+        assert_eq!(tree.len(), 5);
+        // the remaining geometries were pulled back up into the root
+        assert!(tree.root.children.is_none());
+        assert_eq!(tree.root.ids.len(), 5);
+
+        let id = tree.insert(PointZ::new(0.5, 0.5, 0.5));
+        assert!(id < 995);
+        assert_eq!(tree.get(id), Some(&PointZ::new(0.5, 0.5, 0.5)));
+        let nearest = tree.nearest_neighbors(CoordZ::zero(), 1);
+        assert_eq!(nearest, vec![(id, &PointZ::new(0.5, 0.5, 0.5))]);
+    }
+
+    #[test]
+    fn rays_and_outliers() {
+        let mut tree = Octree::new(Cube::new((0., 0., 0.), (10., 10., 10.)));
+        let near = tree.insert(LineZ::new((1., 0., 0.), (1., 10., 10.)));
+        let far = tree.insert(LineZ::new((8., 4., 4.), (9., 6., 6.)));
+        let outside = tree.insert(LineZ::new((20., 5., 5.), (21., 5., 5.)));
+        let ray = Ray::new(
+            CoordZ {
+                x: 0.,
+                y: 5.,
+                z: 5.,
+            },
+            CoordZ {
+                x: 2.,
+                y: 0.,
+                z: 0.,
+            },
+        );
+        let hits: Vec<(usize, f64)> = tree
+            .ray_query(&ray)
+            .into_iter()
+            .map(|(id, _, t)| (id, t))
+            .collect();
+        assert_eq!(hits, vec![(near, 0.5), (far, 4.), (outside, 10.)]);
+
+        let backwards = Ray::new(ray.origin, -ray.direction);
+        assert!(tree.ray_query(&backwards).is_empty());
+        assert_eq!(
+            tree.query(&Cube::new((15., 0., 0.), (30., 10., 10.)))
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>(),
+            vec![outside]
+        );
+    }
+}
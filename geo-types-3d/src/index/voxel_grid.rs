@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use crate::{CoordFloat, CoordZ, MultiPointZ, PointZ};
+
+/// The integer coordinates of a voxel: the point `(x, y, z)` lies in voxel
+/// `[⌊x / size⌋, ⌊y / size⌋, ⌊z / size⌋]`.
+pub type Voxel = [i64; 3];
+
+/// A spatial hash of points into cubic voxels of a fixed size.
+///
+/// Looking up a voxel takes constant time, so for dense, evenly spread points such
+/// as LiDAR returns, radius queries only cost as much as the voxels they overlap.
+/// Pick a cell size close to the typical query radius. For unevenly spread points,
+/// prefer a [`KdTree`](super::KdTree) or an [`Octree`](super::Octree).
+///
+/// # Examples
+///
+/// ```
+/// use geo_types_3d::index::VoxelGrid;
+/// use geo_types_3d::PointZ;
+///
+/// let mut grid = VoxelGrid::new(1.0);
+/// let a = grid.insert(PointZ::new(0.2, 0.2, 0.2));
+/// let b = grid.insert(PointZ::new(0.9, 0.5, 0.1));
+/// let c = grid.insert(PointZ::new(1.1, 0.5, 0.1));
+///
+/// let same_voxel: Vec<usize> = grid.query_cell(&PointZ::new(0.5, 0.5, 0.5)).map(|(id, _)| id).collect();
+/// assert_eq!(same_voxel, vec![a, b]);
+///
+/// let near: Vec<usize> = grid.query_radius(&PointZ::new(1.0, 0.5, 0.1), 0.15).into_iter().map(|(id, _)| id).collect();
+/// assert_eq!(near, vec![b, c]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct VoxelGrid<T: CoordFloat = f64> {
+    cell_size: T,
+    points: Vec<PointZ<T>>,
+    cells: HashMap<Voxel, Vec<usize>>,
+}
+
+impl<T: CoordFloat> VoxelGrid<T> {
+    /// Creates an empty grid of voxels with sides of `cell_size`.
+    ///
+    /// # Panics
+    ///
+    /// If `cell_size` isn't positive and finite.
+    pub fn new(cell_size: T) -> Self {
+        assert!(
+            cell_size > T::zero() && cell_size.is_finite(),
+            "cell size must be positive and finite"
+        );
+        Self {
+            cell_size,
+            points: Vec::new(),
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Creates a grid holding `points`, identified by their position.
+    pub fn from_points(cell_size: T, points: impl IntoIterator<Item = PointZ<T>>) -> Self {
+        let mut grid = Self::new(cell_size);
+        for point in points {
+            grid.insert(point);
+        }
+        grid
+    }
+
+    pub fn cell_size(&self) -> T {
+        self.cell_size
+    }
+
+    /// The number of points in the grid.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The number of voxels holding at least one point.
+    pub fn occupied_cells(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn get(&self, id: usize) -> Option<&PointZ<T>> {
+        self.points.get(id)
+    }
+
+    /// The voxel containing `coord`.
+    ///
+    /// Ordinates too far from the origin to be counted in voxels are clamped to the
+    /// range of `i64`.
+    pub fn cell(&self, coord: CoordZ<T>) -> Voxel {
+        let index = |value: T| {
+            let index = (value / self.cell_size).floor();
+            index.to_i64().unwrap_or(if index > T::zero() {
+                i64::MAX
+            } else {
+                i64::MIN
+            })
+        };
+        [index(coord.x), index(coord.y), index(coord.z)]
+    }
+
+    /// Adds a point, returning its id.
+    pub fn insert(&mut self, point: PointZ<T>) -> usize {
+        let id = self.points.len();
+        self.cells.entry(self.cell(point.0)).or_default().push(id);
+        self.points.push(point);
+        id
+    }
+
+    /// The points in the same voxel as `point`, in insertion order.
+    pub fn query_cell(&self, point: &PointZ<T>) -> impl Iterator<Item = (usize, &PointZ<T>)> {
+        self.cells
+            .get(&self.cell(point.0))
+            .into_iter()
+            .flatten()
+            .map(move |&id| (id, &self.points[id]))
+    }
+
+    /// The points at most `radius` away from `point`, in insertion order.
+    pub fn query_radius(&self, point: &PointZ<T>, radius: T) -> Vec<(usize, &PointZ<T>)> {
+        let offset = CoordZ {
+            x: radius,
+            y: radius,
+            z: radius,
+        };
+        let (min, max) = (self.cell(point.0 - offset), self.cell(point.0 + offset));
+        let radius_2 = radius * radius;
+        let mut ids = Vec::new();
+        let cells = (0..3)
+            .map(|axis| max[axis] as i128 - min[axis] as i128 + 1)
+            .fold(1i128, i128::saturating_mul);
+        if cells > self.cells.len() as i128 {
+            // cheaper to look at every occupied voxel than every voxel in range
+            for (cell, members) in &self.cells {
+                if (0..3).all(|axis| min[axis] <= cell[axis] && cell[axis] <= max[axis]) {
+                    ids.extend(members);
+                }
+            }
+        } else {
+            for x in min[0]..=max[0] {
+                for y in min[1]..=max[1] {
+                    for z in min[2]..=max[2] {
+                        ids.extend(self.cells.get(&[x, y, z]).into_iter().flatten());
+                    }
+                }
+            }
+        }
+        ids.retain(|&id| {
+            let other: &PointZ<T> = &self.points[id];
+            let delta = other.0 - point.0;
+            delta.x * delta.x + delta.y * delta.y + delta.z * delta.z <= radius_2
+        });
+        ids.sort_unstable();
+        ids.into_iter().map(|id| (id, &self.points[id])).collect()
+    }
+
+    /// Iterates over the occupied voxels and the ids of their points.
+    pub fn cells(&self) -> impl Iterator<Item = (&Voxel, &[usize])> {
+        self.cells.iter().map(|(cell, ids)| (cell, ids.as_slice()))
+    }
+}
+
+impl<T: CoordFloat> MultiPointZ<T> {
+    /// Reduces the points to one per voxel of side `cell_size`: the centroid of the
+    /// points in that voxel.
+    ///
+    /// The centroids are in the order their voxels were first visited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::{MultiPointZ, PointZ};
+    ///
+    /// let points = MultiPointZ(vec![
+    ///     PointZ::new(0.1, 0.1, 0.1),
+    ///     PointZ::new(5.0, 5.0, 5.0),
+    ///     PointZ::new(0.3, 0.5, 0.1),
+    /// ]);
+    /// let downsampled = points.voxel_downsample(1.0);
+    /// assert_eq!(
+    ///     downsampled.0,
+    ///     vec![PointZ::new(0.2, 0.3, 0.1), PointZ::new(5.0, 5.0, 5.0)]
+    /// );
+    /// ```
+    pub fn voxel_downsample(&self, cell_size: T) -> MultiPointZ<T> {
+        let grid = VoxelGrid::new(cell_size);
+        let mut order: HashMap<Voxel, usize> = HashMap::new();
+        let mut sums: Vec<(CoordZ<T>, T)> = Vec::new();
+        for point in &self.0 {
+            let slot = *order.entry(grid.cell(point.0)).or_insert_with(|| {
+                sums.push((CoordZ::zero(), T::zero()));
+                sums.len() - 1
+            });
+            let (sum, count) = &mut sums[slot];
+            *sum += point.0;
+            *count = *count + T::one();
+        }
+        sums.into_iter()
+            .map(|(sum, count)| PointZ(sum / count))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn radius_query_matches_brute_force() {
+        let points: Vec<PointZ> = (0..1000)
+            .map(|i| {
+                let i = i as f64;
+                PointZ::new(
+                    (i * 0.618).fract() * 10.,
+                    (i * 0.414).fract() * 10.,
+                    (i * 0.732).fract() - 0.5,
+                )
+            })
+            .collect();
+        let grid = VoxelGrid::from_points(0.5, points.iter().copied());
+        assert_eq!(grid.len(), 1000);
+        for (target, radius) in [(points[3], 0.7), (PointZ::new(-1., 5., 0.), 1.5)] {
+            let found: Vec<usize> = grid
+                .query_radius(&target, radius)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect();
+            let expected: Vec<usize> = (0..points.len())
+                .filter(|&id| {
+                    let delta = points[id].0 - target.0;
+                    delta.x * delta.x + delta.y * delta.y + delta.z * delta.z <= radius * radius
+                })
+                .collect();
+            assert_eq!(found, expected);
+        }
+        // a radius covering far more voxels than are occupied
+        assert_eq!(grid.query_radius(&points[0], 1e4).len(), 1000);
+    }
+
+    #[test]
+    fn negative_coordinates_and_downsampling() {
+        let grid = VoxelGrid::new(2.0);
+        assert_eq!(
+            grid.cell(CoordZ {
+                x: -0.5,
+                y: 0.5,
+                z: 4.
+            }),
+            [-1, 0, 2]
+        );
+
+        let points: MultiPointZ = (0..100)
+            .map(|i| PointZ::new(i as f64 / 10., 0., i as f64 / 10.))
+            .collect();
+        let downsampled = points.voxel_downsample(2.5);
+        // four voxels along the diagonal, holding 25 points each
+        assert_eq!(downsampled.0.len(), 4);
+        assert_relative_eq!(downsampled.0[0], PointZ::new(1.2, 0., 1.2));
+        assert_relative_eq!(downsampled.0[3], PointZ::new(8.7, 0., 8.7));
+    }
+}
@@ -62,11 +62,38 @@
 //! The following optional [Cargo features] are available:
 //!
 //! - `std`: Enables use of the full `std` library. Enabled by default.
-//! - `multithreading`: Enables multi-threaded iteration over `Multi*` geometries. **Disabled**
-//!    by default but **enabled** by `geo`'s default features.
+//! - `multithreading`: Enables multi-threaded iteration over `Multi*` geometries, and
+//!   parallel versions of some algorithms such as [`TinZ::par_viewshed`], and
+//!   [`process::par_process`]. **Disabled** by default but **enabled** by `geo`'s default
+//!   features.
 //! - `approx`: Allows geometry types to be checked for approximate equality with [approx]
 //! - `arbitrary`: Allows geometry types to be created from unstructured input with [arbitrary]
-//! - `serde`: Allows geometry types to be serialized and deserialized with [Serde]
+//! - `capi`: Adds a C API in [`capi`], exchanging geometries as coordinates or well-known
+//!   binary, for use from C, C++ and other languages
+//! - `geohash`: Adds [`PointZ::geohash`], placing points in [`cells`] with [geohash]
+//! - `h3`: Adds [`PointZ::to_h3`], placing points in [`cells`] with [h3o]
+//! - `http`: Adds a client fetching the items of OGC API - Features collections in
+//!   [`conversion::ogc_api`], with [ureq]
+//! - `rkyv`: Allows geometry types to be archived with [rkyv], so that large caches of
+//!   geometries can be memory-mapped and read in place, as `rkyv::Archived<Geometry>` and so on,
+//!   without deserializing them
+//! - `s2`: Adds [`PointZ::to_s2_cell`], placing points in [`cells`] of the S2 grid
+//! - `serde`: Allows geometry types to be serialized and deserialized with [Serde], in JSON or
+//!   in binary formats such as bincode
+//! - `schemars`: Implements `JsonSchema` for geometry types with [schemars], describing their
+//!   serde representation, such as for OpenAPI specs
+//! - `proptest`: Adds [`proptest`](mod@proptest) strategies generating valid geometries
+//! - `pyo3`: Converts `PointZ`, `LineStringZ` and `PolygonZ` to and from Python objects such
+//!   as shapely geometries with [pyo3], in [`conversion::python`]
+//! - `rand`: Adds [`random`] generators of random points, point clouds and walks with [rand]
+//! - `mmap`: Allows a [`dataset::Dataset`] to be opened from a memory-mapped file
+//! - `sample-data`: Embeds small example datasets in [`sample_data`]
+//! - `simd-json`: Parses GeoJSON in [`conversion::geojson`] with [simd-json]
+//! - `tokio`: Allows datasets, and with `http` OGC API - Features items, to be read as a
+//!   `Stream` and datasets to be written from async code with [tokio]. FlatGeobuf and LAS
+//!   are not supported
+//! - `wasm-bindgen`: Adds JavaScript classes for geometries, their coordinates and GeoJSON in
+//!   [`wasm`], with [wasm-bindgen]
 //! - `use-rstar_0_8`: Allows geometry types to be inserted into [rstar] R*-trees (`rstar v0.8`)
 //! - `use-rstar_0_9`: Allows geometry types to be inserted into [rstar] R*-trees (`rstar v0.9`)
 //! - `use-rstar_0_10`: Allows geometry types to be inserted into [rstar] R*-trees (`rstar v0.10`)
@@ -77,15 +104,31 @@
 //! the moment, the `arbitrary` and `use-rstar_0_8` features require `std`. This may change in a
 //! future release.
 //!
+//! Without `std`, the geometry types, the [`index`]es other than the voxel grid, in-memory
+//! [`dataset`]s, and the algorithms that don't depend on [`geo`] remain available. The
+//! [`conversion`] modules other than [`conversion::wkb`], the [`process`] module, writing datasets, and
+//! the buffer, boolean and geodesic algorithms need `std`, as do the `arbitrary`, `capi`, `geohash`, `h3`, `http`, `mmap`, `multithreading`, `proptest`,
+//! `pyo3`, `sample-data`, `simd-json`, `tokio` and `wasm-bindgen` features, which enable it.
+//!
 //! [approx]: https://github.com/brendanzab/approx
 //! [arbitrary]: https://github.com/rust-fuzz/arbitrary
 //! [Cargo features]: https://doc.rust-lang.org/cargo/reference/features.html
+//! [geohash]: https://github.com/georust/geohash
 //! [GeoRust]: https://georust.org
+//! [pyo3]: https://pyo3.rs
+//! [rand]: https://docs.rs/rand
 //! [GEOS]: https://trac.osgeo.org/geos
+//! [h3o]: https://github.com/HydroniumLabs/h3o
 //! [JTS]: https://github.com/locationtech/jts
 //! [OGC-SFA]: https://www.ogc.org/standards/sfa
+//! [rkyv]: https://rkyv.org
 //! [rstar]: https://github.com/Stoeoef/rstar
+//! [schemars]: https://graham.cool/schemars/
 //! [Serde]: https://serde.rs/
+//! [simd-json]: https://github.com/simd-lite/simd-json
+//! [tokio]: https://tokio.rs
+//! [ureq]: https://github.com/algesten/ureq
+//! [wasm-bindgen]: https://rustwasm.github.io/docs/wasm-bindgen/
 extern crate alloc;
 
 use core::fmt::Debug;
@@ -137,9 +180,41 @@ mod wkt_macro;
 
 pub mod conversion;
 
+pub mod dataset;
+
+#[cfg(feature = "std")]
+pub mod process;
+
+pub mod algorithm;
+
+pub mod curves;
+
+pub mod cells;
+
+pub mod index;
+
+pub mod tiling;
+
+pub mod bench_helpers;
+
+#[cfg(feature = "sample-data")]
+pub mod sample_data;
+
 #[cfg(feature = "arbitrary")]
 mod arbitrary;
 
+#[cfg(feature = "capi")]
+pub mod capi;
+
+#[cfg(feature = "proptest")]
+pub mod proptest;
+
+#[cfg(feature = "rand")]
+pub mod random;
+
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
+
 #[cfg(any(
     feature = "rstar_0_8",
     feature = "rstar_0_9",
@@ -152,6 +227,8 @@ pub mod private_utils;
 
 mod debug;
 
+mod splitmix;
+
 #[doc(hidden)]
 pub mod _alloc {
     //! Needed to access these types from `alloc` in macros when the std feature is
@@ -43,6 +43,14 @@ macro_rules! coordZ {
     };
 }
 
+/// Creates a [`CoordZM`](crate::CoordZM) from its ordinates and measure.
+#[macro_export]
+macro_rules! coordZM {
+    (x: $x:expr, y: $y:expr, z: $z:expr, m: $m:expr $(,)? ) => {
+        $crate::CoordZM { x: $x, y: $y, z: $z, m: $m }
+    };
+}
+
 /// Creates a [`LineStringZ`] containing the given coordinates.
 ///
 /// ```txt
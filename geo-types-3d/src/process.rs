@@ -0,0 +1,252 @@
+//! Streaming processing of feature sources far larger than memory, as the
+//! backbone of ETL pipelines.
+//!
+//! A source is any iterator of fallible features, read as it is consumed: the
+//! features of a GeoJSON file through [`geojson_features`], those of a
+//! [`Dataset`](crate::dataset::Dataset) through `dataset.iter()` and
+//! [`FeatureView::to_geometry`](crate::dataset::FeatureView::to_geometry), or the
+//! output of any other streaming reader. [`process`] passes each feature through a
+//! closure and hands the result to a sink, such as a
+//! [`DatasetWriter`](crate::dataset::DatasetWriter) or a GeoJSON writer, one
+//! feature at a time. With the `multithreading` feature, [`par_process`] runs the
+//! closure on the rayon thread pool, a chunk of features at a time, so memory stays
+//! bounded by the chunk size however large the source.
+//!
+//! Both stop at the first error, from the source, the closure or the sink, and
+//! otherwise return the number of features passed to the sink.
+//!
+//! # Examples
+//!
+//! ```
+//! use geo_types_3d::algorithm::BoundingCube;
+//! use geo_types_3d::process::{geojson_features, process};
+//! use geo_types_3d::Error;
+//!
+//! let ndjson = br#"{"type": "Feature", "properties": {"name": "mast"}, "geometry": {"type": "Point", "coordinates": [1, 2, 30]}}
+//! {"type": "Feature", "properties": {"name": "cable"}, "geometry": {"type": "LineString", "coordinates": [[0, 0, 10], [5, 5, 12]]}}
+//! "#;
+//!
+//! // the name and top of each feature, streamed from the file
+//! let mut tops = Vec::new();
+//! let count = process(
+//!     geojson_features::<_, f64>(&ndjson[..]),
+//!     |(geometry, properties)| {
+//!         let top = geometry.bounding_cube().map_or(0., |cube| cube.max().z);
+//!         Ok::<_, Error>((properties["name"].to_string(), top))
+//!     },
+//!     |top| {
+//!         tops.push(top);
+//!         Ok(())
+//!     },
+//! )
+//! .unwrap();
+//! assert_eq!(count, 2);
+//! assert_eq!(tops, [(r#""mast""#.to_string(), 30.), (r#""cable""#.to_string(), 12.)]);
+//! ```
+
+use std::io::Read;
+
+#[cfg(feature = "multithreading")]
+use rayon::prelude::*;
+
+use geojson::JsonObject;
+
+use crate::conversion::geojson::FeatureReader;
+use crate::{CoordFloat, Error, Geometry};
+
+/// The features of GeoJSON read from `reader` one at a time, as by a
+/// [`FeatureReader`], with errors as this crate's [`Error`].
+pub fn geojson_features<R: Read, T: CoordFloat>(
+    reader: R,
+) -> impl Iterator<Item = Result<(Geometry<T>, JsonObject), Error>> {
+    FeatureReader::new(reader).map(|feature| feature.map_err(Error::from))
+}
+
+/// Passes each feature of `source` through `f`, and the result to `sink`, in
+/// order, holding one feature at a time.
+///
+/// Returns the number of features passed to `sink`, or the first error.
+pub fn process<S, I, R, E>(
+    source: S,
+    mut f: impl FnMut(I) -> Result<R, E>,
+    mut sink: impl FnMut(R) -> Result<(), E>,
+) -> Result<usize, E>
+where
+    S: IntoIterator<Item = Result<I, E>>,
+{
+    let mut count = 0;
+    for feature in source {
+        sink(f(feature?)?)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Like [`process`], but runs `f` in parallel on chunks of `chunk_size` features,
+/// passing the results of each chunk to `sink` in order before the next is read.
+///
+/// At most `chunk_size` features and their results are held at a time. The
+/// features of a chunk read before an error in the source are still processed.
+///
+/// # Panics
+///
+/// If `chunk_size` is zero.
+#[cfg(feature = "multithreading")]
+pub fn par_process<S, I, R, E>(
+    source: S,
+    chunk_size: usize,
+    f: impl Fn(I) -> Result<R, E> + Sync,
+    mut sink: impl FnMut(R) -> Result<(), E>,
+) -> Result<usize, E>
+where
+    S: IntoIterator<Item = Result<I, E>>,
+    I: Send,
+    R: Send,
+    E: Send,
+{
+    assert!(chunk_size > 0, "chunks must hold at least one feature");
+    let mut source = source.into_iter();
+    let mut chunk = Vec::with_capacity(chunk_size);
+    let mut count = 0;
+    loop {
+        let mut failure = None;
+        for feature in source.by_ref().take(chunk_size) {
+            match feature {
+                Ok(feature) => chunk.push(feature),
+                Err(error) => {
+                    failure = Some(error);
+                    break;
+                }
+            }
+        }
+        if chunk.is_empty() && failure.is_none() {
+            return Ok(count);
+        }
+        let results: Vec<Result<R, E>> = chunk.par_drain(..).map(&f).collect();
+        for result in results {
+            sink(result?)?;
+            count += 1;
+        }
+        if let Some(error) = failure {
+            return Err(error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::LengthZ;
+    use crate::dataset::DatasetBuilder;
+    use crate::{LineStringZ, PointZ};
+
+    fn features(count: usize) -> Vec<Result<LineStringZ, Error>> {
+        (0..count)
+            .map(|i| Ok(vec![(0., 0., 0.), (i as f64, 0., 0.)].into()))
+            .collect()
+    }
+
+    #[test]
+    fn in_order() {
+        let mut lengths = Vec::new();
+        let count = process(
+            features(100),
+            |line| Ok(line.length()),
+            |length| {
+                lengths.push(length);
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(count, 100);
+        assert_eq!(lengths, (0..100).map(|i| i as f64).collect::<Vec<_>>());
+
+        #[cfg(feature = "multithreading")]
+        {
+            let mut parallel = Vec::new();
+            let count = par_process(
+                features(100),
+                7,
+                |line| Ok(line.length()),
+                |length| {
+                    parallel.push(length);
+                    Ok(())
+                },
+            )
+            .unwrap();
+            assert_eq!(count, 100);
+            assert_eq!(parallel, lengths);
+        }
+
+        // features of a dataset
+        let mut builder = DatasetBuilder::new();
+        for i in 0..10 {
+            builder
+                .push(&Geometry::PointZ(PointZ::new(0., 0., i as f64)))
+                .unwrap();
+        }
+        let dataset = builder.finish();
+        let mut total = 0.;
+        process(
+            dataset.iter().map(|feature| feature.to_geometry()),
+            |geometry| match geometry {
+                Geometry::PointZ(point) => Ok(point.z()),
+                _ => Err(Error::MismatchedGeometry {
+                    expected: "PointZ",
+                    found: "other",
+                }),
+            },
+            |z| {
+                total += z;
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(total, 45.);
+    }
+
+    #[test]
+    fn stops_at_errors() {
+        let mut source = features(10);
+        source[4] = Err(Error::InvalidTrajectory { reason: "broken" });
+        let mut seen = 0;
+        let result = process(
+            source,
+            |line| Ok(line.length()),
+            |_| {
+                seen += 1;
+                Ok(())
+            },
+        );
+        assert!(matches!(result, Err(Error::InvalidTrajectory { .. })));
+        assert_eq!(seen, 4);
+
+        let result = process(
+            features(10),
+            |line| Ok(line.length()),
+            |length| match length {
+                length if length < 3. => Ok(()),
+                _ => Err(Error::InvalidTrajectory { reason: "too long" }),
+            },
+        );
+        assert!(matches!(result, Err(Error::InvalidTrajectory { .. })));
+
+        #[cfg(feature = "multithreading")]
+        {
+            let mut source = features(10);
+            source[4] = Err(Error::InvalidTrajectory { reason: "broken" });
+            let mut seen = 0;
+            let result = par_process(
+                source,
+                3,
+                |line| Ok(line.length()),
+                |_| {
+                    seen += 1;
+                    Ok(())
+                },
+            );
+            assert!(result.is_err());
+            assert_eq!(seen, 4);
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! [proptest] strategies generating valid Z geometries.
+//!
+//! Every strategy takes a [`Cube`] bounding the coordinates it generates, and all
+//! coordinates are finite. Rings are closed, and polygons are simple: each
+//! exterior ring is star-shaped around a point, and its one optional hole lies well
+//! inside it. The polygons of a multi-polygon don't touch.
+//!
+//! Enable this module with the `proptest` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use geo_types_3d::algorithm::BoundingCube;
+//! use geo_types_3d::proptest::polygon_z;
+//! use geo_types_3d::Cube;
+//! use proptest::prelude::*;
+//! use proptest::test_runner::TestRunner;
+//!
+//! let bounds = Cube::new((0., 0., 0.), (100., 100., 10.));
+//! TestRunner::default()
+//!     .run(&polygon_z(bounds), |polygon| {
+//!         let cube = polygon.bounding_cube().unwrap();
+//!         prop_assert!(cube.min().x >= 0. && cube.max().x <= 100.);
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+//!
+//! [proptest]: https://docs.rs/proptest
+
+use core::f64::consts::PI;
+use core::ops::Range;
+
+use ::proptest::collection::vec;
+use ::proptest::prelude::*;
+
+use crate::{
+    CoordZ, Cube, Geometry, GeometryCollection, LineStringZ, LineZ, MultiLineStringZ, MultiPointZ,
+    MultiPolygonZ, PointZ, PolygonZ, Triangle,
+};
+
+/// The fewest vertices of an exterior ring, before closing. With at least four, the
+/// angle between neighbouring vertices stays below 135°, leaving room for a hole.
+const MIN_EXTERIOR_VERTICES: usize = 4;
+
+pub fn coord_z(bounds: Cube<f64>) -> impl Strategy<Value = CoordZ<f64>> {
+    let (min, max) = (bounds.min(), bounds.max());
+    (min.x..=max.x, min.y..=max.y, min.z..=max.z).prop_map(|(x, y, z)| CoordZ { x, y, z })
+}
+
+pub fn point_z(bounds: Cube<f64>) -> impl Strategy<Value = PointZ<f64>> {
+    coord_z(bounds).prop_map(PointZ)
+}
+
+pub fn line_z(bounds: Cube<f64>) -> impl Strategy<Value = LineZ<f64>> {
+    (coord_z(bounds), coord_z(bounds)).prop_map(|(start, end)| LineZ::new(start, end))
+}
+
+/// Line strings with a number of coordinates in `len`, and at least two.
+pub fn line_string_z(
+    bounds: Cube<f64>,
+    len: Range<usize>,
+) -> impl Strategy<Value = LineStringZ<f64>> {
+    let len = len.start.max(2)..len.end.max(3);
+    vec(coord_z(bounds), len).prop_map(LineStringZ)
+}
+
+pub fn triangle(bounds: Cube<f64>) -> impl Strategy<Value = Triangle<f64>> {
+    (coord_z(bounds), coord_z(bounds), coord_z(bounds)).prop_map(|(a, b, c)| Triangle(a, b, c))
+}
+
+pub fn cube(bounds: Cube<f64>) -> impl Strategy<Value = Cube<f64>> {
+    (coord_z(bounds), coord_z(bounds)).prop_map(|(a, b)| Cube::new(a, b))
+}
+
+/// Simple polygons with between 4 and 16 exterior vertices and at most one hole.
+pub fn polygon_z(bounds: Cube<f64>) -> impl Strategy<Value = PolygonZ<f64>> {
+    let (min, max) = (bounds.min(), bounds.max());
+    let (width, height) = (bounds.width(), bounds.height());
+    // keep the centre in the middle half, so the polygon has room around it
+    let centre = (
+        min.x + width / 4.0..=max.x - width / 4.0,
+        min.y + height / 4.0..=max.y - height / 4.0,
+    );
+    let exterior = star(MIN_EXTERIOR_VERTICES..17, 0.5..=1.0, min.z..=max.z);
+    let hole = prop::option::of(star(3..9, 0.05..=0.15, min.z..=max.z));
+    (centre, exterior, hole).prop_map(move |((cx, cy), exterior, hole)| {
+        let radius = (cx - min.x).min(max.x - cx).min(cy - min.y).min(max.y - cy);
+        let ring = |vertices: Vec<(f64, f64, f64)>| {
+            vertices
+                .into_iter()
+                .map(|(angle, r, z)| CoordZ {
+                    x: (cx + radius * r * angle.cos()).clamp(min.x, max.x),
+                    y: (cy + radius * r * angle.sin()).clamp(min.y, max.y),
+                    z,
+                })
+                .collect::<LineStringZ<f64>>()
+        };
+        PolygonZ::new(ring(exterior), hole.into_iter().map(ring).collect())
+    })
+}
+
+/// The vertices of a star-shaped ring, as angle, relative radius and z.
+///
+/// Each vertex lies in its own slice of the circle, and less than halfway through
+/// it, so neighbouring vertices are less than one and a half slices apart.
+fn star(
+    vertices: Range<usize>,
+    radius: core::ops::RangeInclusive<f64>,
+    z: core::ops::RangeInclusive<f64>,
+) -> impl Strategy<Value = Vec<(f64, f64, f64)>> {
+    vertices.prop_flat_map(move |n| {
+        vec((0.0..0.5, radius.clone(), z.clone()), n).prop_map(move |jitters| {
+            jitters
+                .into_iter()
+                .enumerate()
+                .map(|(i, (jitter, r, z))| ((i as f64 + jitter) * 2.0 * PI / n as f64, r, z))
+                .collect()
+        })
+    })
+}
+
+pub fn multi_point_z(
+    bounds: Cube<f64>,
+    len: Range<usize>,
+) -> impl Strategy<Value = MultiPointZ<f64>> {
+    vec(point_z(bounds), len).prop_map(MultiPointZ)
+}
+
+pub fn multi_line_string_z(
+    bounds: Cube<f64>,
+    len: Range<usize>,
+) -> impl Strategy<Value = MultiLineStringZ<f64>> {
+    vec(line_string_z(bounds, 2..10), len).prop_map(MultiLineStringZ)
+}
+
+/// Multi-polygons whose members lie in separate slabs of `bounds` along x, so
+/// they never touch.
+pub fn multi_polygon_z(
+    bounds: Cube<f64>,
+    len: Range<usize>,
+) -> impl Strategy<Value = MultiPolygonZ<f64>> {
+    len.prop_flat_map(move |n| {
+        let (min, max) = (bounds.min(), bounds.max());
+        let slab = bounds.width() / n.max(1) as f64;
+        (0..n)
+            .map(|i| {
+                let x0 = min.x + slab * i as f64;
+                polygon_z(Cube::new(
+                    (x0, min.y, min.z),
+                    ((x0 + slab).min(max.x), max.y, max.z),
+                ))
+            })
+            .collect::<Vec<_>>()
+            .prop_map(MultiPolygonZ)
+    })
+}
+
+/// Any one of the Z geometries, other than collections.
+pub fn geometry(bounds: Cube<f64>) -> impl Strategy<Value = Geometry<f64>> {
+    prop_oneof![
+        point_z(bounds).prop_map(Geometry::PointZ),
+        line_z(bounds).prop_map(Geometry::LineZ),
+        line_string_z(bounds, 2..10).prop_map(Geometry::LineStringZ),
+        polygon_z(bounds).prop_map(Geometry::PolygonZ),
+        multi_point_z(bounds, 0..10).prop_map(Geometry::MultiPointZ),
+        multi_line_string_z(bounds, 0..5).prop_map(Geometry::MultiLineStringZ),
+        multi_polygon_z(bounds, 0..5).prop_map(Geometry::MultiPolygonZ),
+    ]
+}
+
+pub fn geometry_collection(
+    bounds: Cube<f64>,
+    len: Range<usize>,
+) -> impl Strategy<Value = GeometryCollection<f64>> {
+    vec(geometry(bounds), len).prop_map(GeometryCollection)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::xy::polygon_xy;
+    use crate::algorithm::BoundingCube;
+    use geo::Validation;
+
+    fn bounds() -> Cube<f64> {
+        Cube::new((-50., 10., 0.), (150., 60., 5.))
+    }
+
+    proptest! {
+        #[test]
+        fn polygons_are_valid(multi in multi_polygon_z(bounds(), 1..4)) {
+            for polygon in &multi.0 {
+                prop_assert!(polygon.exterior().is_closed());
+                prop_assert!(polygon.interiors().iter().all(LineStringZ::is_closed));
+                prop_assert!(polygon_xy(polygon).is_valid());
+            }
+            let union = multi.bounding_cube().unwrap();
+            prop_assert_eq!(union.union(&bounds()), bounds());
+        }
+
+        #[test]
+        fn geometries_are_finite(collection in geometry_collection(bounds(), 0..5)) {
+            if let Some(cube) = collection.bounding_cube() {
+                prop_assert_eq!(cube.union(&bounds()), bounds());
+            }
+        }
+    }
+}
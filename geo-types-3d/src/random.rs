@@ -0,0 +1,258 @@
+//! Random geometries, for simulations and benchmarks.
+//!
+//! The generators take any [`rand`] random number generator, so a seeded one gives
+//! the same geometries on every run. Coordinates are drawn in their own scalar
+//! type, so `f32` geometries aren't rounded from `f64` ones.
+//!
+//! Enable this module with the `rand` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use geo_types_3d::random::points_in_cube;
+//! use geo_types_3d::Cube;
+//! use rand::rngs::SmallRng;
+//! use rand::SeedableRng;
+//!
+//! let mut rng = SmallRng::seed_from_u64(7);
+//! let bounds: Cube = Cube::new((0., 0., 0.), (100., 100., 10.));
+//! let points = points_in_cube(&mut rng, &bounds, 1000);
+//! assert_eq!(points.0.len(), 1000);
+//! assert!(points.iter().all(|point| bounds.contains_coord(point.0)));
+//! ```
+
+use alloc::vec::Vec;
+
+use rand::distr::uniform::SampleUniform;
+use rand::{Rng, RngExt};
+
+use crate::algorithm::ransac::fit_plane;
+use crate::{
+    CoordFloat, CoordZ, Cube, EllipsoidZ, LineStringZ, MultiPointZ, PointZ, PolygonZ, Sphere,
+};
+
+/// `count` points spread uniformly through `cube`.
+pub fn points_in_cube<T, R>(rng: &mut R, cube: &Cube<T>, count: usize) -> MultiPointZ<T>
+where
+    T: CoordFloat + SampleUniform,
+    R: Rng + ?Sized,
+{
+    let (min, max) = (cube.min(), cube.max());
+    (0..count)
+        .map(|_| {
+            PointZ::new(
+                rng.random_range(min.x..=max.x),
+                rng.random_range(min.y..=max.y),
+                rng.random_range(min.z..=max.z),
+            )
+        })
+        .collect()
+}
+
+/// `count` points spread uniformly over the surface of `sphere`.
+pub fn points_on_sphere<T, R>(rng: &mut R, sphere: &Sphere<T>, count: usize) -> MultiPointZ<T>
+where
+    T: CoordFloat + SampleUniform,
+    R: Rng + ?Sized,
+{
+    (0..count)
+        .map(|_| PointZ(sphere.center + direction(rng) * sphere.radius))
+        .collect()
+}
+
+/// `count` points spread uniformly over the surface of `ellipsoid`.
+///
+/// Points of the unit sphere are stretched onto the ellipsoid, and kept in
+/// proportion to how much the stretching enlarges the surface around them, so that
+/// each part of the surface gets its share of points.
+pub fn points_on_ellipsoid<T, R>(
+    rng: &mut R,
+    ellipsoid: &EllipsoidZ<T>,
+    count: usize,
+) -> MultiPointZ<T>
+where
+    T: CoordFloat + SampleUniform,
+    R: Rng + ?Sized,
+{
+    let a = ellipsoid.semi_axes;
+    let (yz, xz, xy) = (a.y * a.z, a.x * a.z, a.x * a.y);
+    let most = yz.max(xz).max(xy);
+    let mut points = Vec::with_capacity(count);
+    while points.len() < count {
+        let unit: CoordZ<T> = direction(rng);
+        let (x, y, z) = (unit.x * yz, unit.y * xz, unit.z * xy);
+        let stretch = (x * x + y * y + z * z).sqrt();
+        if rng.random_range(T::zero()..=most) <= stretch {
+            points.push(PointZ(
+                ellipsoid.center + CoordZ::from((unit.x * a.x, unit.y * a.y, unit.z * a.z)),
+            ));
+        }
+    }
+    MultiPointZ(points)
+}
+
+/// `count` points spread uniformly over `polygon`, seen from above.
+///
+/// Points are drawn inside the polygon's footprint in XY, avoiding its holes, and
+/// lifted onto the plane best fitting its exterior ring, so that they lie on a
+/// sloping roof or hillside as well as on flat ground. A polygon with no area
+/// seen from above, such as a wall, gets no points.
+pub fn points_in_polygon<T, R>(rng: &mut R, polygon: &PolygonZ<T>, count: usize) -> MultiPointZ<T>
+where
+    T: CoordFloat + SampleUniform,
+    R: Rng + ?Sized,
+{
+    let exterior = &polygon.exterior().0;
+    let plane = (!exterior.is_empty())
+        .then(|| fit_plane(exterior))
+        .flatten();
+    let plane = match plane {
+        Some(plane) if plane.normal.z != T::zero() && area_xy(exterior) != T::zero() => plane,
+        _ => return MultiPointZ::new(Vec::new()),
+    };
+    let (mut min, mut max) = (exterior[0], exterior[0]);
+    for c in exterior {
+        min = CoordZ::from((min.x.min(c.x), min.y.min(c.y), min.z));
+        max = CoordZ::from((max.x.max(c.x), max.y.max(c.y), max.z));
+    }
+
+    // draw from the footprint's bounding rectangle until enough land inside it
+    let mut points = Vec::with_capacity(count);
+    while points.len() < count {
+        let (x, y) = (
+            rng.random_range(min.x..=max.x),
+            rng.random_range(min.y..=max.y),
+        );
+        let inside = contains_xy(polygon.exterior(), x, y)
+            && !polygon
+                .interiors()
+                .iter()
+                .any(|ring| contains_xy(ring, x, y));
+        if inside {
+            let n = plane.normal;
+            let z = (plane.offset - n.x * x - n.y * y) / n.z;
+            points.push(PointZ::new(x, y, z));
+        }
+    }
+    MultiPointZ::new(points)
+}
+
+/// A walk of `steps` steps of `step_length` from `start`, each in a direction
+/// drawn uniformly from all directions.
+pub fn random_walk<T, R>(
+    rng: &mut R,
+    start: PointZ<T>,
+    steps: usize,
+    step_length: T,
+) -> LineStringZ<T>
+where
+    T: CoordFloat + SampleUniform,
+    R: Rng + ?Sized,
+{
+    let mut coords = Vec::with_capacity(steps + 1);
+    let mut at = start.0;
+    coords.push(at);
+    for _ in 0..steps {
+        at += direction(rng) * step_length;
+        coords.push(at);
+    }
+    LineStringZ::new(coords)
+}
+
+/// A unit vector drawn uniformly from all directions, by Archimedes' theorem that
+/// the height of a uniform point on a sphere is uniform too.
+fn direction<T, R>(rng: &mut R) -> CoordZ<T>
+where
+    T: CoordFloat + SampleUniform,
+    R: Rng + ?Sized,
+{
+    let z: T = rng.random_range(-T::one()..=T::one());
+    let angle: T = rng.random_range(T::zero()..T::from(core::f64::consts::TAU).unwrap());
+    let (sin, cos) = angle.sin_cos();
+    let r = (T::one() - z * z).max(T::zero()).sqrt();
+    CoordZ::from((r * cos, r * sin, z))
+}
+
+fn area_xy<T: CoordFloat>(ring: &[CoordZ<T>]) -> T {
+    ring.windows(2).fold(T::zero(), |sum, pair| {
+        sum + pair[0].x * pair[1].y - pair[1].x * pair[0].y
+    })
+}
+
+/// Whether `(x, y)` lies inside the ring seen from above, by counting crossings.
+fn contains_xy<T: CoordFloat>(ring: &LineStringZ<T>, x: T, y: T) -> bool {
+    let mut inside = false;
+    for line in ring.lines() {
+        let (a, b) = (line.start, line.end);
+        if (a.y > y) != (b.y > y) && x < a.x + (y - a.y) / (b.y - a.y) * (b.x - a.x) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::LengthZ;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn generators() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let sphere = Sphere::new(CoordZ::from((1., 2., 3.)), 5f64);
+        let points = points_on_sphere(&mut rng, &sphere, 500);
+        assert!(points
+            .iter()
+            .all(|p| sphere.signed_distance(p.0).abs() < 1e-9));
+        // about as many points above the centre as below it
+        let above = points.iter().filter(|p| p.z() > 3.).count();
+        assert!((200..300).contains(&above));
+
+        // three fifths of the surface of a long thin ellipsoid is in the middle half
+        // of its length, where directions drawn uniformly would put only half
+        let ellipsoid: EllipsoidZ =
+            EllipsoidZ::new(CoordZ::from((0., 0., 0.)), (1., 1., 10.).into());
+        let points = points_on_ellipsoid(&mut rng, &ellipsoid, 500);
+        for p in &points {
+            let (x, y, z) = p.x_y_z();
+            assert!((x * x + y * y + z * z / 100. - 1.).abs() < 1e-9);
+        }
+        let middle = points.iter().filter(|p| p.z().abs() < 5.).count();
+        assert!(middle > 280, "{middle}");
+
+        // a sloping roof with a chimney hole
+        let roof: PolygonZ = polygon_z!(
+            exterior: [
+                (x: 0., y: 0., z: 0.),
+                (x: 10., y: 0., z: 0.),
+                (x: 10., y: 10., z: 5.),
+                (x: 0., y: 10., z: 5.),
+            ],
+            interiors: [[
+                (x: 4., y: 4., z: 2.),
+                (x: 6., y: 4., z: 2.),
+                (x: 6., y: 6., z: 3.),
+                (x: 4., y: 6., z: 3.),
+            ]],
+        );
+        let points = points_in_polygon(&mut rng, &roof, 500);
+        assert_eq!(points.0.len(), 500);
+        for p in &points {
+            assert!((p.z() - p.y() / 2.).abs() < 1e-9);
+            assert!(!(4. ..=6.).contains(&p.x()) || !(4. ..=6.).contains(&p.y()));
+        }
+        let wall: PolygonZ = polygon_z![
+            (x: 0., y: 0., z: 0.),
+            (x: 1., y: 0., z: 0.),
+            (x: 1., y: 0., z: 1.),
+        ];
+        assert!(points_in_polygon(&mut rng, &wall, 10).0.is_empty());
+
+        let start = PointZ::new(0f32, 0., 0.);
+        let walk = random_walk(&mut rng, start, 100, 2.);
+        assert_eq!(walk.0.len(), 101);
+        assert!((walk.length() - 200.).abs() < 1e-3);
+    }
+}
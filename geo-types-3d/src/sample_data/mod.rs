@@ -0,0 +1,134 @@
+//! Small, representative 3D datasets for examples, benchmarks and tests.
+//!
+//! The datasets are synthetic but shaped like real data, and are embedded in the
+//! crate as GeoJSON, so each constructor parses its dataset afresh. Enable them with
+//! the `sample-data` feature.
+//!
+//! | Constructor        | Contents                                                    |
+//! |--------------------|-------------------------------------------------------------|
+//! | [`terrain_tin`]    | a `TinZ` of 200 triangles over a 400 m × 400 m terrain      |
+//! | [`building`]       | the closed shell of a 12 m × 8 m house with a gabled roof   |
+//! | [`gps_track`]      | a 240 point hiking track in lon/lat with elevation          |
+//! | [`lidar_points`]   | 640 points over a 30 m × 30 m tile with a shed and two trees |
+//!
+//! The terrain, building and LiDAR points use projected coordinates in metres; the
+//! GPS track uses WGS84 longitude and latitude in degrees and elevation in metres.
+//!
+//! The crate has no solid type, so the building is the `MultiPolygonZ` of its
+//! boundary faces, the form [`Voxelize`](crate::algorithm::Voxelize) and the other
+//! algorithms on closed shells take.
+//!
+//! # Examples
+//!
+//! ```
+//! use geo_types_3d::sample_data;
+//!
+//! let track = sample_data::gps_track();
+//! assert_eq!(track.0.len(), 240);
+//! assert!(track.0.windows(2).all(|pair| pair[0] != pair[1]));
+//! ```
+
+use alloc::collections::BTreeMap;
+use core::convert::TryFrom;
+
+use geojson::GeoJson;
+
+use crate::{LineStringZ, MultiPointZ, MultiPolygonZ, TinZ};
+
+const TERRAIN_TIN: &str = include_str!("terrain_tin.geojson");
+const BUILDING: &str = include_str!("building.geojson");
+const GPS_TRACK: &str = include_str!("gps_track.geojson");
+const LIDAR_POINTS: &str = include_str!("lidar_points.geojson");
+
+/// A triangulated terrain on a 40 m grid, with heights between about 400 m and
+/// 450 m. Neighbouring triangles share their vertices.
+pub fn terrain_tin() -> TinZ<f64> {
+    let triangles: MultiPolygonZ<f64> = parse(TERRAIN_TIN);
+    let mut vertices = Vec::new();
+    let mut indices = BTreeMap::new();
+    let triangles = triangles
+        .0
+        .iter()
+        .map(|triangle| {
+            let coords = &triangle.exterior().0;
+            core::array::from_fn(|i| {
+                let coord = coords[i];
+                let key = [coord.x, coord.y, coord.z].map(f64::to_bits);
+                *indices.entry(key).or_insert_with(|| {
+                    vertices.push(coord);
+                    vertices.len() - 1
+                })
+            })
+        })
+        .collect();
+    TinZ::new(vertices, triangles)
+}
+
+/// The seven faces of a house: a floor, four walls and two roof slopes. Every face
+/// is wound counter-clockwise when seen from outside, so the shell is closed and
+/// its face normals point outwards.
+pub fn building() -> MultiPolygonZ<f64> {
+    parse(BUILDING)
+}
+
+/// A hiking track, climbing about 250 m.
+pub fn gps_track() -> LineStringZ<f64> {
+    parse(GPS_TRACK)
+}
+
+/// Points of an airborne LiDAR scan: ground, a flat roof and two tree crowns.
+pub fn lidar_points() -> MultiPointZ<f64> {
+    parse(LIDAR_POINTS)
+}
+
+fn parse<G>(json: &str) -> G
+where
+    G: for<'a> TryFrom<&'a geojson::Value, Error = geojson::Error>,
+{
+    match json.parse() {
+        Ok(GeoJson::Geometry(geometry)) => {
+            G::try_from(&geometry.value).expect("sample data has the expected type")
+        }
+        _ => panic!("sample data is a GeoJSON geometry"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::BoundingCube;
+
+    #[test]
+    fn datasets() {
+        let terrain = terrain_tin();
+        assert_eq!(terrain.len(), 200);
+        // an 11 × 11 grid of vertices
+        assert_eq!(terrain.vertices().len(), 121);
+        let bounds = terrain.bounding_cube().unwrap();
+        assert_eq!((bounds.width(), bounds.height()), (400., 400.));
+        assert!(bounds.min().z > 400. && bounds.max().z < 450.);
+
+        let building = building();
+        assert_eq!(building.0.len(), 7);
+        // every edge of a closed shell is shared by exactly two faces, in opposite
+        // directions
+        let edges: Vec<_> = building
+            .0
+            .iter()
+            .flat_map(|face| face.exterior().lines())
+            .collect();
+        for edge in &edges {
+            let reversed = edges
+                .iter()
+                .filter(|other| other.start == edge.end && other.end == edge.start)
+                .count();
+            assert_eq!(reversed, 1);
+        }
+
+        let track = gps_track();
+        let climb = track.0.last().unwrap().z - track.0[0].z;
+        assert!((200. ..300.).contains(&climb));
+
+        assert_eq!(lidar_points().0.len(), 640);
+    }
+}
@@ -0,0 +1,20 @@
+/// The SplitMix64 generator, a small seeded generator for repeatable sampling and
+/// benchmark data.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl SplitMix64 {
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A float drawn uniformly from `[0, 1)`.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        // the top 53 bits, as many as an f64 holds exactly
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
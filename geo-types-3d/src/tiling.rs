@@ -0,0 +1,286 @@
+//! Partitioning of 3D datasets into the XYZ tiles of web maps, such as to export
+//! them as Mapbox Vector Tiles or 3D Tiles.
+//!
+//! Tiles are those of the Web Mercator pyramid used by most web maps: at zoom `z`
+//! there are `2^z` columns from the antimeridian eastwards, and `2^z` rows from
+//! about 85.05° north southwards. Geometries are in longitude (x) and latitude (y)
+//! degrees, and keep their z. [`partition`] clips each geometry to every tile it
+//! touches, with a buffer around the tile so that lines and fills continue past its
+//! edges when rendered.
+//!
+//! Geometries aren't split across the antimeridian: one that crosses it should be
+//! split beforehand.
+//!
+//! # Examples
+//!
+//! ```
+//! use geo_types_3d::tiling::{partition, TileId};
+//! use geo_types_3d::{wkt, Geometry};
+//!
+//! // a power line crossing the prime meridian, at zoom 1
+//! let line: Geometry = wkt! { LINESTRING Z(-10. 40. 30.,10. 40. 50.) }.into();
+//! let tiles = partition([&line], 1, 0.);
+//! assert_eq!(
+//!     tiles.keys().copied().collect::<Vec<_>>(),
+//!     [TileId::new(0, 0, 1), TileId::new(1, 0, 1)]
+//! );
+//! // the western half, at the height the line crosses the meridian
+//! assert_eq!(
+//!     tiles[&TileId::new(0, 0, 1)].0,
+//!     [wkt! { MULTILINESTRING Z((-10. 40. 30.,0. 40. 40.)) }.into()]
+//! );
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use geo_types::{coord, Rect};
+
+use crate::algorithm::{BoundingCube, Clip};
+use crate::{CoordFloat, Cube, Geometry, GeometryCollection};
+
+/// The deepest zoom level, whose tile columns and rows still fit a `u32`.
+pub const MAX_ZOOM: u8 = 31;
+
+/// A tile of the Web Mercator pyramid: column `x` and row `y` at `zoom`.
+///
+/// Tiles order by zoom first, then row and column, so that those of one zoom
+/// level sort together, row by row from the north.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TileId {
+    pub zoom: u8,
+    pub y: u32,
+    pub x: u32,
+}
+
+impl TileId {
+    /// The tile in column `x` and row `y` at `zoom`.
+    ///
+    /// # Panics
+    ///
+    /// If `zoom` is beyond [`MAX_ZOOM`], or the column or row beyond the last at
+    /// that zoom.
+    pub fn new(x: u32, y: u32, zoom: u8) -> Self {
+        assert!(zoom <= MAX_ZOOM, "zoom must be at most {MAX_ZOOM}");
+        let tiles = 1u64 << zoom;
+        assert!(
+            u64::from(x) < tiles && u64::from(y) < tiles,
+            "tile must be within the {tiles} columns and rows at zoom {zoom}"
+        );
+        Self { zoom, y, x }
+    }
+
+    /// The tile containing the point at longitude `lon` and latitude `lat`, in
+    /// degrees. Points beyond the edges of the map are placed in the nearest tile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::tiling::TileId;
+    ///
+    /// // Amsterdam
+    /// assert_eq!(TileId::at(4.9, 52.37, 10), TileId::new(525, 336, 10));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `zoom` is beyond [`MAX_ZOOM`].
+    pub fn at<T: CoordFloat>(lon: T, lat: T, zoom: u8) -> Self {
+        assert!(zoom <= MAX_ZOOM, "zoom must be at most {MAX_ZOOM}");
+        Self {
+            zoom,
+            y: index(row(lat, zoom), zoom),
+            x: index(column(lon, zoom), zoom),
+        }
+    }
+
+    /// The tile one zoom level up containing this one, or `None` at zoom 0.
+    pub fn parent(self) -> Option<Self> {
+        let zoom = self.zoom.checked_sub(1)?;
+        Some(Self {
+            zoom,
+            y: self.y / 2,
+            x: self.x / 2,
+        })
+    }
+
+    /// The four tiles one zoom level down covering this one, row by row.
+    ///
+    /// # Panics
+    ///
+    /// At [`MAX_ZOOM`].
+    pub fn children(self) -> [Self; 4] {
+        let (x, y, zoom) = (self.x * 2, self.y * 2, self.zoom + 1);
+        [
+            Self::new(x, y, zoom),
+            Self::new(x + 1, y, zoom),
+            Self::new(x, y + 1, zoom),
+            Self::new(x + 1, y + 1, zoom),
+        ]
+    }
+
+    /// The longitudes and latitudes covered by this tile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo_types_3d::tiling::TileId;
+    ///
+    /// let bounds = TileId::new(1, 0, 1).bounds::<f64>();
+    /// assert_eq!((bounds.min().x, bounds.max().x), (0., 180.));
+    /// assert_eq!(bounds.min().y, 0.);
+    /// assert!((bounds.max().y - 85.0511).abs() < 1e-4);
+    /// ```
+    pub fn bounds<T: CoordFloat>(self) -> Rect<T> {
+        let bounds = self.buffered(T::zero());
+        Rect::new(
+            coord! { x: bounds.min().x, y: bounds.min().y },
+            coord! { x: bounds.max().x, y: bounds.max().y },
+        )
+    }
+
+    /// The tile grown by `buffer` tile widths on each side, over all heights.
+    fn buffered<T: CoordFloat>(self, buffer: T) -> Cube<T> {
+        let tiles = T::from(1u64 << self.zoom).unwrap();
+        let (x, y) = (T::from(self.x).unwrap(), T::from(self.y).unwrap());
+        let lon = |column: T| column / tiles * T::from(360).unwrap() - T::from(180).unwrap();
+        let lat = |row: T| {
+            let pi = T::from(core::f64::consts::PI).unwrap();
+            (pi * (T::one() - (row + row) / tiles))
+                .sinh()
+                .atan()
+                .to_degrees()
+        };
+        Cube::new(
+            (
+                lon(x - buffer),
+                lat(y + T::one() + buffer),
+                T::neg_infinity(),
+            ),
+            (lon(x + T::one() + buffer), lat(y - buffer), T::infinity()),
+        )
+    }
+}
+
+/// The parts of `geometries` in each tile they touch at `zoom`, grown by `buffer`
+/// tile widths on each side, such as `64. / 4096.` for the usual buffer of vector
+/// tiles.
+///
+/// Each geometry is [clipped](Clip) to the buffered bounds of each tile, so its
+/// parts have the same z as the geometry where it crosses them, and planar members
+/// are lifted to z = 0. Geometries on the edge between tiles, or within the buffer
+/// of a neighbour, go into each. The parts of each tile are in the order of
+/// `geometries`.
+///
+/// # Panics
+///
+/// If `zoom` is beyond [`MAX_ZOOM`], or `buffer` is negative.
+pub fn partition<'a, T: CoordFloat + 'a>(
+    geometries: impl IntoIterator<Item = &'a Geometry<T>>,
+    zoom: u8,
+    buffer: T,
+) -> BTreeMap<TileId, GeometryCollection<T>> {
+    assert!(zoom <= MAX_ZOOM, "zoom must be at most {MAX_ZOOM}");
+    assert!(buffer >= T::zero(), "buffer must not be negative");
+    let mut tiles: BTreeMap<TileId, Vec<Geometry<T>>> = BTreeMap::new();
+    for geometry in geometries {
+        let Some(bounds) = geometry.bounding_cube() else {
+            continue;
+        };
+        let (min, max) = (bounds.min(), bounds.max());
+        let columns =
+            index(column(min.x, zoom) - buffer, zoom)..=index(column(max.x, zoom) + buffer, zoom);
+        let rows = index(row(max.y, zoom) - buffer, zoom)..=index(row(min.y, zoom) + buffer, zoom);
+        for y in rows {
+            for x in columns.clone() {
+                let tile = TileId { zoom, y, x };
+                if let Some(part) = geometry.clip(&tile.buffered(buffer)) {
+                    tiles.entry(tile).or_default().push(part);
+                }
+            }
+        }
+    }
+    tiles
+        .into_iter()
+        .map(|(tile, parts)| (tile, GeometryCollection(parts)))
+        .collect()
+}
+
+/// The fractional column of longitude `lon` at `zoom`.
+fn column<T: CoordFloat>(lon: T, zoom: u8) -> T {
+    let tiles = T::from(1u64 << zoom).unwrap();
+    (lon + T::from(180).unwrap()) / T::from(360).unwrap() * tiles
+}
+
+/// The fractional row of latitude `lat` at `zoom`.
+fn row<T: CoordFloat>(lat: T, zoom: u8) -> T {
+    let tiles = T::from(1u64 << zoom).unwrap();
+    let pi = T::from(core::f64::consts::PI).unwrap();
+    let half = T::from(0.5).unwrap();
+    (half - lat.to_radians().tan().asinh() / (pi + pi)) * tiles
+}
+
+/// The column or row containing a fractional one, within those at `zoom`.
+fn index<T: CoordFloat>(fraction: T, zoom: u8) -> u32 {
+    let last = T::from((1u64 << zoom) - 1).unwrap();
+    fraction
+        .floor()
+        .max(T::zero())
+        .min(last)
+        .to_u32()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{MultiPointZ, PointZ};
+    use alloc::vec;
+
+    #[test]
+    fn pyramid() {
+        let tile = TileId::at(-73.99, 40.73, 12);
+        assert_eq!(tile, TileId::new(1206, 1539, 12));
+        assert_eq!(TileId::at(-180., 90., 3), TileId::new(0, 0, 3));
+        assert_eq!(TileId::at(180., -90., 3), TileId::new(7, 7, 3));
+        assert_eq!(TileId::new(0, 0, 0).parent(), None);
+        for child in tile.children() {
+            assert_eq!(child.parent(), Some(tile));
+            let bounds = child.bounds::<f64>();
+            assert_eq!(TileId::at(bounds.center().x, bounds.center().y, 13), child);
+        }
+    }
+
+    #[test]
+    fn buffers() {
+        // two points either side of the edge between tiles 0 and 1 at zoom 1, one
+        // just within the buffer of the other tile
+        let points =
+            Geometry::MultiPointZ(MultiPointZ::from(vec![(-1., 10., 5.), (-50., 10., 6.)]));
+        let tiles = partition([&points], 1, 0.01);
+        assert_eq!(tiles.len(), 2);
+        assert_eq!(
+            tiles[&TileId::new(0, 0, 1)].0,
+            core::slice::from_ref(&points)
+        );
+        assert_eq!(
+            tiles[&TileId::new(1, 0, 1)].0,
+            [MultiPointZ::from(vec![PointZ::new(-1., 10., 5.)]).into()]
+        );
+
+        // without a buffer, nothing crosses
+        let tiles = partition([&points], 1, 0.);
+        assert_eq!(tiles.keys().collect::<Vec<_>>(), [&TileId::new(0, 0, 1)]);
+
+        // planar geometries are lifted, and empty ones have no tiles
+        let planar = Geometry::Point(geo_types::Point::new(100., -20.));
+        let empty = Geometry::from(GeometryCollection::<f64>::empty());
+        let tiles = partition([&planar, &empty], 2, 0.);
+        assert_eq!(
+            tiles[&TileId::new(3, 2, 2)].0,
+            [PointZ::new(100., -20., 0.).into()]
+        );
+        assert_eq!(tiles.len(), 1);
+    }
+}
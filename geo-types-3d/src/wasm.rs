@@ -0,0 +1,273 @@
+//! JavaScript classes wrapping the geometry types with [wasm-bindgen], so that web
+//! clients can build geometries, read them from GeoJSON and run the 3D algorithms
+//! in the browser.
+//!
+//! `wasm-bindgen` classes can't be generic, so the classes hold `f64` geometries.
+//! Coordinates cross into JavaScript as a `Float64Array` of `x, y, z` triples,
+//! copied once rather than an object per coordinate. The classes convert to and
+//! from the geometry types they wrap, so that a crate building its own WebAssembly
+//! module can add functions taking and returning them.
+//!
+//! From JavaScript:
+//!
+//! ```js
+//! import { Geometry, LineStringZ } from "./pkg/geo_types_3d.js";
+//!
+//! const track = new LineStringZ(new Float64Array([0, 0, 100, 3, 4, 100, 3, 4, 112]));
+//! track.length(); // 17
+//!
+//! const shape = Geometry.fromGeoJson('{"type":"Point","coordinates":[1,2,3]}');
+//! shape.translate(1, 1, 1);
+//! shape.coordinates(); // Float64Array [2, 3, 4]
+//! shape.toGeoJson();
+//! ```
+//!
+//! [wasm-bindgen]: https://rustwasm.github.io/docs/wasm-bindgen/
+
+use std::cell::RefCell;
+use std::convert::TryFrom;
+
+use wasm_bindgen::prelude::*;
+
+use crate::algorithm::{AffineOpsZ, AffineTransform3D, BoundingCube, LengthZ, MapCoordsZ};
+use crate::{CoordZ, Geometry, LineStringZ, PointZ};
+
+fn to_coords(coordinates: &[f64]) -> Result<Vec<CoordZ>, JsError> {
+    if coordinates.len() % 3 != 0 {
+        return Err(JsError::new(
+            "coordinates must be x, y, z triples, so their number a multiple of 3",
+        ));
+    }
+    Ok(coordinates
+        .chunks_exact(3)
+        .map(|xyz| CoordZ::from((xyz[0], xyz[1], xyz[2])))
+        .collect())
+}
+
+fn from_coords(coords: impl IntoIterator<Item = CoordZ>) -> Vec<f64> {
+    coords
+        .into_iter()
+        .flat_map(|coord| [coord.x, coord.y, coord.z])
+        .collect()
+}
+
+/// A [`PointZ`] in JavaScript.
+#[wasm_bindgen(js_name = PointZ)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JsPointZ(PointZ);
+
+#[wasm_bindgen(js_class = PointZ)]
+impl JsPointZ {
+    #[wasm_bindgen(constructor)]
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Self(PointZ::new(x, y, z))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn x(&self) -> f64 {
+        self.0.x()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn y(&self) -> f64 {
+        self.0.y()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn z(&self) -> f64 {
+        self.0.z()
+    }
+
+    /// The straight-line distance in 3D to `other`.
+    pub fn distance(&self, other: &JsPointZ) -> f64 {
+        let offset = (self.0 - other.0).0;
+        (offset.x * offset.x + offset.y * offset.y + offset.z * offset.z).sqrt()
+    }
+}
+
+impl From<PointZ> for JsPointZ {
+    fn from(point: PointZ) -> Self {
+        Self(point)
+    }
+}
+
+impl From<JsPointZ> for PointZ {
+    fn from(point: JsPointZ) -> Self {
+        point.0
+    }
+}
+
+/// A [`LineStringZ`] in JavaScript.
+#[wasm_bindgen(js_name = LineStringZ)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsLineStringZ(LineStringZ);
+
+#[wasm_bindgen(js_class = LineStringZ)]
+impl JsLineStringZ {
+    /// Creates a line string from a flat array of `x, y, z` triples.
+    #[wasm_bindgen(constructor)]
+    pub fn new(coordinates: &[f64]) -> Result<JsLineStringZ, JsError> {
+        Ok(Self(LineStringZ(to_coords(coordinates)?)))
+    }
+
+    /// The coordinates, as a flat array of `x, y, z` triples.
+    pub fn coordinates(&self) -> Vec<f64> {
+        from_coords(self.0 .0.iter().copied())
+    }
+
+    #[wasm_bindgen(getter, js_name = numCoords)]
+    pub fn num_coords(&self) -> usize {
+        self.0 .0.len()
+    }
+
+    /// The length in 3D.
+    pub fn length(&self) -> f64 {
+        self.0.length()
+    }
+}
+
+impl From<LineStringZ> for JsLineStringZ {
+    fn from(line_string: LineStringZ) -> Self {
+        Self(line_string)
+    }
+}
+
+impl From<JsLineStringZ> for LineStringZ {
+    fn from(line_string: JsLineStringZ) -> Self {
+        line_string.0
+    }
+}
+
+/// Any [`Geometry`] in JavaScript, read from and written to GeoJSON.
+#[wasm_bindgen(js_name = Geometry)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsGeometry(Geometry);
+
+#[wasm_bindgen(js_class = Geometry)]
+impl JsGeometry {
+    /// Reads a GeoJSON geometry, feature or feature collection, the latter as a
+    /// geometry collection.
+    #[wasm_bindgen(js_name = fromGeoJson)]
+    pub fn from_geojson(text: &str) -> Result<JsGeometry, JsError> {
+        let geojson: geojson::GeoJson = text.parse()?;
+        Ok(Self(Geometry::try_from(geojson)?))
+    }
+
+    #[wasm_bindgen(js_name = toGeoJson)]
+    pub fn to_geojson(&self) -> String {
+        geojson::Geometry::new(geojson::Value::from(&self.0)).to_string()
+    }
+
+    /// The name of the geometry type, such as `"LineStringZ"`.
+    #[wasm_bindgen(getter, js_name = geometryType)]
+    pub fn geometry_type(&self) -> String {
+        match self.0 {
+            Geometry::Point(_) => "Point",
+            Geometry::PointZ(_) => "PointZ",
+            Geometry::Line(_) => "Line",
+            Geometry::LineZ(_) => "LineZ",
+            Geometry::LineString(_) => "LineString",
+            Geometry::LineStringZ(_) => "LineStringZ",
+            Geometry::Polygon(_) => "Polygon",
+            Geometry::PolygonZ(_) => "PolygonZ",
+            Geometry::MultiPoint(_) => "MultiPoint",
+            Geometry::MultiPointZ(_) => "MultiPointZ",
+            Geometry::MultiLineString(_) => "MultiLineString",
+            Geometry::MultiLineStringZ(_) => "MultiLineStringZ",
+            Geometry::MultiPolygon(_) => "MultiPolygon",
+            Geometry::MultiPolygonZ(_) => "MultiPolygonZ",
+            Geometry::GeometryCollection(_) => "GeometryCollection",
+            Geometry::Rect(_) => "Rect",
+        }
+        .to_string()
+    }
+
+    /// All the coordinates, as a flat array of `x, y, z` triples. Planar members
+    /// have a z of 0.
+    pub fn coordinates(&self) -> Vec<f64> {
+        let coords = RefCell::new(Vec::new());
+        let _: Geometry = self.0.map_coords(|coord| {
+            coords.borrow_mut().push(coord);
+            coord
+        });
+        from_coords(coords.into_inner())
+    }
+
+    /// The bounding cube as `[min x, min y, min z, max x, max y, max z]`, or
+    /// `undefined` for an empty geometry.
+    #[wasm_bindgen(js_name = boundingCube)]
+    pub fn bounding_cube(&self) -> Option<Vec<f64>> {
+        self.0
+            .bounding_cube()
+            .map(|cube| from_coords([cube.min(), cube.max()]))
+    }
+
+    pub fn translate(&mut self, x: f64, y: f64, z: f64) {
+        self.0
+            .affine_transform_mut(&AffineTransform3D::translate(x, y, z));
+    }
+
+    pub fn scale(&mut self, x: f64, y: f64, z: f64) {
+        self.0
+            .affine_transform_mut(&AffineTransform3D::scale(x, y, z));
+    }
+}
+
+impl From<Geometry> for JsGeometry {
+    fn from(geometry: Geometry) -> Self {
+        Self(geometry)
+    }
+}
+
+impl From<JsGeometry> for Geometry {
+    fn from(geometry: JsGeometry) -> Self {
+        geometry.0
+    }
+}
+
+impl From<JsPointZ> for JsGeometry {
+    fn from(point: JsPointZ) -> Self {
+        Self(Geometry::PointZ(point.0))
+    }
+}
+
+impl From<JsLineStringZ> for JsGeometry {
+    fn from(line_string: JsLineStringZ) -> Self {
+        Self(Geometry::LineStringZ(line_string.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Only what runs without JavaScript: errors and typed arrays need a
+    // WebAssembly host.
+    #[test]
+    fn classes() {
+        let track = JsLineStringZ::new(&[0., 0., 100., 3., 4., 100., 3., 4., 112.]).unwrap();
+        assert_eq!(track.length(), 17.);
+        assert_eq!(track.num_coords(), 3);
+
+        let mut shape =
+            JsGeometry::from_geojson(r#"{"type":"Point","coordinates":[1,2,3]}"#).unwrap();
+        assert_eq!(shape.geometry_type(), "PointZ");
+        shape.translate(1., 1., 1.);
+        assert_eq!(shape.coordinates(), [2., 3., 4.]);
+        assert_eq!(
+            shape.to_geojson(),
+            r#"{"type":"Point","coordinates":[2.0,3.0,4.0]}"#
+        );
+
+        let mut track = JsGeometry::from(track);
+        track.scale(2., 2., 1.);
+        assert_eq!(
+            track.bounding_cube(),
+            Some(vec![0., 0., 100., 6., 8., 112.])
+        );
+        assert_eq!(
+            JsPointZ::new(0., 0., 0.).distance(&JsPointZ::new(2., 3., 6.)),
+            7.
+        );
+    }
+}
@@ -44,6 +44,12 @@ macro_rules! wkt_internal {
     (POINT Z $($tail: tt)*) => {
         compile_error!("Invalid POINT wkt");
     };
+    (POINT ZM ($x: literal $y: literal $z: literal $m: literal)) => {
+        $crate::PointZM($crate::coordZM!(x: $x, y: $y, z: $z, m: $m))
+    };
+    (POINT ZM $($tail: tt)*) => {
+        compile_error!("Invalid POINT ZM wkt");
+    };
 
     // LINESTRING
     (LINESTRING Z EMPTY) => {
@@ -55,6 +61,50 @@ macro_rules! wkt_internal {
     (LINESTRING Z $($tail: tt)*) => {
         compile_error!("Invalid LINESTRING Z wkt");
     };
+    (LINESTRING ZM EMPTY) => {
+        $crate::LineStringZM::empty()
+    };
+    (LINESTRING ZM( $( $x: literal $y: literal $z: literal $m: literal ),* $(,)? )) => {
+        $crate::LineStringZM($crate::_alloc::vec![ $( $crate::coordZM!(x: $x, y: $y, z: $z, m: $m) ),* ])
+    };
+    (LINESTRING ZM $($tail: tt)*) => {
+        compile_error!("Invalid LINESTRING ZM wkt");
+    };
+
+    // CIRCULARSTRING
+    (CIRCULARSTRING Z EMPTY) => {
+        $crate::CircularStringZ::empty()
+    };
+    (CIRCULARSTRING Z( $( $x: literal $y: literal $z: literal ),* $(,)? )) => {
+        $crate::CircularStringZ($crate::_alloc::vec![ $( $crate::coordZ!(x: $x, y: $y, z: $z) ),* ])
+    };
+    (CIRCULARSTRING Z $($tail: tt)*) => {
+        compile_error!("Invalid CIRCULARSTRING Z wkt");
+    };
+
+    // COMPOUNDCURVE
+    (COMPOUNDCURVE Z EMPTY) => {
+        $crate::CompoundCurveZ::empty()
+    };
+    (COMPOUNDCURVE Z( $($segments: tt)+ )) => {
+        $crate::CompoundCurveZ($crate::wkt_internal!(@segments [] $($segments)+))
+    };
+    (COMPOUNDCURVE Z $($tail: tt)*) => {
+        compile_error!("Invalid COMPOUNDCURVE Z wkt");
+    };
+    (@segments [$($done: expr),*] CIRCULARSTRING Z $segment: tt $(, $($rest: tt)*)?) => {
+        $crate::wkt_internal!(@segments [
+            $($done,)* $crate::CurveSegmentZ::CircularString($crate::wkt_internal!(CIRCULARSTRING Z $segment))
+        ] $($($rest)*)?)
+    };
+    (@segments [$($done: expr),*] $segment: tt $(, $($rest: tt)*)?) => {
+        $crate::wkt_internal!(@segments [
+            $($done,)* $crate::CurveSegmentZ::LineString($crate::wkt_internal!(LINESTRING Z $segment))
+        ] $($($rest)*)?)
+    };
+    (@segments [$($done: expr),*]) => {
+        $crate::_alloc::vec![$($done),*]
+    };
 
     // POLYGON
     (POLYGON Z EMPTY) => {
@@ -137,7 +187,7 @@ macro_rules! wkt_internal {
         compile_error!("Invalid GEOMETRYCOLLECTION wkt");
     };
     ($name: ident ($($tail: tt)*)) => {
-        compile_error!("Unknown type. Must be one of POINT Z, LINESTRING Z, POLYGON Z, MULTIPOINT Z, MULTILINESTRING Z, MULTIPOLYGON Z, or GEOMETRYCOLLECTION Z");
+        compile_error!("Unknown type. Must be one of POINT Z, POINT ZM, LINESTRING Z, LINESTRING ZM, CIRCULARSTRING Z, COMPOUNDCURVE Z, POLYGON Z, MULTIPOINT Z, MULTILINESTRING Z, MULTIPOLYGON Z, or GEOMETRYCOLLECTION Z");
     };
 }
 